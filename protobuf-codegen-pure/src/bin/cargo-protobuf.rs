@@ -0,0 +1,327 @@
+//! `cargo protobuf` subcommand to generate Rust code from `.proto` files
+//! and keep checked-in generated code in sync with them.
+//!
+//! Configuration is read from `[package.metadata.protobuf]` section of `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.protobuf]
+//! inputs = ["protos/a.proto", "protos/b.proto"]
+//! includes = ["protos"]
+//! out_dir = "src/protos"
+//! # Optional, same syntax as `protoc --rust_opt`
+//! customize = "gen_mod_rs=true serde_derive=true"
+//! # Optional, descriptor set of the previous version for `vet`
+//! baseline = "protos/baseline.pb"
+//! ```
+//!
+//! Paths are relative to the package directory.
+//!
+//! Commands:
+//!
+//! * `generate` writes generated files to `out_dir`
+//! * `check` exits with code 1 if files in `out_dir` differ from generated files
+//! * `vet [--baseline <file.pb>]` exits with code 1 if `.proto` files have
+//!   backward incompatible changes compared to the baseline descriptor set,
+//!   which can be created with `protoc --include_imports -o`
+//!
+//! Package is selected by `--manifest-path` or by `Cargo.toml` in current directory
+//! or its parents. When the manifest is a virtual workspace manifest,
+//! all workspace members with the section are processed.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::process::Command;
+
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::well_known_types::Struct;
+use protobuf::well_known_types::Value;
+use protobuf::Message;
+use protobuf_codegen_pure::Codegen;
+use protobuf_codegen_pure::Customize;
+
+/// Error is printed by `main` which exits with code 2.
+type Result<T> = std::result::Result<T, String>;
+
+/// `[package.metadata.protobuf]` section of a package.
+struct Config {
+    package: String,
+    inputs: Vec<PathBuf>,
+    includes: Vec<PathBuf>,
+    out_dir: PathBuf,
+    customize: Customize,
+    baseline: Option<PathBuf>,
+}
+
+impl Config {
+    fn codegen(&self) -> Codegen {
+        let mut codegen = Codegen::new();
+        codegen
+            .inputs(&self.inputs)
+            .includes(&self.includes)
+            .out_dir(&self.out_dir)
+            .customize(self.customize.clone());
+        codegen
+    }
+}
+
+fn key(name: &str) -> String {
+    format!("`package.metadata.protobuf.{}`", name)
+}
+
+fn field<'a>(s: &'a Struct, name: &str) -> Option<&'a Value> {
+    s.fields.get(name).filter(|v| !v.has_null_value())
+}
+
+fn string_field(s: &Struct, name: &str, package: &str) -> Result<Option<String>> {
+    match field(s, name) {
+        None => Ok(None),
+        Some(v) if v.has_string_value() => Ok(Some(v.get_string_value().to_owned())),
+        Some(_) => Err(format!("{}: {} must be a string", package, key(name))),
+    }
+}
+
+fn paths_field(s: &Struct, name: &str, dir: &Path, package: &str) -> Result<Vec<PathBuf>> {
+    let error = || format!("{}: {} must be a list of strings", package, key(name));
+    match field(s, name) {
+        None => Ok(Vec::new()),
+        Some(v) if v.has_list_value() => v
+            .get_list_value()
+            .values
+            .iter()
+            .map(|v| {
+                if v.has_string_value() {
+                    Ok(dir.join(v.get_string_value()))
+                } else {
+                    Err(error())
+                }
+            })
+            .collect(),
+        Some(_) => Err(error()),
+    }
+}
+
+/// Parse config from package object of `cargo metadata` output,
+/// `None` if the package has no `[package.metadata.protobuf]` section.
+fn parse_config(package: &Struct) -> Result<Option<Config>> {
+    let name = string_field(package, "name", "")?.unwrap_or_default();
+    let manifest_path = string_field(package, "manifest_path", &name)?.unwrap_or_default();
+    let dir = Path::new(&manifest_path).parent().unwrap_or(Path::new("."));
+
+    let section = match field(package, "metadata") {
+        Some(metadata) if metadata.has_struct_value() => {
+            match field(metadata.get_struct_value(), "protobuf") {
+                Some(section) if section.has_struct_value() => section.get_struct_value(),
+                Some(_) => {
+                    return Err(format!(
+                        "{}: `package.metadata.protobuf` must be a table",
+                        name
+                    ))
+                }
+                None => return Ok(None),
+            }
+        }
+        _ => return Ok(None),
+    };
+
+    let inputs = paths_field(section, "inputs", dir, &name)?;
+    if inputs.is_empty() {
+        return Err(format!("{}: {} is empty", name, key("inputs")));
+    }
+    let out_dir = match string_field(section, "out_dir", &name)? {
+        Some(out_dir) => dir.join(out_dir),
+        None => return Err(format!("{}: {} is not specified", name, key("out_dir"))),
+    };
+    let customize = match string_field(section, "customize", &name)? {
+        Some(parameter) => Customize::parse_from_parameter(&parameter)
+            .map_err(|e| format!("{}: {}: {}", name, key("customize"), e))?,
+        None => Customize::default(),
+    };
+    Ok(Some(Config {
+        inputs,
+        includes: paths_field(section, "includes", dir, &name)?,
+        out_dir,
+        customize,
+        baseline: string_field(section, "baseline", &name)?.map(|b| dir.join(b)),
+        package: name,
+    }))
+}
+
+/// Find `Cargo.toml` in current directory or its parents like cargo does.
+fn find_manifest() -> Result<PathBuf> {
+    let cwd = env::current_dir().map_err(|e| format!("cannot get current directory: {}", e))?;
+    cwd.ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| manifest.is_file())
+        .ok_or_else(|| format!("could not find `Cargo.toml` in {}", cwd.display()))
+}
+
+fn read_configs(manifest_path: Option<PathBuf>) -> Result<Vec<Config>> {
+    let manifest_path = match manifest_path {
+        Some(manifest_path) => manifest_path,
+        None => find_manifest()?,
+    };
+    let manifest_path = fs::canonicalize(&manifest_path)
+        .map_err(|e| format!("cannot access {}: {}", manifest_path.display(), e))?;
+
+    let output = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .args(&["metadata", "--no-deps", "--format-version", "1"])
+        .arg("--manifest-path")
+        .arg(&manifest_path)
+        .output()
+        .map_err(|e| format!("failed to run `cargo metadata`: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`cargo metadata` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let metadata: Struct = protobuf::json::parse_from_str(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|e| format!("cannot parse `cargo metadata` output: {}", e))?;
+
+    let packages: Vec<&Struct> = match field(&metadata, "packages") {
+        Some(packages) => packages
+            .get_list_value()
+            .values
+            .iter()
+            .map(|p| p.get_struct_value())
+            .collect(),
+        None => Vec::new(),
+    };
+    let is_selected = |p: &Struct| match field(p, "manifest_path") {
+        Some(path) => fs::canonicalize(path.get_string_value()).ok() == Some(manifest_path.clone()),
+        None => false,
+    };
+    // Virtual manifest selects all workspace members
+    let selected = if packages.iter().any(|p| is_selected(p)) {
+        packages.into_iter().filter(|p| is_selected(p)).collect()
+    } else {
+        packages
+    };
+
+    let mut configs = Vec::new();
+    for package in selected {
+        configs.extend(parse_config(package)?);
+    }
+    if configs.is_empty() {
+        return Err(format!(
+            "no packages with `[package.metadata.protobuf]` section in {}",
+            manifest_path.display()
+        ));
+    }
+    Ok(configs)
+}
+
+fn generate(config: &Config) -> Result<bool> {
+    fs::create_dir_all(&config.out_dir)
+        .and_then(|()| config.codegen().run())
+        .map_err(|e| format!("{}: {}", config.package, e))?;
+    Ok(true)
+}
+
+fn check(config: &Config) -> Result<bool> {
+    let files = config
+        .codegen()
+        .generate()
+        .map_err(|e| format!("{}: {}", config.package, e))?;
+    let mut up_to_date = true;
+    for file in files {
+        let path = config.out_dir.join(&file.name);
+        let problem = match fs::read_to_string(&path) {
+            Ok(content) if content == file.content => continue,
+            Ok(_) => "is out of date",
+            Err(_) => "is missing",
+        };
+        println!("{}: {} {}", config.package, path.display(), problem);
+        up_to_date = false;
+    }
+    Ok(up_to_date)
+}
+
+fn vet(config: &Config, baseline: Option<&Path>) -> Result<bool> {
+    let baseline = match baseline.or(config.baseline.as_deref()) {
+        Some(baseline) => baseline,
+        None => {
+            return Err(format!(
+                "{}: baseline descriptor set is not specified with `--baseline` or {}",
+                config.package,
+                key("baseline")
+            ))
+        }
+    };
+    let old = fs::read(baseline)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| FileDescriptorSet::parse_from_bytes(&bytes).map_err(|e| e.to_string()))
+        .map_err(|e| {
+            format!(
+                "{}: cannot read {}: {}",
+                config.package,
+                baseline.display(),
+                e
+            )
+        })?;
+    let new = protobuf_codegen_pure::parse_and_typecheck(&config.includes, &config.inputs)
+        .map_err(|e| format!("{}: {}", config.package, e))?;
+
+    let changes = protobuf_codegen::breaking::check(&old.file, &new.file_descriptors);
+    for change in &changes {
+        println!("{}: {}", config.package, change);
+    }
+    Ok(changes.is_empty())
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: cargo protobuf <generate|check|vet> \
+         [--manifest-path <Cargo.toml>] [--baseline <file.pb>]"
+    );
+    process::exit(2);
+}
+
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    // Cargo passes subcommand name as the first argument
+    if args.peek().map(String::as_str) == Some("protobuf") {
+        args.next();
+    }
+    let command = match args.next() {
+        Some(command) if ["generate", "check", "vet"].contains(&command.as_str()) => command,
+        _ => usage(),
+    };
+    let mut manifest_path = None;
+    let mut baseline = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--manifest-path" => {
+                manifest_path = Some(PathBuf::from(args.next().unwrap_or_else(|| usage())))
+            }
+            "--baseline" if command == "vet" => {
+                baseline = Some(PathBuf::from(args.next().unwrap_or_else(|| usage())))
+            }
+            _ => usage(),
+        }
+    }
+
+    let result = read_configs(manifest_path).and_then(|configs| {
+        let mut ok = true;
+        for config in &configs {
+            ok &= match command.as_str() {
+                "generate" => generate(config)?,
+                "check" => check(config)?,
+                "vet" => vet(config, baseline.as_deref())?,
+                _ => unreachable!(),
+            };
+        }
+        Ok(ok)
+    });
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    }
+}
@@ -1,10 +1,14 @@
 //! Convert parser model to rust-protobuf model
 
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::iter;
 use std::path::Path;
 
+use crate::diagnostic::did_you_mean;
 use crate::fmt;
 use crate::model;
+use crate::model::Loc;
 
 use protobuf;
 use protobuf::descriptor::field_descriptor_proto;
@@ -22,7 +26,9 @@ use crate::protobuf_codegen::ProtobufAbsolutePath;
 use crate::protobuf_codegen::ProtobufIdent;
 use crate::protobuf_codegen::ProtobufRelativePath;
 use protobuf::descriptor::descriptor_proto::ReservedRange;
+use protobuf::descriptor::enum_descriptor_proto::EnumReservedRange;
 use protobuf::descriptor::field_descriptor_proto::Type;
+use protobuf::reflect::RegisteredTypes;
 use protobuf::reflect::RuntimeTypeBox;
 use protobuf::text_format::lexer::StrLitDecodeError;
 use protobuf::text_format::quote_bytes_to;
@@ -48,6 +54,7 @@ pub enum ConvertError {
     ExpectingEnum(ProtobufAbsolutePath),
     UnknownEnumValue(String),
     UnknownFieldName(String),
+    DuplicateEnumValueNumber(String, String),
 }
 
 impl fmt::Display for ConvertError {
@@ -85,15 +92,23 @@ impl fmt::Display for ConvertError {
             ConvertError::ConstantsOfTypeMessageEnumGroupNotImplemented => {
                 write!(f, "constants of this type are not implemented")
             }
-            ConvertError::NotFoundByAbsPath(p) => write!(f, "object is not found by path: {}", p),
-            // TODO: explain what are r and a
+            ConvertError::NotFoundByAbsPath(p) => write!(f, "type `{}` is not found", p),
+            ConvertError::NotFoundByRelPath(r, a) if a.is_empty() => {
+                write!(f, "type `{}` is not found", r)
+            }
             ConvertError::NotFoundByRelPath(r, a) => {
-                write!(f, "object is not found by path: {} {}", r, a)
+                write!(f, "type `{}` is not found in scope `{}`", r, a)
             }
             ConvertError::ExpectingMessage(p) => write!(f, "expecting a message for name {}", p),
             ConvertError::ExpectingEnum(p) => write!(f, "expecting an enum for name {}", p),
             ConvertError::UnknownEnumValue(v) => write!(f, "unknown enum value: {}", v),
             ConvertError::UnknownFieldName(n) => write!(f, "unknown field name: {}", n),
+            ConvertError::DuplicateEnumValueNumber(e, v) => write!(
+                f,
+                "enum {} value {} reuses number of previous value, \
+                 set `option allow_alias = true` to allow aliases",
+                e, v
+            ),
         }
     }
 }
@@ -106,6 +121,22 @@ impl From<StrLitDecodeError> for ConvertError {
 
 pub type ConvertResult<T> = Result<T, ConvertError>;
 
+/// Conversion error with location of the element it was found in.
+#[derive(Debug)]
+pub struct ConvertErrorWithLocation {
+    /// The error
+    pub error: ConvertError,
+    /// Location of the field, option or other element
+    pub loc: Loc,
+    /// "Did you mean" hint
+    pub help: Option<String>,
+}
+
+const SCALAR_TYPE_NAMES: &[&str] = &[
+    "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32", "sfixed32", "fixed64",
+    "sfixed64", "bool", "string", "bytes", "float", "double",
+];
+
 trait ProtobufOptions {
     fn by_name(&self, name: &str) -> Option<&model::ProtobufConstant>;
 
@@ -132,7 +163,7 @@ trait ProtobufOptions {
 impl<'a> ProtobufOptions for &'a [model::ProtobufOption] {
     fn by_name(&self, name: &str) -> Option<&model::ProtobufConstant> {
         let option_name = ProtobufOptionName::simple(name);
-        for model::ProtobufOption { name, value } in *self {
+        for model::ProtobufOption { name, value, .. } in *self {
             if name == &option_name {
                 return Some(&value);
             }
@@ -188,7 +219,7 @@ impl<'a> LookupScope<'a> {
             .map(|m| &m.t)
     }
 
-    fn enums(&self) -> &'a [model::Enumeration] {
+    fn enums(&self) -> &'a [model::WithLoc<model::Enumeration>] {
         match self {
             &LookupScope::File(file) => &file.enums,
             &LookupScope::Message(messasge, _) => &messasge.enums,
@@ -197,11 +228,12 @@ impl<'a> LookupScope<'a> {
 
     fn members(&self) -> Vec<(ProtobufIdent, MessageOrEnum<'a>)> {
         let mut r = Vec::new();
-        r.extend(
-            self.enums()
-                .into_iter()
-                .map(|e| (ProtobufIdent::from(&e.name[..]), MessageOrEnum::Enum(e))),
-        );
+        r.extend(self.enums().into_iter().map(|e| {
+            (
+                ProtobufIdent::from(&e.t.name[..]),
+                MessageOrEnum::Enum(&e.t),
+            )
+        }));
         r.extend(self.messages().into_iter().map(|m| {
             (
                 ProtobufIdent::from(&m.t.name[..]),
@@ -211,6 +243,20 @@ impl<'a> LookupScope<'a> {
         r
     }
 
+    fn add_names_including_nested(&self, r: &mut Vec<ProtobufAbsolutePath>) {
+        let current_path = self.current_path();
+        for (name, _) in self.members() {
+            let mut path = current_path.clone();
+            path.push_simple(name);
+            r.push(path);
+        }
+        for m in self.messages() {
+            let mut path = current_path.clone();
+            path.push_simple(ProtobufIdent::from(&m.t.name[..]));
+            LookupScope::Message(&m.t, path).add_names_including_nested(r);
+        }
+    }
+
     fn find_member(&self, simple_name: &ProtobufIdent) -> Option<MessageOrEnum<'a>> {
         self.members()
             .into_iter()
@@ -314,9 +360,107 @@ impl TypeResolved {
 struct Resolver<'a> {
     current_file: &'a model::FileDescriptor,
     deps: &'a [model::FileDescriptor],
+    errors: RefCell<Vec<ConvertErrorWithLocation>>,
 }
 
 impl<'a> Resolver<'a> {
+    /// Record the error, so conversion continues with the next element
+    /// and all errors of the file are reported.
+    fn error(&self, loc: Loc, error: ConvertError) {
+        let help = self
+            .suggestion(&error)
+            .map(|s| format!("did you mean `{}`?", s));
+        self.errors
+            .borrow_mut()
+            .push(ConvertErrorWithLocation { error, loc, help });
+    }
+
+    fn check<T>(&self, loc: Loc, result: ConvertResult<T>) -> Option<T> {
+        match result {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.error(loc, e);
+                None
+            }
+        }
+    }
+
+    fn suggestion(&self, error: &ConvertError) -> Option<String> {
+        match error {
+            ConvertError::NotFoundByAbsPath(path) => did_you_mean(
+                &path.path,
+                self.all_type_names().into_iter().map(|p| p.path),
+            ),
+            ConvertError::NotFoundByRelPath(name, scope) => {
+                did_you_mean(name.get(), self.type_names_in_scope(scope))
+            }
+            ConvertError::BuiltinOptionNotFound(options, option) => {
+                did_you_mean(option, self.builtin_option_names(options))
+                    .or_else(|| self.extension_option_name(options, option))
+            }
+            _ => None,
+        }
+    }
+
+    fn all_type_names(&self) -> Vec<ProtobufAbsolutePath> {
+        let mut r = Vec::new();
+        for file in self.all_files() {
+            LookupScope::File(file).add_names_including_nested(&mut r);
+        }
+        r
+    }
+
+    /// Names by which types could be referenced in the scope.
+    fn type_names_in_scope(&self, scope: &ProtobufAbsolutePath) -> Vec<String> {
+        let all = self.all_type_names();
+        let mut r: Vec<String> = SCALAR_TYPE_NAMES.iter().map(|&n| n.to_owned()).collect();
+        for parent in scope.self_and_parents() {
+            for path in &all {
+                match path.remove_prefix(&parent) {
+                    Some(relative) if !relative.is_empty() => r.push(relative.get().to_owned()),
+                    _ => {}
+                }
+            }
+        }
+        r
+    }
+
+    fn builtin_option_names(&self, options: &str) -> Vec<String> {
+        let types = RegisteredTypes::of_file(&protobuf::descriptor::file_descriptor());
+        let mut r: Vec<String> = match types.message_by_name(options) {
+            Some(m) => m.fields().map(|f| f.get_name().to_owned()).collect(),
+            None => Vec::new(),
+        };
+        if options == "google.protobuf.FieldOptions" {
+            r.push("default".to_owned());
+            r.push("json_name".to_owned());
+        }
+        r
+    }
+
+    /// Custom option with the name similar to builtin option,
+    /// when parentheses are forgotten.
+    fn extension_option_name(&self, options: &str, option: &str) -> Option<String> {
+        let mut names = Vec::new();
+        for file in self.all_files() {
+            for ext in &file.extensions {
+                if ext.t.extendee.trim_start_matches('.') == options {
+                    let name = ext.t.field.t.name.as_str();
+                    let full_name = match &file.package {
+                        Some(package) => format!("({}.{})", package, name),
+                        None => format!("({})", name),
+                    };
+                    names.push((name, full_name));
+                }
+            }
+        }
+        let name = did_you_mean(option, names.iter().map(|(name, _)| *name))?;
+        names
+            .into_iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, full_name)| full_name)
+    }
+
     fn map_entry_name_for_field_name(field_name: &str) -> ProtobufIdent {
         // Field name and message name must match, otherwise
         // Google's validation fails.
@@ -383,7 +527,8 @@ impl<'a> Resolver<'a> {
         output.set_name(name.to_owned());
 
         for f in fields {
-            output.field.push(self.field(f, None, path_in_file)?);
+            let field = self.field(f, None, path_in_file);
+            output.field.extend(self.check(f.loc, field));
         }
 
         Ok(output)
@@ -410,22 +555,26 @@ impl<'a> Resolver<'a> {
         let mut nested_messages = Vec::new();
 
         for m in &input.messages {
-            let message = self.message(&m.t, &nested_path_in_file)?;
-            nested_messages.push(model::WithLoc {
-                t: message,
-                loc: m.loc,
-            });
+            let message = self.message(&m.t, &nested_path_in_file);
+            if let Some(message) = self.check(m.loc, message) {
+                nested_messages.push(model::WithLoc {
+                    t: message,
+                    loc: m.loc,
+                });
+            }
         }
 
         for f in input.regular_fields_including_in_oneofs() {
             match &f.t.typ {
                 model::FieldType::Map(t) => {
                     let message =
-                        self.map_entry_message(&f.t.name, &t.0, &t.1, &nested_path_in_file)?;
-                    nested_messages.push(model::WithLoc {
-                        t: message,
-                        loc: f.loc,
-                    });
+                        self.map_entry_message(&f.t.name, &t.0, &t.1, &nested_path_in_file);
+                    if let Some(message) = self.check(f.loc, message) {
+                        nested_messages.push(model::WithLoc {
+                            t: message,
+                            loc: f.loc,
+                        });
+                    }
                 }
                 model::FieldType::Group(model::Group {
                     name: group_name,
@@ -449,11 +598,10 @@ impl<'a> Resolver<'a> {
             .map(|model::WithLoc { t, .. }| t)
             .collect();
 
-        output.enum_type = input
-            .enums
-            .iter()
-            .map(|e| self.enumeration(e, path_in_file))
-            .collect::<Result<_, _>>()?;
+        for e in &input.enums {
+            let enumeration = self.enumeration(&e.t, path_in_file);
+            output.enum_type.extend(self.check(e.loc, enumeration));
+        }
 
         {
             let mut fields = Vec::new();
@@ -461,16 +609,15 @@ impl<'a> Resolver<'a> {
             for fo in &input.fields {
                 match &fo.t {
                     model::FieldOrOneOf::Field(f) => {
-                        fields.push(self.field(f, None, &nested_path_in_file)?);
+                        let field = self.field(f, None, &nested_path_in_file);
+                        fields.extend(self.check(f.loc, field));
                     }
                     model::FieldOrOneOf::OneOf(o) => {
                         let oneof_index = output.oneof_decl.len();
                         for f in &o.fields {
-                            fields.push(self.field(
-                                f,
-                                Some(oneof_index as i32),
-                                &nested_path_in_file,
-                            )?);
+                            let field =
+                                self.field(f, Some(oneof_index as i32), &nested_path_in_file);
+                            fields.extend(self.check(f.loc, field));
                         }
                         output.oneof_decl.push(self.oneof(o, path_in_file)?);
                     }
@@ -489,13 +636,8 @@ impl<'a> Resolver<'a> {
             output.extension_range.push(extension_range);
         }
         for ext in &input.extensions {
-            let mut extension = self.field(&ext.t.field, None, path_in_file)?;
-            extension.set_extendee(
-                self.resolve_message_or_enum_leg(&ext.t.extendee, path_in_file)?
-                    .0
-                    .path,
-            );
-            output.extension.push(extension);
+            let extension = self.message_extension(&ext.t, path_in_file);
+            output.extension.extend(self.check(ext.loc, extension));
         }
 
         for reserved in &input.reserved_nums {
@@ -509,6 +651,20 @@ impl<'a> Resolver<'a> {
         Ok(output)
     }
 
+    fn message_extension(
+        &self,
+        input: &model::Extension,
+        path_in_file: &ProtobufRelativePath,
+    ) -> ConvertResult<protobuf::descriptor::FieldDescriptorProto> {
+        let mut extension = self.field(&input.field, None, path_in_file)?;
+        extension.set_extendee(
+            self.resolve_message_or_enum_leg(&input.extendee, path_in_file)?
+                .0
+                .path,
+        );
+        Ok(extension)
+    }
+
     fn service_options(
         &self,
         input: &[model::ProtobufOption],
@@ -523,12 +679,27 @@ impl<'a> Resolver<'a> {
         self.custom_options(input, &ProtobufRelativePath::empty())
     }
 
+    fn service_method_type(&self, name: &str) -> ConvertResult<String> {
+        match self.resolve_message_or_enum_leg(name, &ProtobufRelativePath::empty())? {
+            (path, MessageOrEnum::Message(..)) => Ok(path.path),
+            (path, MessageOrEnum::Enum(..)) => Err(ConvertError::ExpectingMessage(path)),
+        }
+    }
+
     fn service_method(
         &self,
         input: &model::Method,
     ) -> ConvertResult<protobuf::descriptor::MethodDescriptorProto> {
         let mut output = protobuf::descriptor::MethodDescriptorProto::new();
         output.set_name(input.name.clone());
+        output.set_input_type(self.service_method_type(&input.input_type)?);
+        output.set_output_type(self.service_method_type(&input.output_type)?);
+        if input.client_streaming {
+            output.set_client_streaming(true);
+        }
+        if input.server_streaming {
+            output.set_server_streaming(true);
+        }
         output.options = Some(self.service_method_options(&input.options)?).into();
         Ok(output)
     }
@@ -541,11 +712,10 @@ impl<'a> Resolver<'a> {
         output.set_name(input.name.clone());
         output.options = Some(self.service_options(&input.options)?).into();
 
-        output.method = input
-            .methods
-            .iter()
-            .map(|m| self.service_method(m))
-            .collect::<Result<_, _>>()?;
+        for m in &input.methods {
+            let method = self.service_method(&m.t);
+            output.method.extend(self.check(m.loc, method));
+        }
 
         Ok(output)
     }
@@ -664,7 +834,8 @@ impl<'a> Resolver<'a> {
         let mut options = M::new();
 
         for option in input {
-            self.custom_option(path_in_file, &mut options, option)?;
+            let result = self.custom_option(path_in_file, &mut options, option);
+            self.check(option.loc, result);
         }
         Ok(options)
     }
@@ -692,6 +863,9 @@ impl<'a> Resolver<'a> {
             output.set_label(label(input.t.rule));
         }
 
+        // Options are checked first to report their errors along with type errors
+        output.options = Some(self.field_options(&input.t.options, path_in_file)?).into();
+
         let t = self.field_type_leg(&input.t.name, &input.t.typ, path_in_file)?;
         output.set_field_type(t.type_enum());
         if let Some(t_name) = t.type_name() {
@@ -722,8 +896,6 @@ impl<'a> Resolver<'a> {
             output.set_default_value(default);
         }
 
-        output.options = Some(self.field_options(&input.t.options, path_in_file)?).into();
-
         if let Some(oneof_index) = oneof_index {
             output.set_oneof_index(oneof_index);
         }
@@ -936,12 +1108,33 @@ impl<'a> Resolver<'a> {
     ) -> ConvertResult<protobuf::descriptor::EnumDescriptorProto> {
         let mut output = protobuf::descriptor::EnumDescriptorProto::new();
         output.set_name(input.name.clone());
-        output.value = input
-            .values
-            .iter()
-            .map(|v| self.enum_value(&v, path_in_file))
-            .collect::<Result<_, _>>()?;
+        for v in &input.values {
+            let value = self.enum_value(&v.t, path_in_file);
+            output.value.extend(self.check(v.loc, value));
+        }
         output.options = Some(self.enum_options(&input.options, path_in_file)?).into();
+        for reserved in &input.reserved_nums {
+            let mut reserved_range = EnumReservedRange::new();
+            reserved_range.set_start(reserved.from);
+            // Enum reserved range end is inclusive
+            reserved_range.set_end(reserved.to);
+            output.reserved_range.push(reserved_range);
+        }
+        output.reserved_name = input.reserved_names.clone().into();
+        if !output.options.get_or_default().get_allow_alias() {
+            let mut numbers = HashSet::new();
+            for value in &input.values {
+                if !numbers.insert(value.t.number) {
+                    self.error(
+                        value.loc,
+                        ConvertError::DuplicateEnumValueNumber(
+                            input.name.clone(),
+                            value.t.name.clone(),
+                        ),
+                    );
+                }
+            }
+        }
         Ok(output)
     }
 
@@ -1085,7 +1278,12 @@ impl<'a> Resolver<'a> {
             model::ProtobufConstant::Ident(ident) => match &field_type {
                 TypeResolved::Enum(e) => {
                     let e = self.find_enum_by_abs_name(e)?;
-                    let n = match e.values.iter().find(|v| v.name == *ident).map(|v| v.number) {
+                    let n = match e
+                        .values
+                        .iter()
+                        .find(|v| v.t.name == *ident)
+                        .map(|v| v.t.number)
+                    {
                         Some(n) => n,
                         None => return Err(ConvertError::UnknownEnumValue(ident.clone())),
                     };
@@ -1186,14 +1384,16 @@ fn label(input: model::Rule) -> protobuf::descriptor::field_descriptor_proto::La
     }
 }
 
+/// Convert parsed file, all errors found in the file are returned sorted by location.
 pub fn file_descriptor(
     name: &Path,
     input: &model::FileDescriptor,
     deps: &[model::FileDescriptor],
-) -> ConvertResult<protobuf::descriptor::FileDescriptorProto> {
+) -> Result<protobuf::descriptor::FileDescriptorProto, Vec<ConvertErrorWithLocation>> {
     let resolver = Resolver {
         current_file: &input,
         deps,
+        errors: RefCell::new(Vec::new()),
     };
 
     let mut output = protobuf::descriptor::FileDescriptorProto::new();
@@ -1205,14 +1405,14 @@ pub fn file_descriptor(
     }
 
     for import in &input.imports {
-        if import.vis == model::ImportVis::Public {
+        if import.t.vis == model::ImportVis::Public {
             output
                 .public_dependency
                 .push(output.dependency.len() as i32);
-        } else if import.vis == model::ImportVis::Weak {
+        } else if import.t.vis == model::ImportVis::Weak {
             output.weak_dependency.push(output.dependency.len() as i32);
         }
-        output.dependency.push(import.path.clone());
+        output.dependency.push(import.t.path.clone());
     }
 
     let mut messages = Vec::new();
@@ -1220,26 +1420,32 @@ pub fn file_descriptor(
 
     let mut extensions = Vec::new();
     for e in &input.extensions {
-        let (ext, group_messages) = resolver.extension(&e.t)?;
-        extensions.push(ext);
-        messages.extend(group_messages.map(model::WithLoc::with_loc(e.loc)));
+        let extension = resolver.extension(&e.t);
+        if let Some((ext, group_messages)) = resolver.check(e.loc, extension) {
+            extensions.push(ext);
+            messages.extend(group_messages.map(model::WithLoc::with_loc(e.loc)));
+        }
     }
     output.extension = extensions;
 
     for m in &input.messages {
-        let message = resolver.message(&m.t, &ProtobufRelativePath::empty())?;
-        messages.push(model::WithLoc {
-            t: message,
-            loc: m.loc,
-        });
+        let message = resolver.message(&m.t, &ProtobufRelativePath::empty());
+        if let Some(message) = resolver.check(m.loc, message) {
+            messages.push(model::WithLoc {
+                t: message,
+                loc: m.loc,
+            });
+        }
     }
 
     for s in &input.services {
-        let service = resolver.service(&s.t)?;
-        services.push(model::WithLoc {
-            t: service,
-            loc: s.loc,
-        })
+        let service = resolver.service(&s.t);
+        if let Some(service) = resolver.check(s.loc, service) {
+            services.push(model::WithLoc {
+                t: service,
+                loc: s.loc,
+            })
+        }
     }
 
     // Preserve declaration order
@@ -1249,19 +1455,25 @@ pub fn file_descriptor(
         .map(|model::WithLoc { t, .. }| t)
         .collect();
 
-    output.enum_type = input
-        .enums
-        .iter()
-        .map(|e| resolver.enumeration(e, &ProtobufRelativePath::empty()))
-        .collect::<Result<_, _>>()?;
+    for e in &input.enums {
+        let enumeration = resolver.enumeration(&e.t, &ProtobufRelativePath::empty());
+        output.enum_type.extend(resolver.check(e.loc, enumeration));
+    }
 
     output.service = services
         .into_iter()
         .map(|model::WithLoc { t, .. }| t)
         .collect();
 
-    output.options =
-        Some(resolver.file_options(&input.options, &ProtobufRelativePath::empty())?).into();
+    let options = resolver.file_options(&input.options, &ProtobufRelativePath::empty());
+    // Errors of options are recorded by location, so this always succeeds
+    output.options = options.ok().into();
+
+    let mut errors = resolver.errors.into_inner();
+    if !errors.is_empty() {
+        errors.sort_by_key(|e| e.loc);
+        return Err(errors);
+    }
 
     Ok(output)
 }
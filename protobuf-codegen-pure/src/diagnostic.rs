@@ -0,0 +1,213 @@
+//! Rendering of errors in `.proto` files with source snippets and hints.
+
+use std::cmp;
+use std::fmt;
+
+use crate::model::Loc;
+
+/// Edit distance where insertion, deletion, substitution
+/// and transposition of adjacent chars cost one.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // d[i][j] is the distance between a[..i] and b[..j]
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len() {
+        d[i][0] = i;
+    }
+    for j in 0..=b.len() {
+        d[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = cmp::min(
+                d[i - 1][j - 1] + cost,
+                cmp::min(d[i - 1][j], d[i][j - 1]) + 1,
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = cmp::min(d[i][j], d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Candidate closest to `name` if it is close enough to be a likely typo.
+///
+/// Case is ignored, so `String` suggests `string`.
+pub(crate) fn did_you_mean<I>(name: &str, candidates: I) -> Option<I::Item>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let name = name.to_lowercase();
+    let max_distance = cmp::max(1, name.chars().count() / 3);
+    candidates
+        .into_iter()
+        .map(|c| (edit_distance(&name, &c.as_ref().to_lowercase()), c))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
+/// Write error like:
+///
+/// ```text
+/// error: type `Fo` is not found in scope `.pkg.Bar`
+///  --> pkg.proto:5:5
+///   |
+/// 5 |     Fo fo = 1;
+///   |     ^
+///   = help: did you mean `Foo`?
+/// ```
+pub(crate) fn write_error(
+    f: &mut fmt::Formatter,
+    file: &str,
+    source: &str,
+    loc: Loc,
+    message: &dyn fmt::Display,
+    help: Option<&str>,
+) -> fmt::Result {
+    writeln!(f, "error: {}", message)?;
+    let line_number = format!("{}", loc.line);
+    let margin = " ".repeat(line_number.len());
+    write!(f, "{}--> {}:{}", margin, file, loc)?;
+    if let Some(line) = source.lines().nth(loc.line as usize - 1) {
+        // Keep tabs so the caret is aligned with the line above
+        let indent: String = line
+            .chars()
+            .take(loc.col as usize - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(f, "\n{} |", margin)?;
+        write!(f, "\n{} | {}", line_number, line.trim_end())?;
+        write!(f, "\n{} | {}^", margin, indent)?;
+    }
+    if let Some(help) = help {
+        write!(f, "\n{} = help: {}", margin, help)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+    use std::path::Path;
+
+    use super::*;
+    use crate::linked_hash_map::LinkedHashMap;
+    use crate::Run;
+
+    fn errors(content: &str) -> String {
+        let mut run = Run {
+            parsed_files: LinkedHashMap::new(),
+            includes: &[],
+        };
+        let e: io::Error = run
+            .add_file_content(Path::new("test.proto"), Path::new("test.proto"), content)
+            .unwrap_err();
+        format!("{}", e)
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(0, edit_distance("", ""));
+        assert_eq!(3, edit_distance("abc", ""));
+        assert_eq!(1, edit_distance("int23", "int32"));
+        assert_eq!(2, edit_distance("int23", "int64"));
+        assert_eq!(1, edit_distance("Mesage", "Message"));
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        let candidates = &["Message", "Other", "string"];
+        assert_eq!(Some(&"Message"), did_you_mean("Mesage", candidates));
+        assert_eq!(Some(&"string"), did_you_mean("String", candidates));
+        assert_eq!(None, did_you_mean("Unrelated", candidates));
+    }
+
+    #[test]
+    fn test_parser_error() {
+        assert_eq!(
+            "\
+error: unknown statement `mesage`
+ --> test.proto:2:1
+  |
+2 | mesage Foo {}
+  | ^
+  = help: did you mean `message`?",
+            errors("syntax = \"proto3\";\nmesage Foo {}\n")
+        );
+    }
+
+    #[test]
+    fn test_all_errors_reported() {
+        let content = "\
+syntax = \"proto2\";
+package pkg;
+message Foo {
+    optional Fo fo = 1;
+    optional int23 bar = 2 [deprecatd = true];
+    optional Foo ok = 3 [lazy = true];
+}
+";
+        assert_eq!(
+            "\
+error: type `Fo` is not found in scope `.pkg.Foo`
+ --> test.proto:4:5
+  |
+4 |     optional Fo fo = 1;
+  |     ^
+  = help: did you mean `Foo`?
+
+error: type `int23` is not found in scope `.pkg.Foo`
+ --> test.proto:5:5
+  |
+5 |     optional int23 bar = 2 [deprecatd = true];
+  |     ^
+  = help: did you mean `int32`?
+
+error: builtin option deprecatd not found for options google.protobuf.FieldOptions
+ --> test.proto:5:29
+  |
+5 |     optional int23 bar = 2 [deprecatd = true];
+  |                             ^
+  = help: did you mean `deprecated`?
+
+3 errors in test.proto",
+            errors(content)
+        );
+    }
+
+    #[test]
+    fn test_misspelled_option() {
+        let content = "\
+syntax = \"proto3\";
+import \"rustproto.proto\";
+option optimize_fo = SPEED;
+message Foo {
+    string s = 1 [expose_fields_field = true];
+}
+";
+        assert_eq!(
+            "\
+error: builtin option optimize_fo not found for options google.protobuf.FileOptions
+ --> test.proto:3:8
+  |
+3 | option optimize_fo = SPEED;
+  |        ^
+  = help: did you mean `optimize_for`?
+
+error: builtin option expose_fields_field not found for options google.protobuf.FieldOptions
+ --> test.proto:5:19
+  |
+5 |     string s = 1 [expose_fields_field = true];
+  |                   ^
+  = help: did you mean `(rustproto.expose_fields_field)`?
+
+2 errors in test.proto",
+            errors(content)
+        );
+    }
+}
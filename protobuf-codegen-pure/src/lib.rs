@@ -19,6 +19,13 @@
 //! `protobuf` dependency.
 //!
 //! The alternative is to use `protoc-rust` crate.
+//!
+//! Well-known types (`google/protobuf/*.proto`), `descriptor.proto`, `compiler/plugin.proto`
+//! and `rustproto.proto` are bundled with the crate, so they can be imported
+//! without adding them to include path.
+//!
+//! Syntax tree of parsed `.proto` files with locations and comments
+//! is available in [`model`] module.
 
 #![deny(missing_docs)]
 #![deny(broken_intra_doc_links)]
@@ -27,6 +34,7 @@ extern crate protobuf;
 extern crate protobuf_codegen;
 
 mod convert;
+mod diagnostic;
 
 use std::error::Error;
 use std::fmt;
@@ -38,13 +46,14 @@ use std::path::StripPrefixError;
 use std::process;
 
 mod linked_hash_map;
-mod model;
+pub mod model;
 mod parser;
 mod path;
 
 use linked_hash_map::LinkedHashMap;
 use protobuf_codegen::amend_io_error;
 pub use protobuf_codegen::Customize;
+pub use protobuf_codegen::GeneratedFile;
 
 #[cfg(test)]
 mod test_against_protobuf_protos;
@@ -116,13 +125,34 @@ impl Codegen {
 
         protobuf_codegen::gen_and_write(
             &p.file_descriptors,
-            &format!("protobuf-codegen-pure={}", env!("CARGO_PKG_VERSION")),
+            &parser_name(),
             &p.relative_paths,
             &self.out_dir,
             &self.customize,
         )
     }
 
+    /// Generate files in memory instead of writing them to output directory.
+    ///
+    /// Content is identical to files written by [`run`](Codegen::run),
+    /// so it can be used to check that generated files are up to date.
+    pub fn generate(&self) -> io::Result<Vec<GeneratedFile>> {
+        let p = parse_and_typecheck(&self.includes, &self.inputs)?;
+
+        Ok(protobuf_codegen::gen(
+            &p.file_descriptors,
+            &parser_name(),
+            &p.relative_paths,
+            &self.customize,
+        )
+        .into_iter()
+        .map(|r| GeneratedFile {
+            name: r.name,
+            content: String::from_utf8(r.content).expect("generated code is UTF-8"),
+        })
+        .collect())
+    }
+
     /// Similar to `run`, but prints the message to stderr and exits the process on error.
     pub fn run_from_script(&self) {
         if let Err(e) = self.run() {
@@ -132,6 +162,10 @@ impl Codegen {
     }
 }
 
+fn parser_name() -> String {
+    format!("protobuf-codegen-pure={}", env!("CARGO_PKG_VERSION"))
+}
+
 #[derive(Clone)]
 struct FileDescriptorPair {
     parsed: model::FileDescriptor,
@@ -141,16 +175,7 @@ struct FileDescriptorPair {
 #[derive(Debug)]
 enum CodegenError {
     ParserErrorWithLocation(parser::ParserErrorWithLocation),
-    ConvertError(convert::ConvertError),
-}
-
-impl fmt::Display for CodegenError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            CodegenError::ParserErrorWithLocation(e) => write!(f, "{}", e),
-            CodegenError::ConvertError(e) => write!(f, "{}", e),
-        }
-    }
+    ConvertErrors(Vec<convert::ConvertErrorWithLocation>),
 }
 
 impl From<parser::ParserErrorWithLocation> for CodegenError {
@@ -159,21 +184,58 @@ impl From<parser::ParserErrorWithLocation> for CodegenError {
     }
 }
 
-impl From<convert::ConvertError> for CodegenError {
-    fn from(e: convert::ConvertError) -> Self {
-        CodegenError::ConvertError(e)
+impl From<Vec<convert::ConvertErrorWithLocation>> for CodegenError {
+    fn from(e: Vec<convert::ConvertErrorWithLocation>) -> Self {
+        CodegenError::ConvertErrors(e)
     }
 }
 
 #[derive(Debug)]
 struct WithFileError {
     file: String,
+    /// File content to show source snippets
+    content: String,
     error: CodegenError,
 }
 
 impl fmt::Display for WithFileError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "error in {}: {}", self.file, self.error)
+        match &self.error {
+            CodegenError::ParserErrorWithLocation(e) => {
+                let loc = model::Loc {
+                    line: e.line,
+                    col: e.col,
+                };
+                let help = e.error.help();
+                diagnostic::write_error(
+                    f,
+                    &self.file,
+                    &self.content,
+                    loc,
+                    &e.error,
+                    help.as_deref(),
+                )
+            }
+            CodegenError::ConvertErrors(errors) => {
+                for (i, e) in errors.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, "\n\n")?;
+                    }
+                    diagnostic::write_error(
+                        f,
+                        &self.file,
+                        &self.content,
+                        e.loc,
+                        &e.error,
+                        e.help.as_deref(),
+                    )?;
+                }
+                if errors.len() > 1 {
+                    write!(f, "\n\n{} errors in {}", errors.len(), self.file)?;
+                }
+                Ok(())
+            }
+        }
     }
 }
 
@@ -209,7 +271,7 @@ impl<'a> Run<'a> {
         result: &mut LinkedHashMap<PathBuf, FileDescriptorPair>,
     ) {
         for import in &parsed.imports {
-            self.get_file_and_all_deps_already_parsed(Path::new(&import.path), result);
+            self.get_file_and_all_deps_already_parsed(Path::new(&import.t.path), result);
         }
     }
 
@@ -235,13 +297,14 @@ impl<'a> Run<'a> {
                 io::ErrorKind::Other,
                 WithFileError {
                     file: format!("{}", fs_path.display()),
+                    content: content.to_owned(),
                     error: e.into(),
                 },
             )
         })?;
 
         for import in &parsed.imports {
-            self.add_imported_file(Path::new(&import.path))?;
+            self.add_imported_file(Path::new(&import.t.path))?;
         }
 
         let mut this_file_deps = LinkedHashMap::new();
@@ -255,6 +318,7 @@ impl<'a> Run<'a> {
                     io::ErrorKind::Other,
                     WithFileError {
                         file: format!("{}", fs_path.display()),
+                        content: content.to_owned(),
                         error: e.into(),
                     },
                 )
@@ -289,6 +353,7 @@ impl<'a> Run<'a> {
             Some("google/protobuf/timestamp.proto") => Some(TIMESTAMP_PROTO),
             Some("google/protobuf/type.proto") => Some(TYPE_PROTO),
             Some("google/protobuf/wrappers.proto") => Some(WRAPPERS_PROTO),
+            Some("google/protobuf/compiler/plugin.proto") => Some(PLUGIN_PROTO),
             _ => None,
         };
 
@@ -371,7 +436,9 @@ pub fn parse_and_typecheck(
     })
 }
 
-// TODO: these include don't work when publishing to crates
+// Bundled files are used when imported files are not found in include path,
+// so well-known types can be imported without configuring includes.
+// Files are copied to `src/proto` to be published with the crate.
 const RUSTPROTO_PROTO: &str = include_str!("proto/rustproto.proto");
 const ANY_PROTO: &str = include_str!("proto/google/protobuf/any.proto");
 const API_PROTO: &str = include_str!("proto/google/protobuf/api.proto");
@@ -384,3 +451,47 @@ const STRUCT_PROTO: &str = include_str!("proto/google/protobuf/struct.proto");
 const TIMESTAMP_PROTO: &str = include_str!("proto/google/protobuf/timestamp.proto");
 const TYPE_PROTO: &str = include_str!("proto/google/protobuf/type.proto");
 const WRAPPERS_PROTO: &str = include_str!("proto/google/protobuf/wrappers.proto");
+const PLUGIN_PROTO: &str = include_str!("proto/google/protobuf/compiler/plugin.proto");
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn bundled_imports() {
+        let mut run = Run {
+            parsed_files: LinkedHashMap::new(),
+            includes: &[],
+        };
+        let content = r#"
+            syntax = "proto3";
+            import "google/protobuf/any.proto";
+            import "google/protobuf/api.proto";
+            import "google/protobuf/compiler/plugin.proto";
+            import "google/protobuf/duration.proto";
+            import "google/protobuf/empty.proto";
+            import "google/protobuf/field_mask.proto";
+            import "google/protobuf/struct.proto";
+            import "google/protobuf/timestamp.proto";
+            import "google/protobuf/wrappers.proto";
+            import "rustproto.proto";
+            message Event {
+                google.protobuf.Timestamp time = 1;
+                google.protobuf.Int32Value count = 2;
+            }
+        "#;
+        run.add_file_content(Path::new("event.proto"), Path::new("event.proto"), content)
+            .unwrap();
+        let event = &run.parsed_files[Path::new("event.proto")].descriptor;
+        assert_eq!(
+            ".google.protobuf.Timestamp",
+            event.message_type[0].field[0].get_type_name()
+        );
+        assert!(run
+            .parsed_files
+            .get(Path::new("google/protobuf/descriptor.proto"))
+            .is_some());
+    }
+}
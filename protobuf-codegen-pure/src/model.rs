@@ -1,11 +1,27 @@
-//! A nom-based protobuf file parser
+//! Syntax tree of `.proto` file.
 //!
-//! This crate can be seen as a rust transcription of the
+//! Unlike `FileDescriptorProto`, the tree keeps source-level details:
+//! locations of declarations, all comments of the file and option values
+//! as they are written. It is intended for tools like formatters and linters.
+//!
+//! ```
+//! use protobuf_codegen_pure::model::FileDescriptor;
+//!
+//! let file = FileDescriptor::parse("// Greeting\nmessage Hello { optional string name = 1; }")
+//!     .unwrap();
+//! assert_eq!("Hello", file.messages[0].t.name);
+//! assert_eq!(2, file.messages[0].loc.line);
+//! assert_eq!(vec!["// Greeting"], file.leading_comments(file.messages[0].loc));
+//! ```
+//!
+//! This module can be seen as a rust transcription of the
 //! [descriptor.proto](https://github.com/google/protobuf/blob/master/src/google/protobuf/descriptor.proto) file
 
 use protobuf::text_format::lexer::float;
-use protobuf::text_format::lexer::Loc;
-use protobuf::text_format::lexer::StrLit;
+use protobuf::text_format::lexer::Lexer;
+pub use protobuf::text_format::lexer::Loc;
+use protobuf::text_format::lexer::ParserLanguage;
+pub use protobuf::text_format::lexer::StrLit;
 
 use crate::parser::Parser;
 use std::fmt::Write;
@@ -21,14 +37,17 @@ use protobuf::text_format::lexer::float::format_protobuf_float;
 use protobuf_codegen::ProtobufIdent;
 use std::fmt;
 
+/// Syntax tree element with its location.
 #[derive(Debug, Clone, PartialEq)]
 pub struct WithLoc<T> {
+    /// Location of the first token of the element
     pub loc: Loc,
+    /// The element
     pub t: T,
 }
 
 impl<T> WithLoc<T> {
-    pub fn with_loc(loc: Loc) -> impl FnOnce(T) -> WithLoc<T> {
+    pub(crate) fn with_loc(loc: Loc) -> impl FnOnce(T) -> WithLoc<T> {
         move |t| WithLoc {
             t,
             loc: loc.clone(),
@@ -68,6 +87,7 @@ pub enum Rule {
 pub struct Group {
     /// Group name
     pub name: String,
+    /// Group fields
     pub fields: Vec<WithLoc<Field>>,
 }
 
@@ -182,7 +202,9 @@ pub struct Field {
 /// A Protobuf field of oneof group
 #[derive(Debug, Clone, PartialEq)]
 pub enum FieldOrOneOf {
+    /// Regular field
     Field(WithLoc<Field>),
+    /// Oneof
     OneOf(OneOf),
 }
 
@@ -211,7 +233,7 @@ pub struct Message {
     /// Nested messages
     pub messages: Vec<WithLoc<Message>>,
     /// Nested enums
-    pub enums: Vec<Enumeration>,
+    pub enums: Vec<WithLoc<Enumeration>>,
     /// Non-builtin options
     pub options: Vec<ProtobufOption>,
     /// Extension field numbers
@@ -221,6 +243,7 @@ pub struct Message {
 }
 
 impl Message {
+    /// Fields of the message including fields of oneofs.
     pub fn regular_fields_including_in_oneofs(&self) -> Vec<&WithLoc<Field>> {
         self.fields
             .iter()
@@ -239,7 +262,7 @@ impl Message {
             .map(|f| &f.t)
     }
 
-    pub fn _nested_extensions(&self) -> Vec<&Group> {
+    pub(crate) fn _nested_extensions(&self) -> Vec<&Group> {
         self.regular_fields_including_in_oneofs()
             .into_iter()
             .flat_map(|f| match &f.t.typ {
@@ -250,7 +273,7 @@ impl Message {
     }
 
     #[cfg(test)]
-    pub fn regular_fields_for_test(&self) -> Vec<&Field> {
+    pub(crate) fn regular_fields_for_test(&self) -> Vec<&Field> {
         self.fields
             .iter()
             .flat_map(|fo| match &fo.t {
@@ -261,7 +284,7 @@ impl Message {
     }
 
     #[cfg(test)]
-    pub fn oneofs_for_test(&self) -> Vec<&OneOf> {
+    pub(crate) fn oneofs_for_test(&self) -> Vec<&OneOf> {
         self.fields
             .iter()
             .flat_map(|fo| match &fo.t {
@@ -289,9 +312,13 @@ pub struct Enumeration {
     /// enum name
     pub name: String,
     /// enum values
-    pub values: Vec<EnumValue>,
+    pub values: Vec<WithLoc<EnumValue>>,
     /// enum options
     pub options: Vec<ProtobufOption>,
+    /// enum reserved numbers
+    pub reserved_nums: Vec<FieldNumberRange>,
+    /// enum reserved names
+    pub reserved_names: Vec<String>,
 }
 
 /// A OneOf
@@ -305,6 +332,7 @@ pub struct OneOf {
     pub options: Vec<ProtobufOption>,
 }
 
+/// Extension field
 #[derive(Debug, Clone)]
 pub struct Extension {
     /// Extend this type with field
@@ -335,24 +363,37 @@ pub struct Method {
 pub struct Service {
     /// Service name
     pub name: String,
-    pub methods: Vec<Method>,
+    /// Service methods
+    pub methods: Vec<WithLoc<Method>>,
+    /// Service options
     pub options: Vec<ProtobufOption>,
 }
 
+/// Message constant in text format, like `{ a: 1 }`
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ProtobufConstantMessage {
+    /// Fields in the order they are written
     pub fields: LinkedHashMap<String, ProtobufConstant>,
+    /// Extensions like `[pkg.ext]: 1` by extension name
     pub extensions: LinkedHashMap<String, ProtobufConstantMessage>,
 }
 
+/// Constant value of option or field default
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProtobufConstant {
+    /// Non-negative integer
     U64(u64),
+    /// Negative integer
     I64(i64),
+    /// Floating point number
     F64(f64), // TODO: eq
+    /// `true` or `false`
     Bool(bool),
+    /// Identifier like enum value name or `inf`
     Ident(String),
+    /// String literal as written, not decoded
     String(StrLit),
+    /// Message in text format
     Message(ProtobufConstantMessage),
 }
 
@@ -372,6 +413,7 @@ impl fmt::Display for ProtobufConstant {
 }
 
 impl ProtobufConstantMessage {
+    /// Format as text format message.
     pub fn format(&self) -> String {
         let mut s = String::new();
         write!(s, "{{").unwrap();
@@ -387,6 +429,7 @@ impl ProtobufConstantMessage {
 }
 
 impl ProtobufConstant {
+    /// Format as in `.proto` file.
     pub fn format(&self) -> String {
         match *self {
             ProtobufConstant::U64(u) => u.to_string(),
@@ -400,7 +443,7 @@ impl ProtobufConstant {
     }
 
     /** Interpret .proto constant as an reflection value. */
-    pub fn as_type(&self, ty: RuntimeTypeBox) -> ConvertResult<ReflectValueBox> {
+    pub(crate) fn as_type(&self, ty: RuntimeTypeBox) -> ConvertResult<ReflectValueBox> {
         match (self, &ty) {
             (ProtobufConstant::Ident(ident), RuntimeTypeBox::Enum(e)) => {
                 if let Some(v) = e.get_value_by_name(ident) {
@@ -419,9 +462,12 @@ impl ProtobufConstant {
     }
 }
 
+/// Component of option name
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProtobufOptionNameComponent {
+    /// Field name like `foo`
     Direct(ProtobufIdent),
+    /// Extension name like `(pkg.ext)`, without parentheses
     Ext(String),
 }
 
@@ -434,23 +480,28 @@ impl fmt::Display for ProtobufOptionNameComponent {
     }
 }
 
+/// Option name which is not a builtin option, like `(pkg.ext).foo`
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProtobufOptionNameExt(pub Vec<ProtobufOptionNameComponent>);
 
 impl ProtobufOptionNameExt {
     // TODO: get rid of it
-    pub fn full_name(&self) -> String {
+    pub(crate) fn full_name(&self) -> String {
         format!("{}", self)
     }
 }
 
+/// Option name
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProtobufOptionName {
+    /// Builtin option like `deprecated`
     Builtin(ProtobufIdent),
+    /// Extension option like `(pkg.ext)`
     Ext(ProtobufOptionNameExt),
 }
 
 impl ProtobufOptionName {
+    /// Builtin option name.
     pub fn simple(name: &str) -> ProtobufOptionName {
         ProtobufOptionName::Builtin(ProtobufIdent::new(name))
     }
@@ -477,17 +528,25 @@ impl fmt::Display for ProtobufOptionName {
     }
 }
 
+/// Option like `option java_package = "foo";` or `[deprecated = true]`
 #[derive(Debug, Clone, PartialEq)]
 pub struct ProtobufOption {
+    /// Option name
     pub name: ProtobufOptionName,
+    /// Option value as written
     pub value: ProtobufConstant,
+    /// Location of option name
+    pub loc: Loc,
 }
 
 /// Visibility of import statement
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ImportVis {
+    /// `import`
     Default,
+    /// `import public`
     Public,
+    /// `import weak`
     Weak,
 }
 
@@ -500,7 +559,9 @@ impl Default for ImportVis {
 /// Import statement
 #[derive(Debug, Default, Clone)]
 pub struct Import {
+    /// Imported file path
     pub path: String,
+    /// Import visibility
     pub vis: ImportVis,
 }
 
@@ -508,7 +569,7 @@ pub struct Import {
 #[derive(Debug, Default, Clone)]
 pub struct FileDescriptor {
     /// Imports
-    pub imports: Vec<Import>,
+    pub imports: Vec<WithLoc<Import>>,
     /// Package
     pub package: Option<String>,
     /// Protobuf Syntax
@@ -516,13 +577,15 @@ pub struct FileDescriptor {
     /// Top level messages
     pub messages: Vec<WithLoc<Message>>,
     /// Enums
-    pub enums: Vec<Enumeration>,
+    pub enums: Vec<WithLoc<Enumeration>>,
     /// Extensions
     pub extensions: Vec<WithLoc<Extension>>,
     /// Services
     pub services: Vec<WithLoc<Service>>,
     /// Non-builtin options
     pub options: Vec<ProtobufOption>,
+    /// All comments of the file in source order, including comment markers
+    pub comments: Vec<WithLoc<String>>,
 }
 
 impl FileDescriptor {
@@ -530,11 +593,43 @@ impl FileDescriptor {
     pub fn parse<S: AsRef<str>>(file: S) -> Result<Self, ParserErrorWithLocation> {
         let mut parser = Parser::new(file.as_ref());
         match parser.next_proto() {
-            Ok(r) => Ok(r),
+            Ok(mut r) => {
+                // Input is already tokenized successfully, so this cannot fail
+                r.comments = Lexer::new(file.as_ref(), ParserLanguage::Proto)
+                    .comments()
+                    .expect("comments")
+                    .into_iter()
+                    .map(|(loc, comment)| WithLoc {
+                        loc,
+                        t: comment.to_owned(),
+                    })
+                    .collect();
+                Ok(r)
+            }
             Err(error) => {
                 let Loc { line, col } = parser.tokenizer.loc();
                 Err(ParserErrorWithLocation { error, line, col })
             }
         }
     }
+
+    /// Comments on the lines immediately preceding the element at given location,
+    /// like documentation comments of messages and fields.
+    pub fn leading_comments(&self, loc: Loc) -> Vec<&str> {
+        let mut r = Vec::new();
+        let mut line = loc.line;
+        for comment in self.comments.iter().rev() {
+            if comment.loc >= loc {
+                continue;
+            }
+            let end_line = comment.loc.line + comment.t.matches('\n').count() as u32;
+            if end_line + 1 != line {
+                break;
+            }
+            r.push(&comment.t[..]);
+            line = comment.loc.line;
+        }
+        r.reverse();
+        r
+    }
 }
@@ -6,6 +6,7 @@ use protobuf::text_format::lexer::ParserLanguage;
 use protobuf::text_format::lexer::StrLitDecodeError;
 use protobuf::text_format::lexer::Token;
 
+use crate::diagnostic::did_you_mean;
 use crate::fmt;
 use crate::model::*;
 use protobuf::text_format::lexer::int;
@@ -16,21 +17,38 @@ use protobuf_codegen::ProtobufIdent;
 /// Basic information about parsing error.
 #[derive(Debug)]
 pub enum ParserError {
+    /// Tokenizer error
     TokenizerError(TokenizerError),
+    /// Incorrect input
     IncorrectInput,
+    /// Input is not UTF-8
     NotUtf8,
+    /// Expecting a constant
     ExpectConstant,
+    /// Unknown `syntax`
     UnknownSyntax,
+    /// Integer overflow
     IntegerOverflow,
+    /// Field label is not allowed here
     LabelNotAllowed,
+    /// Field label is required
     LabelRequired,
+    /// Group name should start with upper case
     GroupNameShouldStartWithUpperCase,
+    /// Map field is not allowed here
     MapFieldNotAllowed,
+    /// String literal decode error
     StrLitDecodeError(StrLitDecodeError),
+    /// Lexer error
     LexerError(LexerError),
+    /// Oneof in group
     OneOfInGroup,
+    /// Oneof in oneof
     OneOfInOneOf,
+    /// Oneof in `extend`
     OneOfInExtend,
+    /// Unknown top-level statement, like misspelled `message`
+    UnknownStatement(String),
 }
 
 impl fmt::Display for ParserError {
@@ -54,6 +72,23 @@ impl fmt::Display for ParserError {
             ParserError::OneOfInGroup => write!(f, "oneof in group"),
             ParserError::OneOfInOneOf => write!(f, "oneof in oneof"),
             ParserError::OneOfInExtend => write!(f, "oneof in extend"),
+            ParserError::UnknownStatement(s) => write!(f, "unknown statement `{}`", s),
+        }
+    }
+}
+
+const TOP_LEVEL_KEYWORDS: &[&str] = &[
+    "import", "package", "option", "message", "enum", "extend", "service",
+];
+
+impl ParserError {
+    /// "Did you mean" hint for the error.
+    pub(crate) fn help(&self) -> Option<String> {
+        match self {
+            ParserError::UnknownStatement(s) => {
+                did_you_mean(s, TOP_LEVEL_KEYWORDS).map(|k| format!("did you mean `{}`?", k))
+            }
+            _ => None,
         }
     }
 }
@@ -82,8 +117,10 @@ impl From<int::Overflow> for ParserError {
     }
 }
 
+/// Parsing error with location.
 #[derive(Debug)]
 pub struct ParserErrorWithLocation {
+    /// The error
     pub error: ParserError,
     /// 1-based
     pub line: u32,
@@ -243,7 +280,7 @@ pub struct MessageBody {
     pub reserved_nums: Vec<FieldNumberRange>,
     pub reserved_names: Vec<String>,
     pub messages: Vec<WithLoc<Message>>,
-    pub enums: Vec<Enumeration>,
+    pub enums: Vec<WithLoc<Enumeration>>,
     pub options: Vec<ProtobufOption>,
     pub extension_ranges: Vec<FieldNumberRange>,
     pub extensions: Vec<WithLoc<Extension>>,
@@ -442,7 +479,9 @@ impl<'a> Parser<'a> {
     // Import Statement
 
     // import = "import" [ "weak" | "public" ] strLit ";"
-    fn next_import_opt(&mut self) -> ParserResult<Option<Import>> {
+    fn next_import_opt(&mut self) -> ParserResult<Option<WithLoc<Import>>> {
+        let loc = self.tokenizer.lookahead_loc();
+
         if self.tokenizer.next_ident_if_eq("import")? {
             let vis = if self.tokenizer.next_ident_if_eq("weak")? {
                 ImportVis::Weak
@@ -453,7 +492,10 @@ impl<'a> Parser<'a> {
             };
             let path = self.tokenizer.next_str_lit()?.decode_utf8()?;
             self.tokenizer.next_symbol_expect_eq(';')?;
-            Ok(Some(Import { path, vis }))
+            Ok(Some(WithLoc {
+                loc,
+                t: Import { path, vis },
+            }))
         } else {
             Ok(None)
         }
@@ -508,11 +550,12 @@ impl<'a> Parser<'a> {
     // option = "option" optionName  "=" constant ";"
     fn next_option_opt(&mut self) -> ParserResult<Option<ProtobufOption>> {
         if self.tokenizer.next_ident_if_eq("option")? {
+            let loc = self.tokenizer.lookahead_loc();
             let name = self.next_option_name()?;
             self.tokenizer.next_symbol_expect_eq('=')?;
             let value = self.next_constant()?;
             self.tokenizer.next_symbol_expect_eq(';')?;
-            Ok(Some(ProtobufOption { name, value }))
+            Ok(Some(ProtobufOption { name, value, loc }))
         } else {
             Ok(None)
         }
@@ -588,10 +631,11 @@ impl<'a> Parser<'a> {
 
     // fieldOption = optionName "=" constant
     fn next_field_option(&mut self) -> ParserResult<ProtobufOption> {
+        let loc = self.tokenizer.lookahead_loc();
         let name = self.next_option_name()?;
         self.tokenizer.next_symbol_expect_eq('=')?;
         let value = self.next_constant()?;
-        Ok(ProtobufOption { name, value })
+        Ok(ProtobufOption { name, value, loc })
     }
 
     // fieldOptions = fieldOption { ","  fieldOption }
@@ -762,6 +806,20 @@ impl<'a> Parser<'a> {
     // reserved = "reserved" ( ranges | fieldNames ) ";"
     // fieldNames = fieldName { "," fieldName }
     fn next_reserved_opt(&mut self) -> ParserResult<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
+        self.next_reserved_opt_with(Parser::next_ranges)
+    }
+
+    // enumReserved = "reserved" ( enumRanges | fieldNames ) ";"
+    fn next_enum_reserved_opt(
+        &mut self,
+    ) -> ParserResult<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
+        self.next_reserved_opt_with(Parser::next_enum_ranges)
+    }
+
+    fn next_reserved_opt_with(
+        &mut self,
+        next_ranges: fn(&mut Self) -> ParserResult<Vec<FieldNumberRange>>,
+    ) -> ParserResult<Option<(Vec<FieldNumberRange>, Vec<String>)>> {
         if self.tokenizer.next_ident_if_eq("reserved")? {
             let (ranges, names) = if let &Token::StrLit(..) = self.tokenizer.lookahead_some()? {
                 let mut names = Vec::new();
@@ -771,7 +829,7 @@ impl<'a> Parser<'a> {
                 }
                 (Vec::new(), names)
             } else {
-                (self.next_ranges()?, Vec::new())
+                (next_ranges(self)?, Vec::new())
             };
 
             self.tokenizer.next_symbol_expect_eq(';')?;
@@ -788,10 +846,11 @@ impl<'a> Parser<'a> {
 
     // enumValueOption = optionName "=" constant
     fn next_enum_value_option(&mut self) -> ParserResult<ProtobufOption> {
+        let loc = self.tokenizer.lookahead_loc();
         let name = self.next_option_name()?;
         self.tokenizer.next_symbol_expect_eq('=')?;
         let value = self.next_constant()?;
-        Ok(ProtobufOption { name, value })
+        Ok(ProtobufOption { name, value, loc })
     }
 
     // https://github.com/google/protobuf/issues/4561
@@ -809,8 +868,34 @@ impl<'a> Parser<'a> {
         })
     }
 
+    // enumRange = enumValue [ "to" ( enumValue | "max" ) ]
+    fn next_enum_range(&mut self) -> ParserResult<FieldNumberRange> {
+        let from = self.next_enum_value()?;
+        let to = if self.tokenizer.next_ident_if_eq("to")? {
+            if self.tokenizer.next_ident_if_eq("max")? {
+                i32::MAX
+            } else {
+                self.next_enum_value()?
+            }
+        } else {
+            from
+        };
+        Ok(FieldNumberRange { from, to })
+    }
+
+    // enumRanges = enumRange { "," enumRange }
+    fn next_enum_ranges(&mut self) -> ParserResult<Vec<FieldNumberRange>> {
+        let mut ranges = Vec::new();
+        ranges.push(self.next_enum_range()?);
+        while self.tokenizer.next_symbol_if_eq(',')? {
+            ranges.push(self.next_enum_range()?);
+        }
+        Ok(ranges)
+    }
+
     // enumField = ident "=" intLit [ "[" enumValueOption { ","  enumValueOption } "]" ]";"
-    fn next_enum_field(&mut self) -> ParserResult<EnumValue> {
+    fn next_enum_field(&mut self) -> ParserResult<WithLoc<EnumValue>> {
+        let loc = self.tokenizer.lookahead_loc();
         let name = self.tokenizer.next_ident()?.to_owned();
         self.tokenizer.next_symbol_expect_eq('=')?;
         let number = self.next_enum_value()?;
@@ -823,21 +908,28 @@ impl<'a> Parser<'a> {
             self.tokenizer.next_symbol_expect_eq(']')?;
         }
 
-        Ok(EnumValue {
-            name,
-            number,
-            options,
+        Ok(WithLoc {
+            loc,
+            t: EnumValue {
+                name,
+                number,
+                options,
+            },
         })
     }
 
     // enum = "enum" enumName enumBody
-    // enumBody = "{" { option | enumField | emptyStatement } "}"
-    fn next_enum_opt(&mut self) -> ParserResult<Option<Enumeration>> {
+    // enumBody = "{" { option | enumField | reserved | emptyStatement } "}"
+    fn next_enum_opt(&mut self) -> ParserResult<Option<WithLoc<Enumeration>>> {
+        let loc = self.tokenizer.lookahead_loc();
+
         if self.tokenizer.next_ident_if_eq("enum")? {
             let name = self.tokenizer.next_ident()?.to_owned();
 
             let mut values = Vec::new();
             let mut options = Vec::new();
+            let mut reserved_nums = Vec::new();
+            let mut reserved_names = Vec::new();
 
             self.tokenizer.next_symbol_expect_eq('{')?;
             while self.tokenizer.lookahead_if_symbol()? != Some('}') {
@@ -851,13 +943,24 @@ impl<'a> Parser<'a> {
                     continue;
                 }
 
+                if let Some((nums, names)) = self.next_enum_reserved_opt()? {
+                    reserved_nums.extend(nums);
+                    reserved_names.extend(names);
+                    continue;
+                }
+
                 values.push(self.next_enum_field()?);
             }
             self.tokenizer.next_symbol_expect_eq('}')?;
-            Ok(Some(Enumeration {
-                name,
-                values,
-                options,
+            Ok(Some(WithLoc {
+                loc,
+                t: Enumeration {
+                    name,
+                    values,
+                    options,
+                    reserved_nums,
+                    reserved_names,
+                },
             }))
         } else {
             Ok(None)
@@ -1052,8 +1155,9 @@ impl<'a> Parser<'a> {
 
     // stream = "stream" streamName "(" messageType "," messageType ")"
     //        (( "{" { option | emptyStatement } "}") | ";" )
-    fn next_stream_opt(&mut self) -> ParserResult<Option<Method>> {
+    fn next_stream_opt(&mut self) -> ParserResult<Option<WithLoc<Method>>> {
         assert_eq!(Syntax::Proto2, self.syntax);
+        let loc = self.tokenizer.lookahead_loc();
         if self.tokenizer.next_ident_if_eq("stream")? {
             let name = self.tokenizer.next_ident()?;
             self.tokenizer.next_symbol_expect_eq('(')?;
@@ -1062,13 +1166,16 @@ impl<'a> Parser<'a> {
             let output_type = self.tokenizer.next_ident()?;
             self.tokenizer.next_symbol_expect_eq(')')?;
             let options = self.next_options_or_colon()?;
-            Ok(Some(Method {
-                name,
-                input_type,
-                output_type,
-                client_streaming: true,
-                server_streaming: true,
-                options,
+            Ok(Some(WithLoc {
+                loc,
+                t: Method {
+                    name,
+                    input_type,
+                    output_type,
+                    client_streaming: true,
+                    server_streaming: true,
+                    options,
+                },
             }))
         } else {
             Ok(None)
@@ -1078,7 +1185,8 @@ impl<'a> Parser<'a> {
     // rpc = "rpc" rpcName "(" [ "stream" ] messageType ")"
     //     "returns" "(" [ "stream" ] messageType ")"
     //     (( "{" { option | emptyStatement } "}" ) | ";" )
-    fn next_rpc_opt(&mut self) -> ParserResult<Option<Method>> {
+    fn next_rpc_opt(&mut self) -> ParserResult<Option<WithLoc<Method>>> {
+        let loc = self.tokenizer.lookahead_loc();
         if self.tokenizer.next_ident_if_eq("rpc")? {
             let name = self.tokenizer.next_ident()?;
             self.tokenizer.next_symbol_expect_eq('(')?;
@@ -1091,13 +1199,16 @@ impl<'a> Parser<'a> {
             let output_type = self.next_message_or_enum_type()?;
             self.tokenizer.next_symbol_expect_eq(')')?;
             let options = self.next_options_or_colon()?;
-            Ok(Some(Method {
-                name,
-                input_type,
-                output_type,
-                client_streaming,
-                server_streaming,
-                options,
+            Ok(Some(WithLoc {
+                loc,
+                t: Method {
+                    name,
+                    input_type,
+                    output_type,
+                    client_streaming,
+                    server_streaming,
+                    options,
+                },
             }))
         } else {
             Ok(None)
@@ -1211,6 +1322,10 @@ impl<'a> Parser<'a> {
                 continue;
             }
 
+            if let Token::Ident(ident) = self.tokenizer.lookahead_some()? {
+                return Err(ParserError::UnknownStatement(ident.clone()));
+            }
+
             return Err(ParserError::IncorrectInput);
         }
 
@@ -1223,6 +1338,7 @@ impl<'a> Parser<'a> {
             extensions,
             services,
             options,
+            comments: Vec::new(),
         })
     }
 }
@@ -1322,7 +1438,7 @@ mod test {
     }"#;
 
         let enumeration = parse_opt(msg, |p| p.next_enum_opt());
-        assert_eq!(4, enumeration.values.len());
+        assert_eq!(4, enumeration.t.values.len());
     }
 
     #[test]
@@ -1347,7 +1463,10 @@ mod test {
 
         assert_eq!(
             vec!["test_import_nested_imported_pb.proto"],
-            desc.imports.into_iter().map(|i| i.path).collect::<Vec<_>>()
+            desc.imports
+                .into_iter()
+                .map(|i| i.t.path)
+                .collect::<Vec<_>>()
         );
     }
 
@@ -1453,6 +1572,30 @@ mod test {
         assert_eq!(2, mess.t.fields.len());
     }
 
+    #[test]
+    fn test_enum_reserved() {
+        let msg = r#"enum Sample {
+       ZERO = 0;
+       reserved -2, 15, 17 to max;
+       reserved "FOO";
+    }"#;
+
+        let enumeration = parse_opt(msg, |p| p.next_enum_opt());
+        assert_eq!(
+            vec![
+                FieldNumberRange { from: -2, to: -2 },
+                FieldNumberRange { from: 15, to: 15 },
+                FieldNumberRange {
+                    from: 17,
+                    to: i32::MAX
+                },
+            ],
+            enumeration.t.reserved_nums
+        );
+        assert_eq!(vec!["FOO".to_string()], enumeration.t.reserved_names);
+        assert_eq!(1, enumeration.t.values.len());
+    }
+
     #[test]
     fn test_default_value_int() {
         let msg = r#"message Sample {
@@ -1563,4 +1706,47 @@ mod test {
         );
         assert_eq!(17003, fd.extensions[2].t.field.t.number);
     }
+
+    #[test]
+    fn test_locations_and_comments() {
+        let proto = r#"syntax = "proto3";
+import "a.proto";
+
+/* Colors */
+// of things
+enum Color {
+  RED = 0; // red
+  GREEN = 1;
+}
+
+service S {
+  // Get
+  rpc Get(Color) returns (Color) { option deprecated = true; }
+}
+"#;
+
+        let fd = FileDescriptor::parse(proto).expect("fd");
+        assert_eq!(Loc { line: 2, col: 1 }, fd.imports[0].loc);
+
+        let color = &fd.enums[0];
+        assert_eq!(Loc { line: 6, col: 1 }, color.loc);
+        assert_eq!(Loc { line: 8, col: 3 }, color.t.values[1].loc);
+        assert_eq!(
+            vec!["/* Colors */", "// of things"],
+            fd.leading_comments(color.loc)
+        );
+        assert_eq!(vec!["// red"], fd.leading_comments(color.t.values[1].loc));
+
+        let get = &fd.services[0].t.methods[0];
+        assert_eq!(Loc { line: 13, col: 3 }, get.loc);
+        assert_eq!(vec!["// Get"], fd.leading_comments(get.loc));
+        assert_eq!(
+            ProtobufOptionName::simple("deprecated"),
+            get.t.options[0].name
+        );
+        assert_eq!(ProtobufConstant::Bool(true), get.t.options[0].value);
+
+        assert_eq!(4, fd.comments.len());
+        assert_eq!(Loc { line: 7, col: 12 }, fd.comments[2].loc);
+    }
 }
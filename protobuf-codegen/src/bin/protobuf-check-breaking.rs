@@ -0,0 +1,41 @@
+//! Compare two `FileDescriptorSet` files and report backward incompatible changes.
+//!
+//! Descriptor sets can be generated with `protoc --include_imports -o`.
+//! Exit code is 1 if breaking changes are found.
+
+use std::env;
+use std::fs::File;
+use std::process;
+
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::Message;
+
+fn read_fds(path: &str) -> FileDescriptorSet {
+    let mut file = File::open(path).unwrap_or_else(|e| {
+        eprintln!("failed to open {}: {}", path, e);
+        process::exit(2);
+    });
+    FileDescriptorSet::parse_from_reader(&mut file).unwrap_or_else(|e| {
+        eprintln!("failed to parse {}: {}", path, e);
+        process::exit(2);
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("usage: {} <old.pb> <new.pb>", args[0]);
+        process::exit(2);
+    }
+
+    let old = read_fds(&args[1]);
+    let new = read_fds(&args[2]);
+
+    let changes = protobuf_codegen::breaking::check(&old.file, &new.file);
+    for change in &changes {
+        println!("{}", change);
+    }
+    if !changes.is_empty() {
+        process::exit(1);
+    }
+}
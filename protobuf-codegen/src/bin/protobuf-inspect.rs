@@ -0,0 +1,93 @@
+//! Decode binary message and print it as text format or JSON.
+//!
+//! ```text
+//! protobuf-inspect [--hex|--base64] [--json] [--unredacted] --descriptor_set=<fds.pb> <message> [input]
+//! protobuf-inspect [--hex|--base64] --decode_raw [input]
+//! ```
+//!
+//! Descriptor sets can be generated with `protoc --include_imports -o`.
+//! Input is read from stdin if file is not specified.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::process;
+
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::Message;
+use protobuf_codegen::inspect;
+use protobuf_codegen::inspect::InputFormat;
+use protobuf_codegen::inspect::OutputFormat;
+
+const USAGE: &str = "\
+usage: protobuf-inspect [--hex|--base64] [--json] [--unredacted] --descriptor_set=<fds.pb> <message> [input]
+       protobuf-inspect [--hex|--base64] --decode_raw [input]";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
+
+fn main() {
+    let mut input_format = InputFormat::Binary;
+    let mut output_format = OutputFormat::Text;
+    let mut unredacted = false;
+    let mut decode_raw = false;
+    let mut descriptor_set = None;
+    let mut free = Vec::new();
+
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--hex" => input_format = InputFormat::Hex,
+            "--base64" => input_format = InputFormat::Base64,
+            "--json" => output_format = OutputFormat::Json,
+            "--unredacted" => unredacted = true,
+            "--decode_raw" => decode_raw = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => match arg.strip_prefix("--descriptor_set=") {
+                Some(path) => descriptor_set = Some(path.to_owned()),
+                None if arg.starts_with("--") => fail(USAGE),
+                None => free.push(arg),
+            },
+        }
+    }
+
+    let (message, input) = match (decode_raw, free.len()) {
+        (true, 0) | (true, 1) => (None, free.get(0)),
+        (false, 1) | (false, 2) if descriptor_set.is_some() => (Some(&free[0]), free.get(1)),
+        _ => fail(USAGE),
+    };
+
+    let mut bytes = Vec::new();
+    let read = match input {
+        Some(path) => fs::File::open(path).and_then(|mut f| f.read_to_end(&mut bytes)),
+        None => io::stdin().read_to_end(&mut bytes),
+    };
+    if let Err(e) = read {
+        fail(&format!("failed to read input: {}", e));
+    }
+
+    let result = inspect::decode_input(input_format, &bytes).and_then(|bytes| match message {
+        None => inspect::decode_raw(&bytes),
+        Some(message) => {
+            let path = descriptor_set.as_ref().unwrap();
+            let fds = fs::read(path)
+                .map_err(|e| e.to_string())
+                .and_then(|b| FileDescriptorSet::parse_from_bytes(&b).map_err(|e| e.to_string()))
+                .unwrap_or_else(|e| fail(&format!("failed to read {}: {}", path, e)));
+            let descriptor = inspect::find_message(&fds, message)?;
+            inspect::decode(&descriptor, &bytes, output_format, unredacted)
+        }
+    });
+    match result {
+        Ok(s) => print!("{}", s),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
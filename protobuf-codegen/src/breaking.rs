@@ -0,0 +1,606 @@
+//! Detect backward incompatible changes between two versions of `.proto` files.
+//!
+//! Files are compared by name, messages and enums by fully qualified name,
+//! fields and enum values by number, so renaming a field is reported only
+//! when its number is reused by a different name.
+//!
+//! ```
+//! # use protobuf::descriptor::FileDescriptorSet;
+//! # fn f(old: &FileDescriptorSet, new: &FileDescriptorSet) {
+//! for change in protobuf_codegen::breaking::check(&old.file, &new.file) {
+//!     eprintln!("{}", change);
+//! }
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use protobuf::descriptor::field_descriptor_proto::Label;
+use protobuf::descriptor::field_descriptor_proto::Type;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::EnumDescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::descriptor::ServiceDescriptorProto;
+
+/// Kind of breaking change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakingChangeKind {
+    /// File was removed.
+    FileRemoved,
+    /// Package of the file changed.
+    PackageChanged {
+        /// Old package.
+        old: String,
+        /// New package.
+        new: String,
+    },
+    /// Message was removed.
+    MessageRemoved,
+    /// Field was removed without reserving its number.
+    FieldRemoved {
+        /// Field number.
+        number: i32,
+    },
+    /// Field with the same name now has different number.
+    FieldNumberChanged {
+        /// Old number.
+        old: i32,
+        /// New number.
+        new: i32,
+    },
+    /// Field with the same number has different name.
+    FieldNameChanged {
+        /// Old name.
+        old: String,
+        /// New name.
+        new: String,
+    },
+    /// Field type or type name changed.
+    FieldTypeChanged {
+        /// Old type, e. g. `int32` or `.foo.Bar`.
+        old: String,
+        /// New type.
+        new: String,
+    },
+    /// Field label (optional, required, repeated) changed.
+    FieldCardinalityChanged {
+        /// Old label.
+        old: Label,
+        /// New label.
+        new: Label,
+    },
+    /// Field moved into or out of a oneof, or to a different oneof.
+    FieldOneofChanged,
+    /// Field uses number or name reserved in the old version.
+    ReservedFieldUsed {
+        /// Field number.
+        number: i32,
+    },
+    /// Enum was removed.
+    EnumRemoved,
+    /// Enum value was removed without reserving its number.
+    EnumValueRemoved {
+        /// Value number.
+        number: i32,
+    },
+    /// Enum value with the same name now has different number.
+    EnumValueNumberChanged {
+        /// Old number.
+        old: i32,
+        /// New number.
+        new: i32,
+    },
+    /// Enum value uses number or name reserved in the old version.
+    ReservedEnumValueUsed {
+        /// Value number.
+        number: i32,
+    },
+    /// Service was removed.
+    ServiceRemoved,
+    /// Method was removed.
+    MethodRemoved,
+    /// Method input or output type changed.
+    MethodTypeChanged,
+    /// Method client or server streaming changed.
+    MethodStreamingChanged,
+}
+
+impl fmt::Display for BreakingChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BreakingChangeKind::FileRemoved => write!(f, "file removed"),
+            BreakingChangeKind::PackageChanged { old, new } => {
+                write!(f, "package changed from `{}` to `{}`", old, new)
+            }
+            BreakingChangeKind::MessageRemoved => write!(f, "message removed"),
+            BreakingChangeKind::FieldRemoved { number } => {
+                write!(f, "field {} removed without reserving it", number)
+            }
+            BreakingChangeKind::FieldNumberChanged { old, new } => {
+                write!(f, "field number changed from {} to {}", old, new)
+            }
+            BreakingChangeKind::FieldNameChanged { old, new } => {
+                write!(f, "field renamed from `{}` to `{}`", old, new)
+            }
+            BreakingChangeKind::FieldTypeChanged { old, new } => {
+                write!(f, "field type changed from `{}` to `{}`", old, new)
+            }
+            BreakingChangeKind::FieldCardinalityChanged { old, new } => {
+                write!(f, "field label changed from {:?} to {:?}", old, new)
+            }
+            BreakingChangeKind::FieldOneofChanged => write!(f, "field oneof changed"),
+            BreakingChangeKind::ReservedFieldUsed { number } => {
+                write!(f, "field {} uses reserved number or name", number)
+            }
+            BreakingChangeKind::EnumRemoved => write!(f, "enum removed"),
+            BreakingChangeKind::EnumValueRemoved { number } => {
+                write!(f, "enum value {} removed without reserving it", number)
+            }
+            BreakingChangeKind::EnumValueNumberChanged { old, new } => {
+                write!(f, "enum value number changed from {} to {}", old, new)
+            }
+            BreakingChangeKind::ReservedEnumValueUsed { number } => {
+                write!(f, "enum value {} uses reserved number or name", number)
+            }
+            BreakingChangeKind::ServiceRemoved => write!(f, "service removed"),
+            BreakingChangeKind::MethodRemoved => write!(f, "method removed"),
+            BreakingChangeKind::MethodTypeChanged => {
+                write!(f, "method input or output type changed")
+            }
+            BreakingChangeKind::MethodStreamingChanged => write!(f, "method streaming changed"),
+        }
+    }
+}
+
+/// Single breaking change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    /// File name in the old version.
+    pub file: String,
+    /// Fully qualified name of changed element, e. g. `foo.Bar.baz`,
+    /// or empty if the change is about the file.
+    pub path: String,
+    /// What has changed.
+    pub kind: BreakingChangeKind,
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}: {}", self.file, self.kind)
+        } else {
+            write!(f, "{}: {}: {}", self.file, self.path, self.kind)
+        }
+    }
+}
+
+fn concat(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn field_type_name(field: &FieldDescriptorProto) -> String {
+    match field.get_field_type() {
+        Type::TYPE_MESSAGE | Type::TYPE_ENUM | Type::TYPE_GROUP => field.get_type_name().to_owned(),
+        t => format!("{:?}", t)["TYPE_".len()..].to_lowercase(),
+    }
+}
+
+fn oneof_name<'m>(message: &'m DescriptorProto, field: &FieldDescriptorProto) -> Option<&'m str> {
+    if field.has_oneof_index() && !field.get_proto3_optional() {
+        Some(message.oneof_decl[field.get_oneof_index() as usize].get_name())
+    } else {
+        None
+    }
+}
+
+struct Checker<'a> {
+    file: &'a str,
+    changes: Vec<BreakingChange>,
+}
+
+impl<'a> Checker<'a> {
+    fn add(&mut self, path: String, kind: BreakingChangeKind) {
+        self.changes.push(BreakingChange {
+            file: self.file.to_owned(),
+            path,
+            kind,
+        });
+    }
+
+    fn check_file(&mut self, old: &FileDescriptorProto, new: &FileDescriptorProto) {
+        if old.get_package() != new.get_package() {
+            self.add(
+                String::new(),
+                BreakingChangeKind::PackageChanged {
+                    old: old.get_package().to_owned(),
+                    new: new.get_package().to_owned(),
+                },
+            );
+            return;
+        }
+
+        let package = old.get_package();
+        self.check_messages(package, &old.message_type, &new.message_type);
+        self.check_enums(package, &old.enum_type, &new.enum_type);
+
+        for old_service in &old.service {
+            let path = concat(package, old_service.get_name());
+            match new
+                .service
+                .iter()
+                .find(|s| s.get_name() == old_service.get_name())
+            {
+                Some(new_service) => self.check_service(&path, old_service, new_service),
+                None => self.add(path, BreakingChangeKind::ServiceRemoved),
+            }
+        }
+    }
+
+    fn check_messages(&mut self, scope: &str, old: &[DescriptorProto], new: &[DescriptorProto]) {
+        for old_message in old {
+            let path = concat(scope, old_message.get_name());
+            match new.iter().find(|m| m.get_name() == old_message.get_name()) {
+                Some(new_message) => self.check_message(&path, old_message, new_message),
+                None => self.add(path, BreakingChangeKind::MessageRemoved),
+            }
+        }
+    }
+
+    fn check_enums(
+        &mut self,
+        scope: &str,
+        old: &[EnumDescriptorProto],
+        new: &[EnumDescriptorProto],
+    ) {
+        for old_enum in old {
+            let path = concat(scope, old_enum.get_name());
+            match new.iter().find(|e| e.get_name() == old_enum.get_name()) {
+                Some(new_enum) => self.check_enum(&path, old_enum, new_enum),
+                None => self.add(path, BreakingChangeKind::EnumRemoved),
+            }
+        }
+    }
+
+    fn check_message(&mut self, path: &str, old: &DescriptorProto, new: &DescriptorProto) {
+        let message_reserved = |m: &DescriptorProto, f: &FieldDescriptorProto| {
+            m.reserved_range
+                .iter()
+                .any(|r| r.get_start() <= f.get_number() && f.get_number() < r.get_end())
+                || m.reserved_name.iter().any(|n| n == f.get_name())
+        };
+
+        let new_by_number: HashMap<i32, &FieldDescriptorProto> =
+            new.field.iter().map(|f| (f.get_number(), f)).collect();
+
+        for old_field in &old.field {
+            let field_path = concat(path, old_field.get_name());
+            let new_field = match new_by_number.get(&old_field.get_number()) {
+                Some(f) => f,
+                None => {
+                    match new
+                        .field
+                        .iter()
+                        .find(|f| f.get_name() == old_field.get_name())
+                    {
+                        Some(f) => self.add(
+                            field_path,
+                            BreakingChangeKind::FieldNumberChanged {
+                                old: old_field.get_number(),
+                                new: f.get_number(),
+                            },
+                        ),
+                        None if message_reserved(new, old_field) => {}
+                        None => self.add(
+                            field_path,
+                            BreakingChangeKind::FieldRemoved {
+                                number: old_field.get_number(),
+                            },
+                        ),
+                    }
+                    continue;
+                }
+            };
+
+            if old_field.get_name() != new_field.get_name() {
+                self.add(
+                    field_path.clone(),
+                    BreakingChangeKind::FieldNameChanged {
+                        old: old_field.get_name().to_owned(),
+                        new: new_field.get_name().to_owned(),
+                    },
+                );
+            }
+            let (old_type, new_type) = (field_type_name(old_field), field_type_name(new_field));
+            if old_type != new_type {
+                self.add(
+                    field_path.clone(),
+                    BreakingChangeKind::FieldTypeChanged {
+                        old: old_type,
+                        new: new_type,
+                    },
+                );
+            }
+            if old_field.get_label() != new_field.get_label() {
+                self.add(
+                    field_path.clone(),
+                    BreakingChangeKind::FieldCardinalityChanged {
+                        old: old_field.get_label(),
+                        new: new_field.get_label(),
+                    },
+                );
+            }
+            if oneof_name(old, old_field) != oneof_name(new, new_field) {
+                self.add(field_path, BreakingChangeKind::FieldOneofChanged);
+            }
+        }
+
+        for new_field in &new.field {
+            if message_reserved(old, new_field) {
+                self.add(
+                    concat(path, new_field.get_name()),
+                    BreakingChangeKind::ReservedFieldUsed {
+                        number: new_field.get_number(),
+                    },
+                );
+            }
+        }
+
+        self.check_messages(path, &old.nested_type, &new.nested_type);
+        self.check_enums(path, &old.enum_type, &new.enum_type);
+    }
+
+    fn check_enum(&mut self, path: &str, old: &EnumDescriptorProto, new: &EnumDescriptorProto) {
+        // Enum reserved ranges are inclusive.
+        let enum_reserved = |e: &EnumDescriptorProto, number: i32, name: &str| {
+            e.reserved_range
+                .iter()
+                .any(|r| r.get_start() <= number && number <= r.get_end())
+                || e.reserved_name.iter().any(|n| n == name)
+        };
+
+        for old_value in &old.value {
+            let value_path = concat(path, old_value.get_name());
+            if new
+                .value
+                .iter()
+                .any(|v| v.get_number() == old_value.get_number())
+            {
+                continue;
+            }
+            match new
+                .value
+                .iter()
+                .find(|v| v.get_name() == old_value.get_name())
+            {
+                Some(v) => self.add(
+                    value_path,
+                    BreakingChangeKind::EnumValueNumberChanged {
+                        old: old_value.get_number(),
+                        new: v.get_number(),
+                    },
+                ),
+                None if enum_reserved(new, old_value.get_number(), old_value.get_name()) => {}
+                None => self.add(
+                    value_path,
+                    BreakingChangeKind::EnumValueRemoved {
+                        number: old_value.get_number(),
+                    },
+                ),
+            }
+        }
+
+        for new_value in &new.value {
+            if enum_reserved(old, new_value.get_number(), new_value.get_name()) {
+                self.add(
+                    concat(path, new_value.get_name()),
+                    BreakingChangeKind::ReservedEnumValueUsed {
+                        number: new_value.get_number(),
+                    },
+                );
+            }
+        }
+    }
+
+    fn check_service(
+        &mut self,
+        path: &str,
+        old: &ServiceDescriptorProto,
+        new: &ServiceDescriptorProto,
+    ) {
+        for old_method in &old.method {
+            let method_path = concat(path, old_method.get_name());
+            let new_method = match new
+                .method
+                .iter()
+                .find(|m| m.get_name() == old_method.get_name())
+            {
+                Some(m) => m,
+                None => {
+                    self.add(method_path, BreakingChangeKind::MethodRemoved);
+                    continue;
+                }
+            };
+            if old_method.get_input_type() != new_method.get_input_type()
+                || old_method.get_output_type() != new_method.get_output_type()
+            {
+                self.add(method_path.clone(), BreakingChangeKind::MethodTypeChanged);
+            }
+            if old_method.get_client_streaming() != new_method.get_client_streaming()
+                || old_method.get_server_streaming() != new_method.get_server_streaming()
+            {
+                self.add(method_path, BreakingChangeKind::MethodStreamingChanged);
+            }
+        }
+    }
+}
+
+/// Find backward incompatible changes between old and new versions of files.
+///
+/// Files are matched by name. Files present only in the new version are ignored.
+pub fn check(old: &[FileDescriptorProto], new: &[FileDescriptorProto]) -> Vec<BreakingChange> {
+    let mut changes = Vec::new();
+    for old_file in old {
+        let mut checker = Checker {
+            file: old_file.get_name(),
+            changes: Vec::new(),
+        };
+        match new.iter().find(|f| f.get_name() == old_file.get_name()) {
+            Some(new_file) => checker.check_file(old_file, new_file),
+            None => checker.add(String::new(), BreakingChangeKind::FileRemoved),
+        }
+        changes.extend(checker.changes);
+    }
+    changes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use protobuf::descriptor::descriptor_proto::ReservedRange;
+    use protobuf::descriptor::EnumValueDescriptorProto;
+
+    fn field(name: &str, number: i32, t: Type) -> FieldDescriptorProto {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name(name.to_owned());
+        field.set_number(number);
+        field.set_label(Label::LABEL_OPTIONAL);
+        field.set_field_type(t);
+        field
+    }
+
+    fn file(fields: Vec<FieldDescriptorProto>) -> FileDescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field = fields;
+        let mut file = FileDescriptorProto::new();
+        file.set_name("f.proto".to_owned());
+        file.set_package("p".to_owned());
+        file.message_type.push(message);
+        file
+    }
+
+    fn kinds(old: &FileDescriptorProto, new: &FileDescriptorProto) -> Vec<BreakingChangeKind> {
+        check(&[old.clone()], &[new.clone()])
+            .into_iter()
+            .map(|c| c.kind)
+            .collect()
+    }
+
+    #[test]
+    fn no_changes() {
+        let f = file(vec![field("a", 1, Type::TYPE_INT32)]);
+        assert_eq!(Vec::<BreakingChange>::new(), check(&[f.clone()], &[f]));
+    }
+
+    #[test]
+    fn field_added() {
+        let old = file(vec![field("a", 1, Type::TYPE_INT32)]);
+        let new = file(vec![
+            field("a", 1, Type::TYPE_INT32),
+            field("b", 2, Type::TYPE_STRING),
+        ]);
+        assert_eq!(Vec::<BreakingChangeKind>::new(), kinds(&old, &new));
+    }
+
+    #[test]
+    fn field_removed() {
+        let old = file(vec![field("a", 1, Type::TYPE_INT32)]);
+        let mut new = file(Vec::new());
+        let changes = check(&[old.clone()], &[new.clone()]);
+        assert_eq!(
+            "f.proto: p.M.a: field 1 removed without reserving it",
+            changes[0].to_string()
+        );
+
+        let mut range = ReservedRange::new();
+        range.set_start(1);
+        range.set_end(2);
+        new.message_type[0].reserved_range.push(range);
+        assert_eq!(Vec::<BreakingChangeKind>::new(), kinds(&old, &new));
+    }
+
+    #[test]
+    fn field_changed() {
+        let old = file(vec![
+            field("a", 1, Type::TYPE_INT32),
+            field("b", 2, Type::TYPE_INT32),
+            field("c", 3, Type::TYPE_INT32),
+        ]);
+        let mut repeated = field("c", 3, Type::TYPE_INT32);
+        repeated.set_label(Label::LABEL_REPEATED);
+        let new = file(vec![
+            field("a", 1, Type::TYPE_STRING),
+            field("b", 4, Type::TYPE_INT32),
+            repeated,
+        ]);
+        assert_eq!(
+            vec![
+                BreakingChangeKind::FieldTypeChanged {
+                    old: "int32".to_owned(),
+                    new: "string".to_owned(),
+                },
+                BreakingChangeKind::FieldNumberChanged { old: 2, new: 4 },
+                BreakingChangeKind::FieldCardinalityChanged {
+                    old: Label::LABEL_OPTIONAL,
+                    new: Label::LABEL_REPEATED,
+                },
+            ],
+            kinds(&old, &new)
+        );
+    }
+
+    #[test]
+    fn reserved_field_used() {
+        let mut old = file(Vec::new());
+        old.message_type[0].reserved_name.push("a".to_owned());
+        let new = file(vec![field("a", 1, Type::TYPE_INT32)]);
+        assert_eq!(
+            vec![BreakingChangeKind::ReservedFieldUsed { number: 1 }],
+            kinds(&old, &new)
+        );
+    }
+
+    #[test]
+    fn enum_value_removed() {
+        let value = |name: &str, number| {
+            let mut value = EnumValueDescriptorProto::new();
+            value.set_name(name.to_owned());
+            value.set_number(number);
+            value
+        };
+        let mut e = EnumDescriptorProto::new();
+        e.set_name("E".to_owned());
+        e.value = vec![value("A", 0), value("B", 1)];
+
+        let mut old = file(Vec::new());
+        old.enum_type.push(e.clone());
+        let mut new = file(Vec::new());
+        e.value.pop();
+        new.enum_type.push(e);
+        assert_eq!(
+            vec![BreakingChangeKind::EnumValueRemoved { number: 1 }],
+            kinds(&old, &new)
+        );
+    }
+
+    #[test]
+    fn message_and_file_removed() {
+        let old = file(Vec::new());
+        let mut new = old.clone();
+        new.message_type.clear();
+        assert_eq!(vec![BreakingChangeKind::MessageRemoved], kinds(&old, &new));
+        assert_eq!(
+            vec![BreakingChangeKind::FileRemoved],
+            check(&[old], &[])
+                .into_iter()
+                .map(|c| c.kind)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -37,6 +37,7 @@ impl<'a> CodeWriter<'a> {
     pub fn write_generated(&mut self) {
         self.write_line("// This file is generated. Do not edit");
         self.write_generated_common();
+        self.write_default_file_attributes();
     }
 
     pub fn write_generated_by(&mut self, pkg: &str, version: &str, parser: &str) {
@@ -55,7 +56,10 @@ impl<'a> CodeWriter<'a> {
     fn write_generated_common(&mut self) {
         // https://secure.phabricator.com/T784
         self.write_line("// @generated");
+    }
 
+    /// Lint and rustfmt attributes written to each generated file.
+    pub fn write_default_file_attributes(&mut self) {
         self.write_line("");
         self.comment("https://github.com/rust-lang/rust-clippy/issues/702");
         self.write_line("#![allow(unknown_lints)]");
@@ -19,7 +19,7 @@ pub struct GenResult {
 
 pub fn plugin_main<F>(gen: F)
 where
-    F: Fn(&GenRequest) -> Vec<GenResult>,
+    F: Fn(&GenRequest) -> Result<Vec<GenResult>, String>,
 {
     let req = CodeGeneratorRequest::parse_from_reader(&mut stdin()).unwrap();
     let result = gen(&GenRequest {
@@ -32,14 +32,20 @@ where
         parameter: req.get_parameter(),
     });
     let mut resp = CodeGeneratorResponse::new();
-    resp.file = result
-        .iter()
-        .map(|file| {
-            let mut r = code_generator_response::File::new();
-            r.set_name(file.name.to_string());
-            r.set_content(str::from_utf8(file.content.as_ref()).unwrap().to_string());
-            r
-        })
-        .collect();
+    match result {
+        Ok(result) => {
+            resp.file = result
+                .iter()
+                .map(|file| {
+                    let mut r = code_generator_response::File::new();
+                    r.set_name(file.name.to_string());
+                    r.set_content(str::from_utf8(file.content.as_ref()).unwrap().to_string());
+                    r
+                })
+                .collect();
+        }
+        // protoc prints the error and fails
+        Err(e) => resp.set_error(e),
+    }
     resp.write_to_writer(&mut stdout()).unwrap();
 }
@@ -1,3 +1,6 @@
+use std::error::Error;
+use std::fmt;
+
 use protobuf::descriptor::FieldOptions;
 use protobuf::descriptor::FileOptions;
 use protobuf::descriptor::MessageOptions;
@@ -16,6 +19,12 @@ pub struct Customize {
     pub generate_accessors: Option<bool>,
     /// When false, `get_` is not generated even if `syntax = "proto2"`
     pub generate_getter: Option<bool>,
+    /// When true all fields and oneofs are private, and accessors are generated
+    /// for all fields regardless of `expose_fields` and `generate_accessors`.
+    ///
+    /// Messages then cannot be constructed with struct literals outside
+    /// of the generated module, only with `new()` and setters.
+    pub private_fields: Option<bool>,
     /// Use `bytes::Bytes` for `bytes` fields
     pub carllerche_bytes_for_bytes: Option<bool>,
     /// Use `bytes::Bytes` for `string` fields
@@ -24,17 +33,90 @@ pub struct Customize {
     pub serde_derive: Option<bool>,
     /// When `serde_derive` is set, serde annotations will be guarded with `#[cfg(cfg, ...)]`.
     pub serde_derive_cfg: Option<String>,
-    /// Enable lite runtime
+    /// Enable lite runtime.
+    ///
+    /// Lite runtime messages derive `Debug` instead of printing text format.
     pub lite_runtime: Option<bool>,
     /// Generate `mod.rs` in the output directory.
     ///
     /// This option allows inclusion of generated files from cargo output directory.
     ///
+    /// `mod.rs` also contains `registered_types()` function listing
+    /// messages and enums of all generated files.
+    ///
     /// This option will likely be on by default in rust-protobuf version 3.
     pub gen_mod_rs: Option<bool>,
+    /// When `gen_mod_rs` is set, guard modules of each package in `mod.rs`
+    /// with `#[cfg(feature = "foo_bar")]` for package `foo.bar`,
+    /// and generate `features.toml` with the list of these features.
+    ///
+    /// Dependent crates then compile only the packages they enable.
+    pub gen_mod_rs_features: Option<bool>,
     /// Used internally to generate protos bundled in protobuf crate
     /// like `descriptor.proto`
     pub inside_protobuf: Option<bool>,
+    /// Store repeated fields in `protobuf::SmallVec` with given inline capacity
+    /// instead of `Vec`. Zero means `Vec`.
+    pub small_vec_for_repeated: Option<u32>,
+    /// Use `protobuf::InternedString` for repeated and map `string` fields.
+    pub interned_string_for_string: Option<bool>,
+    /// Use `Option<Box<M>>` instead of `MessageField<M>` for singular message fields.
+    pub singular_field_option_box: Option<bool>,
+    /// Use `Option<M>` instead of `MessageField<M>` for singular message fields.
+    /// Cannot be used with recursive messages.
+    pub singular_field_option: Option<bool>,
+    /// Use `Option<Arc<M>>` instead of `MessageField<M>` for singular message fields.
+    /// Large read-only submessages can be shared by many messages instead of cloned.
+    pub singular_field_option_arc: Option<bool>,
+    /// Derive `Hash`, `Eq`, `PartialOrd` and `Ord` for messages.
+    ///
+    /// Codegen fails if message cannot derive these traits: when it has
+    /// (possibly transitively) `float`, `double` or map fields,
+    /// or declares extension ranges.
+    /// Message types of fields must be generated with this option too.
+    pub derive_hash_eq_ord: Option<bool>,
+    /// When `derive_hash_eq_ord` is set, silently skip these derives
+    /// for messages which cannot have them instead of failing.
+    pub derive_hash_eq_ord_skip_ineligible: Option<bool>,
+    /// Implement `TryFrom<&[u8]>` for messages (parse) and
+    /// `TryFrom<M>` and `TryFrom<&M>` for `Vec<u8>` (serialize).
+    pub impl_try_from_bytes: Option<bool>,
+    /// Implement `Drop` for messages with `protobuf::rt::drop_iterative`,
+    /// so dropping deeply nested messages does not overflow the stack.
+    ///
+    /// Messages implementing `Drop` cannot be constructed with
+    /// struct update syntax (`..Default::default()`).
+    pub iterative_drop: Option<bool>,
+    /// Generate `FooView<'a>` types which read fields of serialized
+    /// messages on demand without parsing and copying.
+    pub generate_view: Option<bool>,
+    /// Reference types of imported `google/protobuf/*.proto` files
+    /// as `protobuf::well_known_types`, which is the default.
+    ///
+    /// When false, these types are referenced in generated modules of these files,
+    /// like types of other imported files, so these files must be generated too.
+    pub protobuf_well_known_types: Option<bool>,
+    /// Text written as is at the top of each generated `.rs` file,
+    /// like license comment or additional `#![allow(...)]` attributes.
+    ///
+    /// This option cannot be specified in `protoc` parameter.
+    pub file_header: Option<String>,
+    /// When false, default `#![allow(...)]` and `#![rustfmt::skip]` attributes
+    /// are not written to generated files, so they can be replaced with `file_header`.
+    pub default_file_attributes: Option<bool>,
+    /// Skip unknown fields when parsing instead of storing them in messages,
+    /// trading forward compatibility for parsing speed and memory.
+    pub discard_unknown_fields: Option<bool>,
+    /// Generate `has_x`, `set_x`, `clear_x` and `get_x_opt` accessors for all
+    /// singular fields, so proto2 and proto3 messages have the same accessors.
+    ///
+    /// Proto3 fields without presence are considered set when not equal to default.
+    pub uniform_accessors: Option<bool>,
+    /// When false, [`gen_and_write`](crate::gen_and_write) always generates
+    /// and writes all files and does not write a stamp file to the output directory.
+    ///
+    /// This option cannot be specified in `protoc` parameter.
+    pub incremental: Option<bool>,
 
     // When adding more options please keep in sync with `parse_from_parameter` below.
     /// Make sure `Customize` is always used with `..Default::default()`
@@ -46,9 +128,25 @@ pub struct Customize {
 pub enum CustomizeParseParameterError {
     EqNotFound,
     CannotParseBool,
+    CannotParseInt,
     UnknownOptionName(String),
 }
 
+impl fmt::Display for CustomizeParseParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CustomizeParseParameterError::EqNotFound => write!(f, "`=` not found in option"),
+            CustomizeParseParameterError::CannotParseBool => write!(f, "cannot parse bool"),
+            CustomizeParseParameterError::CannotParseInt => write!(f, "cannot parse int"),
+            CustomizeParseParameterError::UnknownOptionName(n) => {
+                write!(f, "unknown option name: {}", n)
+            }
+        }
+    }
+}
+
+impl Error for CustomizeParseParameterError {}
+
 pub type CustomizeParseParameterResult<T> = Result<T, CustomizeParseParameterError>;
 
 impl Customize {
@@ -66,6 +164,9 @@ impl Customize {
         if let Some(v) = that.generate_getter {
             self.generate_getter = Some(v);
         }
+        if let Some(v) = that.private_fields {
+            self.private_fields = Some(v);
+        }
         if let Some(v) = that.carllerche_bytes_for_bytes {
             self.carllerche_bytes_for_bytes = Some(v);
         }
@@ -84,9 +185,60 @@ impl Customize {
         if let Some(v) = that.gen_mod_rs {
             self.gen_mod_rs = Some(v);
         }
+        if let Some(v) = that.gen_mod_rs_features {
+            self.gen_mod_rs_features = Some(v);
+        }
         if let Some(v) = that.inside_protobuf {
             self.inside_protobuf = Some(v);
         }
+        if let Some(v) = that.small_vec_for_repeated {
+            self.small_vec_for_repeated = Some(v);
+        }
+        if let Some(v) = that.interned_string_for_string {
+            self.interned_string_for_string = Some(v);
+        }
+        if let Some(v) = that.singular_field_option_box {
+            self.singular_field_option_box = Some(v);
+        }
+        if let Some(v) = that.singular_field_option {
+            self.singular_field_option = Some(v);
+        }
+        if let Some(v) = that.singular_field_option_arc {
+            self.singular_field_option_arc = Some(v);
+        }
+        if let Some(v) = that.derive_hash_eq_ord {
+            self.derive_hash_eq_ord = Some(v);
+        }
+        if let Some(v) = that.derive_hash_eq_ord_skip_ineligible {
+            self.derive_hash_eq_ord_skip_ineligible = Some(v);
+        }
+        if let Some(v) = that.impl_try_from_bytes {
+            self.impl_try_from_bytes = Some(v);
+        }
+        if let Some(v) = that.iterative_drop {
+            self.iterative_drop = Some(v);
+        }
+        if let Some(v) = that.generate_view {
+            self.generate_view = Some(v);
+        }
+        if let Some(v) = that.protobuf_well_known_types {
+            self.protobuf_well_known_types = Some(v);
+        }
+        if let Some(ref v) = that.file_header {
+            self.file_header = Some(v.clone());
+        }
+        if let Some(v) = that.default_file_attributes {
+            self.default_file_attributes = Some(v);
+        }
+        if let Some(v) = that.discard_unknown_fields {
+            self.discard_unknown_fields = Some(v);
+        }
+        if let Some(v) = that.uniform_accessors {
+            self.uniform_accessors = Some(v);
+        }
+        if let Some(v) = that.incremental {
+            self.incremental = Some(v);
+        }
     }
 
     /// Update unset fields of self with fields from other customize
@@ -97,21 +249,43 @@ impl Customize {
     }
 
     /// Parse customize options from a string passed via protoc flag.
+    ///
+    /// Options are separated by commas or whitespace, like in
+    /// `protoc --rust_out=serde_derive=true,gen_mod_rs=true:src/gen`.
+    /// Option names are `Customize` field names, and also `serde`
+    /// for `serde_derive` and `bytes` for `carllerche_bytes_for_bytes`.
+    /// Boolean option without value like `expose_fields` means `true`.
     pub fn parse_from_parameter(parameter: &str) -> CustomizeParseParameterResult<Customize> {
         fn parse_bool(v: &str) -> CustomizeParseParameterResult<bool> {
             v.parse()
                 .map_err(|_| CustomizeParseParameterError::CannotParseBool)
         }
 
+        fn parse_u32(v: &str) -> CustomizeParseParameterResult<u32> {
+            v.parse()
+                .map_err(|_| CustomizeParseParameterError::CannotParseInt)
+        }
+
         let mut r = Customize::default();
-        for nv in parameter.split_whitespace() {
-            let eq = match nv.find('=') {
-                Some(eq) => eq,
-                None => return Err(CustomizeParseParameterError::EqNotFound),
+        for nv in parameter
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|nv| !nv.is_empty())
+        {
+            let (n, v) = match nv.find('=') {
+                Some(eq) => (&nv[..eq], &nv[eq + 1..]),
+                None => match nv {
+                    "serde_derive_cfg" | "small_vec_for_repeated" => {
+                        return Err(CustomizeParseParameterError::EqNotFound)
+                    }
+                    _ => (nv, "true"),
+                },
             };
 
-            let n = &nv[..eq];
-            let v = &nv[eq + 1..];
+            let n = match n {
+                "serde" => "serde_derive",
+                "bytes" => "carllerche_bytes_for_bytes",
+                n => n,
+            };
 
             if n == "expose_oneof" {
                 r.expose_oneof = Some(parse_bool(v)?);
@@ -121,6 +295,8 @@ impl Customize {
                 r.generate_accessors = Some(parse_bool(v)?);
             } else if n == "generate_getter" {
                 r.generate_getter = Some(parse_bool(v)?);
+            } else if n == "private_fields" {
+                r.private_fields = Some(parse_bool(v)?);
             } else if n == "carllerche_bytes_for_bytes" {
                 r.carllerche_bytes_for_bytes = Some(parse_bool(v)?);
             } else if n == "carllerche_bytes_for_string" {
@@ -133,8 +309,38 @@ impl Customize {
                 r.lite_runtime = Some(parse_bool(v)?);
             } else if n == "gen_mod_rs" {
                 r.gen_mod_rs = Some(parse_bool(v)?);
+            } else if n == "gen_mod_rs_features" {
+                r.gen_mod_rs_features = Some(parse_bool(v)?);
             } else if n == "inside_protobuf" {
                 r.inside_protobuf = Some(parse_bool(v)?);
+            } else if n == "small_vec_for_repeated" {
+                r.small_vec_for_repeated = Some(parse_u32(v)?);
+            } else if n == "interned_string_for_string" {
+                r.interned_string_for_string = Some(parse_bool(v)?);
+            } else if n == "singular_field_option_box" {
+                r.singular_field_option_box = Some(parse_bool(v)?);
+            } else if n == "singular_field_option" {
+                r.singular_field_option = Some(parse_bool(v)?);
+            } else if n == "singular_field_option_arc" {
+                r.singular_field_option_arc = Some(parse_bool(v)?);
+            } else if n == "derive_hash_eq_ord" {
+                r.derive_hash_eq_ord = Some(parse_bool(v)?);
+            } else if n == "derive_hash_eq_ord_skip_ineligible" {
+                r.derive_hash_eq_ord_skip_ineligible = Some(parse_bool(v)?);
+            } else if n == "impl_try_from_bytes" {
+                r.impl_try_from_bytes = Some(parse_bool(v)?);
+            } else if n == "iterative_drop" {
+                r.iterative_drop = Some(parse_bool(v)?);
+            } else if n == "generate_view" {
+                r.generate_view = Some(parse_bool(v)?);
+            } else if n == "protobuf_well_known_types" {
+                r.protobuf_well_known_types = Some(parse_bool(v)?);
+            } else if n == "default_file_attributes" {
+                r.default_file_attributes = Some(parse_bool(v)?);
+            } else if n == "discard_unknown_fields" {
+                r.discard_unknown_fields = Some(parse_bool(v)?);
+            } else if n == "uniform_accessors" {
+                r.uniform_accessors = Some(parse_bool(v)?);
             } else {
                 return Err(CustomizeParseParameterError::UnknownOptionName(
                     n.to_owned(),
@@ -150,25 +356,62 @@ pub fn customize_from_rustproto_for_message(source: &MessageOptions) -> Customiz
     let expose_fields = rustproto::exts::expose_fields.get(source);
     let generate_accessors = rustproto::exts::generate_accessors.get(source);
     let generate_getter = rustproto::exts::generate_getter.get(source);
+    let private_fields = rustproto::exts::private_fields.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes.get(source);
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string.get(source);
     let serde_derive = rustproto::exts::serde_derive.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg.get(source);
     let lite_runtime = None;
     let gen_mod_rs = None;
+    let gen_mod_rs_features = None;
     let inside_protobuf = None;
+    let small_vec_for_repeated = rustproto::exts::small_vec_for_repeated.get(source);
+    let interned_string_for_string = rustproto::exts::interned_string_for_string.get(source);
+    let singular_field_option_box = rustproto::exts::singular_field_option_box.get(source);
+    let singular_field_option = rustproto::exts::singular_field_option.get(source);
+    let singular_field_option_arc = rustproto::exts::singular_field_option_arc.get(source);
+    let derive_hash_eq_ord = rustproto::exts::derive_hash_eq_ord.get(source);
+    let derive_hash_eq_ord_skip_ineligible =
+        rustproto::exts::derive_hash_eq_ord_skip_ineligible.get(source);
+    let impl_try_from_bytes = rustproto::exts::impl_try_from_bytes.get(source);
+    let iterative_drop = rustproto::exts::iterative_drop.get(source);
+    let generate_view = rustproto::exts::generate_view.get(source);
+    let protobuf_well_known_types = None;
+    let file_header = None;
+    let default_file_attributes = None;
+    let discard_unknown_fields = rustproto::exts::discard_unknown_fields.get(source);
+    let uniform_accessors = rustproto::exts::uniform_accessors.get(source);
+    let incremental = None;
     Customize {
         expose_oneof,
         expose_fields,
         generate_accessors,
         generate_getter,
+        private_fields,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
         serde_derive,
         serde_derive_cfg,
         lite_runtime,
         gen_mod_rs,
+        gen_mod_rs_features,
         inside_protobuf,
+        small_vec_for_repeated,
+        interned_string_for_string,
+        singular_field_option_box,
+        singular_field_option,
+        singular_field_option_arc,
+        derive_hash_eq_ord,
+        derive_hash_eq_ord_skip_ineligible,
+        impl_try_from_bytes,
+        iterative_drop,
+        generate_view,
+        protobuf_well_known_types,
+        file_header,
+        default_file_attributes,
+        discard_unknown_fields,
+        uniform_accessors,
+        incremental,
         _future_options: (),
     }
 }
@@ -178,6 +421,7 @@ pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
     let expose_fields = rustproto::exts::expose_fields_field.get(source);
     let generate_accessors = rustproto::exts::generate_accessors_field.get(source);
     let generate_getter = rustproto::exts::generate_getter_field.get(source);
+    let private_fields = rustproto::exts::private_fields_field.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes_field.get(source);
     let carllerche_bytes_for_string =
         rustproto::exts::carllerche_bytes_for_string_field.get(source);
@@ -185,19 +429,54 @@ pub fn customize_from_rustproto_for_field(source: &FieldOptions) -> Customize {
     let serde_derive_cfg = None;
     let lite_runtime = None;
     let gen_mod_rs = None;
+    let gen_mod_rs_features = None;
     let inside_protobuf = None;
+    let small_vec_for_repeated = rustproto::exts::small_vec_for_repeated_field.get(source);
+    let interned_string_for_string = rustproto::exts::interned_string_for_string_field.get(source);
+    let singular_field_option_box = rustproto::exts::singular_field_option_box_field.get(source);
+    let singular_field_option = rustproto::exts::singular_field_option_field.get(source);
+    let singular_field_option_arc = rustproto::exts::singular_field_option_arc_field.get(source);
+    let derive_hash_eq_ord = None;
+    let derive_hash_eq_ord_skip_ineligible = None;
+    let impl_try_from_bytes = None;
+    let iterative_drop = None;
+    let generate_view = None;
+    let protobuf_well_known_types = None;
+    let file_header = None;
+    let default_file_attributes = None;
+    let discard_unknown_fields = None;
+    let uniform_accessors = rustproto::exts::uniform_accessors_field.get(source);
+    let incremental = None;
     Customize {
         expose_oneof,
         expose_fields,
         generate_accessors,
         generate_getter,
+        private_fields,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
         serde_derive,
         serde_derive_cfg,
         lite_runtime,
         gen_mod_rs,
+        gen_mod_rs_features,
         inside_protobuf,
+        small_vec_for_repeated,
+        interned_string_for_string,
+        singular_field_option_box,
+        singular_field_option,
+        singular_field_option_arc,
+        derive_hash_eq_ord,
+        derive_hash_eq_ord_skip_ineligible,
+        impl_try_from_bytes,
+        iterative_drop,
+        generate_view,
+        protobuf_well_known_types,
+        file_header,
+        default_file_attributes,
+        discard_unknown_fields,
+        uniform_accessors,
+        incremental,
         _future_options: (),
     }
 }
@@ -207,18 +486,38 @@ pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
     let expose_fields = rustproto::exts::expose_fields_all.get(source);
     let generate_accessors = rustproto::exts::generate_accessors_all.get(source);
     let generate_getter = rustproto::exts::generate_getter_all.get(source);
+    let private_fields = rustproto::exts::private_fields_all.get(source);
     let carllerche_bytes_for_bytes = rustproto::exts::carllerche_bytes_for_bytes_all.get(source);
     let carllerche_bytes_for_string = rustproto::exts::carllerche_bytes_for_string_all.get(source);
     let serde_derive = rustproto::exts::serde_derive_all.get(source);
     let serde_derive_cfg = rustproto::exts::serde_derive_cfg_all.get(source);
     let lite_runtime = rustproto::exts::lite_runtime_all.get(source);
     let gen_mod_rs = None;
+    let gen_mod_rs_features = None;
     let inside_protobuf = None;
+    let small_vec_for_repeated = rustproto::exts::small_vec_for_repeated_all.get(source);
+    let interned_string_for_string = rustproto::exts::interned_string_for_string_all.get(source);
+    let singular_field_option_box = rustproto::exts::singular_field_option_box_all.get(source);
+    let singular_field_option = rustproto::exts::singular_field_option_all.get(source);
+    let singular_field_option_arc = rustproto::exts::singular_field_option_arc_all.get(source);
+    let derive_hash_eq_ord = rustproto::exts::derive_hash_eq_ord_all.get(source);
+    let derive_hash_eq_ord_skip_ineligible =
+        rustproto::exts::derive_hash_eq_ord_skip_ineligible_all.get(source);
+    let impl_try_from_bytes = rustproto::exts::impl_try_from_bytes_all.get(source);
+    let iterative_drop = rustproto::exts::iterative_drop_all.get(source);
+    let generate_view = rustproto::exts::generate_view_all.get(source);
+    let protobuf_well_known_types = None;
+    let file_header = None;
+    let default_file_attributes = None;
+    let discard_unknown_fields = rustproto::exts::discard_unknown_fields_all.get(source);
+    let uniform_accessors = rustproto::exts::uniform_accessors_all.get(source);
+    let incremental = None;
     Customize {
         expose_oneof,
         expose_fields,
         generate_accessors,
         generate_getter,
+        private_fields,
         carllerche_bytes_for_bytes,
         carllerche_bytes_for_string,
         serde_derive,
@@ -226,6 +525,62 @@ pub fn customize_from_rustproto_for_file(source: &FileOptions) -> Customize {
         lite_runtime,
         inside_protobuf,
         gen_mod_rs,
+        gen_mod_rs_features,
+        small_vec_for_repeated,
+        interned_string_for_string,
+        singular_field_option_box,
+        singular_field_option,
+        singular_field_option_arc,
+        derive_hash_eq_ord,
+        derive_hash_eq_ord_skip_ineligible,
+        impl_try_from_bytes,
+        iterative_drop,
+        generate_view,
+        protobuf_well_known_types,
+        file_header,
+        default_file_attributes,
+        discard_unknown_fields,
+        uniform_accessors,
+        incremental,
         _future_options: (),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_from_parameter() {
+        let c = Customize::parse_from_parameter(
+            "serde=true,bytes=true gen_mod_rs expose_fields=false,small_vec_for_repeated=4",
+        )
+        .unwrap();
+        assert_eq!(Some(true), c.serde_derive);
+        assert_eq!(Some(true), c.carllerche_bytes_for_bytes);
+        assert_eq!(Some(true), c.gen_mod_rs);
+        assert_eq!(Some(false), c.expose_fields);
+        assert_eq!(Some(4), c.small_vec_for_repeated);
+        assert_eq!(None, c.lite_runtime);
+
+        let c = Customize::parse_from_parameter("serde_derive_cfg=serde,").unwrap();
+        assert_eq!(Some("serde"), c.serde_derive_cfg.as_deref());
+        assert!(Customize::parse_from_parameter("").is_ok());
+    }
+
+    #[test]
+    fn parse_from_parameter_errors() {
+        assert!(matches!(
+            Customize::parse_from_parameter("foo=true"),
+            Err(CustomizeParseParameterError::UnknownOptionName(n)) if n == "foo"
+        ));
+        assert!(matches!(
+            Customize::parse_from_parameter("serde=yes"),
+            Err(CustomizeParseParameterError::CannotParseBool)
+        ));
+        assert!(matches!(
+            Customize::parse_from_parameter("small_vec_for_repeated"),
+            Err(CustomizeParseParameterError::EqNotFound)
+        ));
+    }
+}
@@ -0,0 +1,751 @@
+//! Structural diff of two versions of `.proto` files.
+//!
+//! Unlike [`breaking`](crate::breaking), this module reports all changes,
+//! including compatible ones, and is meant for rendering changelogs.
+//!
+//! ```
+//! # use protobuf::descriptor::FileDescriptorSet;
+//! # fn f(old: &FileDescriptorSet, new: &FileDescriptorSet) {
+//! let diff = protobuf_codegen::descriptor_diff::diff(&old.file, &new.file);
+//! println!("{}", diff.summary());
+//! for change in &diff.changes {
+//!     println!("{}", change);
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::EnumDescriptorProto;
+use protobuf::descriptor::FieldDescriptorProto;
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::descriptor::ServiceDescriptorProto;
+use protobuf::text_format;
+use protobuf::Message;
+
+/// Kind of `.proto` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ElementKind {
+    /// `.proto` file.
+    File,
+    /// Message.
+    Message,
+    /// Message field.
+    Field,
+    /// Oneof.
+    Oneof,
+    /// Enum.
+    Enum,
+    /// Enum value.
+    EnumValue,
+    /// Service.
+    Service,
+    /// Service method.
+    Method,
+}
+
+impl fmt::Display for ElementKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ElementKind::File => "file",
+            ElementKind::Message => "message",
+            ElementKind::Field => "field",
+            ElementKind::Oneof => "oneof",
+            ElementKind::Enum => "enum",
+            ElementKind::EnumValue => "enum value",
+            ElementKind::Service => "service",
+            ElementKind::Method => "method",
+        })
+    }
+}
+
+/// What happened to the element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Element exists only in the new version.
+    Added,
+    /// Element exists only in the old version.
+    Removed,
+    /// Element was renamed. Change path is the new name.
+    ///
+    /// Fields and enum values are matched by number, messages and enums
+    /// are considered renamed when they have the same fields or values.
+    Renamed {
+        /// Old fully qualified name.
+        old_path: String,
+    },
+    /// Property of the element changed.
+    Changed {
+        /// Property name, e. g. `type` or `options`.
+        property: &'static str,
+        /// Old value, options are printed in text format.
+        old: String,
+        /// New value.
+        new: String,
+    },
+}
+
+/// Single change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Kind of changed element.
+    pub element: ElementKind,
+    /// File name for files, fully qualified name for other elements.
+    pub path: String,
+    /// What has changed.
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ChangeKind::Added => write!(f, "added {} `{}`", self.element, self.path),
+            ChangeKind::Removed => write!(f, "removed {} `{}`", self.element, self.path),
+            ChangeKind::Renamed { old_path } => write!(
+                f,
+                "renamed {} `{}` to `{}`",
+                self.element, old_path, self.path
+            ),
+            ChangeKind::Changed { property, old, new } => write!(
+                f,
+                "changed {} of {} `{}` from `{}` to `{}`",
+                property, self.element, self.path, old, new
+            ),
+        }
+    }
+}
+
+/// Number of changes of each kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiffSummary {
+    /// Number of added elements.
+    pub added: usize,
+    /// Number of removed elements.
+    pub removed: usize,
+    /// Number of renamed elements.
+    pub renamed: usize,
+    /// Number of property changes.
+    pub changed: usize,
+}
+
+impl fmt::Display for DiffSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} removed, {} renamed, {} changed",
+            self.added, self.removed, self.renamed, self.changed
+        )
+    }
+}
+
+/// Diff of two versions of files.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DescriptorDiff {
+    /// Changes in the order of old version declarations,
+    /// followed by additions.
+    pub changes: Vec<Change>,
+}
+
+impl DescriptorDiff {
+    /// No changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Count changes.
+    pub fn summary(&self) -> DiffSummary {
+        let mut summary = DiffSummary::default();
+        for change in &self.changes {
+            match change.kind {
+                ChangeKind::Added => summary.added += 1,
+                ChangeKind::Removed => summary.removed += 1,
+                ChangeKind::Renamed { .. } => summary.renamed += 1,
+                ChangeKind::Changed { .. } => summary.changed += 1,
+            }
+        }
+        summary
+    }
+
+    /// Changes of given element kind.
+    pub fn changes_of<'a>(&'a self, element: ElementKind) -> impl Iterator<Item = &'a Change> + 'a {
+        self.changes.iter().filter(move |c| c.element == element)
+    }
+}
+
+fn concat(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn options_text<M: Message>(options: &protobuf::MessageField<M>) -> String {
+    options
+        .as_ref()
+        .map(|o| text_format::print_to_string(o))
+        .unwrap_or_default()
+}
+
+fn message_shape(m: &DescriptorProto) -> Option<Vec<(i32, String)>> {
+    let mut shape: Vec<(i32, String)> = m
+        .field
+        .iter()
+        .map(|f| (f.get_number(), f.get_name().to_owned()))
+        .collect();
+    shape.sort();
+    Some(shape)
+}
+
+fn enum_shape(e: &EnumDescriptorProto) -> Option<Vec<(i32, String)>> {
+    let mut shape: Vec<(i32, String)> = e
+        .value
+        .iter()
+        .map(|v| (v.get_number(), v.get_name().to_owned()))
+        .collect();
+    shape.sort();
+    Some(shape)
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn add(&mut self, element: ElementKind, path: String, kind: ChangeKind) {
+        self.changes.push(Change {
+            element,
+            path,
+            kind,
+        });
+    }
+
+    fn changed(
+        &mut self,
+        element: ElementKind,
+        path: &str,
+        property: &'static str,
+        old: String,
+        new: String,
+    ) {
+        if old != new {
+            self.add(
+                element,
+                path.to_owned(),
+                ChangeKind::Changed { property, old, new },
+            );
+        }
+    }
+
+    /// Match elements by name, then pair remaining with equal shape as renames.
+    ///
+    /// Elements without shape are never considered renamed.
+    fn diff_named<T, S: PartialEq>(
+        &mut self,
+        element: ElementKind,
+        scope: &str,
+        old: &[T],
+        new: &[T],
+        name: impl Fn(&T) -> &str,
+        shape: impl Fn(&T) -> Option<S>,
+        mut diff: impl FnMut(&mut Self, &str, &T, &T),
+    ) {
+        let mut new_matched = vec![false; new.len()];
+        let mut old_unmatched = Vec::new();
+        for o in old {
+            match new.iter().position(|n| name(n) == name(o)) {
+                Some(i) => {
+                    new_matched[i] = true;
+                    diff(self, &concat(scope, name(o)), o, &new[i]);
+                }
+                None => old_unmatched.push(o),
+            }
+        }
+        for o in old_unmatched {
+            let renamed = (0..new.len())
+                .find(|&i| !new_matched[i] && shape(o).is_some() && shape(&new[i]) == shape(o));
+            match renamed {
+                Some(i) => {
+                    new_matched[i] = true;
+                    let path = concat(scope, name(&new[i]));
+                    self.add(
+                        element,
+                        path.clone(),
+                        ChangeKind::Renamed {
+                            old_path: concat(scope, name(o)),
+                        },
+                    );
+                    diff(self, &path, o, &new[i]);
+                }
+                None => self.add(element, concat(scope, name(o)), ChangeKind::Removed),
+            }
+        }
+        for (i, n) in new.iter().enumerate() {
+            if !new_matched[i] {
+                self.add(element, concat(scope, name(n)), ChangeKind::Added);
+            }
+        }
+    }
+
+    fn diff_file(&mut self, old: &FileDescriptorProto, new: &FileDescriptorProto) {
+        let name = old.get_name();
+        self.changed(
+            ElementKind::File,
+            name,
+            "package",
+            old.get_package().to_owned(),
+            new.get_package().to_owned(),
+        );
+        self.changed(
+            ElementKind::File,
+            name,
+            "options",
+            options_text(&old.options),
+            options_text(&new.options),
+        );
+
+        let package = new.get_package();
+        self.diff_messages(package, &old.message_type, &new.message_type);
+        self.diff_enums(package, &old.enum_type, &new.enum_type);
+        self.diff_named(
+            ElementKind::Service,
+            package,
+            &old.service,
+            &new.service,
+            |s| s.get_name(),
+            |_| None::<()>,
+            Differ::diff_service,
+        );
+    }
+
+    fn diff_messages(&mut self, scope: &str, old: &[DescriptorProto], new: &[DescriptorProto]) {
+        self.diff_named(
+            ElementKind::Message,
+            scope,
+            old,
+            new,
+            |m| m.get_name(),
+            message_shape,
+            Differ::diff_message,
+        );
+    }
+
+    fn diff_enums(
+        &mut self,
+        scope: &str,
+        old: &[EnumDescriptorProto],
+        new: &[EnumDescriptorProto],
+    ) {
+        self.diff_named(
+            ElementKind::Enum,
+            scope,
+            old,
+            new,
+            |e| e.get_name(),
+            enum_shape,
+            Differ::diff_enum,
+        );
+    }
+
+    fn diff_message(&mut self, path: &str, old: &DescriptorProto, new: &DescriptorProto) {
+        self.changed(
+            ElementKind::Message,
+            path,
+            "options",
+            options_text(&old.options),
+            options_text(&new.options),
+        );
+
+        for old_field in &old.field {
+            match new
+                .field
+                .iter()
+                .find(|f| f.get_number() == old_field.get_number())
+            {
+                Some(new_field) => {
+                    let field_path = concat(path, new_field.get_name());
+                    if old_field.get_name() != new_field.get_name() {
+                        self.add(
+                            ElementKind::Field,
+                            field_path.clone(),
+                            ChangeKind::Renamed {
+                                old_path: concat(path, old_field.get_name()),
+                            },
+                        );
+                    }
+                    self.diff_field(&field_path, old, old_field, new, new_field);
+                }
+                None => self.add(
+                    ElementKind::Field,
+                    concat(path, old_field.get_name()),
+                    ChangeKind::Removed,
+                ),
+            }
+        }
+        for new_field in &new.field {
+            if !old
+                .field
+                .iter()
+                .any(|f| f.get_number() == new_field.get_number())
+            {
+                self.add(
+                    ElementKind::Field,
+                    concat(path, new_field.get_name()),
+                    ChangeKind::Added,
+                );
+            }
+        }
+
+        self.diff_named(
+            ElementKind::Oneof,
+            path,
+            &old.oneof_decl,
+            &new.oneof_decl,
+            |o| o.get_name(),
+            |_| None::<()>,
+            |d, path, o, n| {
+                d.changed(
+                    ElementKind::Oneof,
+                    path,
+                    "options",
+                    options_text(&o.options),
+                    options_text(&n.options),
+                )
+            },
+        );
+
+        self.diff_messages(path, &old.nested_type, &new.nested_type);
+        self.diff_enums(path, &old.enum_type, &new.enum_type);
+    }
+
+    fn diff_field(
+        &mut self,
+        path: &str,
+        old_message: &DescriptorProto,
+        old: &FieldDescriptorProto,
+        new_message: &DescriptorProto,
+        new: &FieldDescriptorProto,
+    ) {
+        let field_type = |f: &FieldDescriptorProto| {
+            if f.has_type_name() {
+                f.get_type_name().to_owned()
+            } else {
+                format!("{:?}", f.get_field_type())
+            }
+        };
+        let oneof = |m: &DescriptorProto, f: &FieldDescriptorProto| {
+            if f.has_oneof_index() {
+                m.oneof_decl[f.get_oneof_index() as usize]
+                    .get_name()
+                    .to_owned()
+            } else {
+                String::new()
+            }
+        };
+
+        self.changed(
+            ElementKind::Field,
+            path,
+            "type",
+            field_type(old),
+            field_type(new),
+        );
+        self.changed(
+            ElementKind::Field,
+            path,
+            "label",
+            format!("{:?}", old.get_label()),
+            format!("{:?}", new.get_label()),
+        );
+        self.changed(
+            ElementKind::Field,
+            path,
+            "default",
+            old.get_default_value().to_owned(),
+            new.get_default_value().to_owned(),
+        );
+        self.changed(
+            ElementKind::Field,
+            path,
+            "json_name",
+            old.get_json_name().to_owned(),
+            new.get_json_name().to_owned(),
+        );
+        self.changed(
+            ElementKind::Field,
+            path,
+            "oneof",
+            oneof(old_message, old),
+            oneof(new_message, new),
+        );
+        self.changed(
+            ElementKind::Field,
+            path,
+            "options",
+            options_text(&old.options),
+            options_text(&new.options),
+        );
+    }
+
+    fn diff_enum(&mut self, path: &str, old: &EnumDescriptorProto, new: &EnumDescriptorProto) {
+        self.changed(
+            ElementKind::Enum,
+            path,
+            "options",
+            options_text(&old.options),
+            options_text(&new.options),
+        );
+
+        for old_value in &old.value {
+            // Aliases share numbers, so match by name first.
+            let new_value = new
+                .value
+                .iter()
+                .find(|v| v.get_name() == old_value.get_name())
+                .or_else(|| {
+                    new.value.iter().find(|v| {
+                        v.get_number() == old_value.get_number()
+                            && !old.value.iter().any(|o| o.get_name() == v.get_name())
+                    })
+                });
+            match new_value {
+                Some(new_value) => {
+                    let value_path = concat(path, new_value.get_name());
+                    if old_value.get_name() != new_value.get_name() {
+                        self.add(
+                            ElementKind::EnumValue,
+                            value_path.clone(),
+                            ChangeKind::Renamed {
+                                old_path: concat(path, old_value.get_name()),
+                            },
+                        );
+                    }
+                    self.changed(
+                        ElementKind::EnumValue,
+                        &value_path,
+                        "number",
+                        old_value.get_number().to_string(),
+                        new_value.get_number().to_string(),
+                    );
+                    self.changed(
+                        ElementKind::EnumValue,
+                        &value_path,
+                        "options",
+                        options_text(&old_value.options),
+                        options_text(&new_value.options),
+                    );
+                }
+                None => self.add(
+                    ElementKind::EnumValue,
+                    concat(path, old_value.get_name()),
+                    ChangeKind::Removed,
+                ),
+            }
+        }
+        for new_value in &new.value {
+            let matched = old.value.iter().any(|o| {
+                o.get_name() == new_value.get_name()
+                    || (o.get_number() == new_value.get_number()
+                        && !new.value.iter().any(|n| n.get_name() == o.get_name()))
+            });
+            if !matched {
+                self.add(
+                    ElementKind::EnumValue,
+                    concat(path, new_value.get_name()),
+                    ChangeKind::Added,
+                );
+            }
+        }
+    }
+
+    fn diff_service(
+        &mut self,
+        path: &str,
+        old: &ServiceDescriptorProto,
+        new: &ServiceDescriptorProto,
+    ) {
+        self.changed(
+            ElementKind::Service,
+            path,
+            "options",
+            options_text(&old.options),
+            options_text(&new.options),
+        );
+        self.diff_named(
+            ElementKind::Method,
+            path,
+            &old.method,
+            &new.method,
+            |m| m.get_name(),
+            |_| None::<()>,
+            |d, path, o, n| {
+                let e = ElementKind::Method;
+                d.changed(
+                    e,
+                    path,
+                    "input_type",
+                    o.get_input_type().to_owned(),
+                    n.get_input_type().to_owned(),
+                );
+                d.changed(
+                    e,
+                    path,
+                    "output_type",
+                    o.get_output_type().to_owned(),
+                    n.get_output_type().to_owned(),
+                );
+                d.changed(
+                    e,
+                    path,
+                    "client_streaming",
+                    o.get_client_streaming().to_string(),
+                    n.get_client_streaming().to_string(),
+                );
+                d.changed(
+                    e,
+                    path,
+                    "server_streaming",
+                    o.get_server_streaming().to_string(),
+                    n.get_server_streaming().to_string(),
+                );
+                d.changed(
+                    e,
+                    path,
+                    "options",
+                    options_text(&o.options),
+                    options_text(&n.options),
+                );
+            },
+        );
+    }
+}
+
+/// Compute the diff between old and new versions of files.
+///
+/// Files are matched by name. Elements are identified by fully qualified names
+/// in the new version.
+pub fn diff(old: &[FileDescriptorProto], new: &[FileDescriptorProto]) -> DescriptorDiff {
+    let mut differ = Differ::default();
+    for old_file in old {
+        match new.iter().find(|f| f.get_name() == old_file.get_name()) {
+            Some(new_file) => differ.diff_file(old_file, new_file),
+            None => differ.add(
+                ElementKind::File,
+                old_file.get_name().to_owned(),
+                ChangeKind::Removed,
+            ),
+        }
+    }
+    for new_file in new {
+        if !old.iter().any(|f| f.get_name() == new_file.get_name()) {
+            differ.add(
+                ElementKind::File,
+                new_file.get_name().to_owned(),
+                ChangeKind::Added,
+            );
+        }
+    }
+    DescriptorDiff {
+        changes: differ.changes,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use protobuf::descriptor::field_descriptor_proto::Type;
+    use protobuf::descriptor::MessageOptions;
+
+    fn field(name: &str, number: i32) -> FieldDescriptorProto {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name(name.to_owned());
+        field.set_number(number);
+        field.set_field_type(Type::TYPE_INT32);
+        field
+    }
+
+    fn message(name: &str, fields: Vec<FieldDescriptorProto>) -> DescriptorProto {
+        let mut message = DescriptorProto::new();
+        message.set_name(name.to_owned());
+        message.field = fields;
+        message
+    }
+
+    fn file(messages: Vec<DescriptorProto>) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("f.proto".to_owned());
+        file.set_package("p".to_owned());
+        file.message_type = messages;
+        file
+    }
+
+    #[test]
+    fn empty() {
+        let f = file(vec![message("M", vec![field("a", 1)])]);
+        assert!(diff(&[f.clone()], &[f]).is_empty());
+    }
+
+    #[test]
+    fn fields() {
+        let old = file(vec![message("M", vec![field("a", 1), field("b", 2)])]);
+        let mut c = field("c", 2);
+        c.set_field_type(Type::TYPE_STRING);
+        let new = file(vec![message("M", vec![c, field("d", 3)])]);
+
+        let diff = diff(&[old], &[new]);
+        let changes: Vec<String> = diff.changes.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            vec![
+                "removed field `p.M.a`",
+                "renamed field `p.M.b` to `p.M.c`",
+                "changed type of field `p.M.c` from `TYPE_INT32` to `TYPE_STRING`",
+                "added field `p.M.d`",
+            ],
+            changes
+        );
+        assert_eq!(
+            DiffSummary {
+                added: 1,
+                removed: 1,
+                renamed: 1,
+                changed: 1,
+            },
+            diff.summary()
+        );
+    }
+
+    #[test]
+    fn messages() {
+        let old = file(vec![
+            message("A", vec![field("a", 1)]),
+            message("B", vec![field("b", 1)]),
+        ]);
+        let mut options = MessageOptions::new();
+        options.set_deprecated(true);
+        let mut c = message("C", vec![field("a", 1)]);
+        c.options = Some(options).into();
+        let new = file(vec![c]);
+
+        let diff = diff(&[old], &[new]);
+        assert_eq!(
+            vec![
+                ChangeKind::Renamed {
+                    old_path: "p.A".to_owned()
+                },
+                ChangeKind::Changed {
+                    property: "options",
+                    old: "".to_owned(),
+                    new: "deprecated: true".to_owned(),
+                },
+                ChangeKind::Removed,
+            ],
+            diff.changes
+                .iter()
+                .map(|c| c.kind.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(3, diff.changes_of(ElementKind::Message).count());
+    }
+}
@@ -37,6 +37,11 @@ impl<'a> EnumValueGen<'a> {
         self.value.proto.get_proto().get_number()
     }
 
+    // name of enum value in `.proto` file
+    fn proto_name(&self) -> &str {
+        self.value.proto.get_proto().get_name()
+    }
+
     // name of enum variant in generated rust code
     pub fn rust_name_inner(&self) -> RustIdent {
         self.value.rust_name()
@@ -115,8 +120,7 @@ impl<'a> EnumGen<'a> {
         let mut used = HashSet::new();
         let mut r = Vec::new();
         for p in self.enum_with_scope.values() {
-            // skipping non-unique enums
-            // TODO: should support it
+            // skipping aliases: number is converted to the first variant
             if !used.insert(p.proto.get_proto().get_number()) {
                 continue;
             }
@@ -138,6 +142,8 @@ impl<'a> EnumGen<'a> {
         w.write_line("");
         self.write_impl_default(w);
         w.write_line("");
+        self.write_impl_display(w);
+        w.write_line("");
         self.write_impl_value(w);
         w.write_line("");
         self.write_impl_self(w);
@@ -208,6 +214,40 @@ impl<'a> EnumGen<'a> {
         });
     }
 
+    fn write_fn_name(&self, w: &mut CodeWriter) {
+        w.def_fn("name(&self) -> &'static str", |w| {
+            w.match_expr("*self", |w| {
+                for value in self.values_all() {
+                    w.case_expr(
+                        &format!("{}", value.rust_name_outer()),
+                        &format!("\"{}\"", value.proto_name()),
+                    );
+                }
+            });
+        });
+    }
+
+    fn write_fn_from_str(&self, w: &mut CodeWriter) {
+        w.def_fn(
+            &format!(
+                "from_str(name: &str) -> ::std::option::Option<{}>",
+                self.type_name
+            ),
+            |w| {
+                w.match_expr("name", |w| {
+                    for value in self.values_all() {
+                        w.write_line(&format!(
+                            "\"{}\" => ::std::option::Option::Some({}),",
+                            value.proto_name(),
+                            value.rust_name_outer()
+                        ));
+                    }
+                    w.write_line(&format!("_ => {}", EXPR_NONE));
+                });
+            },
+        );
+    }
+
     fn write_impl_enum(&self, w: &mut CodeWriter) {
         let ref type_name = self.type_name;
         w.impl_for_block(
@@ -238,6 +278,12 @@ impl<'a> EnumGen<'a> {
                     },
                 );
 
+                w.write_line("");
+                self.write_fn_name(w);
+
+                w.write_line("");
+                self.write_fn_from_str(w);
+
                 w.write_line("");
                 w.def_fn(&format!("values() -> &'static [Self]"), |w| {
                     w.write_line(&format!("static values: &'static [{}] = &[", type_name));
@@ -347,6 +393,20 @@ impl<'a> EnumGen<'a> {
         });
     }
 
+    fn write_impl_display(&self, w: &mut CodeWriter) {
+        w.impl_for_block("::std::fmt::Display", &format!("{}", self.type_name), |w| {
+            w.def_fn(
+                "fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result",
+                |w| {
+                    w.write_line(&format!(
+                        "f.pad({}::ProtobufEnum::name(self))",
+                        protobuf_crate_path(&self.customize)
+                    ));
+                },
+            );
+        });
+    }
+
     fn write_impl_default(&self, w: &mut CodeWriter) {
         let first_value = &self.enum_with_scope.values()[0];
         if first_value.proto.get_proto().get_number() != 0 {
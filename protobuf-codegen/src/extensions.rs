@@ -16,6 +16,8 @@ struct ExtGen<'a> {
     file: &'a FileDescriptor,
     root_scope: &'a RootScope<'a>,
     field: &'a FieldDescriptorProto,
+    // module where `exts` constants are generated, relative to file
+    relative_mod: RustRelativePath,
     customize: Customize,
 }
 
@@ -23,15 +25,19 @@ impl<'a> ExtGen<'a> {
     fn extendee_rust_name(&self) -> RustIdentWithPath {
         type_name_to_rust_relative(
             &ProtobufAbsolutePath::from(self.field.get_extendee()),
-            &FileAndMod {
-                file: self.file.proto().get_name().to_owned(),
-                relative_mod: RustRelativePath::from("exts"),
-                customize: self.customize.clone(),
-            },
+            &self.file_and_mod(),
             self.root_scope,
         )
     }
 
+    fn file_and_mod(&self) -> FileAndMod {
+        FileAndMod {
+            file: self.file.proto().get_name().to_owned(),
+            relative_mod: self.relative_mod.clone(),
+            customize: self.customize.clone(),
+        }
+    }
+
     fn repeated(&self) -> bool {
         match self.field.get_label() {
             field_descriptor_proto::Label::LABEL_REPEATED => true,
@@ -46,11 +52,7 @@ impl<'a> ExtGen<'a> {
         if self.field.has_type_name() {
             let rust_name_relative = type_name_to_rust_relative(
                 &ProtobufAbsolutePath::from(self.field.get_type_name()),
-                &FileAndMod {
-                    file: self.file.proto().get_name().to_owned(),
-                    relative_mod: RustRelativePath::from("exts"),
-                    customize: self.customize.clone(),
-                },
+                &self.file_and_mod(),
                 self.root_scope,
             );
             match self.field.get_field_type() {
@@ -74,6 +76,12 @@ impl<'a> ExtGen<'a> {
             "ExtFieldOptional"
         };
         let field_type = format!("{}::ext::{}", protobuf_crate_path(&self.customize), suffix);
+        w.write_line(&format!(
+            "/// Extension field `{}` = {} of `{}`",
+            self.field.get_name(),
+            self.field.get_number(),
+            self.field.get_extendee(),
+        ));
         w.pub_const(
             rust_field_name_for_protobuf_field_name(self.field.get_name()).get(),
             &format!(
@@ -97,14 +105,35 @@ pub(crate) fn write_extensions(
     w: &mut CodeWriter,
     customize: &Customize,
 ) {
-    if file.proto().extension.is_empty() {
+    write_extensions_in_mod(
+        file,
+        &file.proto().extension,
+        RustRelativePath::from("exts"),
+        root_scope,
+        w,
+        customize,
+    );
+}
+
+/// Write `exts` module with constants for given extensions.
+///
+/// `relative_mod` is the path of the `exts` module relative to the file.
+pub(crate) fn write_extensions_in_mod(
+    file: &FileDescriptor,
+    extensions: &[FieldDescriptorProto],
+    relative_mod: RustRelativePath,
+    root_scope: &RootScope,
+    w: &mut CodeWriter,
+    customize: &Customize,
+) {
+    if extensions.is_empty() {
         return;
     }
 
     w.write_line("");
     w.write_line("/// Extension fields");
     w.pub_mod("exts", |w| {
-        for field in &file.proto().extension {
+        for field in extensions {
             if field.get_field_type() == field_descriptor_proto::Type::TYPE_GROUP {
                 continue;
             }
@@ -114,6 +143,7 @@ pub(crate) fn write_extensions(
                 file: file,
                 root_scope: root_scope,
                 field: field,
+                relative_mod: relative_mod.clone(),
                 customize: customize.clone(),
             }
             .write(w);
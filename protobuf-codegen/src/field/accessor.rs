@@ -69,22 +69,24 @@ impl FieldGen<'_> {
         ]
     }
 
-    fn make_accessor_fns_has_get_set(&self) -> Vec<String> {
+    fn make_accessor_fns_has_get_set_clear(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
             format!("{}::get_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
+            format!("{}::clear_{}", message, self.rust_name),
         ]
     }
 
-    fn make_accessor_fns_has_get_mut_set(&self) -> Vec<String> {
+    fn make_accessor_fns_has_get_mut_set_clear(&self) -> Vec<String> {
         let message = self.proto_field.message.rust_name();
         vec![
             format!("{}::has_{}", message, self.rust_name),
             format!("{}::get_{}", message, self.rust_name),
             format!("{}::mut_{}", message, self.rust_name),
             format!("{}::set_{}", message, self.rust_name),
+            format!("{}::clear_{}", message, self.rust_name),
         ]
     }
 
@@ -99,12 +101,16 @@ impl FieldGen<'_> {
 
     fn accessor_fn_repeated(&self, repeated_field: &RepeatedField) -> AccessorFn {
         let RepeatedField { .. } = repeated_field;
-        let name = match repeated_field.kind() {
-            RepeatedFieldKind::Vec => "make_vec_simpler_accessor",
+        let (name, type_params) = match repeated_field.kind() {
+            RepeatedFieldKind::Vec => ("make_vec_simpler_accessor", vec![format!("_")]),
+            RepeatedFieldKind::SmallVec(n) => (
+                "make_small_vec_simpler_accessor",
+                vec![format!("_"), format!("{}", n)],
+            ),
         };
         AccessorFn {
             name: name.to_owned(),
-            type_params: vec![format!("_")],
+            type_params,
             callback_params: self.make_accessor_fns_lambda(),
         }
     }
@@ -133,11 +139,17 @@ impl FieldGen<'_> {
     fn accessor_fn_singular_with_flag(
         &self,
         elem: &FieldElem,
-        _option_kind: OptionKind,
+        option_kind: OptionKind,
     ) -> AccessorFn {
         match elem {
             FieldElem::Message(m) => AccessorFn {
-                name: "make_message_field_accessor".to_owned(),
+                name: match option_kind {
+                    OptionKind::MessageField => "make_message_field_accessor",
+                    OptionKind::Option => "make_option_message_accessor",
+                    OptionKind::OptionBox => "make_option_box_message_accessor",
+                    OptionKind::OptionArc => "make_option_arc_message_accessor",
+                }
+                .to_owned(),
                 type_params: vec![format!(
                     "{}",
                     m.rust_name_relative(&self.get_file_and_mod())
@@ -183,7 +195,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_copy_has_get_set_simpler_accessors".to_owned(),
                 type_params: vec![format!("_")],
-                callback_params: self.make_accessor_fns_has_get_set(),
+                callback_params: self.make_accessor_fns_has_get_set_clear(),
             };
         }
 
@@ -191,7 +203,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_copy_has_get_set_simpler_accessors".to_owned(),
                 type_params: vec![format!("_")],
-                callback_params: self.make_accessor_fns_has_get_set(),
+                callback_params: self.make_accessor_fns_has_get_set_clear(),
             };
         }
 
@@ -199,7 +211,7 @@ impl FieldGen<'_> {
             return AccessorFn {
                 name: "make_oneof_message_has_get_mut_set_accessor".to_owned(),
                 type_params: vec![format!("{}", name)],
-                callback_params: self.make_accessor_fns_has_get_mut_set(),
+                callback_params: self.make_accessor_fns_has_get_mut_set_clear(),
             };
         }
 
@@ -207,7 +219,7 @@ impl FieldGen<'_> {
         AccessorFn {
             name: "make_oneof_deref_has_get_set_simpler_accessor".to_owned(),
             type_params: vec![format!("_")],
-            callback_params: self.make_accessor_fns_has_get_set(),
+            callback_params: self.make_accessor_fns_has_get_set_clear(),
         }
     }
 
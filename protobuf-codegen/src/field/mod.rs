@@ -32,6 +32,10 @@ use protobuf::reflect::ReflectValueRef;
 use protobuf::wire_format::WireType;
 
 mod accessor;
+mod validate;
+mod view;
+
+pub(crate) use self::validate::message_needs_validation;
 
 fn type_is_copy(field_type: field_descriptor_proto::Type) -> bool {
     match field_type {
@@ -60,6 +64,7 @@ impl FieldDescriptorProtoTypeExt for field_descriptor_proto::Type {
                     };
                     format!("{}.read_carllerche_{}()", is, protobuf_name)
                 }
+                PrimitiveTypeVariant::Interned => format!("{}.read_interned_string()", is),
             },
         }
     }
@@ -138,35 +143,70 @@ fn field_type_size(field_type: field_descriptor_proto::Type) -> Option<u32> {
     }
 }
 
-/// Optional fields can be stored are `Option<T>` or `SingularPtrField<T>`.
+/// Optional fields can be stored are `Option<T>`, `Option<Box<T>>`, `Option<Arc<T>>`
+/// or `MessageField<T>`.
 #[derive(Copy, Clone, Eq, PartialEq)]
 pub enum OptionKind {
     /// Field is `Option<T>`
     Option,
-    /// Field is `SingularPtrField<T>`
+    /// Field is `Option<Box<T>>`
+    OptionBox,
+    /// Field is `Option<Arc<T>>`
+    OptionArc,
+    /// Field is `MessageField<T>`
     MessageField,
 }
 
 impl OptionKind {
+    fn for_message(customize: &Customize) -> OptionKind {
+        if customize.singular_field_option.unwrap_or(false) {
+            OptionKind::Option
+        } else if customize.singular_field_option_box.unwrap_or(false) {
+            OptionKind::OptionBox
+        } else if customize.singular_field_option_arc.unwrap_or(false) {
+            OptionKind::OptionArc
+        } else {
+            OptionKind::MessageField
+        }
+    }
+
     fn wrap_element(&self, element_type: RustType) -> RustType {
         let element_type = Box::new(element_type);
         match self {
             OptionKind::Option => RustType::Option(element_type),
+            OptionKind::OptionBox => RustType::Option(Box::new(RustType::Uniq(element_type))),
+            OptionKind::OptionArc => RustType::Option(Box::new(RustType::Arc(element_type))),
             OptionKind::MessageField => RustType::MessageField(element_type),
         }
     }
 
-    // Type of `as_ref()` operation
+    // Type of `as_option_ref()` operation
     fn as_ref_type(&self, element_type: RustType) -> RustType {
         match self {
-            OptionKind::Option => RustType::Option(Box::new(element_type.ref_type())),
+            OptionKind::Option | OptionKind::OptionBox | OptionKind::OptionArc => {
+                RustType::Option(Box::new(element_type.ref_type()))
+            }
             OptionKind::MessageField => RustType::MessageField(Box::new(element_type.ref_type())),
         }
     }
 
-    fn _as_option_ref(&self, v: &str) -> String {
+    // `Option<&T>` from field
+    fn as_option_ref(&self, v: &str) -> String {
         match self {
             OptionKind::Option | OptionKind::MessageField => format!("{}.as_ref()", v),
+            OptionKind::OptionBox | OptionKind::OptionArc => format!("{}.as_deref()", v),
+        }
+    }
+
+    // `Option<T>` from field, leaving field empty
+    fn take(&self, v: &str) -> String {
+        match self {
+            OptionKind::Option | OptionKind::MessageField => format!("{}.take()", v),
+            OptionKind::OptionBox => format!("{}.take().map(|v| *v)", v),
+            OptionKind::OptionArc => format!(
+                "{}.take().map(|v| ::std::sync::Arc::try_unwrap(v).unwrap_or_else(|v| (*v).clone()))",
+                v
+            ),
         }
     }
 
@@ -185,6 +225,14 @@ impl OptionKind {
     fn wrap_value(&self, value: &str, customize: &Customize) -> String {
         match self {
             OptionKind::Option => format!("::std::option::Option::Some({})", value),
+            OptionKind::OptionBox => format!(
+                "::std::option::Option::Some(::std::boxed::Box::new({}))",
+                value
+            ),
+            OptionKind::OptionArc => format!(
+                "::std::option::Option::Some(::std::sync::Arc::new({}))",
+                value
+            ),
             OptionKind::MessageField => format!(
                 "{}::MessageField::some({})",
                 protobuf_crate_path(customize),
@@ -241,23 +289,35 @@ impl<'a> SingularField<'a> {
     }
 }
 
-/// Repeated field can be `Vec<T>` or `RepeatedField<T>`.
+/// Repeated field can be `Vec<T>` or `SmallVec<T, N>`.
 #[derive(Eq, PartialEq, Copy, Clone)]
 pub enum RepeatedFieldKind {
     Vec,
+    SmallVec(u32),
 }
 
 impl RepeatedFieldKind {
+    fn from_customize(customize: &Customize) -> RepeatedFieldKind {
+        match customize.small_vec_for_repeated {
+            None | Some(0) => RepeatedFieldKind::Vec,
+            Some(n) => RepeatedFieldKind::SmallVec(n),
+        }
+    }
+
     fn wrap_element(&self, element_type: RustType) -> RustType {
         let element_type = Box::new(element_type);
         match self {
             RepeatedFieldKind::Vec => RustType::Vec(element_type),
+            RepeatedFieldKind::SmallVec(n) => RustType::SmallVec(element_type, *n),
         }
     }
 
-    fn default(&self) -> String {
+    fn default(&self, customize: &Customize) -> String {
         match self {
             RepeatedFieldKind::Vec => EXPR_VEC_NEW.to_owned(),
+            RepeatedFieldKind::SmallVec(..) => {
+                format!("{}::SmallVec::new()", protobuf_crate_path(customize))
+            }
         }
     }
 }
@@ -266,11 +326,12 @@ impl RepeatedFieldKind {
 pub(crate) struct RepeatedField<'a> {
     pub elem: FieldElem<'a>,
     pub packed: bool,
+    pub kind: RepeatedFieldKind,
 }
 
 impl<'a> RepeatedField<'a> {
     fn kind(&self) -> RepeatedFieldKind {
-        RepeatedFieldKind::Vec
+        self.kind
     }
 
     fn rust_type(&self, reference: &FileAndMod) -> RustType {
@@ -278,8 +339,8 @@ impl<'a> RepeatedField<'a> {
             .wrap_element(self.elem.rust_storage_elem_type(reference))
     }
 
-    fn default(&self) -> String {
-        self.kind().default()
+    fn default(&self, customize: &Customize) -> String {
+        self.kind().default(customize)
     }
 }
 
@@ -311,7 +372,7 @@ impl<'a> FieldKind<'a> {
     ) -> String {
         match self {
             FieldKind::Singular(s) => s.default_value(customize, reference, const_expr),
-            FieldKind::Repeated(r) => r.default(),
+            FieldKind::Repeated(r) => r.default(customize),
             FieldKind::Oneof(..) => EXPR_NONE.to_owned(),
             FieldKind::Map(..) => panic!("map fields cannot have field value"),
         }
@@ -433,6 +494,11 @@ impl<'a> FieldElem<'a> {
                 PrimitiveTypeVariant::Carllerche,
             ) => RustType::Bytes,
             FieldElem::Primitive(.., PrimitiveTypeVariant::Carllerche) => unreachable!(),
+            FieldElem::Primitive(
+                field_descriptor_proto::Type::TYPE_STRING,
+                PrimitiveTypeVariant::Interned,
+            ) => RustType::InternedString,
+            FieldElem::Primitive(.., PrimitiveTypeVariant::Interned) => unreachable!(),
             FieldElem::Group => RustType::Group,
             FieldElem::Message(ref m) => m.rust_type(reference),
             FieldElem::Enum(ref en) => en.enum_or_unknown_rust_type(reference),
@@ -524,8 +590,19 @@ fn field_elem<'a>(
     } else if field.field.get_proto().has_field_type() {
         let carllerche_for_bytes = customize.carllerche_bytes_for_bytes.unwrap_or(false);
         let carllerche_for_string = customize.carllerche_bytes_for_string.unwrap_or(false);
+        // only repeated fields and map keys and values are interned
+        let interned_for_string = customize.interned_string_for_string.unwrap_or(false)
+            && (!parse_map
+                || field.field.get_proto().get_label()
+                    == field_descriptor_proto::Label::LABEL_REPEATED);
 
         let elem = match field.field.get_proto().get_field_type() {
+            field_descriptor_proto::Type::TYPE_STRING if interned_for_string => {
+                FieldElem::Primitive(
+                    field_descriptor_proto::Type::TYPE_STRING,
+                    PrimitiveTypeVariant::Interned,
+                )
+            }
             field_descriptor_proto::Type::TYPE_STRING if carllerche_for_string => {
                 FieldElem::Primitive(
                     field_descriptor_proto::Type::TYPE_STRING,
@@ -562,8 +639,13 @@ pub(crate) struct FieldGen<'a> {
     serde_name: String,
     pub kind: FieldKind<'a>,
     pub expose_field: bool,
+    /// Field is private regardless of its kind
+    pub private_field: bool,
     pub generate_accessors: bool,
     pub generate_getter: bool,
+    /// Generate `has_`, `set_`, `clear_` and `get_..._opt` for singular field
+    /// regardless of syntax
+    pub uniform_accessors: bool,
     customize: Customize,
     path: Vec<i32>,
     info: Option<&'a SourceCodeInfo>,
@@ -597,18 +679,28 @@ impl<'a> FieldGen<'a> {
             && field.field.get_proto().get_field_type()
                 != field_descriptor_proto::Type::TYPE_MESSAGE;
 
+        let private_field = customize.private_fields.unwrap_or(false);
+
         let default_expose_field = !field_may_have_custom_default_value;
-        let expose_field = customize.expose_fields.unwrap_or(default_expose_field);
+        let expose_field =
+            !private_field && customize.expose_fields.unwrap_or(default_expose_field);
+
+        let uniform_accessors = customize.uniform_accessors.unwrap_or(false)
+            && field.field.get_proto().get_label() != field_descriptor_proto::Label::LABEL_REPEATED;
 
         let default_generate_accessors = !expose_field;
         let generate_accessors = customize
             .generate_accessors
             .unwrap_or(default_generate_accessors)
-            || field.is_oneof();
+            || field.is_oneof()
+            || private_field
+            || uniform_accessors;
 
         let default_generate_getter = generate_accessors || field_may_have_custom_default_value;
-        let generate_getter =
-            customize.generate_getter.unwrap_or(default_generate_getter) || field.is_oneof();
+        let generate_getter = customize.generate_getter.unwrap_or(default_generate_getter)
+            || field.is_oneof()
+            || private_field
+            || uniform_accessors;
 
         let kind = if field.field.get_proto().get_label()
             == field_descriptor_proto::Label::LABEL_REPEATED
@@ -633,6 +725,7 @@ impl<'a> FieldGen<'a> {
                         .options
                         .get_or_default()
                         .get_packed(),
+                    kind: RepeatedFieldKind::from_customize(&customize),
                 }),
             }
         } else if let Some(oneof) = field.oneof() {
@@ -647,7 +740,9 @@ impl<'a> FieldGen<'a> {
                 let required = field.field.get_proto().get_label()
                     == field_descriptor_proto::Label::LABEL_REQUIRED;
                 let option_kind = match field.field.get_proto().get_field_type() {
-                    field_descriptor_proto::Type::TYPE_MESSAGE => OptionKind::MessageField,
+                    field_descriptor_proto::Type::TYPE_MESSAGE => {
+                        OptionKind::for_message(&customize)
+                    }
                     _ => OptionKind::Option,
                 };
 
@@ -669,8 +764,10 @@ impl<'a> FieldGen<'a> {
             proto_field: field,
             kind,
             expose_field,
+            private_field,
             generate_accessors,
             generate_getter,
+            uniform_accessors,
             customize,
             path,
             info,
@@ -823,6 +920,26 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// `proto2` enums are closed, undeclared values are handled
+    /// according to `CodedInputStream::unknown_enum_value_policy`.
+    fn is_proto2_enum(&self) -> bool {
+        self.proto_type == field_descriptor_proto::Type::TYPE_ENUM
+            && self.proto_field.message.scope.file_scope.syntax() == Syntax::PROTO2
+    }
+
+    /// Write `if let` which reads `proto2` enum value into `v`.
+    fn write_if_let_read_proto2_enum(&self, w: &mut CodeWriter, cb: impl FnOnce(&mut CodeWriter)) {
+        w.if_let_stmt(
+            "::std::option::Option::Some(v)",
+            &format!(
+                "{}::rt::read_proto2_enum_or_unknown(is, {}, &mut self.unknown_fields)?",
+                protobuf_crate_path(&self.customize),
+                self.proto_field.number()
+            ),
+            cb,
+        );
+    }
+
     // fixed size type?
     fn is_fixed(&self) -> bool {
         field_type_size(self.proto_type).is_some()
@@ -937,6 +1054,29 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// Name of constant with explicit `[default = ...]` value of the field.
+    pub fn default_value_const_name(&self) -> String {
+        format!("DEFAULT_{}", self.proto_field.name().to_uppercase())
+    }
+
+    /// Write `pub const DEFAULT_XXX` for proto2 field with explicit default value.
+    pub fn write_default_value_const(&self, w: &mut CodeWriter) {
+        let value = match self.kind {
+            FieldKind::Singular(..) | FieldKind::Oneof(..) => match self.default_value_from_proto()
+            {
+                Some(value) => value,
+                None => return,
+            },
+            FieldKind::Repeated(..) | FieldKind::Map(..) => return,
+        };
+        let const_type = match self.proto_type {
+            field_descriptor_proto::Type::TYPE_STRING => "&'static str".to_owned(),
+            field_descriptor_proto::Type::TYPE_BYTES => "&'static [u8]".to_owned(),
+            _ => self.get_xxx_return_type().to_code(&self.customize),
+        };
+        w.pub_const(&self.default_value_const_name(), &const_type, &value);
+    }
+
     // default to be assigned to field
     fn element_default_value_rust(&self) -> RustValueTyped {
         match self.kind {
@@ -996,6 +1136,65 @@ impl<'a> FieldGen<'a> {
         }
     }
 
+    /// Field is not oneof and owns nested messages.
+    pub fn has_nested_messages(&self) -> bool {
+        match self.kind {
+            FieldKind::Singular(SingularField { ref elem, .. })
+            | FieldKind::Repeated(RepeatedField { ref elem, .. })
+            | FieldKind::Map(MapField {
+                value: ref elem, ..
+            }) => match elem {
+                FieldElem::Message(..) => true,
+                _ => false,
+            },
+            FieldKind::Oneof(..) => false,
+        }
+    }
+
+    /// Move nested messages of the field to `worklist` for iterative drop.
+    pub fn write_detach_nested_messages(&self, w: &mut CodeWriter) {
+        assert!(self.has_nested_messages());
+        match self.kind {
+            // `Option<M>` and `M` are not recursive, detach one level in place
+            FieldKind::Singular(SingularField {
+                flag:
+                    SingularFieldFlag::WithFlag {
+                        option_kind: OptionKind::Option,
+                        ..
+                    },
+                ..
+            }) => {
+                w.if_let_stmt(
+                    "::std::option::Option::Some(ref mut v)",
+                    &self.self_field(),
+                    |w| {
+                        w.write_line(&format!(
+                            "{}::rt::DropIterative::take_nested_messages(v, worklist);",
+                            protobuf_crate_path(&self.customize)
+                        ));
+                    },
+                );
+            }
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithoutFlag,
+                ..
+            }) => {
+                w.write_line(&format!(
+                    "{}::rt::DropIterative::take_nested_messages(&mut {}, worklist);",
+                    protobuf_crate_path(&self.customize),
+                    self.self_field()
+                ));
+            }
+            _ => {
+                w.write_line(&format!(
+                    "{}::rt::detach_nested_messages(&mut {}, worklist);",
+                    protobuf_crate_path(&self.customize),
+                    self.self_field()
+                ));
+            }
+        }
+    }
+
     // expression that returns size of data is variable
     fn element_size(&self, var: &str, var_type: &RustType) -> String {
         assert!(!self.is_repeated_packed());
@@ -1110,6 +1309,28 @@ impl<'a> FieldGen<'a> {
         format!("!{}.is_empty()", self.self_field())
     }
 
+    // proto3 field without presence flag is set if it is not default
+    fn self_field_is_not_default(&self) -> String {
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                flag: SingularFieldFlag::WithoutFlag,
+                ref elem,
+            }) => match *elem {
+                FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)
+                | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_BYTES, ..) => {
+                    format!("!{}.is_empty()", self.self_field())
+                }
+                _ => format!(
+                    "{} != {}",
+                    self.self_field(),
+                    self.full_storage_type(&self.get_file_and_mod())
+                        .default_value(&self.customize, false)
+                ),
+            },
+            _ => panic!("field has presence flag"),
+        }
+    }
+
     fn self_field_is_none(&self) -> String {
         assert!(self.is_singular());
         format!("{}.is_none()", self.self_field())
@@ -1147,12 +1368,14 @@ impl<'a> FieldGen<'a> {
             ),
         );
 
-        as_option_type.value(format!("{}.as_ref()", self.self_field()))
+        as_option_type.value(option_kind.as_option_ref(&self.self_field()))
     }
 
     /// Field visibility in message struct
     fn visibility(&self) -> Visibility {
-        if self.expose_field {
+        if self.private_field {
+            Visibility::Default
+        } else if self.expose_field {
             Visibility::Public
         } else {
             match self.kind {
@@ -1245,54 +1468,22 @@ impl<'a> FieldGen<'a> {
             }
             SingularField {
                 flag: SingularFieldFlag::WithoutFlag,
-                ref elem,
-            } => match *elem {
-                FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)
-                | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_BYTES, ..) => {
-                    w.if_stmt(format!("!{}.is_empty()", self.self_field()), |w| {
-                        let v = RustValueTyped {
-                            value: self.self_field(),
-                            rust_type: self.full_storage_type(
-                                &self
-                                    .proto_field
-                                    .message
-                                    .scope
-                                    .get_file_and_mod(self.customize.clone()),
-                            ),
-                        };
-                        cb(&v, w);
-                    });
-                }
-                _ => {
-                    w.if_stmt(
-                        format!(
-                            "{} != {}",
-                            self.self_field(),
-                            self.full_storage_type(
-                                &self
-                                    .proto_field
-                                    .message
-                                    .scope
-                                    .get_file_and_mod(self.customize.clone())
-                            )
-                            .default_value(&self.customize, false)
+                ..
+            } => {
+                w.if_stmt(self.self_field_is_not_default(), |w| {
+                    let v = RustValueTyped {
+                        value: self.self_field(),
+                        rust_type: self.full_storage_type(
+                            &self
+                                .proto_field
+                                .message
+                                .scope
+                                .get_file_and_mod(self.customize.clone()),
                         ),
-                        |w| {
-                            let v = RustValueTyped {
-                                value: self.self_field(),
-                                rust_type: self.full_storage_type(
-                                    &self
-                                        .proto_field
-                                        .message
-                                        .scope
-                                        .get_file_and_mod(self.customize.clone()),
-                                ),
-                            };
-                            cb(&v, w);
-                        },
-                    );
-                }
-            },
+                    };
+                    cb(&v, w);
+                });
+            }
         }
     }
 
@@ -1337,6 +1528,24 @@ impl<'a> FieldGen<'a> {
                     |w| cb(w, &oneof_field.elem.rust_storage_elem_type(&file_and_mod)),
                 )
             }
+            FieldKind::Singular(SingularField {
+                flag:
+                    SingularFieldFlag::WithFlag {
+                        option_kind:
+                            OptionKind::Option | OptionKind::OptionBox | OptionKind::OptionArc,
+                        ..
+                    },
+                ..
+            }) => {
+                // `for` over `&Option` triggers `for_loops_over_fallibles` lint
+                let v_type = self.full_storage_iter_elem_type(&file_and_mod);
+                let self_field = self.self_field();
+                w.if_let_stmt(
+                    &format!("::std::option::Option::Some({})", varn),
+                    &format!("&{}", self_field),
+                    |w| cb(w, &v_type),
+                );
+            }
             _ => {
                 let v_type = self.full_storage_iter_elem_type(&file_and_mod);
                 let self_field = self.self_field();
@@ -1567,6 +1776,7 @@ impl<'a> FieldGen<'a> {
     ) {
         let carllerche = match r.elem.primitive_type_variant() {
             PrimitiveTypeVariant::Carllerche => "carllerche_",
+            PrimitiveTypeVariant::Interned => "interned_",
             PrimitiveTypeVariant::Default => "",
         };
         let type_name_for_fn = protobuf_name(self.proto_type);
@@ -1602,12 +1812,34 @@ impl<'a> FieldGen<'a> {
                 ..
             } => "singular_proto3",
         };
-        let suffix = match s.elem {
-            FieldElem::Message(..) => "into_field",
+        let suffix = match (&s.elem, s.flag) {
+            (
+                FieldElem::Message(..),
+                SingularFieldFlag::WithFlag {
+                    option_kind: OptionKind::Option,
+                    ..
+                },
+            ) => "into_option",
+            (
+                FieldElem::Message(..),
+                SingularFieldFlag::WithFlag {
+                    option_kind: OptionKind::OptionBox,
+                    ..
+                },
+            ) => "into_option_box",
+            (
+                FieldElem::Message(..),
+                SingularFieldFlag::WithFlag {
+                    option_kind: OptionKind::OptionArc,
+                    ..
+                },
+            ) => "into_option_arc",
+            (FieldElem::Message(..), _) => "into_field",
             _ => "into",
         };
         let carllerche = match s.elem.primitive_type_variant() {
             PrimitiveTypeVariant::Carllerche => "carllerche_",
+            PrimitiveTypeVariant::Interned => unreachable!(),
             PrimitiveTypeVariant::Default => "",
         };
         let type_name_for_fn = protobuf_name(self.proto_type);
@@ -1662,11 +1894,22 @@ impl<'a> FieldGen<'a> {
     fn write_merge_from_oneof(&self, o: &OneofField, wire_type_var: &str, w: &mut CodeWriter) {
         self.write_assert_wire_type(wire_type_var, w);
 
-        let typed = RustValueTyped {
-            value: format!(
+        if self.is_proto2_enum() {
+            self.write_if_let_read_proto2_enum(w, |w| {
+                self.write_merge_from_oneof_value(o, "v".to_owned(), w)
+            });
+        } else {
+            let value = format!(
                 "{}?",
                 self.proto_type.read("is", o.elem.primitive_type_variant())
-            ),
+            );
+            self.write_merge_from_oneof_value(o, value, w);
+        }
+    }
+
+    fn write_merge_from_oneof_value(&self, o: &OneofField, value: String, w: &mut CodeWriter) {
+        let typed = RustValueTyped {
+            value,
             rust_type: self.full_storage_iter_elem_type(
                 &self
                     .proto_field
@@ -1723,6 +1966,12 @@ impl<'a> FieldGen<'a> {
             FieldElem::Message(..) => {
                 self.write_merge_from_field_message_string_bytes(w);
             }
+            _ if self.is_proto2_enum() => {
+                self.write_assert_wire_type(wire_type_var, w);
+                self.write_if_let_read_proto2_enum(w, |w| {
+                    self.write_self_field_assign_some(w, s, "v");
+                });
+            }
             _ => {
                 self.write_assert_wire_type(wire_type_var, w);
                 let read_proc = format!(
@@ -1741,12 +1990,32 @@ impl<'a> FieldGen<'a> {
             _ => panic!(),
         };
 
+        if let RepeatedFieldKind::SmallVec(..) = field.kind() {
+            w.write_line(&format!(
+                "{}::rt::read_repeated_into_small_vec::<{}, _>({}, is, &mut self.{})?;",
+                protobuf_crate_path(&self.customize),
+                field.elem.lib_protobuf_type(&self.get_file_and_mod()),
+                wire_type_var,
+                self.rust_name,
+            ));
+            return;
+        }
+
         match field.elem {
             FieldElem::Message(..)
             | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_STRING, ..)
             | FieldElem::Primitive(field_descriptor_proto::Type::TYPE_BYTES, ..) => {
                 self.write_merge_from_field_message_string_bytes(w);
             }
+            FieldElem::Enum(..) if self.is_proto2_enum() => {
+                w.write_line(&format!(
+                    "{}::rt::read_repeated_proto2_enum_or_unknown_into({}, is, &mut self.{}, {}, &mut self.unknown_fields)?",
+                    protobuf_crate_path(&self.customize),
+                    wire_type_var,
+                    self.rust_name,
+                    self.proto_field.number(),
+                ));
+            }
             FieldElem::Enum(..) => {
                 w.write_line(&format!(
                     "{}::rt::read_repeated_enum_or_unknown_into({}, is, &mut self.{})?",
@@ -1948,7 +2217,7 @@ impl<'a> FieldGen<'a> {
                         .get_file_and_mod(self.customize.clone()),
                 );
                 w.write_line(option_kind.unwrap_ref_or_else(
-                    &format!("{}.as_ref()", self_field),
+                    &option_kind.as_option_ref(&self_field),
                     &format!(
                         "<{} as {}::Message>::default_instance()",
                         field_type_name.to_code(&self.customize),
@@ -2151,6 +2420,17 @@ impl<'a> FieldGen<'a> {
     }
 
     fn write_message_field_has(&self, w: &mut CodeWriter) {
+        if let FieldKind::Singular(SingularField {
+            flag: SingularFieldFlag::WithoutFlag,
+            ..
+        }) = self.kind
+        {
+            w.write_line("/// Field without presence is set when it is not equal to default.");
+            w.pub_fn(&format!("{}(&self) -> bool", self.has_name()), |w| {
+                w.write_line(self.self_field_is_not_default());
+            });
+            return;
+        }
         w.pub_fn(
             &format!("{}(&self) -> bool", self.has_name()),
             |w| match self.kind {
@@ -2182,6 +2462,33 @@ impl<'a> FieldGen<'a> {
         );
     }
 
+    fn write_message_field_get_opt(&self, w: &mut CodeWriter) {
+        let get_xxx_return_type = self.get_xxx_return_type();
+        w.write_line(&format!(
+            "/// `Some` if `{}()` returns true.",
+            self.has_name()
+        ));
+        w.pub_fn(
+            &format!(
+                "get_{}_opt(&self) -> ::std::option::Option<{}>",
+                self.rust_name,
+                get_xxx_return_type.to_code(&self.customize)
+            ),
+            |w| {
+                w.write_line(&format!("if self.{}() {{", self.has_name()));
+                w.indented(|w| {
+                    w.write_line(&format!(
+                        "::std::option::Option::Some(self.get_{}())",
+                        self.rust_name
+                    ))
+                });
+                w.write_line("} else {");
+                w.indented(|w| w.write_line("::std::option::Option::None"));
+                w.write_line("}");
+            },
+        );
+    }
+
     fn write_message_field_set(&self, w: &mut CodeWriter) {
         let set_xxx_param_type = self.set_xxx_param_type(
             &self
@@ -2242,7 +2549,7 @@ impl<'a> FieldGen<'a> {
     fn write_message_field_mut_singular(&self, s: &SingularField, w: &mut CodeWriter) {
         match s {
             SingularField {
-                flag: SingularFieldFlag::WithFlag { .. },
+                flag: flag @ SingularFieldFlag::WithFlag { .. },
                 ..
             } => {
                 self.write_if_self_field_is_none(w, |w| {
@@ -2252,7 +2559,16 @@ impl<'a> FieldGen<'a> {
                     );
                 });
                 let self_field = self.self_field();
-                w.write_line(&format!("{}.as_mut().unwrap()", self_field));
+                match flag {
+                    SingularFieldFlag::WithFlag {
+                        option_kind: OptionKind::OptionArc,
+                        ..
+                    } => w.write_line(&format!(
+                        "::std::sync::Arc::make_mut({}.as_mut().unwrap())",
+                        self_field
+                    )),
+                    _ => w.write_line(&format!("{}.as_mut().unwrap()", self_field)),
+                }
             }
             SingularField {
                 flag: SingularFieldFlag::WithoutFlag,
@@ -2444,7 +2760,7 @@ impl<'a> FieldGen<'a> {
                 if !elem.is_copy() {
                     w.write_line(
                         &option_kind.unwrap_or_else(
-                            &format!("{}.take()", self.self_field()),
+                            &option_kind.take(&self.self_field()),
                             &elem
                                 .rust_storage_elem_type(
                                     &self
@@ -2511,6 +2827,71 @@ impl<'a> FieldGen<'a> {
         );
     }
 
+    fn write_message_field_replace(&self, w: &mut CodeWriter) {
+        let take_xxx_return_type = self.take_xxx_return_type(
+            &self
+                .proto_field
+                .message
+                .scope
+                .get_file_and_mod(self.customize.clone()),
+        );
+        let return_type = take_xxx_return_type.to_code(&self.customize);
+        w.comment("Replace field value, returning the previous one");
+        w.pub_fn(
+            &format!(
+                "replace_{}(&mut self, v: {}) -> {}",
+                self.rust_name, return_type, return_type
+            ),
+            |w| {
+                w.write_line(&format!("let r = self.take_{}();", self.rust_name));
+                w.write_line(&format!("self.set_{}(v);", self.rust_name));
+                w.write_line("r");
+            },
+        );
+    }
+
+    // Proto3 enum fields are open: value unknown to the schema is preserved,
+    // and these accessors get and set it as raw number.
+    fn has_enum_value_accessors(&self) -> bool {
+        match self.kind {
+            FieldKind::Singular(SingularField {
+                elem: FieldElem::Enum(..),
+                flag: SingularFieldFlag::WithoutFlag,
+            }) => {}
+            _ => return false,
+        }
+        // Field `x_value` would clash with accessors of `x`
+        let value_name = format!("{}_value", self.proto_field.name());
+        self.proto_field
+            .message
+            .fields()
+            .iter()
+            .all(|f| f.name() != value_name)
+    }
+
+    fn write_message_field_enum_value_accessors(&self, w: &mut CodeWriter) {
+        w.write_line("");
+        w.comment("Raw value, including values not known to the enum type");
+        w.pub_fn(&format!("get_{}_value(&self) -> i32", self.rust_name), |w| {
+            w.write_line(&format!(
+                "{}::ProtobufEnumOrUnknown::value(&self.{})",
+                protobuf_crate_path(&self.customize),
+                self.rust_name
+            ));
+        });
+        w.write_line("");
+        w.pub_fn(
+            &format!("set_{}_value(&mut self, v: i32)", self.rust_name),
+            |w| {
+                w.write_line(&format!(
+                    "self.{} = {}::ProtobufEnumOrUnknown::from_i32(v);",
+                    self.rust_name,
+                    protobuf_crate_path(&self.customize),
+                ));
+            },
+        );
+    }
+
     pub fn write_message_single_field_accessors(&self, w: &mut CodeWriter) {
         if self.generate_accessors || self.generate_getter {
             w.write_line("");
@@ -2533,11 +2914,16 @@ impl<'a> FieldGen<'a> {
             self.write_clear(w);
         });
 
-        if self.has_has() {
+        if self.has_has() || self.uniform_accessors {
             w.write_line("");
             self.write_message_field_has(w);
         }
 
+        if self.uniform_accessors {
+            w.write_line("");
+            self.write_message_field_get_opt(w);
+        }
+
         w.write_line("");
         self.write_message_field_set(w);
 
@@ -2549,6 +2935,12 @@ impl<'a> FieldGen<'a> {
         if self.has_take() {
             w.write_line("");
             self.write_message_field_take(w);
+            w.write_line("");
+            self.write_message_field_replace(w);
+        }
+
+        if self.has_enum_value_accessors() {
+            self.write_message_field_enum_value_accessors(w);
         }
     }
 }
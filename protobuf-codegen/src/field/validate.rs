@@ -0,0 +1,307 @@
+//! Code generation for `rustproto.validate_*` rules.
+
+use std::collections::HashSet;
+
+use protobuf::descriptor::field_descriptor_proto::Type;
+use protobuf::descriptor::FieldOptions;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::RuntimeFieldType;
+use protobuf::reflect::RuntimeTypeBox;
+use protobuf::rustproto;
+
+use crate::code_writer::CodeWriter;
+use crate::field::FieldElem;
+use crate::field::FieldGen;
+use crate::field::FieldKind;
+use crate::field::MapField;
+use crate::field::OptionKind;
+use crate::field::RepeatedField;
+use crate::field::SingularField;
+use crate::field::SingularFieldFlag;
+use crate::inside::protobuf_crate_path;
+
+/// Validation rules of a field.
+#[derive(Default)]
+pub(crate) struct FieldRules {
+    gte: Option<f64>,
+    lte: Option<f64>,
+    min_len: Option<u64>,
+    max_len: Option<u64>,
+    pattern: Option<String>,
+    required: bool,
+    min_items: Option<u64>,
+    max_items: Option<u64>,
+}
+
+impl FieldRules {
+    pub fn from_options(options: &FieldOptions) -> FieldRules {
+        FieldRules {
+            gte: rustproto::exts::validate_gte.get(options),
+            lte: rustproto::exts::validate_lte.get(options),
+            min_len: rustproto::exts::validate_min_len.get(options),
+            max_len: rustproto::exts::validate_max_len.get(options),
+            pattern: rustproto::exts::validate_pattern.get(options),
+            required: rustproto::exts::validate_required
+                .get(options)
+                .unwrap_or(false),
+            min_items: rustproto::exts::validate_min_items.get(options),
+            max_items: rustproto::exts::validate_max_items.get(options),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.has_element_rules() && !self.required && !self.has_items_rules()
+    }
+
+    fn has_element_rules(&self) -> bool {
+        self.gte.is_some()
+            || self.lte.is_some()
+            || self.min_len.is_some()
+            || self.max_len.is_some()
+            || self.pattern.is_some()
+    }
+
+    fn has_items_rules(&self) -> bool {
+        self.min_items.is_some() || self.max_items.is_some()
+    }
+}
+
+/// Message or one of nested messages has validation rules,
+/// so `Message::validate_to` needs to be generated.
+pub(crate) fn message_needs_validation(
+    message: &MessageDescriptor,
+    visited: &mut HashSet<String>,
+) -> bool {
+    if !visited.insert(message.full_name().to_owned()) {
+        // Recursive message, checked by the caller
+        return false;
+    }
+    for field in message.fields() {
+        if !FieldRules::from_options(field.get_proto().options.get_or_default()).is_empty() {
+            return true;
+        }
+        // Nested messages in oneofs and groups are not validated
+        if field.get_proto().has_oneof_index()
+            || field.get_proto().get_field_type() == Type::TYPE_GROUP
+        {
+            continue;
+        }
+        let t = match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) | RuntimeFieldType::Repeated(t) => t,
+            RuntimeFieldType::Map(_, v) => v,
+        };
+        if let RuntimeTypeBox::Message(m) = t {
+            if message_needs_validation(&m, visited) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn option_lit<T: std::fmt::Debug>(v: &Option<T>) -> String {
+    match v {
+        Some(v) => format!("::std::option::Option::Some({:?})", v),
+        None => "::std::option::Option::None".to_owned(),
+    }
+}
+
+impl FieldGen<'_> {
+    fn rules(&self) -> FieldRules {
+        FieldRules::from_options(self.proto_field.field.get_proto().options.get_or_default())
+    }
+
+    fn validate_panic(&self, what: &str) -> ! {
+        panic!(
+            "field `{}.{}`: {}",
+            self.proto_field.message.message.full_name(),
+            self.proto_field.name(),
+            what
+        )
+    }
+
+    fn elem_needs_validation(elem: &FieldElem) -> bool {
+        match elem {
+            FieldElem::Message(m) => {
+                message_needs_validation(&m.message.message, &mut HashSet::new())
+            }
+            _ => false,
+        }
+    }
+
+    /// Check element rules of value `var` which is a reference to an element.
+    fn write_validate_elem(
+        &self,
+        w: &mut CodeWriter,
+        rules: &FieldRules,
+        elem: &FieldElem,
+        field: &str,
+        var: &str,
+    ) {
+        let protobuf = protobuf_crate_path(&self.customize);
+        let proto_type = match elem {
+            FieldElem::Primitive(t, ..) => Some(*t),
+            _ => None,
+        };
+        if rules.gte.is_some() || rules.lte.is_some() {
+            match proto_type {
+                Some(Type::TYPE_BOOL) | Some(Type::TYPE_STRING) | Some(Type::TYPE_BYTES) | None => {
+                    self.validate_panic("`validate_gte` and `validate_lte` require a number field")
+                }
+                Some(_) => {}
+            }
+            w.write_line(&format!(
+                "{}::rt::validate_range(v, \"{}\", *{}, {}, {});",
+                protobuf,
+                field,
+                var,
+                option_lit(&rules.gte),
+                option_lit(&rules.lte),
+            ));
+        }
+        if rules.min_len.is_some() || rules.max_len.is_some() {
+            let f = match proto_type {
+                Some(Type::TYPE_STRING) => "validate_str_len",
+                Some(Type::TYPE_BYTES) => "validate_bytes_len",
+                _ => self.validate_panic(
+                    "`validate_min_len` and `validate_max_len` require a string or bytes field",
+                ),
+            };
+            w.write_line(&format!(
+                "{}::rt::{}(v, \"{}\", &**{}, {}, {});",
+                protobuf,
+                f,
+                field,
+                var,
+                option_lit(&rules.min_len),
+                option_lit(&rules.max_len),
+            ));
+        }
+        if let Some(pattern) = &rules.pattern {
+            if proto_type != Some(Type::TYPE_STRING) {
+                self.validate_panic("`validate_pattern` requires a string field");
+            }
+            w.block("{", "}", |w| {
+                w.write_line(&format!(
+                    "static PATTERN: {}::validate::Pattern = {}::validate::Pattern::new({:?});",
+                    protobuf, protobuf, pattern,
+                ));
+                w.write_line(&format!(
+                    "{}::rt::validate_pattern(v, \"{}\", &**{}, &PATTERN);",
+                    protobuf, field, var,
+                ));
+            });
+        }
+        if Self::elem_needs_validation(elem) {
+            if field.is_empty() {
+                w.write_line(&format!("{}.validate_to(v);", var));
+            } else {
+                w.write_line(&format!(
+                    "v.with_field(\"{}\", |v| {}.validate_to(v));",
+                    field, var
+                ));
+            }
+        }
+    }
+
+    fn write_validate_items(&self, w: &mut CodeWriter, rules: &FieldRules, name: &str) {
+        if rules.has_items_rules() {
+            w.write_line(&format!(
+                "{}::rt::validate_items(v, \"{}\", {}.len(), {}, {});",
+                protobuf_crate_path(&self.customize),
+                name,
+                self.self_field(),
+                option_lit(&rules.min_items),
+                option_lit(&rules.max_items),
+            ));
+        }
+    }
+
+    /// Body of generated `validate_to` for this field.
+    pub(crate) fn write_validate(&self, w: &mut CodeWriter) {
+        let rules = self.rules();
+        let name = self.proto_field.name().to_owned();
+        let items_rule_error = "`validate_min_items` and `validate_max_items` require \
+                                repeated or map field";
+        match &self.kind {
+            FieldKind::Oneof(..) => {
+                if !rules.is_empty() {
+                    self.validate_panic("validation rules are not supported for oneof fields");
+                }
+            }
+            FieldKind::Singular(SingularField { elem, flag }) => {
+                if rules.has_items_rules() {
+                    self.validate_panic(items_rule_error);
+                }
+                match flag {
+                    SingularFieldFlag::WithoutFlag => {
+                        if rules.required {
+                            self.validate_panic(
+                                "`validate_required` requires a field with presence",
+                            );
+                        }
+                        let var = format!("&{}", self.self_field());
+                        self.write_validate_elem(w, &rules, elem, &name, &format!("({})", var));
+                    }
+                    SingularFieldFlag::WithFlag { option_kind, .. } => {
+                        if rules.required {
+                            w.write_line(&format!(
+                                "{}::rt::validate_required(v, \"{}\", {});",
+                                protobuf_crate_path(&self.customize),
+                                name,
+                                self.self_field_is_some(),
+                            ));
+                        }
+                        if rules.has_element_rules() || Self::elem_needs_validation(elem) {
+                            let field = format!("&{}", self.self_field());
+                            let cb = |w: &mut CodeWriter| {
+                                self.write_validate_elem(w, &rules, elem, &name, "e");
+                            };
+                            match option_kind {
+                                OptionKind::MessageField => w.for_stmt(&field, "e", cb),
+                                // `for` over `&Option` triggers `for_loops_over_fallibles` lint
+                                _ => w.if_let_stmt("::std::option::Option::Some(e)", &field, cb),
+                            }
+                        }
+                    }
+                }
+            }
+            FieldKind::Repeated(RepeatedField { elem, .. }) => {
+                if rules.required {
+                    self.validate_panic("`validate_required` requires a field with presence");
+                }
+                self.write_validate_items(w, &rules, &name);
+                if rules.has_element_rules() || Self::elem_needs_validation(elem) {
+                    w.for_stmt(
+                        &format!("{}.iter().enumerate()", self.self_field()),
+                        "(i, e)",
+                        |w| {
+                            w.write_line(&format!("v.with_index(\"{}\", i, |v| {{", name));
+                            w.indented(|w| {
+                                self.write_validate_elem(w, &rules, elem, "", "e");
+                            });
+                            w.write_line("});");
+                        },
+                    );
+                }
+            }
+            FieldKind::Map(MapField { value, .. }) => {
+                if rules.required {
+                    self.validate_panic("`validate_required` requires a field with presence");
+                }
+                if rules.has_element_rules() {
+                    self.validate_panic("only `validate_min_items` and `validate_max_items` rules are supported for map fields");
+                }
+                self.write_validate_items(w, &rules, &name);
+                if Self::elem_needs_validation(value) {
+                    w.for_stmt(&format!("&{}", self.self_field()), "(k, e)", |w| {
+                        w.write_line(&format!(
+                            "v.with_key(\"{}\", k, |v| e.validate_to(v));",
+                            name
+                        ));
+                    });
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Accessors of `FooView` types generated with `generate_view` option.
+
+use protobuf::descriptor::field_descriptor_proto::Type;
+
+use crate::code_writer::CodeWriter;
+use crate::field::FieldElem;
+use crate::field::FieldGen;
+use crate::field::FieldKind;
+use crate::inside::protobuf_crate_path;
+use crate::rust_name::RustIdent;
+use crate::rust_types_values::PrimitiveTypeVariant;
+use crate::rust_types_values::ProtobufTypeGen;
+use crate::scope::MessageWithScope;
+
+impl FieldGen<'_> {
+    /// View type for message field, `None` if the message has no view.
+    fn view_type(&self, has_view: &dyn Fn(&MessageWithScope) -> bool) -> Option<String> {
+        match self.elem() {
+            FieldElem::Message(m) if has_view(&m.message) => {
+                let mut view = m.rust_name_relative(&self.get_file_and_mod()).0;
+                view.ident = RustIdent::new(&format!("{}View", view.ident.get()));
+                Some(format!("{}<'a>", view))
+            }
+            _ => None,
+        }
+    }
+
+    /// Write `if` which returns default value when other field of oneof is set.
+    fn write_view_oneof_check(&self, oneof_numbers: &[u32], default: &str, w: &mut CodeWriter) {
+        if let FieldKind::Oneof(..) = self.kind {
+            w.if_stmt(
+                format!(
+                    "{}::rt::view_oneof_case(self.bytes, &{:?})? != ::std::option::Option::Some({})",
+                    protobuf_crate_path(&self.customize),
+                    oneof_numbers,
+                    self.proto_field.number(),
+                ),
+                |w| {
+                    w.write_line(&format!("return ::std::result::Result::Ok({});", default));
+                },
+            );
+        }
+    }
+
+    fn write_view_has(&self, oneof_numbers: &[u32], w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        w.pub_fn(
+            &format!(
+                "{}(&self) -> {}::ProtobufResult<bool>",
+                self.has_name(),
+                protobuf_crate
+            ),
+            |w| match self.kind {
+                FieldKind::Oneof(..) => w.write_line(&format!(
+                    "::std::result::Result::Ok({}::rt::view_oneof_case(self.bytes, &{:?})? == ::std::option::Option::Some({}))",
+                    protobuf_crate,
+                    oneof_numbers,
+                    self.proto_field.number(),
+                )),
+                _ => w.write_line(&format!(
+                    "{}::rt::view_has_field(self.bytes, {})",
+                    protobuf_crate,
+                    self.proto_field.number()
+                )),
+            },
+        );
+    }
+
+    fn write_view_singular_get(
+        &self,
+        oneof_numbers: &[u32],
+        has_view: &dyn Fn(&MessageWithScope) -> bool,
+        w: &mut CodeWriter,
+    ) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        let number = self.proto_field.number();
+        let (return_type, default) = match self.elem() {
+            FieldElem::Primitive(Type::TYPE_STRING, ..) => (
+                "&'a str".to_owned(),
+                self.default_value_from_proto()
+                    .unwrap_or_else(|| "\"\"".to_owned()),
+            ),
+            FieldElem::Primitive(Type::TYPE_BYTES, ..) => (
+                "&'a [u8]".to_owned(),
+                self.default_value_from_proto()
+                    .unwrap_or_else(|| "&[]".to_owned()),
+            ),
+            FieldElem::Primitive(..) | FieldElem::Enum(..) => (
+                self.get_xxx_return_type().to_code(&self.customize),
+                self.get_xxx_default_value_rust(),
+            ),
+            FieldElem::Message(..) => match self.view_type(has_view) {
+                Some(view) => (
+                    format!("::std::option::Option<{}>", view),
+                    "::std::option::Option::None".to_owned(),
+                ),
+                None => return,
+            },
+            FieldElem::Group => return,
+        };
+
+        w.write_line("");
+        w.pub_fn(
+            &format!(
+                "get_{}(&self) -> {}::ProtobufResult<{}>",
+                self.rust_name, protobuf_crate, return_type
+            ),
+            |w| {
+                self.write_view_oneof_check(oneof_numbers, &default, w);
+                match self.elem() {
+                    FieldElem::Primitive(Type::TYPE_STRING, ..) => w.write_line(&format!(
+                        "::std::result::Result::Ok({}::rt::view_string(self.bytes, {})?.unwrap_or({}))",
+                        protobuf_crate, number, default
+                    )),
+                    FieldElem::Primitive(Type::TYPE_BYTES, ..) => w.write_line(&format!(
+                        "::std::result::Result::Ok({}::rt::view_bytes(self.bytes, {})?.unwrap_or({}))",
+                        protobuf_crate, number, default
+                    )),
+                    FieldElem::Primitive(t, ..) => w.write_line(&format!(
+                        "::std::result::Result::Ok({}::rt::view_singular::<{}>(self.bytes, {})?.unwrap_or({}))",
+                        protobuf_crate,
+                        ProtobufTypeGen::Primitive(*t, PrimitiveTypeVariant::Default)
+                            .rust_type(&self.customize),
+                        number,
+                        default
+                    )),
+                    FieldElem::Enum(..) => {
+                        w.match_expr(
+                            &format!(
+                                "{}::rt::view_singular::<{}>(self.bytes, {})?",
+                                protobuf_crate,
+                                self.elem().lib_protobuf_type(&self.get_file_and_mod()),
+                                number
+                            ),
+                            |w| {
+                                w.case_expr(
+                                    "::std::option::Option::Some(e)",
+                                    &format!(
+                                        "::std::result::Result::Ok(e.enum_value_or({}))",
+                                        default
+                                    ),
+                                );
+                                w.case_expr(
+                                    "::std::option::Option::None",
+                                    &format!("::std::result::Result::Ok({})", default),
+                                );
+                            },
+                        );
+                    }
+                    FieldElem::Message(..) => w.write_line(&format!(
+                        "{}::rt::view_message(self.bytes, {})",
+                        protobuf_crate, number
+                    )),
+                    FieldElem::Group => unreachable!(),
+                }
+            },
+        );
+    }
+
+    fn write_view_repeated_get(
+        &self,
+        has_view: &dyn Fn(&MessageWithScope) -> bool,
+        w: &mut CodeWriter,
+    ) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        let (item_type, function) = match self.elem() {
+            FieldElem::Primitive(Type::TYPE_STRING, ..) => {
+                ("&'a str".to_owned(), "view_repeated_string")
+            }
+            FieldElem::Primitive(Type::TYPE_BYTES, ..) => {
+                ("&'a [u8]".to_owned(), "view_repeated_bytes")
+            }
+            FieldElem::Message(..) => match self.view_type(has_view) {
+                Some(view) => (view, "view_repeated_message"),
+                None => return,
+            },
+            // Repeated scalars may be packed, they are not supported by views
+            _ => return,
+        };
+        w.write_line("");
+        w.pub_fn(
+            &format!(
+                "get_{}(&self) -> impl ::std::iter::Iterator<Item = {}::ProtobufResult<{}>> + 'a",
+                self.rust_name, protobuf_crate, item_type
+            ),
+            |w| {
+                w.write_line(&format!(
+                    "{}::rt::{}(self.bytes, {})",
+                    protobuf_crate,
+                    function,
+                    self.proto_field.number()
+                ));
+            },
+        );
+    }
+
+    /// Write accessors of the field in `FooView` type.
+    ///
+    /// `oneof_numbers` are numbers of fields of the oneof this field is part of.
+    pub(crate) fn write_view_accessors(
+        &self,
+        oneof_numbers: &[u32],
+        has_view: &dyn Fn(&MessageWithScope) -> bool,
+        w: &mut CodeWriter,
+    ) {
+        match self.kind {
+            FieldKind::Singular(..) | FieldKind::Oneof(..) => {
+                let is_message = match self.elem() {
+                    FieldElem::Message(..) | FieldElem::Group => true,
+                    _ => false,
+                };
+                if self.has_has() && !is_message {
+                    w.write_line("");
+                    self.write_view_has(oneof_numbers, w);
+                }
+                self.write_view_singular_get(oneof_numbers, has_view, w);
+            }
+            FieldKind::Repeated(..) => self.write_view_repeated_get(has_view, w),
+            FieldKind::Map(..) => {}
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Code generation from descriptors in memory, without filesystem or `protoc`.
+
+use std::path::PathBuf;
+
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::Message;
+use protobuf::ProtobufResult;
+
+use crate::gen;
+use crate::Customize;
+
+/// Generated file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    /// File name like `foo_bar.rs`, relative to output directory.
+    pub name: String,
+    /// Rust source.
+    pub content: String,
+}
+
+/// Generate Rust sources for all files of the descriptor set.
+///
+/// The set must contain all dependencies of files,
+/// e. g. as produced by `protoc --include_imports --descriptor_set_out`.
+/// Result also contains `mod.rs` if [`Customize::gen_mod_rs`] is set.
+pub fn gen_from_descriptor_set(
+    file_descriptor_set: &FileDescriptorSet,
+    customize: &Customize,
+) -> Vec<GeneratedFile> {
+    let files_to_generate: Vec<PathBuf> = file_descriptor_set
+        .file
+        .iter()
+        .map(|f| PathBuf::from(f.get_name()))
+        .collect();
+    gen_from_descriptor_set_files(file_descriptor_set, &files_to_generate, customize)
+}
+
+/// Generate Rust sources for the specified files of the descriptor set.
+///
+/// File names are file names in descriptors like `dir/foo.proto`.
+///
+/// # Panics
+///
+/// If a file is not found in the descriptor set.
+pub fn gen_from_descriptor_set_files(
+    file_descriptor_set: &FileDescriptorSet,
+    files_to_generate: &[PathBuf],
+    customize: &Customize,
+) -> Vec<GeneratedFile> {
+    gen(
+        &file_descriptor_set.file,
+        "in-memory descriptors",
+        files_to_generate,
+        customize,
+    )
+    .into_iter()
+    .map(|r| GeneratedFile {
+        name: r.name,
+        content: String::from_utf8(r.content).expect("generated code is UTF-8"),
+    })
+    .collect()
+}
+
+/// Generate Rust sources for all files of serialized descriptor set.
+pub fn gen_from_descriptor_set_bytes(
+    file_descriptor_set: &[u8],
+    customize: &Customize,
+) -> ProtobufResult<Vec<GeneratedFile>> {
+    let file_descriptor_set = FileDescriptorSet::parse_from_bytes(file_descriptor_set)?;
+    Ok(gen_from_descriptor_set(&file_descriptor_set, customize))
+}
+
+#[cfg(test)]
+mod test {
+    use protobuf::well_known_types::Duration;
+
+    use super::*;
+
+    fn duration_fds() -> FileDescriptorSet {
+        let mut fds = FileDescriptorSet::new();
+        fds.file.push(
+            Duration::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+        );
+        fds
+    }
+
+    #[test]
+    fn gen_all() {
+        let mut customize = Customize::default();
+        customize.gen_mod_rs = Some(true);
+        let files = gen_from_descriptor_set(&duration_fds(), &customize);
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(vec!["duration.rs", "mod.rs"], names);
+        assert!(files[0].content.contains("pub struct Duration"));
+        assert_eq!(
+            files[1].content,
+            "// @generated\n\
+             \n\
+             pub mod duration;\n\
+             \n\
+             /// Messages and enums defined in all files of this module\n\
+             #[allow(dead_code)]\n\
+             pub fn registered_types() -> ::protobuf::reflect::RegisteredTypes {\n    \
+                 let mut types = ::protobuf::reflect::RegisteredTypes::new();\n    \
+                 types.extend(duration::registered_types());\n    \
+                 types\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn gen_mod_rs_features() {
+        let mut customize = Customize::default();
+        customize.gen_mod_rs = Some(true);
+        customize.gen_mod_rs_features = Some(true);
+        let files = gen_from_descriptor_set(&duration_fds(), &customize);
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(vec!["duration.rs", "mod.rs", "features.toml"], names);
+        assert_eq!(
+            files[1].content,
+            "// @generated\n\
+             \n\
+             #[cfg(feature = \"google_protobuf\")]\n\
+             pub mod duration;\n\
+             \n\
+             /// Messages and enums defined in all files of this module\n\
+             #[allow(dead_code)]\n\
+             pub fn registered_types() -> ::protobuf::reflect::RegisteredTypes {\n    \
+                 let mut types = ::protobuf::reflect::RegisteredTypes::new();\n    \
+                 #[cfg(feature = \"google_protobuf\")]\n    \
+                 types.extend(duration::registered_types());\n    \
+                 types\n\
+             }\n"
+        );
+        assert_eq!(
+            files[2].content,
+            "# @generated\n\n[features]\ngoogle_protobuf = []\n"
+        );
+    }
+
+    #[test]
+    fn file_header() {
+        let mut customize = Customize::default();
+        customize.gen_mod_rs = Some(true);
+        customize.file_header = Some("// Copyright Example\n#![allow(clippy::all)]".to_owned());
+        customize.default_file_attributes = Some(false);
+        let files = gen_from_descriptor_set(&duration_fds(), &customize);
+        for f in &files {
+            assert!(f
+                .content
+                .starts_with("// Copyright Example\n#![allow(clippy::all)]\n\n// "));
+        }
+        assert!(!files[0].content.contains("#![rustfmt::skip]"));
+        assert!(files[0].content.contains("pub struct Duration"));
+    }
+
+    #[test]
+    fn well_known_types() {
+        use protobuf::descriptor::field_descriptor_proto::Type;
+        use protobuf::descriptor::DescriptorProto;
+        use protobuf::descriptor::FieldDescriptorProto;
+        use protobuf::descriptor::FileDescriptorProto;
+        use protobuf::well_known_types::Any;
+        use protobuf::well_known_types::Field;
+        use protobuf::well_known_types::SourceContext;
+
+        let mut fds = FileDescriptorSet::new();
+        for d in &[
+            Any::descriptor_static(),
+            SourceContext::descriptor_static(),
+            Field::descriptor_static(),
+        ] {
+            fds.file.push(d.file_descriptor_proto().clone());
+        }
+
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("cardinality".to_owned());
+        field.set_number(1);
+        field.set_field_type(Type::TYPE_ENUM);
+        field.set_type_name(".google.protobuf.Field.Cardinality".to_owned());
+        let mut message = DescriptorProto::new();
+        message.set_name("Column".to_owned());
+        message.field.push(field);
+        let mut file = FileDescriptorProto::new();
+        file.set_name("column.proto".to_owned());
+        file.set_syntax("proto3".to_owned());
+        file.dependency
+            .push("google/protobuf/type.proto".to_owned());
+        file.message_type.push(message);
+        fds.file.push(file);
+
+        let gen = |customize: &Customize| {
+            let files =
+                gen_from_descriptor_set_files(&fds, &[PathBuf::from("column.proto")], customize);
+            assert_eq!(1, files.len());
+            files.into_iter().next().unwrap().content
+        };
+
+        let content = gen(&Customize::default());
+        assert!(content.contains("::protobuf::well_known_types::field::Cardinality"));
+        assert!(content.contains("::protobuf::well_known_types::file_descriptors::type_pb()"));
+
+        let mut customize = Customize::default();
+        customize.protobuf_well_known_types = Some(false);
+        let content = gen(&customize);
+        assert!(!content.contains("well_known_types"));
+        assert!(content.contains("super::type_pb::field::Cardinality"));
+        assert!(content.contains("super::type_pb::file_descriptor()"));
+    }
+
+    #[test]
+    fn gen_bytes() {
+        let bytes = duration_fds().write_to_bytes().unwrap();
+        let files = gen_from_descriptor_set_bytes(&bytes, &Customize::default()).unwrap();
+        assert_eq!(1, files.len());
+        assert!(gen_from_descriptor_set_bytes(&[0xff], &Customize::default()).is_err());
+    }
+}
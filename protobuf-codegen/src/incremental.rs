@@ -0,0 +1,261 @@
+//! Skip code generation when inputs did not change.
+//!
+//! [`gen_and_write`](crate::gen_and_write) stores a hash of its inputs and
+//! names and content hashes of generated files in a stamp file in the output directory.
+//! When the inputs hash matches and all files exist with the same content,
+//! generation is skipped.
+//! Otherwise only files with changed content are rewritten, so
+//! modification times of unchanged files are preserved.
+//!
+//! Incremental mode can be disabled with [`Customize::incremental`].
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use protobuf::descriptor::FileDescriptorProto;
+use protobuf::Message;
+
+use crate::amend_io_error;
+use crate::Customize;
+
+/// Name of the file with inputs hash in the output directory.
+pub(crate) const STAMP_FILE_NAME: &str = ".rust-protobuf-codegen-stamp";
+
+/// FNV-1a, hash must be the same across Rust releases and platforms.
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Fnv64 {
+        Fnv64(0xcbf29ce484222325)
+    }
+
+    /// Write length-prefixed bytes so adjacent inputs do not collide.
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn write_option<T: fmt::Display>(&mut self, name: &str, value: &Option<T>) {
+        self.write(name.as_bytes());
+        match value {
+            Some(value) => self.write(value.to_string().as_bytes()),
+            None => self.write(b"-"),
+        }
+    }
+}
+
+fn content_hash(content: &[u8]) -> u64 {
+    let mut hasher = Fnv64::new();
+    hasher.write(content);
+    hasher.0
+}
+
+/// Hash options which affect generated code.
+fn write_customize(hasher: &mut Fnv64, customize: &Customize) {
+    // Destructure so a new option cannot be forgotten here
+    let Customize {
+        expose_oneof,
+        expose_fields,
+        generate_accessors,
+        generate_getter,
+        private_fields,
+        carllerche_bytes_for_bytes,
+        carllerche_bytes_for_string,
+        serde_derive,
+        serde_derive_cfg,
+        lite_runtime,
+        gen_mod_rs,
+        gen_mod_rs_features,
+        inside_protobuf,
+        small_vec_for_repeated,
+        interned_string_for_string,
+        singular_field_option_box,
+        singular_field_option,
+        singular_field_option_arc,
+        derive_hash_eq_ord,
+        derive_hash_eq_ord_skip_ineligible,
+        impl_try_from_bytes,
+        iterative_drop,
+        generate_view,
+        protobuf_well_known_types,
+        file_header,
+        default_file_attributes,
+        discard_unknown_fields,
+        uniform_accessors,
+        // Does not affect generated code
+        incremental: _,
+        _future_options: (),
+    } = customize;
+    hasher.write_option("expose_oneof", expose_oneof);
+    hasher.write_option("expose_fields", expose_fields);
+    hasher.write_option("generate_accessors", generate_accessors);
+    hasher.write_option("generate_getter", generate_getter);
+    hasher.write_option("private_fields", private_fields);
+    hasher.write_option("carllerche_bytes_for_bytes", carllerche_bytes_for_bytes);
+    hasher.write_option("carllerche_bytes_for_string", carllerche_bytes_for_string);
+    hasher.write_option("serde_derive", serde_derive);
+    hasher.write_option("serde_derive_cfg", serde_derive_cfg);
+    hasher.write_option("lite_runtime", lite_runtime);
+    hasher.write_option("gen_mod_rs", gen_mod_rs);
+    hasher.write_option("gen_mod_rs_features", gen_mod_rs_features);
+    hasher.write_option("inside_protobuf", inside_protobuf);
+    hasher.write_option("small_vec_for_repeated", small_vec_for_repeated);
+    hasher.write_option("interned_string_for_string", interned_string_for_string);
+    hasher.write_option("singular_field_option_box", singular_field_option_box);
+    hasher.write_option("singular_field_option", singular_field_option);
+    hasher.write_option("singular_field_option_arc", singular_field_option_arc);
+    hasher.write_option("derive_hash_eq_ord", derive_hash_eq_ord);
+    hasher.write_option(
+        "derive_hash_eq_ord_skip_ineligible",
+        derive_hash_eq_ord_skip_ineligible,
+    );
+    hasher.write_option("impl_try_from_bytes", impl_try_from_bytes);
+    hasher.write_option("iterative_drop", iterative_drop);
+    hasher.write_option("generate_view", generate_view);
+    hasher.write_option("protobuf_well_known_types", protobuf_well_known_types);
+    hasher.write_option("file_header", file_header);
+    hasher.write_option("default_file_attributes", default_file_attributes);
+    hasher.write_option("discard_unknown_fields", discard_unknown_fields);
+    hasher.write_option("uniform_accessors", uniform_accessors);
+}
+
+/// Hash of everything which affects generated code.
+pub(crate) fn inputs_hash(
+    file_descriptors: &[FileDescriptorProto],
+    parser: &str,
+    files_to_generate: &[PathBuf],
+    customize: &Customize,
+) -> u64 {
+    let mut hasher = Fnv64::new();
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.write(parser.as_bytes());
+    for file in file_descriptors {
+        hasher.write(&file.write_to_bytes().expect("serialize file descriptor"));
+    }
+    for file in files_to_generate {
+        hasher.write(file.to_string_lossy().as_bytes());
+    }
+    write_customize(&mut hasher, customize);
+    hasher.0
+}
+
+/// Generation is not needed: stamp has the same hash
+/// and all files exist with the content they were generated with.
+pub(crate) fn is_up_to_date(out_dir: &Path, hash: u64) -> bool {
+    let stamp = match fs::read_to_string(out_dir.join(STAMP_FILE_NAME)) {
+        Ok(stamp) => stamp,
+        Err(_) => return false,
+    };
+    let mut lines = stamp.lines();
+    if lines.next() != Some(&format!("{:016x}", hash)[..]) {
+        return false;
+    }
+    lines.all(|line| {
+        let (file_hash, name) = match line.find(' ') {
+            Some(space) => (&line[..space], &line[space + 1..]),
+            None => return false,
+        };
+        match fs::read(out_dir.join(name)) {
+            Ok(content) => file_hash == format!("{:016x}", content_hash(&content)),
+            Err(_) => false,
+        }
+    })
+}
+
+pub(crate) fn write_stamp<'a>(
+    out_dir: &Path,
+    hash: u64,
+    files: impl Iterator<Item = (&'a str, &'a [u8])>,
+) -> io::Result<()> {
+    let mut stamp = format!("{:016x}\n", hash);
+    for (name, content) in files {
+        stamp.push_str(&format!("{:016x} {}\n", content_hash(content), name));
+    }
+    write_if_changed(&out_dir.join(STAMP_FILE_NAME), stamp.as_bytes())
+}
+
+/// Write file unless it already has the same content.
+pub(crate) fn write_if_changed(path: &Path, content: &[u8]) -> io::Result<()> {
+    if let Ok(existing) = fs::read(path) {
+        if existing == content {
+            return Ok(());
+        }
+    }
+    fs::write(path, content).map_err(|e| amend_io_error(e, format!("failed to write {:?}", path)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "protobuf-codegen-incremental-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn hash_depends_on_inputs() {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        let files = vec![PathBuf::from("a.proto")];
+        let customize = Customize::default();
+        let hash = inputs_hash(&[file.clone()], "p", &files, &customize);
+        assert_eq!(hash, inputs_hash(&[file.clone()], "p", &files, &customize));
+        assert_ne!(hash, inputs_hash(&[file.clone()], "q", &files, &customize));
+        let mut customize_mod_rs = Customize::default();
+        customize_mod_rs.gen_mod_rs = Some(true);
+        assert_ne!(
+            hash,
+            inputs_hash(&[file.clone()], "p", &files, &customize_mod_rs)
+        );
+        let mut customize_incremental = Customize::default();
+        customize_incremental.incremental = Some(false);
+        assert_eq!(
+            hash,
+            inputs_hash(&[file.clone()], "p", &files, &customize_incremental)
+        );
+        file.set_package("pkg".to_owned());
+        assert_ne!(hash, inputs_hash(&[file], "p", &files, &customize));
+    }
+
+    #[test]
+    fn stamp() {
+        let dir = temp_dir("stamp");
+        assert!(!is_up_to_date(&dir, 1));
+        fs::write(dir.join("a.rs"), "a").unwrap();
+        write_stamp(&dir, 1, vec![("a.rs", &b"a"[..])].into_iter()).unwrap();
+        assert!(is_up_to_date(&dir, 1));
+        assert!(!is_up_to_date(&dir, 2));
+        // Output file edited by hand
+        fs::write(dir.join("a.rs"), "b").unwrap();
+        assert!(!is_up_to_date(&dir, 1));
+        fs::remove_file(dir.join("a.rs")).unwrap();
+        assert!(!is_up_to_date(&dir, 1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unchanged_file_not_rewritten() {
+        let dir = temp_dir("unchanged");
+        let path = dir.join("a.rs");
+        write_if_changed(&path, b"x").unwrap();
+        let mtime = fs::metadata(&path).unwrap().modified().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        write_if_changed(&path, b"x").unwrap();
+        assert_eq!(mtime, fs::metadata(&path).unwrap().modified().unwrap());
+        write_if_changed(&path, b"y").unwrap();
+        assert_eq!(b"y".to_vec(), fs::read(&path).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
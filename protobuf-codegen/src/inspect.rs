@@ -0,0 +1,365 @@
+//! Decode binary messages using descriptors for debugging.
+//!
+//! This is the library part of `protobuf-inspect` binary.
+//! Message type is looked up in a `FileDescriptorSet`
+//! (generated with `protoc --include_imports -o`),
+//! and decoded message is printed as text format or JSON.
+//! [`decode_raw`] prints messages without descriptors similar
+//! to `protoc --decode_raw`.
+//!
+//! ```
+//! # use protobuf_codegen::inspect;
+//! // field 1 is varint 150, field 2 is message with string "ab" in field 3
+//! let text = inspect::decode_raw(b"\x08\x96\x01\x12\x04\x1a\x02ab").unwrap();
+//! assert_eq!("1: 150\n2 {\n  3: \"ab\"\n}\n", text);
+//! ```
+
+use std::fmt;
+
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::json;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::text_format;
+use protobuf::text_format::Unredacted;
+use protobuf::wire_format::WireType;
+use protobuf::CodedInputStream;
+use protobuf::MessageDyn;
+use protobuf::ProtobufError;
+
+/// Encoding of input message bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Bytes as is.
+    Binary,
+    /// Hexadecimal digits, whitespace is ignored.
+    Hex,
+    /// Standard or URL-safe base64, padding and whitespace are ignored.
+    Base64,
+}
+
+/// How decoded message is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Multiline text format.
+    Text,
+    /// JSON.
+    Json,
+}
+
+/// Error of decoding a message.
+#[derive(Debug)]
+pub enum InspectError {
+    /// Input is not valid hex or base64.
+    InvalidInput(String),
+    /// Message type is not found in descriptor set.
+    MessageNotFound(String),
+    /// Message cannot be parsed.
+    Parse(ProtobufError),
+    /// Group with this field number is not terminated properly in raw input.
+    InvalidGroup(u32),
+    /// Message cannot be printed as JSON.
+    Json(json::PrintError),
+}
+
+impl fmt::Display for InspectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InspectError::InvalidInput(e) => write!(f, "invalid input: {}", e),
+            InspectError::MessageNotFound(name) => {
+                write!(f, "message `{}` not found in descriptor set", name)
+            }
+            InspectError::Parse(e) => write!(f, "failed to parse message: {}", e),
+            InspectError::InvalidGroup(n) => write!(f, "unterminated group {}", n),
+            InspectError::Json(e) => write!(f, "failed to print JSON: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for InspectError {}
+
+impl From<ProtobufError> for InspectError {
+    fn from(e: ProtobufError) -> Self {
+        InspectError::Parse(e)
+    }
+}
+
+/// Decode input bytes according to the format.
+pub fn decode_input(format: InputFormat, input: &[u8]) -> Result<Vec<u8>, InspectError> {
+    match format {
+        InputFormat::Binary => Ok(input.to_vec()),
+        InputFormat::Hex => decode_hex(input),
+        InputFormat::Base64 => decode_base64(input),
+    }
+}
+
+fn decode_hex(input: &[u8]) -> Result<Vec<u8>, InspectError> {
+    let digits: Vec<u8> = input
+        .iter()
+        .cloned()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    if digits.len() % 2 != 0 {
+        return Err(InspectError::InvalidInput(
+            "odd number of hex digits".to_owned(),
+        ));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).unwrap_or("");
+            u8::from_str_radix(pair, 16)
+                .map_err(|_| InspectError::InvalidInput(format!("invalid hex: {:?}", pair)))
+        })
+        .collect()
+}
+
+fn decode_base64(input: &[u8]) -> Result<Vec<u8>, InspectError> {
+    let mut r = Vec::with_capacity(input.len() * 3 / 4);
+    let mut acc: u32 = 0;
+    let mut bits = 0;
+    for &c in input {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => continue,
+            c if c.is_ascii_whitespace() => continue,
+            c => {
+                return Err(InspectError::InvalidInput(format!(
+                    "invalid base64 character: {:?}",
+                    c as char
+                )))
+            }
+        };
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            r.push((acc >> bits) as u8);
+        }
+    }
+    Ok(r)
+}
+
+/// Find message type by fully qualified name (leading dot is optional).
+pub fn find_message(
+    fds: &FileDescriptorSet,
+    message: &str,
+) -> Result<MessageDescriptor, InspectError> {
+    let message = message.strip_prefix('.').unwrap_or(message);
+    let files = FileDescriptor::new_dynamic_fds(fds.file.clone());
+    files
+        .iter()
+        .filter_map(|f| f.message_by_full_name(&format!(".{}", message)))
+        .next()
+        .ok_or_else(|| InspectError::MessageNotFound(message.to_owned()))
+}
+
+/// Parse message of given type and print it.
+///
+/// Values of sensitive fields are redacted unless `unredacted` is set.
+pub fn decode(
+    descriptor: &MessageDescriptor,
+    bytes: &[u8],
+    output: OutputFormat,
+    unredacted: bool,
+) -> Result<String, InspectError> {
+    let mut message = descriptor.new_instance();
+    message
+        .merge_from_bytes_dyn(bytes)
+        .map_err(InspectError::Parse)?;
+    print(&*message, output, unredacted)
+}
+
+fn print(
+    message: &dyn MessageDyn,
+    output: OutputFormat,
+    unredacted: bool,
+) -> Result<String, InspectError> {
+    match output {
+        OutputFormat::Text => {
+            struct Redacted<'a>(&'a dyn MessageDyn);
+            impl<'a> fmt::Debug for Redacted<'a> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    text_format::fmt(self.0, f)
+                }
+            }
+            Ok(if unredacted {
+                format!("{:#?}", Unredacted(message))
+            } else {
+                format!("{:#?}", Redacted(message))
+            })
+        }
+        OutputFormat::Json => {
+            let options = json::PrintOptions {
+                unredacted,
+                ..Default::default()
+            };
+            let mut r = json::print_to_string_with_options(message, &options)
+                .map_err(InspectError::Json)?;
+            r.push('\n');
+            Ok(r)
+        }
+    }
+}
+
+/// Print fields of a message without descriptor like `protoc --decode_raw`.
+///
+/// Length-delimited values which parse as messages are printed as nested messages,
+/// other length-delimited values are printed as escaped strings.
+pub fn decode_raw(bytes: &[u8]) -> Result<String, InspectError> {
+    let mut buf = String::new();
+    let mut is = CodedInputStream::from_bytes(bytes);
+    decode_raw_fields(&mut is, &mut buf, 0, None)?;
+    Ok(buf)
+}
+
+fn write_indent(buf: &mut String, indent: usize) {
+    for _ in 0..indent {
+        buf.push_str("  ");
+    }
+}
+
+fn decode_raw_fields(
+    is: &mut CodedInputStream,
+    buf: &mut String,
+    indent: usize,
+    group: Option<u32>,
+) -> Result<(), InspectError> {
+    loop {
+        if is.eof()? {
+            return match group {
+                None => Ok(()),
+                Some(n) => Err(InspectError::InvalidGroup(n)),
+            };
+        }
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        if wire_type == WireType::WireTypeEndGroup {
+            return match group {
+                Some(n) if n == field_number => Ok(()),
+                _ => Err(InspectError::InvalidGroup(field_number)),
+            };
+        }
+        write_indent(buf, indent);
+        match wire_type {
+            WireType::WireTypeVarint => {
+                buf.push_str(&format!("{}: {}\n", field_number, is.read_raw_varint64()?));
+            }
+            WireType::WireTypeFixed32 => {
+                buf.push_str(&format!("{}: 0x{:08x}\n", field_number, is.read_fixed32()?));
+            }
+            WireType::WireTypeFixed64 => {
+                buf.push_str(&format!(
+                    "{}: 0x{:016x}\n",
+                    field_number,
+                    is.read_fixed64()?
+                ));
+            }
+            WireType::WireTypeLengthDelimited => {
+                let bytes = is.read_bytes()?;
+                let mut nested = String::new();
+                let parsed = !bytes.is_empty()
+                    && decode_raw_fields(
+                        &mut CodedInputStream::from_bytes(&bytes),
+                        &mut nested,
+                        indent + 1,
+                        None,
+                    )
+                    .is_ok();
+                if parsed {
+                    buf.push_str(&format!("{} {{\n", field_number));
+                    buf.push_str(&nested);
+                    write_indent(buf, indent);
+                    buf.push_str("}\n");
+                } else {
+                    buf.push_str(&format!("{}: \"", field_number));
+                    text_format::quote_bytes_to(&bytes, buf);
+                    buf.push_str("\"\n");
+                }
+            }
+            WireType::WireTypeStartGroup => {
+                buf.push_str(&format!("{} {{\n", field_number));
+                decode_raw_fields(is, buf, indent + 1, Some(field_number))?;
+                write_indent(buf, indent);
+                buf.push_str("}\n");
+            }
+            WireType::WireTypeEndGroup => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::field_descriptor_proto::Type;
+    use protobuf::descriptor::DescriptorProto;
+    use protobuf::descriptor::FieldDescriptorProto;
+    use protobuf::descriptor::FileDescriptorProto;
+
+    use super::*;
+
+    #[test]
+    fn input_formats() {
+        assert_eq!(
+            b"\x08\x96\x01".to_vec(),
+            decode_input(InputFormat::Hex, b"08 96\n01").unwrap()
+        );
+        assert!(decode_input(InputFormat::Hex, b"089").is_err());
+        assert_eq!(
+            b"\x08\x96\x01\xff".to_vec(),
+            decode_input(InputFormat::Base64, b"CJYB/w==").unwrap()
+        );
+        assert_eq!(
+            b"\x08\x96\x01\xff".to_vec(),
+            decode_input(InputFormat::Base64, b"CJYB_w").unwrap()
+        );
+        assert!(decode_input(InputFormat::Base64, b"CJ*B").is_err());
+    }
+
+    #[test]
+    fn raw() {
+        assert_eq!(
+            "1: 0x00000001\n2: 0x0000000000000002\n3: \"\"\n4 {\n  5: 7\n}\n",
+            decode_raw(
+                b"\x0d\x01\x00\x00\x00\x11\x02\x00\x00\x00\x00\x00\x00\x00\x1a\x00\x23\x28\x07\x24"
+            )
+            .unwrap()
+        );
+        // not a message
+        assert_eq!("1: \"\\377\"\n", decode_raw(b"\x0a\x01\xff").unwrap());
+        assert!(decode_raw(b"\x08").is_err());
+    }
+
+    #[test]
+    fn with_descriptor() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("text".to_owned());
+        field.set_json_name("text".to_owned());
+        field.set_number(3);
+        field.set_field_type(Type::TYPE_STRING);
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        message.field.push(field);
+        let mut file = FileDescriptorProto::new();
+        file.set_name("m.proto".to_owned());
+        file.set_package("p".to_owned());
+        file.set_syntax("proto3".to_owned());
+        file.message_type.push(message);
+        let mut fds = FileDescriptorSet::new();
+        fds.file.push(file);
+
+        let descriptor = find_message(&fds, ".p.M").unwrap();
+        assert_eq!(
+            "{\"text\": \"ab\"}\n",
+            decode(&descriptor, b"\x1a\x02ab", OutputFormat::Json, false).unwrap()
+        );
+        assert!(
+            decode(&descriptor, b"\x1a\x02ab", OutputFormat::Text, false)
+                .unwrap()
+                .contains("text: \"ab\"")
+        );
+        assert!(find_message(&fds, "p.N").is_err());
+    }
+}
@@ -12,22 +12,29 @@ use protobuf::descriptor::*;
 use protobuf::Message;
 
 mod amend_io_error_util;
+pub mod breaking;
 pub mod case_convert;
 mod compiler_plugin;
 mod customize;
+pub mod descriptor_diff;
 mod enums;
 mod extensions;
 mod field;
 mod file;
 pub(crate) mod file_and_mod;
+mod in_memory;
+mod incremental;
 mod inside;
+pub mod inspect;
 mod map;
 mod message;
 mod oneof;
+mod package_features;
 mod protobuf_abs_path;
 mod protobuf_ident;
 mod protobuf_path;
 mod protobuf_rel_path;
+mod runtime_compat;
 mod rust_name;
 mod rust_types_values;
 mod serde;
@@ -40,6 +47,10 @@ pub(crate) mod syntax;
 
 use customize::customize_from_rustproto_for_file;
 pub use customize::Customize;
+pub use in_memory::gen_from_descriptor_set;
+pub use in_memory::gen_from_descriptor_set_bytes;
+pub use in_memory::gen_from_descriptor_set_files;
+pub use in_memory::GeneratedFile;
 
 pub mod code_writer;
 
@@ -216,6 +227,43 @@ fn write_file_descriptor_data(file: &FileDescriptor, customize: &Customize, w: &
     );
     w.write_line("");
     write_file_descriptor(file, &customize, w);
+    w.write_line("");
+    write_file_descriptor_set_bytes(customize, w);
+    w.write_line("");
+    write_registered_types(customize, w);
+}
+
+fn write_registered_types(customize: &Customize, w: &mut CodeWriter) {
+    w.write_line("/// Messages and enums defined in this file, including nested types");
+    w.pub_fn(
+        &format!(
+            "registered_types() -> {}::reflect::RegisteredTypes",
+            protobuf_crate_path(customize)
+        ),
+        |w| {
+            w.write_line(&format!(
+                "{}::reflect::RegisteredTypes::of_file(&file_descriptor())",
+                protobuf_crate_path(customize)
+            ));
+        },
+    );
+}
+
+fn write_file_descriptor_set_bytes(customize: &Customize, w: &mut CodeWriter) {
+    w.write_line("/// Serialized `FileDescriptorSet` with this file and all its dependencies");
+    w.pub_fn("file_descriptor_set_bytes() -> &'static [u8]", |w| {
+        w.lazy_static(
+            "file_descriptor_set_bytes_lazy",
+            "::std::vec::Vec<u8>",
+            &format!("{}", protobuf_crate_path(customize)),
+        );
+        w.block("file_descriptor_set_bytes_lazy.get(|| {", "})", |w| {
+            w.write_line(&format!(
+                "{}::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()",
+                protobuf_crate_path(customize)
+            ));
+        });
+    });
 }
 
 pub(crate) struct FileIndex {
@@ -247,6 +295,18 @@ impl FileIndex {
 struct GenFileResult {
     compiler_plugin_result: compiler_plugin::GenResult,
     mod_name: String,
+    /// `registered_types()` function is generated
+    has_registered_types: bool,
+}
+
+/// Write `file_header` option text.
+fn write_file_header(customize: &Customize, w: &mut CodeWriter) {
+    if let Some(header) = &customize.file_header {
+        for line in header.lines() {
+            w.write_line(line);
+        }
+        w.write_line("");
+    }
 }
 
 fn gen_file(
@@ -281,7 +341,11 @@ fn gen_file(
     {
         let mut w = CodeWriter::new(&mut v);
 
+        write_file_header(&customize, &mut w);
         w.write_generated_by("rust-protobuf", env!("CARGO_PKG_VERSION"), parser);
+        if customize.default_file_attributes != Some(false) {
+            w.write_default_file_attributes();
+        }
 
         w.write_line("");
         w.write_line(&format!(
@@ -289,14 +353,7 @@ fn gen_file(
             file_descriptor.proto().get_name()
         ));
         if customize.inside_protobuf != Some(true) {
-            w.write_line("");
-            w.write_line("/// Generated files are compatible only with the same version");
-            w.write_line("/// of protobuf runtime.");
-            w.write_line(&format!(
-                "const _PROTOBUF_VERSION_CHECK: () = {}::{};",
-                protobuf_crate_path(&customize),
-                protobuf::VERSION_IDENT
-            ));
+            runtime_compat::write_runtime_compat_check(file_descriptor.proto(), &customize, &mut w);
         }
 
         static NESTED_TYPE_NUMBER: protobuf::rt::LazyV2<i32> = protobuf::rt::LazyV2::INIT;
@@ -321,6 +378,7 @@ fn gen_file(
                     &file_index,
                     &root_scope,
                     &customize,
+                    &customize,
                     &path,
                     file_descriptor.proto().source_code_info.as_ref(),
                 )
@@ -367,17 +425,53 @@ fn gen_file(
             content: v,
         },
         mod_name: proto_path_to_rust_mod(file_descriptor.proto().get_name()).into_string(),
+        has_registered_types: !lite_runtime,
     }
 }
 
-fn gen_mod_rs(mods: &[String]) -> compiler_plugin::GenResult {
+fn gen_mod_rs(
+    mods: &[String],
+    features: &[Option<String>],
+    has_registered_types: &[bool],
+    customize: &Customize,
+) -> compiler_plugin::GenResult {
     let mut v = Vec::new();
     let mut w = CodeWriter::new(&mut v);
+    write_file_header(customize, &mut w);
     w.comment("@generated");
     w.write_line("");
-    for m in mods {
+    for (m, feature) in mods.iter().zip(features) {
+        if let Some(feature) = feature {
+            w.write_line(&format!("#[cfg(feature = \"{}\")]", feature));
+        }
         w.write_line(&format!("pub mod {};", m));
     }
+    w.write_line("");
+    w.write_line("/// Messages and enums defined in all files of this module");
+    // `mod.rs` may be included without file attributes of generated files
+    w.write_line("#[allow(dead_code)]");
+    w.pub_fn(
+        &format!(
+            "registered_types() -> {}::reflect::RegisteredTypes",
+            protobuf_crate_path(customize)
+        ),
+        |w| {
+            w.write_line(&format!(
+                "let mut types = {}::reflect::RegisteredTypes::new();",
+                protobuf_crate_path(customize)
+            ));
+            for ((m, feature), has) in mods.iter().zip(features).zip(has_registered_types) {
+                if !has {
+                    continue;
+                }
+                if let Some(feature) = feature {
+                    w.write_line(&format!("#[cfg(feature = \"{}\")]", feature));
+                }
+                w.write_line(&format!("types.extend({}::registered_types());", m));
+            }
+            w.write_line("types");
+        },
+    );
     drop(w);
     compiler_plugin::GenResult {
         name: "mod.rs".to_owned(),
@@ -407,6 +501,8 @@ pub fn gen(
         .collect();
 
     let mut mods = Vec::new();
+    let mut has_registered_types = Vec::new();
+    let mut generated_files = Vec::new();
 
     for file_name in files_to_generate {
         let file = files_map.get(file_name.as_path()).expect(&format!(
@@ -417,6 +513,8 @@ pub fn gen(
         let gen_file_result = gen_file(file, &files_map, &root_scope, customize, parser);
         results.push(gen_file_result.compiler_plugin_result);
         mods.push(gen_file_result.mod_name);
+        has_registered_types.push(gen_file_result.has_registered_types);
+        generated_files.push(*file);
     }
 
     if customize.inside_protobuf.unwrap_or(false) {
@@ -424,12 +522,37 @@ pub fn gen(
     }
 
     if customize.gen_mod_rs.unwrap_or(false) {
-        results.push(gen_mod_rs(&mods));
+        let gen_features = customize.gen_mod_rs_features.unwrap_or(false);
+        let features: Vec<Option<String>> = generated_files
+            .iter()
+            .map(|f| {
+                if gen_features {
+                    package_features::package_feature(f.proto().get_package())
+                } else {
+                    None
+                }
+            })
+            .collect();
+        results.push(gen_mod_rs(
+            &mods,
+            &features,
+            &has_registered_types,
+            customize,
+        ));
+        if gen_features {
+            results.push(package_features::gen_features_toml(&generated_files));
+        }
     }
 
     results
 }
 
+/// Generate code and write it to `out_dir`.
+///
+/// Generation is skipped when inputs did not change since the previous
+/// invocation with the same `out_dir` and generated files were not modified,
+/// and files with unchanged content are not rewritten, which preserves
+/// their modification times. This can be disabled with [`Customize::incremental`].
 pub fn gen_and_write(
     file_descriptors: &[FileDescriptorProto],
     parser: &str,
@@ -454,32 +577,49 @@ pub fn gen_and_write(
         }
     }
 
+    if !customize.incremental.unwrap_or(true) {
+        for r in gen(file_descriptors, parser, files_to_generate, customize) {
+            let mut file_path = out_dir.to_owned();
+            file_path.push(&r.name);
+            let mut file_writer = File::create(&file_path)
+                .map_err(|e| amend_io_error(e, format!("failed to create {:?}", file_path)))?;
+            file_writer
+                .write_all(&r.content)
+                .map_err(|e| amend_io_error(e, format!("failed to write to {:?}", file_path)))?;
+            file_writer
+                .flush()
+                .map_err(|e| amend_io_error(e, format!("failed to flush {:?}", file_path)))?;
+        }
+        return Ok(());
+    }
+
+    let hash = incremental::inputs_hash(file_descriptors, parser, files_to_generate, customize);
+    if incremental::is_up_to_date(out_dir, hash) {
+        return Ok(());
+    }
+
     let results = gen(file_descriptors, parser, files_to_generate, customize);
 
     for r in &results {
-        let mut file_path = out_dir.to_owned();
-        file_path.push(&r.name);
-        let mut file_writer = File::create(&file_path)
-            .map_err(|e| amend_io_error(e, format!("failed to create {:?}", file_path)))?;
-        file_writer
-            .write_all(&r.content)
-            .map_err(|e| amend_io_error(e, format!("failed to write to {:?}", file_path)))?;
-        file_writer
-            .flush()
-            .map_err(|e| amend_io_error(e, format!("failed to flush {:?}", file_path)))?;
+        incremental::write_if_changed(&out_dir.join(&r.name), &r.content)?;
     }
 
-    Ok(())
+    incremental::write_stamp(
+        out_dir,
+        hash,
+        results.iter().map(|r| (r.name.as_str(), &r.content[..])),
+    )
 }
 
 pub fn protoc_gen_rust_main() {
     compiler_plugin::plugin_main(|r| {
-        let customize = Customize::parse_from_parameter(r.parameter).expect("parse options");
-        gen(
+        let customize = Customize::parse_from_parameter(r.parameter)
+            .map_err(|e| format!("incorrect parameter {:?}: {}", r.parameter, e))?;
+        Ok(gen(
             r.file_descriptors,
             "protoc --rust-out=...",
             r.files_to_generate,
             &customize,
-        )
+        ))
     });
 }
@@ -7,6 +7,7 @@ use super::enums::*;
 use super::field::*;
 use super::rust_types_values::*;
 use crate::case_convert::snake_case;
+use crate::extensions::write_extensions_in_mod;
 use crate::file_and_mod::FileAndMod;
 use crate::inside::protobuf_crate_path;
 use crate::oneof::OneofGen;
@@ -21,8 +22,14 @@ use crate::scope::RootScope;
 use crate::scope::WithScope;
 use crate::serde;
 use crate::FileIndex;
+use protobuf::reflect::FieldDescriptor;
 use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::RuntimeFieldType;
+use protobuf::reflect::RuntimeTypeBox;
+use protobuf::rustproto;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fmt;
 
 /// Protobuf message Rust type name
@@ -52,6 +59,91 @@ impl RustTypeMessage {
     }
 }
 
+/// Find fields which cannot be distinguished in JSON.
+///
+/// JSON parser accepts both field name and JSON name,
+/// so JSON name must not be equal to JSON name or name of another field.
+fn json_name_collision(message: &MessageDescriptor) -> Option<String> {
+    let names: HashMap<String, FieldDescriptor> = message
+        .fields()
+        .map(|f| (f.get_name().to_owned(), f))
+        .collect();
+    let mut json_names: HashMap<String, FieldDescriptor> = HashMap::new();
+    for field in message.fields() {
+        let json_name = field.json_name().to_owned();
+        if let Some(other) = names.get(&json_name) {
+            if other != &field {
+                return Some(format!(
+                    "JSON name `{}` of field `{}` is the name of field `{}`",
+                    json_name,
+                    field.get_name(),
+                    other.get_name()
+                ));
+            }
+        }
+        if let Some(other) = json_names.get(&json_name) {
+            return Some(format!(
+                "fields `{}` and `{}` have the same JSON name `{}`",
+                other.get_name(),
+                field.get_name(),
+                json_name
+            ));
+        }
+        json_names.insert(json_name, field);
+    }
+    None
+}
+
+/// Find why message cannot derive `Hash`, `Eq` and `Ord`.
+fn hash_eq_ord_ineligible_reason(
+    message: &MessageDescriptor,
+    inside_protobuf: bool,
+    visited: &mut HashSet<String>,
+) -> Option<String> {
+    if !visited.insert(message.full_name().to_owned()) {
+        // Recursive message, checked by the caller
+        return None;
+    }
+    if !message.get_proto().extension_range.is_empty() {
+        return Some(format!(
+            "message `{}` declares extension ranges",
+            message.full_name()
+        ));
+    }
+    if !inside_protobuf && message.file_descriptor_proto().get_package() == "google.protobuf" {
+        return Some(format!(
+            "message `{}` is generated in protobuf crate without these derives",
+            message.full_name()
+        ));
+    }
+    if message.fields().len() > 500 {
+        return Some(format!(
+            "message `{}` has too many fields",
+            message.full_name()
+        ));
+    }
+    for field in message.fields() {
+        let t = match field.runtime_field_type() {
+            RuntimeFieldType::Map(..) => {
+                return Some(format!("field `{}` is a map", field));
+            }
+            RuntimeFieldType::Singular(t) | RuntimeFieldType::Repeated(t) => t,
+        };
+        match t {
+            RuntimeTypeBox::F32 | RuntimeTypeBox::F64 => {
+                return Some(format!("field `{}` has floating point type", field));
+            }
+            RuntimeTypeBox::Message(m) => {
+                if let Some(reason) = hash_eq_ord_ineligible_reason(&m, inside_protobuf, visited) {
+                    return Some(reason);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 /// Message info for codegen
 pub(crate) struct MessageGen<'a> {
     file_descriptor: &'a FileDescriptor,
@@ -63,6 +155,8 @@ pub(crate) struct MessageGen<'a> {
     pub fields: Vec<FieldGen<'a>>,
     pub lite_runtime: bool,
     customize: Customize,
+    /// Customize of the file, used to find messages with views
+    file_customize: &'a Customize,
     path: &'a [i32],
     info: Option<&'a SourceCodeInfo>,
 }
@@ -74,6 +168,7 @@ impl<'a> MessageGen<'a> {
         file_index: &'a FileIndex,
         root_scope: &'a RootScope<'a>,
         customize: &Customize,
+        file_customize: &'a Customize,
         path: &'a [i32],
         info: Option<&'a SourceCodeInfo>,
     ) -> MessageGen<'a> {
@@ -81,6 +176,10 @@ impl<'a> MessageGen<'a> {
             .message_by_package_relative_name(message.protobuf_name_to_package().get())
             .unwrap();
 
+        if let Some(reason) = json_name_collision(&message_descriptor) {
+            panic!("message `{}`: {}", message_descriptor.full_name(), reason);
+        }
+
         let mut customize = customize.clone();
         customize.update_with(&customize_from_rustproto_for_message(
             message.message.get_proto().options.get_or_default(),
@@ -123,6 +222,7 @@ impl<'a> MessageGen<'a> {
             fields,
             lite_runtime,
             customize,
+            file_customize,
             path,
             info,
         }
@@ -136,8 +236,73 @@ impl<'a> MessageGen<'a> {
         self.message.scope.get_file_and_mod(self.customize.clone())
     }
 
+    /// Message declares `extensions` ranges, so it gets typed extension storage.
+    fn has_extension_ranges(&self) -> bool {
+        !self.message.message.get_proto().extension_range.is_empty()
+    }
+
+    /// Extensions are encoded as `MessageSet` items.
+    fn is_message_set(&self) -> bool {
+        self.has_extension_ranges()
+            && self
+                .message
+                .message
+                .get_proto()
+                .options
+                .get_or_default()
+                .get_message_set_wire_format()
+    }
+
+    /// `rustproto.max_size` option of the message.
+    fn max_size(&self) -> Option<u64> {
+        rustproto::exts::max_size.get(self.message.message.get_proto().options.get_or_default())
+    }
+
+    /// Write check of message size against `rustproto.max_size`.
+    fn write_check_message_size(&self, size: &str, w: &mut CodeWriter) {
+        if let Some(max_size) = self.max_size() {
+            w.write_line(&format!(
+                "{}::rt::check_message_size({:?}, {}, {})?;",
+                protobuf_crate_path(&self.customize),
+                self.message_descriptor.full_name(),
+                size,
+                max_size
+            ));
+        }
+    }
+
+    /// Derive `Hash`, `Eq` and `Ord` for message struct and oneof enums.
+    pub(crate) fn derive_hash_eq_ord(&self) -> bool {
+        if !self.customize.derive_hash_eq_ord.unwrap_or(false) {
+            return false;
+        }
+        let reason = hash_eq_ord_ineligible_reason(
+            &self.message_descriptor,
+            self.customize.inside_protobuf.unwrap_or(false),
+            &mut HashSet::new(),
+        );
+        match reason {
+            None => true,
+            Some(_)
+                if self
+                    .customize
+                    .derive_hash_eq_ord_skip_ineligible
+                    .unwrap_or(false) =>
+            {
+                false
+            }
+            Some(reason) => panic!(
+                "cannot derive `Hash`, `Eq` and `Ord` for message `{}`: {}; \
+                use `derive_hash_eq_ord_skip_ineligible` option to skip such messages",
+                self.message_descriptor.full_name(),
+                reason
+            ),
+        }
+    }
+
     fn expose_oneof(&self) -> bool {
-        self.customize.expose_oneof.unwrap_or(true)
+        !self.customize.private_fields.unwrap_or(false)
+            && self.customize.expose_oneof.unwrap_or(true)
     }
 
     fn oneofs(&'a self) -> Vec<OneofGen<'a>> {
@@ -239,6 +404,7 @@ impl<'a> MessageGen<'a> {
             protobuf_crate_path(&self.customize),
         );
         w.def_fn(&sig, |w| {
+            self.write_check_message_size("self.get_cached_size() as u64", w);
             // To have access to its methods but not polute the name space.
             for f in self.fields_except_oneof_and_group() {
                 f.write_message_write_field(w);
@@ -250,7 +416,17 @@ impl<'a> MessageGen<'a> {
                 };
                 variant.field.write_write_element(w, "os", &v);
             });
-            w.write_line("os.write_unknown_fields(self.get_unknown_fields())?;");
+            if self.is_message_set() {
+                w.write_line(&format!(
+                    "{}::rt::write_message_set(&self.extension_fields, self.get_unknown_fields(), os)?;",
+                    protobuf_crate_path(&self.customize)
+                ));
+            } else {
+                if self.has_extension_ranges() {
+                    w.write_line("os.write_extension_fields(&self.extension_fields)?;");
+                }
+                w.write_line("os.write_unknown_fields(self.get_unknown_fields())?;");
+            }
             w.write_line("::std::result::Result::Ok(())");
         });
     }
@@ -261,6 +437,227 @@ impl<'a> MessageGen<'a> {
         });
     }
 
+    fn write_space_used_excluding_self(&self, w: &mut CodeWriter) {
+        // there are unused variables in oneof
+        w.allow(&["unused_variables"]);
+        w.def_fn("space_used_excluding_self(&self) -> usize", |w| {
+            w.write_line("let mut my_size = 0;");
+            for field in self.fields_except_oneof_and_group() {
+                w.write_line(&format!(
+                    "my_size += {}::rt::space_used_excluding_self(&self.{});",
+                    protobuf_crate_path(&self.customize),
+                    field.rust_name
+                ));
+            }
+            self.write_match_each_oneof_variant(w, |w, variant, v, _| {
+                if !variant.field.elem_type_is_copy() {
+                    w.write_line(&format!(
+                        "my_size += {}::rt::space_used_excluding_self({});",
+                        protobuf_crate_path(&self.customize),
+                        v
+                    ));
+                }
+            });
+            if self.has_extension_ranges() {
+                w.write_line(&format!(
+                    "my_size += {}::rt::space_used_excluding_self(&self.extension_fields);",
+                    protobuf_crate_path(&self.customize)
+                ));
+            }
+            w.write_line(&format!(
+                "my_size += {}::rt::space_used_excluding_self(&self.unknown_fields);",
+                protobuf_crate_path(&self.customize)
+            ));
+            w.write_line("my_size");
+        });
+    }
+
+    fn write_validate_to(&self, w: &mut CodeWriter) {
+        w.def_fn(
+            &format!(
+                "validate_to(&self, v: &mut {}::validate::Violations)",
+                protobuf_crate_path(&self.customize)
+            ),
+            |w| {
+                for field in self.fields_except_group() {
+                    field.write_validate(w);
+                }
+            },
+        );
+    }
+
+    fn write_shrink_to_fit(&self, w: &mut CodeWriter) {
+        w.def_fn("shrink_to_fit(&mut self)", |w| {
+            for field in self.fields_except_oneof_and_group() {
+                w.write_line(&format!(
+                    "{}::rt::shrink_to_fit(&mut self.{});",
+                    protobuf_crate_path(&self.customize),
+                    field.rust_name
+                ));
+            }
+            for oneof in self.oneofs() {
+                let variants = oneof.variants_except_group();
+                let (copy, non_copy): (Vec<_>, Vec<_>) = variants
+                    .into_iter()
+                    .partition(|variant| variant.field.elem_type_is_copy());
+                if non_copy.is_empty() {
+                    continue;
+                }
+                w.if_let_stmt(
+                    "::std::option::Option::Some(ref mut v)",
+                    &format!("self.{}", oneof.oneof.field_name())[..],
+                    |w| {
+                        w.match_block("v", |w| {
+                            for variant in non_copy {
+                                w.case_block(
+                                    format!(
+                                        "&mut {}(ref mut v)",
+                                        variant.path(&self.get_file_and_mod())
+                                    ),
+                                    |w| {
+                                        w.write_line(&format!(
+                                            "{}::rt::shrink_to_fit(v);",
+                                            protobuf_crate_path(&self.customize)
+                                        ));
+                                    },
+                                );
+                            }
+                            if !copy.is_empty() {
+                                w.case_expr("_", "{}");
+                            }
+                        });
+                    },
+                );
+            }
+            if self.has_extension_ranges() {
+                w.write_line(&format!(
+                    "{}::rt::shrink_to_fit(&mut self.extension_fields);",
+                    protobuf_crate_path(&self.customize)
+                ));
+            }
+            w.write_line(&format!(
+                "{}::rt::shrink_to_fit(&mut self.unknown_fields);",
+                protobuf_crate_path(&self.customize)
+            ));
+        });
+    }
+
+    fn write_impl_drop_iterative(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        let fields: Vec<_> = self
+            .fields_except_oneof_and_group()
+            .into_iter()
+            .filter(|f| f.has_nested_messages())
+            .collect();
+        let oneofs = self.oneofs();
+        let oneof_variants: Vec<(&OneofGen, Vec<OneofVariantGen>)> = oneofs
+            .iter()
+            .map(|oneof| {
+                let variants = oneof
+                    .variants_except_group()
+                    .into_iter()
+                    .filter(|v| v.field.proto_type == field_descriptor_proto::Type::TYPE_MESSAGE)
+                    .collect();
+                (oneof, variants)
+            })
+            .filter(|(_, variants): &(_, Vec<_>)| !variants.is_empty())
+            .collect();
+        let worklist = if fields.is_empty() && oneof_variants.is_empty() {
+            "_worklist"
+        } else {
+            "worklist"
+        };
+        w.impl_for_block(
+            &format!("{}::rt::DropIterative", protobuf_crate),
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn(
+                    &format!(
+                        "take_nested_messages(&mut self, {}: &mut {}::rt::DropWorklist)",
+                        worklist, protobuf_crate
+                    ),
+                    |w| {
+                        for field in &fields {
+                            field.write_detach_nested_messages(w);
+                        }
+                        for (oneof, variants) in &oneof_variants {
+                            let field = format!("self.{}", oneof.oneof.field_name());
+                            // Boxed variants may be recursive, so they are moved to the worklist,
+                            // other variants are detached in place
+                            w.match_block(&format!("{}.take()", field), |w| {
+                                for variant in variants {
+                                    let path = variant.path(&self.get_file_and_mod());
+                                    if variant.boxed() {
+                                        w.case_expr(
+                                            format!("::std::option::Option::Some({}(v))", path),
+                                            "worklist.push(v)",
+                                        );
+                                    } else {
+                                        w.case_block(
+                                            format!("::std::option::Option::Some({}(mut v))", path),
+                                            |w| {
+                                                w.write_line(&format!(
+                                                    "{}::rt::DropIterative::take_nested_messages(&mut v, worklist);",
+                                                    protobuf_crate
+                                                ));
+                                                w.write_line(&format!(
+                                                    "{} = ::std::option::Option::Some({}(v));",
+                                                    field, path
+                                                ));
+                                            },
+                                        );
+                                    }
+                                }
+                                w.case_expr("v", &format!("{} = v", field));
+                            });
+                        }
+                    },
+                );
+            },
+        );
+    }
+
+    fn write_impl_drop(&self, w: &mut CodeWriter) {
+        w.impl_for_block("::std::ops::Drop", &format!("{}", self.type_name), |w| {
+            w.def_fn("drop(&mut self)", |w| {
+                w.write_line(&format!(
+                    "{}::rt::drop_iterative(self);",
+                    protobuf_crate_path(&self.customize)
+                ));
+            });
+        });
+    }
+
+    fn write_impl_shrink_to_fit(&self, w: &mut CodeWriter) {
+        w.impl_for_block(
+            &format!("{}::rt::ShrinkToFit", protobuf_crate_path(&self.customize)),
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn("shrink_to_fit(&mut self)", |w| {
+                    w.write_line(&format!(
+                        "{}::Message::shrink_to_fit(self)",
+                        protobuf_crate_path(&self.customize)
+                    ));
+                });
+            },
+        );
+    }
+
+    fn write_impl_space_used(&self, w: &mut CodeWriter) {
+        w.impl_for_block(
+            &format!("{}::rt::SpaceUsed", protobuf_crate_path(&self.customize)),
+            &format!("{}", self.type_name),
+            |w| {
+                w.def_fn("space_used_excluding_self(&self) -> usize", |w| {
+                    w.write_line(&format!(
+                        "{}::Message::space_used_excluding_self(self)",
+                        protobuf_crate_path(&self.customize)
+                    ));
+                });
+            },
+        );
+    }
+
     fn write_default_instance_lazy(&self, w: &mut CodeWriter) {
         w.lazy_static_decl_get_simple(
             "instance",
@@ -284,6 +681,15 @@ impl<'a> MessageGen<'a> {
                 for o in &self.oneofs() {
                     w.field_entry(o.oneof.field_name().get(), EXPR_NONE);
                 }
+                if self.has_extension_ranges() {
+                    w.field_entry(
+                        "extension_fields",
+                        &format!(
+                            "{}::ext::ExtensionFields::new()",
+                            protobuf_crate_path(&self.customize)
+                        ),
+                    );
+                }
                 w.field_entry(
                     "unknown_fields",
                     &format!(
@@ -336,10 +742,23 @@ impl<'a> MessageGen<'a> {
             self.write_match_each_oneof_variant(w, |w, variant, v, vtype| {
                 variant.field.write_element_size(w, v, vtype, "my_size");
             });
-            w.write_line(&format!(
-                "my_size += {}::rt::unknown_fields_size(self.get_unknown_fields());",
-                protobuf_crate_path(&self.customize)
-            ));
+            if self.is_message_set() {
+                w.write_line(&format!(
+                    "my_size += {}::rt::message_set_size(&self.extension_fields, self.get_unknown_fields());",
+                    protobuf_crate_path(&self.customize)
+                ));
+            } else {
+                if self.has_extension_ranges() {
+                    w.write_line(&format!(
+                        "my_size += {}::rt::extension_fields_size(&self.extension_fields);",
+                        protobuf_crate_path(&self.customize)
+                    ));
+                }
+                w.write_line(&format!(
+                    "my_size += {}::rt::unknown_fields_size(self.get_unknown_fields());",
+                    protobuf_crate_path(&self.customize)
+                ));
+            }
             w.write_line("self.cached_size.set(my_size);");
             w.write_line("my_size");
         });
@@ -351,6 +770,22 @@ impl<'a> MessageGen<'a> {
         }
     }
 
+    fn write_default_value_consts(&self, w: &mut CodeWriter) {
+        let fields: Vec<_> = self
+            .fields_except_group()
+            .into_iter()
+            .filter(|f| f.proto_field.field.get_proto().has_default_value())
+            .collect();
+        if fields.is_empty() {
+            return;
+        }
+        w.write_line("");
+        w.comment("explicit default values");
+        for f in fields {
+            f.write_default_value_const(w);
+        }
+    }
+
     fn write_impl_self(&self, w: &mut CodeWriter) {
         w.impl_self_block(&format!("{}", self.type_name), |w| {
             // TODO: new should probably be a part of Message trait
@@ -358,6 +793,7 @@ impl<'a> MessageGen<'a> {
                 w.write_line("::std::default::Default::default()");
             });
 
+            self.write_default_value_consts(w);
             self.write_field_accessors(w);
             w.write_line("");
             self.write_generated_message_descriptor_data(w);
@@ -382,29 +818,87 @@ impl<'a> MessageGen<'a> {
         });
     }
 
+    fn write_extension_fields(&self, w: &mut CodeWriter) {
+        let sig = format!(
+            "get_extension_fields(&self) -> ::std::option::Option<&{}::ext::ExtensionFields>",
+            protobuf_crate_path(&self.customize)
+        );
+        w.def_fn(&sig, |w| {
+            w.write_line("::std::option::Option::Some(&self.extension_fields)");
+        });
+        w.write_line("");
+        let sig = format!(
+            "mut_extension_fields(&mut self) -> ::std::option::Option<&mut {}::ext::ExtensionFields>",
+            protobuf_crate_path(&self.customize)
+        );
+        w.def_fn(&sig, |w| {
+            w.write_line("::std::option::Option::Some(&mut self.extension_fields)");
+        });
+    }
+
+    /// Handle unknown field in message with `discard_unknown_fields` option.
+    fn write_skip_unknown_field(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        if self.is_message_set() {
+            // `MessageSet` items of unregistered types are unknown fields
+            w.write_line(&format!("{0}::rt::read_message_set_field_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut {0}::UnknownFields::new())?;", protobuf_crate));
+        } else if self.has_extension_ranges() {
+            w.write_line(&format!("{}::rt::read_extension_or_skip_unknown::<Self>(field_number, wire_type, is, &mut self.extension_fields)?;", protobuf_crate));
+        } else {
+            w.write_line(&format!(
+                "{}::rt::skip_unknown_field(wire_type, is)?;",
+                protobuf_crate
+            ));
+        }
+    }
+
     fn write_merge_from(&self, w: &mut CodeWriter) {
         let sig = format!(
             "merge_from(&mut self, is: &mut {}::CodedInputStream<'_>) -> {}::ProtobufResult<()>",
             protobuf_crate_path(&self.customize),
             protobuf_crate_path(&self.customize),
         );
-        w.def_fn(&sig, |w| {
-            w.while_block("!is.eof()?", |w| {
-                w.write_line(&format!("let (field_number, wire_type) = is.read_tag_unpack()?;"));
-                w.match_block("field_number", |w| {
-                    for f in &self.fields_except_group() {
-                        let number = f.proto_field.number();
-                        w.case_block(number.to_string(), |w| {
-                            f.write_merge_from_field("wire_type", w);
-                        });
-                    }
-                    w.case_block("_", |w| {
-                        w.write_line(&format!("{}::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;", protobuf_crate_path(&self.customize)));
+        w.def_fn(&sig, |w| match self.max_size() {
+            Some(max_size) => {
+                w.block(
+                    &format!(
+                        "{}::rt::merge_with_max_size({:?}, {}, is, |is| {{",
+                        protobuf_crate_path(&self.customize),
+                        self.message_descriptor.full_name(),
+                        max_size
+                    ),
+                    "})",
+                    |w| self.write_merge_from_loop(w),
+                );
+            }
+            None => self.write_merge_from_loop(w),
+        });
+    }
+
+    fn write_merge_from_loop(&self, w: &mut CodeWriter) {
+        w.while_block("!is.eof()?", |w| {
+            w.write_line(&format!("let (field_number, wire_type) = is.read_tag_unpack()?;"));
+            w.match_block("field_number", |w| {
+                for f in &self.fields_except_group() {
+                    let number = f.proto_field.number();
+                    w.case_block(number.to_string(), |w| {
+                        f.write_merge_from_field("wire_type", w);
                     });
+                }
+                w.case_block("_", |w| {
+                    if self.customize.discard_unknown_fields.unwrap_or(false) {
+                        self.write_skip_unknown_field(w);
+                    } else if self.is_message_set() {
+                        w.write_line(&format!("{}::rt::read_message_set_field_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;", protobuf_crate_path(&self.customize)));
+                    } else if self.has_extension_ranges() {
+                        w.write_line(&format!("{}::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;", protobuf_crate_path(&self.customize)));
+                    } else {
+                        w.write_line(&format!("{}::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;", protobuf_crate_path(&self.customize)));
+                    }
                 });
             });
-            w.write_line("::std::result::Result::Ok(())");
         });
+        w.write_line("::std::result::Result::Ok(())");
     }
 
     fn write_descriptor_static_new(&self, w: &mut CodeWriter) {
@@ -499,8 +993,20 @@ impl<'a> MessageGen<'a> {
                 w.write_line("");
                 self.write_get_cached_size(w);
                 w.write_line("");
+                self.write_space_used_excluding_self(w);
+                w.write_line("");
+                self.write_shrink_to_fit(w);
+                w.write_line("");
+                if message_needs_validation(&self.message_descriptor, &mut HashSet::new()) {
+                    self.write_validate_to(w);
+                    w.write_line("");
+                }
                 self.write_unknown_fields(w);
                 w.write_line("");
+                if self.has_extension_ranges() {
+                    self.write_extension_fields(w);
+                    w.write_line("");
+                }
                 w.def_fn(&format!("new() -> {}", self.type_name), |w| {
                     w.write_line(&format!("{}::new()", self.type_name));
                 });
@@ -530,6 +1036,56 @@ impl<'a> MessageGen<'a> {
         )
     }
 
+    fn write_impl_try_from_bytes(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        w.impl_args_for_block(
+            &["'a"],
+            "::std::convert::TryFrom<&'a [u8]>",
+            &format!("{}", self.type_name),
+            |w| {
+                w.write_line(&format!("type Error = {}::ProtobufError;", protobuf_crate));
+                w.write_line("");
+                w.def_fn(
+                    "try_from(bytes: &'a [u8]) -> ::std::result::Result<Self, Self::Error>",
+                    |w| {
+                        w.write_line(&format!(
+                            "{}::Message::parse_from_bytes(bytes)",
+                            protobuf_crate
+                        ));
+                    },
+                );
+            },
+        );
+        let owned = format!("{}", self.type_name);
+        let borrowed = format!("&'a {}", self.type_name);
+        let serialize: [(&[&str], &str, &str); 2] =
+            [(&[], &owned, "&message"), (&["'a"], &borrowed, "message")];
+        for &(args, arg_type, message) in &serialize {
+            w.write_line("");
+            w.impl_args_for_block(
+                args,
+                &format!("::std::convert::TryFrom<{}>", arg_type),
+                "::std::vec::Vec<u8>",
+                |w| {
+                    w.write_line(&format!("type Error = {}::ProtobufError;", protobuf_crate));
+                    w.write_line("");
+                    w.def_fn(
+                        &format!(
+                            "try_from(message: {}) -> ::std::result::Result<Self, Self::Error>",
+                            arg_type
+                        ),
+                        |w| {
+                            w.write_line(&format!(
+                                "{}::Message::write_to_bytes({})",
+                                protobuf_crate, message
+                            ));
+                        },
+                    );
+                },
+            );
+        }
+    }
+
     fn write_impl_show(&self, w: &mut CodeWriter) {
         w.impl_for_block("::std::fmt::Debug", &format!("{}", self.type_name), |w| {
             w.def_fn(
@@ -553,6 +1109,9 @@ impl<'a> MessageGen<'a> {
                     for f in self.fields_except_group() {
                         f.write_clear(w);
                     }
+                    if self.has_extension_ranges() {
+                        w.write_line("self.extension_fields.clear();");
+                    }
                     w.write_line("self.unknown_fields.clear();");
                 });
             },
@@ -570,10 +1129,17 @@ impl<'a> MessageGen<'a> {
         if self.supports_derive_partial_eq() {
             derive.push("PartialEq");
         }
+        let derive_hash_eq_ord = self.derive_hash_eq_ord();
+        if derive_hash_eq_ord {
+            derive.push("Eq");
+        }
         derive.extend(&["Clone", "Default"]);
         if self.lite_runtime {
             derive.push("Debug");
         }
+        if derive_hash_eq_ord {
+            derive.extend(&["Hash", "PartialOrd", "Ord"]);
+        }
         w.derive(&derive);
         serde::write_serde_attr(
             w,
@@ -603,6 +1169,16 @@ impl<'a> MessageGen<'a> {
             }
             w.comment("special fields");
 
+            if self.has_extension_ranges() {
+                serde::write_serde_attr(w, &self.customize, "serde(skip)");
+                w.pub_field_decl(
+                    "extension_fields",
+                    &format!(
+                        "{}::ext::ExtensionFields",
+                        protobuf_crate_path(&self.customize)
+                    ),
+                );
+            }
             serde::write_serde_attr(w, &self.customize, "serde(skip)");
             w.pub_field_decl(
                 "unknown_fields",
@@ -646,6 +1222,90 @@ impl<'a> MessageGen<'a> {
         );
     }
 
+    /// Message is generated in the same file with `generate_view` option.
+    fn message_has_view(&self, message: &MessageWithScope) -> bool {
+        if message.get_file_descriptor().get_name() != self.message.get_file_descriptor().get_name()
+        {
+            return false;
+        }
+        let mut customize = self.file_customize.clone();
+        for m in message.scope.path.iter().chain(Some(&message.message)) {
+            customize.update_with(&customize_from_rustproto_for_message(
+                m.get_proto().options.get_or_default(),
+            ));
+        }
+        customize.generate_view.unwrap_or(false)
+    }
+
+    /// Field numbers of the oneof this field is part of.
+    fn oneof_field_numbers(&self, field: &FieldGen) -> Vec<u32> {
+        let proto = field.proto_field.field.get_proto();
+        if !proto.has_oneof_index() {
+            return Vec::new();
+        }
+        self.message
+            .message
+            .get_proto()
+            .field
+            .iter()
+            .filter(|f| f.has_oneof_index() && f.get_oneof_index() == proto.get_oneof_index())
+            .map(|f| f.get_number() as u32)
+            .collect()
+    }
+
+    fn write_view(&self, w: &mut CodeWriter) {
+        let protobuf_crate = protobuf_crate_path(&self.customize);
+        let view = format!("{}View", self.message.rust_name());
+        w.write_line(&format!(
+            "/// Zero-copy read-only view of serialized `{}`.",
+            self.type_name
+        ));
+        w.write_line("///");
+        w.write_line(&format!(
+            "/// See [`{}::view`] for details.",
+            protobuf_crate
+        ));
+        w.derive(&["Clone", "Copy", "Debug"]);
+        w.pub_struct(&format!("{}<'a>", view), |w| {
+            w.field_decl("bytes", "&'a [u8]");
+        });
+        w.write_line("");
+        w.expr_block(&format!("impl<'a> {}<'a>", view), |w| {
+            w.write_line("/// Parse the viewed message.");
+            w.pub_fn(
+                &format!(
+                    "to_message(&self) -> {}::ProtobufResult<{}>",
+                    protobuf_crate, self.type_name
+                ),
+                |w| {
+                    w.write_line(&format!(
+                        "{}::Message::parse_from_bytes(self.bytes)",
+                        protobuf_crate
+                    ));
+                },
+            );
+            let has_view = |m: &MessageWithScope| self.message_has_view(m);
+            for field in &self.fields {
+                field.write_view_accessors(&self.oneof_field_numbers(field), &has_view, w);
+            }
+        });
+        w.write_line("");
+        w.impl_args_for_block(
+            &["'a"],
+            &format!("{}::view::MessageView<'a>", protobuf_crate),
+            &format!("{}<'a>", view),
+            |w| {
+                w.def_fn("from_bytes(bytes: &'a [u8]) -> Self", |w| {
+                    w.write_line(&format!("{} {{ bytes }}", view));
+                });
+                w.write_line("");
+                w.def_fn("as_bytes(&self) -> &'a [u8]", |w| {
+                    w.write_line("self.bytes");
+                });
+            },
+        );
+    }
+
     pub fn write(&self, w: &mut CodeWriter) {
         w.all_documentation(self.info, self.path);
         self.write_struct(w);
@@ -663,6 +1323,16 @@ impl<'a> MessageGen<'a> {
         w.write_line("");
         self.write_impl_message(w);
         w.write_line("");
+        self.write_impl_space_used(w);
+        w.write_line("");
+        self.write_impl_shrink_to_fit(w);
+        w.write_line("");
+        self.write_impl_drop_iterative(w);
+        if self.customize.iterative_drop.unwrap_or(false) {
+            w.write_line("");
+            self.write_impl_drop(w);
+        }
+        w.write_line("");
         self.write_impl_clear(w);
         if !self.lite_runtime {
             w.write_line("");
@@ -670,6 +1340,14 @@ impl<'a> MessageGen<'a> {
         }
         w.write_line("");
         self.write_impl_value(w);
+        if self.customize.impl_try_from_bytes.unwrap_or(false) {
+            w.write_line("");
+            self.write_impl_try_from_bytes(w);
+        }
+        if self.customize.generate_view.unwrap_or(false) {
+            w.write_line("");
+            self.write_view(w);
+        }
 
         let mod_name = message_name_to_nested_mod_name(&self.message.message.get_name());
 
@@ -685,8 +1363,13 @@ impl<'a> MessageGen<'a> {
             })
             .collect();
         let nested_enums = self.message.to_scope().get_enums();
+        let extensions = &self.message.message.get_proto().extension;
 
-        if !oneofs.is_empty() || !nested_messages.is_empty() || !nested_enums.is_empty() {
+        if !oneofs.is_empty()
+            || !nested_messages.is_empty()
+            || !nested_enums.is_empty()
+            || !extensions.is_empty()
+        {
             w.write_line("");
             w.write_line(&format!(
                 "/// Nested message and enums of message `{}`",
@@ -725,6 +1408,7 @@ impl<'a> MessageGen<'a> {
                         self.file_index,
                         self.root_scope,
                         &self.customize,
+                        self.file_customize,
                         &path,
                         self.info,
                     )
@@ -760,6 +1444,19 @@ impl<'a> MessageGen<'a> {
                     )
                     .write(w);
                 }
+
+                write_extensions_in_mod(
+                    &self.file_descriptor,
+                    extensions,
+                    self.message
+                        .to_scope()
+                        .get_file_and_mod(self.customize.clone())
+                        .relative_mod
+                        .append_ident(RustIdent::from("exts")),
+                    self.root_scope,
+                    w,
+                    &self.customize,
+                );
             });
         }
     }
@@ -772,3 +1469,46 @@ pub(crate) fn message_name_to_nested_mod_name(message_name: &str) -> RustIdent {
     }
     RustIdent::new(&mod_name)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use protobuf::descriptor::field_descriptor_proto::Label;
+    use protobuf::descriptor::field_descriptor_proto::Type;
+
+    fn message(fields: &[(&str, &str)]) -> MessageDescriptor {
+        let mut message = DescriptorProto::new();
+        message.set_name("M".to_owned());
+        for (i, &(name, json_name)) in fields.iter().enumerate() {
+            let mut field = FieldDescriptorProto::new();
+            field.set_name(name.to_owned());
+            field.set_json_name(json_name.to_owned());
+            field.set_number(i as i32 + 1);
+            field.set_label(Label::LABEL_OPTIONAL);
+            field.set_field_type(Type::TYPE_INT32);
+            message.field.push(field);
+        }
+        let mut file = FileDescriptorProto::new();
+        file.set_name("f.proto".to_owned());
+        file.message_type.push(message);
+        FileDescriptor::new_dynamic(file, Vec::new())
+            .message_by_package_relative_name("M")
+            .unwrap()
+    }
+
+    #[test]
+    fn json_name_collisions() {
+        assert_eq!(
+            None,
+            json_name_collision(&message(&[("foo_bar", "fooBar"), ("baz", "Baz")]))
+        );
+        assert_eq!(
+            Some("fields `a` and `b` have the same JSON name `x`".to_owned()),
+            json_name_collision(&message(&[("a", "x"), ("b", "x")]))
+        );
+        assert_eq!(
+            Some("JSON name `b` of field `a` is the name of field `b`".to_owned()),
+            json_name_collision(&message(&[("a", "b"), ("b", "c")]))
+        );
+    }
+}
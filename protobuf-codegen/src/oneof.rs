@@ -146,6 +146,11 @@ impl<'a> OneofVariantGen<'a> {
         self.oneof_field.rust_type(reference)
     }
 
+    /// Variant value is stored in a `Box`.
+    pub fn boxed(&self) -> bool {
+        self.oneof_field.boxed
+    }
+
     pub fn path(&self, reference: &FileAndMod) -> RustPath {
         RustPath::from(format!(
             "{}::{}",
@@ -236,7 +241,10 @@ impl<'a> OneofGen<'a> {
     }
 
     fn write_enum(&self, w: &mut CodeWriter) {
-        let derive = vec!["Clone", "PartialEq", "Debug"];
+        let mut derive = vec!["Clone", "PartialEq", "Debug"];
+        if self.message.derive_hash_eq_ord() {
+            derive.extend(&["Eq", "Hash", "PartialOrd", "Ord"]);
+        }
         w.derive(&derive);
         serde::write_serde_attr(
             w,
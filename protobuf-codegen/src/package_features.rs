@@ -0,0 +1,104 @@
+//! Cargo features for generated modules of each package.
+//!
+//! With [`Customize::gen_mod_rs_features`](crate::Customize::gen_mod_rs_features)
+//! modules in generated `mod.rs` are guarded with `#[cfg(feature = "...")]`,
+//! and `features.toml` lists these features, so it can be copied to `Cargo.toml`
+//! of the crate which includes generated code.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use protobuf::reflect::FileDescriptor;
+
+use crate::compiler_plugin;
+
+/// Name of the file with features list.
+pub(crate) const FEATURES_FILE_NAME: &str = "features.toml";
+
+/// Feature name for a package, e. g. `foo_bar` for `foo.bar`.
+///
+/// Files without package are not guarded.
+pub(crate) fn package_feature(package: &str) -> Option<String> {
+    if package.is_empty() {
+        None
+    } else {
+        Some(package.replace('.', "_"))
+    }
+}
+
+/// `[features]` section with a feature for each package.
+///
+/// Feature enables features of packages imported by its files,
+/// because generated code refers to imported types.
+pub(crate) fn gen_features_toml(files: &[&FileDescriptor]) -> compiler_plugin::GenResult {
+    let features_by_file: HashMap<&str, Option<String>> = files
+        .iter()
+        .map(|f| {
+            (
+                f.proto().get_name(),
+                package_feature(f.proto().get_package()),
+            )
+        })
+        .collect();
+
+    let mut features: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for file in files {
+        let feature = match &features_by_file[file.proto().get_name()] {
+            Some(feature) => feature,
+            None => continue,
+        };
+        let deps = features.entry(feature.clone()).or_default();
+        for dep in &file.proto().dependency {
+            if let Some(Some(dep_feature)) = features_by_file.get(dep.as_str()) {
+                if dep_feature != feature {
+                    deps.insert(dep_feature.clone());
+                }
+            }
+        }
+    }
+
+    let mut content = String::new();
+    content.push_str("# @generated\n\n");
+    content.push_str("[features]\n");
+    for (feature, deps) in &features {
+        let deps: Vec<String> = deps.iter().map(|d| format!("\"{}\"", d)).collect();
+        content.push_str(&format!("{} = [{}]\n", feature, deps.join(", ")));
+    }
+
+    compiler_plugin::GenResult {
+        name: FEATURES_FILE_NAME.to_owned(),
+        content: content.into_bytes(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::FileDescriptorProto;
+
+    use super::*;
+
+    fn file(name: &str, package: &str, deps: &[&str]) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        file.set_package(package.to_owned());
+        file.dependency = deps.iter().map(|d| d.to_string()).collect();
+        file
+    }
+
+    #[test]
+    fn features_toml() {
+        let files = FileDescriptor::new_dynamic_fds(vec![
+            file("a.proto", "foo.a", &[]),
+            file("b.proto", "foo.b", &["a.proto"]),
+            file("b2.proto", "foo.b", &["b.proto"]),
+            file("root.proto", "", &["a.proto"]),
+        ]);
+        let files: Vec<&FileDescriptor> = files.iter().collect();
+        let toml = String::from_utf8(gen_features_toml(&files).content).unwrap();
+        assert_eq!(
+            "# @generated\n\n[features]\nfoo_a = []\nfoo_b = [\"foo_a\"]\n",
+            toml
+        );
+    }
+}
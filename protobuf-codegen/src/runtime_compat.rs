@@ -0,0 +1,133 @@
+//! Compile-time check that generated code is used with compatible runtime.
+//!
+//! Generated file references markers in `protobuf::rt::compat`:
+//! one for the codegen version and one for each optional runtime feature
+//! the file needs. When the marker is missing, compiler error points
+//! to the line with the comment explaining how to fix it.
+
+use protobuf::descriptor::field_descriptor_proto;
+use protobuf::descriptor::DescriptorProto;
+use protobuf::descriptor::FileDescriptorProto;
+
+use crate::code_writer::CodeWriter;
+use crate::customize::customize_from_rustproto_for_field;
+use crate::customize::customize_from_rustproto_for_message;
+use crate::inside::protobuf_crate_path;
+use crate::Customize;
+
+/// `(major, minor)` of codegen version.
+fn codegen_major_minor() -> (u32, u32) {
+    let mut parts = env!("CARGO_PKG_VERSION").split(|c| c == '.' || c == '-');
+    let major = parts.next().unwrap().parse().unwrap();
+    let minor = parts.next().unwrap().parse().unwrap();
+    (major, minor)
+}
+
+fn message_uses_bytes(message: &DescriptorProto, customize: &Customize) -> bool {
+    let mut customize = customize.clone();
+    customize.update_with(&customize_from_rustproto_for_message(
+        message.options.get_or_default(),
+    ));
+    let field_uses_bytes = message.field.iter().any(|field| {
+        let mut customize = customize.clone();
+        customize.update_with(&customize_from_rustproto_for_field(
+            field.options.get_or_default(),
+        ));
+        match field.get_field_type() {
+            field_descriptor_proto::Type::TYPE_BYTES => {
+                customize.carllerche_bytes_for_bytes.unwrap_or(false)
+            }
+            field_descriptor_proto::Type::TYPE_STRING => {
+                customize.carllerche_bytes_for_string.unwrap_or(false)
+            }
+            _ => false,
+        }
+    });
+    field_uses_bytes
+        || message
+            .nested_type
+            .iter()
+            .any(|nested| message_uses_bytes(nested, &customize))
+}
+
+/// Write constants referencing `protobuf::rt::compat` markers.
+///
+/// `customize` includes file options.
+pub(crate) fn write_runtime_compat_check(
+    file: &FileDescriptorProto,
+    customize: &Customize,
+    w: &mut CodeWriter,
+) {
+    let protobuf_crate = protobuf_crate_path(customize);
+    let (major, minor) = codegen_major_minor();
+
+    w.write_line("");
+    w.write_line(&format!(
+        "/// Generated files are compatible with protobuf runtime `{}.x` since `{}.{}`.",
+        major, major, minor
+    ));
+    w.write_line(&format!(
+        "const _PROTOBUF_VERSION_CHECK: () = {}::rt::compat::REQUIRE_{}_{}; \
+        // requires `protobuf` crate `{}.{}` or newer `{}.x`, \
+        or regenerate this file with codegen of the same version as `protobuf` crate",
+        protobuf_crate, major, minor, major, minor, major
+    ));
+
+    if file
+        .message_type
+        .iter()
+        .any(|m| message_uses_bytes(m, customize))
+    {
+        w.write_line("/// Generated file uses `bytes::Bytes`.");
+        w.write_line(&format!(
+            "const _PROTOBUF_FEATURE_CHECK_WITH_BYTES: () = {}::rt::compat::FEATURE_WITH_BYTES; \
+            // enable `with-bytes` feature of `protobuf` crate",
+            protobuf_crate
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use protobuf::descriptor::FieldDescriptorProto;
+    use protobuf::rustproto;
+
+    use super::*;
+
+    fn write(file: &FileDescriptorProto, customize: &Customize) -> String {
+        let mut v = Vec::new();
+        write_runtime_compat_check(file, customize, &mut CodeWriter::new(&mut v));
+        String::from_utf8(v).unwrap()
+    }
+
+    #[test]
+    fn check_version_and_bytes() {
+        let mut field = FieldDescriptorProto::new();
+        field.set_name("b".to_owned());
+        field.set_field_type(field_descriptor_proto::Type::TYPE_BYTES);
+        let mut nested = DescriptorProto::new();
+        nested.field.push(field);
+        let mut message = DescriptorProto::new();
+        message.nested_type.push(nested);
+        let mut file = FileDescriptorProto::new();
+        file.message_type.push(message);
+
+        let (major, minor) = codegen_major_minor();
+        let code = write(&file, &Customize::default());
+        assert!(code.contains(&format!(
+            "::protobuf::rt::compat::REQUIRE_{}_{};",
+            major, minor
+        )));
+        assert!(!code.contains("FEATURE_WITH_BYTES"));
+
+        let mut customize = Customize::default();
+        customize.carllerche_bytes_for_bytes = Some(true);
+        assert!(write(&file, &customize).contains("::protobuf::rt::compat::FEATURE_WITH_BYTES;"));
+
+        let mut customize = Customize::default();
+        customize.carllerche_bytes_for_bytes = Some(true);
+        let options = file.message_type[0].options.mut_or_default();
+        rustproto::exts::carllerche_bytes_for_bytes.set(options, false);
+        assert!(!write(&file, &customize).contains("FEATURE_WITH_BYTES"));
+    }
+}
@@ -25,6 +25,8 @@ pub(crate) enum RustType {
     Float(u32),
     Bool,
     Vec(Box<RustType>),
+    // protobuf::SmallVec<T, N>
+    SmallVec(Box<RustType>, u32),
     HashMap(Box<RustType>, Box<RustType>),
     String,
     // [T], not &[T]
@@ -35,6 +37,8 @@ pub(crate) enum RustType {
     MessageField(Box<RustType>),
     // Box<T>
     Uniq(Box<RustType>),
+    // Arc<T>
+    Arc(Box<RustType>),
     // &T
     Ref(Box<RustType>),
     // protobuf message
@@ -49,6 +53,8 @@ pub(crate) enum RustType {
     Bytes,
     // chars::Chars
     Chars,
+    // protobuf::InternedString
+    InternedString,
     // group
     Group,
 }
@@ -62,6 +68,12 @@ impl RustType {
             RustType::Float(bits) => format!("f{}", bits),
             RustType::Bool => format!("bool"),
             RustType::Vec(ref param) => format!("::std::vec::Vec<{}>", param.to_code(customize)),
+            RustType::SmallVec(ref param, n) => format!(
+                "{}::SmallVec<{}, {}>",
+                protobuf_crate_path(customize),
+                param.to_code(customize),
+                n
+            ),
             RustType::HashMap(ref key, ref value) => format!(
                 "::std::collections::HashMap<{}, {}>",
                 key.to_code(customize),
@@ -79,6 +91,7 @@ impl RustType {
                 param.to_code(customize)
             ),
             RustType::Uniq(ref param) => format!("::std::boxed::Box<{}>", param.to_code(customize)),
+            RustType::Arc(ref param) => format!("::std::sync::Arc<{}>", param.to_code(customize)),
             RustType::Ref(ref param) => format!("&{}", param.to_code(customize)),
             RustType::Message(ref name) => format!("{}", name),
             RustType::Enum(ref name, ..) | RustType::Oneof(ref name) => format!("{}", name),
@@ -90,6 +103,9 @@ impl RustType {
             RustType::Group => format!("<group>"),
             RustType::Bytes => format!("::bytes::Bytes"),
             RustType::Chars => format!("{}::Chars", protobuf_crate_path(customize)),
+            RustType::InternedString => {
+                format!("{}::InternedString", protobuf_crate_path(customize))
+            }
         }
     }
 }
@@ -198,10 +214,14 @@ impl RustType {
             RustType::Float(..) => "0.".to_string(),
             RustType::Bool => "false".to_string(),
             RustType::Vec(..) => EXPR_VEC_NEW.to_string(),
+            RustType::SmallVec(..) => format!("{}::SmallVec::new()", protobuf_crate_path(customize)),
             RustType::HashMap(..) => "::std::collections::HashMap::new()".to_string(),
             RustType::String => "::std::string::String::new()".to_string(),
             RustType::Bytes => "::bytes::Bytes::new()".to_string(),
             RustType::Chars => format!("{}::Chars::new()", protobuf_crate_path(customize)),
+            RustType::InternedString => {
+                format!("{}::InternedString::new()", protobuf_crate_path(customize))
+            }
             RustType::Option(..) => EXPR_NONE.to_string(),
             RustType::MessageField(..) => {
                 format!("{}::MessageField::none()", protobuf_crate_path(customize))
@@ -240,11 +260,12 @@ impl RustType {
         match *self {
             RustType::Option(..) => format!("{} = {}", v, EXPR_NONE),
             RustType::Vec(..)
+            | RustType::SmallVec(..)
             | RustType::Bytes
             | RustType::String
             | RustType::MessageField(..)
             | RustType::HashMap(..) => format!("{}.clear()", v),
-            RustType::Chars => format!(
+            RustType::Chars | RustType::InternedString => format!(
                 "{}::Clear::clear(&mut {})",
                 protobuf_crate_path(customize),
                 v
@@ -286,12 +307,18 @@ impl RustType {
                 return Ok(format!("::std::boxed::Box::new({})", v))
             }
             (&RustType::Uniq(ref x), y) if **x == *y => return Ok(format!("*{}", v)),
+            (x, &RustType::Arc(ref y)) if *x == **y => {
+                return Ok(format!("::std::sync::Arc::new({})", v))
+            }
             (&RustType::String, &RustType::Ref(ref t)) if **t == RustType::Str => {
                 return Ok(format!("&{}", v))
             }
             (&RustType::Chars, &RustType::Ref(ref t)) if **t == RustType::Str => {
                 return Ok(format!("&{}", v))
             }
+            (&RustType::InternedString, &RustType::Ref(ref t)) if **t == RustType::Str => {
+                return Ok(format!("&{}", v))
+            }
             (&RustType::Ref(ref t1), &RustType::Ref(ref t2)) if t1.is_string() && t2.is_str() => {
                 return Ok(format!("&{}", v))
             }
@@ -309,6 +336,10 @@ impl RustType {
                 return Ok(format!("<{}::Chars as ::std::convert::From<_>>::from({}.to_owned())",
                     protobuf_crate_path(customize), v))
             },
+            (&RustType::Ref(ref t1), &RustType::InternedString) if **t1 == RustType::Str => {
+                return Ok(format!("<{}::InternedString as ::std::convert::From<_>>::from({})",
+                    protobuf_crate_path(customize), v))
+            }
             (&RustType::Ref(ref t1), &RustType::Vec(ref t2))
                 if match (&**t1, &**t2) {
                        (&RustType::Slice(ref x), ref y) => **x == **y,
@@ -322,6 +353,11 @@ impl RustType {
                        RustType::Slice(ref y) => x == y,
                        _ => false,
                    } => return Ok(format!("&{}", v)),
+            (&RustType::SmallVec(ref x, ..), &RustType::Ref(ref t))
+                if match **t {
+                       RustType::Slice(ref y) => x == y,
+                       _ => false,
+                   } => return Ok(format!("&{}", v)),
             (&RustType::Bytes, &RustType::Ref(ref t))
                 if match **t {
                        RustType::Slice(ref y) => **y == RustType::u8(),
@@ -330,6 +366,7 @@ impl RustType {
             (&RustType::Ref(ref t1), &RustType::Ref(ref t2))
                 if match (&**t1, &**t2) {
                        (&RustType::Vec(ref x), &RustType::Slice(ref y)) => x == y,
+                       (&RustType::SmallVec(ref x, ..), &RustType::Slice(ref y)) => x == y,
                        _ => false,
                    } => return Ok(format!("&{}", v)),
             (&RustType::Enum(..), &RustType::Int(true, 32)) => {
@@ -366,8 +403,8 @@ impl RustType {
     /// Type to view data of this type
     pub fn ref_type(&self) -> RustType {
         RustType::Ref(Box::new(match self {
-            &RustType::String | &RustType::Chars => RustType::Str,
-            &RustType::Vec(ref p) => RustType::Slice(p.clone()),
+            &RustType::String | &RustType::Chars | &RustType::InternedString => RustType::Str,
+            &RustType::Vec(ref p) | &RustType::SmallVec(ref p, ..) => RustType::Slice(p.clone()),
             &RustType::Bytes => RustType::Slice(Box::new(RustType::u8())),
             &RustType::Message(ref p) => RustType::Message(p.clone()),
             &RustType::Uniq(ref p) => RustType::Uniq(p.clone()),
@@ -387,6 +424,7 @@ impl RustType {
     pub fn iter_elem_type(&self) -> RustType {
         match self {
             &RustType::Vec(ref ty)
+            | &RustType::SmallVec(ref ty, ..)
             | &RustType::Option(ref ty)
             | &RustType::MessageField(ref ty) => RustType::Ref(ty.clone()),
             x => panic!("cannot iterate {:?}", x),
@@ -522,13 +560,16 @@ pub(crate) fn message_or_enum_to_rust_relative(
             &current.relative_mod.clone().into_path(),
             &message_or_enum.rust_name_to_file(),
         )
-    } else if let Some(name) = is_well_known_type_full(&message_or_enum.name_absolute()) {
+    } else if current.customize.protobuf_well_known_types.unwrap_or(true)
+        && is_well_known_type_full(&message_or_enum.name_absolute()).is_some()
+    {
         // Well-known types are included in rust-protobuf library
         // https://developers.google.com/protocol-buffers/docs/reference/google.protobuf
+        // Nested types like `Field.Cardinality` are in reexported modules like `field`
         RustIdentWithPath::from(format!(
             "{}::well_known_types::{}",
             protobuf_crate_path(&current.customize),
-            name
+            message_or_enum.rust_name_to_file()
         ))
     } else if is_descriptor_proto(message_or_enum.get_file_descriptor()) {
         // Messages defined in descriptor.proto
@@ -561,6 +602,8 @@ pub(crate) fn type_name_to_rust_relative(
 pub enum PrimitiveTypeVariant {
     Default,
     Carllerche,
+    // `string` as `protobuf::InternedString`
+    Interned,
 }
 
 pub enum _CarllercheBytesType {
@@ -599,6 +642,14 @@ impl ProtobufTypeGen {
                 protobuf_crate_path(customize)
             ),
             &ProtobufTypeGen::Primitive(.., PrimitiveTypeVariant::Carllerche) => unreachable!(),
+            &ProtobufTypeGen::Primitive(
+                field_descriptor_proto::Type::TYPE_STRING,
+                PrimitiveTypeVariant::Interned,
+            ) => format!(
+                "{}::reflect::types::ProtobufTypeInternedString",
+                protobuf_crate_path(customize)
+            ),
+            &ProtobufTypeGen::Primitive(.., PrimitiveTypeVariant::Interned) => unreachable!(),
             &ProtobufTypeGen::Message(ref name) => format!(
                 "{}::reflect::types::ProtobufTypeMessage<{}>",
                 protobuf_crate_path(customize),
@@ -148,6 +148,10 @@ pub(crate) fn gen_well_known_types_mod(
             }
         }
 
+        w.write_line("");
+        // Hand-written utilities like parse errors
+        w.write_line("pub use crate::well_known_types_util::*;");
+
         w.write_line("");
         w.write_line("#[doc(hidden)]");
         w.pub_mod("file_descriptors", |w| {
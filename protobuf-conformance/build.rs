@@ -0,0 +1,14 @@
+fn main() {
+    protobuf_test_common::build::clean_old_files();
+
+    protobuf_codegen_pure::Codegen::new()
+        .out_dir("src")
+        .include("src")
+        .inputs(&[
+            "src/conformance_pb.proto",
+            "src/test_messages_proto2_pb.proto",
+            "src/test_messages_proto3_pb.proto",
+        ])
+        .run()
+        .expect("protoc");
+}
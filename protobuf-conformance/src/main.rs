@@ -0,0 +1,214 @@
+//! Program under test for the protobuf conformance test suite.
+//!
+//! Reads `ConformanceRequest` messages from stdin and writes
+//! `ConformanceResponse` messages to stdout, each prefixed with
+//! little-endian `u32` length, as expected by `conformance-test-runner`
+//! from google/protobuf.
+
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use protobuf::json;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::text_format;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+mod conformance_pb;
+mod test_messages_proto2_pb;
+mod test_messages_proto3_pb;
+
+use conformance_pb::conformance_request::Payload;
+use conformance_pb::conformance_response::Result as ResponseResult;
+use conformance_pb::ConformanceRequest;
+use conformance_pb::ConformanceResponse;
+use conformance_pb::FailureSet;
+use conformance_pb::TestCategory;
+use conformance_pb::WireFormat;
+
+fn message_descriptor(message_type: &str) -> Option<MessageDescriptor> {
+    [
+        test_messages_proto2_pb::TestAllTypesProto2::descriptor_static(),
+        test_messages_proto3_pb::TestAllTypesProto3::descriptor_static(),
+    ]
+    .iter()
+    .find(|d| d.full_name() == message_type)
+    .cloned()
+}
+
+fn parse(request: &ConformanceRequest, message: &mut dyn MessageDyn) -> Result<(), String> {
+    match &request.payload {
+        Some(Payload::protobuf_payload(bytes)) => message
+            .merge_from_bytes_dyn(bytes)
+            .map_err(|e| e.to_string()),
+        Some(Payload::json_payload(json)) => {
+            let options = json::ParseOptions {
+                ignore_unknown_fields: request.test_category.enum_value()
+                    == Ok(TestCategory::JSON_IGNORE_UNKNOWN_PARSING_TEST),
+                ..Default::default()
+            };
+            json::merge_from_str_with_options(message, json, &options).map_err(|e| e.to_string())
+        }
+        Some(Payload::text_payload(text)) => {
+            text_format::merge_from_str(message, text).map_err(|e| e.to_string())
+        }
+        Some(Payload::jspb_payload(..)) | None => unreachable!(),
+    }
+}
+
+fn serialize(
+    format: Result<WireFormat, i32>,
+    message: &dyn MessageDyn,
+) -> Result<ResponseResult, String> {
+    Ok(match format {
+        Ok(WireFormat::PROTOBUF) => ResponseResult::protobuf_payload(
+            message.write_to_bytes_dyn().map_err(|e| e.to_string())?,
+        ),
+        Ok(WireFormat::JSON) => {
+            ResponseResult::json_payload(json::print_to_string(message).map_err(|e| e.to_string())?)
+        }
+        Ok(WireFormat::TEXT_FORMAT) => {
+            ResponseResult::text_payload(text_format::print_to_string(message))
+        }
+        Ok(WireFormat::JSPB) => ResponseResult::skipped("JSPB is not supported".to_owned()),
+        Ok(WireFormat::UNSPECIFIED) | Err(..) => {
+            ResponseResult::runtime_error(format!("unspecified output format: {:?}", format))
+        }
+    })
+}
+
+fn run_test(request: &ConformanceRequest) -> ResponseResult {
+    if request.message_type == "conformance.FailureSet" {
+        // Expected failures are listed in the runner command line.
+        return ResponseResult::protobuf_payload(FailureSet::new().write_to_bytes().unwrap());
+    }
+
+    match &request.payload {
+        Some(Payload::jspb_payload(..)) => {
+            return ResponseResult::skipped("JSPB is not supported".to_owned())
+        }
+        None => return ResponseResult::runtime_error("request has no payload".to_owned()),
+        _ => {}
+    }
+
+    let descriptor = match message_descriptor(&request.message_type) {
+        Some(d) => d,
+        None => {
+            return ResponseResult::runtime_error(format!(
+                "unknown message type: {}",
+                request.message_type
+            ))
+        }
+    };
+
+    let mut message = descriptor.new_instance();
+    if let Err(e) = parse(request, &mut *message) {
+        return ResponseResult::parse_error(e);
+    }
+
+    match serialize(request.requested_output_format.enum_value(), &*message) {
+        Ok(r) => r,
+        Err(e) => ResponseResult::serialize_error(e),
+    }
+}
+
+/// Read single request, return `false` on EOF.
+fn serve_one(input: &mut dyn Read, output: &mut dyn Write) -> io::Result<bool> {
+    let mut len = [0; 4];
+    match input.read_exact(&mut len) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+        Err(e) => return Err(e),
+    }
+    let mut bytes = vec![0; u32::from_le_bytes(len) as usize];
+    input.read_exact(&mut bytes)?;
+
+    let request = ConformanceRequest::parse_from_bytes(&bytes)?;
+    let mut response = ConformanceResponse::new();
+    response.result = Some(run_test(&request));
+
+    let bytes = response.write_to_bytes()?;
+    output.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    output.write_all(&bytes)?;
+    output.flush()?;
+    Ok(true)
+}
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut stdin = stdin.lock();
+    let mut stdout = stdout.lock();
+    while serve_one(&mut stdin, &mut stdout)? {}
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_messages_proto3_pb::TestAllTypesProto3;
+
+    fn request(payload: Payload, format: WireFormat) -> ConformanceRequest {
+        let mut request = ConformanceRequest::new();
+        request.message_type = "protobuf_test_messages.proto3.TestAllTypesProto3".to_owned();
+        request.requested_output_format = format.into();
+        request.payload = Some(payload);
+        request
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let mut message = TestAllTypesProto3::new();
+        message.optional_int32 = 17;
+        let bytes = message.write_to_bytes().unwrap();
+
+        let request = request(
+            Payload::protobuf_payload(bytes.clone()),
+            WireFormat::PROTOBUF,
+        );
+        assert_eq!(ResponseResult::protobuf_payload(bytes), run_test(&request));
+    }
+
+    #[test]
+    fn json_to_binary() {
+        let request = request(
+            Payload::json_payload("{\"optionalInt32\": 17}".to_owned()),
+            WireFormat::PROTOBUF,
+        );
+        let mut message = TestAllTypesProto3::new();
+        message.optional_int32 = 17;
+        assert_eq!(
+            ResponseResult::protobuf_payload(message.write_to_bytes().unwrap()),
+            run_test(&request)
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let request = request(Payload::protobuf_payload(vec![0xff]), WireFormat::PROTOBUF);
+        match run_test(&request) {
+            ResponseResult::parse_error(..) => {}
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn serve() {
+        let request = request(Payload::protobuf_payload(Vec::new()), WireFormat::JSON);
+        let bytes = request.write_to_bytes().unwrap();
+        let mut input = Vec::new();
+        input.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        input.extend_from_slice(&bytes);
+
+        let mut output = Vec::new();
+        assert!(serve_one(&mut &input[..], &mut output).unwrap());
+        assert!(!serve_one(&mut &input[input.len()..], &mut output).unwrap());
+
+        let response = ConformanceResponse::parse_from_bytes(&output[4..]).unwrap();
+        assert_eq!(
+            Some(ResponseResult::json_payload("{}".to_owned())),
+            response.result
+        );
+    }
+}
@@ -85,6 +85,19 @@ fn test_message<M: Message>(bytes: &[u8]) {
     }
 }
 
+pub fn fuzz_target_round_trip(bytes: &[u8]) {
+    if bytes.len() < 1 {
+        return;
+    }
+    let descriptor = match bytes[0] {
+        0 => all_types_pb::TestTypesSingular::descriptor_static(),
+        1 => all_types_pb::TestTypesRepeated::descriptor_static(),
+        2 => all_types_pb::TestTypesMap::descriptor_static(),
+        _ => return,
+    };
+    protobuf::fuzz::fuzz_all(&descriptor, &bytes[1..]);
+}
+
 pub fn fuzz_target_all(bytes: &[u8]) {
     if bytes.len() < 1 {
         return;
@@ -96,3 +109,4 @@ pub fn fuzz_target_all(bytes: &[u8]) {
         _ => {}
     }
 }
+
@@ -16,9 +16,16 @@ use protobuf_test_common::build::*;
 use protoc::Protoc;
 
 fn test_protoc_bin_path() -> PathBuf {
-    let path = protoc_bin_vendored::protoc_bin_path().unwrap();
-    assert!(Protoc::from_path(&path).version().unwrap().is_3());
-    path
+    protoc_bin_vendored::protoc_bin_path().unwrap()
+}
+
+/// `src/v3` and `src/google` tests are compiled with `cfg(proto3)`
+/// when `protoc` supports proto3.
+fn protoc_supports_proto3() -> bool {
+    Protoc::from_path(test_protoc_bin_path())
+        .version()
+        .unwrap()
+        .is_3()
 }
 
 fn codegen() -> protoc_rust::Codegen {
@@ -53,12 +60,14 @@ fn generate_in_common() {
     gen_in_dir("src/common/v3", "src/common/v3");
 }
 
-fn generate_in_v2_v3() {
+fn generate_in_v2_v3(proto3: bool) {
     gen_in_dir("src/v2", "src/v2");
 
-    gen_in_dir("src/v3", "src/v3");
+    if proto3 {
+        gen_in_dir("src/v3", "src/v3");
 
-    gen_in_dir("src/google/protobuf", "src");
+        gen_in_dir("src/google/protobuf", "src");
+    }
 }
 
 fn generate_interop() {
@@ -89,9 +98,9 @@ fn generate_include_generated() {
         .unwrap();
 }
 
-fn generate_pb_rs() {
+fn generate_pb_rs(proto3: bool) {
     generate_in_common();
-    generate_in_v2_v3();
+    generate_in_v2_v3(proto3);
     generate_interop();
     generate_include_generated();
 }
@@ -101,7 +110,13 @@ fn main() {
 
     cfg_serde();
 
+    println!("cargo:rustc-check-cfg=cfg(proto3)");
+    let proto3 = protoc_supports_proto3();
+    if proto3 {
+        println!("cargo:rustc-cfg=proto3");
+    }
+
     clean_old_files();
 
-    generate_pb_rs();
+    generate_pb_rs(proto3);
 }
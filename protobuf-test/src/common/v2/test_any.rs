@@ -1,6 +1,7 @@
 use super::test_any_pb::MessageOne;
 use super::test_any_pb::MessageTwo;
 use protobuf::well_known_types::Any;
+use protobuf::well_known_types::AnyUnpackError;
 use protobuf::Message;
 
 #[test]
@@ -36,3 +37,27 @@ fn test_dynamic() {
         .unwrap()
         .is_none());
 }
+
+#[test]
+fn test_unpack_checked() {
+    let mut m1 = MessageOne::new();
+    m1.set_i(10);
+    let mut any = Any::pack(&m1).unwrap();
+    assert_eq!(m1, any.unpack_checked::<MessageOne>().unwrap());
+    match any.unpack_checked::<MessageTwo>() {
+        Err(AnyUnpackError::TypeMismatch { expected, type_url }) => {
+            assert_eq!("test_any.MessageTwo", expected);
+            assert_eq!("type.googleapis.com/test_any.MessageOne", type_url);
+        }
+        r => panic!("{:?}", r),
+    }
+
+    any.type_url = "example.com/types/test_any.MessageOne".to_owned();
+    assert_eq!(m1, any.unpack_checked::<MessageOne>().unwrap());
+
+    any.value = vec![0xff];
+    match any.unpack_checked::<MessageOne>() {
+        Err(AnyUnpackError::Parse(..)) => {}
+        r => panic!("{:?}", r),
+    }
+}
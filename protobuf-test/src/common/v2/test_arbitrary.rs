@@ -0,0 +1,83 @@
+use protobuf::arbitrary::arbitrary_dynamic;
+use protobuf::arbitrary::arbitrary_message;
+use protobuf::arbitrary::ArbitraryOptions;
+use protobuf::arbitrary::ArbitrarySource;
+use protobuf::json;
+use protobuf::text_format;
+use protobuf::Message;
+
+use super::test_arbitrary_pb::Everything;
+use super::test_arbitrary_pb::Node;
+
+#[test]
+fn binary_round_trip() {
+    let options = ArbitraryOptions::default();
+    for seed in 0..100 {
+        let m: Everything = arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+        let bytes = m.write_to_bytes().unwrap();
+        assert_eq!(m, Everything::parse_from_bytes(&bytes).unwrap());
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    let options = ArbitraryOptions::default();
+    for seed in 0..100 {
+        let m: Everything = arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+        let json = json::print_to_string(&m).unwrap();
+        assert_eq!(
+            m,
+            json::parse_from_str::<Everything>(&json).unwrap(),
+            "{}",
+            json
+        );
+    }
+}
+
+#[test]
+fn text_format_round_trip() {
+    let options = ArbitraryOptions::default();
+    for seed in 0..100 {
+        let m: Everything = arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+        let text = text_format::print_to_string(&m);
+        assert_eq!(
+            m,
+            text_format::parse_from_str::<Everything>(&text).unwrap(),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn dynamic_from_bytes() {
+    let options = ArbitraryOptions::default();
+    let input: Vec<u8> = (0..1000u32).map(|i| (i * 7919 % 251) as u8).collect();
+    let m = arbitrary_dynamic(
+        &Everything::descriptor_static(),
+        &mut ArbitrarySource::new(&input),
+        &options,
+    );
+    let bytes = m.write_to_bytes_dyn().unwrap();
+    let parsed = Everything::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(Some(&parsed), m.downcast_ref::<Everything>());
+}
+
+#[test]
+fn max_depth() {
+    fn depth(node: &Node) -> u32 {
+        let children = node.children.iter().chain(node.named.values());
+        1 + children.map(depth).max().unwrap_or(0)
+    }
+
+    let options = ArbitraryOptions {
+        max_depth: 2,
+        ..Default::default()
+    };
+    for seed in 0..100 {
+        let m: Everything = arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+        if let Some(node) = m.node.as_ref() {
+            assert!(depth(node) <= 2);
+        }
+    }
+}
@@ -64,6 +64,34 @@ fn test_recursion_limit() {
     }
 }
 
+#[test]
+fn test_recursion_limit_struct_value() {
+    use protobuf::ProtobufError;
+    use protobuf::well_known_types::Struct;
+    use protobuf::well_known_types::Value;
+
+    // Oneof and map message fields are counted too
+    let mut value = Value::new();
+    for _ in 0..60 {
+        let mut s = Struct::new();
+        s.fields.insert("a".to_owned(), value);
+        value = Value::new();
+        value.set_struct_value(s);
+    }
+
+    let bytes = value.write_to_bytes().unwrap();
+    match Value::parse_from_bytes(&bytes) {
+        Err(ProtobufError::WireError(..)) => {}
+        r => panic!("{:?}", r),
+    }
+
+    let mut is = CodedInputStream::from_bytes(&bytes);
+    is.set_recursion_limit(1000);
+    let mut parsed = Value::new();
+    parsed.merge_from(&mut is).unwrap();
+    assert_eq!(value, parsed);
+}
+
 #[test]
 fn test_end_by_negative_int() {
     // added following https://github.com/stepancheg/rust-protobuf/pull/209
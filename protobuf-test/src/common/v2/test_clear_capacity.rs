@@ -0,0 +1,75 @@
+use protobuf::Clear;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use super::test_clear_capacity_pb::*;
+
+fn filled() -> Buffers {
+    let mut m = Buffers::new();
+    for i in 0..100 {
+        m.names.push(format!("name {}", i));
+        m.values.push(i);
+        m.strings.insert(i as i32, "x".repeat(10));
+    }
+    m
+}
+
+#[test]
+fn clear_keeps_capacity() {
+    let mut m = filled();
+    m.clear();
+    assert_eq!(Buffers::new(), m);
+    assert!(m.names.capacity() >= 100);
+    assert!(m.values.capacity() >= 100);
+    assert!(m.strings.capacity() >= 100);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut m = filled();
+    m.clear();
+    let retained = m.space_used_excluding_self();
+    m.shrink_to_fit();
+    assert_eq!(Buffers::new(), m);
+    assert_eq!(0, m.names.capacity());
+    assert_eq!(0, m.values.capacity());
+    assert!(m.space_used_excluding_self() < retained);
+}
+
+#[test]
+fn shrink_to_fit_keeps_values() {
+    let mut m = filled();
+    m.values.reserve(1000);
+    m.nested.push(filled());
+    m.nested[0].values.reserve(1000);
+    m.child = protobuf::MessageField::some(filled());
+    m.child.as_mut().unwrap().values.reserve(1000);
+    m.set_kind_name("name".to_owned());
+    m.mut_unknown_fields().add_varint(100, 1);
+
+    let copy = m.clone();
+    m.shrink_to_fit();
+    assert_eq!(copy, m);
+    assert_eq!(100, m.values.capacity());
+    assert_eq!(100, m.nested[0].values.capacity());
+    assert_eq!(100, m.child.as_ref().unwrap().values.capacity());
+}
+
+#[test]
+fn shrink_to_fit_unknown_fields() {
+    let mut m = Buffers::new();
+    m.mut_unknown_fields().add_varint(100, 1);
+    m.clear();
+    assert!(m.space_used_excluding_self() > 0);
+    m.shrink_to_fit();
+    assert_eq!(0, m.space_used_excluding_self());
+}
+
+#[test]
+fn shrink_to_fit_dyn() {
+    let mut m = filled();
+    m.clear();
+    let d: &mut dyn MessageDyn = &mut m;
+    d.shrink_to_fit_dyn();
+    assert_eq!(0, m.values.capacity());
+}
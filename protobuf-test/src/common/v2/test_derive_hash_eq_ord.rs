@@ -0,0 +1,65 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use protobuf::Message;
+
+use super::test_derive_hash_eq_ord_pb::*;
+
+fn key(name: &str, id: i64) -> Key {
+    let mut key = Key::new();
+    key.set_name(name.to_owned());
+    key.set_id(id);
+    key
+}
+
+#[test]
+fn hash_map_key() {
+    let mut map = HashMap::new();
+    map.insert(key("a", 1), 10);
+    map.insert(key("b", 2), 20);
+
+    assert_eq!(Some(&10), map.get(&key("a", 1)));
+    assert_eq!(None, map.get(&key("a", 2)));
+}
+
+#[test]
+fn nested_and_oneof() {
+    let mut a = key("a", 1);
+    a.set_kind_key(key("x", 1));
+    a.set_parent(key("p", 1));
+
+    let mut map = HashMap::new();
+    map.insert(a.clone(), ());
+    assert!(map.contains_key(&a));
+
+    let mut b = a.clone();
+    b.set_kind_name("x".to_owned());
+    assert!(!map.contains_key(&b));
+}
+
+#[test]
+fn cached_size_ignored() {
+    let a = key("a", 1);
+    let b = a.clone();
+    a.compute_size();
+    assert_eq!(a, b);
+    assert_eq!(std::cmp::Ordering::Equal, a.cmp(&b));
+}
+
+#[test]
+fn ord() {
+    let mut set = BTreeSet::new();
+    set.insert(key("b", 1));
+    set.insert(key("a", 2));
+    set.insert(key("a", 1));
+    let names: Vec<_> = set.iter().map(|k| (k.get_name(), k.get_id())).collect();
+    assert_eq!(vec![("a", 1), ("a", 2), ("b", 1)], names);
+}
+
+#[test]
+fn ineligible_skipped() {
+    // Only `PartialEq` is derived for messages with floats
+    let mut m = WithNestedFloat::new();
+    m.mut_with_float().set_f(1.0);
+    assert_eq!(m, m.clone());
+}
@@ -1,3 +1,5 @@
+use protobuf::json;
+use protobuf::text_format;
 use protobuf::ProtobufEnum;
 
 use super::test_enum_alias_pb::*;
@@ -20,9 +22,50 @@ fn test_enum() {
     assert_eq!(EnumWithAlias::A, EnumWithAlias::A_AGAIN);
 }
 
+#[test]
+fn test_enum_alias_names() {
+    assert_eq!("A", EnumWithAlias::A.name());
+    assert_eq!("A_AGAIN", EnumWithAlias::A_AGAIN.name());
+    assert_eq!(Some(EnumWithAlias::A_AGAIN), EnumWithAlias::from_str("A_AGAIN"));
+    assert_eq!("A_AGAIN", format!("{}", EnumWithAlias::A_AGAIN));
+}
+
 #[test]
 fn test_enum_in_message() {
     let mut m = TestEnumWithAlias::new();
     m.set_en(EnumWithAlias::A);
     test_serialize_deserialize("08 0a", &m);
 }
+
+#[test]
+fn test_enum_alias_reflect() {
+    let d = EnumWithAlias::enum_descriptor_static();
+    assert!(d.allow_alias());
+    assert_eq!("A", d.get_value_by_number(10).unwrap().get_name());
+    let names: Vec<_> = d
+        .get_values_by_number(10)
+        .map(|v| v.get_name().to_owned())
+        .collect();
+    assert_eq!(vec!["A", "A_AGAIN"], names);
+
+    let again = d.get_value_by_name("A_AGAIN").unwrap();
+    assert!(again.is_alias());
+    assert!(!d.get_value_by_name("A").unwrap().is_alias());
+    assert_eq!("A_AGAIN", again.cast::<EnumWithAlias>().unwrap().name());
+}
+
+#[test]
+fn test_enum_alias_text_and_json() {
+    let mut m = TestEnumWithAlias::new();
+    m.set_en(EnumWithAlias::A_AGAIN);
+
+    // The first name is printed
+    assert_eq!("en: A", text_format::print_to_string(&m));
+    assert_eq!("{\"en\": \"A\"}", json::print_to_string(&m).unwrap());
+
+    // Any name is parsed
+    let parsed: TestEnumWithAlias = text_format::parse_from_str("en: A_AGAIN").unwrap();
+    assert_eq!(m, parsed);
+    let parsed: TestEnumWithAlias = json::parse_from_str("{\"en\": \"A_AGAIN\"}").unwrap();
+    assert_eq!(m, parsed);
+}
@@ -13,3 +13,43 @@ fn test_enum_values() {
     ];
     assert_eq!(expected, TestEnumValuesEnum::values());
 }
+
+#[test]
+fn test_enum_names() {
+    assert_eq!("WINTER", TestEnumValuesEnum::WINTER.name());
+    assert_eq!(
+        Some(TestEnumValuesEnum::SPRING),
+        TestEnumValuesEnum::from_str("SPRING")
+    );
+    assert_eq!(None, TestEnumValuesEnum::from_str("spring"));
+    assert_eq!(
+        Some(TestEnumValuesEnum::SPRING),
+        TestEnumValuesEnum::from_str_ignore_case("spring")
+    );
+    assert_eq!(None, TestEnumValuesEnum::from_str_ignore_case("FALL"));
+    for &value in TestEnumValuesEnum::values() {
+        assert_eq!(Some(value), TestEnumValuesEnum::from_str(value.name()));
+    }
+}
+
+#[test]
+fn test_enum_display() {
+    assert_eq!("AUTUMN", format!("{}", TestEnumValuesEnum::AUTUMN));
+    assert_eq!("SUMMER  ", format!("{:8}", TestEnumValuesEnum::SUMMER));
+    assert_eq!(
+        "WINTER",
+        format!("{}", ProtobufEnumOrUnknown::new(TestEnumValuesEnum::WINTER))
+    );
+    assert_eq!(
+        "12",
+        format!("{}", ProtobufEnumOrUnknown::<TestEnumValuesEnum>::from_i32(12))
+    );
+}
+
+#[test]
+fn test_dynamic_enum_names() {
+    let descriptor = TestEnumValuesEnum::enum_descriptor_static();
+    let value = descriptor.get_value_by_name_ignore_case("Summer").unwrap();
+    assert_eq!(33, value.value());
+    assert_eq!("SUMMER", format!("{}", value));
+}
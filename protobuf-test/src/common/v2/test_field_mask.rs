@@ -0,0 +1,198 @@
+use protobuf::field_mask;
+use protobuf::field_mask::FieldMaskError;
+use protobuf::field_mask::MergeOptions;
+use protobuf::well_known_types::FieldMask;
+
+use super::test_field_mask_pb::*;
+
+fn mask(paths: &[&str]) -> FieldMask {
+    let mut mask = FieldMask::new();
+    mask.paths = paths.iter().map(|p| (*p).to_owned()).collect();
+    mask
+}
+
+fn src() -> Outer {
+    let mut inner = Inner::new();
+    inner.set_a(10);
+    inner.set_b("src".to_owned());
+    inner.r = vec![3];
+
+    let mut src = Outer::new();
+    src.set_id(1);
+    src.set_name("src".to_owned());
+    src.set_inner(inner);
+    src.values = vec![1, 2];
+    src.counts.insert("x".to_owned(), 1);
+    src.set_kind_int(7);
+    src
+}
+
+fn dst() -> Outer {
+    let mut inner = Inner::new();
+    inner.set_a(20);
+    inner.set_b("dst".to_owned());
+    inner.r = vec![4];
+
+    let mut dst = Outer::new();
+    dst.set_id(2);
+    dst.set_name("dst".to_owned());
+    dst.set_inner(inner);
+    dst.values = vec![0];
+    dst.counts.insert("x".to_owned(), 2);
+    dst.counts.insert("y".to_owned(), 2);
+    dst.set_kind_str("dst".to_owned());
+    dst
+}
+
+#[test]
+fn scalar_fields() {
+    let mut dst = dst();
+    field_mask::merge(&mut dst, &src(), &mask(&["id"]), &MergeOptions::default()).unwrap();
+    assert_eq!(1, dst.get_id());
+    assert_eq!("dst", dst.get_name());
+}
+
+#[test]
+fn nested_path() {
+    let mut dst = dst();
+    field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["inner.a"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(10, dst.get_inner().get_a());
+    assert_eq!("dst", dst.get_inner().get_b());
+
+    // Prefix covers longer path
+    let mut dst = self::dst();
+    field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["inner.a", "inner"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert_eq!("src", dst.get_inner().get_b());
+
+    // Nested message is not created when not set on both sides
+    let mut dst = Outer::new();
+    field_mask::merge(
+        &mut dst,
+        &Outer::new(),
+        &mask(&["inner.a"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert!(!dst.has_inner());
+}
+
+#[test]
+fn message_field() {
+    let mut dst = dst();
+    field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["inner"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(10, dst.get_inner().get_a());
+    assert_eq!(vec![4, 3], dst.get_inner().r);
+
+    let options = MergeOptions {
+        replace_message_fields: true,
+        ..Default::default()
+    };
+    let mut dst = self::dst();
+    field_mask::merge(&mut dst, &src(), &mask(&["inner"]), &options).unwrap();
+    assert_eq!(src().get_inner(), dst.get_inner());
+
+    field_mask::merge(&mut dst, &Outer::new(), &mask(&["inner"]), &options).unwrap();
+    assert!(!dst.has_inner());
+}
+
+#[test]
+fn repeated_fields() {
+    let mut dst = dst();
+    field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["values", "counts"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(vec![0, 1, 2], dst.values);
+    assert_eq!(2, dst.counts.len());
+    assert_eq!(1, dst.counts["x"]);
+
+    let options = MergeOptions {
+        replace_repeated_fields: true,
+        ..Default::default()
+    };
+    let mut dst = self::dst();
+    field_mask::merge(&mut dst, &src(), &mask(&["values", "counts"]), &options).unwrap();
+    assert_eq!(vec![1, 2], dst.values);
+    assert_eq!(src().counts, dst.counts);
+}
+
+#[test]
+fn oneof() {
+    let mut dst = dst();
+    field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["kind_str"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    // Not set in source, cleared in destination
+    assert!(!dst.has_kind_str());
+
+    let mut dst = self::dst();
+    field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["kind_int"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(7, dst.get_kind_int());
+
+    // Other field of the oneof is preserved
+    let mut dst = self::dst();
+    field_mask::merge(
+        &mut dst,
+        &Outer::new(),
+        &mask(&["kind_int"]),
+        &MergeOptions::default(),
+    )
+    .unwrap();
+    assert_eq!("dst", dst.get_kind_str());
+}
+
+#[test]
+fn invalid_paths() {
+    let mut dst = dst();
+    match field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["id", "inner.zz"]),
+        &MergeOptions::default(),
+    ) {
+        Err(FieldMaskError::UnknownField(path)) => assert_eq!("inner.zz", path),
+        r => panic!("expecting unknown field: {:?}", r),
+    }
+    match field_mask::merge(
+        &mut dst,
+        &src(),
+        &mask(&["values.a"]),
+        &MergeOptions::default(),
+    ) {
+        Err(FieldMaskError::NotMessageField(path)) => assert_eq!("values.a", path),
+        r => panic!("expecting not message field: {:?}", r),
+    }
+    // Not modified on error
+    assert_eq!(self::dst(), dst);
+}
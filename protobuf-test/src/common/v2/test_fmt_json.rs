@@ -3,6 +3,7 @@ use std::f64;
 
 use protobuf::json;
 use protobuf::Message;
+use protobuf::ProtobufEnumOrUnknown;
 
 use protobuf_test_common::*;
 
@@ -54,6 +55,17 @@ fn test_double() {
     test_json_print_parse_message("{\"doubleRepeated\": [13.0, \"Infinity\"]}", &m);
 }
 
+#[test]
+fn test_float_round_trip() {
+    let mut m = TestTypes::new();
+    m.float_repeated = vec![0.1, 3.4028235e38, 1e-45];
+    m.double_repeated = vec![0.1 + 0.2, 1.7976931348623157e308, 5e-324];
+    test_json_print_parse_message(
+        "{\"doubleRepeated\": [0.30000000000000004, 1.7976931348623157e308, 5e-324], \"floatRepeated\": [0.1, 3.4028235e38, 1e-45]}",
+        &m,
+    );
+}
+
 #[test]
 fn test_int32() {
     let mut m = TestTypes::new();
@@ -241,6 +253,31 @@ fn test_enum_int() {
     assert_eq!("{\"testEnumSingular\": 10}", json);
 }
 
+#[test]
+fn test_enum_lenient() {
+    let mut m = TestTypes::new();
+    m.set_test_enum_singular(TestEnum::DARK);
+    test_json_parse_message("{\"testEnumSingular\": \"10\"}", &m);
+
+    assert!(json::parse_from_str::<TestTypes>("{\"testEnumSingular\": \"BRIGHT\"}").is_err());
+
+    let parse_options = json::ParseOptions {
+        ignore_unknown_enum_values: true,
+        ..Default::default()
+    };
+    let parsed: TestTypes = json::parse_from_str_with_options(
+        "{\"testEnumSingular\": \"BRIGHT\", \"testEnumRepeated\": [\"DARK\", \"BRIGHT\", 30]}",
+        &parse_options,
+    )
+    .unwrap();
+    let mut expected = TestTypes::new();
+    expected.set_test_enum_repeated(vec![
+        TestEnum::DARK.into(),
+        ProtobufEnumOrUnknown::from_i32(30),
+    ]);
+    assert_eq!(expected, parsed);
+}
+
 #[test]
 fn test_map_field_int_key() {
     let mut m = TestTypes::new();
@@ -332,6 +369,21 @@ fn test_use_json_name() {
     assert_eq!("{\"Field With json_name\": true}", json);
 }
 
+#[test]
+fn test_parse_json_name() {
+    let mut expected = TestJsonName::new();
+    expected.set_field_with_json_name(true);
+    for json in &[
+        "{\"Field With json_name\": true}",
+        "{\"field_with_json_name\": true}",
+    ] {
+        let m: TestJsonName = json::parse_from_str(json).unwrap();
+        assert_eq!(expected, m);
+    }
+    // Default JSON name is not used when `json_name` is specified
+    assert!(json::parse_from_str::<TestJsonName>("{\"fieldWithJsonName\": true}").is_err());
+}
+
 #[test]
 fn test_more_than_one() {
     let mut m = TestTypes::new();
@@ -344,3 +396,31 @@ fn test_more_than_one() {
         &m,
     );
 }
+
+#[test]
+fn json_schema() {
+    let schema = json::json_schema(&TestTypes::descriptor_static());
+    assert!(schema.starts_with(
+        "{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \
+         \"$ref\": \"#/$defs/test_fmt_json.TestTypes\",\n"
+    ));
+    assert!(schema.contains(
+        "\"int64Singular\": {\n          \"type\": [\"string\", \"integer\"],\n          \
+         \"pattern\": \"^-?[0-9]+$\"\n        }"
+    ));
+    assert!(schema.contains(
+        "\"bytesSingular\": {\n          \"type\": \"string\",\n          \
+         \"contentEncoding\": \"base64\"\n        }"
+    ));
+    assert!(schema.contains(
+        "\"testEnumRepeated\": {\n          \"type\": \"array\",\n          \
+         \"items\": {\n            \"$ref\": \"#/$defs/test_fmt_json.TestEnum\"\n          }\n        }"
+    ));
+    assert!(schema.contains(
+        "\"uint32MapField\": {\n          \"type\": \"object\",\n          \
+         \"propertyNames\": {\n            \"type\": \"string\",\n            \
+         \"pattern\": \"^(true|false)$\"\n          },"
+    ));
+    assert!(schema.contains("\"enum\": [\"UNKNOWN\", \"DARK\", \"LIGHT\"]"));
+    assert!(schema.contains("\"test_fmt_json.TestMessage\": {"));
+}
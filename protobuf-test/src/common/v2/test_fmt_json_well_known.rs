@@ -1,4 +1,10 @@
+use protobuf::json;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::reflect::MessageFactory;
 use protobuf::well_known_types::*;
+use protobuf::Message;
+use protobuf::MessageDyn;
 
 use protobuf_test_common::*;
 
@@ -129,9 +135,114 @@ fn test_wrappers() {
 
 #[test]
 fn test_any() {
+    MessageFactory::global().add_file(&file_descriptor());
+
     let mut m = TestFmtJsonWellKnownTypes::new();
     m.any_value.mut_or_default();
-    // TODO
+    test_json_print_parse_message("{\"anyValue\": {}}", &m);
+
+    let mut inner = TestFmtJsonWellKnownTypes::new();
+    inner.mut_value().set_bool_value(true);
+    m.set_any_value(Any::pack(&inner).unwrap());
+    test_json_print_parse_message(
+        "{\"anyValue\": {\"@type\": \"type.googleapis.com/TestFmtJsonWellKnownTypes\", \"value\": true}}",
+        &m,
+    );
+    // `@type` is not required to be the first member
+    test_json_parse_message(
+        "{\"anyValue\": {\"value\": true, \"@type\": \"type.googleapis.com/TestFmtJsonWellKnownTypes\"}}",
+        &m,
+    );
+}
+
+#[test]
+fn test_any_well_known() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    let mut d = Duration::new();
+    d.seconds = 2;
+    m.set_any_value(Any::pack(&d).unwrap());
+    test_json_print_parse_message(
+        "{\"anyValue\": {\"@type\": \"type.googleapis.com/google.protobuf.Duration\", \"value\": \"2s\"}}",
+        &m,
+    );
+
+    let mut v = Int32Value::new();
+    v.value = 3;
+    let any = Any::pack(&v).unwrap();
+    m.set_any_value(Any::pack(&any).unwrap());
+    test_json_print_parse_message(
+        "{\"anyValue\": {\"@type\": \"type.googleapis.com/google.protobuf.Any\", \"value\": \
+         {\"@type\": \"type.googleapis.com/google.protobuf.Int32Value\", \"value\": 3}}}",
+        &m,
+    );
+}
+
+#[test]
+fn test_any_unknown_type() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.mut_any_value().type_url = "type.googleapis.com/unknown.Message".to_owned();
+    assert!(json::print_to_string(&m).is_err());
+
+    let r = json::parse_from_str::<TestFmtJsonWellKnownTypes>(
+        "{\"anyValue\": {\"@type\": \"type.googleapis.com/unknown.Message\"}}",
+    );
+    assert!(r.is_err());
+    // Non-empty `Any` without `@type`
+    let r = json::parse_from_str::<TestFmtJsonWellKnownTypes>("{\"anyValue\": {\"value\": 1}}");
+    assert!(r.is_err());
+}
+
+/// Descriptor of `TestFmtJsonWellKnownTypes` from dynamic copies
+/// of its file and well-known type files.
+fn dynamic_descriptor() -> MessageDescriptor {
+    let file = TestFmtJsonWellKnownTypes::descriptor_static()
+        .file_descriptor()
+        .clone();
+    let files = FileDescriptor::new_dynamic_fds(file.file_descriptor_set().file.into());
+    files
+        .iter()
+        .find(|f| f.proto().get_name() == file.proto().get_name())
+        .unwrap()
+        .message_by_package_relative_name("TestFmtJsonWellKnownTypes")
+        .unwrap()
+}
+
+#[test]
+fn test_dynamic() {
+    let mut m = TestFmtJsonWellKnownTypes::new();
+    m.mut_duration().seconds = 3;
+    m.mut_timestamp().nanos = 1000;
+    m.mut_field_mask().paths.push("a.b".to_owned());
+    m.mut_value().set_string_value("v".to_owned());
+    let mut number = Value::new();
+    number.set_number_value(1.5);
+    m.mut_struct_value().fields.insert("k".to_owned(), number);
+    m.mut_int32_value().value = 7;
+    let mut d = Duration::new();
+    d.seconds = 2;
+    m.set_any_value(Any::pack(&d).unwrap());
+    let json = json::print_to_string(&m).unwrap();
+
+    let descriptor = dynamic_descriptor();
+    let dynamic = (&m as &dyn MessageDyn)
+        .convert_to_dyn(&descriptor)
+        .unwrap()
+        .unwrap();
+    assert!(dynamic
+        .downcast_ref::<TestFmtJsonWellKnownTypes>()
+        .is_none());
+    assert_eq!(json, json::print_to_string(&*dynamic).unwrap());
+
+    let parsed = json::parse_dynamic_from_str(&descriptor, &json).unwrap();
+    assert_eq!(descriptor, parsed.descriptor_dyn());
+    assert_eq!(Some(m.clone()), parsed.convert_to().unwrap());
+
+    // Well-known type fields are replaced, not merged, like in generated messages
+    let mut parsed = dynamic;
+    json::merge_from_str(&mut *parsed, "{\"fieldMask\": \"c\", \"duration\": \"1s\"}").unwrap();
+    m.mut_field_mask().paths = vec!["c".to_owned()];
+    m.mut_duration().seconds = 1;
+    assert_eq!(Some(m), parsed.convert_to().unwrap());
 }
 
 #[test]
@@ -154,4 +265,58 @@ fn test_field_mask() {
         v
     });
     test_json_print_parse_message("{\"fieldMask\": \"ab,c.d.e\"}", &m);
+
+    m.set_field_mask({
+        let mut v = FieldMask::new();
+        v.paths = vec!["foo_bar.baz".to_owned(), "qux".to_owned()].into();
+        v
+    });
+    test_json_print_parse_message("{\"fieldMask\": \"fooBar.baz,qux\"}", &m);
+}
+
+#[test]
+fn test_value_recursion_limit() {
+    let depth = 150;
+    let json = format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(json::parse_from_str::<Value>(&json).is_err());
+
+    let mut parse_options = json::ParseOptions::default();
+    parse_options.recursion_limit = depth as u32 + 1;
+    let value: Value = json::parse_from_str_with_options(&json, &parse_options).unwrap();
+    assert!(value.has_list_value());
+
+    // Ignored unknown fields are limited too
+    let json = format!(
+        "{{\"unknown\": {}{}}}",
+        "[".repeat(depth),
+        "]".repeat(depth)
+    );
+    let mut parse_options = json::ParseOptions::default();
+    parse_options.ignore_unknown_fields = true;
+    assert!(
+        json::parse_from_str_with_options::<TestFmtJsonWellKnownTypes>(&json, &parse_options)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_value_nodes_limit() {
+    let json = "{\"value\": [1, 2, {\"a\": [3]}]}";
+    let mut parse_options = json::ParseOptions::default();
+    parse_options.value_nodes_limit = Some(6);
+    json::parse_from_str_with_options::<TestFmtJsonWellKnownTypes>(json, &parse_options).unwrap();
+    parse_options.value_nodes_limit = Some(5);
+    assert!(
+        json::parse_from_str_with_options::<TestFmtJsonWellKnownTypes>(json, &parse_options)
+            .is_err()
+    );
+}
+
+#[test]
+fn json_schema() {
+    let schema = json::json_schema(&TestFmtJsonWellKnownTypes::descriptor_static());
+    assert!(schema.contains("\"format\": \"date-time\""));
+    assert!(schema.contains("\"required\": [\"@type\"]"));
+    // well-known types are inlined
+    assert!(!schema.contains("#/$defs/google.protobuf."));
 }
@@ -1,4 +1,7 @@
+use protobuf::reflect::FileDescriptor;
+use protobuf::text_format;
 use protobuf::Message;
+use protobuf::MessageDyn;
 
 use protobuf_test_common::*;
 
@@ -12,6 +15,18 @@ fn test_show() {
     assert_eq!("bool_singular: true", &*format!("{:?}", m));
 }
 
+#[test]
+fn test_show_no_internals() {
+    let mut m = TestTypes::new();
+    m.set_bool_singular(true);
+    m.mut_unknown_fields().add_varint(1000, 17);
+    m.compute_size();
+    let s = format!("{:?}", m);
+    assert_eq!("bool_singular: true 1000: 17", &*s);
+    assert!(!s.contains("cached_size"));
+    assert!(!s.contains("unknown_fields"));
+}
+
 #[test]
 fn test_pretty() {
     let mut tm = TestMessage::new();
@@ -82,6 +97,45 @@ fn test_parse_float() {
     test_text_format_str_descriptor("double_singular: 99", &TestTypes::descriptor_static());
 }
 
+#[test]
+fn test_float_shortest() {
+    t("float_singular: 0.1", |m| m.set_float_singular(0.1));
+    t("float_singular: 1e+20", |m| m.set_float_singular(1e20));
+    t("double_singular: 0.1", |m| m.set_double_singular(0.1));
+    t("double_singular: 1e-07", |m| m.set_double_singular(1e-7));
+    t("double_singular: 0.30000000000000004", |m| {
+        m.set_double_singular(0.1 + 0.2)
+    });
+    t("double_singular: nan", |m| m.set_double_singular(f64::NAN));
+    t("double_singular: -inf", |m| {
+        m.set_double_singular(f64::NEG_INFINITY)
+    });
+
+    test_text_format_str_descriptor(
+        "float_singular: 3.4028235e+38",
+        &TestTypes::descriptor_static(),
+    );
+    test_text_format_str_descriptor("double_singular: 5e-324", &TestTypes::descriptor_static());
+    test_text_format_str_descriptor(
+        "double_singular: 1.7976931348623157e+308",
+        &TestTypes::descriptor_static(),
+    );
+}
+
+#[test]
+fn test_parse_float_full_precision_and_hex() {
+    let m: TestTypes = text_format::parse_from_str(
+        "double_singular: 0.1000000000000000055511151231257827021181583404541015625",
+    )
+    .unwrap();
+    assert_eq!(0.1, m.get_double_singular());
+
+    let m: TestTypes =
+        text_format::parse_from_str("double_singular: -0x1.8p3 float_singular: 0x1p-2").unwrap();
+    assert_eq!(-12.0, m.get_double_singular());
+    assert_eq!(0.25, m.get_float_singular());
+}
+
 #[test]
 fn test_bool() {
     test_text_format_str_descriptor("bool_singular: true", &TestTypes::descriptor_static());
@@ -284,3 +338,32 @@ fn test_string_escaped() {
         &*format!("{:?}", m)
     );
 }
+
+#[test]
+fn test_dynamic() {
+    let file = TestTypes::descriptor_static().file_descriptor().clone();
+    let files = FileDescriptor::new_dynamic_fds(file.file_descriptor_set().file.into());
+    let descriptor = files
+        .iter()
+        .find(|f| f.proto().get_name() == file.proto().get_name())
+        .unwrap()
+        .message_by_package_relative_name("TestTypes")
+        .unwrap();
+
+    let mut m = TestTypes::new();
+    m.set_string_singular("ab".to_owned());
+    m.test_message_singular.mut_or_default().set_value(30);
+    m.mut_int32_repeated().push(1);
+    let text = print_to_string(&m);
+
+    let dynamic = (&m as &dyn MessageDyn)
+        .convert_to_dyn(&descriptor)
+        .unwrap()
+        .unwrap();
+    assert!(!dynamic.is::<TestTypes>());
+    assert_eq!(text, print_to_string(&*dynamic));
+
+    let parsed = text_format::parse_dynamic_from_str(&descriptor, &text).unwrap();
+    assert_eq!(descriptor, parsed.descriptor_dyn());
+    assert_eq!(Some(m), parsed.convert_to().unwrap());
+}
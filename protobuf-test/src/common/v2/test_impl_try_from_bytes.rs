@@ -0,0 +1,24 @@
+use std::convert::TryFrom;
+
+use protobuf::Message;
+
+use super::test_impl_try_from_bytes_pb::*;
+
+fn decode<T: for<'a> TryFrom<&'a [u8]>>(bytes: &[u8]) -> Option<T> {
+    T::try_from(bytes).ok()
+}
+
+#[test]
+fn test_try_from_bytes() {
+    let mut p = Point::new();
+    p.set_x(10);
+    p.set_y(-2);
+
+    let bytes = Vec::<u8>::try_from(&p).unwrap();
+    assert_eq!(p.write_to_bytes().unwrap(), bytes);
+    assert_eq!(bytes, Vec::<u8>::try_from(p.clone()).unwrap());
+
+    assert_eq!(p, Point::try_from(&bytes[..]).unwrap());
+    assert_eq!(Some(p), decode::<Point>(&bytes));
+    assert!(Point::try_from(&[0x08][..]).is_err());
+}
@@ -1,6 +1,21 @@
+use protobuf::descriptor::FileDescriptorSet;
+use protobuf::Message;
+
+use super::test_import_descriptor_pb;
 use super::test_import_descriptor_pb::*;
 
 #[test]
 fn test() {
     let _ = FooBar::new();
 }
+
+#[test]
+fn test_file_descriptor_set_bytes() {
+    let set =
+        FileDescriptorSet::parse_from_bytes(test_import_descriptor_pb::file_descriptor_set_bytes())
+            .unwrap();
+    let names: Vec<&str> = set.file.iter().map(|f| f.get_name()).collect();
+    assert_eq!(2, names.len());
+    assert_eq!("google/protobuf/descriptor.proto", names[0]);
+    assert!(names[1].ends_with("test_import_descriptor_pb.proto"));
+}
@@ -0,0 +1,75 @@
+use protobuf::MessageField;
+
+use super::test_iterative_drop_pb::*;
+
+// Deep enough to overflow the stack of a test thread with recursive drop
+const DEPTH: usize = 1_000_000;
+
+#[test]
+fn test_drop_deep_singular() {
+    let mut node = Node::new();
+    for _ in 0..DEPTH {
+        let mut parent = Node::new();
+        parent.child = MessageField::some(node);
+        node = parent;
+    }
+    drop(node);
+}
+
+#[test]
+fn test_drop_deep_repeated_and_map() {
+    let mut node = Node::new();
+    for i in 0..DEPTH {
+        let mut parent = Node::new();
+        if i % 2 == 0 {
+            parent.children.push(node);
+            parent.children.push(Node::new());
+        } else {
+            parent.named.insert(format!("{}", i), node);
+        }
+        node = parent;
+    }
+    drop(node);
+}
+
+#[test]
+fn test_drop_deep_oneof() {
+    let mut node = Node::new();
+    for i in 0..DEPTH {
+        let mut parent = Node::new();
+        if i % 2 == 0 {
+            parent.set_oneof_child(node);
+        } else {
+            let mut leaf = Leaf::new();
+            leaf.node = MessageField::some(node);
+            parent.set_leaf(leaf);
+        }
+        node = parent;
+    }
+    drop(node);
+}
+
+#[test]
+fn test_drop_iterative_explicitly() {
+    let mut node = WithoutDrop::new();
+    for _ in 0..DEPTH {
+        let mut parent = WithoutDrop::new();
+        parent.child = MessageField::some(node);
+        node = parent;
+    }
+    protobuf::rt::drop_iterative(&mut node);
+    assert!(node.child.is_none());
+}
+
+#[test]
+fn test_take_nested_messages_keeps_other_fields() {
+    let mut leaf = Leaf::new();
+    leaf.node = MessageField::some(Node::new());
+    let mut node = Node::new();
+    node.set_name("n".to_owned());
+    node.set_leaf(leaf);
+    protobuf::rt::drop_iterative(&mut node);
+    assert_eq!("n", node.get_name());
+    assert!(node.has_leaf());
+    assert!(node.get_leaf().node.is_none());
+}
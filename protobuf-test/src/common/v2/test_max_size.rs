@@ -0,0 +1,116 @@
+use protobuf::field_mask;
+use protobuf::field_mask::FieldMaskError;
+use protobuf::json;
+use protobuf::reflect::MessageFactory;
+use protobuf::text_format;
+use protobuf::well_known_types::Any;
+use protobuf::well_known_types::FieldMask;
+use protobuf::Message;
+use protobuf::MessageTooLargeError;
+use protobuf::ProtobufError;
+
+use super::test_max_size_pb::*;
+
+fn limited(name: &str) -> Limited {
+    let mut m = Limited::new();
+    m.set_name(name.to_owned());
+    m
+}
+
+fn assert_too_large<T>(size: u64, r: Result<T, ProtobufError>) {
+    match r {
+        Err(ProtobufError::MessageTooLarge(e)) => assert_eq!(
+            MessageTooLargeError {
+                message: "test_max_size.Limited".to_owned(),
+                size,
+                max_size: 8,
+            },
+            e
+        ),
+        Err(e) => panic!("unexpected error: {}", e),
+        Ok(..) => panic!("expecting error"),
+    }
+}
+
+#[test]
+fn test_within_limit() {
+    // tag, length and 6 bytes
+    let m = limited("abcdef");
+    let bytes = m.write_to_bytes().unwrap();
+    assert_eq!(8, bytes.len());
+    assert_eq!(m, Limited::parse_from_bytes(&bytes).unwrap());
+}
+
+#[test]
+fn test_serialize() {
+    assert_too_large(9, limited("abcdefg").write_to_bytes());
+
+    // Limit applies to nested messages too
+    let mut outer = Outer::new();
+    outer.set_other("large message is fine".to_owned());
+    outer.limited = Some(limited("abcdefg")).into();
+    assert_too_large(9, outer.write_to_bytes());
+}
+
+#[test]
+fn test_parse() {
+    // name = "abcdefg"
+    let bytes = b"\x0a\x07abcdefg";
+    assert_too_large(9, Limited::parse_from_bytes(bytes));
+
+    // limited = { name = "abcdefg" }
+    let mut outer = vec![0x0a, bytes.len() as u8];
+    outer.extend_from_slice(bytes);
+    assert_too_large(9, Outer::parse_from_bytes(&outer));
+}
+
+#[test]
+fn test_parse_large_field() {
+    // data = [0; 1000], fails after reading tag and length
+    let mut bytes = vec![0x1a, 0xe8, 0x07];
+    bytes.resize(1003, 0);
+    assert_too_large(3, Limited::parse_from_bytes(&bytes));
+    // Where input ends is not known for `Read`, so it can be truncated input,
+    // but field is not read past the limit anyway
+    match Limited::parse_from_reader(&mut &bytes[..]) {
+        Err(ProtobufError::WireError(..)) => {}
+        r => panic!("expecting wire error: {:?}", r),
+    }
+
+    // Declared length is larger than the limit, but input is truncated
+    match Limited::parse_from_bytes(&bytes[..5]) {
+        Err(ProtobufError::WireError(..)) => {}
+        r => panic!("expecting wire error: {:?}", r),
+    }
+}
+
+#[test]
+fn test_parse_any() {
+    MessageFactory::global().add_file(&file_descriptor());
+
+    assert!(json::parse_from_str::<Any>(
+        r#"{"@type": "type.googleapis.com/test_max_size.Limited", "name": "much too long"}"#
+    )
+    .is_err());
+    assert!(text_format::parse_from_str::<Any>(
+        r#"[type.googleapis.com/test_max_size.Limited] { name: "much too long" }"#
+    )
+    .is_err());
+}
+
+#[test]
+fn test_field_mask_merge() {
+    let mut src = Outer::new();
+    src.limited = Some(limited("abcdefg")).into();
+    let mut mask = FieldMask::new();
+    mask.paths.push("limited".to_owned());
+    let mut dst = Outer::new();
+    dst.limited = Some(limited("a")).into();
+    match field_mask::merge(&mut dst, &src, &mask, &Default::default()) {
+        Err(FieldMaskError::MergeMessage(name, e)) => {
+            assert_eq!("limited", name);
+            assert_too_large(9, Err::<(), _>(e));
+        }
+        r => panic!("expecting merge error: {:?}", r),
+    }
+}
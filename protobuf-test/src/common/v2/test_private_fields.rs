@@ -0,0 +1,27 @@
+use super::test_private_fields_pb::*;
+
+#[test]
+fn test_accessors() {
+    let mut m = WithPrivateFields::new();
+    m.set_i(10);
+    m.set_s("x".to_owned());
+    m.mut_inner().set_value(20);
+    m.mut_is().push(30);
+    m.mut_m().insert("k".to_owned(), 40);
+    m.set_b("y".to_owned());
+
+    assert_eq!(10, m.get_i());
+    assert_eq!("x", m.get_s());
+    assert!(m.has_inner());
+    assert_eq!(20, m.get_inner().get_value());
+    assert_eq!(&[30], m.get_is());
+    assert_eq!(Some(&40), m.get_m().get("k"));
+    assert!(m.has_b());
+    assert!(!m.has_a());
+    assert_eq!("y", m.get_b());
+
+    m.clear_inner();
+    m.clear_b();
+    assert!(!m.has_inner());
+    assert!(!m.has_b());
+}
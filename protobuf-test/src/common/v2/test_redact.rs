@@ -0,0 +1,85 @@
+use protobuf::json;
+use protobuf::reflect::FileDescriptor;
+use protobuf::reflect::ReflectValueBox;
+use protobuf::text_format;
+use protobuf::text_format::Unredacted;
+use protobuf::Message;
+
+use super::test_redact_pb::*;
+
+fn request() -> Request {
+    let mut credentials = Credentials::new();
+    credentials.set_user("alice".to_owned());
+    credentials.set_token("secret".to_owned());
+    credentials.keys = vec!["k1".to_owned(), "k2".to_owned()];
+    let mut request = Request::new();
+    request.set_method("get".to_owned());
+    request.set_credentials(credentials);
+    request
+        .headers
+        .insert("authorization".to_owned(), "bearer secret".to_owned());
+    request
+}
+
+#[test]
+fn text_format() {
+    let request = request();
+    assert_eq!(
+        "method: \"get\" credentials {user: \"alice\" token: [REDACTED] keys: [REDACTED]} headers: [REDACTED]",
+        text_format::print_to_string(&request)
+    );
+    assert_eq!(
+        text_format::print_to_string(&request),
+        format!("{:?}", request)
+    );
+    assert!(!format!("{:#?}", request).contains("secret"));
+    assert!(!text_format::print_to_string(&Credentials::new()).contains(text_format::REDACTED));
+}
+
+#[test]
+fn unredacted() {
+    let request = request();
+    let text = text_format::print_to_string_unredacted(&request);
+    assert!(text.contains("token: \"secret\""));
+    assert_eq!(text, format!("{:?}", Unredacted(&request)));
+    assert_eq!(request, text_format::parse_from_str(&text).unwrap());
+}
+
+#[test]
+fn json() {
+    let request = request();
+    let redacted = json::print_to_string(&request).unwrap();
+    assert!(redacted.contains("\"token\": \"[REDACTED]\""));
+    assert!(!redacted.contains("secret"));
+
+    let print_options = json::PrintOptions {
+        unredacted: true,
+        ..Default::default()
+    };
+    let unredacted = json::print_to_string_with_options(&request, &print_options).unwrap();
+    assert_eq!(request, json::parse_from_str(&unredacted).unwrap());
+}
+
+#[test]
+fn debug_redact_option() {
+    // `debug_redact = true` set by newer `protoc`,
+    // field is unknown to our `descriptor.proto`
+    let mut file = file_descriptor().proto().clone();
+    let field = &mut file.message_type[0].field[0];
+    assert_eq!("user", field.get_name());
+    field
+        .options
+        .mut_or_default()
+        .mut_unknown_fields()
+        .add_varint(16, 1);
+    let file = FileDescriptor::new_dynamic(file, vec![protobuf::rustproto::file_descriptor()]);
+    let descriptor = file
+        .message_by_package_relative_name("Credentials")
+        .unwrap();
+    let user = descriptor.get_field_by_name("user").unwrap();
+    assert!(user.is_redacted());
+
+    let mut m = descriptor.new_instance();
+    user.set_singular_field(&mut *m, ReflectValueBox::String("alice".to_owned()));
+    assert_eq!("user: [REDACTED]", text_format::print_to_string(&*m));
+}
@@ -1,7 +1,9 @@
 use protobuf::reflect::FieldDescriptor;
+use protobuf::reflect::FileDescriptor;
 use protobuf::reflect::ReflectFieldRef;
 use protobuf::reflect::ReflectValueBox;
 use protobuf::reflect::ReflectValueRef;
+use protobuf::rustproto;
 use protobuf::Message;
 use protobuf::MessageDyn;
 use protobuf::ProtobufEnum;
@@ -266,3 +268,143 @@ fn test_json_name() {
     // the descriptor itself.
     assert_eq!("subM", field_descriptor.get_proto().get_json_name());
 }
+
+#[test]
+fn test_reserved() {
+    let m = WithReserved::descriptor_static();
+    assert_eq!(
+        vec![2..3, 15..16, 9..12],
+        m.reserved_ranges().collect::<Vec<_>>()
+    );
+    assert_eq!(vec!["bar", "baz"], m.reserved_names().collect::<Vec<_>>());
+    assert!(m.is_reserved_number(10));
+    assert!(!m.is_reserved_number(12));
+    assert!(m.is_reserved_name("baz"));
+    assert!(!m.is_reserved_name("foo"));
+
+    let e = EnumWithReserved::enum_descriptor_static();
+    assert_eq!(
+        vec![2..=2, 15..=15, 9..=11],
+        e.reserved_ranges().collect::<Vec<_>>()
+    );
+    assert_eq!(vec!["BAR", "BAZ"], e.reserved_names().collect::<Vec<_>>());
+    assert!(e.is_reserved_number(11));
+    assert!(!e.is_reserved_number(12));
+    assert!(e.is_reserved_name("BAR"));
+}
+
+#[test]
+fn test_options() {
+    let m = WithOptions::descriptor_static();
+    assert!(m.options().get_deprecated());
+    assert!(m
+        .get_field_by_name("field")
+        .unwrap()
+        .options()
+        .get_deprecated());
+    assert!(!m.get_field_by_name("a").unwrap().options().get_deprecated());
+    let oneof = m.oneofs().next().unwrap();
+    assert_eq!("one", oneof.get_name());
+    assert!(oneof.options().uninterpreted_option.is_empty());
+
+    let e = EnumWithOptions::enum_descriptor_static();
+    assert!(e.options().get_deprecated());
+    assert!(e.get_value_by_number(0).unwrap().options().get_deprecated());
+
+    let file = m.file_descriptor();
+    assert_eq!(
+        Some(true),
+        rustproto::exts::generate_accessors_all.get(file.options())
+    );
+}
+
+#[test]
+fn test_convert_dynamic_generated() {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![rustproto::file_descriptor()],
+    );
+    let dynamic_descriptor = file.message_by_package_relative_name("M").unwrap();
+    assert!(dynamic_descriptor != M::descriptor_static());
+
+    let mut m = M::new();
+    m.mut_sub_m().set_n(42);
+    m.mut_unknown_fields().add_varint(1000, 7);
+
+    let dynamic = (&m as &dyn MessageDyn)
+        .convert_to_dyn(&dynamic_descriptor)
+        .unwrap()
+        .unwrap();
+    assert_eq!(dynamic_descriptor, dynamic.descriptor_dyn());
+    let sub_m = dynamic_descriptor.get_field_by_name("sub_m").unwrap();
+    assert_eq!(
+        Some(ReflectValueRef::I32(42)),
+        sub_m
+            .get_message(&*dynamic)
+            .descriptor_dyn()
+            .get_field_by_name("n")
+            .unwrap()
+            .get_singular(&*sub_m.get_message(&*dynamic))
+    );
+
+    assert_eq!(Some(m.clone()), dynamic.convert_to::<M>().unwrap());
+    assert_eq!(None, dynamic.convert_to::<SubM>().unwrap());
+    assert!(dynamic
+        .convert_to_dyn(&SubM::descriptor_static())
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        Some(m.clone()),
+        (&m as &dyn MessageDyn).convert_to::<M>().unwrap()
+    );
+}
+
+#[test]
+fn test_convert_dynamic_all_types() {
+    let file = FileDescriptor::new_dynamic(
+        file_descriptor().proto().clone(),
+        vec![rustproto::file_descriptor()],
+    );
+
+    fn check<M: Message + PartialEq>(file: &FileDescriptor, fill: fn(&mut M)) {
+        let mut m = M::new();
+        fill(&mut m);
+        let dynamic_descriptor = file
+            .message_by_package_relative_name(M::descriptor_static().name())
+            .unwrap();
+        let dynamic = (&m as &dyn MessageDyn)
+            .convert_to_dyn(&dynamic_descriptor)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            m.write_to_bytes().unwrap(),
+            dynamic.write_to_bytes_dyn().unwrap()
+        );
+        assert_eq!(Some(m), dynamic.convert_to::<M>().unwrap());
+    }
+
+    check::<TestTypesSingular>(&file, |m| {
+        for field in m.descriptor_dyn().fields() {
+            if let RuntimeFieldType::Singular(t) = field.runtime_field_type() {
+                field.set_singular_field(m, value_for_runtime_type(&t));
+            }
+        }
+    });
+    check::<TestTypesRepeated>(&file, |m| {
+        for field in m.descriptor_dyn().fields() {
+            if let RuntimeFieldType::Repeated(t) = field.runtime_field_type() {
+                field.mut_repeated(m).push(value_for_runtime_type(&t));
+                field.mut_repeated(m).push(value_for_runtime_type(&t));
+            }
+        }
+    });
+    check::<TestTypesMap>(&file, |m| {
+        for field in m.descriptor_dyn().fields() {
+            if let RuntimeFieldType::Map(k, v) = field.runtime_field_type() {
+                field
+                    .mut_map(m)
+                    .insert(value_for_runtime_type(&k), value_for_runtime_type(&v));
+            }
+        }
+    });
+}
@@ -1,3 +1,9 @@
+use protobuf::reflect::DynamicInvoker;
+use protobuf::Message;
+use protobuf::ProtobufError;
+
+use super::test_basic_pb::Test1;
+use super::test_basic_pb::Test2;
 use super::test_service_pb::*;
 
 #[test]
@@ -8,3 +14,94 @@ fn test_service() {
     let _ = Request::new();
     let _ = Response::new();
 }
+
+#[test]
+fn test_service_descriptor() {
+    let file = file_descriptor();
+    let service = file.service_by_name("Service").unwrap();
+    assert_eq!("service.Service", service.full_name());
+    assert_eq!(4, service.methods().len());
+
+    let method = service.method_by_name("ShortForm").unwrap();
+    assert_eq!("service.Service.ShortForm", method.full_name());
+    assert_eq!(Request::descriptor_static(), method.input_type());
+    assert_eq!(Response::descriptor_static(), method.output_type());
+    assert!(!method.is_client_streaming());
+    assert!(!method.is_server_streaming());
+
+    let method = service.method_by_name("DottyLongForm").unwrap();
+    assert_eq!(Test1::descriptor_static(), method.input_type());
+    assert_eq!(Test2::descriptor_static(), method.output_type());
+}
+
+#[test]
+fn test_dynamic_invoker() {
+    let mut invoker = DynamicInvoker::new();
+    invoker.add_file(&file_descriptor());
+    assert_eq!(4, invoker.methods().count());
+    assert!(invoker.method("service.Service.Unknown").is_none());
+
+    invoker.set_handler("service.Service.ShortForm", |request| {
+        assert!(request.downcast_ref::<Request>().is_some());
+        Ok(Box::new(Response::new()))
+    });
+
+    let request = Request::new().write_to_bytes().unwrap();
+    let response = invoker
+        .invoke("service.Service.ShortForm", &request)
+        .unwrap()
+        .unwrap();
+    Response::parse_from_bytes(&response).unwrap();
+
+    assert!(invoker.invoke("service.Service.LongForm", &[]).is_none());
+}
+
+#[test]
+fn test_dynamic_invoker_type_mismatch() {
+    let mut invoker = DynamicInvoker::new();
+    invoker.add_file(&file_descriptor());
+    invoker.set_handler("service.Service.ShortForm", |_request| {
+        Ok(Box::new(Test2::new()))
+    });
+
+    match invoker.invoke_dyn("service.Service.ShortForm", &Test1::new()) {
+        Some(Err(ProtobufError::MessageTypeMismatch(e))) => {
+            assert_eq!("service.Request", e.expected);
+            assert_eq!("basic.Test1", e.actual);
+        }
+        _ => panic!("expecting request type mismatch"),
+    }
+
+    match invoker.invoke_dyn("service.Service.ShortForm", &Request::new()) {
+        Some(Err(ProtobufError::MessageTypeMismatch(e))) => {
+            assert_eq!("service.Response", e.expected);
+            assert_eq!("basic.Test2", e.actual);
+        }
+        _ => panic!("expecting response type mismatch"),
+    }
+}
+
+#[test]
+fn test_dynamic_invoker_request_not_initialized() {
+    let mut invoker = DynamicInvoker::new();
+    invoker.add_file(&file_descriptor());
+    invoker.set_handler("service.Service.DottyShortForm", |request| {
+        assert!(request.is_initialized_dyn());
+        let mut response = Test2::new();
+        response.set_b("b".to_owned());
+        Ok(Box::new(response))
+    });
+
+    let response = invoker
+        .invoke("service.Service.DottyShortForm", &[])
+        .unwrap();
+    // Field `a` is required only in proto2.
+    if Test1::new().is_initialized() {
+        response.unwrap();
+    } else {
+        match response {
+            Err(ProtobufError::MessageNotInitialized(e)) => assert_eq!("basic.Test1", e.message),
+            _ => panic!("expecting not initialized error"),
+        }
+    }
+}
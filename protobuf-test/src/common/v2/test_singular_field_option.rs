@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use protobuf::reflect::ReflectValueBox;
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use protobuf_test_common::*;
+
+use super::test_singular_field_option_pb::*;
+
+fn sub(n: i32) -> SubMessage {
+    let mut sub = SubMessage::new();
+    sub.set_n(n);
+    sub
+}
+
+#[test]
+fn test_option_box_types() {
+    let mut m = TestOptionBox::new();
+    let _: &Option<Box<SubMessage>> = &m.sub;
+    let _: &Option<Box<TestOptionBox>> = &m.recursive;
+
+    assert!(!m.has_sub());
+    assert_eq!(0, m.get_sub().get_n());
+    m.set_sub(sub(10));
+    assert!(m.has_sub());
+    assert_eq!(10, m.get_sub().get_n());
+    m.mut_sub().set_n(11);
+    assert_eq!(Some(11), m.sub.as_ref().map(|s| s.get_n()));
+    assert_eq!(11, m.take_sub().get_n());
+    assert!(m.sub.is_none());
+
+    m.mut_recursive().mut_sub().set_n(1);
+    test_serialize_deserialize_no_hex(&m);
+}
+
+#[test]
+fn test_option_arc_types() {
+    let mut m = TestOptionArc::new();
+    let _: &Option<Arc<SubMessage>> = &m.sub;
+    let _: &Option<Arc<TestOptionArc>> = &m.recursive;
+
+    assert!(!m.has_sub());
+    assert_eq!(0, m.get_sub().get_n());
+    m.set_sub(sub(10));
+    assert!(m.has_sub());
+    assert_eq!(10, m.get_sub().get_n());
+    m.mut_sub().set_n(11);
+    assert_eq!(Some(11), m.sub.as_ref().map(|s| s.get_n()));
+    assert_eq!(11, m.take_sub().get_n());
+    assert!(m.sub.is_none());
+
+    m.mut_recursive().mut_sub().set_n(1);
+    test_serialize_deserialize_no_hex(&m);
+}
+
+#[test]
+fn test_option_arc_shared() {
+    let shared = Arc::new(sub(30));
+    let mut a = TestOptionArc::new();
+    a.sub = Some(shared.clone());
+    let b = a.clone();
+    assert!(Arc::ptr_eq(&shared, b.sub.as_ref().unwrap()));
+
+    // Mutation does not affect other messages
+    a.mut_sub().set_n(31);
+    assert_eq!(31, a.get_sub().get_n());
+    assert_eq!(30, b.get_sub().get_n());
+    assert_eq!(30, shared.get_n());
+
+    // Shared value is cloned on take
+    assert_eq!(30, b.clone().take_sub().get_n());
+    assert_eq!(30, shared.get_n());
+}
+
+#[test]
+fn test_option_types() {
+    let mut m = TestOption::new();
+    let _: &Option<SubMessage> = &m.sub;
+
+    m.sub = Some(sub(20));
+    assert_eq!(20, m.get_sub().get_n());
+    test_serialize_deserialize_no_hex(&m);
+
+    m.clear_sub();
+    assert!(!m.has_sub());
+}
+
+#[test]
+fn test_write_read() {
+    let mut m = TestOptionBox::new();
+    m.set_sub(sub(3));
+    let bytes = m.write_to_bytes().unwrap();
+
+    let m = TestOption::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(Some(sub(3)), m.sub);
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = TestOptionBox::new();
+    let field = TestOptionBox::descriptor_static()
+        .get_field_by_name("sub")
+        .unwrap();
+    assert!(!field.has_field(&m));
+    field.set_singular_field(&mut m, ReflectValueBox::from(Box::new(sub(5)) as Box<dyn MessageDyn>));
+    assert_eq!(5, m.get_sub().get_n());
+
+    let mut m = TestOptionArc::new();
+    let field = TestOptionArc::descriptor_static()
+        .get_field_by_name("sub")
+        .unwrap();
+    field.set_singular_field(&mut m, ReflectValueBox::from(Box::new(sub(7)) as Box<dyn MessageDyn>));
+    assert!(field.has_field(&m));
+    assert_eq!(7, m.get_sub().get_n());
+
+    let mut m = TestOption::new();
+    let field = TestOption::descriptor_static()
+        .get_field_by_name("sub")
+        .unwrap();
+    field.set_singular_field(&mut m, ReflectValueBox::from(Box::new(sub(6)) as Box<dyn MessageDyn>));
+    assert!(field.has_field(&m));
+    assert_eq!(6, m.get_sub().get_n());
+}
@@ -0,0 +1,69 @@
+use std::mem;
+
+use protobuf::Message;
+use protobuf::MessageDyn;
+
+use super::test_space_used_pb::*;
+
+fn item(name: &str) -> Item {
+    let mut item = Item::new();
+    item.set_name(name.to_owned());
+    item
+}
+
+#[test]
+fn empty() {
+    assert_eq!(0, Container::new().space_used_excluding_self());
+    assert_eq!(mem::size_of::<Container>(), Container::new().space_used());
+}
+
+#[test]
+fn string() {
+    let name = "x".repeat(1000);
+    assert!(item(&name).space_used_excluding_self() >= 1000);
+}
+
+#[test]
+fn nested() {
+    let name = "x".repeat(1000);
+
+    let mut m = Container::new();
+    m.set_item(item(&name));
+    assert!(m.space_used_excluding_self() >= 1000 + mem::size_of::<Item>());
+
+    let mut m = Container::new();
+    m.items.push(item(&name));
+    m.items.push(item(&name));
+    assert!(m.space_used_excluding_self() >= 2000 + 2 * mem::size_of::<Item>());
+
+    let mut m = Container::new();
+    m.named.insert(name.clone(), item(&name));
+    assert!(m.space_used_excluding_self() >= 2000);
+
+    let mut m = Container::new();
+    m.set_kind_item(item(&name));
+    assert!(m.space_used_excluding_self() >= 1000);
+}
+
+#[test]
+fn capacity_is_counted() {
+    let mut m = Item::new();
+    m.values.reserve(1000);
+    assert!(m.space_used_excluding_self() >= 8000);
+}
+
+#[test]
+fn unknown_fields() {
+    let mut m = Item::new();
+    m.mut_unknown_fields()
+        .add_length_delimited(100, vec![0; 1000]);
+    assert!(m.space_used_excluding_self() >= 1000);
+}
+
+#[test]
+fn space_used_dyn() {
+    let mut m = Container::new();
+    m.items.push(item(&"x".repeat(1000)));
+    let d: &dyn MessageDyn = &m;
+    assert_eq!(m.space_used(), d.space_used_dyn());
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use super::test_take_replace_pb::*;
+
+fn sub(n: i32) -> Sub {
+    let mut sub = Sub::new();
+    sub.set_n(n);
+    sub
+}
+
+#[test]
+fn test_singular() {
+    let mut m = Fields::new();
+    assert_eq!("", m.replace_s("a".to_owned()));
+    assert_eq!("a", m.replace_s("b".to_owned()));
+    assert_eq!("b", m.take_s());
+    assert_eq!("", m.get_s());
+
+    assert_eq!(Vec::<u8>::new(), m.replace_b(vec![1]));
+    assert_eq!(vec![1], m.take_b());
+
+    assert_eq!(Sub::new(), m.replace_sub(sub(1)));
+    assert_eq!(sub(1), m.replace_sub(sub(2)));
+    assert_eq!(2, m.get_sub().get_n());
+    assert_eq!(sub(2), m.take_sub());
+    assert!(!m.has_sub());
+}
+
+#[test]
+fn test_repeated_and_map() {
+    let mut m = Fields::new();
+    m.mut_rs().push("a".to_owned());
+    assert_eq!(vec!["a".to_owned()], m.replace_rs(vec!["b".to_owned()]));
+    assert_eq!(vec!["b".to_owned()], m.take_rs());
+    assert!(m.get_rs().is_empty());
+
+    let mut map = HashMap::new();
+    map.insert("x".to_owned(), 1);
+    assert!(m.replace_m(map.clone()).is_empty());
+    assert_eq!(map, m.take_m());
+    assert!(m.get_m().is_empty());
+}
+
+#[test]
+fn test_oneof() {
+    let mut m = Fields::new();
+    assert_eq!("", m.replace_one_s("a".to_owned()));
+    assert_eq!("a", m.get_one_s());
+    // Replacing another variant returns default and switches the variant
+    assert_eq!(Sub::new(), m.replace_one_sub(sub(3)));
+    assert!(!m.has_one_s());
+    assert_eq!(sub(3), m.replace_one_sub(sub(4)));
+    assert_eq!(sub(4), m.take_one_sub());
+    assert!(!m.has_one_sub());
+}
@@ -0,0 +1,71 @@
+use protobuf::Message;
+
+use super::test_uniform_accessors_pb::*;
+
+fn is_proto3() -> bool {
+    Uniform::descriptor_static()
+        .file_descriptor()
+        .proto()
+        .get_syntax()
+        == "proto3"
+}
+
+#[test]
+fn test_unset() {
+    let m = Uniform::new();
+    assert!(!m.has_i());
+    assert!(!m.has_s());
+    assert!(!m.has_b());
+    assert!(!m.has_c());
+    assert!(!m.has_m());
+    assert!(!m.has_o());
+    assert_eq!(None, m.get_i_opt());
+    assert_eq!(None, m.get_s_opt());
+    assert_eq!(None, m.get_b_opt());
+    assert_eq!(None, m.get_c_opt());
+    assert_eq!(None, m.get_m_opt());
+    assert_eq!(None, m.get_o_opt());
+}
+
+#[test]
+fn test_set_and_clear() {
+    let mut m = Uniform::new();
+    m.set_i(10);
+    m.set_s("a".to_owned());
+    m.set_b(vec![1]);
+    m.set_c(Color::RED);
+    m.mut_m().set_x(1);
+    m.set_o(20);
+    assert_eq!(Some(10), m.get_i_opt());
+    assert_eq!(Some("a"), m.get_s_opt());
+    assert_eq!(Some(&[1][..]), m.get_b_opt());
+    assert_eq!(Some(Color::RED), m.get_c_opt());
+    assert_eq!(Some(1), m.get_m_opt().map(|m| m.get_x()));
+    assert_eq!(Some(20), m.get_o_opt());
+
+    m.clear_i();
+    m.clear_s();
+    m.clear_b();
+    m.clear_c();
+    m.clear_m();
+    m.clear_o();
+    assert_eq!(Uniform::new(), m);
+    assert!(!m.has_i());
+    assert!(!m.has_m());
+    assert!(!m.has_o());
+}
+
+#[test]
+fn test_set_default() {
+    let mut m = Uniform::new();
+    m.set_i(0);
+    m.set_s(String::new());
+    m.set_c(Color::UNKNOWN);
+    m.set_o(0);
+    // Fields without presence are compared with default
+    assert_eq!(!is_proto3(), m.has_i());
+    assert_eq!(!is_proto3(), m.has_s());
+    assert_eq!(!is_proto3(), m.has_c());
+    // Oneof fields have presence in both syntaxes
+    assert!(m.has_o());
+}
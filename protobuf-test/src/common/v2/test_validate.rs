@@ -0,0 +1,101 @@
+use protobuf::Message;
+
+use super::test_validate_pb::*;
+
+fn valid_address() -> Address {
+    let mut address = Address::new();
+    address.set_city("Paris".to_owned());
+    address.set_zip("75001".to_owned());
+    address
+}
+
+fn valid_user() -> User {
+    let mut user = User::new();
+    user.set_name("alice".to_owned());
+    user.set_age(30);
+    user.set_score(1.0);
+    user.set_address(valid_address());
+    user
+}
+
+fn violation_fields<M: Message>(m: &M) -> Vec<String> {
+    match m.validate() {
+        Ok(()) => Vec::new(),
+        Err(e) => e.violations.into_iter().map(|v| v.field).collect(),
+    }
+}
+
+#[test]
+fn valid() {
+    assert_eq!(Ok(()), valid_user().validate());
+    assert_eq!(Ok(()), NoRules::new().validate());
+}
+
+#[test]
+fn scalars() {
+    let mut user = valid_user();
+    user.set_name("".to_owned());
+    user.set_age(151);
+    user.set_score(0.0);
+    user.set_avatar(vec![0; 5]);
+    assert_eq!(
+        vec!["name", "age", "score", "avatar"],
+        violation_fields(&user)
+    );
+
+    user.set_name("very long name".to_owned());
+    user.set_age(-1);
+    let e = user.validate().unwrap_err();
+    assert_eq!(
+        "length 14 is greater than 8 chars",
+        e.violations[0].description
+    );
+    assert_eq!("value -1 is less than 0", e.violations[1].description);
+}
+
+#[test]
+fn required() {
+    let mut user = valid_user();
+    user.clear_address();
+    assert_eq!(vec!["address"], violation_fields(&user));
+}
+
+#[test]
+fn repeated() {
+    let mut user = valid_user();
+    user.emails = vec!["a@b".to_owned(), "x".to_owned(), "c@d".to_owned()];
+    assert_eq!(vec!["emails", "emails[1]"], violation_fields(&user));
+}
+
+#[test]
+fn nested() {
+    let mut wrapper = Wrapper::new();
+    assert_eq!(Ok(()), wrapper.validate());
+
+    let mut user = valid_user();
+    user.mut_address().set_zip("1234x".to_owned());
+    user.previous.push(valid_address());
+    user.previous.push(valid_address());
+    user.previous[1].set_city("".to_owned());
+    let mut address = valid_address();
+    address.set_city("".to_owned());
+    user.named.insert("home".to_owned(), address);
+    wrapper.set_user(user);
+
+    assert_eq!(
+        vec![
+            "user.address.zip",
+            "user.previous[1].city",
+            "user.named[\"home\"].city",
+        ],
+        violation_fields(&wrapper)
+    );
+    let e = wrapper.validate().unwrap_err();
+    assert_eq!(
+        "value does not match pattern `^[0-9]{5}$`",
+        e.violations[0].description
+    );
+    assert!(e
+        .to_string()
+        .starts_with("validation failed: user.address.zip: "));
+}
@@ -0,0 +1,114 @@
+use protobuf::view::MessageView;
+use protobuf::Message;
+
+use super::test_view_pb::*;
+
+fn person() -> Person {
+    let mut friend = Person::new();
+    friend.set_name("Bob".to_owned());
+    friend.set_age(30);
+
+    let mut person = Person::new();
+    person.set_name("Alice".to_owned());
+    person.set_age(-5);
+    person.set_balance(-1000);
+    person.set_photo(vec![1, 2, 3]);
+    person.set_color(Color::GREEN);
+    person.set_score(1.5);
+    person.set_friend(friend.clone());
+    person.mut_tags().push("a".to_owned());
+    person.mut_tags().push("bc".to_owned());
+    person.mut_children().push(friend);
+    person.mut_numbers().push(7);
+    person.set_email("alice@example.com".to_owned());
+    person
+}
+
+#[test]
+fn test_scalars_and_strings() {
+    let bytes = person().write_to_bytes().unwrap();
+    let view = PersonView::from_bytes(&bytes);
+    assert_eq!("Alice", view.get_name().unwrap());
+    assert_eq!(-5, view.get_age().unwrap());
+    assert_eq!(-1000, view.get_balance().unwrap());
+    assert_eq!(&[1, 2, 3], view.get_photo().unwrap());
+    assert_eq!(Color::GREEN, view.get_color().unwrap());
+    assert_eq!(1.5, view.get_score().unwrap());
+    assert_eq!(&bytes[..], view.as_bytes());
+    assert_eq!(person(), view.to_message().unwrap());
+}
+
+#[test]
+fn test_borrows_input() {
+    let bytes = person().write_to_bytes().unwrap();
+    let name = PersonView::from_bytes(&bytes).get_name().unwrap();
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&name.as_ptr()));
+}
+
+#[test]
+fn test_defaults() {
+    let view = PersonView::from_bytes(&[]);
+    assert_eq!("", view.get_name().unwrap());
+    assert_eq!(0, view.get_age().unwrap());
+    assert_eq!(Color::UNKNOWN_COLOR, view.get_color().unwrap());
+    assert!(view.get_friend().unwrap().is_none());
+    assert_eq!(0, view.get_tags().count());
+}
+
+#[test]
+fn test_nested_and_repeated() {
+    let bytes = person().write_to_bytes().unwrap();
+    let view = PersonView::from_bytes(&bytes);
+    let friend = view.get_friend().unwrap().unwrap();
+    assert_eq!("Bob", friend.get_name().unwrap());
+    assert_eq!(30, friend.get_age().unwrap());
+    let tags: Vec<&str> = view.get_tags().collect::<Result<_, _>>().unwrap();
+    assert_eq!(vec!["a", "bc"], tags);
+    let children: Vec<PersonView> = view.get_children().collect::<Result<_, _>>().unwrap();
+    assert_eq!(1, children.len());
+    assert_eq!("Bob", children[0].get_name().unwrap());
+}
+
+#[test]
+fn test_oneof() {
+    let mut person = person();
+    let bytes = person.write_to_bytes().unwrap();
+    let view = PersonView::from_bytes(&bytes);
+    assert!(view.has_email().unwrap());
+    assert!(!view.has_phone().unwrap());
+    assert_eq!("alice@example.com", view.get_email().unwrap());
+
+    // Last field of oneof wins
+    person.set_phone("123".to_owned());
+    let mut bytes = bytes.clone();
+    bytes.extend(person.write_to_bytes().unwrap());
+    let view = PersonView::from_bytes(&bytes);
+    assert!(!view.has_email().unwrap());
+    assert_eq!("", view.get_email().unwrap());
+    assert_eq!("123", view.get_phone().unwrap());
+}
+
+#[test]
+fn test_last_value_wins() {
+    let mut bytes = person().write_to_bytes().unwrap();
+    let mut other = Person::new();
+    other.set_age(40);
+    bytes.extend(other.write_to_bytes().unwrap());
+    let view = PersonView::from_bytes(&bytes);
+    assert_eq!(40, view.get_age().unwrap());
+    assert_eq!("Alice", view.get_name().unwrap());
+}
+
+#[test]
+fn test_malformed() {
+    // field 1, length 5, but only one byte follows
+    let view = PersonView::from_bytes(&[0x0a, 0x05, b'a']);
+    assert!(view.get_name().is_err());
+    // invalid UTF-8
+    let view = PersonView::from_bytes(&[0x0a, 0x01, 0xff]);
+    assert!(view.get_name().is_err());
+    // name encoded as varint
+    let view = PersonView::from_bytes(&[0x08, 0x01]);
+    assert!(view.get_name().is_err());
+}
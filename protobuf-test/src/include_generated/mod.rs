@@ -9,3 +9,13 @@ fn test() {
     let _ = V2Message::new();
     let _ = V3Message::new();
 }
+
+#[test]
+fn test_registered_types() {
+    let types = registered_types();
+    assert_eq!(
+        vec!["V2Message", "V3Message"],
+        types.message_names().collect::<Vec<_>>()
+    );
+    assert!(types.new_message_by_name("V3Message").unwrap().is::<V3Message>());
+}
@@ -1,6 +1,8 @@
 use std::f32;
 use std::f64;
 
+use protobuf::Clear;
+
 use super::test_default_values_pb::*;
 
 #[test]
@@ -44,3 +46,34 @@ fn test_default_value_extreme() {
     assert_eq!(b"'", d.get_bquote1());
     assert_eq!(b"\"", d.get_bquote2());
 }
+
+#[test]
+fn test_default_value_consts() {
+    assert_eq!(3, TestDefaultValues::DEFAULT_INT32_FIELD);
+    assert_eq!("abc\n22", TestDefaultValues::DEFAULT_STRING_FIELD);
+    assert_eq!(b"cde\n33", TestDefaultValues::DEFAULT_BYTES_FIELD);
+    assert_eq!(
+        EnumForDefaultValue::TWO,
+        TestDefaultValues::DEFAULT_ENUM_FIELD
+    );
+    assert!(TestExtremeDefaultValues::DEFAULT_NAN_FLOAT.is_nan());
+    assert_eq!("'", TestExtremeDefaultValues::DEFAULT_QUOTE1);
+}
+
+#[test]
+fn test_default_value_after_clear() {
+    let mut d = TestDefaultValues::default();
+    assert_eq!(3, d.get_int32_field());
+    d.set_int32_field(30);
+    d.set_string_field("x".to_owned());
+    d.set_enum_field(EnumForDefaultValue::THREE);
+    assert_eq!(30, d.get_int32_field());
+
+    d.clear_int32_field();
+    assert_eq!(TestDefaultValues::DEFAULT_INT32_FIELD, d.get_int32_field());
+    assert!(!d.has_int32_field());
+
+    d.clear();
+    assert_eq!("abc\n22", d.get_string_field());
+    assert_eq!(EnumForDefaultValue::TWO, d.get_enum_field());
+}
@@ -0,0 +1,61 @@
+use protobuf::CodedInputStream;
+use protobuf::Message;
+
+use super::test_discard_unknown_fields_pb::*;
+
+// a = 1, field 3 = 2, field 150 = 3
+const WITH_UNKNOWN: &[u8] = &[0x08, 0x01, 0x18, 0x02, 0xb0, 0x09, 0x03];
+
+#[test]
+fn test_retained_by_default() {
+    let m = Retained::parse_from_bytes(WITH_UNKNOWN).unwrap();
+    assert_eq!(2, m.get_unknown_fields().iter().count());
+    assert_eq!(WITH_UNKNOWN.len(), m.write_to_bytes().unwrap().len());
+}
+
+#[test]
+fn test_discard_option() {
+    let m = Discarded::parse_from_bytes(WITH_UNKNOWN).unwrap();
+    assert_eq!(1, m.get_a());
+    assert_eq!(0, m.get_unknown_fields().iter().count());
+
+    let m = DiscardedWithExtensions::parse_from_bytes(WITH_UNKNOWN).unwrap();
+    assert_eq!(1, m.get_a());
+    assert_eq!(0, m.get_unknown_fields().iter().count());
+
+    // Option does not apply to nested messages of other types
+    let mut bytes = vec![0x12, WITH_UNKNOWN.len() as u8];
+    bytes.extend_from_slice(WITH_UNKNOWN);
+    let m = Discarded::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(
+        2,
+        m.retained
+            .get_or_default()
+            .get_unknown_fields()
+            .iter()
+            .count()
+    );
+}
+
+#[test]
+fn test_discard_stream_option() {
+    let mut bytes = WITH_UNKNOWN.to_vec();
+    bytes.extend_from_slice(&[0x12, WITH_UNKNOWN.len() as u8]);
+    bytes.extend_from_slice(WITH_UNKNOWN);
+
+    let mut is = CodedInputStream::from_bytes(&bytes);
+    is.set_discard_unknown_fields(true);
+    let mut m = Retained::new();
+    m.merge_from(&mut is).unwrap();
+    assert_eq!(1, m.get_a());
+    assert_eq!(0, m.get_unknown_fields().iter().count());
+    assert_eq!(1, m.nested.get_or_default().get_a());
+    assert_eq!(
+        0,
+        m.nested
+            .get_or_default()
+            .get_unknown_fields()
+            .iter()
+            .count()
+    );
+}
@@ -0,0 +1,126 @@
+use protobuf::ext::ExtensionRegistry;
+use protobuf::Clear;
+use protobuf::Message;
+
+use super::test_extension_registry_pb::*;
+
+fn extendable_for_test() -> Extendable {
+    let mut m = Extendable::new();
+    m.set_n(1);
+    exts::ext_int32.set(&mut m, 10);
+    exts::ext_repeated_int32.set(&mut m, vec![1, 2, 3]);
+    exts::ext_packed_int32.set(&mut m, vec![4, 5]);
+    exts::ext_string.set(&mut m, "ext".to_owned());
+    let mut sub = SubM::new();
+    sub.set_n(22);
+    exts::ext_message.set(&mut m, sub);
+    m
+}
+
+fn assert_extendable_for_test(m: &Extendable) {
+    assert_eq!(1, m.get_n());
+    assert_eq!(Some(10), exts::ext_int32.get(m));
+    assert_eq!(vec![1, 2, 3], exts::ext_repeated_int32.get(m));
+    assert_eq!(vec![4, 5], exts::ext_packed_int32.get(m));
+    assert_eq!(Some("ext".to_owned()), exts::ext_string.get(m));
+    assert_eq!(22, exts::ext_message.get(m).unwrap().get_n());
+}
+
+fn registry_for_test() -> ExtensionRegistry {
+    let mut registry = ExtensionRegistry::new();
+    registry.add_optional(&exts::ext_int32);
+    registry.add_repeated(&exts::ext_repeated_int32);
+    registry.add_repeated(&exts::ext_packed_int32);
+    registry.add_optional(&exts::ext_string);
+    registry.add_optional(&exts::ext_message);
+    registry
+}
+
+#[test]
+fn test_set_stores_typed() {
+    let m = extendable_for_test();
+    assert!(!m.extension_fields.is_empty());
+    assert!(m.unknown_fields.iter().next().is_none());
+    assert_extendable_for_test(&m);
+}
+
+#[test]
+fn test_parse_without_registry() {
+    let bytes = extendable_for_test().write_to_bytes().unwrap();
+    let m = Extendable::parse_from_bytes(&bytes).unwrap();
+    assert!(m.extension_fields.is_empty());
+    assert!(m.unknown_fields.get(100).is_some());
+    assert_extendable_for_test(&m);
+}
+
+#[test]
+fn test_parse_with_registry() {
+    let registry = registry_for_test();
+    let bytes = extendable_for_test().write_to_bytes().unwrap();
+    let m = Extendable::parse_from_bytes_with_registry(&bytes, &registry).unwrap();
+    assert!(m.unknown_fields.iter().next().is_none());
+    assert_extendable_for_test(&m);
+    assert_eq!(extendable_for_test(), m);
+    assert_eq!(bytes, m.write_to_bytes().unwrap());
+}
+
+#[test]
+fn test_parse_with_registry_packed() {
+    let registry = registry_for_test();
+    let mut m = Extendable::new();
+    // packed encoding of `[7, 8]` for unpacked `ext_repeated_int32`
+    m.mut_unknown_fields().add_length_delimited(101, vec![7, 8]);
+    let bytes = m.write_to_bytes().unwrap();
+    assert_eq!(vec![7, 8], exts::ext_repeated_int32.get(&m));
+    let m = Extendable::parse_from_bytes_with_registry(&bytes, &registry).unwrap();
+    assert!(m.extension_fields.contains(101));
+    assert_eq!(vec![7, 8], exts::ext_repeated_int32.get(&m));
+}
+
+#[test]
+fn test_parse_with_registry_wrong_wire_type() {
+    let registry = registry_for_test();
+    let mut m = Extendable::new();
+    // `ext_int32` is a varint, encode it as fixed32
+    m.mut_unknown_fields().add_fixed32(100, 17);
+    let bytes = m.write_to_bytes().unwrap();
+    let m = Extendable::parse_from_bytes_with_registry(&bytes, &registry).unwrap();
+    assert!(!m.extension_fields.contains(100));
+    assert_eq!(&[17], &m.unknown_fields.get(100).unwrap().fixed32[..]);
+}
+
+#[test]
+fn test_has_clear() {
+    let mut m = extendable_for_test();
+    assert!(exts::ext_int32.has(&m));
+    exts::ext_int32.clear(&mut m);
+    assert!(!exts::ext_int32.has(&m));
+    assert_eq!(None, exts::ext_int32.get(&m));
+    assert_eq!(0, exts::ext_int32.get_or_default(&m));
+
+    exts::ext_repeated_int32.set(&mut m, Vec::new());
+    assert!(!exts::ext_repeated_int32.has(&m));
+
+    let bytes = m.write_to_bytes().unwrap();
+    let mut m = Extendable::parse_from_bytes(&bytes).unwrap();
+    assert!(exts::ext_string.has(&m));
+    exts::ext_string.clear(&mut m);
+    assert!(!exts::ext_string.has(&m));
+
+    m.clear();
+    assert!(!exts::ext_message.has(&m));
+}
+
+#[test]
+fn test_message_scoped_extensions() {
+    let mut m = Extendable::new();
+    scoped::exts::scoped_int32.set(&mut m, 5);
+    let mut scoped = Scoped::new();
+    scoped.set_s(6);
+    scoped::exts::scoped_message.set(&mut m, scoped);
+
+    let bytes = m.write_to_bytes().unwrap();
+    let m = Extendable::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(Some(5), scoped::exts::scoped_int32.get(&m));
+    assert_eq!(6, scoped::exts::scoped_message.get(&m).unwrap().get_s());
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use protobuf::CodedInputStream;
+use protobuf::InternedString;
+use protobuf::Message;
+use protobuf::SmallVec;
+use protobuf::StringInterner;
+
+use protobuf_test_common::*;
+
+use super::test_interned_string_pb::*;
+
+#[test]
+fn test_types() {
+    let m = TestInternedString::new();
+    let _: &str = m.get_singular_field();
+    let _: &Vec<InternedString> = &m.repeated_field;
+    let _: &HashMap<InternedString, InternedString> = &m.map_field;
+    let _: &HashMap<InternedString, i32> = &m.map_key_field;
+    let _: &SmallVec<InternedString, 2> = &m.small_vec_field;
+}
+
+fn message_for_test() -> TestInternedString {
+    let mut m = TestInternedString::new();
+    m.set_singular_field("s".to_owned());
+    m.repeated_field.push("label".into());
+    m.repeated_field.push("label".into());
+    m.repeated_field.push("other".into());
+    m.map_field.insert("k".into(), "label".into());
+    m.map_key_field.insert("label".into(), 10);
+    m.small_vec_field.push("label".into());
+    m
+}
+
+#[test]
+fn test_write_read() {
+    test_serialize_deserialize_no_hex(&message_for_test());
+}
+
+#[test]
+fn test_shared_allocations() {
+    let bytes = message_for_test().write_to_bytes().unwrap();
+
+    let interner = StringInterner::new();
+    let mut is = CodedInputStream::from_bytes(&bytes);
+    is.set_string_interner(&interner);
+    let mut m = TestInternedString::new();
+    m.merge_from(&mut is).unwrap();
+
+    assert_eq!("label", m.repeated_field[0]);
+    assert!(m.repeated_field[0].ptr_eq(&m.repeated_field[1]));
+    assert!(!m.repeated_field[0].ptr_eq(&m.repeated_field[2]));
+    assert!(m.repeated_field[0].ptr_eq(&m.map_field["k"]));
+    assert!(m.repeated_field[0].ptr_eq(&m.small_vec_field[0]));
+    let (key, _) = m.map_key_field.iter().next().unwrap();
+    assert!(m.repeated_field[0].ptr_eq(key));
+    // "label", "other" and map key "k"
+    assert_eq!(3, interner.len());
+}
+
+#[test]
+fn test_default_interner() {
+    let bytes = message_for_test().write_to_bytes().unwrap();
+    let a = TestInternedString::parse_from_bytes(&bytes).unwrap();
+    let b = TestInternedString::parse_from_bytes(&bytes).unwrap();
+    // Shared within one parse, but not between parses
+    assert!(a.repeated_field[0].ptr_eq(&a.repeated_field[1]));
+    assert!(!a.repeated_field[2].ptr_eq(&b.repeated_field[2]));
+}
+
+#[test]
+fn test_global_interner() {
+    let bytes = message_for_test().write_to_bytes().unwrap();
+    let parse = || {
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        is.set_string_interner(StringInterner::global());
+        let mut m = TestInternedString::new();
+        m.merge_from(&mut is).unwrap();
+        m
+    };
+    let a = parse();
+    let b = parse();
+    assert!(a.repeated_field[2].ptr_eq(&b.repeated_field[2]));
+}
+
+#[test]
+fn test_read_from_small_buffer() {
+    let bytes = message_for_test().write_to_bytes().unwrap();
+    let mut read = &bytes[..];
+    let mut is = CodedInputStream::with_buffer_size(&mut read, 3);
+    let mut m = TestInternedString::new();
+    m.merge_from(&mut is).unwrap();
+    assert_eq!(message_for_test(), m);
+    assert!(m.repeated_field[0].ptr_eq(&m.repeated_field[1]));
+}
+
+#[test]
+fn test_reflect() {
+    let m = message_for_test();
+    let field = TestInternedString::descriptor_static()
+        .get_field_by_name("map_field")
+        .unwrap();
+    let map = field.get_map(&m);
+    assert_eq!(1, map.len());
+    assert_eq!(
+        Some(protobuf::reflect::ReflectValueRef::String("label")),
+        map.get(protobuf::reflect::ReflectValueRef::String("k"))
+    );
+}
@@ -0,0 +1,117 @@
+use protobuf::ext::ExtensionRegistry;
+use protobuf::reflect::MessageDescriptor;
+use protobuf::Message;
+
+use super::test_message_set_pb::*;
+
+fn item(text: &str) -> Item {
+    let mut item = Item::new();
+    item.set_text(text.to_owned());
+    item
+}
+
+// item with `type_id = 1000` and `message = Item { text: "ab" }`
+const ENCODED: &[u8] = &[
+    0x0b, 0x10, 0xe8, 0x07, 0x1a, 0x04, 0x0a, 0x02, b'a', b'b', 0x0c,
+];
+
+#[test]
+fn test_write() {
+    let mut m = Container::new();
+    item::exts::item.set(&mut m, item("ab"));
+    assert_eq!(ENCODED, &m.write_to_bytes().unwrap()[..]);
+    assert_eq!(ENCODED.len() as u32, m.compute_size());
+}
+
+#[test]
+fn test_parse_without_registry() {
+    let m = Container::parse_from_bytes(ENCODED).unwrap();
+    assert_eq!(
+        &[&[0x0a, 0x02, b'a', b'b'][..]],
+        &m.unknown_fields.get(1000).unwrap().length_delimited[..]
+    );
+    assert_eq!("ab", item::exts::item.get(&m).unwrap().get_text());
+    assert_eq!(ENCODED, &m.write_to_bytes().unwrap()[..]);
+}
+
+#[test]
+fn test_parse_with_registry() {
+    let mut registry = ExtensionRegistry::new();
+    registry.add_optional(&item::exts::item);
+    registry.add_optional(&item_with_container::exts::item_with_container);
+
+    let mut nested = Container::new();
+    item::exts::item.set(&mut nested, item("nested"));
+    let mut outer = ItemWithContainer::new();
+    outer.container = protobuf::MessageField::some(nested);
+    let mut m = Container::new();
+    item_with_container::exts::item_with_container.set(&mut m, outer);
+    let bytes = m.write_to_bytes().unwrap();
+
+    let parsed = Container::parse_from_bytes_with_registry(&bytes, &registry).unwrap();
+    assert!(parsed.unknown_fields.iter().next().is_none());
+    assert!(parsed.extension_fields.contains(1001));
+    let outer = item_with_container::exts::item_with_container
+        .get(&parsed)
+        .unwrap();
+    // registry is used for nested message sets
+    assert!(outer
+        .container
+        .get_or_default()
+        .extension_fields
+        .contains(1000));
+    assert_eq!(
+        "nested",
+        item::exts::item
+            .get(outer.container.get_or_default())
+            .unwrap()
+            .get_text()
+    );
+    assert_eq!(m, parsed);
+    assert_eq!(bytes, parsed.write_to_bytes().unwrap());
+}
+
+#[test]
+fn test_message_before_type_id() {
+    let bytes = [
+        0x0b, 0x1a, 0x04, 0x0a, 0x02, b'a', b'b', 0x10, 0xe8, 0x07, 0x0c,
+    ];
+    let m = Container::parse_from_bytes(&bytes).unwrap();
+    assert_eq!("ab", item::exts::item.get(&m).unwrap().get_text());
+    assert_eq!(ENCODED, &m.write_to_bytes().unwrap()[..]);
+}
+
+#[test]
+fn test_regular_encoding_accepted() {
+    // extension field encoded as regular length-delimited field 1000
+    let bytes = [0xc2, 0x3e, 0x04, 0x0a, 0x02, b'a', b'b'];
+    let m = Container::parse_from_bytes(&bytes).unwrap();
+    assert_eq!("ab", item::exts::item.get(&m).unwrap().get_text());
+    assert_eq!(ENCODED, &m.write_to_bytes().unwrap()[..]);
+}
+
+#[test]
+fn test_truncated_item() {
+    assert!(Container::parse_from_bytes(&ENCODED[..ENCODED.len() - 1]).is_err());
+}
+
+#[test]
+fn test_dynamic() {
+    let descriptor = MessageDescriptor::for_type::<Container>();
+    let dynamic_descriptor = protobuf::reflect::FileDescriptor::new_dynamic(
+        descriptor.file_descriptor().proto().clone(),
+        Vec::new(),
+    )
+    .message_by_full_name(".test_message_set.Container")
+    .unwrap();
+    let mut m = dynamic_descriptor.new_instance();
+    m.merge_from_bytes_dyn(ENCODED).unwrap();
+    assert_eq!(
+        &[&[0x0a, 0x02, b'a', b'b'][..]],
+        &m.get_unknown_fields_dyn()
+            .get(1000)
+            .unwrap()
+            .length_delimited[..]
+    );
+    assert_eq!(ENCODED, &m.write_to_bytes_dyn().unwrap()[..]);
+}
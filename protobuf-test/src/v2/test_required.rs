@@ -1,5 +1,6 @@
 use super::test_required_pb::*;
 use protobuf::Message;
+use protobuf::ProtobufError;
 
 #[test]
 fn test_write_missing_required() {
@@ -20,3 +21,38 @@ fn test_is_initialized_is_recursive() {
     m.inner.as_mut().unwrap().set_b(false);
     assert!(m.is_initialized());
 }
+
+#[test]
+fn test_missing_required_fields_paths() {
+    let mut m = TestRequiredNested::new();
+    m.outer.mut_or_default().inner = Some(Default::default()).into();
+    m.repeated_inner.push(Default::default());
+    m.repeated_inner.push(Default::default());
+    m.repeated_inner[0].set_b(true);
+    m.map_inner.insert("k".to_owned(), Default::default());
+    assert_eq!(
+        vec![
+            "outer.inner.b",
+            "repeated_inner[1].b",
+            "map_inner[\"k\"].b",
+            "i"
+        ],
+        m.missing_required_fields()
+    );
+
+    let e = match m.check_initialized() {
+        Err(ProtobufError::MessageNotInitialized(e)) => e,
+        r => panic!("{:?}", r),
+    };
+    assert_eq!("test_required.TestRequiredNested", e.message);
+    assert_eq!(4, e.missing_fields.len());
+    assert!(e.to_string().contains("repeated_inner[1].b"));
+}
+
+#[test]
+fn test_parse_from_bytes_partial() {
+    let m = TestRequiredOuter::parse_from_bytes_partial(&[]).unwrap();
+    assert_eq!(vec!["inner"], m.missing_required_fields());
+    assert!(TestRequiredOuter::parse_from_bytes_checked(&[]).is_err());
+    assert!(TestRequiredOuter::parse_from_bytes_checked(&[0x0a, 0x02, 0x28, 0x01]).is_ok());
+}
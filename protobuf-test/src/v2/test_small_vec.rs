@@ -0,0 +1,83 @@
+use protobuf::reflect::ReflectValueRef;
+use protobuf::Clear;
+use protobuf::Message;
+use protobuf::ProtobufEnumOrUnknown;
+use protobuf::SmallVec;
+
+use protobuf_test_common::*;
+
+use super::test_small_vec_pb::*;
+
+#[test]
+fn test_types() {
+    let m = TestSmallVec::new();
+    let _: &SmallVec<i32, 2> = &m.int32_field;
+    let _: &SmallVec<::std::string::String, 2> = &m.string_field;
+    let _: &SmallVec<SmallVecSub, 2> = &m.message_field;
+    let _: &Vec<u64> = &m.vec_fixed64_field;
+
+    let m = TestSmallVecCapacity::new();
+    let _: &SmallVec<u64, 4> = &m.uint64_field;
+}
+
+#[test]
+fn test_inline_and_spilled() {
+    let mut m = TestSmallVec::new();
+    m.int32_field.push(1);
+    m.int32_field.push(2);
+    assert!(!m.int32_field.spilled());
+    m.int32_field.push(3);
+    assert!(m.int32_field.spilled());
+    assert_eq!(&[1, 2, 3], m.get_int32_field());
+}
+
+#[test]
+fn test_write_read() {
+    let mut m = TestSmallVec::new();
+    m.int32_field.extend(vec![10, 20, 30]);
+    m.packed_int32_field.extend(vec![1, 2, 3, 4]);
+    m.string_field.push("aa".to_owned());
+    m.bytes_field.push(b"bb".to_vec());
+    let mut sub = SmallVecSub::new();
+    sub.set_n(7);
+    m.message_field.push(sub);
+    m.enum_field.push(ProtobufEnumOrUnknown::new(SmallVecEnum::TWO));
+    m.vec_fixed64_field.push(5);
+    test_serialize_deserialize_no_hex(&m);
+
+    let bytes = m.write_to_bytes().unwrap();
+    let parsed = TestSmallVec::parse_from_bytes(&bytes).unwrap();
+    assert_eq!(&[10, 20, 30], parsed.get_int32_field());
+    assert_eq!(&[1, 2, 3, 4], parsed.get_packed_int32_field());
+    assert_eq!(7, parsed.get_message_field()[0].get_n());
+}
+
+#[test]
+fn test_packed_and_unpacked_accepted() {
+    // field 2 first unpacked, then packed
+    let m = TestSmallVec::parse_from_bytes(&[0x10, 1, 0x10, 2, 0x12, 2, 3, 4]).unwrap();
+    assert_eq!(&[1, 2, 3, 4], m.get_packed_int32_field());
+}
+
+#[test]
+fn test_reflect() {
+    let mut m = TestSmallVec::new();
+    m.int32_field.extend(vec![1, 2, 3]);
+    let descriptor = TestSmallVec::descriptor_static();
+    let field = descriptor.get_field_by_name("int32_field").unwrap();
+    let repeated = field.get_repeated(&m);
+    assert_eq!(3, repeated.len());
+    assert_eq!(ReflectValueRef::I32(2), repeated.get(1));
+
+    field.mut_repeated(&mut m).push(From::from(4));
+    assert_eq!(&[1, 2, 3, 4], m.get_int32_field());
+}
+
+#[test]
+fn test_clear() {
+    let mut m = TestSmallVec::new();
+    m.int32_field.extend(vec![1, 2, 3]);
+    m.clear();
+    assert!(m.int32_field.is_empty());
+    assert_eq!(TestSmallVec::new(), m);
+}
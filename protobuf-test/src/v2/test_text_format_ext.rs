@@ -0,0 +1,132 @@
+use protobuf::reflect::MessageFactory;
+use protobuf::text_format;
+use protobuf::well_known_types::Any;
+
+use super::test_text_format_ext_pb::*;
+
+fn register() {
+    MessageFactory::global().add_file(&file_descriptor());
+}
+
+#[test]
+fn parse_extensions() {
+    register();
+    let m: Extendable = text_format::parse_from_str(
+        "n: 1 \
+         [test_text_format_ext.ext_int32]: 10 \
+         [test_text_format_ext.ext_repeated_sint64]: -3 \
+         [test_text_format_ext.ext_repeated_sint64]: 4 \
+         [test_text_format_ext.ext_string]: \"ext\" \
+         [test_text_format_ext.ext_message] { n: 22 } \
+         [test_text_format_ext.ext_color]: GREEN \
+         [test_text_format_ext.Scoped.scoped_int32]: 7",
+    )
+    .unwrap();
+    assert_eq!(1, m.get_n());
+    assert_eq!(Some(10), exts::ext_int32.get(&m));
+    assert_eq!(vec![-3, 4], exts::ext_repeated_sint64.get(&m));
+    assert_eq!(Some("ext".to_owned()), exts::ext_string.get(&m));
+    assert_eq!(22, exts::ext_message.get(&m).unwrap().get_n());
+    assert_eq!(Some(Color::GREEN.into()), exts::ext_color.get(&m));
+    assert_eq!(Some(7), scoped::exts::scoped_int32.get(&m));
+}
+
+#[test]
+fn parse_unknown_extension() {
+    register();
+    assert!(
+        text_format::parse_from_str::<Extendable>("[test_text_format_ext.missing]: 1").is_err()
+    );
+    // Extension of another message
+    assert!(text_format::parse_from_str::<Sub>("[test_text_format_ext.ext_int32]: 1").is_err());
+}
+
+#[test]
+fn print_extensions() {
+    register();
+    let mut m = Extendable::new();
+    m.set_n(1);
+    exts::ext_int32.set(&mut m, 10);
+    exts::ext_packed_int32.set(&mut m, vec![4, 5]);
+    exts::ext_double.set(&mut m, 1.5);
+    let mut sub = Sub::new();
+    sub.set_n(22);
+    exts::ext_message.set(&mut m, sub);
+    assert_eq!(
+        "n: 1 \
+         [test_text_format_ext.ext_int32]: 10 \
+         [test_text_format_ext.ext_packed_int32]: 4 \
+         [test_text_format_ext.ext_packed_int32]: 5 \
+         [test_text_format_ext.ext_message] {n: 22} \
+         [test_text_format_ext.ext_double]: 1.5",
+        text_format::print_to_string(&m)
+    );
+}
+
+#[test]
+fn print_malformed_extensions() {
+    register();
+    // Empty length-delimited values of optional and packed extensions
+    let m: Extendable =
+        protobuf::Message::parse_from_bytes(&[0xa2, 0x06, 0x00, 0xb2, 0x06, 0x00]).unwrap();
+    // Printed as unknown fields
+    assert_eq!("100: \"\" 102: \"\"", text_format::print_to_string(&m));
+}
+
+#[test]
+fn round_trip_extensions() {
+    register();
+    let mut m = Extendable::new();
+    exts::ext_repeated_sint64.set(&mut m, vec![-1, 2]);
+    exts::ext_color.set(&mut m, Color::RED.into());
+    let text = text_format::print_to_string(&m);
+    let parsed: Extendable = text_format::parse_from_str(&text).unwrap();
+    assert_eq!(vec![-1, 2], exts::ext_repeated_sint64.get(&parsed));
+    assert_eq!(Some(Color::RED.into()), exts::ext_color.get(&parsed));
+    assert_eq!(text, text_format::print_to_string(&parsed));
+}
+
+#[test]
+fn merge_into_typed_extensions() {
+    register();
+    let mut m = Extendable::new();
+    exts::ext_int32.set(&mut m, 1);
+    exts::ext_repeated_sint64.set(&mut m, vec![1]);
+    text_format::merge_from_str(
+        &mut m,
+        "[test_text_format_ext.ext_int32]: 2 \
+         [test_text_format_ext.ext_repeated_sint64]: 2",
+    )
+    .unwrap();
+    assert_eq!(Some(2), exts::ext_int32.get(&m));
+    assert_eq!(vec![1, 2], exts::ext_repeated_sint64.get(&m));
+}
+
+#[test]
+fn any() {
+    register();
+    let mut sub = Sub::new();
+    sub.set_n(3);
+    let any = Any::pack(&sub).unwrap();
+    let text = text_format::print_to_string(&any);
+    assert_eq!(
+        "[type.googleapis.com/test_text_format_ext.Sub] {n: 3}",
+        text
+    );
+    let parsed: Any = text_format::parse_from_str(&text).unwrap();
+    assert_eq!(any, parsed);
+    assert_eq!(sub, parsed.unpack::<Sub>().unwrap().unwrap());
+}
+
+#[test]
+fn any_unknown_type() {
+    let mut any = Any::new();
+    any.type_url = "type.googleapis.com/test_text_format_ext.Missing".to_owned();
+    any.value = vec![8, 1];
+    // Fields are printed as is
+    assert!(text_format::print_to_string(&any).starts_with("type_url: "));
+    assert!(text_format::parse_from_str::<Any>(
+        "[type.googleapis.com/test_text_format_ext.Missing] {}"
+    )
+    .is_err());
+}
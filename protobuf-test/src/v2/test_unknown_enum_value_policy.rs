@@ -0,0 +1,88 @@
+use protobuf::CodedInputStream;
+use protobuf::Message;
+use protobuf::ProtobufEnumOrUnknown;
+use protobuf::ProtobufError;
+use protobuf::UnknownEnumValuePolicy;
+
+use super::test_unknown_enum_value_policy_pb::*;
+
+// color = 5, colors = [2, 5], packed_colors = [5, 1], one_color = 5
+const UNDECLARED: &[u8] = &[
+    0x08, 0x05, 0x10, 0x02, 0x10, 0x05, 0x1a, 0x02, 0x05, 0x01, 0x20, 0x05,
+];
+
+fn parse(policy: UnknownEnumValuePolicy) -> Result<WithEnums, ProtobufError> {
+    let mut is = CodedInputStream::from_bytes(UNDECLARED);
+    is.set_unknown_enum_value_policy(policy);
+    let mut m = WithEnums::new();
+    m.merge_from(&mut is)?;
+    Ok(m)
+}
+
+#[test]
+fn test_keep() {
+    assert_eq!(
+        UnknownEnumValuePolicy::Keep,
+        UnknownEnumValuePolicy::default()
+    );
+    let m = parse(UnknownEnumValuePolicy::Keep).unwrap();
+    assert!(m.has_color());
+    assert_eq!(
+        vec![
+            ProtobufEnumOrUnknown::new(Color::GREEN),
+            ProtobufEnumOrUnknown::from_i32(5)
+        ],
+        m.colors
+    );
+    assert_eq!(2, m.packed_colors.len());
+    assert!(m.has_one_color());
+    assert_eq!(0, m.get_unknown_fields().iter().count());
+    assert_eq!(UNDECLARED.len(), m.write_to_bytes().unwrap().len());
+}
+
+#[test]
+fn test_unknown_fields() {
+    let m = parse(UnknownEnumValuePolicy::UnknownFields).unwrap();
+    assert!(!m.has_color());
+    assert_eq!(vec![ProtobufEnumOrUnknown::new(Color::GREEN)], m.colors);
+    assert_eq!(
+        vec![ProtobufEnumOrUnknown::new(Color::RED)],
+        m.packed_colors
+    );
+    assert!(!m.has_one_color());
+    for number in 1..=4 {
+        assert_eq!(
+            &[5],
+            m.get_unknown_fields()
+                .get(number)
+                .unwrap()
+                .varint
+                .as_slice()
+        );
+    }
+    // Values are written back, packed value is written as unpacked
+    assert_eq!(UNDECLARED.len() + 1, m.write_to_bytes().unwrap().len());
+}
+
+#[test]
+fn test_error() {
+    match parse(UnknownEnumValuePolicy::Error) {
+        Err(ProtobufError::WireError(..)) => {}
+        r => panic!("{:?}", r),
+    }
+}
+
+#[test]
+fn test_default() {
+    let m = parse(UnknownEnumValuePolicy::Default).unwrap();
+    assert_eq!(Color::RED, m.get_color());
+    assert_eq!(
+        vec![
+            ProtobufEnumOrUnknown::new(Color::GREEN),
+            ProtobufEnumOrUnknown::new(Color::RED)
+        ],
+        m.colors
+    );
+    assert_eq!(Color::RED, m.get_one_color());
+    assert_eq!(0, m.get_unknown_fields().iter().count());
+}
@@ -0,0 +1,56 @@
+use protobuf::json;
+use protobuf::text_format;
+use protobuf::Message;
+use protobuf::ProtobufEnumOrUnknown;
+use protobuf_test_common::*;
+
+use super::test_enum_open_pb::*;
+
+#[test]
+fn unknown_value_preserved() {
+    let m = WithOpenEnum::parse_from_bytes(&hex::decode_hex("08 1e")).unwrap();
+    assert_eq!(OpenEnum::ZERO, m.get_singular());
+    assert_eq!(30, m.get_singular_value());
+    test_serialize_deserialize("08 1e", &m);
+}
+
+#[test]
+fn value_accessors() {
+    let mut m = WithOpenEnum::new();
+    m.set_singular_value(30);
+    assert_eq!(None, m.singular.enum_value().ok());
+    assert_eq!(30, m.get_singular_value());
+    test_serialize("08 1e", &m);
+
+    m.set_singular(OpenEnum::ONE);
+    assert_eq!(1, m.get_singular_value());
+}
+
+#[test]
+fn repeated_and_map() {
+    let mut m = WithOpenEnum::new();
+    m.repeated_packed.push(ProtobufEnumOrUnknown::from_i32(30));
+    m.repeated_packed.push(OpenEnum::ONE.into());
+    m.map_values
+        .insert("a".to_owned(), ProtobufEnumOrUnknown::from_i32(40));
+    test_serialize_deserialize_no_hex(&m);
+}
+
+#[test]
+fn text_format() {
+    let mut m = WithOpenEnum::new();
+    m.set_singular_value(30);
+    m.repeated_packed.push(ProtobufEnumOrUnknown::from_i32(-2));
+    let text = text_format::print_to_string(&m);
+    assert_eq!("singular: 30 repeated_packed: -2", text);
+    assert_eq!(m, text_format::parse_from_str(&text).unwrap());
+}
+
+#[test]
+fn json() {
+    let mut m = WithOpenEnum::new();
+    m.set_singular_value(30);
+    let j = json::print_to_string(&m).unwrap();
+    assert_eq!("{\"singular\": 30}", j);
+    assert_eq!(m, json::parse_from_str(&j).unwrap());
+}
@@ -86,8 +86,29 @@ fn write_version() {
     file.flush().unwrap();
 }
 
+/// Markers for `rt::compat`: runtime supports code generated
+/// by codegen of the same major and the same or older minor version.
+fn write_compat() {
+    let version = version();
+    let mut parts = version.split(['.', '-']);
+    let major: u32 = parts.next().unwrap().parse().expect("major");
+    let minor: u32 = parts.next().unwrap().parse().expect("minor");
+    let mut file = File::create(Path::join(&out_dir(), "compat.rs")).expect("open");
+    for m in 0..=minor {
+        writeln!(
+            file,
+            "/// Runtime supports code generated by rust-protobuf `{}.{}`",
+            major, m
+        )
+        .unwrap();
+        writeln!(file, "pub const REQUIRE_{}_{}: () = ();", major, m).unwrap();
+    }
+    file.flush().unwrap();
+}
+
 fn main() {
     cfg_rust_version();
     cfg_serde();
     write_version();
+    write_compat();
 }
@@ -0,0 +1,365 @@
+//! Generation of arbitrary message values driven by descriptors.
+//!
+//! Values are derived from a byte string, like `arbitrary::Unstructured`
+//! does, so the generator can be fed directly by a fuzzer (e. g.
+//! `cargo fuzz` or AFL), or by a property testing framework which
+//! generates byte vectors. When input bytes are exhausted, all further
+//! choices are zeros, so any input produces a valid message.
+//!
+//! Both generated and dynamic messages are supported:
+//!
+//! ```
+//! use protobuf::arbitrary::ArbitraryOptions;
+//! use protobuf::arbitrary::ArbitrarySource;
+//! use protobuf::well_known_types::Duration;
+//!
+//! let mut source = ArbitrarySource::new(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+//! let duration: Duration =
+//!     protobuf::arbitrary::arbitrary_message(&mut source, &ArbitraryOptions::default());
+//! # drop(duration);
+//! ```
+//!
+//! Generation is bounded by [`ArbitraryOptions`]: message fields are not
+//! populated below `max_depth` (except required fields), and repeated
+//! fields, maps, strings and bytes are limited in length.
+
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+use crate::descriptor::field_descriptor_proto::Label;
+
+/// Source of arbitrary choices.
+#[derive(Debug, Clone)]
+pub struct ArbitrarySource<'a> {
+    data: &'a [u8],
+    // Used when constructed from seed.
+    rng: Option<u64>,
+}
+
+impl<'a> ArbitrarySource<'a> {
+    /// Derive values from given bytes.
+    pub fn new(data: &'a [u8]) -> ArbitrarySource<'a> {
+        ArbitrarySource { data, rng: None }
+    }
+
+    /// Number of remaining input bytes.
+    pub fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Is input exhausted?
+    pub fn is_empty(&self) -> bool {
+        self.rng.is_none() && self.data.is_empty()
+    }
+
+    fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut r = [0; N];
+        match &mut self.rng {
+            Some(state) => {
+                for b in r.iter_mut() {
+                    // xorshift64
+                    *state ^= *state << 13;
+                    *state ^= *state >> 7;
+                    *state ^= *state << 17;
+                    *b = *state as u8;
+                }
+            }
+            None => {
+                let n = N.min(self.data.len());
+                r[..n].copy_from_slice(&self.data[..n]);
+                self.data = &self.data[n..];
+            }
+        }
+        r
+    }
+
+    /// Arbitrary `u8`.
+    pub fn u8(&mut self) -> u8 {
+        self.bytes::<1>()[0]
+    }
+
+    /// Arbitrary `u32`.
+    pub fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.bytes())
+    }
+
+    /// Arbitrary `u64`.
+    pub fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.bytes())
+    }
+
+    /// Arbitrary `bool`.
+    pub fn bool(&mut self) -> bool {
+        self.u8() & 1 != 0
+    }
+
+    /// Arbitrary number in range `0..=max`.
+    pub fn up_to(&mut self, max: usize) -> usize {
+        match max {
+            0 => 0,
+            m if m < 0x100 => self.u8() as usize % (m + 1),
+            m => self.u32() as usize % (m + 1),
+        }
+    }
+}
+
+impl ArbitrarySource<'static> {
+    /// Pseudo-random source, for tests which do not have input bytes.
+    ///
+    /// Same seed produces same sequence of values.
+    pub fn from_seed(seed: u64) -> ArbitrarySource<'static> {
+        ArbitrarySource {
+            data: &[],
+            // xorshift state must not be zero
+            rng: Some(seed | 1),
+        }
+    }
+}
+
+/// Limits for generated values.
+#[derive(Debug, Clone)]
+pub struct ArbitraryOptions {
+    /// Message-typed fields nested deeper than this are left unset,
+    /// unless they are required.
+    pub max_depth: u32,
+    /// Maximum number of elements of repeated and map fields.
+    pub max_repeated_len: usize,
+    /// Maximum length of strings (in chars) and bytes.
+    pub max_bytes_len: usize,
+    /// Generate NaN and infinite values for floating point fields.
+    ///
+    /// This is off by default, because messages with NaN are not equal
+    /// to themselves, which is inconvenient for round trip tests.
+    pub non_finite_floats: bool,
+    /// Prevent struct construction with struct literal.
+    pub _future_options: (),
+}
+
+impl Default for ArbitraryOptions {
+    fn default() -> Self {
+        ArbitraryOptions {
+            max_depth: 4,
+            max_repeated_len: 4,
+            max_bytes_len: 16,
+            non_finite_floats: false,
+            _future_options: (),
+        }
+    }
+}
+
+pub(crate) struct Generator<'s, 'a, 'o> {
+    pub(crate) source: &'s mut ArbitrarySource<'a>,
+    pub(crate) options: &'o ArbitraryOptions,
+}
+
+impl<'s, 'a, 'o> Generator<'s, 'a, 'o> {
+    fn f64(&mut self) -> f64 {
+        let v = f64::from_bits(self.source.u64());
+        if v.is_finite() || self.options.non_finite_floats {
+            v
+        } else {
+            0.0
+        }
+    }
+
+    fn f32(&mut self) -> f32 {
+        let v = f32::from_bits(self.source.u32());
+        if v.is_finite() || self.options.non_finite_floats {
+            v
+        } else {
+            0.0
+        }
+    }
+
+    fn string(&mut self) -> String {
+        let len = self.source.up_to(self.options.max_bytes_len);
+        (0..len)
+            .map(|_| {
+                if self.source.bool() {
+                    // Printable ASCII is most common in real messages.
+                    (b' ' + self.source.u8() % 95) as char
+                } else {
+                    std::char::from_u32(self.source.u32() % 0x11_0000).unwrap_or('\u{fffd}')
+                }
+            })
+            .collect()
+    }
+
+    fn bytes(&mut self) -> Vec<u8> {
+        let len = self.source.up_to(self.options.max_bytes_len);
+        (0..len).map(|_| self.source.u8()).collect()
+    }
+
+    pub(crate) fn value(&mut self, t: &RuntimeTypeBox, depth: u32) -> ReflectValueBox {
+        match t {
+            RuntimeTypeBox::I32 => ReflectValueBox::I32(self.source.u32() as i32),
+            RuntimeTypeBox::I64 => ReflectValueBox::I64(self.source.u64() as i64),
+            RuntimeTypeBox::U32 => ReflectValueBox::U32(self.source.u32()),
+            RuntimeTypeBox::U64 => ReflectValueBox::U64(self.source.u64()),
+            RuntimeTypeBox::F32 => ReflectValueBox::F32(self.f32()),
+            RuntimeTypeBox::F64 => ReflectValueBox::F64(self.f64()),
+            RuntimeTypeBox::Bool => ReflectValueBox::Bool(self.source.bool()),
+            RuntimeTypeBox::String => ReflectValueBox::String(self.string()),
+            RuntimeTypeBox::VecU8 => ReflectValueBox::Bytes(self.bytes()),
+            RuntimeTypeBox::Enum(e) => {
+                let values: Vec<_> = e.values().collect();
+                let value = values[self.source.up_to(values.len() - 1)].value();
+                ReflectValueBox::Enum(e.clone(), value)
+            }
+            RuntimeTypeBox::Message(m) => ReflectValueBox::Message(self.message(m, depth + 1)),
+        }
+    }
+
+    fn fill_field(&mut self, message: &mut dyn MessageDyn, field: &FieldDescriptor, depth: u32) {
+        let required = field.get_proto().get_label() == Label::LABEL_REQUIRED;
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => {
+                if let RuntimeTypeBox::Message(..) = t {
+                    if depth >= self.options.max_depth && !required {
+                        return;
+                    }
+                }
+                if required || self.source.bool() {
+                    let value = self.value(&t, depth);
+                    field.set_singular_field(message, value);
+                }
+            }
+            RuntimeFieldType::Repeated(t) => {
+                if let RuntimeTypeBox::Message(..) = t {
+                    if depth >= self.options.max_depth {
+                        return;
+                    }
+                }
+                let len = self.source.up_to(self.options.max_repeated_len);
+                for _ in 0..len {
+                    let value = self.value(&t, depth);
+                    field.mut_repeated(message).push(value);
+                }
+            }
+            RuntimeFieldType::Map(k, v) => {
+                if let RuntimeTypeBox::Message(..) = v {
+                    if depth >= self.options.max_depth {
+                        return;
+                    }
+                }
+                let len = self.source.up_to(self.options.max_repeated_len);
+                for _ in 0..len {
+                    let key = self.value(&k, depth);
+                    let value = self.value(&v, depth);
+                    field.mut_map(message).insert(key, value);
+                }
+            }
+        }
+    }
+
+    fn fill(&mut self, message: &mut dyn MessageDyn, descriptor: &MessageDescriptor, depth: u32) {
+        for field in descriptor.fields() {
+            if field.containing_oneof().is_none() {
+                self.fill_field(message, &field, depth);
+            }
+        }
+
+        for oneof in descriptor.oneofs() {
+            let fields: Vec<_> = oneof.fields().collect();
+            // Zero means oneof is not set.
+            let choice = self.source.up_to(fields.len());
+            if choice != 0 {
+                let field = &fields[choice - 1];
+                match field.runtime_field_type() {
+                    RuntimeFieldType::Singular(RuntimeTypeBox::Message(..))
+                        if depth >= self.options.max_depth => {}
+                    RuntimeFieldType::Singular(t) => {
+                        let value = self.value(&t, depth);
+                        field.set_singular_field(message, value);
+                    }
+                    _ => unreachable!("oneof field must be singular"),
+                }
+            }
+        }
+    }
+
+    fn message(&mut self, descriptor: &MessageDescriptor, depth: u32) -> Box<dyn MessageDyn> {
+        let mut message = descriptor.new_instance();
+        self.fill(&mut *message, descriptor, depth);
+        message
+    }
+}
+
+/// Generate arbitrary message of type specified by descriptor.
+///
+/// Descriptor can be generated or dynamic.
+pub fn arbitrary_dynamic(
+    descriptor: &MessageDescriptor,
+    source: &mut ArbitrarySource,
+    options: &ArbitraryOptions,
+) -> Box<dyn MessageDyn> {
+    Generator { source, options }.message(descriptor, 0)
+}
+
+/// Generate arbitrary generated message.
+pub fn arbitrary_message<M: Message>(
+    source: &mut ArbitrarySource,
+    options: &ArbitraryOptions,
+) -> M {
+    let mut message = M::new();
+    Generator { source, options }.fill(&mut message, &M::descriptor_static(), 0);
+    message
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Struct;
+
+    #[test]
+    fn empty_source() {
+        let mut source = ArbitrarySource::new(&[]);
+        let m: FileDescriptorProto = arbitrary_message(&mut source, &ArbitraryOptions::default());
+        assert_eq!(FileDescriptorProto::new(), m);
+    }
+
+    #[test]
+    fn deterministic() {
+        let options = ArbitraryOptions::default();
+        for seed in 0..20 {
+            let a: FileDescriptorProto =
+                arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+            let b = arbitrary_dynamic(
+                &FileDescriptorProto::descriptor_static(),
+                &mut ArbitrarySource::from_seed(seed),
+                &options,
+            );
+            assert_eq!(Some(&a), b.downcast_ref::<FileDescriptorProto>());
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let options = ArbitraryOptions::default();
+        for seed in 0..50 {
+            let m: Struct = arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+            let bytes = m.write_to_bytes().unwrap();
+            assert_eq!(m, Struct::parse_from_bytes(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let options = ArbitraryOptions {
+            max_depth: 0,
+            ..Default::default()
+        };
+        for seed in 0..20 {
+            let m: FileDescriptorProto =
+                arbitrary_message(&mut ArbitrarySource::from_seed(seed), &options);
+            assert!(m.message_type.is_empty());
+            assert!(m.options.is_none());
+        }
+    }
+}
@@ -0,0 +1,306 @@
+//! Conversion between rust-protobuf messages and messages of other
+//! protobuf runtimes, e. g. [prost](https://github.com/tokio-rs/prost).
+//!
+//! Messages are converted through serialized bytes, so both sides only
+//! need to agree on the `.proto` definition. Foreign message types
+//! implement [`ForeignMessage`], for prost types it can be done with
+//! [`prost_bridge!`](crate::prost_bridge) macro:
+//!
+//! ```ignore
+//! protobuf::prost_bridge!(my_prost_crate::Person, "example.Person");
+//!
+//! let person: my_prost_crate::Person = protobuf::bridge::to_foreign(&rust_protobuf_person)?;
+//! let back: my_protobuf_crate::Person = protobuf::bridge::from_foreign(&person)?;
+//! ```
+//!
+//! Descriptors can be exchanged the same way: prost-build and
+//! `prost-types` use the same `FileDescriptorSet` encoding,
+//! see [`file_descriptor_set_to_bytes`] and [`file_descriptors_from_set_bytes`].
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::descriptor::FileDescriptorSet;
+use crate::error::ProtobufError;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::DescriptorSetError;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+
+/// Message type of other protobuf runtime.
+pub trait ForeignMessage: Sized {
+    /// Serialize the message.
+    fn encode_foreign(&self) -> Vec<u8>;
+
+    /// Parse the message.
+    fn decode_foreign(bytes: &[u8]) -> Result<Self, Box<dyn Error + Send + Sync>>;
+
+    /// Fully qualified protobuf name of the message type, e. g. `foo.bar.Baz`.
+    ///
+    /// When specified, conversion functions check that it matches
+    /// the name of the rust-protobuf message type.
+    fn foreign_full_name() -> Option<&'static str> {
+        None
+    }
+}
+
+/// Implement [`ForeignMessage`] for a prost message type.
+///
+/// Optional second argument is a fully qualified protobuf message name,
+/// used to check types during conversion.
+///
+/// The macro expands to code referring to `::prost` crate, so it must be
+/// a dependency of the crate invoking the macro, `protobuf` crate itself
+/// does not depend on prost.
+#[macro_export]
+macro_rules! prost_bridge {
+    ($t:ty) => {
+        impl $crate::bridge::ForeignMessage for $t {
+            fn encode_foreign(&self) -> ::std::vec::Vec<u8> {
+                ::prost::Message::encode_to_vec(self)
+            }
+
+            fn decode_foreign(
+                bytes: &[u8],
+            ) -> ::std::result::Result<
+                Self,
+                ::std::boxed::Box<dyn ::std::error::Error + Send + Sync>,
+            > {
+                <$t as ::prost::Message>::decode(bytes).map_err(::std::convert::From::from)
+            }
+        }
+    };
+    ($t:ty, $full_name:expr) => {
+        impl $crate::bridge::ForeignMessage for $t {
+            fn encode_foreign(&self) -> ::std::vec::Vec<u8> {
+                ::prost::Message::encode_to_vec(self)
+            }
+
+            fn decode_foreign(
+                bytes: &[u8],
+            ) -> ::std::result::Result<
+                Self,
+                ::std::boxed::Box<dyn ::std::error::Error + Send + Sync>,
+            > {
+                <$t as ::prost::Message>::decode(bytes).map_err(::std::convert::From::from)
+            }
+
+            fn foreign_full_name() -> ::std::option::Option<&'static str> {
+                ::std::option::Option::Some($full_name)
+            }
+        }
+    };
+}
+
+/// Conversion error.
+#[derive(Debug)]
+pub enum BridgeError {
+    /// Failed to serialize or parse rust-protobuf message.
+    Protobuf(ProtobufError),
+    /// Failed to parse foreign message.
+    Foreign(Box<dyn Error + Send + Sync>),
+    /// Invalid descriptor set.
+    Descriptors(DescriptorSetError),
+    /// Message type names do not match.
+    TypeMismatch {
+        /// Name of rust-protobuf message type.
+        protobuf: String,
+        /// Name of foreign message type.
+        foreign: String,
+    },
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BridgeError::Protobuf(e) => fmt::Display::fmt(e, f),
+            BridgeError::Foreign(e) => write!(f, "foreign message: {}", e),
+            BridgeError::Descriptors(e) => fmt::Display::fmt(e, f),
+            BridgeError::TypeMismatch { protobuf, foreign } => write!(
+                f,
+                "message type mismatch: {} and foreign {}",
+                protobuf, foreign
+            ),
+        }
+    }
+}
+
+impl Error for BridgeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BridgeError::Protobuf(e) => Some(e),
+            BridgeError::Foreign(e) => Some(&**e),
+            BridgeError::Descriptors(e) => Some(e),
+            BridgeError::TypeMismatch { .. } => None,
+        }
+    }
+}
+
+impl From<ProtobufError> for BridgeError {
+    fn from(e: ProtobufError) -> Self {
+        BridgeError::Protobuf(e)
+    }
+}
+
+impl From<DescriptorSetError> for BridgeError {
+    fn from(e: DescriptorSetError) -> Self {
+        BridgeError::Descriptors(e)
+    }
+}
+
+/// Result of conversion.
+pub type BridgeResult<T> = Result<T, BridgeError>;
+
+/// Check that foreign message type matches the descriptor,
+/// if foreign message type name is known.
+pub fn check_type<F: ForeignMessage>(descriptor: &MessageDescriptor) -> BridgeResult<()> {
+    match F::foreign_full_name() {
+        Some(name) if name.trim_start_matches('.') != descriptor.full_name() => {
+            Err(BridgeError::TypeMismatch {
+                protobuf: descriptor.full_name().to_owned(),
+                foreign: name.to_owned(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Convert rust-protobuf message (generated or dynamic) to foreign message.
+pub fn to_foreign<F: ForeignMessage>(message: &dyn MessageDyn) -> BridgeResult<F> {
+    check_type::<F>(&message.descriptor_dyn())?;
+    let bytes = message.write_to_bytes_dyn()?;
+    F::decode_foreign(&bytes).map_err(BridgeError::Foreign)
+}
+
+/// Convert foreign message to generated rust-protobuf message.
+pub fn from_foreign<M: Message, F: ForeignMessage>(foreign: &F) -> BridgeResult<M> {
+    check_type::<F>(&M::descriptor_static())?;
+    Ok(M::parse_from_bytes(&foreign.encode_foreign())?)
+}
+
+/// Convert foreign message to rust-protobuf message of given type.
+///
+/// Descriptor may be dynamic, so foreign message can be inspected with reflection.
+pub fn from_foreign_dyn<F: ForeignMessage>(
+    foreign: &F,
+    descriptor: &MessageDescriptor,
+) -> BridgeResult<Box<dyn MessageDyn>> {
+    check_type::<F>(descriptor)?;
+    let mut message = descriptor.new_instance();
+    message.merge_from_bytes_dyn(&foreign.encode_foreign())?;
+    Ok(message)
+}
+
+/// Serialize given files and all their dependencies as `FileDescriptorSet`.
+///
+/// Dependencies are placed before dependent files.
+pub fn file_descriptor_set_to_bytes(files: &[FileDescriptor]) -> BridgeResult<Vec<u8>> {
+    fn visit(file: &FileDescriptor, visited: &mut HashSet<String>, fds: &mut FileDescriptorSet) {
+        if !visited.insert(file.proto().get_name().to_owned()) {
+            return;
+        }
+        for dep in file.deps() {
+            visit(dep, visited, fds);
+        }
+        fds.file.push(file.proto().clone());
+    }
+
+    let mut visited = HashSet::new();
+    let mut fds = FileDescriptorSet::new();
+    for file in files {
+        visit(file, &mut visited, &mut fds);
+    }
+    Ok(fds.write_to_bytes()?)
+}
+
+/// Parse serialized `FileDescriptorSet`, e. g. one generated by prost-build,
+/// into dynamic file descriptors.
+///
+/// Descriptors are checked with
+/// [`FileDescriptor::try_new_dynamic_fds`](crate::reflect::FileDescriptor::try_new_dynamic_fds).
+pub fn file_descriptors_from_set_bytes(bytes: &[u8]) -> BridgeResult<Vec<FileDescriptor>> {
+    let fds = FileDescriptorSet::parse_from_bytes(bytes)?;
+    Ok(FileDescriptor::try_new_dynamic_fds(fds.file)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Timestamp;
+
+    /// Stands for prost-generated `Duration`.
+    #[derive(Debug, PartialEq)]
+    struct ForeignDuration(Vec<u8>);
+
+    impl ForeignMessage for ForeignDuration {
+        fn encode_foreign(&self) -> Vec<u8> {
+            self.0.clone()
+        }
+
+        fn decode_foreign(bytes: &[u8]) -> Result<Self, Box<dyn Error + Send + Sync>> {
+            Ok(ForeignDuration(bytes.to_vec()))
+        }
+
+        fn foreign_full_name() -> Option<&'static str> {
+            Some("google.protobuf.Duration")
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut duration = Duration::new();
+        duration.seconds = 10;
+
+        let foreign: ForeignDuration = to_foreign(&duration).unwrap();
+        assert_eq!(duration, from_foreign::<Duration, _>(&foreign).unwrap());
+
+        let dynamic = from_foreign_dyn(&foreign, &Duration::descriptor_static()).unwrap();
+        assert_eq!(Some(&duration), dynamic.downcast_ref::<Duration>());
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let foreign = ForeignDuration(Vec::new());
+        match from_foreign::<Timestamp, _>(&foreign) {
+            Err(BridgeError::TypeMismatch { .. }) => {}
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn file_descriptor_set() {
+        let bytes = file_descriptor_set_to_bytes(&[descriptor::file_descriptor()]).unwrap();
+        let files = file_descriptors_from_set_bytes(&bytes).unwrap();
+        assert_eq!(1, files.len());
+        assert_eq!(descriptor::file_descriptor().proto(), files[0].proto());
+    }
+
+    #[test]
+    fn file_descriptor_set_missing_dependency() {
+        let mut fds = FileDescriptorSet::new();
+        fds.file.push(
+            crate::well_known_types::file_descriptors::api()
+                .proto()
+                .clone(),
+        );
+        let bytes = fds.write_to_bytes().unwrap();
+        match file_descriptors_from_set_bytes(&bytes) {
+            Err(BridgeError::Descriptors(e)) => assert_eq!(
+                "invalid descriptor set: \
+                dependency google/protobuf/source_context.proto of google/protobuf/api.proto is not found; \
+                dependency google/protobuf/type.proto of google/protobuf/api.proto is not found; \
+                type .google.protobuf.Option of field google.protobuf.Api.options is not found; \
+                type .google.protobuf.SourceContext of field google.protobuf.Api.source_context is not found; \
+                type .google.protobuf.Syntax of field google.protobuf.Api.syntax is not found; \
+                type .google.protobuf.Option of field google.protobuf.Method.options is not found; \
+                type .google.protobuf.Syntax of field google.protobuf.Method.syntax is not found",
+                e.to_string()
+            ),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+    }
+}
@@ -15,6 +15,8 @@ use bytes::Bytes;
 use bytes::BytesMut;
 
 use crate::buf_read_or_reader::BufReadOrReader;
+use crate::coded_input_stream::try_reserve;
+use crate::coded_input_stream::try_reserve_exact;
 use crate::coded_input_stream::READ_RAW_BYTES_MAX_ALLOC;
 use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
@@ -70,9 +72,18 @@ impl<'a> Drop for BufReadIter<'a> {
 
 impl<'ignore> BufReadIter<'ignore> {
     pub fn from_read<'a>(read: &'a mut dyn Read) -> BufReadIter<'a> {
+        BufReadIter::from_read_with_buffer_size(read, INPUT_STREAM_BUFFER_SIZE)
+    }
+
+    pub fn from_read_with_buffer_size<'a>(
+        read: &'a mut dyn Read,
+        buffer_size: usize,
+    ) -> BufReadIter<'a> {
+        // Empty buffer would be filled with nothing which is EOF
+        let buffer_size = cmp::max(buffer_size, 1);
         BufReadIter {
             input_source: InputSource::Read(BufReadOrReader::BufReader(BufReader::with_capacity(
-                INPUT_STREAM_BUFFER_SIZE,
+                buffer_size,
                 read,
             ))),
             buf: &[],
@@ -237,6 +248,9 @@ impl<'ignore> BufReadIter<'ignore> {
             self.pos_within_buf += len;
             Ok(r)
         } else {
+            if len as u64 > self.max_remaining_bytes() {
+                return Err(ProtobufError::WireError(WireError::TruncatedMessage));
+            }
             if len >= READ_RAW_BYTES_MAX_ALLOC {
                 // We cannot trust `len` because protobuf message could be malformed.
                 // Reading should not result in OOM when allocating a buffer.
@@ -326,23 +340,21 @@ impl<'ignore> BufReadIter<'ignore> {
     /// Read raw bytes into the supplied vector.  The vector will be resized as needed and
     /// overwritten.
     pub fn read_exact_to_vec(&mut self, count: usize, target: &mut Vec<u8>) -> ProtobufResult<()> {
-        // TODO: also do some limits when reading from unlimited source
-        if count as u64 > self.bytes_until_limit() {
+        if count as u64 > self.max_remaining_bytes() {
             return Err(ProtobufError::WireError(WireError::TruncatedMessage));
         }
 
         target.clear();
 
-        if count >= READ_RAW_BYTES_MAX_ALLOC && count > target.capacity() {
+        if count > self.prealloc_len(count as u64) && count > target.capacity() {
             // avoid calling `reserve` on buf with very large buffer: could be a malformed message
 
-            target.reserve(READ_RAW_BYTES_MAX_ALLOC);
+            try_reserve(target, READ_RAW_BYTES_MAX_ALLOC)?;
 
             while target.len() < count {
-                if count - target.len() <= target.len() {
-                    target.reserve_exact(count - target.len());
-                } else {
-                    target.reserve(1);
+                if target.len() == target.capacity() {
+                    // at most double the buffer like `Vec::push` does
+                    try_reserve(target, cmp::min(count - target.len(), target.len()))?;
                 }
 
                 let max = cmp::min(target.capacity() - target.len(), count - target.len());
@@ -352,7 +364,7 @@ impl<'ignore> BufReadIter<'ignore> {
                 }
             }
         } else {
-            target.reserve_exact(count);
+            try_reserve_exact(target, count)?;
 
             unsafe {
                 self.read_exact(&mut target.get_unchecked_mut(..count))?;
@@ -365,6 +377,52 @@ impl<'ignore> BufReadIter<'ignore> {
         Ok(())
     }
 
+    /// Skip exact number of bytes without copying them
+    /// (except for reading them from the underlying `BufRead`).
+    pub fn skip_bytes(&mut self, count: usize) -> ProtobufResult<()> {
+        if self.remaining_in_buf_len() >= count {
+            self.pos_within_buf += count;
+            return Ok(());
+        }
+
+        if count as u64 > self.max_remaining_bytes() {
+            return Err(ProtobufError::WireError(WireError::UnexpectedEof));
+        }
+
+        let mut rem = count;
+        while rem != 0 {
+            let len = cmp::min(self.fill_buf()?.len(), rem);
+            if len == 0 {
+                return Err(ProtobufError::WireError(WireError::UnexpectedEof));
+            }
+            self.pos_within_buf += len;
+            rem -= len;
+        }
+
+        Ok(())
+    }
+
+    /// Upper bound of the number of bytes which can be read:
+    /// bytes until limit, and bytes remaining in the input if it is in memory.
+    pub fn max_remaining_bytes(&self) -> u64 {
+        match self.input_source {
+            InputSource::Read(..) => self.bytes_until_limit(),
+            _ => self.remaining_in_buf_len() as u64,
+        }
+    }
+
+    /// How many bytes can be allocated upfront to read `len` bytes.
+    ///
+    /// Declared lengths are not trusted: allocation is capped by the bytes
+    /// remaining in memory, or by a constant when reading from `Read`.
+    pub fn prealloc_len(&self, len: u64) -> usize {
+        let len = cmp::min(len, self.max_remaining_bytes());
+        match self.input_source {
+            InputSource::Read(..) => cmp::min(len, READ_RAW_BYTES_MAX_ALLOC as u64) as usize,
+            _ => len as usize,
+        }
+    }
+
     fn do_fill_buf(&mut self) -> ProtobufResult<()> {
         debug_assert!(self.pos_within_buf == self.limit_within_buf);
 
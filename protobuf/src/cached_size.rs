@@ -1,3 +1,4 @@
+use std::cmp;
 use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::atomic::AtomicUsize;
@@ -6,7 +7,7 @@ use std::sync::atomic::Ordering;
 /// Cached size field used in generated code.
 ///
 /// It is always equal to itself to simplify generated code.
-/// (Generated code can use `#[derive(Eq)]`).
+/// (Generated code can use `#[derive(Eq)]` and `#[derive(Ord)]`).
 ///
 /// This type should rarely be used directly.
 #[derive(Debug, Default)]
@@ -49,6 +50,18 @@ impl PartialEq<CachedSize> for CachedSize {
 
 impl Eq for CachedSize {}
 
+impl PartialOrd for CachedSize {
+    fn partial_cmp(&self, other: &CachedSize) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CachedSize {
+    fn cmp(&self, _other: &CachedSize) -> cmp::Ordering {
+        cmp::Ordering::Equal
+    }
+}
+
 impl Hash for CachedSize {
     fn hash<H: Hasher>(&self, _state: &mut H) {
         // ignore cached size in cache computation
@@ -1,5 +1,7 @@
 #![cfg(feature = "bytes")]
 
+use std::borrow::Borrow;
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Deref;
 use std::str;
@@ -7,7 +9,6 @@ use std::str;
 use bytes::Bytes;
 
 use crate::clear::Clear;
-use std::borrow::Borrow;
 
 /// Thin wrapper around `Bytes` which guarantees that bytes are valid UTF-8 string.
 /// Should be API-compatible to `String`.
@@ -20,6 +21,11 @@ impl Chars {
         Chars(Bytes::new())
     }
 
+    /// Create from static string, without copying.
+    pub const fn from_static(s: &'static str) -> Chars {
+        Chars(Bytes::from_static(s.as_bytes()))
+    }
+
     /// Try convert from `Bytes`
     pub fn from_bytes(bytes: Bytes) -> Result<Chars, str::Utf8Error> {
         str::from_utf8(&bytes)?;
@@ -36,6 +42,21 @@ impl Chars {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Get a string.
+    pub fn as_str(&self) -> &str {
+        &*self
+    }
+
+    /// Underlying bytes.
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.0
+    }
+
+    /// Convert into underlying `Bytes`, without copying.
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
 }
 
 impl<'a> From<&'a str> for Chars {
@@ -57,6 +78,20 @@ impl Into<String> for Chars {
     }
 }
 
+impl From<Chars> for Bytes {
+    fn from(src: Chars) -> Bytes {
+        src.0
+    }
+}
+
+impl TryFrom<Bytes> for Chars {
+    type Error = str::Utf8Error;
+
+    fn try_from(bytes: Bytes) -> Result<Chars, str::Utf8Error> {
+        Chars::from_bytes(bytes)
+    }
+}
+
 impl Default for Chars {
     fn default() -> Self {
         Chars::new()
@@ -78,6 +113,54 @@ impl Borrow<str> for Chars {
     }
 }
 
+impl AsRef<str> for Chars {
+    fn as_ref(&self) -> &str {
+        &*self
+    }
+}
+
+impl AsRef<[u8]> for Chars {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Chars {
+    fn eq(&self, other: &str) -> bool {
+        &**self == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Chars {
+    fn eq(&self, other: &&'a str) -> bool {
+        &**self == *other
+    }
+}
+
+impl PartialEq<String> for Chars {
+    fn eq(&self, other: &String) -> bool {
+        &**self == other.as_str()
+    }
+}
+
+impl PartialEq<Chars> for str {
+    fn eq(&self, other: &Chars) -> bool {
+        self == &**other
+    }
+}
+
+impl<'a> PartialEq<Chars> for &'a str {
+    fn eq(&self, other: &Chars) -> bool {
+        *self == &**other
+    }
+}
+
+impl PartialEq<Chars> for String {
+    fn eq(&self, other: &Chars) -> bool {
+        self.as_str() == &**other
+    }
+}
+
 impl Clear for Chars {
     fn clear(&mut self) {
         self.0.clear();
@@ -98,8 +181,26 @@ impl fmt::Debug for Chars {
     }
 }
 
+#[cfg(feature = "with-serde")]
+impl serde::Serialize for Chars {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de> serde::Deserialize<'de> for Chars {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Chars::from)
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::convert::TryFrom;
+
+    use bytes::Bytes;
+
     use super::Chars;
 
     #[test]
@@ -111,4 +212,23 @@ mod test {
         assert_eq!(format!("{}", string), format!("{}", chars));
         assert_eq!(format!("{:?}", string), format!("{:?}", chars));
     }
+
+    #[test]
+    fn test_eq_str() {
+        let chars = Chars::from("abc");
+        assert_eq!(chars, "abc");
+        assert_eq!("abc", chars);
+        assert_eq!(chars, "abc".to_owned());
+        assert_eq!(*"abc", chars);
+        assert_eq!(Chars::from_static("abc"), chars);
+    }
+
+    #[test]
+    fn test_bytes_conversion() {
+        let bytes = Bytes::from_static(b"abc");
+        let chars = Chars::try_from(bytes.clone()).unwrap();
+        assert_eq!("abc", chars.as_str());
+        assert_eq!(bytes, Bytes::from(chars));
+        assert!(Chars::try_from(Bytes::from_static(b"\xff")).is_err());
+    }
 }
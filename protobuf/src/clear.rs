@@ -2,6 +2,14 @@
 use bytes::Bytes;
 
 /// anything that can be cleared
+///
+/// Generated messages keep allocated capacity of strings, bytes,
+/// repeated and map fields, so cleared message can be reused without
+/// reallocation. Use [`Message::shrink_to_fit`](crate::Message::shrink_to_fit)
+/// to release the memory.
+///
+/// Fields with explicit presence (singular message fields and proto2
+/// optional fields) are reset to `None`, so their storage is not retained.
 pub trait Clear {
     /// Clear this make, make it equivalent to newly created object.
     fn clear(&mut self);
@@ -2,6 +2,7 @@ use std::io;
 use std::io::BufRead;
 use std::io::Read;
 use std::mem;
+use std::str;
 
 #[cfg(feature = "bytes")]
 use crate::bytes::Bytes;
@@ -13,7 +14,15 @@ use crate::enums::ProtobufEnum;
 use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
 use crate::error::WireError;
+use crate::ext::ExtensionRegistry;
 use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+#[cfg(feature = "stats")]
+use crate::stats;
+#[cfg(feature = "stats")]
+use crate::stats::ParseStats;
+use crate::string_interner::InternedString;
+use crate::string_interner::StringInterner;
 use crate::unknown::UnknownValue;
 use crate::wire_format;
 use crate::zigzag::decode_zig_zag_32;
@@ -44,24 +53,84 @@ const DEFAULT_RECURSION_LIMIT: u32 = 100;
 // Max allocated vec when reading length-delimited from unknown input stream
 pub(crate) const READ_RAW_BYTES_MAX_ALLOC: usize = 10_000_000;
 
+/// Grow a vector, return an error instead of aborting if allocation fails.
+pub(crate) fn try_reserve<T>(vec: &mut Vec<T>, additional: usize) -> ProtobufResult<()> {
+    vec.try_reserve(additional)
+        .map_err(|_| ProtobufError::WireError(WireError::OutOfMemory))
+}
+
+/// Like [`try_reserve`] but without overallocation.
+pub(crate) fn try_reserve_exact<T>(vec: &mut Vec<T>, additional: usize) -> ProtobufResult<()> {
+    vec.try_reserve_exact(additional)
+        .map_err(|_| ProtobufError::WireError(WireError::OutOfMemory))
+}
+
+/// How values of `proto2` enum fields which are not declared in the enum
+/// are handled when parsing generated messages.
+///
+/// `proto3` enums are open, their undeclared values are always stored in fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownEnumValuePolicy {
+    /// Store the value in the field like for `proto3` enums.
+    Keep,
+    /// Store the value in unknown fields of the message as the protobuf spec requires.
+    UnknownFields,
+    /// Fail with invalid enum value error.
+    Error,
+    /// Store the default value of the enum, which is its first value.
+    Default,
+}
+
+impl Default for UnknownEnumValuePolicy {
+    fn default() -> UnknownEnumValuePolicy {
+        UnknownEnumValuePolicy::Keep
+    }
+}
+
 /// Buffered read with handy utilities.
 pub struct CodedInputStream<'a> {
     source: BufReadIter<'a>,
     recursion_level: u32,
     recursion_limit: u32,
+    extension_registry: Option<&'a ExtensionRegistry>,
+    string_interner: Option<&'a StringInterner>,
+    // Used when `string_interner` is not set, created on first use
+    own_string_interner: Option<StringInterner>,
+    discard_unknown_fields: bool,
+    unknown_enum_value_policy: UnknownEnumValuePolicy,
+    #[cfg(feature = "stats")]
+    stats: ParseStats,
 }
 
 impl<'a> CodedInputStream<'a> {
     /// Wrap a `Read`.
     ///
     /// Note resulting `CodedInputStream` is buffered even if `Read` is not.
+    /// Use [`from_buffered_reader`](CodedInputStream::from_buffered_reader)
+    /// if the reader is already buffered like `BufReader`, to avoid copying data
+    /// between buffers.
     pub fn new(read: &'a mut dyn Read) -> CodedInputStream<'a> {
         CodedInputStream::from_buf_read_iter(BufReadIter::from_read(read))
     }
 
+    /// Wrap a `Read` with buffer of given size.
+    ///
+    /// Default buffer size of [`new`](CodedInputStream::new) is 4096 bytes.
+    /// Larger buffer means fewer `read` calls, which matters when each call is
+    /// expensive, like for unbuffered files or sockets.
+    pub fn with_buffer_size(read: &'a mut dyn Read, buffer_size: usize) -> CodedInputStream<'a> {
+        CodedInputStream::from_buf_read_iter(BufReadIter::from_read_with_buffer_size(
+            read,
+            buffer_size,
+        ))
+    }
+
     /// Create from `BufRead`.
     ///
-    /// `CodedInputStream` will utilize `BufRead` buffer.
+    /// `CodedInputStream` will utilize `BufRead` buffer: data is parsed
+    /// directly from it without copying to another buffer.
+    /// Bytes which are not read by the stream are left in the `BufRead`
+    /// when the stream is dropped.
     pub fn from_buffered_reader(buf_read: &'a mut dyn BufRead) -> CodedInputStream<'a> {
         CodedInputStream::from_buf_read_iter(BufReadIter::from_buf_read(buf_read))
     }
@@ -86,6 +155,13 @@ impl<'a> CodedInputStream<'a> {
             source: source,
             recursion_level: 0,
             recursion_limit: DEFAULT_RECURSION_LIMIT,
+            extension_registry: None,
+            string_interner: None,
+            own_string_interner: None,
+            discard_unknown_fields: false,
+            unknown_enum_value_policy: UnknownEnumValuePolicy::Keep,
+            #[cfg(feature = "stats")]
+            stats: ParseStats::default(),
         }
     }
 
@@ -94,12 +170,75 @@ impl<'a> CodedInputStream<'a> {
         self.recursion_limit = limit;
     }
 
+    /// Set the registry of extensions which are decoded into typed
+    /// [`ExtensionFields`](crate::ext::ExtensionFields) while parsing.
+    ///
+    /// Registry is used for nested messages too.
+    pub fn set_extension_registry(&mut self, registry: &'a ExtensionRegistry) {
+        self.extension_registry = Some(registry);
+    }
+
+    /// Extension registry previously set with
+    /// [`set_extension_registry`](CodedInputStream::set_extension_registry).
+    pub fn extension_registry(&self) -> Option<&'a ExtensionRegistry> {
+        self.extension_registry
+    }
+
+    /// Set the interner used for fields stored as [`InternedString`].
+    ///
+    /// When not set, strings are interned in an interner owned by this stream,
+    /// so equal strings share allocation only within this stream.
+    /// [`StringInterner::global`] can be set to share strings between streams.
+    pub fn set_string_interner(&mut self, interner: &'a StringInterner) {
+        self.string_interner = Some(interner);
+    }
+
+    /// Interner previously set with
+    /// [`set_string_interner`](CodedInputStream::set_string_interner).
+    pub fn string_interner(&self) -> Option<&'a StringInterner> {
+        self.string_interner
+    }
+
+    /// Skip unknown fields instead of storing them in messages,
+    /// so parsing is faster and uses less memory, but unknown fields
+    /// are lost when the message is serialized again.
+    ///
+    /// Applies to nested messages too. Messages generated with
+    /// `discard_unknown_fields` codegen option discard unknown fields
+    /// regardless of this setting.
+    pub fn set_discard_unknown_fields(&mut self, discard: bool) {
+        self.discard_unknown_fields = discard;
+    }
+
+    /// Unknown fields are skipped, see
+    /// [`set_discard_unknown_fields`](CodedInputStream::set_discard_unknown_fields).
+    pub fn discard_unknown_fields(&self) -> bool {
+        self.discard_unknown_fields
+    }
+
+    /// Set how values of `proto2` enum fields not declared in the enum are handled.
+    ///
+    /// Applies to nested messages too. Default is [`UnknownEnumValuePolicy::Keep`].
+    pub fn set_unknown_enum_value_policy(&mut self, policy: UnknownEnumValuePolicy) {
+        self.unknown_enum_value_policy = policy;
+    }
+
+    /// Policy previously set with
+    /// [`set_unknown_enum_value_policy`](CodedInputStream::set_unknown_enum_value_policy).
+    pub fn unknown_enum_value_policy(&self) -> UnknownEnumValuePolicy {
+        self.unknown_enum_value_policy
+    }
+
     #[inline]
     pub(crate) fn incr_recursion(&mut self) -> ProtobufResult<()> {
         if self.recursion_level >= self.recursion_limit {
             return Err(ProtobufError::WireError(WireError::OverRecursionLimit));
         }
         self.recursion_level += 1;
+        #[cfg(feature = "stats")]
+        {
+            self.stats.max_depth = self.stats.max_depth.max(self.recursion_level);
+        }
         Ok(())
     }
 
@@ -108,6 +247,61 @@ impl<'a> CodedInputStream<'a> {
         self.recursion_level -= 1;
     }
 
+    /// Metrics of what was read from this stream so far.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> ParseStats {
+        ParseStats {
+            bytes: self.pos(),
+            ..self.stats.clone()
+        }
+    }
+
+    /// Run top-level parse function and report stream metrics
+    /// to the hook installed with `stats::set_parse_hook`.
+    #[inline]
+    pub(crate) fn with_stats_report<T, F>(&mut self, parse: F) -> ProtobufResult<T>
+    where
+        F: FnOnce(&mut Self) -> ProtobufResult<T>,
+    {
+        let r = parse(self);
+        #[cfg(feature = "stats")]
+        stats::report_parse(&self.stats());
+        r
+    }
+
+    /// Count heap allocation in metrics if a buffer grew.
+    #[inline]
+    pub(crate) fn count_growth(&mut self, old_capacity: usize, new_capacity: usize) {
+        #[cfg(feature = "stats")]
+        {
+            if new_capacity > old_capacity {
+                self.stats.allocations += 1;
+            }
+        }
+        #[cfg(not(feature = "stats"))]
+        let _ = (old_capacity, new_capacity);
+    }
+
+    /// Count heap allocation of a nested message in metrics.
+    #[inline]
+    pub(crate) fn count_allocation(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.allocations += 1;
+        }
+    }
+
+    /// Count bytes of unknown field in metrics.
+    #[inline]
+    pub(crate) fn count_unknown_bytes(&mut self, count: u64) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.unknown_bytes += count;
+        }
+        #[cfg(not(feature = "stats"))]
+        let _ = count;
+    }
+
     /// How many bytes processed
     pub fn pos(&self) -> u64 {
         self.source.pos()
@@ -118,6 +312,12 @@ impl<'a> CodedInputStream<'a> {
         self.source.bytes_until_limit()
     }
 
+    /// Upper bound of the number of bytes which can be read,
+    /// `u64::MAX` if unknown.
+    pub(crate) fn max_remaining_bytes(&self) -> u64 {
+        self.source.max_remaining_bytes()
+    }
+
     /// Read bytes into given `buf`.
     #[inline]
     pub fn read_exact(&mut self, buf: &mut [u8]) -> ProtobufResult<()> {
@@ -265,6 +465,10 @@ impl<'a> CodedInputStream<'a> {
     #[inline]
     pub fn read_tag(&mut self) -> ProtobufResult<wire_format::Tag> {
         let v = self.read_raw_varint32()?;
+        #[cfg(feature = "stats")]
+        {
+            self.stats.fields += 1;
+        }
         match wire_format::Tag::new(v) {
             Some(tag) => Ok(tag),
             None => Err(ProtobufError::WireError(WireError::IncorrectTag(v))),
@@ -366,45 +570,40 @@ impl<'a> CodedInputStream<'a> {
     ) -> ProtobufResult<()> {
         let len_bytes = self.read_raw_varint64()?;
 
-        let reserve = if len_bytes <= READ_RAW_BYTES_MAX_ALLOC as u64 {
-            (len_bytes as usize) / (T::ENCODED_SIZE as usize)
-        } else {
-            // prevent OOM on malformed input
-            // probably should truncate
-            READ_RAW_BYTES_MAX_ALLOC / (T::ENCODED_SIZE as usize)
-        };
+        // prevent OOM on malformed input
+        let reserve = self.source.prealloc_len(len_bytes) / (T::ENCODED_SIZE as usize);
 
-        target.reserve(reserve);
+        let capacity = target.capacity();
+        try_reserve(target, reserve)?;
 
         let old_limit = self.push_limit(len_bytes)?;
         while !self.eof()? {
             target.push(T::read(self)?);
         }
         self.pop_limit(old_limit);
+        self.count_growth(capacity, target.capacity());
         Ok(())
     }
 
-    fn read_repeated_packed_into<T: ProtobufType>(
+    pub(crate) fn read_repeated_packed_into<T: ProtobufType>(
         &mut self,
         target: &mut Vec<T::ProtobufValue>,
     ) -> ProtobufResult<()> {
         let len_bytes = self.read_raw_varint64()?;
 
-        // value is at least 1 bytes, so this is lower bound of element count
-        let reserve = if len_bytes <= READ_RAW_BYTES_MAX_ALLOC as u64 {
-            len_bytes as usize
-        } else {
-            // prevent OOM on malformed input
-            READ_RAW_BYTES_MAX_ALLOC
-        };
+        // value is at least 1 bytes, so this is upper bound of element count;
+        // prevent OOM on malformed input
+        let reserve = self.source.prealloc_len(len_bytes);
 
-        target.reserve(reserve);
+        let capacity = target.capacity();
+        try_reserve(target, reserve)?;
 
         let old_limit = self.push_limit(len_bytes)?;
         while !self.eof()? {
             target.push(T::read(self)?);
         }
         self.pop_limit(old_limit);
+        self.count_growth(capacity, target.capacity());
         Ok(())
     }
 
@@ -530,15 +729,84 @@ impl<'a> CodedInputStream<'a> {
         }
     }
 
-    /// Skip field
+    /// Skip the value of the field which tag was just read.
+    ///
+    /// Length-delimited values are skipped without copying, and groups
+    /// are skipped up to the matching end group tag.
+    ///
+    /// ```
+    /// # use protobuf::CodedInputStream;
+    /// # use protobuf::wire_format::WireType;
+    /// // field 2 (three bytes), field 1 (varint 7)
+    /// let mut is = CodedInputStream::from_bytes(&[0x12, 0x03, 0xaa, 0xbb, 0xcc, 0x08, 0x07]);
+    /// let (field_number, wire_type) = is.read_tag_unpack().unwrap();
+    /// assert_eq!((2, WireType::WireTypeLengthDelimited), (field_number, wire_type));
+    /// is.skip_field(wire_type).unwrap();
+    /// assert_eq!(1, is.read_tag_unpack().unwrap().0);
+    /// assert_eq!(7, is.read_uint32().unwrap());
+    /// ```
     pub fn skip_field(&mut self, wire_type: wire_format::WireType) -> ProtobufResult<()> {
-        self.read_unknown(wire_type).map(|_| ())
+        match wire_type {
+            wire_format::WireTypeVarint => self.read_raw_varint64().map(|_| ()),
+            wire_format::WireTypeFixed64 => self.skip_raw_bytes(8),
+            wire_format::WireTypeFixed32 => self.skip_raw_bytes(4),
+            wire_format::WireTypeLengthDelimited => {
+                let len = self.read_raw_varint32()?;
+                self.skip_raw_bytes(len)
+            }
+            wire_format::WireTypeStartGroup => {
+                self.incr_recursion()?;
+                let res = self.skip_group();
+                self.decr_recursion();
+                res
+            }
+            wire_format::WireTypeEndGroup => Err(ProtobufError::WireError(
+                WireError::UnexpectedWireType(wire_type),
+            )),
+        }
+    }
+
+    fn skip_group(&mut self) -> ProtobufResult<()> {
+        loop {
+            let (_, wire_type) = self.read_tag_unpack()?;
+            if wire_type == wire_format::WireTypeEndGroup {
+                return Ok(());
+            }
+            self.skip_field(wire_type)?;
+        }
+    }
+
+    /// Skip all remaining fields of the message being read:
+    /// fields until the end of input or until the current limit
+    /// (i. e. the end of the nested message).
+    ///
+    /// Fields are validated to be well-formed, but their values
+    /// are not copied, so this is faster than parsing the message.
+    /// This is useful to read only the first fields of a message:
+    ///
+    /// ```
+    /// # use protobuf::CodedInputStream;
+    /// let mut is = CodedInputStream::from_bytes(&[0x08, 0x07, 0x12, 0x02, 0xaa, 0xbb]);
+    /// assert_eq!(1, is.read_tag_unpack().unwrap().0);
+    /// assert_eq!(7, is.read_uint32().unwrap());
+    /// is.skip_message().unwrap();
+    /// assert!(is.eof().unwrap());
+    /// ```
+    pub fn skip_message(&mut self) -> ProtobufResult<()> {
+        while !self.eof()? {
+            let (_, wire_type) = self.read_tag_unpack()?;
+            self.skip_field(wire_type)?;
+        }
+        Ok(())
     }
 
     /// Read raw bytes into the supplied vector.  The vector will be resized as needed and
     /// overwritten.
     pub fn read_raw_bytes_into(&mut self, count: u32, target: &mut Vec<u8>) -> ProtobufResult<()> {
-        self.source.read_exact_to_vec(count as usize, target)
+        let capacity = target.capacity();
+        let r = self.source.read_exact_to_vec(count as usize, target);
+        self.count_growth(capacity, target.capacity());
+        r
     }
 
     /// Read exact number of bytes
@@ -548,10 +816,9 @@ impl<'a> CodedInputStream<'a> {
         Ok(r)
     }
 
-    /// Skip exact number of bytes
+    /// Skip exact number of bytes without copying them.
     pub fn skip_raw_bytes(&mut self, count: u32) -> ProtobufResult<()> {
-        // TODO: make it more efficient
-        self.read_raw_bytes(count).map(|_| ())
+        self.source.skip_bytes(count as usize)
     }
 
     /// Read `bytes` field, length delimited
@@ -604,8 +871,43 @@ impl<'a> CodedInputStream<'a> {
         Ok(())
     }
 
+    /// Read `string` field, length delimited, and intern it
+    pub fn read_interned_string(&mut self) -> ProtobufResult<InternedString> {
+        let len = self.read_raw_varint32()? as usize;
+        let interner = match self.string_interner {
+            Some(interner) => interner,
+            None => self
+                .own_string_interner
+                .get_or_insert_with(StringInterner::new),
+        };
+        fn intern(interner: &StringInterner, bytes: &[u8]) -> ProtobufResult<InternedString> {
+            match str::from_utf8(bytes) {
+                Ok(s) => Ok(interner.intern(s)),
+                Err(_) => Err(ProtobufError::WireError(WireError::Utf8Error)),
+            }
+        }
+        // Intern directly from the buffer if the string is already there,
+        // which is always the case for in-memory input
+        if self.source.remaining_in_buf_len() >= len {
+            let r = intern(interner, &self.source.remaining_in_buf()[..len])?;
+            self.source.consume(len);
+            Ok(r)
+        } else {
+            let mut vec = Vec::new();
+            self.source.read_exact_to_vec(len, &mut vec)?;
+            intern(interner, &vec)
+        }
+    }
+
     /// Read message, do not check if message is initialized
     pub fn merge_message<M: Message>(&mut self, message: &mut M) -> ProtobufResult<()> {
+        self.incr_recursion()?;
+        let res = self.merge_message_no_recursion(message);
+        self.decr_recursion();
+        res
+    }
+
+    fn merge_message_no_recursion<M: Message>(&mut self, message: &mut M) -> ProtobufResult<()> {
         let len = self.read_raw_varint64()?;
         let old_limit = self.push_limit(len)?;
         message.merge_from(self)?;
@@ -613,6 +915,20 @@ impl<'a> CodedInputStream<'a> {
         Ok(())
     }
 
+    /// Read length-delimited message of any type, do not check if message is initialized
+    pub(crate) fn merge_message_dyn(&mut self, message: &mut dyn MessageDyn) -> ProtobufResult<()> {
+        self.incr_recursion()?;
+        let res = (|| {
+            let len = self.read_raw_varint64()?;
+            let old_limit = self.push_limit(len)?;
+            message.merge_from_dyn(self)?;
+            self.pop_limit(old_limit);
+            Ok(())
+        })();
+        self.decr_recursion();
+        res
+    }
+
     /// Read message
     pub fn read_message<M: Message>(&mut self) -> ProtobufResult<M> {
         let mut r: M = Message::new();
@@ -785,6 +1101,53 @@ mod test {
         test_read("aa bb cc dd ee ff", |reader| {
             reader.skip_raw_bytes(6).unwrap();
         });
+        test_read_partial("aa bb", |reader| {
+            assert!(reader.skip_raw_bytes(3).is_err());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_skip_raw_bytes_small_buffer() {
+        let d = decode_hex("aa bb cc dd ee ff");
+        let mut reader = io::BufReader::with_capacity(2, io::Cursor::new(d));
+        let mut is = CodedInputStream::from_buffered_reader(&mut reader as &mut dyn BufRead);
+        is.skip_raw_bytes(5).unwrap();
+        assert_eq!(5, is.pos());
+        assert_eq!(0xff, is.read_raw_byte().unwrap());
+        assert!(is.skip_raw_bytes(1).is_err());
+    }
+
+    #[test]
+    fn test_input_stream_skip_field() {
+        // group 1 { 1: 1, group 2 {} }, 2: 2
+        test_read("0b 08 01 13 14 0c 10 02", |is| {
+            let (field_number, wire_type) = is.read_tag_unpack().unwrap();
+            assert_eq!(1, field_number);
+            is.skip_field(wire_type).unwrap();
+            assert_eq!(2, is.read_tag_unpack().unwrap().0);
+            assert_eq!(2, is.read_int32().unwrap());
+        });
+        // unterminated group
+        test_read_partial("0b 08 01", |is| {
+            let (_, wire_type) = is.read_tag_unpack().unwrap();
+            assert!(is.skip_field(wire_type).is_err());
+        });
+    }
+
+    #[test]
+    fn test_input_stream_skip_message() {
+        // 1: { 1: 1, 2: 2 }, 3: 5
+        test_read("0a 04 08 01 10 02 18 05", |is| {
+            assert_eq!(1, is.read_tag_unpack().unwrap().0);
+            let len = is.read_raw_varint64().unwrap();
+            let old_limit = is.push_limit(len).unwrap();
+            assert_eq!(1, is.read_tag_unpack().unwrap().0);
+            assert_eq!(1, is.read_int32().unwrap());
+            is.skip_message().unwrap();
+            is.pop_limit(old_limit);
+            assert_eq!(3, is.read_tag_unpack().unwrap().0);
+            assert_eq!(5, is.read_int32().unwrap());
+        });
     }
 
     #[test]
@@ -856,4 +1219,63 @@ mod test {
 
         assert!(is.eof().expect("eof"));
     }
+
+    #[test]
+    fn test_input_stream_huge_length_prefix_slice() {
+        // declared length is `u32::MAX`, but only two bytes follow
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0x0f, 1, 2];
+
+        let mut buf = Vec::new();
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        assert!(is.read_bytes_into(&mut buf).is_err());
+        assert_eq!(0, buf.capacity());
+
+        let mut packed: Vec<i32> = Vec::new();
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        drop(is.read_repeated_packed_int32_into(&mut packed));
+        // reserved for two elements, rounded up to minimal capacity
+        assert!(packed.capacity() <= 4, "{}", packed.capacity());
+
+        let mut packed: Vec<f64> = Vec::new();
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        assert!(is.read_repeated_packed_double_into(&mut packed).is_err());
+        assert_eq!(0, packed.capacity());
+    }
+
+    #[test]
+    fn test_input_stream_with_buffer_size() {
+        let bytes = decode_hex("96 01 ac 02 0a 03 61 62 63");
+        for &buffer_size in &[0, 1, 2, 3, 100] {
+            let mut reader: &[u8] = &bytes;
+            let mut is = CodedInputStream::with_buffer_size(&mut reader, buffer_size);
+            assert_eq!(150, is.read_raw_varint32().unwrap());
+            assert_eq!(300, is.read_raw_varint32().unwrap());
+            assert_eq!(10, is.read_raw_varint32().unwrap());
+            assert_eq!("abc", is.read_string().unwrap());
+            assert!(is.eof().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_input_stream_buffered_reader_leaves_rest() {
+        let bytes = decode_hex("96 01 ac 02");
+        let mut reader = io::BufReader::with_capacity(3, &bytes[..]);
+        {
+            let mut is = CodedInputStream::from_buffered_reader(&mut reader);
+            assert_eq!(150, is.read_raw_varint32().unwrap());
+        }
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(vec![0xac, 0x02], rest);
+    }
+
+    #[test]
+    fn test_input_stream_huge_length_prefix_read() {
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0x0f, 1, 2];
+        let mut reader: &[u8] = &bytes;
+        let mut is = CodedInputStream::new(&mut reader);
+        let mut buf = Vec::new();
+        assert!(is.read_bytes_into(&mut buf).is_err());
+        assert!(buf.capacity() <= READ_RAW_BYTES_MAX_ALLOC * 2);
+    }
 }
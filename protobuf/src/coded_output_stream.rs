@@ -1,6 +1,7 @@
 use crate::misc::remaining_capacity_as_slice_mut;
 use crate::misc::remove_lifetime_mut;
 use crate::varint;
+use crate::ext::ExtensionFields;
 use crate::wire_format;
 use crate::zigzag::encode_zig_zag_32;
 use crate::zigzag::encode_zig_zag_64;
@@ -489,6 +490,14 @@ impl<'a> CodedOutputStream<'a> {
         Ok(())
     }
 
+    /// Write extension fields
+    ///
+    /// Sizes of nested messages must be cached by
+    /// [`rt::extension_fields_size`](crate::rt::extension_fields_size).
+    pub fn write_extension_fields(&mut self, fields: &ExtensionFields) -> ProtobufResult<()> {
+        fields.write_to(self)
+    }
+
     /// Write bytes
     pub fn write_bytes_no_tag(&mut self, bytes: &[u8]) -> ProtobufResult<()> {
         self.write_raw_varint32(bytes.len() as u32)?;
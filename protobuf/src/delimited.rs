@@ -0,0 +1,200 @@
+//! Batches of length-delimited records.
+//!
+//! Records are messages written one after another with
+//! [`Message::write_length_delimited_to`](crate::Message::write_length_delimited_to),
+//! as in snapshot or log files.
+//!
+//! [`record_ranges`] builds an index of record offsets, which is cheap
+//! because only length prefixes are read, and [`parse_records_parallel`]
+//! uses this index to parse records on several threads.
+
+use std::cmp;
+use std::ops::Range;
+use std::thread;
+
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::message::Message;
+use crate::wire;
+
+/// Ranges of record payloads (without length prefixes) in the buffer.
+///
+/// Results in error if the last record is truncated.
+pub fn record_ranges(buf: &[u8]) -> ProtobufResult<Vec<Range<usize>>> {
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while pos != buf.len() {
+        let (len, varint_len) = match wire::decode_varint64(&buf[pos..])? {
+            Some(r) => r,
+            None => return Err(ProtobufError::WireError(WireError::UnexpectedEof)),
+        };
+        let start = pos + varint_len;
+        // After this check `len` fits in `usize`
+        if len > (buf.len() - start) as u64 {
+            return Err(ProtobufError::WireError(WireError::TruncatedMessage));
+        }
+        let end = start + len as usize;
+        ranges.push(start..end);
+        pos = end;
+    }
+    Ok(ranges)
+}
+
+/// Parse all records of the buffer in order.
+pub fn parse_records<M: Message>(buf: &[u8]) -> ProtobufResult<Vec<M>> {
+    record_ranges(buf)?
+        .into_iter()
+        .map(|r| M::parse_from_bytes(&buf[r]))
+        .collect()
+}
+
+/// Parse all records of the buffer using up to `threads` threads.
+///
+/// Records are split into contiguous batches of similar byte size,
+/// each batch is parsed on its own thread, and the result preserves
+/// the order of records. `threads` equal to zero means
+/// [available parallelism](std::thread::available_parallelism).
+///
+/// When several records are malformed, the error of the first one is returned.
+///
+/// ```
+/// # use protobuf::delimited;
+/// # use protobuf::Message;
+/// # use protobuf::well_known_types::Duration;
+/// let mut buf = Vec::new();
+/// for seconds in 0..100 {
+///     let mut d = Duration::new();
+///     d.seconds = seconds;
+///     d.write_length_delimited_to_vec(&mut buf).unwrap();
+/// }
+/// let records: Vec<Duration> = delimited::parse_records_parallel(&buf, 4).unwrap();
+/// assert_eq!(100, records.len());
+/// assert_eq!(99, records[99].seconds);
+/// ```
+pub fn parse_records_parallel<M: Message>(buf: &[u8], threads: usize) -> ProtobufResult<Vec<M>> {
+    let ranges = record_ranges(buf)?;
+    let threads = match threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let threads = cmp::min(threads, ranges.len());
+    if threads <= 1 {
+        return ranges
+            .into_iter()
+            .map(|r| M::parse_from_bytes(&buf[r]))
+            .collect();
+    }
+
+    let batches = split_batches(&ranges, threads);
+    thread::scope(|scope| {
+        let handles: Vec<_> = batches
+            .into_iter()
+            .map(|batch| {
+                scope.spawn(move || {
+                    batch
+                        .iter()
+                        .map(|r| M::parse_from_bytes(&buf[r.clone()]))
+                        .collect::<ProtobufResult<Vec<M>>>()
+                })
+            })
+            .collect();
+
+        let mut records = Vec::with_capacity(ranges.len());
+        let mut error = None;
+        for handle in handles {
+            let batch = handle
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+            match batch {
+                Ok(batch) => records.extend(batch),
+                Err(e) => {
+                    if error.is_none() {
+                        error = Some(e);
+                    }
+                }
+            }
+        }
+        match error {
+            Some(e) => Err(e),
+            None => Ok(records),
+        }
+    })
+}
+
+/// Split records into at most `count` contiguous batches of similar byte size.
+fn split_batches(ranges: &[Range<usize>], count: usize) -> Vec<&[Range<usize>]> {
+    let total: usize = ranges.iter().map(|r| r.len() + 1).sum();
+    let batch_size = (total + count - 1) / count;
+    let mut batches = Vec::with_capacity(count);
+    let mut start = 0;
+    let mut size = 0;
+    for (i, r) in ranges.iter().enumerate() {
+        size += r.len() + 1;
+        if size >= batch_size {
+            batches.push(&ranges[start..i + 1]);
+            start = i + 1;
+            size = 0;
+        }
+    }
+    if start != ranges.len() {
+        batches.push(&ranges[start..]);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::well_known_types::Duration;
+
+    fn records(count: i64) -> (Vec<Duration>, Vec<u8>) {
+        let mut buf = Vec::new();
+        let mut records = Vec::new();
+        for i in 0..count {
+            let mut d = Duration::new();
+            d.seconds = i * 1000;
+            d.write_length_delimited_to_vec(&mut buf).unwrap();
+            records.push(d);
+        }
+        (records, buf)
+    }
+
+    #[test]
+    fn ranges() {
+        let (_, buf) = records(3);
+        // Empty message, then `seconds: 1000`, then `seconds: 2000`
+        assert_eq!(vec![1..1, 2..5, 6..9], record_ranges(&buf).unwrap());
+        assert!(record_ranges(&buf[..buf.len() - 1]).is_err());
+        // Truncated length
+        assert!(record_ranges(&[0x80]).is_err());
+        // Length larger than `u32::MAX`
+        assert!(record_ranges(&[0x81, 0x80, 0x80, 0x80, 0x10, 0]).is_err());
+    }
+
+    #[test]
+    fn parallel_preserves_order() {
+        let (expected, buf) = records(1000);
+        for &threads in &[0, 1, 3, 8, 2000] {
+            let parsed: Vec<Duration> = parse_records_parallel(&buf, threads).unwrap();
+            assert_eq!(expected, parsed);
+        }
+        assert_eq!(expected, parse_records::<Duration>(&buf).unwrap());
+    }
+
+    #[test]
+    fn parallel_error() {
+        let (_, mut buf) = records(100);
+        // Record with incorrect tag
+        buf.extend_from_slice(&[1, 0]);
+        assert!(parse_records_parallel::<Duration>(&buf, 4).is_err());
+    }
+
+    #[test]
+    fn batches() {
+        let ranges: Vec<_> = (0..10).map(|i| i * 10..i * 10 + 9).collect();
+        let batches = split_batches(&ranges, 3);
+        assert_eq!(3, batches.len());
+        assert_eq!(10, batches.iter().map(|b| b.len()).sum::<usize>());
+    }
+}
@@ -111,6 +111,19 @@ impl crate::Message for FileDescriptorSet {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.file);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.file);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -137,6 +150,24 @@ impl crate::Message for FileDescriptorSet {
     }
 }
 
+impl crate::rt::SpaceUsed for FileDescriptorSet {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for FileDescriptorSet {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for FileDescriptorSet {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.file, worklist);
+    }
+}
+
 impl crate::Clear for FileDescriptorSet {
     fn clear(&mut self) {
         self.file.clear();
@@ -236,6 +267,13 @@ impl FileDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     // optional string package = 2;
 
     pub fn get_package(&self) -> &str {
@@ -272,6 +310,13 @@ impl FileDescriptorProto {
         self.package.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_package(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_package();
+        self.set_package(v);
+        r
+    }
+
     // optional string syntax = 12;
 
     pub fn get_syntax(&self) -> &str {
@@ -308,6 +353,13 @@ impl FileDescriptorProto {
         self.syntax.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_syntax(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_syntax();
+        self.set_syntax(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -569,6 +621,41 @@ impl crate::Message for FileDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.package);
+        my_size += crate::rt::space_used_excluding_self(&self.dependency);
+        my_size += crate::rt::space_used_excluding_self(&self.public_dependency);
+        my_size += crate::rt::space_used_excluding_self(&self.weak_dependency);
+        my_size += crate::rt::space_used_excluding_self(&self.message_type);
+        my_size += crate::rt::space_used_excluding_self(&self.enum_type);
+        my_size += crate::rt::space_used_excluding_self(&self.service);
+        my_size += crate::rt::space_used_excluding_self(&self.extension);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.source_code_info);
+        my_size += crate::rt::space_used_excluding_self(&self.syntax);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.package);
+        crate::rt::shrink_to_fit(&mut self.dependency);
+        crate::rt::shrink_to_fit(&mut self.public_dependency);
+        crate::rt::shrink_to_fit(&mut self.weak_dependency);
+        crate::rt::shrink_to_fit(&mut self.message_type);
+        crate::rt::shrink_to_fit(&mut self.enum_type);
+        crate::rt::shrink_to_fit(&mut self.service);
+        crate::rt::shrink_to_fit(&mut self.extension);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.source_code_info);
+        crate::rt::shrink_to_fit(&mut self.syntax);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -606,6 +693,29 @@ impl crate::Message for FileDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for FileDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for FileDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for FileDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.message_type, worklist);
+        crate::rt::detach_nested_messages(&mut self.enum_type, worklist);
+        crate::rt::detach_nested_messages(&mut self.service, worklist);
+        crate::rt::detach_nested_messages(&mut self.extension, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+        crate::rt::detach_nested_messages(&mut self.source_code_info, worklist);
+    }
+}
+
 impl crate::Clear for FileDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -705,6 +815,13 @@ impl DescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -942,6 +1059,37 @@ impl crate::Message for DescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.field);
+        my_size += crate::rt::space_used_excluding_self(&self.extension);
+        my_size += crate::rt::space_used_excluding_self(&self.nested_type);
+        my_size += crate::rt::space_used_excluding_self(&self.enum_type);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_range);
+        my_size += crate::rt::space_used_excluding_self(&self.oneof_decl);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.reserved_range);
+        my_size += crate::rt::space_used_excluding_self(&self.reserved_name);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.field);
+        crate::rt::shrink_to_fit(&mut self.extension);
+        crate::rt::shrink_to_fit(&mut self.nested_type);
+        crate::rt::shrink_to_fit(&mut self.enum_type);
+        crate::rt::shrink_to_fit(&mut self.extension_range);
+        crate::rt::shrink_to_fit(&mut self.oneof_decl);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.reserved_range);
+        crate::rt::shrink_to_fit(&mut self.reserved_name);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -977,6 +1125,31 @@ impl crate::Message for DescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for DescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for DescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for DescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.field, worklist);
+        crate::rt::detach_nested_messages(&mut self.extension, worklist);
+        crate::rt::detach_nested_messages(&mut self.nested_type, worklist);
+        crate::rt::detach_nested_messages(&mut self.enum_type, worklist);
+        crate::rt::detach_nested_messages(&mut self.extension_range, worklist);
+        crate::rt::detach_nested_messages(&mut self.oneof_decl, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+        crate::rt::detach_nested_messages(&mut self.reserved_range, worklist);
+    }
+}
+
 impl crate::Clear for DescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -1169,6 +1342,23 @@ pub mod descriptor_proto {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.start);
+            my_size += crate::rt::space_used_excluding_self(&self.end);
+            my_size += crate::rt::space_used_excluding_self(&self.options);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.start);
+            crate::rt::shrink_to_fit(&mut self.end);
+            crate::rt::shrink_to_fit(&mut self.options);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -1197,6 +1387,24 @@ pub mod descriptor_proto {
         }
     }
 
+    impl crate::rt::SpaceUsed for ExtensionRange {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for ExtensionRange {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for ExtensionRange {
+        fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+            crate::rt::detach_nested_messages(&mut self.options, worklist);
+        }
+    }
+
     impl crate::Clear for ExtensionRange {
         fn clear(&mut self) {
             self.start = ::std::option::Option::None;
@@ -1362,6 +1570,21 @@ pub mod descriptor_proto {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.start);
+            my_size += crate::rt::space_used_excluding_self(&self.end);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.start);
+            crate::rt::shrink_to_fit(&mut self.end);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -1389,6 +1612,23 @@ pub mod descriptor_proto {
         }
     }
 
+    impl crate::rt::SpaceUsed for ReservedRange {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for ReservedRange {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for ReservedRange {
+        fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+        }
+    }
+
     impl crate::Clear for ReservedRange {
         fn clear(&mut self) {
             self.start = ::std::option::Option::None;
@@ -1416,6 +1656,8 @@ pub struct ExtensionRangeOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -1465,7 +1707,7 @@ impl crate::Message for ExtensionRangeOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -1480,6 +1722,7 @@ impl crate::Message for ExtensionRangeOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1489,6 +1732,7 @@ impl crate::Message for ExtensionRangeOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1497,6 +1741,21 @@ impl crate::Message for ExtensionRangeOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -1505,6 +1764,14 @@ impl crate::Message for ExtensionRangeOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> ExtensionRangeOptions {
         ExtensionRangeOptions::new()
     }
@@ -1516,6 +1783,7 @@ impl crate::Message for ExtensionRangeOptions {
     fn default_instance() -> &'static ExtensionRangeOptions {
         static instance: ExtensionRangeOptions = ExtensionRangeOptions {
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -1523,9 +1791,28 @@ impl crate::Message for ExtensionRangeOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for ExtensionRangeOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for ExtensionRangeOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for ExtensionRangeOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for ExtensionRangeOptions {
     fn clear(&mut self) {
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -1652,6 +1939,13 @@ impl FieldDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     // optional int32 number = 3;
 
     pub fn get_number(&self) -> i32 {
@@ -1751,6 +2045,13 @@ impl FieldDescriptorProto {
         self.type_name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_type_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_type_name();
+        self.set_type_name(v);
+        r
+    }
+
     // optional string extendee = 2;
 
     pub fn get_extendee(&self) -> &str {
@@ -1787,6 +2088,13 @@ impl FieldDescriptorProto {
         self.extendee.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_extendee(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_extendee();
+        self.set_extendee(v);
+        r
+    }
+
     // optional string default_value = 7;
 
     pub fn get_default_value(&self) -> &str {
@@ -1823,6 +2131,13 @@ impl FieldDescriptorProto {
         self.default_value.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_default_value(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_default_value();
+        self.set_default_value(v);
+        r
+    }
+
     // optional int32 oneof_index = 9;
 
     pub fn get_oneof_index(&self) -> i32 {
@@ -1878,6 +2193,13 @@ impl FieldDescriptorProto {
         self.json_name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_json_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_json_name();
+        self.set_json_name(v);
+        r
+    }
+
     // optional bool proto3_optional = 17;
 
     pub fn get_proto3_optional(&self) -> bool {
@@ -2002,13 +2324,17 @@ impl crate::Message for FieldDescriptorProto {
                     if wire_type != crate::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(crate::rt::unexpected_wire_type(wire_type));
                     }
-                    self.label = ::std::option::Option::Some(is.read_enum_or_unknown()?);
+                    if let ::std::option::Option::Some(v) = crate::rt::read_proto2_enum_or_unknown(is, 4, &mut self.unknown_fields)? {
+                        self.label = ::std::option::Option::Some(v);
+                    }
                 },
                 5 => {
                     if wire_type != crate::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(crate::rt::unexpected_wire_type(wire_type));
                     }
-                    self.field_type = ::std::option::Option::Some(is.read_enum_or_unknown()?);
+                    if let ::std::option::Option::Some(v) = crate::rt::read_proto2_enum_or_unknown(is, 5, &mut self.unknown_fields)? {
+                        self.field_type = ::std::option::Option::Some(v);
+                    }
                 },
                 6 => {
                     if wire_type != crate::wire_format::WireTypeLengthDelimited {
@@ -2142,6 +2468,39 @@ impl crate::Message for FieldDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.number);
+        my_size += crate::rt::space_used_excluding_self(&self.label);
+        my_size += crate::rt::space_used_excluding_self(&self.field_type);
+        my_size += crate::rt::space_used_excluding_self(&self.type_name);
+        my_size += crate::rt::space_used_excluding_self(&self.extendee);
+        my_size += crate::rt::space_used_excluding_self(&self.default_value);
+        my_size += crate::rt::space_used_excluding_self(&self.oneof_index);
+        my_size += crate::rt::space_used_excluding_self(&self.json_name);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.proto3_optional);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.number);
+        crate::rt::shrink_to_fit(&mut self.label);
+        crate::rt::shrink_to_fit(&mut self.field_type);
+        crate::rt::shrink_to_fit(&mut self.type_name);
+        crate::rt::shrink_to_fit(&mut self.extendee);
+        crate::rt::shrink_to_fit(&mut self.default_value);
+        crate::rt::shrink_to_fit(&mut self.oneof_index);
+        crate::rt::shrink_to_fit(&mut self.json_name);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.proto3_optional);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -2178,6 +2537,24 @@ impl crate::Message for FieldDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for FieldDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for FieldDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for FieldDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for FieldDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -2259,6 +2636,53 @@ pub mod field_descriptor_proto {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Type::TYPE_DOUBLE => "TYPE_DOUBLE",
+                Type::TYPE_FLOAT => "TYPE_FLOAT",
+                Type::TYPE_INT64 => "TYPE_INT64",
+                Type::TYPE_UINT64 => "TYPE_UINT64",
+                Type::TYPE_INT32 => "TYPE_INT32",
+                Type::TYPE_FIXED64 => "TYPE_FIXED64",
+                Type::TYPE_FIXED32 => "TYPE_FIXED32",
+                Type::TYPE_BOOL => "TYPE_BOOL",
+                Type::TYPE_STRING => "TYPE_STRING",
+                Type::TYPE_GROUP => "TYPE_GROUP",
+                Type::TYPE_MESSAGE => "TYPE_MESSAGE",
+                Type::TYPE_BYTES => "TYPE_BYTES",
+                Type::TYPE_UINT32 => "TYPE_UINT32",
+                Type::TYPE_ENUM => "TYPE_ENUM",
+                Type::TYPE_SFIXED32 => "TYPE_SFIXED32",
+                Type::TYPE_SFIXED64 => "TYPE_SFIXED64",
+                Type::TYPE_SINT32 => "TYPE_SINT32",
+                Type::TYPE_SINT64 => "TYPE_SINT64",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<Type> {
+            match name {
+                "TYPE_DOUBLE" => ::std::option::Option::Some(Type::TYPE_DOUBLE),
+                "TYPE_FLOAT" => ::std::option::Option::Some(Type::TYPE_FLOAT),
+                "TYPE_INT64" => ::std::option::Option::Some(Type::TYPE_INT64),
+                "TYPE_UINT64" => ::std::option::Option::Some(Type::TYPE_UINT64),
+                "TYPE_INT32" => ::std::option::Option::Some(Type::TYPE_INT32),
+                "TYPE_FIXED64" => ::std::option::Option::Some(Type::TYPE_FIXED64),
+                "TYPE_FIXED32" => ::std::option::Option::Some(Type::TYPE_FIXED32),
+                "TYPE_BOOL" => ::std::option::Option::Some(Type::TYPE_BOOL),
+                "TYPE_STRING" => ::std::option::Option::Some(Type::TYPE_STRING),
+                "TYPE_GROUP" => ::std::option::Option::Some(Type::TYPE_GROUP),
+                "TYPE_MESSAGE" => ::std::option::Option::Some(Type::TYPE_MESSAGE),
+                "TYPE_BYTES" => ::std::option::Option::Some(Type::TYPE_BYTES),
+                "TYPE_UINT32" => ::std::option::Option::Some(Type::TYPE_UINT32),
+                "TYPE_ENUM" => ::std::option::Option::Some(Type::TYPE_ENUM),
+                "TYPE_SFIXED32" => ::std::option::Option::Some(Type::TYPE_SFIXED32),
+                "TYPE_SFIXED64" => ::std::option::Option::Some(Type::TYPE_SFIXED64),
+                "TYPE_SINT32" => ::std::option::Option::Some(Type::TYPE_SINT32),
+                "TYPE_SINT64" => ::std::option::Option::Some(Type::TYPE_SINT64),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Type] = &[
                 Type::TYPE_DOUBLE,
@@ -2295,6 +2719,12 @@ pub mod field_descriptor_proto {
         }
     }
 
+    impl ::std::fmt::Display for Type {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Type {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -2327,6 +2757,23 @@ pub mod field_descriptor_proto {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Label::LABEL_OPTIONAL => "LABEL_OPTIONAL",
+                Label::LABEL_REQUIRED => "LABEL_REQUIRED",
+                Label::LABEL_REPEATED => "LABEL_REPEATED",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<Label> {
+            match name {
+                "LABEL_OPTIONAL" => ::std::option::Option::Some(Label::LABEL_OPTIONAL),
+                "LABEL_REQUIRED" => ::std::option::Option::Some(Label::LABEL_REQUIRED),
+                "LABEL_REPEATED" => ::std::option::Option::Some(Label::LABEL_REPEATED),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Label] = &[
                 Label::LABEL_OPTIONAL,
@@ -2348,6 +2795,12 @@ pub mod field_descriptor_proto {
         }
     }
 
+    impl ::std::fmt::Display for Label {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Label {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -2420,6 +2873,13 @@ impl OneofDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -2503,6 +2963,21 @@ impl crate::Message for OneofDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -2530,6 +3005,24 @@ impl crate::Message for OneofDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for OneofDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for OneofDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for OneofDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for OneofDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -2617,6 +3110,13 @@ impl EnumDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -2754,6 +3254,27 @@ impl crate::Message for EnumDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.value);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.reserved_range);
+        my_size += crate::rt::space_used_excluding_self(&self.reserved_name);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.value);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.reserved_range);
+        crate::rt::shrink_to_fit(&mut self.reserved_name);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -2784,6 +3305,26 @@ impl crate::Message for EnumDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for EnumDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for EnumDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for EnumDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.value, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+        crate::rt::detach_nested_messages(&mut self.reserved_range, worklist);
+    }
+}
+
 impl crate::Clear for EnumDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -2956,6 +3497,21 @@ pub mod enum_descriptor_proto {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.start);
+            my_size += crate::rt::space_used_excluding_self(&self.end);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.start);
+            crate::rt::shrink_to_fit(&mut self.end);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -2983,6 +3539,23 @@ pub mod enum_descriptor_proto {
         }
     }
 
+    impl crate::rt::SpaceUsed for EnumReservedRange {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for EnumReservedRange {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for EnumReservedRange {
+        fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+        }
+    }
+
     impl crate::Clear for EnumReservedRange {
         fn clear(&mut self) {
             self.start = ::std::option::Option::None;
@@ -3064,6 +3637,13 @@ impl EnumValueDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     // optional int32 number = 2;
 
     pub fn get_number(&self) -> i32 {
@@ -3184,6 +3764,23 @@ impl crate::Message for EnumValueDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.number);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.number);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -3212,6 +3809,24 @@ impl crate::Message for EnumValueDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for EnumValueDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for EnumValueDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for EnumValueDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for EnumValueDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -3293,6 +3908,13 @@ impl ServiceDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -3396,6 +4018,23 @@ impl crate::Message for ServiceDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.method);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.method);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -3424,6 +4063,25 @@ impl crate::Message for ServiceDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for ServiceDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for ServiceDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for ServiceDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.method, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for ServiceDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -3476,6 +4134,10 @@ impl MethodDescriptorProto {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_CLIENT_STREAMING: bool = false;
+    pub const DEFAULT_SERVER_STREAMING: bool = false;
+
     // optional string name = 1;
 
     pub fn get_name(&self) -> &str {
@@ -3512,6 +4174,13 @@ impl MethodDescriptorProto {
         self.name.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_name();
+        self.set_name(v);
+        r
+    }
+
     // optional string input_type = 2;
 
     pub fn get_input_type(&self) -> &str {
@@ -3548,6 +4217,13 @@ impl MethodDescriptorProto {
         self.input_type.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_input_type(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_input_type();
+        self.set_input_type(v);
+        r
+    }
+
     // optional string output_type = 3;
 
     pub fn get_output_type(&self) -> &str {
@@ -3584,6 +4260,13 @@ impl MethodDescriptorProto {
         self.output_type.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_output_type(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_output_type();
+        self.set_output_type(v);
+        r
+    }
+
     // optional bool client_streaming = 5;
 
     pub fn get_client_streaming(&self) -> bool {
@@ -3777,6 +4460,29 @@ impl crate::Message for MethodDescriptorProto {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.input_type);
+        my_size += crate::rt::space_used_excluding_self(&self.output_type);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.client_streaming);
+        my_size += crate::rt::space_used_excluding_self(&self.server_streaming);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.input_type);
+        crate::rt::shrink_to_fit(&mut self.output_type);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.client_streaming);
+        crate::rt::shrink_to_fit(&mut self.server_streaming);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -3808,6 +4514,24 @@ impl crate::Message for MethodDescriptorProto {
     }
 }
 
+impl crate::rt::SpaceUsed for MethodDescriptorProto {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for MethodDescriptorProto {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for MethodDescriptorProto {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for MethodDescriptorProto {
     fn clear(&mut self) {
         self.name = ::std::option::Option::None;
@@ -3920,6 +4644,8 @@ pub struct FileOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -3936,6 +4662,17 @@ impl FileOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_JAVA_MULTIPLE_FILES: bool = false;
+    pub const DEFAULT_JAVA_STRING_CHECK_UTF8: bool = false;
+    pub const DEFAULT_OPTIMIZE_FOR: file_options::OptimizeMode = file_options::OptimizeMode::SPEED;
+    pub const DEFAULT_CC_GENERIC_SERVICES: bool = false;
+    pub const DEFAULT_JAVA_GENERIC_SERVICES: bool = false;
+    pub const DEFAULT_PY_GENERIC_SERVICES: bool = false;
+    pub const DEFAULT_PHP_GENERIC_SERVICES: bool = false;
+    pub const DEFAULT_DEPRECATED: bool = false;
+    pub const DEFAULT_CC_ENABLE_ARENAS: bool = true;
+
     // optional string java_package = 1;
 
     pub fn get_java_package(&self) -> &str {
@@ -3972,6 +4709,13 @@ impl FileOptions {
         self.java_package.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_java_package(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_java_package();
+        self.set_java_package(v);
+        r
+    }
+
     // optional string java_outer_classname = 8;
 
     pub fn get_java_outer_classname(&self) -> &str {
@@ -4008,6 +4752,13 @@ impl FileOptions {
         self.java_outer_classname.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_java_outer_classname(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_java_outer_classname();
+        self.set_java_outer_classname(v);
+        r
+    }
+
     // optional bool java_multiple_files = 10;
 
     pub fn get_java_multiple_files(&self) -> bool {
@@ -4123,6 +4874,13 @@ impl FileOptions {
         self.go_package.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_go_package(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_go_package();
+        self.set_go_package(v);
+        r
+    }
+
     // optional bool cc_generic_services = 16;
 
     pub fn get_cc_generic_services(&self) -> bool {
@@ -4273,6 +5031,13 @@ impl FileOptions {
         self.objc_class_prefix.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_objc_class_prefix(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_objc_class_prefix();
+        self.set_objc_class_prefix(v);
+        r
+    }
+
     // optional string csharp_namespace = 37;
 
     pub fn get_csharp_namespace(&self) -> &str {
@@ -4309,6 +5074,13 @@ impl FileOptions {
         self.csharp_namespace.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_csharp_namespace(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_csharp_namespace();
+        self.set_csharp_namespace(v);
+        r
+    }
+
     // optional string swift_prefix = 39;
 
     pub fn get_swift_prefix(&self) -> &str {
@@ -4345,6 +5117,13 @@ impl FileOptions {
         self.swift_prefix.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_swift_prefix(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_swift_prefix();
+        self.set_swift_prefix(v);
+        r
+    }
+
     // optional string php_class_prefix = 40;
 
     pub fn get_php_class_prefix(&self) -> &str {
@@ -4381,6 +5160,13 @@ impl FileOptions {
         self.php_class_prefix.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_php_class_prefix(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_php_class_prefix();
+        self.set_php_class_prefix(v);
+        r
+    }
+
     // optional string php_namespace = 41;
 
     pub fn get_php_namespace(&self) -> &str {
@@ -4417,6 +5203,13 @@ impl FileOptions {
         self.php_namespace.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_php_namespace(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_php_namespace();
+        self.set_php_namespace(v);
+        r
+    }
+
     // optional string php_metadata_namespace = 44;
 
     pub fn get_php_metadata_namespace(&self) -> &str {
@@ -4453,6 +5246,13 @@ impl FileOptions {
         self.php_metadata_namespace.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_php_metadata_namespace(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_php_metadata_namespace();
+        self.set_php_metadata_namespace(v);
+        r
+    }
+
     // optional string ruby_package = 45;
 
     pub fn get_ruby_package(&self) -> &str {
@@ -4489,6 +5289,13 @@ impl FileOptions {
         self.ruby_package.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_ruby_package(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_ruby_package();
+        self.set_ruby_package(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -4672,7 +5479,9 @@ impl crate::Message for FileOptions {
                     if wire_type != crate::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(crate::rt::unexpected_wire_type(wire_type));
                     }
-                    self.optimize_for = ::std::option::Option::Some(is.read_enum_or_unknown()?);
+                    if let ::std::option::Option::Some(v) = crate::rt::read_proto2_enum_or_unknown(is, 9, &mut self.unknown_fields)? {
+                        self.optimize_for = ::std::option::Option::Some(v);
+                    }
                 },
                 11 => {
                     if wire_type != crate::wire_format::WireTypeLengthDelimited {
@@ -4762,7 +5571,7 @@ impl crate::Message for FileOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -4837,6 +5646,7 @@ impl crate::Message for FileOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -4906,6 +5716,7 @@ impl crate::Message for FileOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -4914,6 +5725,61 @@ impl crate::Message for FileOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.java_package);
+        my_size += crate::rt::space_used_excluding_self(&self.java_outer_classname);
+        my_size += crate::rt::space_used_excluding_self(&self.java_multiple_files);
+        my_size += crate::rt::space_used_excluding_self(&self.java_generate_equals_and_hash);
+        my_size += crate::rt::space_used_excluding_self(&self.java_string_check_utf8);
+        my_size += crate::rt::space_used_excluding_self(&self.optimize_for);
+        my_size += crate::rt::space_used_excluding_self(&self.go_package);
+        my_size += crate::rt::space_used_excluding_self(&self.cc_generic_services);
+        my_size += crate::rt::space_used_excluding_self(&self.java_generic_services);
+        my_size += crate::rt::space_used_excluding_self(&self.py_generic_services);
+        my_size += crate::rt::space_used_excluding_self(&self.php_generic_services);
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.cc_enable_arenas);
+        my_size += crate::rt::space_used_excluding_self(&self.objc_class_prefix);
+        my_size += crate::rt::space_used_excluding_self(&self.csharp_namespace);
+        my_size += crate::rt::space_used_excluding_self(&self.swift_prefix);
+        my_size += crate::rt::space_used_excluding_self(&self.php_class_prefix);
+        my_size += crate::rt::space_used_excluding_self(&self.php_namespace);
+        my_size += crate::rt::space_used_excluding_self(&self.php_metadata_namespace);
+        my_size += crate::rt::space_used_excluding_self(&self.ruby_package);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.java_package);
+        crate::rt::shrink_to_fit(&mut self.java_outer_classname);
+        crate::rt::shrink_to_fit(&mut self.java_multiple_files);
+        crate::rt::shrink_to_fit(&mut self.java_generate_equals_and_hash);
+        crate::rt::shrink_to_fit(&mut self.java_string_check_utf8);
+        crate::rt::shrink_to_fit(&mut self.optimize_for);
+        crate::rt::shrink_to_fit(&mut self.go_package);
+        crate::rt::shrink_to_fit(&mut self.cc_generic_services);
+        crate::rt::shrink_to_fit(&mut self.java_generic_services);
+        crate::rt::shrink_to_fit(&mut self.py_generic_services);
+        crate::rt::shrink_to_fit(&mut self.php_generic_services);
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.cc_enable_arenas);
+        crate::rt::shrink_to_fit(&mut self.objc_class_prefix);
+        crate::rt::shrink_to_fit(&mut self.csharp_namespace);
+        crate::rt::shrink_to_fit(&mut self.swift_prefix);
+        crate::rt::shrink_to_fit(&mut self.php_class_prefix);
+        crate::rt::shrink_to_fit(&mut self.php_namespace);
+        crate::rt::shrink_to_fit(&mut self.php_metadata_namespace);
+        crate::rt::shrink_to_fit(&mut self.ruby_package);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -4922,6 +5788,14 @@ impl crate::Message for FileOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> FileOptions {
         FileOptions::new()
     }
@@ -4953,6 +5827,7 @@ impl crate::Message for FileOptions {
             php_metadata_namespace: ::std::option::Option::None,
             ruby_package: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -4960,6 +5835,24 @@ impl crate::Message for FileOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for FileOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for FileOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for FileOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for FileOptions {
     fn clear(&mut self) {
         self.java_package = ::std::option::Option::None;
@@ -4983,6 +5876,7 @@ impl crate::Clear for FileOptions {
         self.php_metadata_namespace = ::std::option::Option::None;
         self.ruby_package = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -5022,6 +5916,23 @@ pub mod file_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                OptimizeMode::SPEED => "SPEED",
+                OptimizeMode::CODE_SIZE => "CODE_SIZE",
+                OptimizeMode::LITE_RUNTIME => "LITE_RUNTIME",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<OptimizeMode> {
+            match name {
+                "SPEED" => ::std::option::Option::Some(OptimizeMode::SPEED),
+                "CODE_SIZE" => ::std::option::Option::Some(OptimizeMode::CODE_SIZE),
+                "LITE_RUNTIME" => ::std::option::Option::Some(OptimizeMode::LITE_RUNTIME),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [OptimizeMode] = &[
                 OptimizeMode::SPEED,
@@ -5043,6 +5954,12 @@ pub mod file_options {
         }
     }
 
+    impl ::std::fmt::Display for OptimizeMode {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for OptimizeMode {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -5073,6 +5990,8 @@ pub struct MessageOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -5089,6 +6008,11 @@ impl MessageOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_MESSAGE_SET_WIRE_FORMAT: bool = false;
+    pub const DEFAULT_NO_STANDARD_DESCRIPTOR_ACCESSOR: bool = false;
+    pub const DEFAULT_DEPRECATED: bool = false;
+
     // optional bool message_set_wire_format = 1;
 
     pub fn get_message_set_wire_format(&self) -> bool {
@@ -5246,7 +6170,7 @@ impl crate::Message for MessageOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -5273,6 +6197,7 @@ impl crate::Message for MessageOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -5294,6 +6219,7 @@ impl crate::Message for MessageOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -5302,6 +6228,29 @@ impl crate::Message for MessageOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.message_set_wire_format);
+        my_size += crate::rt::space_used_excluding_self(&self.no_standard_descriptor_accessor);
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.map_entry);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.message_set_wire_format);
+        crate::rt::shrink_to_fit(&mut self.no_standard_descriptor_accessor);
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.map_entry);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -5310,6 +6259,14 @@ impl crate::Message for MessageOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> MessageOptions {
         MessageOptions::new()
     }
@@ -5325,6 +6282,7 @@ impl crate::Message for MessageOptions {
             deprecated: ::std::option::Option::None,
             map_entry: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -5332,6 +6290,24 @@ impl crate::Message for MessageOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for MessageOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for MessageOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for MessageOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for MessageOptions {
     fn clear(&mut self) {
         self.message_set_wire_format = ::std::option::Option::None;
@@ -5339,6 +6315,7 @@ impl crate::Clear for MessageOptions {
         self.deprecated = ::std::option::Option::None;
         self.map_entry = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -5420,6 +6397,8 @@ pub struct FieldOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -5436,6 +6415,13 @@ impl FieldOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_CTYPE: field_options::CType = field_options::CType::STRING;
+    pub const DEFAULT_JSTYPE: field_options::JSType = field_options::JSType::JS_NORMAL;
+    pub const DEFAULT_LAZY: bool = false;
+    pub const DEFAULT_DEPRECATED: bool = false;
+    pub const DEFAULT_WEAK: bool = false;
+
     // optional .google.protobuf.FieldOptions.CType ctype = 1;
 
     pub fn get_ctype(&self) -> field_options::CType {
@@ -5625,7 +6611,9 @@ impl crate::Message for FieldOptions {
                     if wire_type != crate::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(crate::rt::unexpected_wire_type(wire_type));
                     }
-                    self.ctype = ::std::option::Option::Some(is.read_enum_or_unknown()?);
+                    if let ::std::option::Option::Some(v) = crate::rt::read_proto2_enum_or_unknown(is, 1, &mut self.unknown_fields)? {
+                        self.ctype = ::std::option::Option::Some(v);
+                    }
                 },
                 2 => {
                     if wire_type != crate::wire_format::WireTypeVarint {
@@ -5637,7 +6625,9 @@ impl crate::Message for FieldOptions {
                     if wire_type != crate::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(crate::rt::unexpected_wire_type(wire_type));
                     }
-                    self.jstype = ::std::option::Option::Some(is.read_enum_or_unknown()?);
+                    if let ::std::option::Option::Some(v) = crate::rt::read_proto2_enum_or_unknown(is, 6, &mut self.unknown_fields)? {
+                        self.jstype = ::std::option::Option::Some(v);
+                    }
                 },
                 5 => {
                     if wire_type != crate::wire_format::WireTypeVarint {
@@ -5661,7 +6651,7 @@ impl crate::Message for FieldOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -5694,6 +6684,7 @@ impl crate::Message for FieldOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -5721,6 +6712,7 @@ impl crate::Message for FieldOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -5729,6 +6721,33 @@ impl crate::Message for FieldOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.ctype);
+        my_size += crate::rt::space_used_excluding_self(&self.packed);
+        my_size += crate::rt::space_used_excluding_self(&self.jstype);
+        my_size += crate::rt::space_used_excluding_self(&self.lazy);
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.weak);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.ctype);
+        crate::rt::shrink_to_fit(&mut self.packed);
+        crate::rt::shrink_to_fit(&mut self.jstype);
+        crate::rt::shrink_to_fit(&mut self.lazy);
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.weak);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -5737,6 +6756,14 @@ impl crate::Message for FieldOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> FieldOptions {
         FieldOptions::new()
     }
@@ -5754,6 +6781,7 @@ impl crate::Message for FieldOptions {
             deprecated: ::std::option::Option::None,
             weak: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -5761,6 +6789,24 @@ impl crate::Message for FieldOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for FieldOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for FieldOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for FieldOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for FieldOptions {
     fn clear(&mut self) {
         self.ctype = ::std::option::Option::None;
@@ -5770,6 +6816,7 @@ impl crate::Clear for FieldOptions {
         self.deprecated = ::std::option::Option::None;
         self.weak = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -5808,6 +6855,23 @@ pub mod field_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                CType::STRING => "STRING",
+                CType::CORD => "CORD",
+                CType::STRING_PIECE => "STRING_PIECE",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<CType> {
+            match name {
+                "STRING" => ::std::option::Option::Some(CType::STRING),
+                "CORD" => ::std::option::Option::Some(CType::CORD),
+                "STRING_PIECE" => ::std::option::Option::Some(CType::STRING_PIECE),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [CType] = &[
                 CType::STRING,
@@ -5828,6 +6892,12 @@ pub mod field_options {
         }
     }
 
+    impl ::std::fmt::Display for CType {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for CType {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -5860,6 +6930,23 @@ pub mod field_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                JSType::JS_NORMAL => "JS_NORMAL",
+                JSType::JS_STRING => "JS_STRING",
+                JSType::JS_NUMBER => "JS_NUMBER",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<JSType> {
+            match name {
+                "JS_NORMAL" => ::std::option::Option::Some(JSType::JS_NORMAL),
+                "JS_STRING" => ::std::option::Option::Some(JSType::JS_STRING),
+                "JS_NUMBER" => ::std::option::Option::Some(JSType::JS_NUMBER),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [JSType] = &[
                 JSType::JS_NORMAL,
@@ -5880,6 +6967,12 @@ pub mod field_options {
         }
     }
 
+    impl ::std::fmt::Display for JSType {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for JSType {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -5899,6 +6992,8 @@ pub struct OneofOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -5948,7 +7043,7 @@ impl crate::Message for OneofOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -5963,6 +7058,7 @@ impl crate::Message for OneofOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -5972,6 +7068,7 @@ impl crate::Message for OneofOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -5980,6 +7077,21 @@ impl crate::Message for OneofOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -5988,6 +7100,14 @@ impl crate::Message for OneofOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> OneofOptions {
         OneofOptions::new()
     }
@@ -5999,6 +7119,7 @@ impl crate::Message for OneofOptions {
     fn default_instance() -> &'static OneofOptions {
         static instance: OneofOptions = OneofOptions {
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -6006,9 +7127,28 @@ impl crate::Message for OneofOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for OneofOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for OneofOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for OneofOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for OneofOptions {
     fn clear(&mut self) {
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6039,6 +7179,8 @@ pub struct EnumOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -6055,6 +7197,9 @@ impl EnumOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_DEPRECATED: bool = false;
+
     // optional bool allow_alias = 2;
 
     pub fn get_allow_alias(&self) -> bool {
@@ -6150,7 +7295,7 @@ impl crate::Message for EnumOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -6171,6 +7316,7 @@ impl crate::Message for EnumOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -6186,6 +7332,7 @@ impl crate::Message for EnumOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -6194,6 +7341,25 @@ impl crate::Message for EnumOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.allow_alias);
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.allow_alias);
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -6202,6 +7368,14 @@ impl crate::Message for EnumOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> EnumOptions {
         EnumOptions::new()
     }
@@ -6215,6 +7389,7 @@ impl crate::Message for EnumOptions {
             allow_alias: ::std::option::Option::None,
             deprecated: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -6222,11 +7397,30 @@ impl crate::Message for EnumOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for EnumOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for EnumOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for EnumOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for EnumOptions {
     fn clear(&mut self) {
         self.allow_alias = ::std::option::Option::None;
         self.deprecated = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6254,6 +7448,8 @@ pub struct EnumValueOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -6270,6 +7466,9 @@ impl EnumValueOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_DEPRECATED: bool = false;
+
     // optional bool deprecated = 1;
 
     pub fn get_deprecated(&self) -> bool {
@@ -6334,7 +7533,7 @@ impl crate::Message for EnumValueOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -6352,6 +7551,7 @@ impl crate::Message for EnumValueOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -6364,6 +7564,7 @@ impl crate::Message for EnumValueOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -6372,6 +7573,23 @@ impl crate::Message for EnumValueOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -6380,6 +7598,14 @@ impl crate::Message for EnumValueOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> EnumValueOptions {
         EnumValueOptions::new()
     }
@@ -6392,6 +7618,7 @@ impl crate::Message for EnumValueOptions {
         static instance: EnumValueOptions = EnumValueOptions {
             deprecated: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -6399,10 +7626,29 @@ impl crate::Message for EnumValueOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for EnumValueOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for EnumValueOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for EnumValueOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for EnumValueOptions {
     fn clear(&mut self) {
         self.deprecated = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6430,6 +7676,8 @@ pub struct ServiceOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -6446,6 +7694,9 @@ impl ServiceOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_DEPRECATED: bool = false;
+
     // optional bool deprecated = 33;
 
     pub fn get_deprecated(&self) -> bool {
@@ -6510,7 +7761,7 @@ impl crate::Message for ServiceOptions {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -6528,6 +7779,7 @@ impl crate::Message for ServiceOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -6540,6 +7792,7 @@ impl crate::Message for ServiceOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -6548,6 +7801,23 @@ impl crate::Message for ServiceOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -6556,6 +7826,14 @@ impl crate::Message for ServiceOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> ServiceOptions {
         ServiceOptions::new()
     }
@@ -6568,6 +7846,7 @@ impl crate::Message for ServiceOptions {
         static instance: ServiceOptions = ServiceOptions {
             deprecated: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -6575,10 +7854,29 @@ impl crate::Message for ServiceOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for ServiceOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for ServiceOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for ServiceOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for ServiceOptions {
     fn clear(&mut self) {
         self.deprecated = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6607,6 +7905,8 @@ pub struct MethodOptions {
     pub uninterpreted_option: ::std::vec::Vec<UninterpretedOption>,
     // special fields
     #[cfg_attr(serde, serde(skip))]
+    pub extension_fields: crate::ext::ExtensionFields,
+    #[cfg_attr(serde, serde(skip))]
     pub unknown_fields: crate::UnknownFields,
     #[cfg_attr(serde, serde(skip))]
     pub cached_size: crate::rt::CachedSize,
@@ -6623,6 +7923,10 @@ impl MethodOptions {
         ::std::default::Default::default()
     }
 
+    // explicit default values
+    pub const DEFAULT_DEPRECATED: bool = false;
+    pub const DEFAULT_IDEMPOTENCY_LEVEL: method_options::IdempotencyLevel = method_options::IdempotencyLevel::IDEMPOTENCY_UNKNOWN;
+
     // optional bool deprecated = 33;
 
     pub fn get_deprecated(&self) -> bool {
@@ -6715,13 +8019,15 @@ impl crate::Message for MethodOptions {
                     if wire_type != crate::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(crate::rt::unexpected_wire_type(wire_type));
                     }
-                    self.idempotency_level = ::std::option::Option::Some(is.read_enum_or_unknown()?);
+                    if let ::std::option::Option::Some(v) = crate::rt::read_proto2_enum_or_unknown(is, 34, &mut self.unknown_fields)? {
+                        self.idempotency_level = ::std::option::Option::Some(v);
+                    }
                 },
                 999 => {
                     crate::rt::read_repeated_message_into_vec(wire_type, is, &mut self.uninterpreted_option)?;
                 },
                 _ => {
-                    crate::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                    crate::rt::read_unknown_or_extension::<Self>(field_number, wire_type, is, &mut self.extension_fields, &mut self.unknown_fields)?;
                 },
             };
         }
@@ -6742,6 +8048,7 @@ impl crate::Message for MethodOptions {
             let len = value.compute_size();
             my_size += 2 + crate::rt::compute_raw_varint32_size(len) + len;
         };
+        my_size += crate::rt::extension_fields_size(&self.extension_fields);
         my_size += crate::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -6757,6 +8064,7 @@ impl crate::Message for MethodOptions {
         for v in &self.uninterpreted_option {
             crate::rt::write_message_field_with_cached_size(999, v, os)?;
         };
+        os.write_extension_fields(&self.extension_fields)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -6765,6 +8073,25 @@ impl crate::Message for MethodOptions {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.deprecated);
+        my_size += crate::rt::space_used_excluding_self(&self.idempotency_level);
+        my_size += crate::rt::space_used_excluding_self(&self.uninterpreted_option);
+        my_size += crate::rt::space_used_excluding_self(&self.extension_fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.deprecated);
+        crate::rt::shrink_to_fit(&mut self.idempotency_level);
+        crate::rt::shrink_to_fit(&mut self.uninterpreted_option);
+        crate::rt::shrink_to_fit(&mut self.extension_fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -6773,6 +8100,14 @@ impl crate::Message for MethodOptions {
         &mut self.unknown_fields
     }
 
+    fn get_extension_fields(&self) -> ::std::option::Option<&crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&self.extension_fields)
+    }
+
+    fn mut_extension_fields(&mut self) -> ::std::option::Option<&mut crate::ext::ExtensionFields> {
+        ::std::option::Option::Some(&mut self.extension_fields)
+    }
+
     fn new() -> MethodOptions {
         MethodOptions::new()
     }
@@ -6786,6 +8121,7 @@ impl crate::Message for MethodOptions {
             deprecated: ::std::option::Option::None,
             idempotency_level: ::std::option::Option::None,
             uninterpreted_option: ::std::vec::Vec::new(),
+            extension_fields: crate::ext::ExtensionFields::new(),
             unknown_fields: crate::UnknownFields::new(),
             cached_size: crate::rt::CachedSize::new(),
         };
@@ -6793,11 +8129,30 @@ impl crate::Message for MethodOptions {
     }
 }
 
+impl crate::rt::SpaceUsed for MethodOptions {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for MethodOptions {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for MethodOptions {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.uninterpreted_option, worklist);
+    }
+}
+
 impl crate::Clear for MethodOptions {
     fn clear(&mut self) {
         self.deprecated = ::std::option::Option::None;
         self.idempotency_level = ::std::option::Option::None;
         self.uninterpreted_option.clear();
+        self.extension_fields.clear();
         self.unknown_fields.clear();
     }
 }
@@ -6839,6 +8194,23 @@ pub mod method_options {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                IdempotencyLevel::IDEMPOTENCY_UNKNOWN => "IDEMPOTENCY_UNKNOWN",
+                IdempotencyLevel::NO_SIDE_EFFECTS => "NO_SIDE_EFFECTS",
+                IdempotencyLevel::IDEMPOTENT => "IDEMPOTENT",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<IdempotencyLevel> {
+            match name {
+                "IDEMPOTENCY_UNKNOWN" => ::std::option::Option::Some(IdempotencyLevel::IDEMPOTENCY_UNKNOWN),
+                "NO_SIDE_EFFECTS" => ::std::option::Option::Some(IdempotencyLevel::NO_SIDE_EFFECTS),
+                "IDEMPOTENT" => ::std::option::Option::Some(IdempotencyLevel::IDEMPOTENT),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [IdempotencyLevel] = &[
                 IdempotencyLevel::IDEMPOTENCY_UNKNOWN,
@@ -6859,6 +8231,12 @@ pub mod method_options {
         }
     }
 
+    impl ::std::fmt::Display for IdempotencyLevel {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for IdempotencyLevel {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -6943,6 +8321,13 @@ impl UninterpretedOption {
         self.identifier_value.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_identifier_value(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_identifier_value();
+        self.set_identifier_value(v);
+        r
+    }
+
     // optional uint64 positive_int_value = 4;
 
     pub fn get_positive_int_value(&self) -> u64 {
@@ -7036,6 +8421,13 @@ impl UninterpretedOption {
         self.string_value.take().unwrap_or_else(|| ::std::vec::Vec::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_string_value(&mut self, v: ::std::vec::Vec<u8>) -> ::std::vec::Vec<u8> {
+        let r = self.take_string_value();
+        self.set_string_value(v);
+        r
+    }
+
     // optional string aggregate_value = 8;
 
     pub fn get_aggregate_value(&self) -> &str {
@@ -7072,6 +8464,13 @@ impl UninterpretedOption {
         self.aggregate_value.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_aggregate_value(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_aggregate_value();
+        self.set_aggregate_value(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -7245,6 +8644,31 @@ impl crate::Message for UninterpretedOption {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.identifier_value);
+        my_size += crate::rt::space_used_excluding_self(&self.positive_int_value);
+        my_size += crate::rt::space_used_excluding_self(&self.negative_int_value);
+        my_size += crate::rt::space_used_excluding_self(&self.double_value);
+        my_size += crate::rt::space_used_excluding_self(&self.string_value);
+        my_size += crate::rt::space_used_excluding_self(&self.aggregate_value);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.identifier_value);
+        crate::rt::shrink_to_fit(&mut self.positive_int_value);
+        crate::rt::shrink_to_fit(&mut self.negative_int_value);
+        crate::rt::shrink_to_fit(&mut self.double_value);
+        crate::rt::shrink_to_fit(&mut self.string_value);
+        crate::rt::shrink_to_fit(&mut self.aggregate_value);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -7277,6 +8701,24 @@ impl crate::Message for UninterpretedOption {
     }
 }
 
+impl crate::rt::SpaceUsed for UninterpretedOption {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for UninterpretedOption {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for UninterpretedOption {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.name, worklist);
+    }
+}
+
 impl crate::Clear for UninterpretedOption {
     fn clear(&mut self) {
         self.name.clear();
@@ -7367,6 +8809,13 @@ pub mod uninterpreted_option {
             self.name_part.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_name_part(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_name_part();
+            self.set_name_part(v);
+            r
+        }
+
         // required bool is_extension = 2;
 
         pub fn get_is_extension(&self) -> bool {
@@ -7473,6 +8922,21 @@ pub mod uninterpreted_option {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.name_part);
+            my_size += crate::rt::space_used_excluding_self(&self.is_extension);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.name_part);
+            crate::rt::shrink_to_fit(&mut self.is_extension);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -7500,6 +8964,23 @@ pub mod uninterpreted_option {
         }
     }
 
+    impl crate::rt::SpaceUsed for NamePart {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for NamePart {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for NamePart {
+        fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+        }
+    }
+
     impl crate::Clear for NamePart {
         fn clear(&mut self) {
             self.name_part = ::std::option::Option::None;
@@ -7609,6 +9090,19 @@ impl crate::Message for SourceCodeInfo {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.location);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.location);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -7635,6 +9129,24 @@ impl crate::Message for SourceCodeInfo {
     }
 }
 
+impl crate::rt::SpaceUsed for SourceCodeInfo {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for SourceCodeInfo {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for SourceCodeInfo {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.location, worklist);
+    }
+}
+
 impl crate::Clear for SourceCodeInfo {
     fn clear(&mut self) {
         self.location.clear();
@@ -7722,6 +9234,13 @@ pub mod source_code_info {
             self.leading_comments.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_leading_comments(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_leading_comments();
+            self.set_leading_comments(v);
+            r
+        }
+
         // optional string trailing_comments = 4;
 
         pub fn get_trailing_comments(&self) -> &str {
@@ -7758,6 +9277,13 @@ pub mod source_code_info {
             self.trailing_comments.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_trailing_comments(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_trailing_comments();
+            self.set_trailing_comments(v);
+            r
+        }
+
         pub(in super) fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
             let mut fields = ::std::vec::Vec::new();
             fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -7891,6 +9417,27 @@ pub mod source_code_info {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.path);
+            my_size += crate::rt::space_used_excluding_self(&self.span);
+            my_size += crate::rt::space_used_excluding_self(&self.leading_comments);
+            my_size += crate::rt::space_used_excluding_self(&self.trailing_comments);
+            my_size += crate::rt::space_used_excluding_self(&self.leading_detached_comments);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.path);
+            crate::rt::shrink_to_fit(&mut self.span);
+            crate::rt::shrink_to_fit(&mut self.leading_comments);
+            crate::rt::shrink_to_fit(&mut self.trailing_comments);
+            crate::rt::shrink_to_fit(&mut self.leading_detached_comments);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -7921,6 +9468,23 @@ pub mod source_code_info {
         }
     }
 
+    impl crate::rt::SpaceUsed for Location {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for Location {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for Location {
+        fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+        }
+    }
+
     impl crate::Clear for Location {
         fn clear(&mut self) {
             self.path.clear();
@@ -8036,6 +9600,19 @@ impl crate::Message for GeneratedCodeInfo {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.annotation);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.annotation);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -8062,6 +9639,24 @@ impl crate::Message for GeneratedCodeInfo {
     }
 }
 
+impl crate::rt::SpaceUsed for GeneratedCodeInfo {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for GeneratedCodeInfo {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for GeneratedCodeInfo {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.annotation, worklist);
+    }
+}
+
 impl crate::Clear for GeneratedCodeInfo {
     fn clear(&mut self) {
         self.annotation.clear();
@@ -8151,6 +9746,13 @@ pub mod generated_code_info {
             self.source_file.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_source_file(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_source_file();
+            self.set_source_file(v);
+            r
+        }
+
         // optional int32 begin = 3;
 
         pub fn get_begin(&self) -> i32 {
@@ -8307,6 +9909,25 @@ pub mod generated_code_info {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.path);
+            my_size += crate::rt::space_used_excluding_self(&self.source_file);
+            my_size += crate::rt::space_used_excluding_self(&self.begin);
+            my_size += crate::rt::space_used_excluding_self(&self.end);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.path);
+            crate::rt::shrink_to_fit(&mut self.source_file);
+            crate::rt::shrink_to_fit(&mut self.begin);
+            crate::rt::shrink_to_fit(&mut self.end);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -8336,6 +9957,23 @@ pub mod generated_code_info {
         }
     }
 
+    impl crate::rt::SpaceUsed for Annotation {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for Annotation {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for Annotation {
+        fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+        }
+    }
+
     impl crate::Clear for Annotation {
         fn clear(&mut self) {
             self.path.clear();
@@ -9859,3 +11497,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
@@ -0,0 +1,160 @@
+//! Dropping deeply nested messages without recursion.
+//!
+//! Default `Drop` of a message drops nested messages recursively,
+//! so dropping a very deep tree (e. g. parsed with raised
+//! [recursion limit](crate::CodedInputStream::set_recursion_limit))
+//! may overflow the stack. [`drop_iterative`] instead moves nested messages
+//! to an explicit worklist and drops them one level at a time.
+//!
+//! Generated messages implement `Drop` with [`drop_iterative`] when
+//! `iterative_drop` codegen option is set.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::Hash;
+use std::mem;
+use std::sync::Arc;
+
+use crate::MessageField;
+use crate::SmallVec;
+
+/// Values with nested messages not yet dropped by [`drop_iterative`].
+pub type DropWorklist = Vec<Box<dyn DropIterative>>;
+
+/// Value which can release its nested messages without recursion.
+///
+/// Implemented by generated messages and by containers of messages.
+pub trait DropIterative: 'static {
+    /// Move nested messages of this value to the worklist, so dropping
+    /// this value afterwards does not drop any nested message.
+    ///
+    /// Implementations must not call this function on nested messages
+    /// which may own messages of the same type, otherwise dropping
+    /// would be recursive again.
+    fn take_nested_messages(&mut self, worklist: &mut DropWorklist);
+}
+
+/// Message field which can move its messages to the worklist.
+///
+/// Generated [`DropIterative::take_nested_messages`] calls this
+/// operation for each message field.
+pub trait DetachNestedMessages {
+    /// Move messages of this field (or their nested messages)
+    /// to the worklist.
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist);
+}
+
+impl<M: DropIterative> DetachNestedMessages for MessageField<M> {
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if let Some(m) = self.0.take() {
+            worklist.push(m);
+        }
+    }
+}
+
+impl<M: DropIterative> DetachNestedMessages for Option<Box<M>> {
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if let Some(m) = self.take() {
+            worklist.push(m);
+        }
+    }
+}
+
+impl<M: DropIterative> DetachNestedMessages for Option<Arc<M>> {
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if let Some(m) = self.take() {
+            worklist.push(Box::new(m));
+        }
+    }
+}
+
+impl<M: DropIterative> DetachNestedMessages for Vec<M> {
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if !self.is_empty() {
+            worklist.push(Box::new(mem::take(self)));
+        }
+    }
+}
+
+impl<M: DropIterative, const N: usize> DetachNestedMessages for SmallVec<M, N> {
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if !self.is_empty() {
+            worklist.push(Box::new(mem::take(self)));
+        }
+    }
+}
+
+impl<K, M, S> DetachNestedMessages for HashMap<K, M, S>
+where
+    K: Eq + Hash + 'static,
+    M: DropIterative,
+    S: BuildHasher + Default + 'static,
+{
+    fn detach_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if !self.is_empty() {
+            worklist.push(Box::new(mem::take(self)));
+        }
+    }
+}
+
+/// Shared messages are dropped by their last owner.
+impl<M: DropIterative> DropIterative for Arc<M> {
+    fn take_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        if let Some(m) = Arc::get_mut(self) {
+            m.take_nested_messages(worklist);
+        }
+    }
+}
+
+impl<M: DropIterative> DropIterative for Vec<M> {
+    fn take_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        for m in self {
+            m.take_nested_messages(worklist);
+        }
+    }
+}
+
+impl<M: DropIterative, const N: usize> DropIterative for SmallVec<M, N> {
+    fn take_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        for m in self.iter_mut() {
+            m.take_nested_messages(worklist);
+        }
+    }
+}
+
+impl<K, M, S> DropIterative for HashMap<K, M, S>
+where
+    K: Eq + Hash + 'static,
+    M: DropIterative,
+    S: BuildHasher + 'static,
+{
+    fn take_nested_messages(&mut self, worklist: &mut DropWorklist) {
+        for m in self.values_mut() {
+            m.take_nested_messages(worklist);
+        }
+    }
+}
+
+/// Move messages of a message field to the worklist.
+pub fn detach_nested_messages<F: DetachNestedMessages + ?Sized>(
+    field: &mut F,
+    worklist: &mut DropWorklist,
+) {
+    field.detach_nested_messages(worklist);
+}
+
+/// Drop all nested messages of a message using an explicit worklist
+/// instead of recursion, so stack usage does not depend on message depth.
+///
+/// The message itself is left without nested messages,
+/// other fields are not modified.
+///
+/// Extension fields are dropped as usual.
+pub fn drop_iterative<M: DropIterative + ?Sized>(message: &mut M) {
+    let mut worklist = DropWorklist::new();
+    message.take_nested_messages(&mut worklist);
+    while let Some(mut value) = worklist.pop() {
+        value.take_nested_messages(&mut worklist);
+        // `value` has no nested messages now, so it is dropped without recursion
+    }
+}
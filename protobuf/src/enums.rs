@@ -1,4 +1,7 @@
+use std::cmp;
 use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::marker;
 
 use crate::reflect::runtime_types::RuntimeTypeEnumOrUnknown;
@@ -15,6 +18,40 @@ pub trait ProtobufEnum: Eq + Sized + Copy + 'static + ProtobufValue + fmt::Debug
     /// Return `None` if value is unknown.
     fn from_i32(v: i32) -> Option<Self>;
 
+    /// Name of the enum value as specified in `.proto` file.
+    ///
+    /// ```
+    /// # use protobuf::ProtobufEnum;
+    /// # use protobuf::well_known_types::NullValue;
+    /// assert_eq!("NULL_VALUE", NullValue::NULL_VALUE.name());
+    /// assert_eq!("NULL_VALUE", format!("{}", NullValue::NULL_VALUE));
+    /// ```
+    fn name(&self) -> &'static str {
+        panic!();
+    }
+
+    /// Find enum value by name as specified in `.proto` file.
+    /// Return `None` if name is unknown.
+    fn from_str(name: &str) -> Option<Self> {
+        Self::values().iter().find(|v| v.name() == name).cloned()
+    }
+
+    /// Find enum value by name ignoring ASCII case.
+    ///
+    /// If several names differ only in case, the first one is returned.
+    ///
+    /// ```
+    /// # use protobuf::ProtobufEnum;
+    /// # use protobuf::well_known_types::NullValue;
+    /// assert_eq!(Some(NullValue::NULL_VALUE), NullValue::from_str_ignore_case("null_value"));
+    /// ```
+    fn from_str_ignore_case(name: &str) -> Option<Self> {
+        Self::values()
+            .iter()
+            .find(|v| v.name().eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
     /// Get all enum values for enum type.
     fn values() -> &'static [Self];
 
@@ -30,14 +67,18 @@ pub trait ProtobufEnum: Eq + Sized + Copy + 'static + ProtobufValue + fmt::Debug
         Self::enum_descriptor_static()
     }
 
-    /// Get enum descriptor by type.
+    /// Get enum descriptor by type, enum value is not needed.
+    ///
+    /// # Panics
+    ///
+    /// For enums generated with `lite_runtime` option.
     fn enum_descriptor_static() -> EnumDescriptor {
-        panic!();
+        panic!("enum_descriptor_static is not available for enums generated with lite_runtime");
     }
 }
 
 /// Protobuf enums with possibly unknown values are preserved in this struct.
-#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[derive(Copy, Clone)]
 #[repr(transparent)]
 // TODO: specify <E: ProtobufEnum> when it no longer prevents using const fns
 pub struct ProtobufEnumOrUnknown<E> {
@@ -98,6 +139,35 @@ impl<E: ProtobufEnum> ProtobufEnumOrUnknown<E> {
     }
 }
 
+// Comparison traits are implemented manually (not derived)
+// to not require them for `E`: only `i32` value is compared.
+
+impl<E> PartialEq for ProtobufEnumOrUnknown<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<E> Eq for ProtobufEnumOrUnknown<E> {}
+
+impl<E> PartialOrd for ProtobufEnumOrUnknown<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for ProtobufEnumOrUnknown<E> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<E> Hash for ProtobufEnumOrUnknown<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
 impl<E: ProtobufEnum> From<E> for ProtobufEnumOrUnknown<E> {
     fn from(e: E) -> Self {
         ProtobufEnumOrUnknown::new(e)
@@ -119,6 +189,16 @@ impl<E: ProtobufEnum> fmt::Debug for ProtobufEnumOrUnknown<E> {
     }
 }
 
+/// Enum value name or number if value is unknown.
+impl<E: ProtobufEnum> fmt::Display for ProtobufEnumOrUnknown<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.enum_value() {
+            Ok(e) => f.pad(e.name()),
+            Err(e) => fmt::Display::fmt(&e, f),
+        }
+    }
+}
+
 impl<E: ProtobufEnum + ProtobufValue> ProtobufValue for ProtobufEnumOrUnknown<E> {
     type RuntimeType = RuntimeTypeEnumOrUnknown<E>;
 }
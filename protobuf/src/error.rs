@@ -3,6 +3,7 @@ use std::fmt;
 use std::io;
 use std::str;
 
+use crate::strict::StrictParseError;
 use crate::wire_format::WireType;
 
 /// `Result` alias for `ProtobufError`
@@ -25,6 +26,10 @@ pub enum WireError {
     // not really possible
     LimitOverflow,
     LimitIncrease,
+    GrpcFrameIncorrectCompressedFlag(u8),
+    GrpcFrameTooLarge(u32),
+    GrpcFrameCompressed,
+    OutOfMemory,
 }
 
 impl fmt::Display for WireError {
@@ -43,6 +48,12 @@ impl fmt::Display for WireError {
             WireError::LimitIncrease => {
                 write!(f, "new limit must be not greater than current limit")
             }
+            WireError::GrpcFrameIncorrectCompressedFlag(..) => {
+                write!(f, "incorrect gRPC frame compressed flag")
+            }
+            WireError::GrpcFrameTooLarge(..) => write!(f, "gRPC frame is too large"),
+            WireError::GrpcFrameCompressed => write!(f, "gRPC frame is compressed"),
+            WireError::OutOfMemory => write!(f, "memory allocation failed"),
         }
     }
 }
@@ -57,9 +68,100 @@ pub enum ProtobufError {
     /// Protocol contains a string which is not valid UTF-8 string
     Utf8(str::Utf8Error),
     /// Not all required fields of message set.
-    MessageNotInitialized(String),
+    MessageNotInitialized(MessageNotInitializedError),
+    /// Input violates [strict parsing](crate::strict) rules.
+    StrictParse(StrictParseError),
+    /// Serialized message exceeds `rustproto.max_size` limit.
+    MessageTooLarge(MessageTooLargeError),
+    /// Malformed hex or base64 input of functions like `Message::parse_from_hex`.
+    TextEncoding(TextEncodingError),
+    /// Message passed to or returned from a dynamic handler is of unexpected type.
+    MessageTypeMismatch(MessageTypeMismatchError),
+}
+
+/// Required fields of a message or nested messages are not set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageNotInitializedError {
+    /// Full name of the message type.
+    pub message: String,
+    /// Paths to fields which are not set, like `foo.bar[1].baz`.
+    pub missing_fields: Vec<String>,
+}
+
+impl fmt::Display for MessageNotInitializedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "message `{}` is missing required fields: {}",
+            self.message,
+            self.missing_fields.join(", ")
+        )
+    }
+}
+
+impl Error for MessageNotInitializedError {}
+
+/// Serialized message is larger than `rustproto.max_size` option of its type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTooLargeError {
+    /// Full name of the message type.
+    pub message: String,
+    /// Serialized size, when parsing it is the size of the part read before the error.
+    pub size: u64,
+    /// Limit declared in `.proto` file.
+    pub max_size: u64,
+}
+
+impl fmt::Display for MessageTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "message `{}` size {} exceeds limit {}",
+            self.message, self.size, self.max_size
+        )
+    }
+}
+
+impl Error for MessageTooLargeError {}
+
+/// Input is not valid hex or base64.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEncodingError {
+    /// `hex` or `base64`.
+    pub encoding: &'static str,
+    /// Byte offset of invalid character or input length if input is truncated.
+    pub position: usize,
 }
 
+impl fmt::Display for TextEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid {} at position {}", self.encoding, self.position)
+    }
+}
+
+impl Error for TextEncodingError {}
+
+/// Message is not of the type required by the method descriptor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageTypeMismatchError {
+    /// Full name of the expected message type.
+    pub expected: String,
+    /// Full name of the actual message type.
+    pub actual: String,
+}
+
+impl fmt::Display for MessageTypeMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected message of type `{}`, got `{}`",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for MessageTypeMismatchError {}
+
 impl fmt::Display for ProtobufError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -67,7 +169,11 @@ impl fmt::Display for ProtobufError {
             &ProtobufError::IoError(ref e) => write!(f, "IO error: {}", e),
             &ProtobufError::WireError(ref e) => fmt::Display::fmt(e, f),
             &ProtobufError::Utf8(ref e) => write!(f, "{}", e),
-            &ProtobufError::MessageNotInitialized { .. } => write!(f, "not all message fields set"),
+            &ProtobufError::MessageNotInitialized(ref e) => fmt::Display::fmt(e, f),
+            &ProtobufError::StrictParse(ref e) => fmt::Display::fmt(e, f),
+            &ProtobufError::MessageTooLarge(ref e) => fmt::Display::fmt(e, f),
+            &ProtobufError::TextEncoding(ref e) => fmt::Display::fmt(e, f),
+            &ProtobufError::MessageTypeMismatch(ref e) => fmt::Display::fmt(e, f),
         }
     }
 }
@@ -79,6 +185,10 @@ impl Error for ProtobufError {
             &ProtobufError::Utf8(ref e) => Some(e),
             &ProtobufError::WireError(..) => None,
             &ProtobufError::MessageNotInitialized { .. } => None,
+            &ProtobufError::StrictParse(ref e) => Some(e),
+            &ProtobufError::MessageTooLarge(ref e) => Some(e),
+            &ProtobufError::TextEncoding(ref e) => Some(e),
+            &ProtobufError::MessageTypeMismatch(ref e) => Some(e),
         }
     }
 }
@@ -106,6 +216,20 @@ impl From<ProtobufError> for io::Error {
                 io::ErrorKind::InvalidInput,
                 ProtobufError::MessageNotInitialized(message),
             ),
+            ProtobufError::StrictParse(e) => {
+                io::Error::new(io::ErrorKind::InvalidData, ProtobufError::StrictParse(e))
+            }
+            ProtobufError::MessageTooLarge(e) => io::Error::new(
+                io::ErrorKind::InvalidData,
+                ProtobufError::MessageTooLarge(e),
+            ),
+            ProtobufError::TextEncoding(e) => {
+                io::Error::new(io::ErrorKind::InvalidData, ProtobufError::TextEncoding(e))
+            }
+            ProtobufError::MessageTypeMismatch(e) => io::Error::new(
+                io::ErrorKind::InvalidInput,
+                ProtobufError::MessageTypeMismatch(e),
+            ),
             e => io::Error::new(io::ErrorKind::Other, Box::new(e)),
         }
     }
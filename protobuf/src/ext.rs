@@ -2,12 +2,37 @@
 //!
 //! Extensions are [described in the official protobuf documentation][exts].
 //!
+//! By default extension values are kept in message
+//! [`UnknownFields`](crate::UnknownFields) and decoded on access.
+//! When a message is parsed with an [`ExtensionRegistry`],
+//! registered extensions are decoded during parsing and stored
+//! in typed [`ExtensionFields`] of the message instead.
+//!
 //! [exts]: https://developers.google.com/protocol-buffers/docs/proto#extensions
 
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fmt;
 use std::marker::PhantomData;
+use std::mem;
 
+use crate::clear::Clear;
+use crate::coded_input_stream::CodedInputStream;
+use crate::coded_output_stream::CodedOutputStream;
+use crate::coded_output_stream::WithCodedOutputStream;
+use crate::error::ProtobufResult;
 use crate::message::Message;
 use crate::reflect::types::ProtobufType;
+use crate::reflect::ProtobufValue;
+use crate::rt;
+use crate::shrink_to_fit::ShrinkToFit;
+use crate::space_used::SpaceUsed;
+use crate::unknown::UnknownFields;
+use crate::unknown::UnknownValue;
+use crate::unknown::UnknownValues;
+use crate::wire_format::WireType;
 
 /// Optional ext field
 ///
@@ -33,21 +58,607 @@ pub struct ExtFieldRepeated<M: Message, T: ProtobufType> {
     pub phantom: PhantomData<(M, T)>,
 }
 
+// Implemented manually to avoid `M: Clone, T: Clone` bounds
+impl<M: Message, T: ProtobufType> Clone for ExtFieldOptional<M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Message, T: ProtobufType> Copy for ExtFieldOptional<M, T> {}
+
+impl<M: Message, T: ProtobufType> Clone for ExtFieldRepeated<M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Message, T: ProtobufType> Copy for ExtFieldRepeated<M, T> {}
+
 impl<M: Message, T: ProtobufType> ExtFieldOptional<M, T> {
     /// Get a copy of value from a message.
     ///
-    /// Extension data is stored in [`UnknownFields`](crate::UnknownFields).
+    /// Value is taken from [`ExtensionFields`] if the message was parsed
+    /// with this extension registered, or from
+    /// [`UnknownFields`](crate::UnknownFields) otherwise.
     pub fn get(&self, m: &M) -> Option<T::ProtobufValue> {
+        if let Some(value) = m
+            .get_extension_fields()
+            .and_then(|e| e.get::<ExtensionValueOptional<T>>(self.field_number))
+        {
+            return Some(value.0.clone());
+        }
         m.get_unknown_fields()
             .get(self.field_number)
             .and_then(T::get_from_unknown)
     }
+
+    /// Get a copy of value from a message, or default value if field is not set.
+    pub fn get_or_default(&self, m: &M) -> T::ProtobufValue {
+        self.get(m).unwrap_or_default()
+    }
+
+    /// Check if the extension field is set in a message.
+    pub fn has(&self, m: &M) -> bool {
+        m.get_extension_fields()
+            .map_or(false, |e| e.contains(self.field_number))
+            || m.get_unknown_fields().get(self.field_number).is_some()
+    }
+
+    /// Set the extension field value.
+    ///
+    /// When the message type has no typed extension storage,
+    /// value is encoded into message unknown fields.
+    pub fn set(&self, m: &mut M, value: T::ProtobufValue) {
+        m.mut_unknown_fields().remove(self.field_number);
+        match m.mut_extension_fields() {
+            Some(e) => e.insert(self.field_number, ExtensionValueOptional::<T>(value)),
+            None => {
+                let unknown = value_to_unknown::<T>(self.field_number, &value);
+                m.mut_unknown_fields().add_value(self.field_number, unknown);
+            }
+        }
+    }
+
+    /// Remove the extension field from a message.
+    pub fn clear(&self, m: &mut M) {
+        if let Some(e) = m.mut_extension_fields() {
+            e.remove(self.field_number);
+        }
+        m.mut_unknown_fields().remove(self.field_number);
+    }
 }
 
 impl<M: Message, T: ProtobufType> ExtFieldRepeated<M, T> {
-    /// Get a copy of value from a message (**not implemented**).
-    pub fn get(&self, _m: &M) -> Vec<T::ProtobufValue> {
-        // TODO
-        unimplemented!()
+    /// Get a copy of values from a message.
+    ///
+    /// Values stored in unknown fields are accepted in both packed
+    /// and unpacked encodings.
+    pub fn get(&self, m: &M) -> Vec<T::ProtobufValue> {
+        if let Some(value) = m
+            .get_extension_fields()
+            .and_then(|e| e.get::<ExtensionValueRepeated<T>>(self.field_number))
+        {
+            return value.0.clone();
+        }
+        match m.get_unknown_fields().get(self.field_number) {
+            Some(values) => values_from_unknown::<T>(values),
+            None => Vec::new(),
+        }
+    }
+
+    /// Check if the extension field has at least one value in a message.
+    pub fn has(&self, m: &M) -> bool {
+        m.get_extension_fields()
+            .map_or(false, |e| e.contains(self.field_number))
+            || m.get_unknown_fields().get(self.field_number).is_some()
+    }
+
+    /// Replace extension field values.
+    pub fn set(&self, m: &mut M, values: Vec<T::ProtobufValue>) {
+        self.clear(m);
+        if values.is_empty() {
+            return;
+        }
+        match m.mut_extension_fields() {
+            Some(e) => e.insert(self.field_number, ExtensionValueRepeated::<T>(values)),
+            None => {
+                for value in &values {
+                    let unknown = value_to_unknown::<T>(self.field_number, value);
+                    m.mut_unknown_fields().add_value(self.field_number, unknown);
+                }
+            }
+        }
+    }
+
+    /// Remove all values of the extension field from a message.
+    pub fn clear(&self, m: &mut M) {
+        if let Some(e) = m.mut_extension_fields() {
+            e.remove(self.field_number);
+        }
+        m.mut_unknown_fields().remove(self.field_number);
+    }
+}
+
+/// Encode a single value as it would be stored in unknown fields.
+fn value_to_unknown<T: ProtobufType>(field_number: u32, value: &T::ProtobufValue) -> UnknownValue {
+    // cache nested message sizes
+    T::compute_size(value);
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        T::write_with_cached_size(field_number, value, &mut os).expect("write to vec");
+        os.flush().expect("write to vec");
+    }
+    let mut is = CodedInputStream::from_bytes(&bytes);
+    let (_, wire_type) = is.read_tag_unpack().expect("just written");
+    is.read_unknown(wire_type).expect("just written")
+}
+
+/// Decode all values of a repeated field from unknown values.
+/// Malformed values are skipped.
+fn values_from_unknown<T: ProtobufType>(values: &UnknownValues) -> Vec<T::ProtobufValue> {
+    let mut r = Vec::new();
+    for value in values {
+        let wire_type = value.wire_type();
+        let mut bytes = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut bytes);
+            os.write_unknown_no_tag(value).expect("write to vec");
+            os.flush().expect("write to vec");
+        }
+        let mut is = CodedInputStream::from_bytes(&bytes);
+        if wire_type == T::WIRE_TYPE {
+            if let Ok(v) = T::read(&mut is) {
+                r.push(v);
+            }
+        } else if wire_type == WireType::WireTypeLengthDelimited {
+            let mut packed = Vec::new();
+            if is.read_repeated_packed_into::<T>(&mut packed).is_ok() {
+                r.extend(packed);
+            }
+        }
+    }
+    r
+}
+
+/// Typed value of an extension field stored in [`ExtensionFields`].
+trait ExtensionValue: Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn clone_box(&self) -> Box<dyn ExtensionValue>;
+    fn eq_dyn(&self, other: &dyn ExtensionValue) -> bool;
+    fn fmt_dyn(&self, f: &mut fmt::Formatter) -> fmt::Result;
+    /// Compute size including tags and cache nested message sizes.
+    fn compute_size(&self, field_number: u32) -> u32;
+    fn write_with_cached_size(
+        &self,
+        field_number: u32,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()>;
+}
+
+struct ExtensionValueOptional<T: ProtobufType>(T::ProtobufValue);
+
+struct ExtensionValueRepeated<T: ProtobufType>(Vec<T::ProtobufValue>);
+
+impl<T: ProtobufType> ExtensionValue for ExtensionValueOptional<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ExtensionValue> {
+        Box::new(ExtensionValueOptional::<T>(self.0.clone()))
+    }
+
+    fn eq_dyn(&self, other: &dyn ExtensionValue) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) => T::ProtobufValue::as_ref(&self.0) == T::ProtobufValue::as_ref(&other.0),
+            None => false,
+        }
+    }
+
+    fn fmt_dyn(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+
+    fn compute_size(&self, field_number: u32) -> u32 {
+        rt::tag_size(field_number) + T::compute_size_with_length_delimiter(&self.0)
+    }
+
+    fn write_with_cached_size(
+        &self,
+        field_number: u32,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        T::write_with_cached_size(field_number, &self.0, os)
+    }
+}
+
+impl<T: ProtobufType> ExtensionValue for ExtensionValueRepeated<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn ExtensionValue> {
+        Box::new(ExtensionValueRepeated::<T>(self.0.clone()))
+    }
+
+    fn eq_dyn(&self, other: &dyn ExtensionValue) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            Some(other) => {
+                self.0.len() == other.0.len()
+                    && self
+                        .0
+                        .iter()
+                        .zip(other.0.iter())
+                        .all(|(a, b)| T::ProtobufValue::as_ref(a) == T::ProtobufValue::as_ref(b))
+            }
+            None => false,
+        }
+    }
+
+    fn fmt_dyn(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+
+    fn compute_size(&self, field_number: u32) -> u32 {
+        let mut size = 0;
+        for value in &self.0 {
+            size += rt::tag_size(field_number) + T::compute_size_with_length_delimiter(value);
+        }
+        size
+    }
+
+    fn write_with_cached_size(
+        &self,
+        field_number: u32,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        for value in &self.0 {
+            T::write_with_cached_size(field_number, value, os)?;
+        }
+        Ok(())
+    }
+}
+
+/// Typed storage of extension fields in generated messages.
+///
+/// Generated code adds this field to messages declaring extension ranges.
+/// Fields are populated when message is parsed with an [`ExtensionRegistry`]
+/// or when extension value is set with
+/// [`ExtFieldOptional::set`] or [`ExtFieldRepeated::set`].
+#[derive(Default)]
+pub struct ExtensionFields {
+    // option is needed to make constructor `const` and cheap;
+    // ordered map to make serialization deterministic
+    fields: Option<Box<BTreeMap<u32, Box<dyn ExtensionValue>>>>,
+}
+
+/// Extension values are type-erased, so serialized size
+/// is used as an estimate of heap used by each value.
+impl SpaceUsed for ExtensionFields {
+    fn space_used_excluding_self(&self) -> usize {
+        match &self.fields {
+            Some(fields) => fields
+                .iter()
+                .map(|(&number, value)| {
+                    mem::size_of::<(u32, Box<dyn ExtensionValue>)>()
+                        + mem::size_of_val(&**value)
+                        + value.compute_size(number) as usize
+                })
+                .sum(),
+            None => 0,
+        }
+    }
+}
+
+impl ExtensionFields {
+    /// Empty extension fields
+    pub const fn new() -> ExtensionFields {
+        ExtensionFields { fields: None }
+    }
+
+    /// Check if there are no extension fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.as_ref().map_or(true, |m| m.is_empty())
+    }
+
+    /// Check if the extension field with given number is stored.
+    pub fn contains(&self, field_number: u32) -> bool {
+        self.fields
+            .as_ref()
+            .map_or(false, |m| m.contains_key(&field_number))
+    }
+
+    /// Remove the extension field by number.
+    pub fn remove(&mut self, field_number: u32) {
+        if let Some(fields) = &mut self.fields {
+            fields.remove(&field_number);
+        }
+    }
+
+    fn get<V: ExtensionValue>(&self, field_number: u32) -> Option<&V> {
+        self.fields
+            .as_ref()?
+            .get(&field_number)?
+            .as_any()
+            .downcast_ref::<V>()
+    }
+
+    fn insert<V: ExtensionValue>(&mut self, field_number: u32, value: V) {
+        self.fields
+            .get_or_insert_with(Default::default)
+            .insert(field_number, Box::new(value));
+    }
+
+    fn entry_or_insert_with<V: ExtensionValue, F: FnOnce() -> V>(
+        &mut self,
+        field_number: u32,
+        f: F,
+    ) -> &mut V {
+        let fields = self.fields.get_or_insert_with(Default::default);
+        let replace = match fields.get(&field_number) {
+            Some(value) => value.as_any().downcast_ref::<V>().is_none(),
+            None => true,
+        };
+        if replace {
+            fields.insert(field_number, Box::new(f()));
+        }
+        fields
+            .get_mut(&field_number)
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<V>()
+            .unwrap()
+    }
+
+    /// Compute serialized size and cache nested message sizes.
+    pub(crate) fn compute_size(&self) -> u32 {
+        let mut size = 0;
+        if let Some(fields) = &self.fields {
+            for (&number, value) in &**fields {
+                size += value.compute_size(number);
+            }
+        }
+        size
+    }
+
+    /// Write extension fields, sizes must be cached.
+    pub(crate) fn write_to(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
+        if let Some(fields) = &self.fields {
+            for (&number, value) in &**fields {
+                value.write_with_cached_size(number, os)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Append values of all extension fields to `unknown_fields`
+    /// in the form they have when parsed without a registry.
+    pub(crate) fn append_to_unknown(&self, unknown_fields: &mut UnknownFields) {
+        if let Some(fields) = &self.fields {
+            for (&number, value) in &**fields {
+                let mut bytes = Vec::with_capacity(value.compute_size(number) as usize);
+                bytes
+                    .with_coded_output_stream(|os| value.write_with_cached_size(number, os))
+                    .expect("write to vec");
+                let mut is = CodedInputStream::from_bytes(&bytes);
+                while !is.eof().expect("read from slice") {
+                    let (number, wire_type) = is.read_tag_unpack().expect("valid tag");
+                    let value = is.read_unknown(wire_type).expect("valid value");
+                    unknown_fields.add_value(number, value);
+                }
+            }
+        }
+    }
+}
+
+impl Clone for ExtensionFields {
+    fn clone(&self) -> Self {
+        ExtensionFields {
+            fields: self.fields.as_ref().map(|fields| {
+                Box::new(
+                    fields
+                        .iter()
+                        .map(|(&number, value)| (number, value.clone_box()))
+                        .collect(),
+                )
+            }),
+        }
+    }
+}
+
+impl PartialEq for ExtensionFields {
+    fn eq(&self, other: &ExtensionFields) -> bool {
+        let empty = BTreeMap::new();
+        let a = self.fields.as_ref().map_or(&empty, |m| &**m);
+        let b = other.fields.as_ref().map_or(&empty, |m| &**m);
+        a.len() == b.len()
+            && a.iter()
+                .zip(b.iter())
+                .all(|((na, va), (nb, vb))| na == nb && va.eq_dyn(&**vb))
+    }
+}
+
+impl fmt::Debug for ExtensionFields {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct ValueDebug<'a>(&'a dyn ExtensionValue);
+
+        impl<'a> fmt::Debug for ValueDebug<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt_dyn(f)
+            }
+        }
+
+        let mut map = f.debug_map();
+        if let Some(fields) = &self.fields {
+            for (number, value) in &**fields {
+                map.entry(number, &ValueDebug(&**value));
+            }
+        }
+        map.finish()
+    }
+}
+
+impl Clear for ExtensionFields {
+    fn clear(&mut self) {
+        if let Some(fields) = &mut self.fields {
+            fields.clear();
+        }
+    }
+}
+
+/// Extension values are type-erased, only the map is released if empty.
+impl ShrinkToFit for ExtensionFields {
+    fn shrink_to_fit(&mut self) {
+        if let Some(fields) = &self.fields {
+            if fields.is_empty() {
+                self.fields = None;
+            }
+        }
+    }
+}
+
+/// Read registered extension value from the stream.
+trait ExtensionReader: Send + Sync + 'static {
+    /// Returns `false` if wire type does not match extension type,
+    /// in that case nothing is read.
+    fn read(
+        &self,
+        wire_type: WireType,
+        is: &mut CodedInputStream,
+        extension_fields: &mut ExtensionFields,
+    ) -> ProtobufResult<bool>;
+}
+
+impl<M: Message, T: ProtobufType> ExtensionReader for ExtFieldOptional<M, T> {
+    fn read(
+        &self,
+        wire_type: WireType,
+        is: &mut CodedInputStream,
+        extension_fields: &mut ExtensionFields,
+    ) -> ProtobufResult<bool> {
+        if wire_type != T::WIRE_TYPE {
+            return Ok(false);
+        }
+        let value = T::read(is)?;
+        extension_fields.insert(self.field_number, ExtensionValueOptional::<T>(value));
+        Ok(true)
+    }
+}
+
+impl<M: Message, T: ProtobufType> ExtensionReader for ExtFieldRepeated<M, T> {
+    fn read(
+        &self,
+        wire_type: WireType,
+        is: &mut CodedInputStream,
+        extension_fields: &mut ExtensionFields,
+    ) -> ProtobufResult<bool> {
+        let packed = wire_type == WireType::WireTypeLengthDelimited
+            && T::WIRE_TYPE != WireType::WireTypeLengthDelimited;
+        if wire_type != T::WIRE_TYPE && !packed {
+            return Ok(false);
+        }
+        let value = extension_fields.entry_or_insert_with(self.field_number, || {
+            ExtensionValueRepeated::<T>(Vec::new())
+        });
+        if packed {
+            is.read_repeated_packed_into::<T>(&mut value.0)?;
+        } else {
+            value.0.push(T::read(is)?);
+        }
+        Ok(true)
+    }
+}
+
+/// Set of extensions which are decoded during parsing.
+///
+/// ```
+/// # use protobuf::ext::ExtensionRegistry;
+/// # use protobuf::Message;
+/// # use protobuf::descriptor::MessageOptions;
+/// # fn foo(bytes: &[u8]) -> protobuf::ProtobufResult<()> {
+/// let mut registry = ExtensionRegistry::new();
+/// registry.add_optional(&protobuf::rustproto::exts::expose_fields);
+///
+/// let options = MessageOptions::parse_from_bytes_with_registry(bytes, &registry)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ExtensionRegistry {
+    readers: HashMap<(TypeId, u32), Box<dyn ExtensionReader>>,
+}
+
+impl ExtensionRegistry {
+    /// Create an empty registry.
+    pub fn new() -> ExtensionRegistry {
+        Default::default()
+    }
+
+    /// Register optional extension field.
+    ///
+    /// Extension registered with the same extendee and number is replaced.
+    pub fn add_optional<M: Message, T: ProtobufType>(&mut self, ext: &ExtFieldOptional<M, T>) {
+        self.readers
+            .insert((TypeId::of::<M>(), ext.field_number), Box::new(*ext));
+    }
+
+    /// Register repeated extension field.
+    ///
+    /// Extension registered with the same extendee and number is replaced.
+    pub fn add_repeated<M: Message, T: ProtobufType>(&mut self, ext: &ExtFieldRepeated<M, T>) {
+        self.readers
+            .insert((TypeId::of::<M>(), ext.field_number), Box::new(*ext));
+    }
+
+    /// Check if extension of message `M` with given number is registered.
+    pub fn contains<M: Message>(&self, field_number: u32) -> bool {
+        self.readers
+            .contains_key(&(TypeId::of::<M>(), field_number))
+    }
+
+    /// Number of registered extensions.
+    pub fn len(&self) -> usize {
+        self.readers.len()
+    }
+
+    /// Check if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.readers.is_empty()
+    }
+
+    /// Read a value of registered extension.
+    ///
+    /// Returns `false` when extension is not registered or wire type
+    /// does not match, and nothing is read from the stream.
+    pub(crate) fn read<M: Message>(
+        &self,
+        field_number: u32,
+        wire_type: WireType,
+        is: &mut CodedInputStream,
+        extension_fields: &mut ExtensionFields,
+    ) -> ProtobufResult<bool> {
+        match self.readers.get(&(TypeId::of::<M>(), field_number)) {
+            Some(reader) => reader.read(wire_type, is, extension_fields),
+            None => Ok(false),
+        }
+    }
+}
+
+impl fmt::Debug for ExtensionRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtensionRegistry")
+            .field("len", &self.readers.len())
+            .finish()
     }
 }
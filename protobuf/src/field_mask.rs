@@ -0,0 +1,243 @@
+//! Operations on messages driven by `google.protobuf.FieldMask`.
+//!
+//! Semantics follow `FieldMaskUtil` of Java protobuf runtime.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::error::ProtobufError;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::well_known_types::FieldMask;
+
+/// Options for [`merge`].
+///
+/// Default options merge message fields and append repeated fields.
+#[derive(Debug, Default, Clone)]
+pub struct MergeOptions {
+    /// Replace message fields in destination instead of merging.
+    ///
+    /// When the field is not set in source, it is cleared in destination.
+    pub replace_message_fields: bool,
+    /// Replace repeated and map fields in destination instead of appending.
+    pub replace_repeated_fields: bool,
+    /// Clear non-message singular fields in destination which are not set in source.
+    ///
+    /// By default such fields are set to the source value, which is the default value
+    /// when the field is not set.
+    pub replace_primitive_fields: bool,
+    /// Prevent initializing `MergeOptions` enumerating all field.
+    pub _future_options: (),
+}
+
+/// Field mask does not match message type or fields cannot be merged.
+#[derive(Debug)]
+pub enum FieldMaskError {
+    /// Path refers to a field which does not exist.
+    UnknownField(String),
+    /// Path continues after a field which is not a singular message field.
+    NotMessageField(String),
+    /// Failed to merge message field, e. g. it exceeds `rustproto.max_size`.
+    MergeMessage(String, ProtobufError),
+}
+
+impl fmt::Display for FieldMaskError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldMaskError::UnknownField(path) => write!(f, "unknown field in path `{}`", path),
+            FieldMaskError::NotMessageField(path) => {
+                write!(f, "not a singular message field in path `{}`", path)
+            }
+            FieldMaskError::MergeMessage(name, e) => {
+                write!(f, "failed to merge field `{}`: {}", name, e)
+            }
+        }
+    }
+}
+
+impl Error for FieldMaskError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FieldMaskError::MergeMessage(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Paths of field mask as a tree, leaf means the whole field.
+#[derive(Default, Debug)]
+struct FieldMaskTree {
+    children: BTreeMap<String, FieldMaskTree>,
+}
+
+impl FieldMaskTree {
+    fn build(
+        mask: &FieldMask,
+        descriptor: &MessageDescriptor,
+    ) -> Result<FieldMaskTree, FieldMaskError> {
+        let mut tree = FieldMaskTree::default();
+        for path in &mask.paths {
+            tree.add_path(path, descriptor)?;
+        }
+        Ok(tree)
+    }
+
+    fn add_path(
+        &mut self,
+        path: &str,
+        descriptor: &MessageDescriptor,
+    ) -> Result<(), FieldMaskError> {
+        let mut node = self;
+        let mut descriptor = descriptor.clone();
+        let components: Vec<&str> = path.split('.').collect();
+        for (i, name) in components.iter().enumerate() {
+            let field = descriptor
+                .get_field_by_name(name)
+                .ok_or_else(|| FieldMaskError::UnknownField(path.to_owned()))?;
+            let last = i == components.len() - 1;
+            if !last {
+                descriptor = match field.runtime_field_type() {
+                    RuntimeFieldType::Singular(RuntimeTypeBox::Message(m)) => m,
+                    _ => return Err(FieldMaskError::NotMessageField(path.to_owned())),
+                };
+            }
+
+            let is_new = !node.children.contains_key(*name);
+            let child = node.children.entry((*name).to_owned()).or_default();
+            if !is_new && child.children.is_empty() {
+                // Whole field is already in the mask
+                return Ok(());
+            }
+            if last {
+                child.children.clear();
+            }
+            node = child;
+        }
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        dst: &mut dyn MessageDyn,
+        src: &dyn MessageDyn,
+        options: &MergeOptions,
+    ) -> Result<(), FieldMaskError> {
+        let descriptor = src.descriptor_dyn();
+        for (name, child) in &self.children {
+            // Paths are validated when the tree is built
+            let field = descriptor.get_field_by_name(name).unwrap();
+
+            if !child.children.is_empty() {
+                if !field.has_field(src) && !field.has_field(dst) {
+                    continue;
+                }
+                child.merge(field.mut_message(dst), &*field.get_message(src), options)?;
+                continue;
+            }
+
+            match field.runtime_field_type() {
+                RuntimeFieldType::Map(..) => {
+                    if options.replace_repeated_fields {
+                        field.clear_field(dst);
+                    }
+                    let entries: Vec<_> = field
+                        .get_map(src)
+                        .into_iter()
+                        .map(|(k, v)| (k.to_box(), v.to_box()))
+                        .collect();
+                    let mut map = field.mut_map(dst);
+                    for (k, v) in entries {
+                        map.insert(k, v);
+                    }
+                }
+                RuntimeFieldType::Repeated(..) => {
+                    if options.replace_repeated_fields {
+                        field.clear_field(dst);
+                    }
+                    let values: Vec<_> = field
+                        .get_repeated(src)
+                        .into_iter()
+                        .map(|v| v.to_box())
+                        .collect();
+                    let mut repeated = field.mut_repeated(dst);
+                    for v in values {
+                        repeated.push(v);
+                    }
+                }
+                RuntimeFieldType::Singular(RuntimeTypeBox::Message(..)) => {
+                    if options.replace_message_fields {
+                        match field.get_singular(src) {
+                            Some(v) => field.set_singular_field(dst, v.to_box()),
+                            None => field.clear_field(dst),
+                        }
+                    } else if field.has_field(src) {
+                        field
+                            .mut_message(dst)
+                            .merge_from_message_dyn(&*field.get_message(src))
+                            .map_err(|e| FieldMaskError::MergeMessage(name.clone(), e))?;
+                    }
+                }
+                RuntimeFieldType::Singular(..) => {
+                    // Unset oneof field is not set to default value
+                    // so that other field of the same oneof in destination is preserved
+                    let replace =
+                        options.replace_primitive_fields || field.containing_oneof().is_some();
+                    if field.has_field(src) || !replace {
+                        let value = field.get_singular_field_or_default(src).to_box();
+                        field.set_singular_field(dst, value);
+                    } else {
+                        field.clear_field(dst);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Copy fields listed in the `mask` from `src` to `dst`.
+///
+/// Each path is dot-separated list of field names, like `a.b.c`.
+/// Only the last component of a path may be repeated or map field.
+/// Fields not listed in the mask are not modified.
+///
+/// `dst` is not modified if the mask is not valid for the message type.
+/// If merging a message field fails, `dst` may be partially modified.
+///
+/// ```
+/// # use protobuf::field_mask;
+/// # use protobuf::well_known_types::Duration;
+/// # use protobuf::well_known_types::FieldMask;
+/// let mut src = Duration::new();
+/// src.seconds = 10;
+/// src.nanos = 20;
+/// let mut dst = Duration::new();
+/// let mut mask = FieldMask::new();
+/// mask.paths.push("seconds".to_owned());
+/// field_mask::merge(&mut dst, &src, &mask, &Default::default()).unwrap();
+/// assert_eq!(10, dst.seconds);
+/// assert_eq!(0, dst.nanos);
+/// ```
+///
+/// # Panics
+///
+/// If messages are of different types.
+pub fn merge(
+    dst: &mut dyn MessageDyn,
+    src: &dyn MessageDyn,
+    mask: &FieldMask,
+    options: &MergeOptions,
+) -> Result<(), FieldMaskError> {
+    let descriptor = src.descriptor_dyn();
+    assert!(
+        descriptor == dst.descriptor_dyn(),
+        "cannot merge {} into {}",
+        descriptor.full_name(),
+        dst.descriptor_dyn().full_name()
+    );
+    let tree = FieldMaskTree::build(mask, &descriptor)?;
+    tree.merge(dst, src, options)
+}
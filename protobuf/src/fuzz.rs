@@ -0,0 +1,300 @@
+//! Helpers for fuzz targets (`fuzz` feature).
+//!
+//! Functions panic when an invariant is violated, which is reported by a fuzzer
+//! as a crash, and silently return when input is not valid.
+//! Messages are specified by descriptors, so both generated and dynamic
+//! messages can be fuzzed. A `cargo fuzz` target looks like this:
+//!
+//! ```ignore
+//! #![no_main]
+//! use libfuzzer_sys::fuzz_target;
+//! use protobuf::Message;
+//!
+//! fuzz_target!(|data: &[u8]| {
+//!     protobuf::fuzz::fuzz_all(&my_pb::MyMessage::descriptor_static(), data);
+//! });
+//! ```
+//!
+//! [`mutate_bytes`] can be used as custom mutator (`fuzz_mutator!`) to mutate
+//! input as a message instead of a byte string.
+
+use std::str;
+
+use crate::arbitrary;
+use crate::arbitrary::ArbitraryOptions;
+use crate::arbitrary::ArbitrarySource;
+use crate::arbitrary::Generator;
+use crate::json;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::ReflectEqMode;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::text_format;
+
+fn parse(descriptor: &MessageDescriptor, bytes: &[u8]) -> Option<Box<dyn MessageDyn>> {
+    let mut message = descriptor.new_instance();
+    message.merge_from_bytes_dyn(bytes).ok()?;
+    Some(message)
+}
+
+fn assert_reflect_eq(expected: &dyn MessageDyn, actual: &dyn MessageDyn, what: &str) {
+    assert!(
+        expected.reflect_eq_dyn(actual, &ReflectEqMode::nan_equal()),
+        "{} round trip changed message:\n{:?}\n{:?}",
+        what,
+        text_format::Unredacted(expected),
+        text_format::Unredacted(actual),
+    );
+}
+
+/// Parse binary message, serialize it, and check that parsing serialized
+/// bytes produces the same message, and serializing it produces the same number of bytes.
+pub fn round_trip_bytes(descriptor: &MessageDescriptor, bytes: &[u8]) {
+    let message = match parse(descriptor, bytes) {
+        Some(message) => message,
+        None => return,
+    };
+    // Fails when required fields are not set
+    let serialized = match message.write_to_bytes_dyn() {
+        Ok(serialized) => serialized,
+        Err(_) => return,
+    };
+    let parsed = parse(descriptor, &serialized).expect("parse serialized message");
+    assert_reflect_eq(&*message, &*parsed, "binary");
+    assert_eq!(
+        parsed.get_unknown_fields_dyn(),
+        message.get_unknown_fields_dyn()
+    );
+    let reserialized = parsed
+        .write_to_bytes_dyn()
+        .expect("serialize parsed message");
+    // Map entries may be serialized in different order
+    assert_eq!(serialized.len(), reserialized.len());
+}
+
+/// Generate message from fuzzer input with [`arbitrary`](crate::arbitrary),
+/// print it as JSON, parse it back, and check that printing and parsing
+/// parsed message produces the same message.
+///
+/// First round trip is not always exact: e. g. `Value` without kind
+/// is printed as `null`, so it is parsed as `Value` with `null_value`.
+/// Messages which cannot be printed (e. g. with `Any` of unknown type or
+/// `Timestamp` out of range) are skipped.
+pub fn round_trip_json(descriptor: &MessageDescriptor, bytes: &[u8]) {
+    let message = arbitrary::arbitrary_dynamic(
+        descriptor,
+        &mut ArbitrarySource::new(bytes),
+        &ArbitraryOptions::default(),
+    );
+    let print_options = json::PrintOptions {
+        unredacted: true,
+        ..Default::default()
+    };
+    let print =
+        |message: &dyn MessageDyn| json::print_to_string_with_options(message, &print_options);
+    let parse = |printed: &str| {
+        json::parse_dynamic_from_str(descriptor, printed)
+            .unwrap_or_else(|e| panic!("parse printed JSON {}: {}", printed, e))
+    };
+    let printed = match print(&*message) {
+        Ok(printed) => printed,
+        Err(_) => return,
+    };
+    let parsed = parse(&printed);
+    let reprinted = print(&*parsed).expect("print parsed message");
+    assert_reflect_eq(&*parsed, &*parse(&reprinted), "JSON");
+}
+
+/// Generate message from fuzzer input with [`arbitrary`](crate::arbitrary),
+/// print it as text format and check that it is parsed back to the same message.
+pub fn round_trip_text(descriptor: &MessageDescriptor, bytes: &[u8]) {
+    let message = arbitrary::arbitrary_dynamic(
+        descriptor,
+        &mut ArbitrarySource::new(bytes),
+        &ArbitraryOptions::default(),
+    );
+    let printed = text_format::print_to_string_unredacted(&*message);
+    let mut parsed = descriptor.new_instance();
+    text_format::merge_from_str(&mut *parsed, &printed)
+        .unwrap_or_else(|e| panic!("parse printed text format {}: {}", printed, e));
+    assert_reflect_eq(&*message, &*parsed, "text format");
+}
+
+/// Parse input as JSON, invalid input must be rejected without panic.
+pub fn parse_json(descriptor: &MessageDescriptor, bytes: &[u8]) {
+    if let Ok(text) = str::from_utf8(bytes) {
+        drop(json::parse_dynamic_from_str(descriptor, text));
+    }
+}
+
+/// Parse input as text format, invalid input must be rejected without panic.
+pub fn parse_text(descriptor: &MessageDescriptor, bytes: &[u8]) {
+    if let Ok(text) = str::from_utf8(bytes) {
+        let mut message = descriptor.new_instance();
+        drop(text_format::merge_from_str(&mut *message, text));
+    }
+}
+
+/// Run one of the checks of this module selected by the first byte of input.
+pub fn fuzz_all(descriptor: &MessageDescriptor, bytes: &[u8]) {
+    let (&first, rem) = match bytes.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    match first % 5 {
+        0 => round_trip_bytes(descriptor, rem),
+        1 => round_trip_json(descriptor, rem),
+        2 => round_trip_text(descriptor, rem),
+        3 => parse_json(descriptor, rem),
+        _ => parse_text(descriptor, rem),
+    }
+}
+
+struct Mutator<'s, 'a, 'o> {
+    generator: Generator<'s, 'a, 'o>,
+}
+
+impl<'s, 'a, 'o> Mutator<'s, 'a, 'o> {
+    fn mutate(&mut self, message: &mut dyn MessageDyn, depth: u32) {
+        let descriptor = message.descriptor_dyn();
+        let fields: Vec<FieldDescriptor> = descriptor.fields().collect();
+        if fields.is_empty() {
+            return;
+        }
+        let field = &fields[self.generator.source.up_to(fields.len() - 1)];
+        match self.generator.source.up_to(2) {
+            0 => field.clear_field(message),
+            1 => self.set(message, field, depth),
+            _ => match field.runtime_field_type() {
+                RuntimeFieldType::Singular(RuntimeTypeBox::Message(..))
+                    if field.has_field(message) =>
+                {
+                    self.mutate(field.mut_message(message), depth + 1)
+                }
+                _ => self.set(message, field, depth),
+            },
+        }
+    }
+
+    /// Set new value of singular field, replace or append element of repeated field,
+    /// or insert map entry.
+    fn set(&mut self, message: &mut dyn MessageDyn, field: &FieldDescriptor, depth: u32) {
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => {
+                let value = self.generator.value(&t, depth);
+                field.set_singular_field(message, value);
+            }
+            RuntimeFieldType::Repeated(t) => {
+                let value = self.generator.value(&t, depth);
+                let mut repeated = field.mut_repeated(message);
+                let index = self.generator.source.up_to(repeated.len());
+                if index == repeated.len() {
+                    repeated.push(value);
+                } else {
+                    repeated.set(index, value);
+                }
+            }
+            RuntimeFieldType::Map(k, v) => {
+                let key = self.generator.value(&k, depth);
+                let value = self.generator.value(&v, depth);
+                field.mut_map(message).insert(key, value);
+            }
+        }
+    }
+}
+
+/// Apply a few random structural changes to a message: clear a field,
+/// set a field to new arbitrary value, or mutate a nested message.
+pub fn mutate(
+    message: &mut dyn MessageDyn,
+    source: &mut ArbitrarySource,
+    options: &ArbitraryOptions,
+) {
+    let count = 1 + source.up_to(3);
+    let mut mutator = Mutator {
+        generator: Generator { source, options },
+    };
+    for _ in 0..count {
+        mutator.mutate(message, 0);
+    }
+}
+
+/// Parse binary message (or take empty message if input is not valid),
+/// [`mutate`] it with pseudo-random choices seeded by `seed`, and serialize it.
+///
+/// Result is empty if mutated message is larger than `max_size`.
+pub fn mutate_bytes(
+    descriptor: &MessageDescriptor,
+    bytes: &[u8],
+    seed: u64,
+    max_size: usize,
+) -> Vec<u8> {
+    let mut message = parse(descriptor, bytes).unwrap_or_else(|| descriptor.new_instance());
+    mutate(
+        &mut *message,
+        &mut ArbitrarySource::from_seed(seed),
+        &ArbitraryOptions::default(),
+    );
+    // Partial serialization, so required fields do not need to be set
+    let mut serialized = Vec::new();
+    {
+        let mut os = crate::CodedOutputStream::vec(&mut serialized);
+        message
+            .write_to_dyn(&mut os)
+            .and_then(|()| os.flush())
+            .expect("serialize mutated message");
+    }
+    if serialized.len() > max_size {
+        serialized.clear();
+    }
+    serialized
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::message::Message;
+    use crate::well_known_types::Struct;
+
+    #[test]
+    fn round_trips() {
+        for descriptor in &[
+            FileDescriptorProto::descriptor_static(),
+            Struct::descriptor_static(),
+        ] {
+            for seed in 0..50u64 {
+                let input = mutate_bytes(descriptor, &[], seed, usize::MAX);
+                round_trip_bytes(descriptor, &input);
+                let mut input = Vec::new();
+                let mut source = ArbitrarySource::from_seed(seed);
+                for _ in 0..200 {
+                    input.push(source.u8());
+                }
+                round_trip_json(descriptor, &input);
+                round_trip_text(descriptor, &input);
+                fuzz_all(descriptor, &input);
+            }
+        }
+    }
+
+    #[test]
+    fn mutate_changes_message() {
+        let descriptor = FileDescriptorProto::descriptor_static();
+        let mut bytes = Vec::new();
+        let mut changed = 0;
+        for seed in 0..50 {
+            let mutated = mutate_bytes(&descriptor, &bytes, seed, 1000);
+            if mutated != bytes {
+                changed += 1;
+            }
+            if !mutated.is_empty() {
+                bytes = mutated;
+            }
+        }
+        assert!(changed > 25, "{}", changed);
+        FileDescriptorProto::parse_from_bytes(&bytes).unwrap();
+    }
+}
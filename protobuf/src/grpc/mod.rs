@@ -0,0 +1,318 @@
+//! gRPC length-prefixed message framing.
+//!
+//! Each gRPC message on the wire is prefixed with a 5-byte header:
+//! one byte compressed flag followed by big-endian `u32` message length.
+//!
+//! These functions do not compress or decompress messages, compression
+//! algorithm is negotiated by gRPC headers and is up to the user.
+
+use std::convert::TryFrom;
+
+use crate::coded_output_stream::CodedOutputStream;
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+
+pub mod reflection;
+
+/// Length of the frame header: compressed flag and message length.
+pub const FRAME_HEADER_LEN: usize = 5;
+
+/// Default limit of frame payload length used by [`FrameDecoder`].
+///
+/// Same as default gRPC max receive message length.
+pub const DEFAULT_MAX_FRAME_LEN: u32 = 4 << 20;
+
+/// Decoded frame header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Payload is compressed.
+    pub compressed: bool,
+    /// Payload length in bytes.
+    pub len: u32,
+}
+
+impl FrameHeader {
+    /// Encode the header.
+    pub fn encode(&self) -> [u8; FRAME_HEADER_LEN] {
+        let len = self.len.to_be_bytes();
+        [self.compressed as u8, len[0], len[1], len[2], len[3]]
+    }
+
+    /// Decode the header.
+    pub fn decode(header: &[u8; FRAME_HEADER_LEN]) -> ProtobufResult<FrameHeader> {
+        let compressed = match header[0] {
+            0 => false,
+            1 => true,
+            flag => {
+                return Err(ProtobufError::WireError(
+                    WireError::GrpcFrameIncorrectCompressedFlag(flag),
+                ))
+            }
+        };
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]);
+        Ok(FrameHeader { compressed, len })
+    }
+}
+
+/// Single decoded frame.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Frame {
+    /// Payload is compressed.
+    pub compressed: bool,
+    /// Frame payload: serialized message, possibly compressed.
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    /// Parse the message from uncompressed frame.
+    ///
+    /// Results in error if frame is compressed: decompress [`data`](Frame::data)
+    /// and parse the message with [`Message::parse_from_bytes`] instead.
+    pub fn parse_message<M: Message>(&self) -> ProtobufResult<M> {
+        if self.compressed {
+            return Err(ProtobufError::WireError(WireError::GrpcFrameCompressed));
+        }
+        M::parse_from_bytes(&self.data)
+    }
+}
+
+/// Append frame with given payload to the vec.
+///
+/// # Panics
+///
+/// If payload is longer than `u32::MAX`.
+pub fn write_frame(data: &[u8], compressed: bool, out: &mut Vec<u8>) {
+    let len = u32::try_from(data.len()).expect("gRPC frame is too large");
+    out.reserve(FRAME_HEADER_LEN + data.len());
+    out.extend_from_slice(&FrameHeader { compressed, len }.encode());
+    out.extend_from_slice(data);
+}
+
+/// Serialize the message as uncompressed frame and append it to the vec.
+pub fn write_message_frame(message: &dyn MessageDyn, out: &mut Vec<u8>) -> ProtobufResult<()> {
+    message.check_initialized_dyn()?;
+    let len = message.compute_size_dyn();
+    out.reserve(FRAME_HEADER_LEN + len as usize);
+    out.extend_from_slice(
+        &FrameHeader {
+            compressed: false,
+            len,
+        }
+        .encode(),
+    );
+    let mut os = CodedOutputStream::vec(out);
+    message.write_to_with_cached_sizes_dyn(&mut os)?;
+    os.flush()?;
+    Ok(())
+}
+
+/// Serialize the message as uncompressed frame.
+pub fn write_message_frame_to_bytes(message: &dyn MessageDyn) -> ProtobufResult<Vec<u8>> {
+    let mut v = Vec::new();
+    write_message_frame(message, &mut v)?;
+    Ok(v)
+}
+
+/// Decode single frame from the beginning of the buffer.
+///
+/// Return the frame header and payload, and the number of bytes consumed,
+/// or `None` if buffer does not contain complete frame.
+pub fn read_frame(buf: &[u8]) -> ProtobufResult<Option<(FrameHeader, &[u8], usize)>> {
+    if buf.len() < FRAME_HEADER_LEN {
+        return Ok(None);
+    }
+    let mut header = [0; FRAME_HEADER_LEN];
+    header.copy_from_slice(&buf[..FRAME_HEADER_LEN]);
+    let header = FrameHeader::decode(&header)?;
+    let end = FRAME_HEADER_LEN + header.len as usize;
+    if buf.len() < end {
+        return Ok(None);
+    }
+    Ok(Some((header, &buf[FRAME_HEADER_LEN..end], end)))
+}
+
+/// Accumulate stream data (e. g. HTTP/2 DATA frames) and split it into gRPC frames.
+///
+/// ```
+/// # use protobuf::grpc;
+/// let mut data = Vec::new();
+/// grpc::write_frame(b"abc", false, &mut data);
+///
+/// let mut decoder = grpc::FrameDecoder::new();
+/// decoder.push(&data[..2]);
+/// assert_eq!(None, decoder.next_frame().unwrap());
+/// decoder.push(&data[2..]);
+/// assert_eq!(b"abc", &decoder.next_frame().unwrap().unwrap().data[..]);
+/// assert!(decoder.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct FrameDecoder {
+    buf: Vec<u8>,
+    pos: usize,
+    max_frame_len: u32,
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        FrameDecoder {
+            buf: Vec::new(),
+            pos: 0,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+}
+
+impl FrameDecoder {
+    /// New decoder with limit [`DEFAULT_MAX_FRAME_LEN`].
+    pub fn new() -> FrameDecoder {
+        FrameDecoder::default()
+    }
+
+    /// Set max frame payload length.
+    ///
+    /// Frames with longer payload result in error
+    /// before the payload is buffered.
+    pub fn set_max_frame_len(&mut self, max_frame_len: u32) {
+        self.max_frame_len = max_frame_len;
+    }
+
+    /// Append received data.
+    pub fn push(&mut self, data: &[u8]) {
+        if self.pos != 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Number of buffered bytes not yet returned as frames.
+    pub fn buffered_len(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// No buffered bytes.
+    ///
+    /// At the end of stream, non-empty decoder means truncated frame.
+    pub fn is_empty(&self) -> bool {
+        self.buffered_len() == 0
+    }
+
+    /// Return next complete frame if it is buffered.
+    pub fn next_frame(&mut self) -> ProtobufResult<Option<Frame>> {
+        let rem = &self.buf[self.pos..];
+        if rem.len() >= FRAME_HEADER_LEN {
+            let mut header = [0; FRAME_HEADER_LEN];
+            header.copy_from_slice(&rem[..FRAME_HEADER_LEN]);
+            let header = FrameHeader::decode(&header)?;
+            if header.len > self.max_frame_len {
+                return Err(ProtobufError::WireError(WireError::GrpcFrameTooLarge(
+                    header.len,
+                )));
+            }
+        }
+
+        let (header, data, consumed) = match read_frame(rem)? {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+        let frame = Frame {
+            compressed: header.compressed,
+            data: data.to_owned(),
+        };
+        self.pos += consumed;
+        if self.pos == self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+        }
+        Ok(Some(frame))
+    }
+
+    /// Return next message if complete frame is buffered.
+    ///
+    /// Results in error if frame is compressed.
+    pub fn next_message<M: Message>(&mut self) -> ProtobufResult<Option<M>> {
+        match self.next_frame()? {
+            Some(frame) => frame.parse_message().map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::well_known_types::Duration;
+
+    #[test]
+    fn header() {
+        let header = FrameHeader {
+            compressed: true,
+            len: 0x01020304,
+        };
+        assert_eq!([1, 1, 2, 3, 4], header.encode());
+        assert_eq!(header, FrameHeader::decode(&header.encode()).unwrap());
+        assert!(FrameHeader::decode(&[2, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn decoder_byte_by_byte() {
+        let mut data = Vec::new();
+        write_frame(b"ab", false, &mut data);
+        write_frame(b"", true, &mut data);
+        write_frame(b"cde", false, &mut data);
+
+        let mut decoder = FrameDecoder::new();
+        let mut frames = Vec::new();
+        for b in &data {
+            decoder.push(&[*b]);
+            while let Some(frame) = decoder.next_frame().unwrap() {
+                frames.push(frame);
+            }
+        }
+        assert!(decoder.is_empty());
+        assert_eq!(
+            vec![
+                Frame {
+                    compressed: false,
+                    data: b"ab".to_vec(),
+                },
+                Frame {
+                    compressed: true,
+                    data: Vec::new(),
+                },
+                Frame {
+                    compressed: false,
+                    data: b"cde".to_vec(),
+                },
+            ],
+            frames
+        );
+    }
+
+    #[test]
+    fn decoder_max_frame_len() {
+        let mut data = Vec::new();
+        write_frame(b"abc", false, &mut data);
+
+        let mut decoder = FrameDecoder::new();
+        decoder.set_max_frame_len(2);
+        decoder.push(&data[..FRAME_HEADER_LEN]);
+        assert!(decoder.next_frame().is_err());
+    }
+
+    #[test]
+    fn message_frame() {
+        let mut duration = Duration::new();
+        duration.seconds = 10;
+        let data = write_message_frame_to_bytes(&duration).unwrap();
+        assert_eq!(FRAME_HEADER_LEN + 2, data.len());
+
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&data);
+        assert_eq!(Some(duration), decoder.next_message().unwrap());
+        assert_eq!(None, decoder.next_message::<Duration>().unwrap());
+    }
+}
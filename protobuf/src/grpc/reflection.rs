@@ -0,0 +1,520 @@
+//! Data provider for gRPC server reflection.
+//!
+//! [`ServerReflection`] answers queries of `grpc.reflection.v1.ServerReflection`
+//! (same as `v1alpha`) service from registered file descriptors.
+//! Transport is up to the user: pass each request of the bidirectional stream
+//! to [`handle`](ServerReflection::handle) as [`ReflectionRequest`],
+//! or, when request and response messages are not generated,
+//! serialized `ServerReflectionRequest` to
+//! [`handle_bytes`](ServerReflection::handle_bytes).
+//!
+//! ```
+//! # use protobuf::grpc::reflection::*;
+//! let mut reflection = ServerReflection::new();
+//! reflection.add_file(&protobuf::well_known_types::file_descriptors::api());
+//!
+//! let request = ReflectionRequest::FileContainingSymbol("google.protobuf.Api".to_owned());
+//! match reflection.handle(&request) {
+//!     ReflectionResponse::FileDescriptors(files) => assert_eq!(4, files.len()),
+//!     r => panic!("{:?}", r),
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use crate::coded_input_stream::CodedInputStream;
+use crate::coded_output_stream::CodedOutputStream;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::error::ProtobufResult;
+use crate::message::Message;
+use crate::reflect::FileDescriptor;
+use crate::wire_format::WireType;
+
+/// gRPC status code `INVALID_ARGUMENT`.
+pub const STATUS_INVALID_ARGUMENT: i32 = 3;
+/// gRPC status code `NOT_FOUND`.
+pub const STATUS_NOT_FOUND: i32 = 5;
+
+/// Query of the reflection service, `message_request` of `ServerReflectionRequest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectionRequest {
+    /// Find file by name like `foo/bar.proto`.
+    FileByFilename(String),
+    /// Find file declaring a message, enum, service, method or extension
+    /// by fully-qualified name like `pkg.Service.Method`.
+    FileContainingSymbol(String),
+    /// Find file declaring an extension.
+    FileContainingExtension {
+        /// Fully-qualified name of extended message.
+        containing_type: String,
+        /// Extension field number.
+        extension_number: i32,
+    },
+    /// Numbers of all known extensions of the message type.
+    AllExtensionNumbersOfType(String),
+    /// List names of services.
+    ListServices,
+}
+
+/// Answer of the reflection service, `message_response` of `ServerReflectionResponse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReflectionResponse {
+    /// Serialized `FileDescriptorProto`s: found file followed by all its dependencies.
+    FileDescriptors(Vec<Vec<u8>>),
+    /// Extension numbers of a message type.
+    ExtensionNumbers {
+        /// Fully-qualified name of the message type.
+        base_type_name: String,
+        /// Numbers of extensions in ascending order.
+        extension_numbers: Vec<i32>,
+    },
+    /// Fully-qualified names of services.
+    Services(Vec<String>),
+    /// Query cannot be answered.
+    Error {
+        /// gRPC status code like [`STATUS_NOT_FOUND`].
+        error_code: i32,
+        /// Explanation.
+        error_message: String,
+    },
+}
+
+/// Index of files for answering gRPC reflection queries.
+#[derive(Debug, Default)]
+pub struct ServerReflection {
+    files: HashMap<String, FileDescriptor>,
+    /// Fully-qualified symbol name to file name.
+    symbols: HashMap<String, String>,
+    /// Extendee fully-qualified name to extension number to file name.
+    extensions: HashMap<String, BTreeMap<i32, String>>,
+    services: Vec<String>,
+}
+
+fn join_name(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", scope, name)
+    }
+}
+
+impl ServerReflection {
+    /// Empty index.
+    pub fn new() -> ServerReflection {
+        ServerReflection::default()
+    }
+
+    /// Register the file and its dependencies.
+    ///
+    /// Services of this file (but not of dependencies) are returned
+    /// by [`ReflectionRequest::ListServices`].
+    pub fn add_file(&mut self, file: &FileDescriptor) {
+        for service in &file.proto().service {
+            let name = join_name(file.proto().get_package(), service.get_name());
+            if !self.services.contains(&name) {
+                self.services.push(name);
+            }
+        }
+        for f in file.all_files() {
+            self.index_file(f);
+        }
+    }
+
+    fn index_file(&mut self, file: &FileDescriptor) {
+        let name = file.proto().get_name().to_owned();
+        if self.files.contains_key(&name) {
+            return;
+        }
+        let package = file.proto().get_package();
+        for message in &file.proto().message_type {
+            self.index_message(&name, package, message);
+        }
+        for e in &file.proto().enum_type {
+            self.add_symbol(&name, join_name(package, e.get_name()));
+        }
+        for service in &file.proto().service {
+            let service_name = join_name(package, service.get_name());
+            for method in &service.method {
+                self.add_symbol(&name, join_name(&service_name, method.get_name()));
+            }
+            self.add_symbol(&name, service_name);
+        }
+        self.index_extensions(&name, package, &file.proto().extension);
+        self.files.insert(name, file.clone());
+    }
+
+    fn index_message(&mut self, file: &str, scope: &str, message: &DescriptorProto) {
+        let full_name = join_name(scope, message.get_name());
+        for nested in &message.nested_type {
+            self.index_message(file, &full_name, nested);
+        }
+        for e in &message.enum_type {
+            self.add_symbol(file, join_name(&full_name, e.get_name()));
+        }
+        self.index_extensions(file, &full_name, &message.extension);
+        self.add_symbol(file, full_name);
+    }
+
+    fn index_extensions(&mut self, file: &str, scope: &str, extensions: &[FieldDescriptorProto]) {
+        for ext in extensions {
+            self.add_symbol(file, join_name(scope, ext.get_name()));
+            let extendee = ext.get_extendee();
+            let extendee = extendee.strip_prefix('.').unwrap_or(extendee);
+            self.extensions
+                .entry(extendee.to_owned())
+                .or_default()
+                .insert(ext.get_number(), file.to_owned());
+        }
+    }
+
+    fn add_symbol(&mut self, file: &str, symbol: String) {
+        self.symbols
+            .entry(symbol)
+            .or_insert_with(|| file.to_owned());
+    }
+
+    /// File with all its dependencies, file first.
+    fn file_response(&self, file: &FileDescriptor) -> ReflectionResponse {
+        let mut set = file.file_descriptor_set().file;
+        // File is the last in the set, dependencies precede it
+        set.rotate_right(1);
+        let files = set
+            .iter()
+            .map(|f| f.write_to_bytes().expect("serialize file descriptor"))
+            .collect();
+        ReflectionResponse::FileDescriptors(files)
+    }
+
+    fn not_found(what: String) -> ReflectionResponse {
+        ReflectionResponse::Error {
+            error_code: STATUS_NOT_FOUND,
+            error_message: format!("{} not found", what),
+        }
+    }
+
+    /// Answer the query.
+    pub fn handle(&self, request: &ReflectionRequest) -> ReflectionResponse {
+        match request {
+            ReflectionRequest::FileByFilename(name) => match self.files.get(name) {
+                Some(file) => self.file_response(file),
+                None => Self::not_found(format!("file `{}`", name)),
+            },
+            ReflectionRequest::FileContainingSymbol(symbol) => {
+                let symbol = symbol.strip_prefix('.').unwrap_or(symbol);
+                match self.symbols.get(symbol) {
+                    Some(file) => self.file_response(&self.files[file]),
+                    None => Self::not_found(format!("symbol `{}`", symbol)),
+                }
+            }
+            ReflectionRequest::FileContainingExtension {
+                containing_type,
+                extension_number,
+            } => {
+                let containing_type = containing_type.strip_prefix('.').unwrap_or(containing_type);
+                match self
+                    .extensions
+                    .get(containing_type)
+                    .and_then(|e| e.get(extension_number))
+                {
+                    Some(file) => self.file_response(&self.files[file]),
+                    None => Self::not_found(format!(
+                        "extension {} of `{}`",
+                        extension_number, containing_type
+                    )),
+                }
+            }
+            ReflectionRequest::AllExtensionNumbersOfType(base_type_name) => {
+                let base_type_name = base_type_name.strip_prefix('.').unwrap_or(base_type_name);
+                if !self.symbols.contains_key(base_type_name) {
+                    return Self::not_found(format!("type `{}`", base_type_name));
+                }
+                ReflectionResponse::ExtensionNumbers {
+                    base_type_name: base_type_name.to_owned(),
+                    extension_numbers: self
+                        .extensions
+                        .get(base_type_name)
+                        .map(|e| e.keys().cloned().collect())
+                        .unwrap_or_default(),
+                }
+            }
+            ReflectionRequest::ListServices => ReflectionResponse::Services(self.services.clone()),
+        }
+    }
+
+    /// Answer serialized `ServerReflectionRequest` with serialized `ServerReflectionResponse`.
+    ///
+    /// Error is returned only when request cannot be parsed,
+    /// requests without query get error response.
+    pub fn handle_bytes(&self, request: &[u8]) -> ProtobufResult<Vec<u8>> {
+        let (host, query) = parse_request(request)?;
+        let response = match query {
+            Some(query) => self.handle(&query),
+            None => ReflectionResponse::Error {
+                error_code: STATUS_INVALID_ARGUMENT,
+                error_message: "request is empty".to_owned(),
+            },
+        };
+
+        let mut bytes = Vec::new();
+        {
+            let mut os = CodedOutputStream::vec(&mut bytes);
+            if !host.is_empty() {
+                os.write_string(1, &host)?;
+            }
+            os.write_bytes(2, request)?;
+            match response {
+                ReflectionResponse::FileDescriptors(files) => {
+                    let nested = encode(|os| {
+                        for f in &files {
+                            os.write_bytes(1, f)?;
+                        }
+                        Ok(())
+                    })?;
+                    os.write_bytes(4, &nested)?;
+                }
+                ReflectionResponse::ExtensionNumbers {
+                    base_type_name,
+                    extension_numbers,
+                } => {
+                    let nested = encode(|os| {
+                        os.write_string(1, &base_type_name)?;
+                        for &n in &extension_numbers {
+                            os.write_int32(2, n)?;
+                        }
+                        Ok(())
+                    })?;
+                    os.write_bytes(5, &nested)?;
+                }
+                ReflectionResponse::Services(services) => {
+                    let nested = encode(|os| {
+                        for s in &services {
+                            let service = encode(|os| os.write_string(1, s))?;
+                            os.write_bytes(1, &service)?;
+                        }
+                        Ok(())
+                    })?;
+                    os.write_bytes(6, &nested)?;
+                }
+                ReflectionResponse::Error {
+                    error_code,
+                    error_message,
+                } => {
+                    let nested = encode(|os| {
+                        os.write_int32(1, error_code)?;
+                        os.write_string(2, &error_message)
+                    })?;
+                    os.write_bytes(7, &nested)?;
+                }
+            }
+            os.flush()?;
+        }
+        Ok(bytes)
+    }
+}
+
+fn encode<F>(f: F) -> ProtobufResult<Vec<u8>>
+where
+    F: FnOnce(&mut CodedOutputStream) -> ProtobufResult<()>,
+{
+    let mut bytes = Vec::new();
+    {
+        let mut os = CodedOutputStream::vec(&mut bytes);
+        f(&mut os)?;
+        os.flush()?;
+    }
+    Ok(bytes)
+}
+
+/// Parse `ServerReflectionRequest` into host and query.
+fn parse_request(bytes: &[u8]) -> ProtobufResult<(String, Option<ReflectionRequest>)> {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let mut host = String::new();
+    let mut query = None;
+    while !is.eof()? {
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        match (field_number, wire_type) {
+            (1, WireType::WireTypeLengthDelimited) => host = is.read_string()?,
+            (3, WireType::WireTypeLengthDelimited) => {
+                query = Some(ReflectionRequest::FileByFilename(is.read_string()?))
+            }
+            (4, WireType::WireTypeLengthDelimited) => {
+                query = Some(ReflectionRequest::FileContainingSymbol(is.read_string()?))
+            }
+            (5, WireType::WireTypeLengthDelimited) => {
+                let nested = is.read_bytes()?;
+                let mut is = CodedInputStream::from_bytes(&nested);
+                let mut containing_type = String::new();
+                let mut extension_number = 0;
+                while !is.eof()? {
+                    let (field_number, wire_type) = is.read_tag_unpack()?;
+                    match (field_number, wire_type) {
+                        (1, WireType::WireTypeLengthDelimited) => {
+                            containing_type = is.read_string()?
+                        }
+                        (2, WireType::WireTypeVarint) => extension_number = is.read_int32()?,
+                        _ => is.skip_field(wire_type)?,
+                    }
+                }
+                query = Some(ReflectionRequest::FileContainingExtension {
+                    containing_type,
+                    extension_number,
+                });
+            }
+            (6, WireType::WireTypeLengthDelimited) => {
+                query = Some(ReflectionRequest::AllExtensionNumbersOfType(
+                    is.read_string()?,
+                ))
+            }
+            (7, WireType::WireTypeLengthDelimited) => {
+                is.read_string()?;
+                query = Some(ReflectionRequest::ListServices);
+            }
+            _ => is.skip_field(wire_type)?,
+        }
+    }
+    Ok((host, query))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::file_descriptors;
+    use crate::well_known_types::Empty;
+
+    fn reflection() -> ServerReflection {
+        let mut reflection = ServerReflection::new();
+        reflection.add_file(&file_descriptors::api());
+        reflection.add_file(&descriptor::file_descriptor());
+        reflection
+    }
+
+    fn file_names(response: ReflectionResponse) -> Vec<String> {
+        match response {
+            ReflectionResponse::FileDescriptors(files) => files
+                .iter()
+                .map(|f| {
+                    FileDescriptorProto::parse_from_bytes(f)
+                        .unwrap()
+                        .get_name()
+                        .to_owned()
+                })
+                .collect(),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn file_by_name_and_symbol() {
+        let reflection = reflection();
+        let names = file_names(reflection.handle(&ReflectionRequest::FileByFilename(
+            "google/protobuf/type.proto".to_owned(),
+        )));
+        assert_eq!(
+            vec![
+                "google/protobuf/type.proto",
+                "google/protobuf/any.proto",
+                "google/protobuf/source_context.proto",
+            ],
+            names
+        );
+
+        for symbol in &[
+            "google.protobuf.Field.Kind",
+            ".google.protobuf.Mixin",
+            "google.protobuf.SourceContext",
+        ] {
+            let names = file_names(
+                reflection.handle(&ReflectionRequest::FileContainingSymbol(symbol.to_string())),
+            );
+            assert!(names[0].ends_with(".proto"), "{}", symbol);
+        }
+
+        match reflection.handle(&ReflectionRequest::FileContainingSymbol(
+            "google.protobuf.Nope".to_owned(),
+        )) {
+            ReflectionResponse::Error { error_code, .. } => {
+                assert_eq!(STATUS_NOT_FOUND, error_code)
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn extensions_and_services() {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("ext.proto".to_owned());
+        file.set_package("ext".to_owned());
+        file.dependency
+            .push("google/protobuf/descriptor.proto".to_owned());
+        for &(name, number) in &[("b", 20001), ("a", 20000)] {
+            let mut ext = FieldDescriptorProto::new();
+            ext.set_name(name.to_owned());
+            ext.set_number(number);
+            ext.set_extendee(".google.protobuf.FileOptions".to_owned());
+            file.extension.push(ext);
+        }
+        let mut service = crate::descriptor::ServiceDescriptorProto::new();
+        service.set_name("Svc".to_owned());
+        file.service.push(service);
+        let file = FileDescriptor::new_dynamic(file, vec![descriptor::file_descriptor()]);
+
+        let mut reflection = ServerReflection::new();
+        reflection.add_file(&file);
+
+        assert_eq!(
+            ReflectionResponse::ExtensionNumbers {
+                base_type_name: "google.protobuf.FileOptions".to_owned(),
+                extension_numbers: vec![20000, 20001],
+            },
+            reflection.handle(&ReflectionRequest::AllExtensionNumbersOfType(
+                "google.protobuf.FileOptions".to_owned()
+            ))
+        );
+        assert_eq!(
+            vec!["ext.proto", "google/protobuf/descriptor.proto"],
+            file_names(
+                reflection.handle(&ReflectionRequest::FileContainingExtension {
+                    containing_type: "google.protobuf.FileOptions".to_owned(),
+                    extension_number: 20001,
+                })
+            )
+        );
+        assert_eq!(
+            ReflectionResponse::Services(vec!["ext.Svc".to_owned()]),
+            reflection.handle(&ReflectionRequest::ListServices)
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        let reflection = reflection();
+        // host = "h", list_services = "*"
+        let request = b"\x0a\x01h\x3a\x01*";
+        let response = Empty::parse_from_bytes(&reflection.handle_bytes(request).unwrap()).unwrap();
+        let field = |number| {
+            response
+                .get_unknown_fields()
+                .get(number)
+                .unwrap()
+                .length_delimited[0]
+                .clone()
+        };
+        assert_eq!(b"h", &field(1)[..]);
+        assert_eq!(&request[..], &field(2)[..]);
+        // no services in files added
+        assert_eq!(b"", &field(6)[..]);
+
+        // file_containing_symbol = "google.protobuf.Empty" is not registered
+        let mut request = vec![0x22, 21];
+        request.extend_from_slice(b"google.protobuf.Empty");
+        let response =
+            Empty::parse_from_bytes(&reflection.handle_bytes(&request).unwrap()).unwrap();
+        assert!(response.get_unknown_fields().get(7).is_some());
+
+        assert!(reflection.handle_bytes(b"\x22\x05ab").is_err());
+    }
+}
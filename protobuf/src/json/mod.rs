@@ -1,12 +1,13 @@
 //! JSON printer and parser which tries to follow
 //! [protobuf conventions](https://developers.google.com/protocol-buffers/docs/proto3#json)
 
-mod base64;
+pub(crate) mod base64;
 mod float;
 mod json_name;
 mod parse;
 mod print;
 mod rfc_3339;
+mod schema;
 mod well_known_wrapper;
 
 #[doc(hidden)]
@@ -23,3 +24,5 @@ pub use self::print::print_to_string;
 pub use self::print::print_to_string_with_options;
 pub use self::print::PrintError;
 pub use self::print::PrintOptions;
+pub use self::schema::json_schema;
+pub use self::schema::JSON_SCHEMA_DIALECT;
@@ -7,13 +7,17 @@ use std::fmt;
 
 use super::base64;
 
+use crate::clear::Clear;
 use crate::enums::ProtobufEnum;
+use crate::error::ProtobufError;
 use crate::json::base64::FromBase64Error;
+use crate::json::print::generated_special_json_type;
+use crate::json::print::has_special_json_representation;
 use crate::message::Message;
 use crate::reflect::EnumDescriptor;
-use crate::reflect::EnumValueDescriptor;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::MessageDescriptor;
+use crate::reflect::MessageFactory;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
@@ -55,10 +59,10 @@ enum ParseErrorWithoutLocInner {
     TokenizerError(TokenizerError),
     UnknownFieldName(String),
     UnknownEnumVariantName(String),
-    UnknownEnumVariantNumber(i32),
     FromBase64Error(FromBase64Error),
     IncorrectStrLit(LexerError),
     IncorrectDuration,
+    IncorrectFieldMask,
     Rfc3339(rfc_3339::Rfc3339ParseError),
     ParseIntError(ParseIntError),
     ParseFloatError(ParseFloatError),
@@ -66,8 +70,13 @@ enum ParseErrorWithoutLocInner {
     ExpectingStrOrInt,
     ExpectingNumber,
     UnexpectedToken,
-    AnyParsingIsNotImplemented,
+    AnyTypeUrlIsMissing,
+    UnknownAnyType(String),
     MessageNotInitialized,
+    OverRecursionLimit,
+    TooManyValueNodes,
+    DynamicWellKnownType(String, ProtobufError),
+    AnyValue(String, ProtobufError),
 }
 
 /// JSON parse error.
@@ -84,12 +93,10 @@ impl fmt::Display for ParseErrorWithoutLoc {
             ParseErrorWithoutLocInner::UnknownEnumVariantName(n) => {
                 write!(f, "unknown enum variant name: {}", n)
             }
-            ParseErrorWithoutLocInner::UnknownEnumVariantNumber(n) => {
-                write!(f, "unknown enum value: {}", n)
-            }
             ParseErrorWithoutLocInner::FromBase64Error(e) => write!(f, "{}", e),
             ParseErrorWithoutLocInner::IncorrectStrLit(e) => write!(f, "{}", e),
             ParseErrorWithoutLocInner::IncorrectDuration => write!(f, "incorrect duration"),
+            ParseErrorWithoutLocInner::IncorrectFieldMask => write!(f, "incorrect field mask"),
             ParseErrorWithoutLocInner::Rfc3339(e) => write!(f, "RFC3339 parse error: {}", e),
             ParseErrorWithoutLocInner::ParseIntError(e) => write!(f, "{}", e),
             ParseErrorWithoutLocInner::ParseFloatError(e) => write!(f, "{}", e),
@@ -99,12 +106,25 @@ impl fmt::Display for ParseErrorWithoutLoc {
             }
             ParseErrorWithoutLocInner::ExpectingNumber => write!(f, "expecting number"),
             ParseErrorWithoutLocInner::UnexpectedToken => write!(f, "unexpected token"),
-            ParseErrorWithoutLocInner::AnyParsingIsNotImplemented => {
-                write!(f, "Any parsing is not implemented")
+            ParseErrorWithoutLocInner::AnyTypeUrlIsMissing => {
+                write!(f, "Any object has no `@type` member")
+            }
+            ParseErrorWithoutLocInner::UnknownAnyType(t) => {
+                write!(f, "Any type is not registered in MessageFactory: {}", t)
             }
             ParseErrorWithoutLocInner::MessageNotInitialized => {
                 write!(f, "Message not initialized")
             }
+            ParseErrorWithoutLocInner::OverRecursionLimit => write!(f, "over recursion limit"),
+            ParseErrorWithoutLocInner::TooManyValueNodes => {
+                write!(f, "too many google.protobuf.Value nodes")
+            }
+            ParseErrorWithoutLocInner::DynamicWellKnownType(t, e) => {
+                write!(f, "failed to convert dynamic {}: {}", t, e)
+            }
+            ParseErrorWithoutLocInner::AnyValue(t, e) => {
+                write!(f, "failed to encode Any {}: {}", t, e)
+            }
         }
     }
 }
@@ -156,6 +176,9 @@ impl fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// Member of JSON object with `Any` type URL.
+const ANY_TYPE_MEMBER: &str = "@type";
+
 type ParseResultWithoutLoc<A> = Result<A, ParseErrorWithoutLoc>;
 type ParseResult<A> = Result<A, ParseError>;
 
@@ -163,6 +186,10 @@ type ParseResult<A> = Result<A, ParseError>;
 struct Parser<'a> {
     tokenizer: Tokenizer<'a>,
     parse_options: ParseOptions,
+    // Nesting level of messages, `Value` objects and skipped JSON values
+    recursion_level: u32,
+    // Number of parsed `google.protobuf.Value` objects
+    value_nodes: usize,
 }
 
 trait FromJsonNumber: PartialEq + Sized {
@@ -393,26 +420,18 @@ impl<'a> Parser<'a> {
         Ok(base64::decode(s)?)
     }
 
-    fn read_enum(
-        &mut self,
-        descriptor: &EnumDescriptor,
-    ) -> ParseResultWithoutLoc<EnumValueDescriptor> {
+    /// `None` if the name is unknown and unknown values are ignored.
+    fn read_enum(&mut self, descriptor: &EnumDescriptor) -> ParseResultWithoutLoc<Option<i32>> {
         if descriptor.is::<NullValue>() {
-            return Ok(self.read_wk_null_value()?.descriptor());
+            return Ok(Some(self.read_wk_null_value()?.value()));
         }
 
         if self.tokenizer.lookahead_is_str_lit()? {
             let name = self.read_string()?;
             self.parse_enum(name, descriptor)
         } else if self.tokenizer.lookahead_is_json_number()? {
-            let number = self.read_i32()?;
-            match descriptor.get_value_by_number(number) {
-                Some(v) => Ok(v),
-                // TODO: EnumValueOrUnknown
-                None => Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::UnknownEnumVariantNumber(number),
-                )),
-            }
+            // Number may be a value unknown to the enum type
+            Ok(Some(self.read_i32()?))
         } else {
             Err(ParseErrorWithoutLoc(
                 ParseErrorWithoutLocInner::ExpectingStrOrInt,
@@ -424,14 +443,21 @@ impl<'a> Parser<'a> {
         &self,
         name: String,
         descriptor: &EnumDescriptor,
-    ) -> ParseResultWithoutLoc<EnumValueDescriptor> {
+    ) -> ParseResultWithoutLoc<Option<i32>> {
         // TODO: can map key be int
-        match descriptor.get_value_by_name(&name) {
-            Some(v) => Ok(v),
-            None => Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::UnknownEnumVariantName(name),
-            )),
+        if let Some(v) = descriptor.get_value_by_name(&name) {
+            return Ok(Some(v.value()));
+        }
+        // Some producers quote numbers
+        if let Ok(v) = name.parse::<i32>() {
+            return Ok(Some(v));
         }
+        if self.parse_options.ignore_unknown_enum_values {
+            return Ok(None);
+        }
+        Err(ParseErrorWithoutLoc(
+            ParseErrorWithoutLocInner::UnknownEnumVariantName(name),
+        ))
     }
 
     fn read_wk_null_value(&mut self) -> ParseResultWithoutLoc<NullValue> {
@@ -448,8 +474,14 @@ impl<'a> Parser<'a> {
         Ok(m)
     }
 
-    fn read_value(&mut self, t: &RuntimeTypeBox) -> ParseResultWithoutLoc<ReflectValueBox> {
-        match t {
+    /// `None` if the value is an ignored unknown enum value.
+    fn read_value(&mut self, t: &RuntimeTypeBox) -> ParseResultWithoutLoc<Option<ReflectValueBox>> {
+        if let RuntimeTypeBox::Enum(e) = t {
+            return Ok(self
+                .read_enum(&e)?
+                .map(|v| ReflectValueBox::Enum(e.clone(), v)));
+        }
+        let value = match t {
             RuntimeTypeBox::I32 => self.read_i32().map(ReflectValueBox::from),
             RuntimeTypeBox::I64 => self.read_i64().map(ReflectValueBox::from),
             RuntimeTypeBox::U32 => self.read_u32().map(ReflectValueBox::from),
@@ -459,9 +491,10 @@ impl<'a> Parser<'a> {
             RuntimeTypeBox::Bool => self.read_bool().map(ReflectValueBox::from),
             RuntimeTypeBox::String => self.read_string().map(ReflectValueBox::from),
             RuntimeTypeBox::VecU8 => self.read_bytes().map(ReflectValueBox::from),
-            RuntimeTypeBox::Enum(e) => self.read_enum(&e).map(ReflectValueBox::from),
+            RuntimeTypeBox::Enum(..) => unreachable!(),
             RuntimeTypeBox::Message(m) => self.read_message(&m).map(ReflectValueBox::from),
-        }
+        }?;
+        Ok(Some(value))
     }
 
     fn merge_singular_field(
@@ -470,7 +503,9 @@ impl<'a> Parser<'a> {
         field: &FieldDescriptor,
         t: &RuntimeTypeBox,
     ) -> ParseResultWithoutLoc<()> {
-        field.set_singular_field(message, self.read_value(t)?);
+        if let Some(value) = self.read_value(t)? {
+            field.set_singular_field(message, value);
+        }
         Ok(())
     }
 
@@ -507,7 +542,9 @@ impl<'a> Parser<'a> {
         repeated.clear();
 
         self.read_list(|s| {
-            repeated.push(s.read_value(t)?);
+            if let Some(value) = s.read_value(t)? {
+                repeated.push(value);
+            }
             Ok(())
         })
     }
@@ -581,8 +618,9 @@ impl<'a> Parser<'a> {
         self.read_map(
             |ss, s| ss.parse_key(s, kt),
             |s, k| {
-                let v = s.read_value(vt)?;
-                map.insert(k, v);
+                if let Some(v) = s.read_value(vt)? {
+                    map.insert(k, v);
+                }
                 Ok(())
             },
         )
@@ -602,6 +640,10 @@ impl<'a> Parser<'a> {
     }
 
     fn skip_json_value(&mut self) -> ParseResultWithoutLoc<()> {
+        self.with_recursion(|s| s.skip_json_value_impl())
+    }
+
+    fn skip_json_value_impl(&mut self) -> ParseResultWithoutLoc<()> {
         if self
             .tokenizer
             .next_ident_if_in(&["true", "false", "null"])?
@@ -635,7 +677,29 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn with_recursion<R>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> ParseResultWithoutLoc<R>,
+    ) -> ParseResultWithoutLoc<R> {
+        if self.recursion_level >= self.parse_options.recursion_limit {
+            return Err(ParseErrorWithoutLoc(
+                ParseErrorWithoutLocInner::OverRecursionLimit,
+            ));
+        }
+        self.recursion_level += 1;
+        let r = f(self);
+        self.recursion_level -= 1;
+        r
+    }
+
     fn merge_inner(&mut self, message: &mut dyn MessageDyn) -> ParseResultWithoutLoc<()> {
+        self.with_recursion(|s| s.merge_message_of_any_type(message))
+    }
+
+    fn merge_message_of_any_type(
+        &mut self,
+        message: &mut dyn MessageDyn,
+    ) -> ParseResultWithoutLoc<()> {
         if let Some(duration) = message.downcast_mut() {
             return self.merge_wk_duration(duration);
         }
@@ -700,6 +764,52 @@ impl<'a> Parser<'a> {
             return self.merge_wk_struct(value);
         }
 
+        if let Some(generated) = generated_special_json_type(&message.descriptor_dyn()) {
+            return self.merge_dynamic_well_known_type(message, &generated);
+        }
+
+        self.merge_regular_message(message, false)
+    }
+
+    /// Merge into dynamic message of well-known type through generated message.
+    fn merge_dynamic_well_known_type(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        generated: &MessageDescriptor,
+    ) -> ParseResultWithoutLoc<()> {
+        let descriptor = message.descriptor_dyn();
+        let convert_error = |e| {
+            ParseErrorWithoutLoc(ParseErrorWithoutLocInner::DynamicWellKnownType(
+                descriptor.full_name().to_owned(),
+                e,
+            ))
+        };
+        let mut converted = message
+            .convert_to_dyn(generated)
+            .map_err(convert_error)?
+            .unwrap();
+        self.merge_message_of_any_type(&mut *converted)?;
+        let converted = converted
+            .convert_to_dyn(&descriptor)
+            .map_err(convert_error)?
+            .unwrap();
+        for field in descriptor.fields() {
+            field.clear_field(message);
+        }
+        message.mut_unknown_fields_dyn().clear();
+        message
+            .merge_from_message_dyn(&*converted)
+            .map_err(convert_error)
+    }
+
+    /// Merge JSON object into message fields.
+    ///
+    /// `@type` member is skipped if `in_any` is true.
+    fn merge_regular_message(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        in_any: bool,
+    ) -> ParseResultWithoutLoc<()> {
         let descriptor = message.descriptor_dyn();
 
         self.tokenizer.next_symbol_expect_eq('{')?;
@@ -711,6 +821,11 @@ impl<'a> Parser<'a> {
             first = false;
 
             let field_name = self.read_string()?;
+            if in_any && field_name == ANY_TYPE_MEMBER {
+                self.tokenizer.next_symbol_expect_eq(':')?;
+                self.skip_json_value()?;
+                continue;
+            }
             // Proto3 JSON parsers are required to accept both
             // the converted `lowerCamelCase` name and the proto field name.
             match descriptor.get_field_by_name_or_json_name(&field_name) {
@@ -734,75 +849,11 @@ impl<'a> Parser<'a> {
 
     fn merge_wk_duration(&mut self, duration: &mut Duration) -> ParseResultWithoutLoc<()> {
         let s = self.read_string()?;
-        let mut lexer = Lexer::new(&s, ParserLanguage::Json);
-
-        fn next_dec(lexer: &mut Lexer) -> ParseResultWithoutLoc<(u64, u32)> {
-            let s = lexer.take_while(|c| c >= '0' && c <= '9');
-
-            if s.len() == 0 {
-                Ok((0, 0))
-            } else {
-                match s.parse() {
-                    Ok(n) => Ok((n, s.len() as u32)),
-                    Err(_) => Err(ParseErrorWithoutLoc(
-                        ParseErrorWithoutLocInner::IncorrectDuration,
-                    )),
-                }
-            }
-        }
-
-        let minus = lexer.next_char_if_eq('-');
-        let seconds = match next_dec(&mut lexer)? {
-            (_, 0) => {
-                return Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::IncorrectDuration,
-                ))
-            }
-            (s, _) => s,
-        };
-        let nanos = if lexer.next_char_if_eq('.') {
-            let (mut a, mut b) = next_dec(&mut lexer)?;
-            if b > 9 {
-                return Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::IncorrectDuration,
-                ));
-            }
-            while b != 9 {
-                b += 1;
-                a *= 10;
-            }
-
-            if a > 999_999_999 {
-                return Err(ParseErrorWithoutLoc(
-                    ParseErrorWithoutLocInner::IncorrectDuration,
-                ));
-            }
-
-            a
-        } else {
-            0
-        };
-
-        // The suffix "s" is required
-        if !lexer.next_char_if_eq('s') {
-            return Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::IncorrectDuration,
-            ));
-        }
-
-        if !lexer.eof() {
-            return Err(ParseErrorWithoutLoc(
-                ParseErrorWithoutLocInner::IncorrectDuration,
-            ));
-        }
-
-        if minus {
-            duration.seconds = -(seconds as i64);
-            duration.nanos = -(nanos as i32);
-        } else {
-            duration.seconds = seconds as i64;
-            duration.nanos = nanos as i32;
-        }
+        let parsed: Duration = s
+            .parse()
+            .map_err(|_| ParseErrorWithoutLoc(ParseErrorWithoutLocInner::IncorrectDuration))?;
+        duration.seconds = parsed.seconds;
+        duration.nanos = parsed.nanos;
         Ok(())
     }
 
@@ -816,9 +867,9 @@ impl<'a> Parser<'a> {
 
     fn merge_wk_field_mask(&mut self, field_mask: &mut FieldMask) -> ParseResultWithoutLoc<()> {
         let s = self.read_string()?;
-        if !s.is_empty() {
-            field_mask.paths = s.split(',').map(|s| s.to_owned()).collect();
-        }
+        let parsed = FieldMask::from_json_string(&s)
+            .map_err(|_| ParseErrorWithoutLoc(ParseErrorWithoutLocInner::IncorrectFieldMask))?;
+        field_mask.paths = parsed.paths;
         Ok(())
     }
 
@@ -835,6 +886,18 @@ impl<'a> Parser<'a> {
     }
 
     fn merge_wk_value(&mut self, value: &mut Value) -> ParseResultWithoutLoc<()> {
+        self.value_nodes += 1;
+        if let Some(limit) = self.parse_options.value_nodes_limit {
+            if self.value_nodes > limit {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::TooManyValueNodes,
+                ));
+            }
+        }
+        self.with_recursion(|s| s.merge_wk_value_impl(value))
+    }
+
+    fn merge_wk_value_impl(&mut self, value: &mut Value) -> ParseResultWithoutLoc<()> {
         if self.tokenizer.lookahead_is_ident("null")? {
             value.kind = Some(value::Kind::null_value(self.read_wk_null_value()?.into()));
         } else if self.tokenizer.lookahead_is_ident("true")?
@@ -857,10 +920,75 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
-    fn merge_wk_any(&mut self, _value: &mut Any) -> ParseResultWithoutLoc<()> {
-        Err(ParseErrorWithoutLoc(
-            ParseErrorWithoutLocInner::AnyParsingIsNotImplemented,
-        ))
+    /// Find `@type` member of JSON object without consuming input.
+    fn lookahead_any_type_url(&self) -> ParseResultWithoutLoc<Option<String>> {
+        let mut parser = self.clone();
+        let mut type_url = None;
+        parser.read_map(
+            |_, key| Ok(key),
+            |s, key| {
+                if key == ANY_TYPE_MEMBER {
+                    type_url = Some(s.read_string()?);
+                    Ok(())
+                } else {
+                    s.skip_json_value()
+                }
+            },
+        )?;
+        Ok(type_url)
+    }
+
+    fn merge_wk_any(&mut self, any: &mut Any) -> ParseResultWithoutLoc<()> {
+        let type_url = match self.lookahead_any_type_url()? {
+            Some(type_url) => type_url,
+            None => {
+                // Only empty object is allowed without `@type`
+                return self.read_map(
+                    |_, _| {
+                        Err(ParseErrorWithoutLoc(
+                            ParseErrorWithoutLocInner::AnyTypeUrlIsMissing,
+                        ))
+                    },
+                    |_, ()| Ok(()),
+                );
+            }
+        };
+        let type_name = &type_url[type_url.rfind('/').map_or(0, |i| i + 1)..];
+        let mut message = match MessageFactory::global().new_by_name(type_name) {
+            Some(message) => message,
+            None => {
+                return Err(ParseErrorWithoutLoc(
+                    ParseErrorWithoutLocInner::UnknownAnyType(type_url),
+                ))
+            }
+        };
+
+        if has_special_json_representation(&message.descriptor_dyn()) {
+            self.read_map(
+                |_, key| Ok(key),
+                |s, key| match &key[..] {
+                    ANY_TYPE_MEMBER => s.skip_json_value(),
+                    "value" => s.merge_inner(&mut *message),
+                    _ => Err(ParseErrorWithoutLoc(
+                        ParseErrorWithoutLocInner::UnknownFieldName(key),
+                    )),
+                },
+            )?;
+        } else {
+            self.merge_regular_message(&mut *message, true)?;
+        }
+
+        // Required fields are checked by the caller
+        any.value = match message.write_to_bytes_partial_dyn() {
+            Ok(value) => value,
+            Err(e) => {
+                return Err(ParseErrorWithoutLoc(ParseErrorWithoutLocInner::AnyValue(
+                    type_url, e,
+                )))
+            }
+        };
+        any.type_url = type_url;
+        Ok(())
     }
 
     fn read_wk_value(&mut self) -> ParseResultWithoutLoc<Value> {
@@ -891,18 +1019,55 @@ impl<'a> Parser<'a> {
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Default, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub struct ParseOptions {
     /// Ignore unknown fields when parsing.
     ///
     /// When `true` fields with unknown names are ignored.
     /// When `false` parser returns an error on unknown field.
     pub ignore_unknown_fields: bool,
+    /// Ignore enum values with unknown names.
+    ///
+    /// When `true` singular field with such value is not set,
+    /// and such elements of repeated fields and entries of map fields are skipped.
+    /// When `false` parser returns an error.
+    ///
+    /// Enum values are accepted as names, numbers and quoted numbers
+    /// regardless of this option, unknown numbers are preserved.
+    pub ignore_unknown_enum_values: bool,
+    /// Max nesting level of messages and JSON values of `google.protobuf.Value`
+    /// and of ignored unknown fields.
+    ///
+    /// Parser returns an error instead of exhausting the stack on deeply nested input.
+    /// Default is 100, same as the default recursion limit of binary parser.
+    pub recursion_limit: u32,
+    /// Max total number of `google.protobuf.Value` objects, including nested,
+    /// in the parsed message.
+    ///
+    /// `None` means no limit.
+    pub value_nodes_limit: Option<usize>,
     /// Prevent initializing `ParseOptions` enumerating all field.
     pub _future_options: (),
 }
 
+const DEFAULT_RECURSION_LIMIT: u32 = 100;
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            ignore_unknown_fields: false,
+            ignore_unknown_enum_values: false,
+            recursion_limit: DEFAULT_RECURSION_LIMIT,
+            value_nodes_limit: None,
+            _future_options: (),
+        }
+    }
+}
+
 /// Merge JSON into provided message
+///
+/// Types of messages packed into `Any` are resolved with
+/// [`MessageFactory::global`](crate::reflect::MessageFactory::global).
 pub fn merge_from_str_with_options(
     message: &mut dyn MessageDyn,
     json: &str,
@@ -911,6 +1076,8 @@ pub fn merge_from_str_with_options(
     let mut parser = Parser {
         tokenizer: Tokenizer::new(json, ParserLanguage::Json),
         parse_options: parse_options.clone(),
+        recursion_level: 0,
+        value_nodes: 0,
     };
     parser.merge(message)
 }
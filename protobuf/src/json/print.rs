@@ -32,17 +32,24 @@ use crate::well_known_types::Value;
 
 use crate::json::well_known_wrapper::WellKnownWrapper;
 
+use crate::error::ProtobufError;
 use crate::json::rfc_3339::TmUtc;
+use crate::message::Message;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::EnumValueDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::MessageFactory;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
+use crate::text_format;
 
 #[derive(Debug)]
 enum PrintErrorInner {
     Fmt(fmt::Error),
-    AnyPrintingIsNotImplemented,
+    UnknownAnyType(String),
+    AnyValue(String, ProtobufError),
     TimestampNegativeNanos,
+    DynamicWellKnownType(String, ProtobufError),
 }
 
 /// Print to JSON error.
@@ -55,6 +62,24 @@ impl From<fmt::Error> for PrintError {
     }
 }
 
+impl fmt::Display for PrintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            PrintErrorInner::Fmt(e) => fmt::Display::fmt(e, f),
+            PrintErrorInner::UnknownAnyType(t) => {
+                write!(f, "Any type is not registered in MessageFactory: {}", t)
+            }
+            PrintErrorInner::AnyValue(t, e) => write!(f, "failed to decode Any {}: {}", t, e),
+            PrintErrorInner::TimestampNegativeNanos => write!(f, "Timestamp with negative nanos"),
+            PrintErrorInner::DynamicWellKnownType(t, e) => {
+                write!(f, "failed to convert dynamic {}: {}", t, e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PrintError {}
+
 pub type PrintResult<T> = Result<T, PrintError>;
 
 struct Printer {
@@ -214,14 +239,7 @@ impl<'a> PrintableToJson for ReflectValueRef<'a> {
 
 impl PrintableToJson for Duration {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        let sign = if self.seconds >= 0 { "" } else { "-" };
-        Ok(write!(
-            w.buf,
-            "\"{}{}.{:09}s\"",
-            sign,
-            self.seconds.abs(),
-            self.nanos.abs()
-        )?)
+        Ok(write!(w.buf, "\"{}\"", self)?)
     }
 }
 
@@ -237,13 +255,13 @@ impl PrintableToJson for Timestamp {
 
 impl PrintableToJson for FieldMask {
     fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
-        w.print_printable(&self.paths.join(","))
+        w.print_printable(&self.to_json_string())
     }
 }
 
 impl PrintableToJson for Any {
-    fn print_to_json(&self, _w: &mut Printer) -> PrintResult<()> {
-        Err(PrintError(PrintErrorInner::AnyPrintingIsNotImplemented))
+    fn print_to_json(&self, w: &mut Printer) -> PrintResult<()> {
+        w.print_any(self)
     }
 }
 
@@ -453,16 +471,71 @@ impl Printer {
             self.print_printable(value)
         } else if let Some(value) = message.downcast_ref::<Struct>() {
             self.print_printable(value)
+        } else if let Some(generated) = generated_special_json_type(&message.descriptor_dyn()) {
+            // Dynamic message of well-known type is printed as generated message
+            let converted = message
+                .convert_to_dyn(&generated)
+                .map_err(|e| {
+                    PrintError(PrintErrorInner::DynamicWellKnownType(
+                        generated.full_name().to_owned(),
+                        e,
+                    ))
+                })?
+                .unwrap();
+            self.print_message(&MessageRef::from(&*converted))
         } else {
             self.print_regular_message(message)
         }
     }
 
-    fn print_regular_message(&mut self, message: &MessageRef) -> Result<(), PrintError> {
-        let descriptor = message.descriptor_dyn();
+    fn print_any(&mut self, any: &Any) -> PrintResult<()> {
+        if any.type_url.is_empty() && any.value.is_empty() {
+            return Ok(write!(self.buf, "{{}}")?);
+        }
+        let message = match any.unpack_with_factory(MessageFactory::global()) {
+            Ok(Some(message)) => message,
+            Ok(None) => {
+                return Err(PrintError(PrintErrorInner::UnknownAnyType(
+                    any.type_url.clone(),
+                )))
+            }
+            Err(e) => {
+                return Err(PrintError(PrintErrorInner::AnyValue(
+                    any.type_url.clone(),
+                    e,
+                )))
+            }
+        };
+        let message = MessageRef::from(&*message);
+
+        write!(self.buf, "{{\"@type\": ")?;
+        self.print_printable(&any.type_url)?;
+        if has_special_json_representation(&message.descriptor_dyn()) {
+            write!(self.buf, ", \"value\": ")?;
+            self.print_message(&message)?;
+        } else {
+            let mut first = false;
+            self.print_regular_message_fields(&message, &mut first)?;
+        }
+        write!(self.buf, "}}")?;
+        Ok(())
+    }
 
+    fn print_regular_message(&mut self, message: &MessageRef) -> Result<(), PrintError> {
         write!(self.buf, "{{")?;
         let mut first = true;
+        self.print_regular_message_fields(message, &mut first)?;
+        write!(self.buf, "}}")?;
+        Ok(())
+    }
+
+    fn print_regular_message_fields(
+        &mut self,
+        message: &MessageRef,
+        first: &mut bool,
+    ) -> PrintResult<()> {
+        let descriptor = message.descriptor_dyn();
+
         for field in descriptor.fields() {
             let json_field_name = if self.print_options.proto_field_name {
                 field.get_name()
@@ -470,6 +543,20 @@ impl Printer {
                 field.json_name()
             };
 
+            if field.is_redacted() && !self.print_options.unredacted {
+                let is_set = match field.get_reflect(&**message) {
+                    ReflectFieldRef::Optional(v) => v.is_some(),
+                    ReflectFieldRef::Repeated(v) => !v.is_empty(),
+                    ReflectFieldRef::Map(v) => !v.is_empty(),
+                };
+                if is_set {
+                    self.print_comma_but_first(first)?;
+                    write!(self.buf, "\"{}\": ", json_field_name)?;
+                    self.print_printable(text_format::REDACTED)?;
+                }
+                continue;
+            }
+
             let field_type = field.runtime_field_type();
 
             match field.get_reflect(&**message) {
@@ -487,34 +574,33 @@ impl Printer {
 
                         if !is_message && !is_oneof {
                             let v = field.get_singular_field_or_default(&**message);
-                            self.print_comma_but_first(&mut first)?;
+                            self.print_comma_but_first(first)?;
                             write!(self.buf, "\"{}\": ", json_field_name)?;
                             self.print_printable(&v)?;
                         }
                     }
                 }
                 ReflectFieldRef::Optional(Some(v)) => {
-                    self.print_comma_but_first(&mut first)?;
+                    self.print_comma_but_first(first)?;
                     write!(self.buf, "\"{}\": ", json_field_name)?;
                     self.print_printable(&v)?;
                 }
                 ReflectFieldRef::Repeated(v) => {
                     if !v.is_empty() {
-                        self.print_comma_but_first(&mut first)?;
+                        self.print_comma_but_first(first)?;
                         write!(self.buf, "\"{}\": ", json_field_name)?;
                         self.print_repeated(&v)?;
                     }
                 }
                 ReflectFieldRef::Map(v) => {
                     if !v.is_empty() {
-                        self.print_comma_but_first(&mut first)?;
+                        self.print_comma_but_first(first)?;
                         write!(self.buf, "\"{}\": ", json_field_name)?;
                         self.print_map(&v)?;
                     }
                 }
             }
         }
-        write!(self.buf, "}}")?;
         Ok(())
     }
 
@@ -531,6 +617,63 @@ impl Printer {
     }
 }
 
+/// Well-known types which are not printed as JSON objects with fields,
+/// so when packed into `Any` they are printed in `value` member.
+pub(crate) fn has_special_json_representation(descriptor: &MessageDescriptor) -> bool {
+    match descriptor.full_name() {
+        "google.protobuf.Any"
+        | "google.protobuf.Duration"
+        | "google.protobuf.FieldMask"
+        | "google.protobuf.ListValue"
+        | "google.protobuf.Struct"
+        | "google.protobuf.Timestamp"
+        | "google.protobuf.Value"
+        | "google.protobuf.BoolValue"
+        | "google.protobuf.BytesValue"
+        | "google.protobuf.DoubleValue"
+        | "google.protobuf.FloatValue"
+        | "google.protobuf.Int32Value"
+        | "google.protobuf.Int64Value"
+        | "google.protobuf.StringValue"
+        | "google.protobuf.UInt32Value"
+        | "google.protobuf.UInt64Value" => true,
+        _ => false,
+    }
+}
+
+/// Generated type for dynamic message of well-known type
+/// with special JSON representation, `None` for other messages.
+///
+/// JSON codec converts dynamic messages of these types to generated messages.
+pub(crate) fn generated_special_json_type(
+    descriptor: &MessageDescriptor,
+) -> Option<MessageDescriptor> {
+    let generated = match descriptor.full_name() {
+        "google.protobuf.Any" => Any::descriptor_static(),
+        "google.protobuf.Duration" => Duration::descriptor_static(),
+        "google.protobuf.FieldMask" => FieldMask::descriptor_static(),
+        "google.protobuf.ListValue" => ListValue::descriptor_static(),
+        "google.protobuf.Struct" => Struct::descriptor_static(),
+        "google.protobuf.Timestamp" => Timestamp::descriptor_static(),
+        "google.protobuf.Value" => Value::descriptor_static(),
+        "google.protobuf.BoolValue" => BoolValue::descriptor_static(),
+        "google.protobuf.BytesValue" => BytesValue::descriptor_static(),
+        "google.protobuf.DoubleValue" => DoubleValue::descriptor_static(),
+        "google.protobuf.FloatValue" => FloatValue::descriptor_static(),
+        "google.protobuf.Int32Value" => Int32Value::descriptor_static(),
+        "google.protobuf.Int64Value" => Int64Value::descriptor_static(),
+        "google.protobuf.StringValue" => StringValue::descriptor_static(),
+        "google.protobuf.UInt32Value" => UInt32Value::descriptor_static(),
+        "google.protobuf.UInt64Value" => UInt64Value::descriptor_static(),
+        _ => return None,
+    };
+    if &generated == descriptor {
+        None
+    } else {
+        Some(generated)
+    }
+}
+
 /// Options for printing JSON to string
 ///
 /// # Examples
@@ -553,11 +696,20 @@ pub struct PrintOptions {
     pub proto_field_name: bool,
     /// Output field default values.
     pub always_output_default_values: bool,
+    /// Print values of fields marked with `debug_redact` or `rustproto.sensitive`
+    /// options instead of [`REDACTED`](crate::text_format::REDACTED).
+    ///
+    /// Redacted output cannot be parsed back, set this when it needs to round-trip.
+    pub unredacted: bool,
     /// Prevent initializing `PrintOptions` enumerating all field.
     pub _future_options: (),
 }
 
 /// Serialize message to JSON according to protobuf specification.
+///
+/// Types of messages packed into `Any` are resolved with
+/// [`MessageFactory::global`](crate::reflect::MessageFactory::global),
+/// printing fails if the type is not registered.
 pub fn print_to_string_with_options(
     message: &dyn MessageDyn,
     print_options: &PrintOptions,
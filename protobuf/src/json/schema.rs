@@ -0,0 +1,340 @@
+//! JSON Schema of protobuf JSON mapping.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::reflect::EnumDescriptor;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// JSON Schema dialect of generated documents.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Minimal JSON tree to build the schema.
+enum Json {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(String, Json)>),
+}
+
+fn obj(members: Vec<(&str, Json)>) -> Json {
+    Json::Obj(
+        members
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v))
+            .collect(),
+    )
+}
+
+fn string(s: &str) -> Json {
+    Json::Str(s.to_owned())
+}
+
+fn type_(t: &str) -> Json {
+    obj(vec![("type", string(t))])
+}
+
+fn def_ref(full_name: &str) -> Json {
+    obj(vec![("$ref", Json::Str(format!("#/$defs/{}", full_name)))])
+}
+
+fn write_str(s: &str, buf: &mut String) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if c.is_control() => write!(buf, "\\u{:04x}", c as u32).unwrap(),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+fn write_indent(buf: &mut String, indent: usize) {
+    buf.push('\n');
+    for _ in 0..indent {
+        buf.push_str("  ");
+    }
+}
+
+impl Json {
+    fn write(&self, buf: &mut String, indent: usize) {
+        match self {
+            Json::Bool(b) => write!(buf, "{}", b).unwrap(),
+            Json::Int(i) => write!(buf, "{}", i).unwrap(),
+            Json::Str(s) => write_str(s, buf),
+            Json::Arr(items) => {
+                buf.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i != 0 {
+                        buf.push_str(", ");
+                    }
+                    item.write(buf, indent);
+                }
+                buf.push(']');
+            }
+            Json::Obj(members) if members.is_empty() => buf.push_str("{}"),
+            Json::Obj(members) => {
+                buf.push('{');
+                for (i, (k, v)) in members.iter().enumerate() {
+                    if i != 0 {
+                        buf.push(',');
+                    }
+                    write_indent(buf, indent + 1);
+                    write_str(k, buf);
+                    buf.push_str(": ");
+                    v.write(buf, indent + 1);
+                }
+                write_indent(buf, indent);
+                buf.push('}');
+            }
+        }
+    }
+}
+
+/// Definitions of messages and enums reachable from the root message.
+#[derive(Default)]
+struct Defs {
+    defs: BTreeMap<String, Option<Json>>,
+}
+
+impl Defs {
+    fn message(&mut self, message: &MessageDescriptor) -> Json {
+        if let Some(schema) = well_known_type_schema(message) {
+            return schema;
+        }
+        let full_name = message.full_name().to_owned();
+        if !self.defs.contains_key(&full_name) {
+            // Reserve the slot before visiting fields to stop on recursive types
+            self.defs.insert(full_name.clone(), None);
+            let properties = message
+                .fields()
+                .map(|f| (f.json_name().to_owned(), self.field(&f)))
+                .collect();
+            let schema = obj(vec![
+                ("type", string("object")),
+                ("properties", Json::Obj(properties)),
+                ("additionalProperties", Json::Bool(false)),
+            ]);
+            self.defs.insert(full_name.clone(), Some(schema));
+        }
+        def_ref(&full_name)
+    }
+
+    fn enumeration(&mut self, e: &EnumDescriptor) -> Json {
+        if e.full_name() == "google.protobuf.NullValue" {
+            return type_("null");
+        }
+        let full_name = e.full_name().to_owned();
+        if !self.defs.contains_key(&full_name) {
+            let names = e.values().map(|v| string(v.get_name())).collect();
+            let schema = obj(vec![(
+                "anyOf",
+                Json::Arr(vec![
+                    obj(vec![("type", string("string")), ("enum", Json::Arr(names))]),
+                    int_schema(i32::MIN as i64, i32::MAX as i64),
+                ]),
+            )]);
+            self.defs.insert(full_name.clone(), Some(schema));
+        }
+        def_ref(&full_name)
+    }
+
+    fn field(&mut self, field: &FieldDescriptor) -> Json {
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) => self.value(&t),
+            RuntimeFieldType::Repeated(t) => {
+                obj(vec![("type", string("array")), ("items", self.value(&t))])
+            }
+            RuntimeFieldType::Map(k, v) => obj(vec![
+                ("type", string("object")),
+                ("propertyNames", map_key_schema(&k)),
+                ("additionalProperties", self.value(&v)),
+            ]),
+        }
+    }
+
+    fn value(&mut self, t: &RuntimeTypeBox) -> Json {
+        match t {
+            RuntimeTypeBox::I32 => int_schema(i32::MIN as i64, i32::MAX as i64),
+            RuntimeTypeBox::U32 => int_schema(0, u32::MAX as i64),
+            RuntimeTypeBox::I64 => int64_schema("^-?[0-9]+$"),
+            RuntimeTypeBox::U64 => int64_schema("^[0-9]+$"),
+            RuntimeTypeBox::F32 | RuntimeTypeBox::F64 => float_schema(),
+            RuntimeTypeBox::Bool => type_("boolean"),
+            RuntimeTypeBox::String => type_("string"),
+            RuntimeTypeBox::VecU8 => bytes_schema(),
+            RuntimeTypeBox::Enum(e) => self.enumeration(e),
+            RuntimeTypeBox::Message(m) => self.message(m),
+        }
+    }
+}
+
+fn int_schema(min: i64, max: i64) -> Json {
+    obj(vec![
+        ("type", string("integer")),
+        ("minimum", Json::Int(min)),
+        ("maximum", Json::Int(max)),
+    ])
+}
+
+/// 64-bit integers are printed as strings, but numbers are accepted.
+fn int64_schema(pattern: &str) -> Json {
+    obj(vec![
+        ("type", Json::Arr(vec![string("string"), string("integer")])),
+        ("pattern", string(pattern)),
+    ])
+}
+
+fn float_schema() -> Json {
+    obj(vec![(
+        "anyOf",
+        Json::Arr(vec![
+            type_("number"),
+            obj(vec![(
+                "enum",
+                Json::Arr(vec![string("NaN"), string("Infinity"), string("-Infinity")]),
+            )]),
+        ]),
+    )])
+}
+
+fn bytes_schema() -> Json {
+    obj(vec![
+        ("type", string("string")),
+        ("contentEncoding", string("base64")),
+    ])
+}
+
+/// Map keys are always strings in JSON.
+fn map_key_schema(t: &RuntimeTypeBox) -> Json {
+    let pattern = match t {
+        RuntimeTypeBox::I32 | RuntimeTypeBox::I64 => "^-?[0-9]+$",
+        RuntimeTypeBox::U32 | RuntimeTypeBox::U64 => "^[0-9]+$",
+        RuntimeTypeBox::Bool => "^(true|false)$",
+        _ => return type_("string"),
+    };
+    obj(vec![
+        ("type", string("string")),
+        ("pattern", string(pattern)),
+    ])
+}
+
+/// Well-known types with special JSON representation.
+fn well_known_type_schema(message: &MessageDescriptor) -> Option<Json> {
+    Some(match message.full_name() {
+        "google.protobuf.Any" => obj(vec![
+            ("type", string("object")),
+            ("properties", obj(vec![("@type", type_("string"))])),
+            ("required", Json::Arr(vec![string("@type")])),
+        ]),
+        "google.protobuf.Duration" => obj(vec![
+            ("type", string("string")),
+            ("pattern", string("^-?[0-9]+(\\.[0-9]{1,9})?s$")),
+        ]),
+        "google.protobuf.Timestamp" => obj(vec![
+            ("type", string("string")),
+            ("format", string("date-time")),
+        ]),
+        "google.protobuf.FieldMask" => type_("string"),
+        "google.protobuf.Struct" => type_("object"),
+        "google.protobuf.ListValue" => type_("array"),
+        "google.protobuf.Value" => obj(vec![]),
+        "google.protobuf.BoolValue" => type_("boolean"),
+        "google.protobuf.StringValue" => type_("string"),
+        "google.protobuf.BytesValue" => bytes_schema(),
+        "google.protobuf.Int32Value" => int_schema(i32::MIN as i64, i32::MAX as i64),
+        "google.protobuf.UInt32Value" => int_schema(0, u32::MAX as i64),
+        "google.protobuf.Int64Value" => int64_schema("^-?[0-9]+$"),
+        "google.protobuf.UInt64Value" => int64_schema("^[0-9]+$"),
+        "google.protobuf.FloatValue" | "google.protobuf.DoubleValue" => float_schema(),
+        _ => return None,
+    })
+}
+
+/// JSON Schema (draft 2020-12) of messages in JSON format.
+///
+/// Schema describes what [`print_to_string`](crate::json::print_to_string)
+/// outputs with default options: fields have JSON names (`lowerCamelCase`),
+/// well-known types have their special representations and enums are either
+/// value names or numbers. Messages and enums are defined in `$defs` by their
+/// fully qualified names, so recursive types are supported.
+///
+/// Parser is more lenient than this schema: it also accepts original
+/// field names and `null` for any field.
+///
+/// ```
+/// # use protobuf::reflect::MessageDescriptor;
+/// # use protobuf::well_known_types::Api;
+/// let schema = protobuf::json::json_schema(&MessageDescriptor::for_type::<Api>());
+/// assert!(schema.contains("\"$ref\": \"#/$defs/google.protobuf.Api\""));
+/// ```
+pub fn json_schema(message: &MessageDescriptor) -> String {
+    let mut defs = Defs::default();
+    let root = defs.message(message);
+    let mut members = vec![("$schema".to_owned(), string(JSON_SCHEMA_DIALECT))];
+    match root {
+        Json::Obj(root) => members.extend(root),
+        _ => unreachable!(),
+    }
+    if !defs.defs.is_empty() {
+        let defs = defs
+            .defs
+            .into_iter()
+            .map(|(k, v)| (k, v.expect("definition is complete")))
+            .collect();
+        members.push(("$defs".to_owned(), Json::Obj(defs)));
+    }
+    finish(&Json::Obj(members))
+}
+
+fn finish(json: &Json) -> String {
+    let mut buf = String::new();
+    json.write(&mut buf, 0);
+    buf.push('\n');
+    buf
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Struct;
+
+    #[test]
+    fn well_known_type() {
+        assert_eq!(
+            "{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \
+             \"type\": \"string\",\n  \"pattern\": \"^-?[0-9]+(\\\\.[0-9]{1,9})?s$\"\n}\n",
+            json_schema(&MessageDescriptor::for_type::<Duration>())
+        );
+    }
+
+    #[test]
+    fn recursive_message() {
+        let schema = json_schema(&MessageDescriptor::for_type::<FileDescriptorProto>());
+        assert!(schema.contains("\"$ref\": \"#/$defs/google.protobuf.FileDescriptorProto\",\n"));
+        // DescriptorProto contains nested DescriptorProto
+        assert!(schema.contains("\"nestedType\": {\n          \"type\": \"array\",\n          \"items\": {\n            \"$ref\": \"#/$defs/google.protobuf.DescriptorProto\""));
+        assert!(
+            schema.contains("\"google.protobuf.FieldDescriptorProto.Type\": {\n      \"anyOf\"")
+        );
+        assert!(schema.contains("\"TYPE_DOUBLE\""));
+    }
+
+    #[test]
+    fn map_and_value() {
+        let schema = json_schema(&MessageDescriptor::for_type::<Struct>());
+        assert!(schema.contains("\"type\": \"object\"\n}"));
+        assert!(!schema.contains("$defs"));
+    }
+}
@@ -16,6 +16,7 @@ extern crate serde;
 extern crate serde_derive;
 pub use crate::clear::Clear;
 pub use crate::coded_input_stream::CodedInputStream;
+pub use crate::coded_input_stream::UnknownEnumValuePolicy;
 pub use crate::coded_output_stream::CodedOutputStream;
 pub use crate::enums::ProtobufEnum;
 pub use crate::enums::ProtobufEnumOrUnknown;
@@ -23,17 +24,25 @@ pub use crate::message::Message;
 pub use crate::message_dyn::MessageDyn;
 pub use crate::message_field::MessageField;
 pub use crate::oneof::Oneof;
+pub use crate::small_vec::SmallVec;
+pub use crate::string_interner::InternedString;
+pub use crate::string_interner::StringInterner;
 pub use crate::unknown::UnknownFields;
 pub use crate::unknown::UnknownFieldsIter;
 pub use crate::unknown::UnknownValue;
 pub use crate::unknown::UnknownValueRef;
 pub use crate::unknown::UnknownValues;
 pub use crate::unknown::UnknownValuesIter;
+pub mod wire;
 pub mod wire_format;
 #[cfg(feature = "bytes")]
 pub use crate::chars::Chars;
+pub use crate::error::MessageNotInitializedError;
+pub use crate::error::MessageTooLargeError;
+pub use crate::error::MessageTypeMismatchError;
 pub use crate::error::ProtobufError;
 pub use crate::error::ProtobufResult;
+pub use crate::error::TextEncodingError;
 
 // generated
 pub mod descriptor;
@@ -41,21 +50,44 @@ pub mod descriptor;
 pub mod plugin;
 pub mod rustproto;
 
+pub mod arbitrary;
+pub mod bridge;
+pub mod delimited;
 mod clear;
 mod coded_input_stream;
 mod coded_output_stream;
+mod drop_iterative;
 mod enums;
 mod error;
 pub mod ext;
+pub mod field_mask;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod grpc;
 pub mod json;
+pub mod push_decoder;
 mod lazy_v2;
 mod message;
 mod message_dyn;
 mod message_field;
+pub mod message_pool;
 mod oneof;
 pub mod reflect;
+mod shrink_to_fit;
+mod small_vec;
+mod space_used;
+mod string_interner;
 pub mod rt;
+#[cfg(feature = "bytes")]
+mod serialize_pool;
+pub mod strict;
+#[cfg(feature = "stats")]
+pub mod stats;
+#[cfg(feature = "hex-base64")]
+mod text_encoding;
 pub mod text_format;
+pub mod validate;
+pub mod view;
 pub mod well_known_types;
 mod well_known_types_util;
 
@@ -1,6 +1,8 @@
 use std::fmt;
+use std::io::BufRead;
 use std::io::Read;
 use std::io::Write;
+use std::mem;
 
 #[cfg(feature = "bytes")]
 use bytes::Bytes;
@@ -9,12 +11,25 @@ use crate::clear::Clear;
 use crate::coded_input_stream::CodedInputStream;
 use crate::coded_output_stream::CodedOutputStream;
 use crate::coded_output_stream::WithCodedOutputStream;
+use crate::error::MessageNotInitializedError;
 use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
+use crate::ext::ExtensionFields;
+use crate::ext::ExtensionRegistry;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::reflect_eq::ReflectEqMode;
+use crate::reflect::required::missing_required_fields;
 use crate::reflect::MessageDescriptor;
+#[cfg(feature = "bytes")]
+use crate::serialize_pool::write_pooled;
+use crate::shrink_to_fit::ShrinkToFit;
+use crate::space_used::space_used_by_reflection;
+use crate::strict::StrictParseOptions;
+#[cfg(feature = "hex-base64")]
+use crate::text_encoding;
 use crate::unknown::UnknownFields;
+use crate::validate::ValidationError;
+use crate::validate::Violations;
 
 /// Trait implemented for all generated structs for protobuf messages.
 ///
@@ -30,6 +45,9 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
 
     /// Get message descriptor for message type.
     ///
+    /// Message instance is not needed, so generic code can access
+    /// reflection with only a type parameter.
+    ///
     /// ```
     /// # use protobuf::Message;
     /// # fn foo<MyMessage: Message>() {
@@ -37,11 +55,12 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     /// assert_eq!("MyMessage", descriptor.name());
     /// # }
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// For messages generated with `lite_runtime` option.
     fn descriptor_static() -> MessageDescriptor {
-        panic!(
-            "descriptor_static is not implemented for message, \
-             LITE_RUNTIME must be used"
-        );
+        panic!("descriptor_static is not available for messages generated with lite_runtime");
     }
 
     /// True iff all required fields are initialized.
@@ -78,9 +97,10 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         self.check_initialized()?;
 
         // cache sizes
-        self.compute_size();
+        let size = self.compute_size();
         // TODO: reserve additional
         self.write_to_with_cached_sizes(os)?;
+        report_serialized(size);
 
         Ok(())
     }
@@ -109,7 +129,7 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     /// Update this message object with fields read from given stream.
     fn merge_from_bytes(&mut self, bytes: &[u8]) -> ProtobufResult<()> {
         let mut is = CodedInputStream::from_bytes(bytes);
-        self.merge_from(&mut is)
+        is.with_stats_report(|is| self.merge_from(is))
     }
 
     /// Parse message from reader.
@@ -119,9 +139,28 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         Self: Sized,
     {
         let mut is = CodedInputStream::new(reader);
-        let r = Message::parse_from(&mut is)?;
-        is.check_eof()?;
-        Ok(r)
+        is.with_stats_report(|is| {
+            let r = Message::parse_from(is)?;
+            is.check_eof()?;
+            Ok(r)
+        })
+    }
+
+    /// Parse message from buffered reader like `BufReader` or `StdinLock`.
+    /// Parse stops on EOF or when error encountered.
+    ///
+    /// Unlike [`parse_from_reader`](Message::parse_from_reader),
+    /// data is parsed directly from the reader buffer.
+    fn parse_from_buf_read(reader: &mut dyn BufRead) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut is = CodedInputStream::from_buffered_reader(reader);
+        is.with_stats_report(|is| {
+            let r = Message::parse_from(is)?;
+            is.check_eof()?;
+            Ok(r)
+        })
     }
 
     /// Parse message from byte array.
@@ -130,9 +169,40 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         Self: Sized,
     {
         let mut is = CodedInputStream::from_bytes(bytes);
-        let r = Message::parse_from(&mut is)?;
-        is.check_eof()?;
-        Ok(r)
+        is.with_stats_report(|is| {
+            let r = Message::parse_from(is)?;
+            is.check_eof()?;
+            Ok(r)
+        })
+    }
+
+    /// Parse message from byte array decoding extensions
+    /// registered in `registry` into typed extension fields.
+    fn parse_from_bytes_with_registry(
+        bytes: &[u8],
+        registry: &ExtensionRegistry,
+    ) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        is.set_extension_registry(registry);
+        is.with_stats_report(|is| {
+            let r = Message::parse_from(is)?;
+            is.check_eof()?;
+            Ok(r)
+        })
+    }
+
+    /// Parse message from byte array rejecting input which is
+    /// accepted by regular parser but violates rules enabled in `options`.
+    ///
+    /// See [`strict`](crate::strict) module for details.
+    fn parse_from_bytes_strict(bytes: &[u8], options: &StrictParseOptions) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        options.parse_from_bytes(bytes)
     }
 
     /// Parse message from `Bytes` object.
@@ -143,22 +213,103 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         Self: Sized,
     {
         let mut is = CodedInputStream::from_carllerche_bytes(bytes);
-        let r = Self::parse_from(&mut is)?;
-        is.check_eof()?;
+        is.with_stats_report(|is| {
+            let r = Self::parse_from(is)?;
+            is.check_eof()?;
+            Ok(r)
+        })
+    }
+
+    /// Parse message from hex string like `089601`.
+    ///
+    /// Digits may be of any case, ASCII whitespace is ignored.
+    #[cfg(feature = "hex-base64")]
+    fn parse_from_hex(hex: &str) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        Self::parse_from_bytes(&text_encoding::decode_hex(hex)?)
+    }
+
+    /// Parse message from base64 string.
+    ///
+    /// Both standard and URL-safe alphabets are accepted, padding is optional.
+    #[cfg(feature = "hex-base64")]
+    fn parse_from_base64(base64: &str) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        Self::parse_from_bytes(&text_encoding::decode_base64(base64)?)
+    }
+
+    /// Parse message from byte array without checking that required fields are set.
+    ///
+    /// Missing fields can be then found with
+    /// [`missing_required_fields`](Message::missing_required_fields).
+    fn parse_from_bytes_partial(bytes: &[u8]) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        let mut is = CodedInputStream::from_bytes(bytes);
+        is.with_stats_report(|is| {
+            let mut r: Self = Message::new();
+            r.merge_from(is)?;
+            is.check_eof()?;
+            Ok(r)
+        })
+    }
+
+    /// Parse message from byte array and check that required fields are set.
+    ///
+    /// This is what [`parse_from_bytes`](Message::parse_from_bytes) does,
+    /// this function is a counterpart of
+    /// [`parse_from_bytes_partial`](Message::parse_from_bytes_partial).
+    fn parse_from_bytes_checked(bytes: &[u8]) -> ProtobufResult<Self>
+    where
+        Self: Sized,
+    {
+        let r = Self::parse_from_bytes_partial(bytes)?;
+        r.check_initialized()?;
         Ok(r)
     }
 
     /// Check if all required fields of this object are initialized.
+    ///
+    /// Error lists paths of all missing fields including fields of nested messages.
     fn check_initialized(&self) -> ProtobufResult<()> {
         if !self.is_initialized() {
             Err(ProtobufError::MessageNotInitialized(
-                self.descriptor_by_instance().name().to_owned(),
+                MessageNotInitializedError {
+                    message: self.descriptor_by_instance().full_name().to_owned(),
+                    missing_fields: self.missing_required_fields(),
+                },
             ))
         } else {
             Ok(())
         }
     }
 
+    /// Paths to required fields which are not set, in this message
+    /// and nested messages.
+    ///
+    /// ```
+    /// # use protobuf::Message;
+    /// # use protobuf::descriptor::UninterpretedOption;
+    /// # use protobuf::descriptor::uninterpreted_option::NamePart;
+    /// let mut option = UninterpretedOption::new();
+    /// option.name.push(NamePart::new());
+    /// assert_eq!(
+    ///     vec!["name[0].name_part", "name[0].is_extension"],
+    ///     option.missing_required_fields()
+    /// );
+    /// ```
+    ///
+    /// Empty for protobuf 3. This function uses reflection,
+    /// [`is_initialized`](Message::is_initialized) is faster.
+    fn missing_required_fields(&self) -> Vec<String> {
+        missing_required_fields(self)
+    }
+
     /// Write the message to the writer.
     fn write_to_writer(&self, w: &mut dyn Write) -> ProtobufResult<()> {
         w.with_coded_output_stream(|os| self.write_to(os))
@@ -187,9 +338,38 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
             self.write_to_with_cached_sizes(&mut os)?;
             os.check_eof();
         }
+        report_serialized(size as u32);
         Ok(v)
     }
 
+    /// Write the message to lowercase hex string without separators.
+    #[cfg(feature = "hex-base64")]
+    fn write_to_hex(&self) -> ProtobufResult<String> {
+        Ok(text_encoding::encode_hex(&self.write_to_bytes()?))
+    }
+
+    /// Write the message to standard base64 string with padding.
+    #[cfg(feature = "hex-base64")]
+    fn write_to_base64(&self) -> ProtobufResult<String> {
+        Ok(text_encoding::encode_base64(&self.write_to_bytes()?))
+    }
+
+    /// Write the message to `Bytes` using thread-local buffer.
+    ///
+    /// Unlike [`write_to_bytes`](Message::write_to_bytes), this function
+    /// does not allocate when the buffer has enough capacity:
+    /// the buffer is reused once all `Bytes` objects previously returned
+    /// on this thread are dropped.
+    #[cfg(feature = "bytes")]
+    fn write_to_bytes_pooled(&self) -> ProtobufResult<Bytes> {
+        self.check_initialized()?;
+
+        let size = self.compute_size() as usize;
+        let bytes = write_pooled(size, |os| self.write_to_with_cached_sizes(os))?;
+        report_serialized(size as u32);
+        Ok(bytes)
+    }
+
     /// Write the message to the writer, prepend the message with message length
     /// encoded as varint.
     fn write_length_delimited_to_writer(&self, w: &mut dyn Write) -> ProtobufResult<()> {
@@ -209,6 +389,84 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
     /// Get a mutable reference to unknown fields.
     fn mut_unknown_fields(&mut self) -> &mut UnknownFields;
 
+    /// Get a reference to typed extension fields.
+    ///
+    /// Returns `None` if message does not declare extension ranges.
+    fn get_extension_fields(&self) -> Option<&ExtensionFields> {
+        None
+    }
+    /// Get a mutable reference to typed extension fields.
+    fn mut_extension_fields(&mut self) -> Option<&mut ExtensionFields> {
+        None
+    }
+
+    /// Estimate heap memory retained by the message: strings, repeated
+    /// and map fields, nested messages, unknown and extension fields.
+    ///
+    /// `size_of::<Self>()` is not included.
+    ///
+    /// Generated messages count allocated capacities,
+    /// default implementation estimates through reflection.
+    fn space_used_excluding_self(&self) -> usize {
+        space_used_by_reflection(self)
+    }
+
+    /// Estimate memory retained by the message including the message struct itself.
+    ///
+    /// This is similar to C++ `SpaceUsedLong()`, and can be used to estimate
+    /// memory used by caches of messages.
+    ///
+    /// ```
+    /// # use protobuf::Message;
+    /// # use protobuf::well_known_types::StringValue;
+    /// let mut m = StringValue::new();
+    /// m.value = "x".repeat(1000);
+    /// assert!(m.space_used() >= 1000);
+    /// ```
+    fn space_used(&self) -> usize {
+        mem::size_of::<Self>() + self.space_used_excluding_self()
+    }
+
+    /// Release unused capacity of string, bytes, repeated and map fields,
+    /// nested messages and unknown fields.
+    ///
+    /// [`clear`](Clear::clear) keeps allocated buffers so message objects
+    /// can be reused without reallocation, this function is the opposite:
+    /// it should be called when a message is going to be stored for long.
+    ///
+    /// ```
+    /// # use protobuf::Clear;
+    /// # use protobuf::Message;
+    /// # use protobuf::well_known_types::StringValue;
+    /// let mut m = StringValue::new();
+    /// m.value = "x".repeat(1000);
+    /// m.clear();
+    /// assert!(m.space_used() >= 1000);
+    /// m.shrink_to_fit();
+    /// assert!(m.space_used() < 1000);
+    /// ```
+    ///
+    /// Default implementation only shrinks unknown fields.
+    fn shrink_to_fit(&mut self) {
+        self.mut_unknown_fields().shrink_to_fit();
+    }
+
+    /// Check validation rules specified with `rustproto.validate_*` field options
+    /// in this message and nested messages, see [`validate`](crate::validate) module.
+    ///
+    /// Messages without rules are always valid.
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut violations = Violations::new();
+        self.validate_to(&mut violations);
+        violations.into_result()
+    }
+
+    /// Collect rule violations, implemented by generated code.
+    #[doc(hidden)]
+    fn validate_to(&self, violations: &mut Violations) {
+        let _ = violations;
+    }
+
     /// Create an empty message object.
     ///
     /// ```
@@ -238,3 +496,13 @@ pub trait Message: fmt::Debug + Clear + Send + Sync + Sized + 'static {
         MessageDyn::reflect_eq_dyn(self, other, mode)
     }
 }
+
+/// Report serialized message size to the hook
+/// installed with `stats::set_serialize_hook`.
+#[inline]
+pub(crate) fn report_serialized(size: u32) {
+    #[cfg(feature = "stats")]
+    crate::stats::report_serialize(&crate::stats::SerializeStats { bytes: size as u64 });
+    #[cfg(not(feature = "stats"))]
+    let _ = size;
+}
@@ -1,6 +1,13 @@
 use crate::coded_output_stream::WithCodedOutputStream;
+use crate::error::MessageNotInitializedError;
+use crate::ext::ExtensionFields;
+use crate::message::report_serialized;
+use crate::reflect::required::missing_required_fields;
 use crate::reflect::MessageDescriptor;
 use crate::reflect::ReflectEqMode;
+#[cfg(feature = "bytes")]
+use crate::serialize_pool::write_pooled;
+use crate::validate::ValidationError;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
 use crate::Message;
@@ -12,6 +19,9 @@ use std::any::TypeId;
 use std::fmt;
 use std::io::Write;
 
+#[cfg(feature = "bytes")]
+use bytes::Bytes;
+
 /// Dynamic-dispatch version of [`Message`].
 pub trait MessageDyn: Any + fmt::Debug + Send + Sync + 'static {
     /// Message descriptor for this message, used for reflection.
@@ -26,6 +36,9 @@ pub trait MessageDyn: Any + fmt::Debug + Send + Sync + 'static {
     /// Compute (and cache) the message size.
     fn compute_size_dyn(&self) -> u32;
 
+    /// Get size previously computed by [`compute_size_dyn`](MessageDyn::compute_size_dyn).
+    fn get_cached_size_dyn(&self) -> u32;
+
     /// True iff all required fields are initialized.
     /// Always returns `true` for protobuf 3.
     fn is_initialized_dyn(&self) -> bool;
@@ -34,6 +47,20 @@ pub trait MessageDyn: Any + fmt::Debug + Send + Sync + 'static {
     fn get_unknown_fields_dyn(&self) -> &UnknownFields;
     /// Get a mutable reference to unknown fields.
     fn mut_unknown_fields_dyn(&mut self) -> &mut UnknownFields;
+
+    /// Dynamic-dispatch version of [`Message::get_extension_fields`].
+    fn get_extension_fields_dyn(&self) -> Option<&ExtensionFields>;
+    /// Dynamic-dispatch version of [`Message::mut_extension_fields`].
+    fn mut_extension_fields_dyn(&mut self) -> Option<&mut ExtensionFields>;
+
+    /// Dynamic-dispatch version of [`Message::space_used`].
+    fn space_used_dyn(&self) -> usize;
+
+    /// Dynamic-dispatch version of [`Message::shrink_to_fit`].
+    fn shrink_to_fit_dyn(&mut self);
+
+    /// Dynamic-dispatch version of [`Message::validate`].
+    fn validate_dyn(&self) -> Result<(), ValidationError>;
 }
 
 impl<M: Message> MessageDyn for M {
@@ -53,6 +80,10 @@ impl<M: Message> MessageDyn for M {
         self.compute_size()
     }
 
+    fn get_cached_size_dyn(&self) -> u32 {
+        self.get_cached_size()
+    }
+
     fn is_initialized_dyn(&self) -> bool {
         self.is_initialized()
     }
@@ -64,6 +95,26 @@ impl<M: Message> MessageDyn for M {
     fn mut_unknown_fields_dyn(&mut self) -> &mut UnknownFields {
         self.mut_unknown_fields()
     }
+
+    fn get_extension_fields_dyn(&self) -> Option<&ExtensionFields> {
+        self.get_extension_fields()
+    }
+
+    fn mut_extension_fields_dyn(&mut self) -> Option<&mut ExtensionFields> {
+        self.mut_extension_fields()
+    }
+
+    fn space_used_dyn(&self) -> usize {
+        self.space_used()
+    }
+
+    fn shrink_to_fit_dyn(&mut self) {
+        self.shrink_to_fit()
+    }
+
+    fn validate_dyn(&self) -> Result<(), ValidationError> {
+        self.validate()
+    }
 }
 
 impl dyn MessageDyn {
@@ -71,7 +122,10 @@ impl dyn MessageDyn {
     pub fn check_initialized_dyn(&self) -> ProtobufResult<()> {
         if !self.is_initialized_dyn() {
             Err(ProtobufError::MessageNotInitialized(
-                self.descriptor_dyn().name().to_owned(),
+                MessageNotInitializedError {
+                    message: self.descriptor_dyn().full_name().to_owned(),
+                    missing_fields: missing_required_fields(self),
+                },
             ))
         } else {
             Ok(())
@@ -95,9 +149,10 @@ impl dyn MessageDyn {
         self.check_initialized_dyn()?;
 
         // cache sizes
-        self.compute_size_dyn();
+        let size = self.compute_size_dyn();
         // TODO: reserve additional
         self.write_to_with_cached_sizes_dyn(os)?;
+        report_serialized(size);
 
         Ok(())
     }
@@ -114,7 +169,7 @@ impl dyn MessageDyn {
     /// Update this message object with fields read from given stream.
     pub fn merge_from_bytes_dyn(&mut self, bytes: &[u8]) -> ProtobufResult<()> {
         let mut is = CodedInputStream::from_bytes(bytes);
-        self.merge_from_dyn(&mut is)
+        is.with_stats_report(|is| self.merge_from_dyn(is))
     }
 
     /// Write the message to bytes vec.
@@ -135,9 +190,23 @@ impl dyn MessageDyn {
             self.write_to_with_cached_sizes_dyn(&mut os)?;
             os.check_eof();
         }
+        report_serialized(size as u32);
         Ok(v)
     }
 
+    /// Write the message to `Bytes` using thread-local buffer.
+    ///
+    /// See [`Message::write_to_bytes_pooled`].
+    #[cfg(feature = "bytes")]
+    pub fn write_to_bytes_pooled_dyn(&self) -> ProtobufResult<Bytes> {
+        self.check_initialized_dyn()?;
+
+        let size = self.compute_size_dyn() as usize;
+        let bytes = write_pooled(size, |os| self.write_to_with_cached_sizes_dyn(os))?;
+        report_serialized(size as u32);
+        Ok(bytes)
+    }
+
     /// Write the message to the stream prepending the message with message length
     /// encoded as varint.
     pub fn write_length_delimited_to_dyn(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
@@ -168,9 +237,10 @@ impl dyn MessageDyn {
     ///
     /// ```
     /// # use protobuf::Message;
-    /// # fn foo<MyMessage: Message>(message: Box<dyn Message>) {
-    /// let m: Box<dyn Message> = message;
-    /// let m: Box<MyMessage> = Message::downcast_box(m).unwrap();
+    /// # use protobuf::MessageDyn;
+    /// # fn foo<MyMessage: Message>(message: Box<dyn MessageDyn>) {
+    /// let m: Box<dyn MessageDyn> = message;
+    /// let m: Box<MyMessage> = m.downcast_box().unwrap();
     /// # }
     /// ```
     pub fn downcast_box<T: Any>(self: Box<dyn MessageDyn>) -> Result<Box<T>, Box<dyn MessageDyn>> {
@@ -188,9 +258,10 @@ impl dyn MessageDyn {
     ///
     /// ```
     /// # use protobuf::Message;
-    /// # fn foo<MyMessage: Message>(message: &dyn Message) {
-    /// let m: &dyn Message = message;
-    /// let m: &MyMessage = Message::downcast_ref(m).unwrap();
+    /// # use protobuf::MessageDyn;
+    /// # fn foo<MyMessage: Message>(message: &dyn MessageDyn) {
+    /// let m: &dyn MessageDyn = message;
+    /// let m: &MyMessage = m.downcast_ref().unwrap();
     /// # }
     /// ```
     pub fn downcast_ref<'a, M: Message + 'a>(&'a self) -> Option<&'a M> {
@@ -205,9 +276,10 @@ impl dyn MessageDyn {
     ///
     /// ```
     /// # use protobuf::Message;
-    /// # fn foo<MyMessage: Message>(message: &mut dyn Message) {
-    /// let m: &mut dyn Message = message;
-    /// let m: &mut MyMessage = Message::downcast_mut(m).unwrap();
+    /// # use protobuf::MessageDyn;
+    /// # fn foo<MyMessage: Message>(message: &mut dyn MessageDyn) {
+    /// let m: &mut dyn MessageDyn = message;
+    /// let m: &mut MyMessage = m.downcast_mut().unwrap();
     /// # }
     /// ```
     pub fn downcast_mut<'a, M: Message + 'a>(&'a mut self) -> Option<&'a mut M> {
@@ -218,11 +290,85 @@ impl dyn MessageDyn {
         }
     }
 
+    /// Check if the message is of given generated message type.
+    pub fn is<M: Message>(&self) -> bool {
+        Any::type_id(self) == TypeId::of::<M>()
+    }
+
     /// Clone from a `dyn Message` reference.
     pub fn clone_box(&self) -> Box<dyn MessageDyn> {
         self.descriptor_dyn().clone_message(self)
     }
 
+    /// Merge other message of the same type into this message.
+    ///
+    /// Singular fields set in `other` overwrite fields of `self`,
+    /// repeated fields are appended, like when parsing concatenated messages.
+    ///
+    /// # Panics
+    ///
+    /// If messages are of different types.
+    pub fn merge_from_message_dyn(&mut self, other: &dyn MessageDyn) -> ProtobufResult<()> {
+        let descriptor = self.descriptor_dyn();
+        assert!(
+            descriptor == other.descriptor_dyn(),
+            "cannot merge {} into {}",
+            other.descriptor_dyn().full_name(),
+            descriptor.full_name()
+        );
+        // Merge is allowed for partial messages.
+        let bytes = other.write_to_bytes_partial_dyn()?;
+        self.merge_from_bytes_dyn(&bytes)
+    }
+
+    /// Convert a message to a generated message of the same protobuf type.
+    ///
+    /// This is used to convert a dynamic message (e. g. created with
+    /// [`MessageDescriptor::new_instance`] for a descriptor loaded at runtime)
+    /// to generated message type, types match when their full names are equal.
+    /// Conversion is done by serializing and parsing, unknown fields are preserved,
+    /// and message is not checked to be initialized.
+    ///
+    /// Returns `Ok(None)` when message type does not match.
+    pub fn convert_to<M: Message>(&self) -> ProtobufResult<Option<M>> {
+        if self.downcast_ref::<M>().is_some() {
+            return Ok(Some(*self.clone_box().downcast_box::<M>().unwrap()));
+        }
+        if self.descriptor_dyn().full_name() != M::descriptor_static().full_name() {
+            return Ok(None);
+        }
+        let mut m = M::new();
+        m.merge_from_bytes(&self.write_to_bytes_partial_dyn()?)?;
+        Ok(Some(m))
+    }
+
+    /// Convert a message to a message of given descriptor with the same full name.
+    ///
+    /// This can convert a generated message into a dynamic message and back.
+    /// See [`convert_to`](MessageDyn::convert_to) for details.
+    pub fn convert_to_dyn(
+        &self,
+        descriptor: &MessageDescriptor,
+    ) -> ProtobufResult<Option<Box<dyn MessageDyn>>> {
+        let self_descriptor = self.descriptor_dyn();
+        if &self_descriptor == descriptor {
+            return Ok(Some(self.clone_box()));
+        }
+        if self_descriptor.full_name() != descriptor.full_name() {
+            return Ok(None);
+        }
+        let mut m = descriptor.new_instance();
+        m.merge_from_bytes_dyn(&self.write_to_bytes_partial_dyn()?)?;
+        Ok(Some(m))
+    }
+
+    /// Serialize without initialization check.
+    pub(crate) fn write_to_bytes_partial_dyn(&self) -> ProtobufResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(self.compute_size_dyn() as usize);
+        bytes.with_coded_output_stream(|os| self.write_to_with_cached_sizes_dyn(os))?;
+        Ok(bytes)
+    }
+
     /// Reflectively compare the messages.
     pub fn reflect_eq_dyn(&self, other: &dyn MessageDyn, mode: &ReflectEqMode) -> bool {
         MessageDescriptor::reflect_eq_maybe_unrelated(self, other, mode)
@@ -243,6 +389,7 @@ impl PartialEq for Box<dyn MessageDyn> {
 
 #[cfg(test)]
 mod test {
+    use crate::descriptor::DescriptorProto;
     use crate::descriptor::FileDescriptorProto;
     use crate::MessageDyn;
 
@@ -268,6 +415,39 @@ mod test {
         );
     }
 
+    #[test]
+    fn is() {
+        let m = FileDescriptorProto::new();
+        let d = &m as &dyn MessageDyn;
+        assert!(d.is::<FileDescriptorProto>());
+        assert!(!d.is::<DescriptorProto>());
+    }
+
+    #[test]
+    fn merge_from_message_dyn() {
+        let mut a = FileDescriptorProto::new();
+        a.set_name("a.proto".to_owned());
+        a.dependency.push("x.proto".to_owned());
+        let mut b = FileDescriptorProto::new();
+        b.set_package("b".to_owned());
+        b.dependency.push("y.proto".to_owned());
+
+        let mut m: Box<dyn MessageDyn> = Box::new(a);
+        m.merge_from_message_dyn(&b).unwrap();
+
+        let m: &FileDescriptorProto = m.downcast_ref().unwrap();
+        assert_eq!("a.proto", m.get_name());
+        assert_eq!("b", m.get_package());
+        assert_eq!(vec!["x.proto", "y.proto"], m.dependency);
+    }
+
+    #[test]
+    #[should_panic]
+    fn merge_from_message_dyn_type_mismatch() {
+        let mut m: Box<dyn MessageDyn> = Box::new(FileDescriptorProto::new());
+        m.merge_from_message_dyn(&DescriptorProto::new()).unwrap();
+    }
+
     #[test]
     fn downcast_box() {
         let m = FileDescriptorProto::new();
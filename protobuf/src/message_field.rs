@@ -32,7 +32,7 @@ use crate::Message;
 /// // or using `Option` and `Into`
 /// customer.address = Some(make_address()).into();
 /// ```
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct MessageField<T>(pub Option<Box<T>>);
 
 impl<T> MessageField<T> {
@@ -133,6 +133,51 @@ impl<T> MessageField<T> {
         MessageField::from_option(self.into_option().map(f))
     }
 
+    /// Apply given function to contained data, returning `None` if this object is empty.
+    #[inline]
+    pub fn and_then<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> Option<U>,
+    {
+        self.into_option().and_then(f)
+    }
+
+    /// Transform into `Result`, mapping empty object to `Err(err)`.
+    #[inline]
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        self.into_option().ok_or(err)
+    }
+
+    /// Transform into `Result`, mapping empty object to `Err(err())`.
+    #[inline]
+    pub fn ok_or_else<E, F>(self, err: F) -> Result<T, E>
+    where
+        F: FnOnce() -> E,
+    {
+        self.into_option().ok_or_else(err)
+    }
+
+    /// Store the value, return mutable reference to it.
+    #[inline]
+    pub fn insert(&mut self, value: T) -> &mut T {
+        &mut **self.0.insert(Box::new(value))
+    }
+
+    /// Store the value computed by `f` if empty, return mutable reference to the value.
+    #[inline]
+    pub fn get_or_insert_with<F>(&mut self, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        &mut **self.0.get_or_insert_with(|| Box::new(f()))
+    }
+
+    /// Replace the data with given value, returning the old value if present.
+    #[inline]
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        self.0.replace(Box::new(value)).map(|v| *v)
+    }
+
     /// View data as iterator.
     #[inline]
     pub fn iter(&self) -> option::IntoIter<&T> {
@@ -202,6 +247,30 @@ impl<T> From<Option<T>> for MessageField<T> {
     }
 }
 
+impl<T> From<MessageField<T>> for Option<T> {
+    fn from(field: MessageField<T>) -> Self {
+        field.into_option()
+    }
+}
+
+impl<T> IntoIterator for MessageField<T> {
+    type Item = T;
+    type IntoIter = option::IntoIter<T>;
+
+    fn into_iter(self) -> option::IntoIter<T> {
+        self.into_option().into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut MessageField<T> {
+    type Item = &'a mut T;
+    type IntoIter = option::IntoIter<&'a mut T>;
+
+    fn into_iter(self) -> option::IntoIter<&'a mut T> {
+        self.mut_iter()
+    }
+}
+
 impl<'a, T> IntoIterator for &'a MessageField<T> {
     type Item = &'a T;
     type IntoIter = option::IntoIter<&'a T>;
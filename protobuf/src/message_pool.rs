@@ -0,0 +1,221 @@
+//! Pool of reusable messages.
+//!
+//! Request-scoped workloads (parse a request, serve it, drop everything)
+//! spend a lot of time in the allocator: each string, repeated field
+//! and nested message of every parsed message is a separate allocation.
+//!
+//! [`MessagePool`] owns message objects and hands out mutable references to them.
+//! All messages are released at once by [`MessagePool::reset`], which does not
+//! free memory, but clears messages keeping allocated capacity
+//! (see [`Clear`](crate::Clear)), so after a few requests parsing into
+//! pooled messages performs almost no allocations.
+//!
+//! This is not an arena allocator: each message and its fields
+//! are still allocated with the global allocator when first used.
+//!
+//! ```
+//! use protobuf::message_pool::MessagePool;
+//! use protobuf::well_known_types::StringValue;
+//! # use protobuf::Message;
+//! # let requests = vec![StringValue::new().write_to_bytes().unwrap()];
+//!
+//! let mut pool = MessagePool::new();
+//! for request in &requests {
+//!     let m: &mut StringValue = pool.parse_from_bytes(request).unwrap();
+//!     // serve the request
+//!     # drop(m);
+//!     pool.reset();
+//! }
+//! ```
+//!
+//! Any generated messages can be taken from the pool, no special
+//! code generation options are needed.
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::ProtobufResult;
+
+struct Pool {
+    // Owned messages, pointers from `Box::into_raw`
+    messages: Vec<*mut dyn MessageDyn>,
+    // Number of messages handed out since last reset
+    used: usize,
+    clear: fn(&mut dyn MessageDyn),
+}
+
+// Messages are `Send`, pointers are owned
+unsafe impl Send for Pool {}
+
+impl Drop for Pool {
+    fn drop(&mut self) {
+        for &message in &self.messages {
+            unsafe {
+                drop(Box::from_raw(message));
+            }
+        }
+    }
+}
+
+fn clear_message<M: Message>(message: &mut dyn MessageDyn) {
+    message.downcast_mut::<M>().unwrap().clear();
+}
+
+/// Pool of messages.
+///
+/// Messages are never moved or dropped while references returned by
+/// [`alloc`](MessagePool::alloc) are alive, so pool can allocate through
+/// shared reference, and several messages can be used at the same time.
+#[derive(Default)]
+pub struct MessagePool {
+    pools: RefCell<HashMap<TypeId, Pool>>,
+}
+
+impl fmt::Debug for MessagePool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MessagePool")
+            .field("allocated", &self.allocated())
+            .finish()
+    }
+}
+
+impl MessagePool {
+    /// Create an empty pool.
+    pub fn new() -> MessagePool {
+        MessagePool::default()
+    }
+
+    /// Get an empty message from the pool.
+    ///
+    /// Message is reused if it was allocated before last [`reset`](MessagePool::reset).
+    // Returning `&mut` from `&self` is what allows using several
+    // messages at once, see the safety comment below.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<M: Message>(&self) -> &mut M {
+        let mut pools = self.pools.borrow_mut();
+        let pool = pools.entry(TypeId::of::<M>()).or_insert_with(|| Pool {
+            messages: Vec::new(),
+            used: 0,
+            clear: clear_message::<M>,
+        });
+        if pool.used == pool.messages.len() {
+            let message: Box<dyn MessageDyn> = Box::new(M::new());
+            pool.messages.push(Box::into_raw(message));
+        }
+        let message = pool.messages[pool.used];
+        pool.used += 1;
+        // SAFETY: the pointer is owned by the pool and stays valid until
+        // `free` or drop, which require exclusive access to the pool, like `reset`.
+        // Boxed messages are not moved when `messages` grows,
+        // and each message is handed out at most once between resets,
+        // so the returned reference is unique for the lifetime of `&self`.
+        unsafe { (*message).downcast_mut::<M>().unwrap() }
+    }
+
+    /// Parse a message from bytes into a message taken from the pool.
+    pub fn parse_from_bytes<M: Message>(&self, bytes: &[u8]) -> ProtobufResult<&mut M> {
+        let message = self.alloc::<M>();
+        message.merge_from_bytes(bytes)?;
+        message.check_initialized()?;
+        Ok(message)
+    }
+
+    /// Return all messages to the pool.
+    ///
+    /// Messages are cleared but not freed, so subsequent allocations reuse
+    /// both message objects and buffers of their fields.
+    pub fn reset(&mut self) {
+        for pool in self.pools.get_mut().values_mut() {
+            for &message in &pool.messages[..pool.used] {
+                (pool.clear)(unsafe { &mut *message });
+            }
+            pool.used = 0;
+        }
+    }
+
+    /// Free all messages and memory owned by the pool.
+    pub fn free(&mut self) {
+        self.pools.get_mut().clear();
+    }
+
+    /// Number of message objects owned by the pool, used or not.
+    pub fn allocated(&self) -> usize {
+        self.pools.borrow().values().map(|p| p.messages.len()).sum()
+    }
+
+    /// Estimate memory owned by the pool,
+    /// see [`Message::space_used`](crate::Message::space_used).
+    pub fn space_used(&mut self) -> usize {
+        self.pools
+            .get_mut()
+            .values()
+            .flat_map(|p| &p.messages)
+            .map(|&m| unsafe { (*m).space_used_dyn() })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::ListValue;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn alloc_multiple() {
+        let pool = MessagePool::new();
+        let a = pool.alloc::<Duration>();
+        let b = pool.alloc::<Duration>();
+        let v = pool.alloc::<Value>();
+        a.seconds = 1;
+        b.seconds = 2;
+        v.set_number_value(3.0);
+        assert_eq!(1, a.seconds);
+        assert_eq!(2, b.seconds);
+        assert_eq!(3, pool.allocated());
+    }
+
+    #[test]
+    fn reset_reuses_messages() {
+        let mut list = ListValue::new();
+        for _ in 0..100 {
+            let mut v = Value::new();
+            v.set_string_value("x".repeat(10));
+            list.values.push(v);
+        }
+        let bytes = list.write_to_bytes().unwrap();
+
+        let mut pool = MessagePool::new();
+        let m: &mut ListValue = pool.parse_from_bytes(&bytes).unwrap();
+        assert_eq!(list, *m);
+        let m: *const ListValue = m;
+        pool.reset();
+        assert_eq!(1, pool.allocated());
+
+        let reused = pool.alloc::<ListValue>();
+        assert_eq!(m, reused as *const ListValue);
+        assert_eq!(ListValue::new(), *reused);
+        assert!(reused.values.capacity() >= 100);
+    }
+
+    #[test]
+    fn parse_error() {
+        let pool = MessagePool::new();
+        assert!(pool.parse_from_bytes::<Duration>(&[0xff]).is_err());
+    }
+
+    #[test]
+    fn free() {
+        let mut pool = MessagePool::new();
+        pool.alloc::<Duration>();
+        assert!(pool.space_used() > 0);
+        pool.free();
+        assert_eq!(0, pool.allocated());
+        assert_eq!(0, pool.space_used());
+    }
+}
@@ -143,6 +143,13 @@ impl Version {
         self.suffix.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_suffix(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_suffix();
+        self.set_suffix(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_option_get_copy_simpler_accessor::<_, _>(
@@ -260,6 +267,25 @@ impl crate::Message for Version {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.major);
+        my_size += crate::rt::space_used_excluding_self(&self.minor);
+        my_size += crate::rt::space_used_excluding_self(&self.patch);
+        my_size += crate::rt::space_used_excluding_self(&self.suffix);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.major);
+        crate::rt::shrink_to_fit(&mut self.minor);
+        crate::rt::shrink_to_fit(&mut self.patch);
+        crate::rt::shrink_to_fit(&mut self.suffix);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -289,6 +315,23 @@ impl crate::Message for Version {
     }
 }
 
+impl crate::rt::SpaceUsed for Version {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Version {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Version {
+    fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+    }
+}
+
 impl crate::Clear for Version {
     fn clear(&mut self) {
         self.major = ::std::option::Option::None;
@@ -391,6 +434,13 @@ impl CodeGeneratorRequest {
         self.parameter.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_parameter(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_parameter();
+        self.set_parameter(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_vec_simpler_accessor::<_, _>(
@@ -508,6 +558,25 @@ impl crate::Message for CodeGeneratorRequest {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.file_to_generate);
+        my_size += crate::rt::space_used_excluding_self(&self.parameter);
+        my_size += crate::rt::space_used_excluding_self(&self.proto_file);
+        my_size += crate::rt::space_used_excluding_self(&self.compiler_version);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.file_to_generate);
+        crate::rt::shrink_to_fit(&mut self.parameter);
+        crate::rt::shrink_to_fit(&mut self.proto_file);
+        crate::rt::shrink_to_fit(&mut self.compiler_version);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -537,6 +606,25 @@ impl crate::Message for CodeGeneratorRequest {
     }
 }
 
+impl crate::rt::SpaceUsed for CodeGeneratorRequest {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for CodeGeneratorRequest {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for CodeGeneratorRequest {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.proto_file, worklist);
+        crate::rt::detach_nested_messages(&mut self.compiler_version, worklist);
+    }
+}
+
 impl crate::Clear for CodeGeneratorRequest {
     fn clear(&mut self) {
         self.file_to_generate.clear();
@@ -629,6 +717,13 @@ impl CodeGeneratorResponse {
         self.error.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_error(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_error();
+        self.set_error(v);
+        r
+    }
+
     // optional uint64 supported_features = 2;
 
     pub fn get_supported_features(&self) -> u64 {
@@ -749,6 +844,23 @@ impl crate::Message for CodeGeneratorResponse {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.error);
+        my_size += crate::rt::space_used_excluding_self(&self.supported_features);
+        my_size += crate::rt::space_used_excluding_self(&self.file);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.error);
+        crate::rt::shrink_to_fit(&mut self.supported_features);
+        crate::rt::shrink_to_fit(&mut self.file);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -777,6 +889,24 @@ impl crate::Message for CodeGeneratorResponse {
     }
 }
 
+impl crate::rt::SpaceUsed for CodeGeneratorResponse {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for CodeGeneratorResponse {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for CodeGeneratorResponse {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.file, worklist);
+    }
+}
+
 impl crate::Clear for CodeGeneratorResponse {
     fn clear(&mut self) {
         self.error = ::std::option::Option::None;
@@ -913,6 +1043,13 @@ pub mod code_generator_response {
             self.name.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_name(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_name();
+            self.set_name(v);
+            r
+        }
+
         // optional string insertion_point = 2;
 
         pub fn get_insertion_point(&self) -> &str {
@@ -949,6 +1086,13 @@ pub mod code_generator_response {
             self.insertion_point.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_insertion_point(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_insertion_point();
+            self.set_insertion_point(v);
+            r
+        }
+
         // optional string content = 15;
 
         pub fn get_content(&self) -> &str {
@@ -985,6 +1129,13 @@ pub mod code_generator_response {
             self.content.take().unwrap_or_else(|| ::std::string::String::new())
         }
 
+        // Replace field value, returning the previous one
+        pub fn replace_content(&mut self, v: ::std::string::String) -> ::std::string::String {
+            let r = self.take_content();
+            self.set_content(v);
+            r
+        }
+
         pub(in super) fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
             let mut fields = ::std::vec::Vec::new();
             fields.push(crate::reflect::rt::v2::make_option_get_ref_simpler_accessor::<_, _>(
@@ -1104,6 +1255,25 @@ pub mod code_generator_response {
             self.cached_size.get()
         }
 
+        #[allow(unused_variables)]
+        fn space_used_excluding_self(&self) -> usize {
+            let mut my_size = 0;
+            my_size += crate::rt::space_used_excluding_self(&self.name);
+            my_size += crate::rt::space_used_excluding_self(&self.insertion_point);
+            my_size += crate::rt::space_used_excluding_self(&self.content);
+            my_size += crate::rt::space_used_excluding_self(&self.generated_code_info);
+            my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+            my_size
+        }
+
+        fn shrink_to_fit(&mut self) {
+            crate::rt::shrink_to_fit(&mut self.name);
+            crate::rt::shrink_to_fit(&mut self.insertion_point);
+            crate::rt::shrink_to_fit(&mut self.content);
+            crate::rt::shrink_to_fit(&mut self.generated_code_info);
+            crate::rt::shrink_to_fit(&mut self.unknown_fields);
+        }
+
         fn get_unknown_fields(&self) -> &crate::UnknownFields {
             &self.unknown_fields
         }
@@ -1133,6 +1303,24 @@ pub mod code_generator_response {
         }
     }
 
+    impl crate::rt::SpaceUsed for File {
+        fn space_used_excluding_self(&self) -> usize {
+            crate::Message::space_used_excluding_self(self)
+        }
+    }
+
+    impl crate::rt::ShrinkToFit for File {
+        fn shrink_to_fit(&mut self) {
+            crate::Message::shrink_to_fit(self)
+        }
+    }
+
+    impl crate::rt::DropIterative for File {
+        fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+            crate::rt::detach_nested_messages(&mut self.generated_code_info, worklist);
+        }
+    }
+
     impl crate::Clear for File {
         fn clear(&mut self) {
             self.name = ::std::option::Option::None;
@@ -1174,6 +1362,21 @@ pub mod code_generator_response {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Feature::FEATURE_NONE => "FEATURE_NONE",
+                Feature::FEATURE_PROTO3_OPTIONAL => "FEATURE_PROTO3_OPTIONAL",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<Feature> {
+            match name {
+                "FEATURE_NONE" => ::std::option::Option::Some(Feature::FEATURE_NONE),
+                "FEATURE_PROTO3_OPTIONAL" => ::std::option::Option::Some(Feature::FEATURE_PROTO3_OPTIONAL),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Feature] = &[
                 Feature::FEATURE_NONE,
@@ -1193,6 +1396,12 @@ pub mod code_generator_response {
         }
     }
 
+    impl ::std::fmt::Display for Feature {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Feature {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -1478,3 +1687,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
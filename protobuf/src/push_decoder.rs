@@ -0,0 +1,287 @@
+//! Push-based incremental decoder of length-delimited messages.
+//!
+//! [`PushDecoder`] accepts data in chunks as it arrives from the network,
+//! for example in non-async event loop, and parses messages written with
+//! [`Message::write_length_delimited_to`](crate::Message::write_length_delimited_to).
+//!
+//! The message is parsed while it is received: each complete top-level
+//! field is merged into the message as soon as it is available,
+//! so only the currently incomplete field is buffered.
+
+use crate::coded_input_stream::CodedInputStream;
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::message::Message;
+use crate::wire::decode_tag;
+use crate::wire::decode_varint64;
+use crate::wire_format::WireType;
+
+/// Max depth of nested groups in a field.
+const MAX_GROUP_DEPTH: u32 = 100;
+
+/// Result of [`PushDecoder::push`].
+#[derive(Debug, PartialEq)]
+pub enum Status<M> {
+    /// Message is not complete yet.
+    NeedMore,
+    /// Message is decoded.
+    Complete(M),
+}
+
+#[derive(Debug)]
+enum State<M> {
+    Length,
+    Body { message: M, remaining: u64 },
+}
+
+/// Resumable decoder of a stream of length-delimited messages.
+///
+/// ```
+/// # use protobuf::Message;
+/// # use protobuf::push_decoder::PushDecoder;
+/// # use protobuf::push_decoder::Status;
+/// # use protobuf::well_known_types::Duration;
+/// let mut d = Duration::new();
+/// d.seconds = 10;
+/// let data = d.write_length_delimited_to_bytes().unwrap();
+///
+/// let mut decoder = PushDecoder::<Duration>::new();
+/// assert_eq!(Status::NeedMore, decoder.push(&data[..2]).unwrap());
+/// assert_eq!(Status::Complete(d), decoder.push(&data[2..]).unwrap());
+/// assert!(decoder.is_empty());
+/// ```
+#[derive(Debug)]
+pub struct PushDecoder<M: Message> {
+    buf: Vec<u8>,
+    pos: usize,
+    state: State<M>,
+}
+
+impl<M: Message> Default for PushDecoder<M> {
+    fn default() -> Self {
+        PushDecoder {
+            buf: Vec::new(),
+            pos: 0,
+            state: State::Length,
+        }
+    }
+}
+
+impl<M: Message> PushDecoder<M> {
+    /// New decoder.
+    pub fn new() -> PushDecoder<M> {
+        PushDecoder::default()
+    }
+
+    /// Append received data and decode as much as possible.
+    ///
+    /// Returns at most one message. When data contains the following messages,
+    /// their bytes are retained, call `push(&[])` to decode them.
+    ///
+    /// Decoder should not be used after an error.
+    pub fn push(&mut self, data: &[u8]) -> ProtobufResult<Status<M>> {
+        if self.pos != 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(data);
+
+        loop {
+            match &mut self.state {
+                State::Length => {
+                    let (len, consumed) = match decode_varint64(&self.buf[self.pos..])? {
+                        Some(r) => r,
+                        None => return Ok(Status::NeedMore),
+                    };
+                    self.pos += consumed;
+                    self.state = State::Body {
+                        message: M::new(),
+                        remaining: len,
+                    };
+                }
+                State::Body { message, remaining } => {
+                    let rem = &self.buf[self.pos..];
+                    let avail = if (rem.len() as u64) < *remaining {
+                        rem.len()
+                    } else {
+                        *remaining as usize
+                    };
+                    let rem = &rem[..avail];
+
+                    let mut complete = 0;
+                    while let Some(len) = field_len(&rem[complete..], 0)? {
+                        complete += len;
+                    }
+                    if complete != 0 {
+                        message.merge_from(&mut CodedInputStream::from_bytes(&rem[..complete]))?;
+                        self.pos += complete;
+                        *remaining -= complete as u64;
+                    }
+
+                    if *remaining == 0 {
+                        let message = match std::mem::replace(&mut self.state, State::Length) {
+                            State::Body { message, .. } => message,
+                            State::Length => unreachable!(),
+                        };
+                        message.check_initialized()?;
+                        return Ok(Status::Complete(message));
+                    }
+                    if (avail - complete) as u64 == *remaining {
+                        // Last field crosses the message boundary
+                        return Err(ProtobufError::WireError(WireError::TruncatedMessage));
+                    }
+                    return Ok(Status::NeedMore);
+                }
+            }
+        }
+    }
+
+    /// Number of buffered bytes not yet merged into a message.
+    pub fn buffered_len(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// No partially decoded message.
+    ///
+    /// At the end of stream, non-empty decoder means truncated message.
+    pub fn is_empty(&self) -> bool {
+        match self.state {
+            State::Length => self.buffered_len() == 0,
+            State::Body { .. } => false,
+        }
+    }
+}
+
+/// Length of the field at the beginning of the buffer,
+/// `None` if the buffer does not contain complete field.
+fn field_len(buf: &[u8], depth: u32) -> ProtobufResult<Option<usize>> {
+    let (tag, mut pos) = match decode_tag(buf)? {
+        Some(r) => r,
+        None => return Ok(None),
+    };
+    let end = match tag.unpack().1 {
+        WireType::WireTypeVarint => match decode_varint64(&buf[pos..])? {
+            Some((_, len)) => pos + len,
+            None => return Ok(None),
+        },
+        WireType::WireTypeFixed32 => pos + 4,
+        WireType::WireTypeFixed64 => pos + 8,
+        WireType::WireTypeLengthDelimited => match decode_varint64(&buf[pos..])? {
+            Some((len, len_len)) => {
+                let rem = (buf.len() - pos - len_len) as u64;
+                if len > rem {
+                    return Ok(None);
+                }
+                pos + len_len + len as usize
+            }
+            None => return Ok(None),
+        },
+        WireType::WireTypeStartGroup => {
+            if depth >= MAX_GROUP_DEPTH {
+                return Err(ProtobufError::WireError(WireError::OverRecursionLimit));
+            }
+            loop {
+                match decode_tag(&buf[pos..])? {
+                    Some((end_tag, len)) if end_tag.unpack().1 == WireType::WireTypeEndGroup => {
+                        if end_tag.field_number() != tag.field_number() {
+                            return Err(ProtobufError::WireError(WireError::UnexpectedWireType(
+                                WireType::WireTypeEndGroup,
+                            )));
+                        }
+                        break pos + len;
+                    }
+                    Some(..) => match field_len(&buf[pos..], depth + 1)? {
+                        Some(len) => pos += len,
+                        None => return Ok(None),
+                    },
+                    None => return Ok(None),
+                }
+            }
+        }
+        WireType::WireTypeEndGroup => {
+            return Err(ProtobufError::WireError(WireError::UnexpectedWireType(
+                WireType::WireTypeEndGroup,
+            )))
+        }
+    };
+    if end > buf.len() {
+        return Ok(None);
+    }
+    Ok(Some(end))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    fn messages() -> Vec<Struct> {
+        let mut r = Vec::new();
+        for i in 0..3 {
+            let mut s = Struct::new();
+            for j in 0..i {
+                let mut v = Value::new();
+                v.set_string_value(format!("value {}", j));
+                s.fields.insert(format!("key {}", j), v);
+            }
+            r.push(s);
+        }
+        r
+    }
+
+    #[test]
+    fn byte_by_byte() {
+        let mut data = Vec::new();
+        for m in messages() {
+            m.write_length_delimited_to_vec(&mut data).unwrap();
+        }
+
+        let mut decoder = PushDecoder::<Struct>::new();
+        let mut decoded = Vec::new();
+        for b in &data {
+            if let Status::Complete(m) = decoder.push(&[*b]).unwrap() {
+                decoded.push(m);
+            }
+            assert!(decoder.buffered_len() < 20);
+        }
+        assert!(decoder.is_empty());
+        assert_eq!(messages(), decoded);
+    }
+
+    #[test]
+    fn several_messages_in_chunk() {
+        let mut data = Vec::new();
+        for m in messages() {
+            m.write_length_delimited_to_vec(&mut data).unwrap();
+        }
+
+        let mut decoder = PushDecoder::<Struct>::new();
+        let mut decoded = Vec::new();
+        let mut status = decoder.push(&data).unwrap();
+        while let Status::Complete(m) = status {
+            decoded.push(m);
+            status = decoder.push(&[]).unwrap();
+        }
+        assert!(decoder.is_empty());
+        assert_eq!(messages(), decoded);
+    }
+
+    #[test]
+    fn group_len() {
+        // Group field 1 with nested varint field 2
+        let group = [0x0b, 0x10, 0x01, 0x0c, 0x08];
+        assert_eq!(Some(4), field_len(&group, 0).unwrap());
+        assert_eq!(None, field_len(&group[..3], 0).unwrap());
+        assert!(field_len(&[0x0b, 0x14], 0).is_err());
+    }
+
+    #[test]
+    fn field_crosses_message_boundary() {
+        // Length 1, but varint field takes 2 bytes
+        let mut decoder = PushDecoder::<Duration>::new();
+        assert!(decoder.push(&[1, 0x08, 0x05]).is_err());
+    }
+}
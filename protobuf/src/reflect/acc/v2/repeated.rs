@@ -11,6 +11,7 @@ use crate::reflect::repeated::ReflectRepeatedMut;
 use crate::reflect::repeated::ReflectRepeatedRef;
 use crate::reflect::ProtobufValue;
 use crate::reflect::RuntimeTypeBox;
+use crate::SmallVec;
 
 pub(crate) trait RepeatedFieldAccessor: Send + Sync + 'static {
     fn get_repeated<'a>(&self, m: &'a dyn MessageDyn) -> ReflectRepeatedRef<'a>;
@@ -58,6 +59,21 @@ where
     }
 }
 
+impl<M, V, const N: usize> RepeatedFieldGetMut<M, dyn ReflectRepeated>
+    for RepeatedFieldGetMutImpl<M, SmallVec<V, N>>
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+{
+    fn get_field<'a>(&self, m: &'a M) -> &'a dyn ReflectRepeated {
+        (self.get_field)(m) as &dyn ReflectRepeated
+    }
+
+    fn mut_field<'a>(&self, m: &'a mut M) -> &'a mut dyn ReflectRepeated {
+        (self.mut_field)(m) as &mut dyn ReflectRepeated
+    }
+}
+
 struct RepeatedFieldAccessorImpl<M, V>
 where
     M: Message,
@@ -112,3 +128,27 @@ where
         }),
     )
 }
+
+/// Make accessor for `SmallVec` field
+pub fn make_small_vec_simpler_accessor<M, V, const N: usize>(
+    name: &'static str,
+    get_vec: for<'a> fn(&'a M) -> &'a SmallVec<V, N>,
+    mut_vec: for<'a> fn(&'a mut M) -> &'a mut SmallVec<V, N>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: ProtobufValue,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Repeated(RepeatedFieldAccessorHolder {
+            accessor: Box::new(RepeatedFieldAccessorImpl::<M, V> {
+                fns: Box::new(RepeatedFieldGetMutImpl::<M, SmallVec<V, N>> {
+                    get_field: get_vec,
+                    mut_field: mut_vec,
+                }),
+                _marker: marker::PhantomData::<V>,
+            }),
+        }),
+    )
+}
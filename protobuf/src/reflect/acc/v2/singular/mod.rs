@@ -1,5 +1,6 @@
 use std::fmt;
 use std::marker;
+use std::sync::Arc;
 
 use crate::enums::ProtobufEnum;
 use crate::enums::ProtobufEnumOrUnknown;
@@ -23,6 +24,7 @@ trait OptionLike<T> {
     fn as_option_ref(&self) -> Option<&T>;
     fn as_option_mut(&mut self) -> Option<&mut T>;
     fn set_value(&mut self, value: T);
+    fn clear_value(&mut self);
 }
 
 impl<T> OptionLike<T> for Option<T> {
@@ -37,6 +39,10 @@ impl<T> OptionLike<T> for Option<T> {
     fn set_value(&mut self, value: T) {
         *self = Some(value);
     }
+
+    fn clear_value(&mut self) {
+        *self = None;
+    }
 }
 
 impl<T> OptionLike<T> for MessageField<T> {
@@ -51,6 +57,47 @@ impl<T> OptionLike<T> for MessageField<T> {
     fn set_value(&mut self, value: T) {
         *self = MessageField::some(value);
     }
+
+    fn clear_value(&mut self) {
+        *self = MessageField::none();
+    }
+}
+
+impl<T> OptionLike<T> for Option<Box<T>> {
+    fn as_option_ref(&self) -> Option<&T> {
+        self.as_deref()
+    }
+
+    fn as_option_mut(&mut self) -> Option<&mut T> {
+        self.as_deref_mut()
+    }
+
+    fn set_value(&mut self, value: T) {
+        *self = Some(Box::new(value));
+    }
+
+    fn clear_value(&mut self) {
+        *self = None;
+    }
+}
+
+impl<T: Clone> OptionLike<T> for Option<Arc<T>> {
+    fn as_option_ref(&self) -> Option<&T> {
+        self.as_deref()
+    }
+
+    /// Shared value is cloned.
+    fn as_option_mut(&mut self) -> Option<&mut T> {
+        self.as_mut().map(Arc::make_mut)
+    }
+
+    fn set_value(&mut self, value: T) {
+        *self = Some(Arc::new(value));
+    }
+
+    fn clear_value(&mut self) {
+        *self = None;
+    }
 }
 
 /// This trait should not be used directly, use `FieldDescriptor` instead
@@ -59,6 +106,7 @@ pub(crate) trait SingularFieldAccessor: Send + Sync + 'static {
     fn get_field_or_default<'a>(&self, m: &'a dyn MessageDyn) -> ReflectValueRef<'a>;
     fn mut_field_or_default<'a>(&self, m: &'a mut dyn MessageDyn) -> ReflectValueMut<'a>;
     fn set_field(&self, m: &mut dyn MessageDyn, value: ReflectValueBox);
+    fn clear_field(&self, m: &mut dyn MessageDyn);
 }
 
 pub(crate) struct SingularFieldAccessorHolder {
@@ -85,6 +133,7 @@ trait MutOrDefaultImpl<M>: Send + Sync + 'static {
 
 trait SetImpl<M>: Send + Sync + 'static {
     fn set_singular_field(&self, m: &mut M, value: ReflectValueBox);
+    fn clear_singular_field(&self, m: &mut M);
 }
 
 struct MutOrDefaultUnmplemented<M>
@@ -160,6 +209,11 @@ where
         let m = m.downcast_mut().unwrap();
         self.set_impl.set_singular_field(m, value)
     }
+
+    fn clear_field(&self, m: &mut dyn MessageDyn) {
+        let m = m.downcast_mut().unwrap();
+        self.set_impl.clear_singular_field(m)
+    }
 }
 
 struct GetOptionImplFieldPointer<M, V>
@@ -432,6 +486,9 @@ where
     V: ProtobufValue,
 {
     set_field: for<'a> fn(&'a mut M, V),
+    has_field: fn(&M) -> bool,
+    // Clears the whole oneof
+    clear_field: fn(&mut M),
 }
 
 impl<M, V> SetImpl<M> for SetImplFieldPointer<M, V>
@@ -442,6 +499,10 @@ where
     fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) {
         V::set_from_value_box((self.mut_field)(m), value);
     }
+
+    fn clear_singular_field(&self, m: &mut M) {
+        *(self.mut_field)(m) = V::default();
+    }
 }
 
 impl<M, V, O> SetImpl<M> for SetImplOptionFieldPointer<M, V, O>
@@ -453,6 +514,10 @@ where
     fn set_singular_field(&self, m: &mut M, value: ReflectValueBox) {
         (self.mut_field)(m).set_value(V::from_value_box(value).expect("wrong type"));
     }
+
+    fn clear_singular_field(&self, m: &mut M) {
+        (self.mut_field)(m).clear_value();
+    }
 }
 
 impl<M, V> SetImpl<M> for SetImplSetField<M, V>
@@ -464,6 +529,12 @@ where
         let value = value.downcast::<V>().expect("message");
         (self.set_field)(m, value)
     }
+
+    fn clear_singular_field(&self, m: &mut M) {
+        if (self.has_field)(m) {
+            (self.clear_field)(m)
+        }
+    }
 }
 
 /// Make accessor for `SingularPtrField`
@@ -502,6 +573,114 @@ where
     )
 }
 
+/// Make accessor for `Option<M>` message field
+pub fn make_option_message_accessor<M, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a Option<V>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<V>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: Message + ProtobufValue + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+                get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                get_or_default_impl: GetOrDefaultOptionRefTypeDefault::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                mut_or_default_impl: MutOrDefaultOptionMut::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                set_impl: SetImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
+/// Make accessor for `Option<Box<M>>` message field
+pub fn make_option_box_message_accessor<M, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a Option<Box<V>>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<Box<V>>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: Message + ProtobufValue + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+                get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                get_or_default_impl: GetOrDefaultOptionRefTypeDefault::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                mut_or_default_impl: MutOrDefaultOptionMut::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                set_impl: SetImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
+/// Make accessor for `Option<Arc<M>>` message field
+pub fn make_option_arc_message_accessor<M, V>(
+    name: &'static str,
+    get_field: for<'a> fn(&'a M) -> &'a Option<Arc<V>>,
+    mut_field: for<'a> fn(&'a mut M) -> &'a mut Option<Arc<V>>,
+) -> FieldAccessor
+where
+    M: Message + 'static,
+    V: Message + Clone + ProtobufValue + 'static,
+{
+    FieldAccessor::new_v2(
+        name,
+        AccessorV2::Singular(SingularFieldAccessorHolder {
+            accessor: Box::new(SingularFieldAccessorImpl::<M, V, _, _, _, _> {
+                get_option_impl: GetOptionImplOptionFieldPointer::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                get_or_default_impl: GetOrDefaultOptionRefTypeDefault::<M, V, _> {
+                    get_field,
+                    _marker: marker::PhantomData,
+                },
+                mut_or_default_impl: MutOrDefaultOptionMut::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                set_impl: SetImplOptionFieldPointer::<M, V, _> {
+                    mut_field,
+                    _marker: marker::PhantomData,
+                },
+                _marker: marker::PhantomData,
+            }),
+        }),
+    )
+}
+
 /// Make accessor for `Option<C>` field
 pub fn make_option_get_copy_simpler_accessor<M, V>(
     name: &'static str,
@@ -23,6 +23,7 @@ pub fn make_oneof_message_has_get_mut_set_accessor<M, F>(
     get_field: for<'a> fn(&'a M) -> &'a F,
     mut_field: for<'a> fn(&'a mut M) -> &'a mut F,
     set_field: fn(&mut M, F),
+    clear_field: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -38,7 +39,11 @@ where
                 },
                 get_or_default_impl: GetOrDefaultGetRef::<M, F> { get_field },
                 mut_or_default_impl: MutOrDefaultGetMut::<M, F> { mut_field },
-                set_impl: SetImplSetField::<M, F> { set_field },
+                set_impl: SetImplSetField::<M, F> {
+                    set_field,
+                    has_field,
+                    clear_field,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -51,6 +56,7 @@ pub fn make_oneof_copy_has_get_set_simpler_accessors<M, V>(
     has: fn(&M) -> bool,
     get: fn(&M) -> V,
     set: fn(&mut M, V),
+    clear: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -63,7 +69,11 @@ where
                 get_option_impl: GetOptionImplHasGetCopy::<M, V> { has, get },
                 get_or_default_impl: GetOrDefaultGetCopy::<M, V> { get_field: get },
                 mut_or_default_impl: MutOrDefaultUnmplemented::new(),
-                set_impl: SetImplSetField::<M, V> { set_field: set },
+                set_impl: SetImplSetField::<M, V> {
+                    set_field: set,
+                    has_field: has,
+                    clear_field: clear,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
@@ -76,6 +86,7 @@ pub fn make_oneof_deref_has_get_set_simpler_accessor<M, F>(
     has: fn(&M) -> bool,
     get: for<'a> fn(&'a M) -> &'a <F::RuntimeType as RuntimeTypeWithDeref>::DerefTarget,
     set: fn(&mut M, F),
+    clear: fn(&mut M),
 ) -> FieldAccessor
 where
     M: Message + 'static,
@@ -89,7 +100,11 @@ where
                 get_option_impl: GetOptionImplHasGetRefDeref::<M, F> { has, get },
                 get_or_default_impl: GetOrDefaultGetRefDeref::<M, F> { get_field: get },
                 mut_or_default_impl: MutOrDefaultUnmplemented::new(),
-                set_impl: SetImplSetField::<M, F> { set_field: set },
+                set_impl: SetImplSetField::<M, F> {
+                    set_field: set,
+                    has_field: has,
+                    clear_field: clear,
+                },
                 _marker: marker::PhantomData,
             }),
         }),
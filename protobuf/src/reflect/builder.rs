@@ -0,0 +1,314 @@
+//! Construction of descriptors in code without `.proto` files.
+
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::EnumDescriptorProto;
+use crate::descriptor::EnumValueDescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::OneofDescriptorProto;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageFactory;
+
+/// Builder of a `.proto` file.
+///
+/// Result is a dynamic [`FileDescriptor`], messages of which
+/// are instantiated as dynamic messages.
+///
+/// ```
+/// # use protobuf::descriptor::field_descriptor_proto::Type;
+/// # use protobuf::reflect::FieldBuilder;
+/// # use protobuf::reflect::FileDescriptorBuilder;
+/// # use protobuf::reflect::MessageBuilder;
+/// # use protobuf::reflect::MessageFactory;
+/// # use protobuf::reflect::ReflectValueBox;
+/// let factory = MessageFactory::new();
+/// let file = FileDescriptorBuilder::new("point.proto")
+///     .package("geo")
+///     .message(
+///         MessageBuilder::new("Point")
+///             .field(FieldBuilder::new("x", 1, Type::TYPE_INT32))
+///             .field(FieldBuilder::new("y", 2, Type::TYPE_INT32)),
+///     )
+///     .register(&factory);
+///
+/// let mut point = factory.new_by_name("geo.Point").unwrap();
+/// let x = file.message_by_package_relative_name("Point").unwrap()
+///     .get_field_by_name("x").unwrap();
+/// x.set_singular_field(&mut *point, ReflectValueBox::I32(10));
+/// assert_eq!(vec![0x08, 10], point.write_to_bytes_dyn().unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileDescriptorBuilder {
+    proto: FileDescriptorProto,
+    dependencies: Vec<FileDescriptor>,
+}
+
+impl FileDescriptorBuilder {
+    /// Start a file with given name like `dir/file.proto` and `proto2` syntax.
+    pub fn new(name: &str) -> FileDescriptorBuilder {
+        let mut proto = FileDescriptorProto::new();
+        proto.set_name(name.to_owned());
+        FileDescriptorBuilder {
+            proto,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Package of the file.
+    pub fn package(mut self, package: &str) -> Self {
+        self.proto.set_package(package.to_owned());
+        self
+    }
+
+    /// Use `proto3` syntax.
+    pub fn proto3(mut self) -> Self {
+        self.proto.set_syntax("proto3".to_owned());
+        self
+    }
+
+    /// Import a file, types of which are referenced by fields.
+    pub fn dependency(mut self, file: &FileDescriptor) -> Self {
+        self.proto
+            .dependency
+            .push(file.proto().get_name().to_owned());
+        self.dependencies.push(file.clone());
+        self
+    }
+
+    /// Add top-level message.
+    pub fn message(mut self, message: MessageBuilder) -> Self {
+        self.proto.message_type.push(message.proto);
+        self
+    }
+
+    /// Add top-level enum.
+    pub fn enum_type(mut self, enum_type: EnumBuilder) -> Self {
+        self.proto.enum_type.push(enum_type.proto);
+        self
+    }
+
+    /// Constructed `.proto` file descriptor.
+    pub fn proto(&self) -> &FileDescriptorProto {
+        &self.proto
+    }
+
+    /// Build the file descriptor.
+    ///
+    /// # Panics
+    ///
+    /// If field references a type not found in the file or its dependencies.
+    pub fn build(self) -> FileDescriptor {
+        FileDescriptor::new_dynamic(self.proto, self.dependencies)
+    }
+
+    /// Build the file descriptor and add it to the factory.
+    pub fn register(self, factory: &MessageFactory) -> FileDescriptor {
+        let file = self.build();
+        factory.add_file(&file);
+        file
+    }
+}
+
+/// Builder of a message type.
+#[derive(Debug, Clone)]
+pub struct MessageBuilder {
+    proto: DescriptorProto,
+}
+
+impl MessageBuilder {
+    /// Start a message with given name without package.
+    pub fn new(name: &str) -> MessageBuilder {
+        let mut proto = DescriptorProto::new();
+        proto.set_name(name.to_owned());
+        MessageBuilder { proto }
+    }
+
+    /// Add field.
+    pub fn field(mut self, field: FieldBuilder) -> Self {
+        self.proto.field.push(field.proto);
+        self
+    }
+
+    /// Add oneof with given fields.
+    pub fn oneof(mut self, name: &str, fields: Vec<FieldBuilder>) -> Self {
+        let index = self.proto.oneof_decl.len() as i32;
+        let mut oneof = OneofDescriptorProto::new();
+        oneof.set_name(name.to_owned());
+        self.proto.oneof_decl.push(oneof);
+        for mut field in fields {
+            field.proto.set_oneof_index(index);
+            self.proto.field.push(field.proto);
+        }
+        self
+    }
+
+    /// Add nested message.
+    pub fn nested_message(mut self, message: MessageBuilder) -> Self {
+        self.proto.nested_type.push(message.proto);
+        self
+    }
+
+    /// Add nested enum.
+    pub fn nested_enum(mut self, enum_type: EnumBuilder) -> Self {
+        self.proto.enum_type.push(enum_type.proto);
+        self
+    }
+}
+
+/// Builder of a message field.
+#[derive(Debug, Clone)]
+pub struct FieldBuilder {
+    proto: FieldDescriptorProto,
+}
+
+impl FieldBuilder {
+    /// Optional field of given type.
+    ///
+    /// Message and enum fields also need [`type_name`](FieldBuilder::type_name).
+    pub fn new(name: &str, number: u32, field_type: Type) -> FieldBuilder {
+        let mut proto = FieldDescriptorProto::new();
+        proto.set_name(name.to_owned());
+        proto.set_number(number as i32);
+        proto.set_label(Label::LABEL_OPTIONAL);
+        proto.set_field_type(field_type);
+        FieldBuilder { proto }
+    }
+
+    /// Message field with type given by fully-qualified name like `pkg.Message`.
+    pub fn message(name: &str, number: u32, type_name: &str) -> FieldBuilder {
+        FieldBuilder::new(name, number, Type::TYPE_MESSAGE).type_name(type_name)
+    }
+
+    /// Enum field with type given by fully-qualified name like `pkg.Enum`.
+    pub fn enum_field(name: &str, number: u32, type_name: &str) -> FieldBuilder {
+        FieldBuilder::new(name, number, Type::TYPE_ENUM).type_name(type_name)
+    }
+
+    /// Fully-qualified name of message or enum type, leading dot is optional.
+    pub fn type_name(mut self, type_name: &str) -> Self {
+        let type_name = if type_name.starts_with('.') {
+            type_name.to_owned()
+        } else {
+            format!(".{}", type_name)
+        };
+        self.proto.set_type_name(type_name);
+        self
+    }
+
+    /// Field label, `optional` by default.
+    pub fn label(mut self, label: Label) -> Self {
+        self.proto.set_label(label);
+        self
+    }
+
+    /// Make the field `repeated`.
+    pub fn repeated(self) -> Self {
+        self.label(Label::LABEL_REPEATED)
+    }
+
+    /// Make the field `required`.
+    pub fn required(self) -> Self {
+        self.label(Label::LABEL_REQUIRED)
+    }
+
+    /// Set `packed` option of repeated field.
+    pub fn packed(mut self, packed: bool) -> Self {
+        self.proto.options.mut_or_default().set_packed(packed);
+        self
+    }
+}
+
+/// Builder of an enum type.
+#[derive(Debug, Clone)]
+pub struct EnumBuilder {
+    proto: EnumDescriptorProto,
+}
+
+impl EnumBuilder {
+    /// Start an enum with given name without package.
+    pub fn new(name: &str) -> EnumBuilder {
+        let mut proto = EnumDescriptorProto::new();
+        proto.set_name(name.to_owned());
+        EnumBuilder { proto }
+    }
+
+    /// Add enum value.
+    pub fn value(mut self, name: &str, number: i32) -> Self {
+        let mut value = EnumValueDescriptorProto::new();
+        value.set_name(name.to_owned());
+        value.set_number(number);
+        self.proto.value.push(value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reflect::ReflectValueBox;
+    use crate::reflect::ReflectValueRef;
+    use crate::well_known_types::Duration;
+    use crate::Message;
+
+    #[test]
+    fn build_and_register() {
+        let factory = MessageFactory::new();
+        let file = FileDescriptorBuilder::new("test/builder.proto")
+            .package("test.builder")
+            .proto3()
+            .dependency(Duration::descriptor_static().file_descriptor())
+            .enum_type(EnumBuilder::new("Color").value("RED", 0).value("BLUE", 1))
+            .message(
+                MessageBuilder::new("Item")
+                    .field(FieldBuilder::new("name", 1, Type::TYPE_STRING))
+                    .field(FieldBuilder::new("tags", 2, Type::TYPE_INT32).repeated())
+                    .field(FieldBuilder::enum_field("color", 3, "test.builder.Color"))
+                    .field(FieldBuilder::message(
+                        "timeout",
+                        4,
+                        "google.protobuf.Duration",
+                    ))
+                    .field(FieldBuilder::message("inner", 5, "test.builder.Item.Inner"))
+                    .oneof(
+                        "kind",
+                        vec![
+                            FieldBuilder::new("a", 6, Type::TYPE_BOOL),
+                            FieldBuilder::new("b", 7, Type::TYPE_BOOL),
+                        ],
+                    )
+                    .nested_message(MessageBuilder::new("Inner")),
+            )
+            .register(&factory);
+
+        let item = file.message_by_package_relative_name("Item").unwrap();
+        assert_eq!(
+            item,
+            factory.descriptor_by_name("test.builder.Item").unwrap()
+        );
+        assert!(factory
+            .descriptor_by_name("test.builder.Item.Inner")
+            .is_some());
+        assert!(factory
+            .descriptor_by_name("google.protobuf.Duration")
+            .is_some());
+        assert_eq!(1, file.enums().len());
+        assert_eq!(1, item.oneofs().len());
+
+        let mut m = item.new_instance();
+        let name = item.get_field_by_name("name").unwrap();
+        name.set_singular_field(&mut *m, ReflectValueBox::String("x".to_owned()));
+        let tags = item.get_field_by_name("tags").unwrap();
+        tags.mut_repeated(&mut *m).push(ReflectValueBox::I32(3));
+
+        let bytes = m.write_to_bytes_dyn().unwrap();
+        let mut parsed = item.new_instance();
+        parsed.merge_from_bytes_dyn(&bytes).unwrap();
+        assert_eq!(
+            Some(ReflectValueRef::String("x")),
+            name.get_singular(&*parsed)
+        );
+        assert_eq!(1, tags.get_repeated(&*parsed).len());
+    }
+}
@@ -0,0 +1,370 @@
+//! Hash of message content which does not depend on serialization.
+
+use std::hash::Hasher;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageFactory;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::unknown::UnknownValueRef;
+
+/// Parameters of [`canonical_hash_with_options`].
+#[derive(Debug, Default, Clone)]
+pub struct CanonicalHashOptions {
+    /// Do not include unknown fields in the hash.
+    pub ignore_unknown_fields: bool,
+    /// Prevent initializing `CanonicalHashOptions` enumerating all field.
+    pub _future_options: (),
+}
+
+/// Feed message content to the hasher.
+///
+/// Messages which are equal field-by-field produce the same sequence of
+/// writes to the hasher regardless of how they were serialized. In particular:
+///
+/// * fields are hashed in the order of field numbers, not declaration order
+/// * map entries are hashed in the order of keys
+/// * unset fields and empty repeated fields are not hashed
+/// * `-0.0` is hashed as `0.0`, all `NaN` values are hashed the same
+/// * `google.protobuf.Timestamp` and `google.protobuf.Duration` are hashed
+///   as total nanoseconds, so non-normalized values hash as normalized
+/// * `google.protobuf.Any` is hashed as the packed message if its type is
+///   registered in [`MessageFactory::global`], ignoring type URL prefix
+///
+/// Note [`DefaultHasher`](std::collections::hash_map::DefaultHasher) output
+/// is not guaranteed to be the same across Rust releases, so for
+/// content-addressed storage a hasher with stable output should be used.
+///
+/// ```
+/// # use std::collections::hash_map::DefaultHasher;
+/// # use std::hash::Hasher;
+/// # use protobuf::reflect::canonical_hash;
+/// # use protobuf::well_known_types::Duration;
+/// fn hash(d: &Duration) -> u64 {
+///     let mut hasher = DefaultHasher::new();
+///     canonical_hash(d, &mut hasher);
+///     hasher.finish()
+/// }
+///
+/// let mut a = Duration::new();
+/// a.seconds = 2;
+/// let mut b = Duration::new();
+/// b.seconds = 1;
+/// b.nanos = 1_000_000_000;
+/// assert_eq!(hash(&a), hash(&b));
+/// ```
+pub fn canonical_hash<H: Hasher>(message: &dyn MessageDyn, state: &mut H) {
+    canonical_hash_with_options(message, &CanonicalHashOptions::default(), state)
+}
+
+/// Feed message content to the hasher.
+///
+/// See [`canonical_hash`].
+pub fn canonical_hash_with_options<H: Hasher>(
+    message: &dyn MessageDyn,
+    options: &CanonicalHashOptions,
+    state: &mut H,
+) {
+    CanonicalHasher { options, state }.hash_message(message)
+}
+
+// Tags distinguish values of different types and mark sequence boundaries
+const TAG_END: u8 = 0;
+const TAG_VALUE: u8 = 1;
+const TAG_REPEATED: u8 = 2;
+const TAG_MAP: u8 = 3;
+const TAG_UNKNOWN: u8 = 4;
+const TAG_ANY: u8 = 5;
+const TAG_NANOS: u8 = 6;
+
+// Map keys ordered
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum MapKey<'a> {
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    String(&'a str),
+}
+
+impl<'a> MapKey<'a> {
+    fn new(key: &ReflectValueRef<'a>) -> MapKey<'a> {
+        match *key {
+            ReflectValueRef::Bool(v) => MapKey::Bool(v),
+            ReflectValueRef::I32(v) => MapKey::I64(v as i64),
+            ReflectValueRef::I64(v) => MapKey::I64(v),
+            ReflectValueRef::U32(v) => MapKey::U64(v as u64),
+            ReflectValueRef::U64(v) => MapKey::U64(v),
+            ReflectValueRef::String(v) => MapKey::String(v),
+            _ => panic!("not a valid map key: {:?}", key),
+        }
+    }
+}
+
+struct CanonicalHasher<'a, H: Hasher> {
+    options: &'a CanonicalHashOptions,
+    state: &'a mut H,
+}
+
+impl<'a, H: Hasher> CanonicalHasher<'a, H> {
+    fn hash_bytes(&mut self, bytes: &[u8]) {
+        self.state.write_usize(bytes.len());
+        self.state.write(bytes);
+    }
+
+    fn hash_f64(&mut self, v: f64) {
+        let v = if v.is_nan() {
+            f64::NAN
+        } else if v == 0.0 {
+            0.0
+        } else {
+            v
+        };
+        self.state.write_u64(v.to_bits());
+    }
+
+    fn hash_value(&mut self, value: &ReflectValueRef) {
+        match value {
+            ReflectValueRef::U32(v) => self.state.write_u32(*v),
+            ReflectValueRef::U64(v) => self.state.write_u64(*v),
+            ReflectValueRef::I32(v) => self.state.write_i32(*v),
+            ReflectValueRef::I64(v) => self.state.write_i64(*v),
+            ReflectValueRef::F32(v) => self.hash_f64(*v as f64),
+            ReflectValueRef::F64(v) => self.hash_f64(*v),
+            ReflectValueRef::Bool(v) => self.state.write_u8(*v as u8),
+            ReflectValueRef::String(v) => self.hash_bytes(v.as_bytes()),
+            ReflectValueRef::Bytes(v) => self.hash_bytes(v),
+            ReflectValueRef::Enum(_, v) => self.state.write_i32(*v),
+            ReflectValueRef::Message(m) => self.hash_message(&**m),
+        }
+    }
+
+    fn hash_message(&mut self, message: &dyn MessageDyn) {
+        let descriptor = message.descriptor_dyn();
+        match descriptor.full_name() {
+            "google.protobuf.Any" => {
+                if self.hash_any(message) {
+                    return;
+                }
+            }
+            "google.protobuf.Timestamp" | "google.protobuf.Duration" => {
+                let field = |name| {
+                    descriptor
+                        .get_field_by_name(name)
+                        .unwrap()
+                        .get_singular_field_or_default(message)
+                };
+                if let (ReflectValueRef::I64(seconds), ReflectValueRef::I32(nanos)) =
+                    (field("seconds"), field("nanos"))
+                {
+                    let total = seconds as i128 * 1_000_000_000 + nanos as i128;
+                    self.state.write_u8(TAG_NANOS);
+                    self.state.write_i128(total);
+                    self.hash_unknown_fields(message);
+                    self.state.write_u8(TAG_END);
+                    return;
+                }
+            }
+            _ => {}
+        }
+
+        let mut fields: Vec<_> = descriptor.fields().collect();
+        fields.sort_by_key(|f| f.get_proto().get_number());
+        for field in fields {
+            let number = field.get_proto().get_number() as u32;
+            match field.get_reflect(message) {
+                ReflectFieldRef::Optional(None) => {}
+                ReflectFieldRef::Optional(Some(v)) => {
+                    self.state.write_u8(TAG_VALUE);
+                    self.state.write_u32(number);
+                    self.hash_value(&v);
+                }
+                ReflectFieldRef::Repeated(repeated) => {
+                    if repeated.is_empty() {
+                        continue;
+                    }
+                    self.state.write_u8(TAG_REPEATED);
+                    self.state.write_u32(number);
+                    self.state.write_usize(repeated.len());
+                    for v in &repeated {
+                        self.hash_value(&v);
+                    }
+                }
+                ReflectFieldRef::Map(map) => {
+                    if map.is_empty() {
+                        continue;
+                    }
+                    self.state.write_u8(TAG_MAP);
+                    self.state.write_u32(number);
+                    self.state.write_usize(map.len());
+                    let mut entries: Vec<_> = map.into_iter().collect();
+                    entries.sort_by(|(a, _), (b, _)| MapKey::new(a).cmp(&MapKey::new(b)));
+                    for (k, v) in &entries {
+                        self.hash_value(k);
+                        self.hash_value(v);
+                    }
+                }
+            }
+        }
+        self.hash_unknown_fields(message);
+        self.state.write_u8(TAG_END);
+    }
+
+    /// Hash `Any` as packed message, return `false` if type is unknown.
+    fn hash_any(&mut self, message: &dyn MessageDyn) -> bool {
+        let descriptor = message.descriptor_dyn();
+        let type_url = descriptor
+            .get_field_by_name("type_url")
+            .unwrap()
+            .get_singular_field_or_default(message);
+        let value = descriptor
+            .get_field_by_name("value")
+            .unwrap()
+            .get_singular_field_or_default(message);
+        let (type_url, value) = match (type_url, value) {
+            (ReflectValueRef::String(t), ReflectValueRef::Bytes(v)) => (t, v),
+            _ => return false,
+        };
+        let type_name = match type_url.rfind('/') {
+            Some(i) => &type_url[i + 1..],
+            None => return false,
+        };
+        let mut packed = match MessageFactory::global().new_by_name(type_name) {
+            Some(m) => m,
+            None => return false,
+        };
+        if packed.merge_from_bytes_dyn(value).is_err() {
+            return false;
+        }
+        self.state.write_u8(TAG_ANY);
+        self.hash_bytes(type_name.as_bytes());
+        self.hash_message(&*packed);
+        self.hash_unknown_fields(message);
+        self.state.write_u8(TAG_END);
+        true
+    }
+
+    fn hash_unknown_fields(&mut self, message: &dyn MessageDyn) {
+        if self.options.ignore_unknown_fields {
+            return;
+        }
+        let unknown_fields = message.get_unknown_fields_dyn();
+        let mut fields: Vec<_> = unknown_fields.iter().collect();
+        fields.sort_by_key(|(number, _)| *number);
+        for (number, values) in fields {
+            for value in values {
+                self.state.write_u8(TAG_UNKNOWN);
+                self.state.write_u32(number);
+                match value {
+                    UnknownValueRef::Fixed32(v) => {
+                        self.state.write_u8(0);
+                        self.state.write_u32(v);
+                    }
+                    UnknownValueRef::Fixed64(v) => {
+                        self.state.write_u8(1);
+                        self.state.write_u64(v);
+                    }
+                    UnknownValueRef::Varint(v) => {
+                        self.state.write_u8(2);
+                        self.state.write_u64(v);
+                    }
+                    UnknownValueRef::LengthDelimited(v) => {
+                        self.state.write_u8(3);
+                        self.hash_bytes(v);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+    use crate::well_known_types::value;
+    use crate::well_known_types::Any;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Timestamp;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    fn hash_with_options(m: &dyn MessageDyn, options: &CanonicalHashOptions) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        canonical_hash_with_options(m, options, &mut hasher);
+        hasher.finish()
+    }
+
+    fn hash(m: &dyn MessageDyn) -> u64 {
+        hash_with_options(m, &CanonicalHashOptions::default())
+    }
+
+    fn number_value(n: f64) -> Value {
+        let mut v = Value::new();
+        v.kind = Some(value::Kind::number_value(n));
+        v
+    }
+
+    #[test]
+    fn map_order() {
+        let mut a = Struct::new();
+        let mut b = Struct::new();
+        for i in 0..20 {
+            a.fields.insert(format!("k{}", i), number_value(i as f64));
+        }
+        for i in (0..20).rev() {
+            b.fields.insert(format!("k{}", i), number_value(i as f64));
+        }
+        assert_eq!(hash(&a), hash(&b));
+        b.fields.insert("k0".to_owned(), number_value(-0.0));
+        assert_eq!(hash(&a), hash(&b));
+        b.fields.insert("k0".to_owned(), number_value(1.0));
+        assert_ne!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn timestamp_normalized() {
+        let mut a = Timestamp::new();
+        a.seconds = 10;
+        a.nanos = 5;
+        let mut b = Timestamp::new();
+        b.seconds = 11;
+        b.nanos = -999_999_995;
+        assert_eq!(hash(&a), hash(&b));
+    }
+
+    #[test]
+    fn any() {
+        let mut t = Timestamp::new();
+        t.seconds = 1;
+        let a = Any::pack(&t).unwrap();
+        let mut b = a.clone();
+        b.type_url = "example.com/google.protobuf.Timestamp".to_owned();
+        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(hash(&a), {
+            let mut t = Timestamp::new();
+            t.nanos = 1_000_000_000;
+            hash(&Any::pack(&t).unwrap())
+        });
+
+        let mut c = a.clone();
+        c.type_url = "example.com/unknown.Type".to_owned();
+        assert_ne!(hash(&a), hash(&c));
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let a = Timestamp::new();
+        let mut b = Timestamp::new();
+        b.mut_unknown_fields().add_varint(100, 1);
+        assert_ne!(hash(&a), hash(&b));
+        let options = CanonicalHashOptions {
+            ignore_unknown_fields: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            hash_with_options(&a, &options),
+            hash_with_options(&b, &options)
+        );
+    }
+}
@@ -0,0 +1,514 @@
+//! Approximate comparison of messages for tests.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectMapRef;
+use crate::reflect::ReflectRepeatedRef;
+use crate::reflect::ReflectValueRef;
+use crate::text_format;
+
+/// First difference found by [`MessageComparer::compare`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageDifference {
+    /// Path to the differing value like `foo.bar[1].baz` or `map["key"]`,
+    /// empty if root messages are of different types.
+    pub path: String,
+    /// Description of the difference.
+    pub description: String,
+}
+
+impl fmt::Display for MessageDifference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.description)
+        } else {
+            write!(f, "{}: {}", self.path, self.description)
+        }
+    }
+}
+
+impl std::error::Error for MessageDifference {}
+
+/// Configurable message comparison: floats with tolerance, repeated fields
+/// ignoring order, ignored fields.
+///
+/// Works with any generated or dynamic message using reflection.
+/// Default comparer is equivalent to `==` except that `NaN` values are equal
+/// to each other.
+///
+/// ```
+/// # use protobuf::reflect::MessageComparer;
+/// # use protobuf::well_known_types::DoubleValue;
+/// let mut a = DoubleValue::new();
+/// a.value = 0.1 + 0.2;
+/// let mut b = DoubleValue::new();
+/// b.value = 0.3;
+///
+/// assert!(!MessageComparer::new().eq(&a, &b));
+/// assert!(MessageComparer::new().float_ulps(4).eq(&a, &b));
+/// ```
+///
+/// Fields are referenced by fully qualified name of the message followed
+/// by field name, like `google.protobuf.FileDescriptorProto.dependency`.
+#[derive(Debug, Clone, Default)]
+pub struct MessageComparer {
+    float_epsilon: f64,
+    float_ulps: u64,
+    repeated_as_set: bool,
+    repeated_fields_as_set: HashSet<String>,
+    ignore_fields: HashSet<String>,
+    ignore_unknown_fields: bool,
+}
+
+fn field_full_name(field: &FieldDescriptor, message: &dyn MessageDyn) -> String {
+    format!(
+        "{}.{}",
+        message.descriptor_dyn().full_name(),
+        field.get_name()
+    )
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+struct DisplayValue<'a, 'b>(&'a ReflectValueRef<'b>);
+
+impl<'a, 'b> fmt::Display for DisplayValue<'a, 'b> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            ReflectValueRef::U32(v) => write!(f, "{}", v),
+            ReflectValueRef::U64(v) => write!(f, "{}", v),
+            ReflectValueRef::I32(v) => write!(f, "{}", v),
+            ReflectValueRef::I64(v) => write!(f, "{}", v),
+            ReflectValueRef::F32(v) => write!(f, "{:?}", v),
+            ReflectValueRef::F64(v) => write!(f, "{:?}", v),
+            ReflectValueRef::Bool(v) => write!(f, "{}", v),
+            ReflectValueRef::String(v) => write!(f, "{:?}", v),
+            ReflectValueRef::Bytes(v) => {
+                let mut s = String::new();
+                text_format::quote_bytes_to(v, &mut s);
+                write!(f, "\"{}\"", s)
+            }
+            ReflectValueRef::Enum(e, v) => match e.get_value_by_number(*v) {
+                Some(value) => write!(f, "{}", value.get_name()),
+                None => write!(f, "{}", v),
+            },
+            ReflectValueRef::Message(m) => write!(f, "{{{:?}}}", &**m),
+        }
+    }
+}
+
+impl MessageComparer {
+    /// Comparer which requires exact equality.
+    pub fn new() -> MessageComparer {
+        MessageComparer::default()
+    }
+
+    /// Floats are equal if absolute difference does not exceed `epsilon`.
+    pub fn float_epsilon(mut self, epsilon: f64) -> MessageComparer {
+        self.float_epsilon = epsilon;
+        self
+    }
+
+    /// Floats are equal if they differ by at most `ulps` units in the last place
+    /// (i. e. there are at most `ulps - 1` representable values between them).
+    ///
+    /// For `float` fields ULPs of `f32` are counted.
+    pub fn float_ulps(mut self, ulps: u64) -> MessageComparer {
+        self.float_ulps = ulps;
+        self
+    }
+
+    /// Compare all repeated fields ignoring order of elements.
+    ///
+    /// Elements are matched pairwise, so the number of elements must be the same.
+    pub fn repeated_as_set(mut self, repeated_as_set: bool) -> MessageComparer {
+        self.repeated_as_set = repeated_as_set;
+        self
+    }
+
+    /// Compare this repeated field ignoring order of elements.
+    pub fn repeated_field_as_set(mut self, field_full_name: &str) -> MessageComparer {
+        self.repeated_fields_as_set
+            .insert(field_full_name.to_owned());
+        self
+    }
+
+    /// Do not compare the field.
+    pub fn ignore_field(mut self, field_full_name: &str) -> MessageComparer {
+        self.ignore_fields.insert(field_full_name.to_owned());
+        self
+    }
+
+    /// Do not compare unknown fields.
+    pub fn ignore_unknown_fields(mut self, ignore_unknown_fields: bool) -> MessageComparer {
+        self.ignore_unknown_fields = ignore_unknown_fields;
+        self
+    }
+
+    /// Messages are equal according to this comparer.
+    pub fn eq(&self, a: &dyn MessageDyn, b: &dyn MessageDyn) -> bool {
+        self.compare(a, b).is_ok()
+    }
+
+    /// Compare messages and return the first difference.
+    pub fn compare(&self, a: &dyn MessageDyn, b: &dyn MessageDyn) -> Result<(), MessageDifference> {
+        self.compare_messages("", a, b)
+    }
+
+    fn f64_eq(&self, a: f64, b: f64) -> bool {
+        if a == b || (a.is_nan() && b.is_nan()) {
+            return true;
+        }
+        if (a - b).abs() <= self.float_epsilon {
+            return true;
+        }
+        self.float_ulps != 0 && ulps_f64(a, b) <= self.float_ulps
+    }
+
+    fn f32_eq(&self, a: f32, b: f32) -> bool {
+        if a == b || (a.is_nan() && b.is_nan()) {
+            return true;
+        }
+        if ((a as f64) - (b as f64)).abs() <= self.float_epsilon {
+            return true;
+        }
+        self.float_ulps != 0 && ulps_f32(a, b) <= self.float_ulps
+    }
+
+    fn compare_values(
+        &self,
+        path: &str,
+        a: &ReflectValueRef,
+        b: &ReflectValueRef,
+    ) -> Result<(), MessageDifference> {
+        let eq = match (a, b) {
+            (ReflectValueRef::F32(a), ReflectValueRef::F32(b)) => self.f32_eq(*a, *b),
+            (ReflectValueRef::F64(a), ReflectValueRef::F64(b)) => self.f64_eq(*a, *b),
+            (ReflectValueRef::Enum(_, a), ReflectValueRef::Enum(_, b)) => a == b,
+            (ReflectValueRef::Message(a), ReflectValueRef::Message(b)) => {
+                return self.compare_messages(path, &**a, &**b)
+            }
+            (a, b) => a == b,
+        };
+        if eq {
+            Ok(())
+        } else {
+            Err(MessageDifference {
+                path: path.to_owned(),
+                description: format!("{} != {}", DisplayValue(a), DisplayValue(b)),
+            })
+        }
+    }
+
+    fn compare_messages(
+        &self,
+        path: &str,
+        a: &dyn MessageDyn,
+        b: &dyn MessageDyn,
+    ) -> Result<(), MessageDifference> {
+        let descriptor = a.descriptor_dyn();
+        if descriptor != b.descriptor_dyn() {
+            return Err(MessageDifference {
+                path: path.to_owned(),
+                description: format!(
+                    "message types differ: {} != {}",
+                    descriptor.full_name(),
+                    b.descriptor_dyn().full_name()
+                ),
+            });
+        }
+
+        for field in descriptor.fields() {
+            let full_name = field_full_name(&field, a);
+            if self.ignore_fields.contains(&full_name) {
+                continue;
+            }
+            let path = join_path(path, field.get_name());
+            match (field.get_reflect(a), field.get_reflect(b)) {
+                (ReflectFieldRef::Optional(None), ReflectFieldRef::Optional(None)) => {}
+                (ReflectFieldRef::Optional(Some(a)), ReflectFieldRef::Optional(Some(b))) => {
+                    self.compare_values(&path, &a, &b)?
+                }
+                (ReflectFieldRef::Optional(a), ReflectFieldRef::Optional(b)) => {
+                    let describe = |v: Option<ReflectValueRef>| match v {
+                        Some(v) => DisplayValue(&v).to_string(),
+                        None => "unset".to_owned(),
+                    };
+                    return Err(MessageDifference {
+                        path,
+                        description: format!("{} != {}", describe(a), describe(b)),
+                    });
+                }
+                (ReflectFieldRef::Repeated(a), ReflectFieldRef::Repeated(b)) => {
+                    if self.repeated_as_set || self.repeated_fields_as_set.contains(&full_name) {
+                        self.compare_repeated_as_set(&path, &a, &b)?
+                    } else {
+                        self.compare_repeated(&path, &a, &b)?
+                    }
+                }
+                (ReflectFieldRef::Map(a), ReflectFieldRef::Map(b)) => {
+                    self.compare_maps(&path, &a, &b)?
+                }
+                _ => unreachable!("same field"),
+            }
+        }
+
+        if !self.ignore_unknown_fields && a.get_unknown_fields_dyn() != b.get_unknown_fields_dyn() {
+            return Err(MessageDifference {
+                path: path.to_owned(),
+                description: "unknown fields differ".to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    fn compare_repeated(
+        &self,
+        path: &str,
+        a: &ReflectRepeatedRef,
+        b: &ReflectRepeatedRef,
+    ) -> Result<(), MessageDifference> {
+        for i in 0..a.len().min(b.len()) {
+            self.compare_values(&format!("{}[{}]", path, i), &a.get(i), &b.get(i))?;
+        }
+        self.compare_len(path, a.len(), b.len())
+    }
+
+    fn compare_len(&self, path: &str, a: usize, b: usize) -> Result<(), MessageDifference> {
+        if a == b {
+            Ok(())
+        } else {
+            Err(MessageDifference {
+                path: path.to_owned(),
+                description: format!("length {} != {}", a, b),
+            })
+        }
+    }
+
+    fn compare_repeated_as_set(
+        &self,
+        path: &str,
+        a: &ReflectRepeatedRef,
+        b: &ReflectRepeatedRef,
+    ) -> Result<(), MessageDifference> {
+        self.compare_len(path, a.len(), b.len())?;
+        let mut matched = vec![false; b.len()];
+        for i in 0..a.len() {
+            let a_value = a.get(i);
+            let j = (0..b.len())
+                .find(|&j| !matched[j] && self.compare_values(path, &a_value, &b.get(j)).is_ok());
+            match j {
+                Some(j) => matched[j] = true,
+                None => {
+                    return Err(MessageDifference {
+                        path: format!("{}[{}]", path, i),
+                        description: format!("{} not found", DisplayValue(&a_value)),
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn compare_maps(
+        &self,
+        path: &str,
+        a: &ReflectMapRef,
+        b: &ReflectMapRef,
+    ) -> Result<(), MessageDifference> {
+        for (k, a_value) in a {
+            let path = format!("{}[{}]", path, DisplayValue(&k));
+            match b.get(k) {
+                Some(b_value) => self.compare_values(&path, &a_value, &b_value)?,
+                None => {
+                    return Err(MessageDifference {
+                        path,
+                        description: format!("{} != unset", DisplayValue(&a_value)),
+                    })
+                }
+            }
+        }
+        for (k, b_value) in b {
+            if a.get(k.clone()).is_none() {
+                return Err(MessageDifference {
+                    path: format!("{}[{}]", path, DisplayValue(&k)),
+                    description: format!("unset != {}", DisplayValue(&b_value)),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Map float bits to integers ordered like floats.
+fn ordered_bits_f64(v: f64) -> i64 {
+    let bits = v.to_bits() as i64;
+    if bits < 0 {
+        i64::MIN - bits
+    } else {
+        bits
+    }
+}
+
+fn ulps_f64(a: f64, b: f64) -> u64 {
+    if a.is_nan() || b.is_nan() {
+        return u64::MAX;
+    }
+    (ordered_bits_f64(a) as i128 - ordered_bits_f64(b) as i128).unsigned_abs() as u64
+}
+
+fn ulps_f32(a: f32, b: f32) -> u64 {
+    if a.is_nan() || b.is_nan() {
+        return u64::MAX;
+    }
+    let ordered = |v: f32| {
+        let bits = v.to_bits() as i32;
+        if bits < 0 {
+            i32::MIN as i64 - bits as i64
+        } else {
+            bits as i64
+        }
+    };
+    (ordered(a) - ordered(b)).unsigned_abs()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::FloatValue;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn ulps() {
+        assert_eq!(0, ulps_f64(0.0, -0.0));
+        assert_eq!(1, ulps_f64(1.0, 1.0 + f64::EPSILON));
+        assert_eq!(2, ulps_f64(-f64::from_bits(1), f64::from_bits(1)));
+        assert_eq!(1, ulps_f32(1.0, 1.0 + f32::EPSILON));
+    }
+
+    #[test]
+    fn floats() {
+        let mut a = FloatValue::new();
+        a.value = 1.0;
+        let mut b = FloatValue::new();
+        b.value = 1.0 + f32::EPSILON * 2.0;
+        assert_eq!(
+            Err(MessageDifference {
+                path: "value".to_owned(),
+                description: "1.0 != 1.0000002".to_owned(),
+            }),
+            MessageComparer::new().compare(&a, &b)
+        );
+        assert!(!MessageComparer::new().float_ulps(1).eq(&a, &b));
+        assert!(MessageComparer::new().float_ulps(2).eq(&a, &b));
+        assert!(MessageComparer::new().float_epsilon(1e-6).eq(&a, &b));
+
+        a.value = f32::NAN;
+        b.value = f32::NAN;
+        assert!(MessageComparer::new().eq(&a, &b));
+    }
+
+    #[test]
+    fn repeated_as_set() {
+        let mut a = FileDescriptorProto::new();
+        a.dependency = vec!["a".to_owned(), "b".to_owned(), "a".to_owned()];
+        let mut b = FileDescriptorProto::new();
+        b.dependency = vec!["a".to_owned(), "a".to_owned(), "b".to_owned()];
+
+        assert_eq!(
+            "dependency[1]: \"b\" != \"a\"",
+            MessageComparer::new()
+                .compare(&a, &b)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(MessageComparer::new().repeated_as_set(true).eq(&a, &b));
+        assert!(MessageComparer::new()
+            .repeated_field_as_set("google.protobuf.FileDescriptorProto.dependency")
+            .eq(&a, &b));
+
+        b.dependency[0] = "b".to_owned();
+        assert_eq!(
+            "dependency[2]: \"a\" not found",
+            MessageComparer::new()
+                .repeated_as_set(true)
+                .compare(&a, &b)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn ignore_fields_and_nested() {
+        let mut a = FileDescriptorProto::new();
+        a.set_name("a.proto".to_owned());
+        let mut m = DescriptorProto::new();
+        m.set_name("M".to_owned());
+        a.message_type.push(m.clone());
+        let mut b = a.clone();
+        b.set_name("b.proto".to_owned());
+        b.message_type[0].set_name("N".to_owned());
+
+        let comparer =
+            MessageComparer::new().ignore_field("google.protobuf.FileDescriptorProto.name");
+        assert_eq!(
+            "message_type[0].name: \"M\" != \"N\"",
+            comparer.compare(&a, &b).unwrap_err().to_string()
+        );
+        let comparer = comparer.ignore_field("google.protobuf.DescriptorProto.name");
+        assert!(comparer.eq(&a, &b));
+
+        b.clear_name();
+        assert_eq!(
+            "name: \"a.proto\" != unset",
+            MessageComparer::new()
+                .compare(&a, &b)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn maps() {
+        let value = |v: f64| {
+            let mut value = Value::new();
+            value.set_number_value(v);
+            value
+        };
+        let mut a = Struct::new();
+        a.fields.insert("x".to_owned(), value(1.0));
+        let mut b = Struct::new();
+        b.fields.insert("x".to_owned(), value(1.01));
+        assert_eq!(
+            "fields[\"x\"].number_value: 1.0 != 1.01",
+            MessageComparer::new()
+                .compare(&a, &b)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(MessageComparer::new().float_epsilon(0.1).eq(&a, &b));
+
+        b.fields.insert("y".to_owned(), value(1.0));
+        assert_eq!(
+            "fields[\"y\"]: unset != {number_value: 1}",
+            MessageComparer::new()
+                .float_epsilon(0.1)
+                .compare(&a, &b)
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::mem;
+
 use crate::cached_size::CachedSize;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::dynamic::map::DynamicMap;
@@ -5,6 +7,7 @@ use crate::reflect::dynamic::optional::DynamicOptional;
 use crate::reflect::dynamic::repeated::DynamicRepeated;
 use crate::reflect::map::ReflectMap;
 use crate::reflect::repeated::ReflectRepeated;
+use crate::reflect::required::missing_required_fields;
 use crate::reflect::value::value_ref::ReflectValueMut;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::MessageDescriptor;
@@ -15,6 +18,7 @@ use crate::reflect::ReflectRepeatedMut;
 use crate::reflect::ReflectRepeatedRef;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeFieldType;
+use crate::space_used::space_used_by_reflection;
 use crate::Clear;
 use crate::CodedInputStream;
 use crate::CodedOutputStream;
@@ -25,6 +29,7 @@ use crate::UnknownFields;
 pub(crate) mod map;
 pub(crate) mod optional;
 pub(crate) mod repeated;
+mod wire;
 
 #[derive(Debug, Clone)]
 enum DynamicFieldValue {
@@ -186,19 +191,21 @@ impl Message for DynamicMessage {
     }
 
     fn is_initialized(&self) -> bool {
-        unimplemented!()
+        missing_required_fields(self).is_empty()
     }
 
-    fn merge_from(&mut self, _is: &mut CodedInputStream) -> ProtobufResult<()> {
-        unimplemented!()
+    fn merge_from(&mut self, is: &mut CodedInputStream) -> ProtobufResult<()> {
+        wire::merge_from(self, is)
     }
 
-    fn write_to_with_cached_sizes(&self, _os: &mut CodedOutputStream) -> ProtobufResult<()> {
-        unimplemented!()
+    fn write_to_with_cached_sizes(&self, os: &mut CodedOutputStream) -> ProtobufResult<()> {
+        wire::write_to_with_cached_sizes(self, os)
     }
 
     fn compute_size(&self) -> u32 {
-        unimplemented!()
+        let size = wire::compute_size(self);
+        self.cached_size.set(size);
+        size
     }
 
     fn get_cached_size(&self) -> u32 {
@@ -213,6 +220,10 @@ impl Message for DynamicMessage {
         &mut self.unknown_fields
     }
 
+    fn space_used_excluding_self(&self) -> usize {
+        self.fields.len() * mem::size_of::<DynamicFieldValue>() + space_used_by_reflection(self)
+    }
+
     fn new() -> Self
     where
         Self: Sized,
@@ -0,0 +1,427 @@
+//! Binary encoding of [`DynamicMessage`].
+//!
+//! Fields are encoded like in generated code: in declaration order,
+//! followed by unknown fields. Groups are not supported and handled
+//! as unknown fields. Unknown fields of messages with `message_set_wire_format`
+//! option are read from and written as `MessageSet` items.
+
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::reflect::dynamic::DynamicMessage;
+use crate::reflect::value::value_ref::ReflectValueMut;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+use crate::rt;
+use crate::rt::compute_raw_varint64_size;
+use crate::wire_format::WireType;
+use crate::zigzag::encode_zig_zag_32;
+use crate::zigzag::encode_zig_zag_64;
+use crate::CodedInputStream;
+use crate::CodedOutputStream;
+use crate::ProtobufResult;
+
+fn wire_type(t: Type) -> WireType {
+    match t {
+        Type::TYPE_INT32
+        | Type::TYPE_INT64
+        | Type::TYPE_UINT32
+        | Type::TYPE_UINT64
+        | Type::TYPE_SINT32
+        | Type::TYPE_SINT64
+        | Type::TYPE_BOOL
+        | Type::TYPE_ENUM => WireType::WireTypeVarint,
+        Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 | Type::TYPE_FLOAT => WireType::WireTypeFixed32,
+        Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 | Type::TYPE_DOUBLE => WireType::WireTypeFixed64,
+        Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE => {
+            WireType::WireTypeLengthDelimited
+        }
+        Type::TYPE_GROUP => WireType::WireTypeStartGroup,
+    }
+}
+
+fn is_packable(t: Type) -> bool {
+    match wire_type(t) {
+        WireType::WireTypeVarint | WireType::WireTypeFixed32 | WireType::WireTypeFixed64 => true,
+        _ => false,
+    }
+}
+
+fn is_proto3(field: &FieldDescriptor) -> bool {
+    field
+        .message_descriptor
+        .file_descriptor_proto()
+        .get_syntax()
+        == "proto3"
+}
+
+/// Like generated code, fields are packed only when `packed` option is set.
+fn is_packed(field: &FieldDescriptor) -> bool {
+    let proto = field.get_proto();
+    is_packable(proto.get_field_type()) && proto.options.get_or_default().get_packed()
+}
+
+/// Extensions of `MessageSet` are encoded as item groups.
+fn is_message_set(m: &DynamicMessage) -> bool {
+    m.descriptor
+        .get_proto()
+        .options
+        .get_or_default()
+        .get_message_set_wire_format()
+}
+
+/// Field is serialized when set even if the value is default.
+fn has_presence(field: &FieldDescriptor) -> bool {
+    field.get_proto().get_field_type() == Type::TYPE_MESSAGE
+        || field.containing_oneof().is_some()
+        || !is_proto3(field)
+}
+
+fn is_default(value: &ReflectValueRef) -> bool {
+    match value {
+        ReflectValueRef::U32(v) => *v == 0,
+        ReflectValueRef::U64(v) => *v == 0,
+        ReflectValueRef::I32(v) => *v == 0,
+        ReflectValueRef::I64(v) => *v == 0,
+        ReflectValueRef::F32(v) => *v == 0.0,
+        ReflectValueRef::F64(v) => *v == 0.0,
+        ReflectValueRef::Bool(v) => !*v,
+        ReflectValueRef::String(v) => v.is_empty(),
+        ReflectValueRef::Bytes(v) => v.is_empty(),
+        ReflectValueRef::Enum(_, v) => *v == 0,
+        ReflectValueRef::Message(..) => false,
+    }
+}
+
+/// Proto types of key and value of map field.
+fn map_entry_types(field: &FieldDescriptor) -> (Type, Type) {
+    let type_name = field.get_proto().get_type_name();
+    let entry = field
+        .message_descriptor
+        .file_descriptor()
+        .message_by_full_name_with_deps(type_name)
+        .unwrap_or_else(|| panic!("map entry not found: {}", type_name));
+    let field_type = |number| {
+        entry
+            .get_field_by_number(number)
+            .unwrap()
+            .get_proto()
+            .get_field_type()
+    };
+    (field_type(1), field_type(2))
+}
+
+/// Size of value without tag, nested message sizes are computed and cached.
+fn value_size(t: Type, value: &ReflectValueRef, cached: bool) -> u32 {
+    match (t, value) {
+        (Type::TYPE_SINT32, ReflectValueRef::I32(v)) => {
+            compute_raw_varint64_size(encode_zig_zag_32(*v) as u64)
+        }
+        (Type::TYPE_SINT64, ReflectValueRef::I64(v)) => {
+            compute_raw_varint64_size(encode_zig_zag_64(*v))
+        }
+        (_, ReflectValueRef::String(v)) => bytes_size(v.len() as u32),
+        (_, ReflectValueRef::Bytes(v)) => bytes_size(v.len() as u32),
+        (_, ReflectValueRef::Message(m)) => bytes_size(if cached {
+            m.get_cached_size_dyn()
+        } else {
+            m.compute_size_dyn()
+        }),
+        _ => match wire_type(t) {
+            WireType::WireTypeFixed32 => 4,
+            WireType::WireTypeFixed64 => 8,
+            _ => compute_raw_varint64_size(varint(value)),
+        },
+    }
+}
+
+fn bytes_size(len: u32) -> u32 {
+    compute_raw_varint64_size(len as u64) + len
+}
+
+/// Varint encoding of non-zigzag varint value.
+fn varint(value: &ReflectValueRef) -> u64 {
+    match value {
+        ReflectValueRef::I32(v) => *v as i64 as u64,
+        ReflectValueRef::I64(v) => *v as u64,
+        ReflectValueRef::U32(v) => *v as u64,
+        ReflectValueRef::U64(v) => *v,
+        ReflectValueRef::Bool(v) => *v as u64,
+        ReflectValueRef::Enum(_, v) => *v as i64 as u64,
+        _ => unreachable!(),
+    }
+}
+
+fn write_value(os: &mut CodedOutputStream, t: Type, value: &ReflectValueRef) -> ProtobufResult<()> {
+    match (t, value) {
+        (Type::TYPE_SINT32, ReflectValueRef::I32(v)) => os.write_sint32_no_tag(*v),
+        (Type::TYPE_SINT64, ReflectValueRef::I64(v)) => os.write_sint64_no_tag(*v),
+        (_, ReflectValueRef::I32(v)) if wire_type(t) == WireType::WireTypeFixed32 => {
+            os.write_sfixed32_no_tag(*v)
+        }
+        (_, ReflectValueRef::I64(v)) if wire_type(t) == WireType::WireTypeFixed64 => {
+            os.write_sfixed64_no_tag(*v)
+        }
+        (_, ReflectValueRef::U32(v)) if wire_type(t) == WireType::WireTypeFixed32 => {
+            os.write_fixed32_no_tag(*v)
+        }
+        (_, ReflectValueRef::U64(v)) if wire_type(t) == WireType::WireTypeFixed64 => {
+            os.write_fixed64_no_tag(*v)
+        }
+        (_, ReflectValueRef::F32(v)) => os.write_float_no_tag(*v),
+        (_, ReflectValueRef::F64(v)) => os.write_double_no_tag(*v),
+        (_, ReflectValueRef::String(v)) => os.write_string_no_tag(v),
+        (_, ReflectValueRef::Bytes(v)) => os.write_bytes_no_tag(v),
+        (_, ReflectValueRef::Message(m)) => {
+            os.write_raw_varint32(m.get_cached_size_dyn())?;
+            m.write_to_with_cached_sizes_dyn(os)
+        }
+        (_, v) => os.write_raw_varint64(varint(v)),
+    }
+}
+
+fn read_value(
+    is: &mut CodedInputStream,
+    t: Type,
+    runtime_type: &RuntimeTypeBox,
+) -> ProtobufResult<ReflectValueBox> {
+    Ok(match t {
+        Type::TYPE_INT32 => ReflectValueBox::I32(is.read_int32()?),
+        Type::TYPE_INT64 => ReflectValueBox::I64(is.read_int64()?),
+        Type::TYPE_UINT32 => ReflectValueBox::U32(is.read_uint32()?),
+        Type::TYPE_UINT64 => ReflectValueBox::U64(is.read_uint64()?),
+        Type::TYPE_SINT32 => ReflectValueBox::I32(is.read_sint32()?),
+        Type::TYPE_SINT64 => ReflectValueBox::I64(is.read_sint64()?),
+        Type::TYPE_FIXED32 => ReflectValueBox::U32(is.read_fixed32()?),
+        Type::TYPE_FIXED64 => ReflectValueBox::U64(is.read_fixed64()?),
+        Type::TYPE_SFIXED32 => ReflectValueBox::I32(is.read_sfixed32()?),
+        Type::TYPE_SFIXED64 => ReflectValueBox::I64(is.read_sfixed64()?),
+        Type::TYPE_FLOAT => ReflectValueBox::F32(is.read_float()?),
+        Type::TYPE_DOUBLE => ReflectValueBox::F64(is.read_double()?),
+        Type::TYPE_BOOL => ReflectValueBox::Bool(is.read_bool()?),
+        Type::TYPE_STRING => ReflectValueBox::String(is.read_string()?),
+        Type::TYPE_BYTES => ReflectValueBox::Bytes(is.read_bytes()?),
+        Type::TYPE_ENUM => match runtime_type {
+            RuntimeTypeBox::Enum(e) => ReflectValueBox::Enum(e.clone(), is.read_int32()?),
+            t => panic!("not an enum type: {:?}", t),
+        },
+        Type::TYPE_MESSAGE => match runtime_type {
+            RuntimeTypeBox::Message(d) => {
+                let mut m = d.new_instance();
+                is.merge_message_dyn(&mut *m)?;
+                ReflectValueBox::Message(m)
+            }
+            t => panic!("not a message type: {:?}", t),
+        },
+        Type::TYPE_GROUP => unreachable!(),
+    })
+}
+
+fn read_map_entry(
+    is: &mut CodedInputStream,
+    (key_type, value_type): (Type, Type),
+    (key_runtime_type, value_runtime_type): (&RuntimeTypeBox, &RuntimeTypeBox),
+) -> ProtobufResult<(ReflectValueBox, ReflectValueBox)> {
+    let len = is.read_raw_varint64()?;
+    let old_limit = is.push_limit(len)?;
+    let mut key = None;
+    let mut value = None;
+    while !is.eof()? {
+        let (number, wt) = is.read_tag_unpack()?;
+        match number {
+            1 if wt == wire_type(key_type) => {
+                key = Some(read_value(is, key_type, key_runtime_type)?)
+            }
+            2 if wt == wire_type(value_type) => {
+                value = Some(read_value(is, value_type, value_runtime_type)?)
+            }
+            _ => is.skip_field(wt)?,
+        }
+    }
+    is.pop_limit(old_limit);
+    let key = key.unwrap_or_else(|| key_runtime_type.default_value_ref().to_box());
+    let value = value.unwrap_or_else(|| value_runtime_type.default_value_ref().to_box());
+    Ok((key, value))
+}
+
+pub(crate) fn merge_from(m: &mut DynamicMessage, is: &mut CodedInputStream) -> ProtobufResult<()> {
+    let descriptor = m.descriptor.clone();
+    let message_set = is_message_set(m);
+    while !is.eof()? {
+        let (number, wt) = is.read_tag_unpack()?;
+        if message_set && number == 1 && wt == WireType::WireTypeStartGroup {
+            is.incr_recursion()?;
+            let r = rt::read_message_set_item(is, &mut m.unknown_fields, |type_id, is, unknown| {
+                rt::read_unknown_or_skip_group(
+                    type_id,
+                    WireType::WireTypeLengthDelimited,
+                    is,
+                    unknown,
+                )
+            });
+            is.decr_recursion();
+            r?;
+            continue;
+        }
+        let field = match descriptor.get_field_by_number(number) {
+            Some(field) if field.get_proto().get_field_type() != Type::TYPE_GROUP => field,
+            _ => {
+                rt::read_unknown_or_skip_group(number, wt, is, &mut m.unknown_fields)?;
+                continue;
+            }
+        };
+        let t = field.get_proto().get_field_type();
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(runtime_type) => {
+                if wt != wire_type(t) {
+                    return Err(rt::unexpected_wire_type(wt));
+                }
+                if t == Type::TYPE_MESSAGE {
+                    match m.mut_singular_field_or_default(&field) {
+                        ReflectValueMut::Message(nested) => is.merge_message_dyn(nested)?,
+                    }
+                } else {
+                    let value = read_value(is, t, &runtime_type)?;
+                    m.clear_oneof_group_fields_except(&field);
+                    m.set_field(&field, value);
+                }
+            }
+            RuntimeFieldType::Repeated(runtime_type) => {
+                let mut values = Vec::new();
+                if wt == WireType::WireTypeLengthDelimited && is_packable(t) {
+                    let len = is.read_raw_varint64()?;
+                    let old_limit = is.push_limit(len)?;
+                    while !is.eof()? {
+                        values.push(read_value(is, t, &runtime_type)?);
+                    }
+                    is.pop_limit(old_limit);
+                } else if wt == wire_type(t) {
+                    values.push(read_value(is, t, &runtime_type)?);
+                } else {
+                    return Err(rt::unexpected_wire_type(wt));
+                }
+                let mut repeated = m.mut_repeated(&field);
+                for value in values {
+                    repeated.push(value);
+                }
+            }
+            RuntimeFieldType::Map(key_runtime_type, value_runtime_type) => {
+                if wt != WireType::WireTypeLengthDelimited {
+                    return Err(rt::unexpected_wire_type(wt));
+                }
+                let (key, value) = read_map_entry(
+                    is,
+                    map_entry_types(&field),
+                    (&key_runtime_type, &value_runtime_type),
+                )?;
+                m.mut_map(&field).insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Size of the message, or write the message if `os` is specified.
+///
+/// Size is computed with nested message sizes cached when writing.
+fn size_or_write(
+    m: &DynamicMessage,
+    mut os: Option<&mut CodedOutputStream>,
+) -> ProtobufResult<u32> {
+    let cached = os.is_some();
+    let mut size = 0;
+    for field in m.descriptor.fields() {
+        let number = field.get_proto().get_number() as u32;
+        let t = field.get_proto().get_field_type();
+        if t == Type::TYPE_GROUP {
+            continue;
+        }
+        match m.get_reflect(&field) {
+            ReflectFieldRef::Optional(Some(value)) => {
+                if !has_presence(&field) && is_default(&value) {
+                    continue;
+                }
+                size += rt::tag_size(number) + value_size(t, &value, cached);
+                if let Some(os) = os.as_mut() {
+                    os.write_tag(number, wire_type(t))?;
+                    write_value(os, t, &value)?;
+                }
+            }
+            ReflectFieldRef::Optional(None) => {}
+            ReflectFieldRef::Repeated(repeated) => {
+                if repeated.is_empty() {
+                    continue;
+                }
+                if is_packed(&field) {
+                    let data_size: u32 = (&repeated)
+                        .into_iter()
+                        .map(|v| value_size(t, &v, cached))
+                        .sum();
+                    size += rt::tag_size(number) + bytes_size(data_size);
+                    if let Some(os) = os.as_mut() {
+                        os.write_tag(number, WireType::WireTypeLengthDelimited)?;
+                        os.write_raw_varint32(data_size)?;
+                        for value in &repeated {
+                            write_value(os, t, &value)?;
+                        }
+                    }
+                } else {
+                    for value in &repeated {
+                        size += rt::tag_size(number) + value_size(t, &value, cached);
+                        if let Some(os) = os.as_mut() {
+                            os.write_tag(number, wire_type(t))?;
+                            write_value(os, t, &value)?;
+                        }
+                    }
+                }
+            }
+            ReflectFieldRef::Map(map) => {
+                if map.is_empty() {
+                    continue;
+                }
+                let (key_type, value_type) = map_entry_types(&field);
+                for (key, value) in &map {
+                    let entry_size = rt::tag_size(1)
+                        + value_size(key_type, &key, cached)
+                        + rt::tag_size(2)
+                        + value_size(value_type, &value, cached);
+                    size += rt::tag_size(number) + bytes_size(entry_size);
+                    if let Some(os) = os.as_mut() {
+                        os.write_tag(number, WireType::WireTypeLengthDelimited)?;
+                        os.write_raw_varint32(entry_size)?;
+                        os.write_tag(1, wire_type(key_type))?;
+                        write_value(os, key_type, &key)?;
+                        os.write_tag(2, wire_type(value_type))?;
+                        write_value(os, value_type, &value)?;
+                    }
+                }
+            }
+        }
+    }
+    if is_message_set(m) {
+        size += rt::message_set_unknown_fields_size(&m.unknown_fields);
+        if let Some(os) = os {
+            rt::write_message_set_unknown_fields(&m.unknown_fields, os)?;
+        }
+    } else {
+        size += rt::unknown_fields_size(&m.unknown_fields);
+        if let Some(os) = os {
+            os.write_unknown_fields(&m.unknown_fields)?;
+        }
+    }
+    Ok(size)
+}
+
+pub(crate) fn compute_size(m: &DynamicMessage) -> u32 {
+    size_or_write(m, None).unwrap()
+}
+
+pub(crate) fn write_to_with_cached_sizes(
+    m: &DynamicMessage,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    size_or_write(m, Some(os))?;
+    Ok(())
+}
@@ -2,9 +2,12 @@ use std::any::TypeId;
 use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::ops::RangeInclusive;
 
 use crate::descriptor::EnumDescriptorProto;
 use crate::descriptor::EnumValueDescriptorProto;
+use crate::descriptor::EnumOptions;
+use crate::descriptor::EnumValueOptions;
 use crate::enums::ProtobufEnum;
 use crate::reflect::enums::dynamic::DynamicEnumDescriptor;
 use crate::reflect::enums::generated::GeneratedEnumDescriptor;
@@ -44,6 +47,13 @@ impl fmt::Debug for EnumValueDescriptor {
     }
 }
 
+/// Name of enum value as specified in `.proto` file.
+impl fmt::Display for EnumValueDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.get_name())
+    }
+}
+
 impl EnumValueDescriptor {
     pub(crate) fn new(enum_descriptor: EnumDescriptor, index: usize) -> EnumValueDescriptor {
         EnumValueDescriptor {
@@ -62,6 +72,11 @@ impl EnumValueDescriptor {
         self.get_proto().get_name()
     }
 
+    /// Enum value options.
+    pub fn options(&self) -> &EnumValueOptions {
+        self.get_proto().options.get_or_default()
+    }
+
     /// `i32` value of the enum variant
     pub fn value(&self) -> i32 {
         self.get_proto().get_number()
@@ -87,7 +102,16 @@ impl EnumValueDescriptor {
         if self.enum_descriptor != E::enum_descriptor_static() {
             return None;
         }
-        E::from_i32(self.value())
+        // Generated `values` are in `.proto` order, which keeps aliases distinct
+        E::values().get(self.index).cloned()
+    }
+
+    /// Check if this value has the same number as one of preceding values
+    /// (possible only with `option allow_alias = true`).
+    pub fn is_alias(&self) -> bool {
+        self.enum_descriptor
+            .get_value_by_number(self.value())
+            .map_or(false, |canonical| canonical.index != self.index)
     }
 }
 
@@ -188,7 +212,50 @@ impl EnumDescriptor {
         })
     }
 
-    /// Find enum variant by number
+    /// Find enum variant by name ignoring ASCII case.
+    ///
+    /// If several names differ only in case, the first one is returned.
+    pub fn get_value_by_name_ignore_case(&self, name: &str) -> Option<EnumValueDescriptor> {
+        self.values()
+            .find(|v| v.get_name().eq_ignore_ascii_case(name))
+    }
+
+    /// Enum options.
+    pub fn options(&self) -> &EnumOptions {
+        self.get_proto().options.get_or_default()
+    }
+
+    /// Enum has `option allow_alias = true`.
+    pub fn allow_alias(&self) -> bool {
+        self.options().get_allow_alias()
+    }
+
+    /// Reserved value number ranges, end is inclusive.
+    pub fn reserved_ranges<'a>(&'a self) -> impl Iterator<Item = RangeInclusive<i32>> + 'a {
+        self.get_proto()
+            .reserved_range
+            .iter()
+            .map(|r| r.get_start()..=r.get_end())
+    }
+
+    /// Reserved value names.
+    pub fn reserved_names<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+        self.get_proto().reserved_name.iter().map(|n| n.as_str())
+    }
+
+    /// Value number is reserved.
+    pub fn is_reserved_number(&self, number: i32) -> bool {
+        self.reserved_ranges().any(|r| r.contains(&number))
+    }
+
+    /// Value name is reserved.
+    pub fn is_reserved_name(&self, name: &str) -> bool {
+        self.reserved_names().any(|n| n == name)
+    }
+
+    /// Find enum variant by number.
+    ///
+    /// When several variants have the same number, the first one is returned.
     pub fn get_value_by_number(&self, number: i32) -> Option<EnumValueDescriptor> {
         let index = match self.get_impl() {
             EnumDescriptorImplRef::Generated(g) => *g.indices.index_by_number.get(&number)?,
@@ -200,6 +267,14 @@ impl EnumDescriptor {
         })
     }
 
+    /// All variants with given number: the first one and its aliases.
+    pub fn get_values_by_number<'a>(
+        &'a self,
+        number: i32,
+    ) -> impl Iterator<Item = EnumValueDescriptor> + 'a {
+        self.values().filter(move |v| v.value() == number)
+    }
+
     /// Default enum value (first variant)
     pub fn get_default_value(&self) -> EnumValueDescriptor {
         EnumValueDescriptor {
@@ -0,0 +1,233 @@
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::descriptor::FieldDescriptorProto;
+use crate::error::ProtobufResult;
+use crate::reflect::FileDescriptor;
+use crate::reflect::ReflectValueBox;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeTypeBox;
+use crate::unknown::UnknownValue;
+use crate::unknown::UnknownValueRef;
+use crate::unknown::UnknownValues;
+use crate::zigzag::decode_zig_zag_32;
+use crate::zigzag::decode_zig_zag_64;
+use crate::zigzag::encode_zig_zag_32;
+use crate::zigzag::encode_zig_zag_64;
+use crate::CodedInputStream;
+
+/// Extension field declared in a `.proto` file.
+///
+/// Extensions are found by name with
+/// [`MessageFactory::extension_by_name`](crate::reflect::MessageFactory::extension_by_name).
+/// Values of extensions are stored in unknown fields of the extended message.
+#[derive(Clone, Debug)]
+pub struct ExtensionDescriptor {
+    file: FileDescriptor,
+    full_name: String,
+    proto: FieldDescriptorProto,
+}
+
+impl ExtensionDescriptor {
+    pub(crate) fn new(
+        file: FileDescriptor,
+        scope: &str,
+        proto: FieldDescriptorProto,
+    ) -> ExtensionDescriptor {
+        let full_name = if scope.is_empty() {
+            proto.get_name().to_owned()
+        } else {
+            format!("{}.{}", scope, proto.get_name())
+        };
+        ExtensionDescriptor {
+            file,
+            full_name,
+            proto,
+        }
+    }
+
+    /// Extension field as declared in `.proto` file.
+    pub fn get_proto(&self) -> &FieldDescriptorProto {
+        &self.proto
+    }
+
+    /// Fully-qualified name like `pkg.ext` or `pkg.Scope.ext`.
+    pub fn full_name(&self) -> &str {
+        &self.full_name
+    }
+
+    /// Fully-qualified name of extended message, without leading dot.
+    pub fn extendee(&self) -> &str {
+        let extendee = self.proto.get_extendee();
+        extendee.strip_prefix('.').unwrap_or(extendee)
+    }
+
+    /// Field number.
+    pub fn number(&self) -> u32 {
+        self.proto.get_number() as u32
+    }
+
+    /// Extension is `repeated`.
+    pub fn is_repeated(&self) -> bool {
+        self.proto.get_label() == Label::LABEL_REPEATED
+    }
+
+    /// File where extension is declared.
+    pub fn file_descriptor(&self) -> &FileDescriptor {
+        &self.file
+    }
+
+    /// Type of a single value of the extension.
+    ///
+    /// # Panics
+    ///
+    /// If message or enum type is not found in the file or its dependencies.
+    pub fn runtime_type(&self) -> RuntimeTypeBox {
+        // Fully-qualified name with leading dot
+        let type_name = self.proto.get_type_name();
+        match self.proto.get_field_type() {
+            Type::TYPE_MESSAGE | Type::TYPE_GROUP => RuntimeTypeBox::Message(
+                self.file
+                    .message_by_full_name_with_deps(type_name)
+                    .unwrap_or_else(|| panic!("message not found: {}", type_name)),
+            ),
+            Type::TYPE_ENUM => RuntimeTypeBox::Enum(
+                self.file
+                    .all_files()
+                    .into_iter()
+                    .find_map(|f| f.enum_by_full_name(type_name))
+                    .unwrap_or_else(|| panic!("enum not found: {}", type_name)),
+            ),
+            t => RuntimeTypeBox::from_proto_type(t),
+        }
+    }
+
+    /// Encode a value as it is stored in unknown fields.
+    ///
+    /// Fails only if message value cannot be serialized.
+    pub(crate) fn value_to_unknown(&self, value: ReflectValueRef) -> ProtobufResult<UnknownValue> {
+        Ok(match (self.proto.get_field_type(), value) {
+            (Type::TYPE_SINT32, ReflectValueRef::I32(v)) => {
+                UnknownValue::Varint(encode_zig_zag_32(v) as u64)
+            }
+            (Type::TYPE_SINT64, ReflectValueRef::I64(v)) => {
+                UnknownValue::Varint(encode_zig_zag_64(v))
+            }
+            (Type::TYPE_FIXED32, ReflectValueRef::U32(v)) => UnknownValue::Fixed32(v),
+            (Type::TYPE_SFIXED32, ReflectValueRef::I32(v)) => UnknownValue::sfixed32(v),
+            (Type::TYPE_FIXED64, ReflectValueRef::U64(v)) => UnknownValue::Fixed64(v),
+            (Type::TYPE_SFIXED64, ReflectValueRef::I64(v)) => UnknownValue::sfixed64(v),
+            (_, ReflectValueRef::I32(v)) => UnknownValue::int32(v),
+            (_, ReflectValueRef::I64(v)) => UnknownValue::int64(v),
+            (_, ReflectValueRef::U32(v)) => UnknownValue::Varint(v as u64),
+            (_, ReflectValueRef::U64(v)) => UnknownValue::Varint(v),
+            (_, ReflectValueRef::Bool(v)) => UnknownValue::Varint(v as u64),
+            (_, ReflectValueRef::Enum(_, v)) => UnknownValue::int32(v),
+            (_, ReflectValueRef::F32(v)) => UnknownValue::float(v),
+            (_, ReflectValueRef::F64(v)) => UnknownValue::double(v),
+            (_, ReflectValueRef::String(v)) => UnknownValue::LengthDelimited(v.as_bytes().to_vec()),
+            (_, ReflectValueRef::Bytes(v)) => UnknownValue::LengthDelimited(v.to_vec()),
+            (_, ReflectValueRef::Message(m)) => {
+                UnknownValue::LengthDelimited(m.write_to_bytes_partial_dyn()?)
+            }
+        })
+    }
+
+    /// Decode a value stored in unknown fields.
+    ///
+    /// `None` if the value does not match the extension type.
+    pub(crate) fn value_from_unknown(&self, value: UnknownValueRef) -> Option<ReflectValueBox> {
+        let t = self.proto.get_field_type();
+        Some(match (t, value) {
+            (Type::TYPE_INT32, UnknownValueRef::Varint(v)) => ReflectValueBox::I32(v as i32),
+            (Type::TYPE_INT64, UnknownValueRef::Varint(v)) => ReflectValueBox::I64(v as i64),
+            (Type::TYPE_UINT32, UnknownValueRef::Varint(v)) => ReflectValueBox::U32(v as u32),
+            (Type::TYPE_UINT64, UnknownValueRef::Varint(v)) => ReflectValueBox::U64(v),
+            (Type::TYPE_SINT32, UnknownValueRef::Varint(v)) => {
+                ReflectValueBox::I32(decode_zig_zag_32(v as u32))
+            }
+            (Type::TYPE_SINT64, UnknownValueRef::Varint(v)) => {
+                ReflectValueBox::I64(decode_zig_zag_64(v))
+            }
+            (Type::TYPE_BOOL, UnknownValueRef::Varint(v)) => ReflectValueBox::Bool(v != 0),
+            (Type::TYPE_ENUM, UnknownValueRef::Varint(v)) => match self.runtime_type() {
+                RuntimeTypeBox::Enum(e) => ReflectValueBox::Enum(e, v as i32),
+                _ => unreachable!(),
+            },
+            (Type::TYPE_FIXED32, UnknownValueRef::Fixed32(v)) => ReflectValueBox::U32(v),
+            (Type::TYPE_SFIXED32, UnknownValueRef::Fixed32(v)) => ReflectValueBox::I32(v as i32),
+            (Type::TYPE_FLOAT, UnknownValueRef::Fixed32(v)) => {
+                ReflectValueBox::F32(f32::from_bits(v))
+            }
+            (Type::TYPE_FIXED64, UnknownValueRef::Fixed64(v)) => ReflectValueBox::U64(v),
+            (Type::TYPE_SFIXED64, UnknownValueRef::Fixed64(v)) => ReflectValueBox::I64(v as i64),
+            (Type::TYPE_DOUBLE, UnknownValueRef::Fixed64(v)) => {
+                ReflectValueBox::F64(f64::from_bits(v))
+            }
+            (Type::TYPE_STRING, UnknownValueRef::LengthDelimited(v)) => {
+                ReflectValueBox::String(String::from_utf8(v.to_vec()).ok()?)
+            }
+            (Type::TYPE_BYTES, UnknownValueRef::LengthDelimited(v)) => {
+                ReflectValueBox::Bytes(v.to_vec())
+            }
+            (Type::TYPE_MESSAGE, UnknownValueRef::LengthDelimited(v)) => {
+                match self.runtime_type() {
+                    RuntimeTypeBox::Message(d) => {
+                        let mut m = d.new_instance();
+                        m.merge_from_bytes_dyn(v).ok()?;
+                        ReflectValueBox::Message(m)
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    /// Decode all values stored in unknown fields, including packed values
+    /// of repeated extensions.
+    ///
+    /// `None` if any value does not match the extension type.
+    pub(crate) fn values_from_unknown(
+        &self,
+        values: &UnknownValues,
+    ) -> Option<Vec<ReflectValueBox>> {
+        let packed_wire_value = if self.is_repeated() {
+            self.packed_wire_value()
+        } else {
+            None
+        };
+        let mut r = Vec::new();
+        for value in values {
+            match (packed_wire_value, value) {
+                (Some(read), UnknownValueRef::LengthDelimited(bytes)) => {
+                    let mut is = CodedInputStream::from_bytes(bytes);
+                    while !is.eof().ok()? {
+                        r.push(self.value_from_unknown(read(&mut is)?)?);
+                    }
+                }
+                (_, value) => r.push(self.value_from_unknown(value)?),
+            }
+        }
+        Some(r)
+    }
+
+    /// Reader of a single packed value if the type can be packed.
+    fn packed_wire_value(
+        &self,
+    ) -> Option<fn(&mut CodedInputStream) -> Option<UnknownValueRef<'static>>> {
+        match self.proto.get_field_type() {
+            Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE | Type::TYPE_GROUP => None,
+            Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 | Type::TYPE_FLOAT => Some(|is| {
+                is.read_raw_little_endian32()
+                    .ok()
+                    .map(UnknownValueRef::Fixed32)
+            }),
+            Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 | Type::TYPE_DOUBLE => Some(|is| {
+                is.read_raw_little_endian64()
+                    .ok()
+                    .map(UnknownValueRef::Fixed64)
+            }),
+            _ => Some(|is| is.read_raw_varint64().ok().map(UnknownValueRef::Varint)),
+        }
+    }
+}
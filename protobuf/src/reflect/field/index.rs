@@ -1,5 +1,6 @@
 use crate::descriptor::field_descriptor_proto;
 use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FieldOptions;
 use crate::json::json_name;
 use crate::reflect::file::building::FileDescriptorBuilding;
 use crate::reflect::EnumDescriptor;
@@ -10,6 +11,8 @@ use crate::reflect::ReflectValueBox;
 use crate::reflect::ReflectValueRef;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
+use crate::rustproto;
+use crate::Message;
 
 #[derive(Debug)]
 pub(crate) enum ForwardRuntimeTypeBox {
@@ -63,9 +66,21 @@ pub(crate) struct FieldIndex {
     pub(crate) json_name: String,
     pub(crate) field_type: ForwardRuntimeFieldType,
     pub(crate) default_value: Option<FieldDefaultValue>,
+    pub(crate) redacted: bool,
 }
 
+// `debug_redact` option is not present in our `descriptor.proto`
+const FIELD_OPTIONS_DEBUG_REDACT: u32 = 16;
+
 impl FieldIndex {
+    fn is_redacted(options: &FieldOptions) -> bool {
+        let debug_redact = match options.get_unknown_fields().get(FIELD_OPTIONS_DEBUG_REDACT) {
+            Some(values) => values.varint.last().map_or(false, |&v| v != 0),
+            None => false,
+        };
+        debug_redact || rustproto::exts::sensitive.get(options).unwrap_or(false)
+    }
+
     fn enum_default_value(
         field: &FieldDescriptorProto,
         building: &FileDescriptorBuilding,
@@ -120,6 +135,7 @@ impl FieldIndex {
             default_value,
             json_name,
             field_type: building.resolve_field_type(field),
+            redacted: Self::is_redacted(field.options.get_or_default()),
         }
     }
 
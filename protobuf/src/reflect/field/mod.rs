@@ -1,5 +1,6 @@
 use crate::descriptor::field_descriptor_proto;
 use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FieldOptions;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::acc::v2::map::MapFieldAccessorHolder;
 use crate::reflect::acc::v2::repeated::RepeatedFieldAccessorHolder;
@@ -46,7 +47,7 @@ impl<'a> ReflectFieldRef<'a> {
             RuntimeFieldType::Repeated(elem) => {
                 ReflectFieldRef::Repeated(ReflectRepeatedRef::new_empty(elem))
             }
-            RuntimeFieldType::Map(..) => unimplemented!(),
+            RuntimeFieldType::Map(k, v) => ReflectFieldRef::Map(ReflectMapRef::new_empty(k, v)),
         }
     }
 }
@@ -108,26 +109,54 @@ impl FieldDescriptor {
         self.get_proto().get_name()
     }
 
+    /// Message containing this field.
+    pub fn containing_message(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
     /// Oneof descriptor containing this field.
+    ///
+    /// Proto3 `optional` fields are contained in synthetic oneofs,
+    /// use [`real_containing_oneof`](FieldDescriptor::real_containing_oneof)
+    /// to skip them.
     pub fn containing_oneof(&self) -> Option<OneofDescriptor> {
+        self.oneof_index().map(|index| OneofDescriptor {
+            message_descriptor: self.message_descriptor.clone(),
+            index,
+        })
+    }
+
+    /// Oneof containing this field unless it is a synthetic oneof
+    /// of proto3 `optional` field.
+    pub fn real_containing_oneof(&self) -> Option<OneofDescriptor> {
+        self.containing_oneof().filter(|o| !o.is_synthetic())
+    }
+
+    /// Index of the containing oneof in
+    /// [`MessageDescriptor::oneofs`](MessageDescriptor::oneofs).
+    pub fn oneof_index(&self) -> Option<usize> {
         let proto = self.get_proto();
         if proto.has_oneof_index() {
-            Some(OneofDescriptor {
-                message_descriptor: self.message_descriptor.clone(),
-                index: proto.get_oneof_index() as usize,
-            })
+            Some(proto.get_oneof_index() as usize)
         } else {
             None
         }
     }
 
+    /// Field is declared as proto3 `optional`.
+    pub fn is_proto3_optional(&self) -> bool {
+        self.get_proto().get_proto3_optional()
+    }
+
     fn get_index(&self) -> &FieldIndex {
         &self.message_descriptor.get_index().fields[self.index]
     }
 
     /// JSON field name.
     ///
-    /// Can be different from `.proto` field name.
+    /// Value of `json_name` option if specified, otherwise
+    /// `.proto` field name converted to lowerCamelCase.
+    /// JSON printer uses this name, and JSON parser accepts both names.
     ///
     /// See [JSON mapping][json] for details.
     ///
@@ -136,6 +165,20 @@ impl FieldDescriptor {
         &self.get_index().json_name
     }
 
+    /// Field options.
+    pub fn options(&self) -> &FieldOptions {
+        self.get_proto().options.get_or_default()
+    }
+
+    /// Field is marked with `debug_redact` or `rustproto.sensitive` option.
+    ///
+    /// Values of such fields are replaced with
+    /// [`REDACTED`](crate::text_format::REDACTED) in `Debug`,
+    /// text format and JSON output.
+    pub fn is_redacted(&self) -> bool {
+        self.get_index().redacted
+    }
+
     /// If this field is optional or required.
     pub fn is_singular(&self) -> bool {
         match self.get_proto().get_label() {
@@ -325,6 +368,28 @@ impl FieldDescriptor {
         }
     }
 
+    /// Clear field: unset singular field or remove all elements
+    /// of repeated or map field.
+    ///
+    /// Clearing `oneof` field which is not currently set is no-op.
+    ///
+    /// # Panics
+    ///
+    /// If this field belongs to a different message type.
+    pub fn clear_field(&self, m: &mut dyn MessageDyn) {
+        if self.is_repeated_or_map() {
+            match self.runtime_field_type() {
+                RuntimeFieldType::Map(..) => self.mut_map(m).clear(),
+                _ => self.mut_repeated(m).clear(),
+            }
+            return;
+        }
+        match self.singular() {
+            SingularFieldAccessorRef::Generated(g) => g.accessor.clear_field(m),
+            SingularFieldAccessorRef::Dynamic(d) => d.clear_field(m),
+        }
+    }
+
     /// Dynamic representation of field type.
     pub fn runtime_field_type(&self) -> RuntimeFieldType {
         self.get_index().field_type.resolve(self)
@@ -0,0 +1,593 @@
+//! Validation of `FileDescriptorProto` sets before building dynamic descriptors.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::descriptor::field_descriptor_proto;
+use crate::descriptor::DescriptorProto;
+use crate::descriptor::EnumDescriptorProto;
+use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::FileDescriptorSet;
+use crate::reflect::name::append_path;
+
+/// Problem found in a set of [`FileDescriptorProto`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DescriptorSetProblem {
+    /// Several files have the same name.
+    DuplicateFile(String),
+    /// Files with the same name in different descriptor sets
+    /// passed to [`FileDescriptor::try_new_dynamic_fds_merged`](crate::reflect::FileDescriptor::try_new_dynamic_fds_merged)
+    /// are not identical.
+    ConflictingFile {
+        /// File name
+        file: String,
+        /// Index of the first set with the file
+        first_set: usize,
+        /// Index of the set with different file content
+        second_set: usize,
+    },
+    /// Dependency of a file is not in the set.
+    MissingDependency {
+        /// File name
+        file: String,
+        /// Name of missing dependency
+        dependency: String,
+    },
+    /// File depends on itself or on a file in a dependency cycle.
+    DependencyCycle(String),
+    /// Message, enum or service name is defined more than once.
+    DuplicateName {
+        /// Fully-qualified name without leading dot
+        name: String,
+        /// File of the first definition
+        first_file: String,
+        /// File of the second definition
+        second_file: String,
+    },
+    /// Several fields of a message have the same number.
+    DuplicateFieldNumber {
+        /// Fully-qualified message name
+        message: String,
+        /// Field number
+        number: i32,
+        /// Name of the first field
+        first_field: String,
+        /// Name of the second field
+        second_field: String,
+    },
+    /// Field number is in a reserved range of the message.
+    ReservedFieldNumber {
+        /// Fully-qualified message name
+        message: String,
+        /// Field name
+        field: String,
+        /// Field number
+        number: i32,
+    },
+    /// Message or enum type of the field is not found in the file
+    /// or its dependencies, or it is an enum where a message is expected
+    /// or vice versa.
+    UnresolvedType {
+        /// Fully-qualified field name
+        field: String,
+        /// Type name as specified in the field
+        type_name: String,
+    },
+}
+
+impl fmt::Display for DescriptorSetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DescriptorSetProblem::DuplicateFile(file) => write!(f, "duplicate file {}", file),
+            DescriptorSetProblem::ConflictingFile {
+                file,
+                first_set,
+                second_set,
+            } => write!(
+                f,
+                "file {} differs in descriptor sets {} and {}",
+                file, first_set, second_set
+            ),
+            DescriptorSetProblem::MissingDependency { file, dependency } => {
+                write!(f, "dependency {} of {} is not found", dependency, file)
+            }
+            DescriptorSetProblem::DependencyCycle(file) => {
+                write!(f, "dependencies of {} form a cycle", file)
+            }
+            DescriptorSetProblem::DuplicateName {
+                name,
+                first_file,
+                second_file,
+            } => write!(
+                f,
+                "{} is defined in {} and {}",
+                name, first_file, second_file
+            ),
+            DescriptorSetProblem::DuplicateFieldNumber {
+                message,
+                number,
+                first_field,
+                second_field,
+            } => write!(
+                f,
+                "fields {} and {} of {} have the same number {}",
+                first_field, second_field, message, number
+            ),
+            DescriptorSetProblem::ReservedFieldNumber {
+                message,
+                field,
+                number,
+            } => write!(
+                f,
+                "field {} of {} uses reserved number {}",
+                field, message, number
+            ),
+            DescriptorSetProblem::UnresolvedType { field, type_name } => {
+                write!(f, "type {} of field {} is not found", type_name, field)
+            }
+        }
+    }
+}
+
+/// Error returned by [`FileDescriptor::try_new_dynamic_fds`](crate::reflect::FileDescriptor::try_new_dynamic_fds)
+/// and [`FileDescriptor::try_new_dynamic_fds_merged`](crate::reflect::FileDescriptor::try_new_dynamic_fds_merged).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptorSetError {
+    /// All problems found in the set.
+    pub problems: Vec<DescriptorSetProblem>,
+}
+
+impl fmt::Display for DescriptorSetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid descriptor set")?;
+        for (i, p) in self.problems.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, p)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for DescriptorSetError {}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    Message,
+    Enum,
+    Service,
+}
+
+struct Definition<'a> {
+    file: &'a str,
+    kind: Kind,
+}
+
+struct Checker<'a> {
+    files: HashMap<&'a str, &'a FileDescriptorProto>,
+    definitions: HashMap<String, Definition<'a>>,
+    problems: Vec<DescriptorSetProblem>,
+}
+
+impl<'a> Checker<'a> {
+    fn define(&mut self, file: &'a str, name: String, kind: Kind) {
+        if let Some(first) = self.definitions.get(&name) {
+            self.problems.push(DescriptorSetProblem::DuplicateName {
+                name,
+                first_file: first.file.to_owned(),
+                second_file: file.to_owned(),
+            });
+        } else {
+            self.definitions.insert(name, Definition { file, kind });
+        }
+    }
+
+    fn define_enum(&mut self, file: &'a str, scope: &str, e: &EnumDescriptorProto) {
+        let mut name = scope.to_owned();
+        append_path(&mut name, e.get_name());
+        self.define(file, name, Kind::Enum);
+    }
+
+    fn define_message(&mut self, file: &'a str, scope: &str, m: &'a DescriptorProto) {
+        let mut name = scope.to_owned();
+        append_path(&mut name, m.get_name());
+        for nested in &m.nested_type {
+            self.define_message(file, &name, nested);
+        }
+        for e in &m.enum_type {
+            self.define_enum(file, &name, e);
+        }
+        self.define(file, name, Kind::Message);
+    }
+
+    fn check_dependencies(&mut self, protos: &'a [FileDescriptorProto]) {
+        for p in protos {
+            for d in &p.dependency {
+                if !self.files.contains_key(d.as_str()) {
+                    self.problems.push(DescriptorSetProblem::MissingDependency {
+                        file: p.get_name().to_owned(),
+                        dependency: d.clone(),
+                    });
+                }
+            }
+        }
+
+        // Files which can be built: all dependencies are built or missing
+        let mut built: HashSet<&str> = HashSet::new();
+        loop {
+            let before = built.len();
+            for p in protos {
+                if p.dependency
+                    .iter()
+                    .all(|d| built.contains(d.as_str()) || !self.files.contains_key(d.as_str()))
+                {
+                    built.insert(p.get_name());
+                }
+            }
+            if built.len() == before {
+                break;
+            }
+        }
+        for p in protos {
+            if !built.contains(p.get_name()) {
+                self.problems.push(DescriptorSetProblem::DependencyCycle(
+                    p.get_name().to_owned(),
+                ));
+            }
+        }
+    }
+
+    /// Files whose types can be referenced from given file:
+    /// the file itself, its dependencies and public dependencies of those.
+    fn visible_files(&self, file: &'a FileDescriptorProto) -> HashSet<&'a str> {
+        let mut visible = HashSet::new();
+        visible.insert(file.get_name());
+        let mut stack: Vec<&str> = file.dependency.iter().map(|d| d.as_str()).collect();
+        while let Some(name) = stack.pop() {
+            if !visible.insert(name) {
+                continue;
+            }
+            if let Some(dep) = self.files.get(name) {
+                for &i in &dep.public_dependency {
+                    if let Some(d) = dep.dependency.get(i as usize) {
+                        stack.push(d);
+                    }
+                }
+            }
+        }
+        visible
+    }
+
+    fn check_field_type(
+        &mut self,
+        scope: &str,
+        field: &FieldDescriptorProto,
+        visible: &HashSet<&str>,
+    ) {
+        let kind = match field.get_field_type() {
+            field_descriptor_proto::Type::TYPE_MESSAGE
+            | field_descriptor_proto::Type::TYPE_GROUP => Kind::Message,
+            field_descriptor_proto::Type::TYPE_ENUM => Kind::Enum,
+            _ => return,
+        };
+        let type_name = field.get_type_name();
+        let resolved = type_name.starts_with('.')
+            && match self.definitions.get(&type_name[1..]) {
+                Some(d) => d.kind == kind && visible.contains(d.file),
+                None => false,
+            };
+        if !resolved {
+            let mut name = scope.to_owned();
+            append_path(&mut name, field.get_name());
+            self.problems.push(DescriptorSetProblem::UnresolvedType {
+                field: name,
+                type_name: type_name.to_owned(),
+            });
+        }
+    }
+
+    fn check_message(&mut self, scope: &str, m: &DescriptorProto, visible: &HashSet<&str>) {
+        let mut name = scope.to_owned();
+        append_path(&mut name, m.get_name());
+
+        let mut numbers: HashMap<i32, &str> = HashMap::new();
+        for field in &m.field {
+            let number = field.get_number();
+            if let Some(first) = numbers.insert(number, field.get_name()) {
+                self.problems
+                    .push(DescriptorSetProblem::DuplicateFieldNumber {
+                        message: name.clone(),
+                        number,
+                        first_field: first.to_owned(),
+                        second_field: field.get_name().to_owned(),
+                    });
+            }
+            // Reserved range end is exclusive
+            if m.reserved_range
+                .iter()
+                .any(|r| r.get_start() <= number && number < r.get_end())
+            {
+                self.problems
+                    .push(DescriptorSetProblem::ReservedFieldNumber {
+                        message: name.clone(),
+                        field: field.get_name().to_owned(),
+                        number,
+                    });
+            }
+            self.check_field_type(&name, field, visible);
+        }
+        for field in &m.extension {
+            self.check_field_type(&name, field, visible);
+        }
+        for nested in &m.nested_type {
+            self.check_message(&name, nested, visible);
+        }
+    }
+}
+
+/// Find problems which would make building dynamic descriptors
+/// from given files panic or produce wrong descriptors.
+pub(crate) fn check_fds(protos: &[FileDescriptorProto]) -> Vec<DescriptorSetProblem> {
+    let mut checker = Checker {
+        files: HashMap::new(),
+        definitions: HashMap::new(),
+        problems: Vec::new(),
+    };
+
+    for p in protos {
+        if checker.files.insert(p.get_name(), p).is_some() {
+            checker
+                .problems
+                .push(DescriptorSetProblem::DuplicateFile(p.get_name().to_owned()));
+        }
+    }
+
+    checker.check_dependencies(protos);
+
+    for p in protos {
+        let file = p.get_name();
+        for m in &p.message_type {
+            checker.define_message(file, p.get_package(), m);
+        }
+        for e in &p.enum_type {
+            checker.define_enum(file, p.get_package(), e);
+        }
+        for s in &p.service {
+            let mut name = p.get_package().to_owned();
+            append_path(&mut name, s.get_name());
+            checker.define(file, name, Kind::Service);
+        }
+    }
+
+    for p in protos {
+        let visible = checker.visible_files(p);
+        for m in &p.message_type {
+            checker.check_message(p.get_package(), m, &visible);
+        }
+        for field in &p.extension {
+            checker.check_field_type(p.get_package(), field, &visible);
+        }
+    }
+
+    checker.problems
+}
+
+/// Concatenate files of several sets dropping files already added
+/// from previous sets.
+///
+/// Files with the same name are identical if they are equal
+/// ignoring `source_code_info`, otherwise the conflict is reported.
+pub(crate) fn merge_fds(
+    sets: Vec<FileDescriptorSet>,
+) -> (Vec<FileDescriptorProto>, Vec<DescriptorSetProblem>) {
+    fn without_source_info(p: &FileDescriptorProto) -> FileDescriptorProto {
+        let mut p = p.clone();
+        p.source_code_info.clear();
+        p
+    }
+
+    // file name -> (index in `files`, index of set)
+    let mut added: HashMap<String, (usize, usize)> = HashMap::new();
+    let mut files: Vec<FileDescriptorProto> = Vec::new();
+    let mut problems = Vec::new();
+    for (set_index, set) in sets.into_iter().enumerate() {
+        for p in set.file {
+            match added.get(p.get_name()) {
+                Some(&(file_index, first_set)) if first_set != set_index => {
+                    if without_source_info(&files[file_index]) != without_source_info(&p) {
+                        problems.push(DescriptorSetProblem::ConflictingFile {
+                            file: p.get_name().to_owned(),
+                            first_set,
+                            second_set: set_index,
+                        });
+                    }
+                }
+                // Duplicate within one set is reported by `check_fds`
+                _ => {
+                    added.insert(p.get_name().to_owned(), (files.len(), set_index));
+                    files.push(p);
+                }
+            }
+        }
+    }
+    (files, problems)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::descriptor_proto::ReservedRange;
+    use crate::descriptor::field_descriptor_proto::Type;
+
+    fn field(name: &str, number: i32, t: Type, type_name: &str) -> FieldDescriptorProto {
+        let mut f = FieldDescriptorProto::new();
+        f.set_name(name.to_owned());
+        f.set_number(number);
+        f.set_field_type(t);
+        if !type_name.is_empty() {
+            f.set_type_name(type_name.to_owned());
+        }
+        f
+    }
+
+    fn file(name: &str, deps: &[&str], messages: Vec<DescriptorProto>) -> FileDescriptorProto {
+        let mut f = FileDescriptorProto::new();
+        f.set_name(name.to_owned());
+        f.set_package("p".to_owned());
+        f.dependency = deps.iter().map(|d| (*d).to_owned()).collect();
+        f.message_type = messages;
+        f
+    }
+
+    fn message(name: &str, fields: Vec<FieldDescriptorProto>) -> DescriptorProto {
+        let mut m = DescriptorProto::new();
+        m.set_name(name.to_owned());
+        m.field = fields;
+        m
+    }
+
+    #[test]
+    fn valid() {
+        let a = file("a.proto", &[], vec![message("A", vec![])]);
+        let b = file(
+            "b.proto",
+            &["a.proto"],
+            vec![message(
+                "B",
+                vec![field("a", 1, Type::TYPE_MESSAGE, ".p.A")],
+            )],
+        );
+        assert_eq!(Vec::<DescriptorSetProblem>::new(), check_fds(&[a, b]));
+    }
+
+    #[test]
+    fn duplicates() {
+        let mut m = message(
+            "M",
+            vec![
+                field("x", 1, Type::TYPE_INT32, ""),
+                field("y", 1, Type::TYPE_INT32, ""),
+                field("z", 5, Type::TYPE_INT32, ""),
+            ],
+        );
+        let mut range = ReservedRange::new();
+        range.set_start(5);
+        range.set_end(6);
+        m.reserved_range.push(range);
+        let a = file("a.proto", &[], vec![m]);
+        let b = file("b.proto", &[], vec![message("M", vec![])]);
+        assert_eq!(
+            vec![
+                DescriptorSetProblem::DuplicateName {
+                    name: "p.M".to_owned(),
+                    first_file: "a.proto".to_owned(),
+                    second_file: "b.proto".to_owned(),
+                },
+                DescriptorSetProblem::DuplicateFieldNumber {
+                    message: "p.M".to_owned(),
+                    number: 1,
+                    first_field: "x".to_owned(),
+                    second_field: "y".to_owned(),
+                },
+                DescriptorSetProblem::ReservedFieldNumber {
+                    message: "p.M".to_owned(),
+                    field: "z".to_owned(),
+                    number: 5,
+                },
+            ],
+            check_fds(&[a, b])
+        );
+    }
+
+    #[test]
+    fn unresolved() {
+        let a = file("a.proto", &[], vec![message("A", vec![])]);
+        // `a.proto` is not imported, `A` is not an enum
+        let b = file(
+            "b.proto",
+            &["c.proto"],
+            vec![message(
+                "B",
+                vec![
+                    field("a", 1, Type::TYPE_MESSAGE, ".p.A"),
+                    field("e", 2, Type::TYPE_ENUM, ".p.B"),
+                ],
+            )],
+        );
+        assert_eq!(
+            vec![
+                DescriptorSetProblem::MissingDependency {
+                    file: "b.proto".to_owned(),
+                    dependency: "c.proto".to_owned(),
+                },
+                DescriptorSetProblem::UnresolvedType {
+                    field: "p.B.a".to_owned(),
+                    type_name: ".p.A".to_owned(),
+                },
+                DescriptorSetProblem::UnresolvedType {
+                    field: "p.B.e".to_owned(),
+                    type_name: ".p.B".to_owned(),
+                },
+            ],
+            check_fds(&[a, b])
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let a = file("a.proto", &["b.proto"], vec![]);
+        let b = file("b.proto", &["a.proto"], vec![]);
+        let c = file("c.proto", &[], vec![]);
+        assert_eq!(
+            vec![
+                DescriptorSetProblem::DependencyCycle("a.proto".to_owned()),
+                DescriptorSetProblem::DependencyCycle("b.proto".to_owned()),
+            ],
+            check_fds(&[a, b, c])
+        );
+    }
+
+    fn set(files: Vec<FileDescriptorProto>) -> FileDescriptorSet {
+        let mut set = FileDescriptorSet::new();
+        set.file = files;
+        set
+    }
+
+    #[test]
+    fn merge() {
+        let a = file("a.proto", &[], vec![message("A", vec![])]);
+        let mut a_with_source_info = a.clone();
+        a_with_source_info.source_code_info.mut_or_default();
+        let b = file("b.proto", &["a.proto"], vec![message("B", vec![])]);
+        let (files, problems) =
+            merge_fds(vec![set(vec![a.clone()]), set(vec![a_with_source_info, b])]);
+        assert_eq!(Vec::<DescriptorSetProblem>::new(), problems);
+        assert_eq!(
+            vec!["a.proto", "b.proto"],
+            files.iter().map(|f| f.get_name()).collect::<Vec<_>>()
+        );
+        assert_eq!(Vec::<DescriptorSetProblem>::new(), check_fds(&files));
+    }
+
+    #[test]
+    fn merge_conflict() {
+        let a = file("a.proto", &[], vec![message("A", vec![])]);
+        let a2 = file("a.proto", &[], vec![message("A2", vec![])]);
+        let (files, problems) = merge_fds(vec![set(vec![a.clone()]), set(vec![]), set(vec![a2])]);
+        assert_eq!(vec![a.clone()], files);
+        assert_eq!(
+            vec![DescriptorSetProblem::ConflictingFile {
+                file: "a.proto".to_owned(),
+                first_set: 0,
+                second_set: 2,
+            }],
+            problems
+        );
+
+        // Duplicates within one set are left for `check_fds`
+        let (files, problems) = merge_fds(vec![set(vec![a.clone(), a])]);
+        assert_eq!(2, files.len());
+        assert!(problems.is_empty());
+    }
+}
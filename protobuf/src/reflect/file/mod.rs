@@ -1,5 +1,10 @@
 use crate::descriptor::DescriptorProto;
 use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::FileDescriptorSet;
+use crate::descriptor::FileOptions;
+use crate::reflect::file::check::check_fds;
+use crate::reflect::file::check::merge_fds;
+use crate::reflect::file::check::DescriptorSetError;
 use crate::reflect::file::dynamic::DynamicFileDescriptor;
 use crate::reflect::file::fds::FdsBuilder;
 use crate::reflect::file::index::FileIndex;
@@ -8,6 +13,7 @@ use crate::reflect::name::protobuf_name_starts_with_package;
 use crate::reflect::EnumDescriptor;
 use crate::reflect::GeneratedFileDescriptor;
 use crate::reflect::MessageDescriptor;
+use crate::reflect::ServiceDescriptor;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::hash::Hash;
@@ -15,6 +21,7 @@ use std::hash::Hasher;
 use std::sync::Arc;
 
 pub(crate) mod building;
+pub(crate) mod check;
 pub(crate) mod dynamic;
 pub(crate) mod fds;
 pub(crate) mod generated;
@@ -93,6 +100,20 @@ impl FileDescriptor {
             .collect()
     }
 
+    /// All messages of this file including nested messages.
+    pub(crate) fn messages_including_nested(&self) -> Vec<MessageDescriptor> {
+        (0..self.index().messages.len())
+            .map(|i| MessageDescriptor::new(self.clone(), i))
+            .collect()
+    }
+
+    /// All enums of this file including nested enums.
+    pub(crate) fn enums_including_nested(&self) -> Vec<EnumDescriptor> {
+        (0..self.index().enums.len())
+            .map(|i| EnumDescriptor::new(self.clone(), i))
+            .collect()
+    }
+
     /// Get top-level enums.
     pub fn enums(&self) -> Vec<EnumDescriptor> {
         self.proto()
@@ -103,6 +124,21 @@ impl FileDescriptor {
             .collect()
     }
 
+    /// Get services.
+    pub fn services(&self) -> Vec<ServiceDescriptor> {
+        (0..self.proto().service.len())
+            .map(|index| ServiceDescriptor {
+                file_descriptor: self.clone(),
+                index,
+            })
+            .collect()
+    }
+
+    /// Find service by name as specified in `.proto` file.
+    pub fn service_by_name(&self, name: &str) -> Option<ServiceDescriptor> {
+        self.services().into_iter().find(|s| s.get_name() == name)
+    }
+
     /// Find message by name relative to the package.
     ///
     /// Only search in the current file, not in any dependencies.
@@ -136,6 +172,13 @@ impl FileDescriptor {
         }
     }
 
+    /// Find message by fully-qualified name in this file and all its dependencies.
+    pub(crate) fn message_by_full_name_with_deps(&self, name: &str) -> Option<MessageDescriptor> {
+        self.all_files()
+            .into_iter()
+            .find_map(|f| f.message_by_full_name(name))
+    }
+
     /// Find enum by name fully-qualified name.
     ///
     /// Only search in the current file, not in any dependencies.
@@ -183,10 +226,50 @@ impl FileDescriptor {
     }
 
     /// Create a set of file descriptors from individual file descriptors.
+    ///
+    /// # Panics
+    ///
+    /// If dependencies or types referenced by fields are missing.
+    /// Use [`try_new_dynamic_fds`](FileDescriptor::try_new_dynamic_fds)
+    /// for descriptors which are not known to be valid.
     pub fn new_dynamic_fds(protos: Vec<FileDescriptorProto>) -> Vec<FileDescriptor> {
         FdsBuilder::build(protos)
     }
 
+    /// Create a set of file descriptors from individual file descriptors
+    /// after checking them.
+    ///
+    /// Duplicate files and type names, duplicate or reserved field numbers,
+    /// missing dependencies and unresolved field types are reported as errors.
+    pub fn try_new_dynamic_fds(
+        protos: Vec<FileDescriptorProto>,
+    ) -> Result<Vec<FileDescriptor>, DescriptorSetError> {
+        let problems = check_fds(&protos);
+        if !problems.is_empty() {
+            return Err(DescriptorSetError { problems });
+        }
+        Ok(FdsBuilder::build(protos))
+    }
+
+    /// Create a set of file descriptors from several descriptor sets,
+    /// e. g. a base schema and its extensions built separately.
+    ///
+    /// Files present in several sets must be identical
+    /// (`source_code_info` is ignored), and only the first copy is used.
+    /// Files may depend on files from other sets.
+    /// Files are returned in order of the first appearance, and the result
+    /// is checked like in [`try_new_dynamic_fds`](FileDescriptor::try_new_dynamic_fds).
+    pub fn try_new_dynamic_fds_merged(
+        sets: Vec<FileDescriptorSet>,
+    ) -> Result<Vec<FileDescriptor>, DescriptorSetError> {
+        let (protos, mut problems) = merge_fds(sets);
+        problems.extend(check_fds(&protos));
+        if !problems.is_empty() {
+            return Err(DescriptorSetError { problems });
+        }
+        Ok(FdsBuilder::build(protos))
+    }
+
     /// `.proto` data for this file.
     pub fn proto(&self) -> &FileDescriptorProto {
         match &self.imp {
@@ -195,7 +278,12 @@ impl FileDescriptor {
         }
     }
 
-    fn deps(&self) -> &[FileDescriptor] {
+    /// File options.
+    pub fn options(&self) -> &FileOptions {
+        self.proto().options.get_or_default()
+    }
+
+    pub(crate) fn deps(&self) -> &[FileDescriptor] {
         match &self.imp {
             FileDescriptorImpl::Generated(g) => &g.dependencies,
             FileDescriptorImpl::Dynamic(d) => &d.dependencies,
@@ -211,7 +299,42 @@ impl FileDescriptor {
             .collect()
     }
 
-    fn _all_files(&self) -> Vec<&FileDescriptor> {
+    /// `FileDescriptorSet` with this file and all its dependencies, recursively.
+    ///
+    /// Each file is included once, and dependencies precede files
+    /// which import them, like in output of `protoc --include_imports`.
+    pub fn file_descriptor_set(&self) -> FileDescriptorSet {
+        FileDescriptor::file_descriptor_set_for_files(&[self.clone()])
+    }
+
+    /// `FileDescriptorSet` with given files and all their dependencies.
+    ///
+    /// Files are deduplicated by name.
+    pub fn file_descriptor_set_for_files(files: &[FileDescriptor]) -> FileDescriptorSet {
+        fn add<'a>(
+            file: &'a FileDescriptor,
+            visited: &mut HashSet<&'a str>,
+            set: &mut FileDescriptorSet,
+        ) {
+            if !visited.insert(file.proto().get_name()) {
+                return;
+            }
+            for dep in file.deps() {
+                add(dep, visited, set);
+            }
+            set.file.push(file.proto().clone());
+        }
+
+        let mut visited = HashSet::new();
+        let mut set = FileDescriptorSet::new();
+        for file in files {
+            add(file, &mut visited, &mut set);
+        }
+        set
+    }
+
+    /// This file and all its dependencies, recursively.
+    pub(crate) fn all_files(&self) -> Vec<&FileDescriptor> {
         let mut r = Vec::new();
         let mut visited = HashSet::new();
 
@@ -238,4 +361,67 @@ mod test {
     fn eq() {
         assert!(descriptor::file_descriptor() == descriptor::file_descriptor().clone());
     }
+
+    #[test]
+    fn file_descriptor_set() {
+        let set = crate::well_known_types::file_descriptors::api().file_descriptor_set();
+        let names: Vec<&str> = set.file.iter().map(|f| f.get_name()).collect();
+        assert_eq!(
+            vec![
+                "google/protobuf/source_context.proto",
+                "google/protobuf/any.proto",
+                "google/protobuf/type.proto",
+                "google/protobuf/api.proto",
+            ],
+            names
+        );
+
+        let set = super::FileDescriptor::file_descriptor_set_for_files(&[
+            crate::well_known_types::file_descriptors::type_pb(),
+            crate::well_known_types::file_descriptors::api(),
+        ]);
+        assert_eq!(4, set.file.len());
+    }
+
+    #[test]
+    fn merged() {
+        use crate::well_known_types::file_descriptors;
+
+        // `type.proto` depends on `any.proto` from the first set
+        let mut layer = file_descriptors::type_pb().file_descriptor_set();
+        layer
+            .file
+            .retain(|f| f.get_name() != "google/protobuf/any.proto");
+        let files = super::FileDescriptor::try_new_dynamic_fds_merged(vec![
+            file_descriptors::any().file_descriptor_set(),
+            layer,
+            file_descriptors::api().file_descriptor_set(),
+        ])
+        .unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.proto().get_name()).collect();
+        assert_eq!(
+            vec![
+                "google/protobuf/any.proto",
+                "google/protobuf/source_context.proto",
+                "google/protobuf/type.proto",
+                "google/protobuf/api.proto",
+            ],
+            names
+        );
+        assert!(files[3]
+            .message_by_full_name_with_deps(".google.protobuf.Any")
+            .is_some());
+
+        let mut conflicting = file_descriptors::any().file_descriptor_set();
+        conflicting.file[0].set_package("other".to_owned());
+        let e = super::FileDescriptor::try_new_dynamic_fds_merged(vec![
+            file_descriptors::any().file_descriptor_set(),
+            conflicting,
+        ])
+        .unwrap_err();
+        assert_eq!(
+            "invalid descriptor set: file google/protobuf/any.proto differs in descriptor sets 0 and 1",
+            e.to_string()
+        );
+    }
 }
@@ -0,0 +1,249 @@
+//! Fingerprint of message type schema.
+
+use std::collections::BTreeMap;
+
+use crate::descriptor::FieldDescriptorProto;
+use crate::reflect::EnumDescriptor;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::RuntimeFieldType;
+use crate::reflect::RuntimeTypeBox;
+
+/// FNV-1a, fingerprint must be the same across Rust releases and platforms.
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Fnv64 {
+        Fnv64(0xcbf29ce484222325)
+    }
+
+    fn write_u8(&mut self, b: u8) {
+        self.0 ^= b as u64;
+        self.0 = self.0.wrapping_mul(0x100000001b3);
+    }
+
+    fn write_u64(&mut self, v: u64) {
+        for &b in v.to_le_bytes().iter() {
+            self.write_u8(b);
+        }
+    }
+
+    /// Write length-prefixed string so adjacent inputs do not collide.
+    fn write_str(&mut self, s: &str) {
+        self.write_u64(s.len() as u64);
+        for &b in s.as_bytes() {
+            self.write_u8(b);
+        }
+    }
+}
+
+// Tags mark kinds of hashed entities
+const TAG_MESSAGE: u8 = 1;
+const TAG_ENUM: u8 = 2;
+const TAG_FIELD: u8 = 3;
+const TAG_MAP: u8 = 4;
+const TAG_ENUM_VALUE: u8 = 5;
+
+/// Message and enum types reachable from the root message,
+/// ordered by full name.
+#[derive(Default)]
+struct ReachableTypes {
+    messages: BTreeMap<String, MessageDescriptor>,
+    enums: BTreeMap<String, EnumDescriptor>,
+}
+
+impl ReachableTypes {
+    fn collect(root: &MessageDescriptor) -> ReachableTypes {
+        let mut types = ReachableTypes::default();
+        let mut queue = vec![root.clone()];
+        while let Some(message) = queue.pop() {
+            if types.messages.contains_key(message.full_name()) {
+                continue;
+            }
+            let mut add = |t: RuntimeTypeBox| match t {
+                RuntimeTypeBox::Message(m) => queue.push(m),
+                RuntimeTypeBox::Enum(e) => {
+                    types.enums.insert(e.full_name().to_owned(), e);
+                }
+                _ => {}
+            };
+            for field in message.fields() {
+                match field.runtime_field_type() {
+                    RuntimeFieldType::Singular(t) | RuntimeFieldType::Repeated(t) => add(t),
+                    RuntimeFieldType::Map(k, v) => {
+                        add(k);
+                        add(v);
+                    }
+                }
+            }
+            types
+                .messages
+                .insert(message.full_name().to_owned(), message);
+        }
+        types
+    }
+}
+
+fn hash_field_type(proto: &FieldDescriptorProto, t: &RuntimeTypeBox, h: &mut Fnv64) {
+    h.write_u64(proto.get_field_type() as u64);
+    match t {
+        RuntimeTypeBox::Message(m) => h.write_str(m.full_name()),
+        RuntimeTypeBox::Enum(e) => h.write_str(e.full_name()),
+        _ => {}
+    }
+}
+
+fn map_entry(message: &MessageDescriptor, field: &FieldDescriptor) -> MessageDescriptor {
+    let type_name = field.get_proto().get_type_name().trim_start_matches('.');
+    message
+        .get_nested_messages()
+        .into_iter()
+        .find(|m| m.full_name() == type_name)
+        .expect("map entry message")
+}
+
+fn hash_message(message: &MessageDescriptor, h: &mut Fnv64) {
+    h.write_u8(TAG_MESSAGE);
+    h.write_str(message.full_name());
+
+    let mut fields: Vec<FieldDescriptor> = message.fields().collect();
+    fields.sort_by_key(|f| f.get_proto().get_number());
+    for field in fields {
+        let proto = field.get_proto();
+        h.write_u8(TAG_FIELD);
+        h.write_u64(proto.get_number() as u64);
+        h.write_str(proto.get_name());
+        h.write_u64(proto.get_label() as u64);
+        h.write_u8(proto.get_proto3_optional() as u8);
+        match field.runtime_field_type() {
+            RuntimeFieldType::Singular(t) | RuntimeFieldType::Repeated(t) => {
+                hash_field_type(proto, &t, h)
+            }
+            RuntimeFieldType::Map(k, v) => {
+                // Hash map as key and value type, not as entry message
+                let entry = map_entry(message, &field);
+                let entry_field = |number| {
+                    entry
+                        .get_proto()
+                        .field
+                        .iter()
+                        .find(|f| f.get_number() == number)
+                        .expect("map entry field")
+                };
+                h.write_u8(TAG_MAP);
+                hash_field_type(entry_field(1), &k, h);
+                hash_field_type(entry_field(2), &v, h);
+            }
+        }
+    }
+}
+
+fn hash_enum(e: &EnumDescriptor, h: &mut Fnv64) {
+    h.write_u8(TAG_ENUM);
+    h.write_str(e.full_name());
+
+    let mut values: Vec<(i32, &str)> = e
+        .get_proto()
+        .value
+        .iter()
+        .map(|v| (v.get_number(), v.get_name()))
+        .collect();
+    values.sort();
+    for (number, name) in values {
+        h.write_u8(TAG_ENUM_VALUE);
+        h.write_u64(number as u64);
+        h.write_str(name);
+    }
+}
+
+pub(crate) fn message_fingerprint(root: &MessageDescriptor) -> u64 {
+    let types = ReachableTypes::collect(root);
+
+    let mut h = Fnv64::new();
+    h.write_str(root.full_name());
+    for message in types.messages.values() {
+        hash_message(message, &mut h);
+    }
+    for e in types.enums.values() {
+        hash_enum(e, &mut h);
+    }
+    h.0
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::FileDescriptor;
+    use crate::well_known_types::Duration;
+    use crate::well_known_types::Struct;
+    use crate::Message;
+
+    fn dynamic_copy(file: &FileDescriptor) -> FileDescriptor {
+        FileDescriptor::new_dynamic(file.proto().clone(), Vec::new())
+    }
+
+    #[test]
+    fn generated_and_dynamic() {
+        let generated = Struct::descriptor_static();
+        let file = dynamic_copy(generated.file_descriptor());
+        let dynamic = file.message_by_package_relative_name("Struct").unwrap();
+        assert_eq!(generated.fingerprint(), dynamic.fingerprint());
+        assert_ne!(
+            generated.fingerprint(),
+            Duration::descriptor_static().fingerprint()
+        );
+    }
+
+    #[test]
+    fn stable() {
+        // Fingerprint must not change between releases
+        assert_eq!(
+            0x324ba93c618cb991,
+            Duration::descriptor_static().fingerprint()
+        );
+    }
+
+    #[test]
+    fn field_changes() {
+        fn duration(file: &mut FileDescriptorProto) -> &mut DescriptorProto {
+            &mut file.message_type[0]
+        }
+
+        fn fingerprint(file: FileDescriptorProto) -> u64 {
+            FileDescriptor::new_dynamic(file, Vec::new())
+                .message_by_package_relative_name("Duration")
+                .unwrap()
+                .fingerprint()
+        }
+
+        let original = Duration::descriptor_static()
+            .file_descriptor_proto()
+            .clone();
+        assert_eq!(
+            fingerprint(original.clone()),
+            Duration::descriptor_static().fingerprint()
+        );
+
+        // Declaration order and options do not matter
+        let mut file = original.clone();
+        duration(&mut file).field.reverse();
+        file.options
+            .mut_or_default()
+            .set_java_package("x".to_owned());
+        assert_eq!(fingerprint(original.clone()), fingerprint(file));
+
+        let mut file = original.clone();
+        duration(&mut file).field[1].set_number(3);
+        assert_ne!(fingerprint(original.clone()), fingerprint(file));
+
+        let mut file = original.clone();
+        duration(&mut file).field[1].set_name("nanoseconds".to_owned());
+        assert_ne!(fingerprint(original.clone()), fingerprint(file));
+
+        let mut file = original.clone();
+        duration(&mut file).field[1]
+            .set_field_type(crate::descriptor::field_descriptor_proto::Type::TYPE_SINT32);
+        assert_ne!(fingerprint(original), fingerprint(file));
+    }
+}
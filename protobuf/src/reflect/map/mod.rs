@@ -58,10 +58,17 @@ impl<'a> IntoIterator for &'a dyn ReflectMap {
     }
 }
 
+#[derive(Clone)]
+enum ReflectMapRefImpl<'a> {
+    Generated(&'a dyn ReflectMap),
+    // Types of keys and values of an empty map, nothing to borrow
+    DynamicEmpty(RuntimeTypeBox, RuntimeTypeBox),
+}
+
 /// Dynamic reference to `map` field
-#[derive(Copy, Clone)]
+#[derive(Clone)]
 pub struct ReflectMapRef<'a> {
-    map: &'a dyn ReflectMap,
+    imp: ReflectMapRefImpl<'a>,
 }
 
 /// Dynamic mutable reference to `map` field
@@ -71,32 +78,57 @@ pub struct ReflectMapMut<'a> {
 
 impl<'a> ReflectMapRef<'a> {
     pub(crate) fn new(map: &'a dyn ReflectMap) -> ReflectMapRef<'a> {
-        ReflectMapRef { map }
+        ReflectMapRef {
+            imp: ReflectMapRefImpl::Generated(map),
+        }
+    }
+
+    pub(crate) fn new_empty(key: RuntimeTypeBox, value: RuntimeTypeBox) -> ReflectMapRef<'static> {
+        ReflectMapRef {
+            imp: ReflectMapRefImpl::DynamicEmpty(key, value),
+        }
     }
 
     /// Size of the map
     pub fn len(&self) -> usize {
-        self.map.len()
+        match &self.imp {
+            ReflectMapRefImpl::Generated(m) => m.len(),
+            ReflectMapRefImpl::DynamicEmpty(..) => 0,
+        }
     }
 
     /// Is map empty?
     pub fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        match &self.imp {
+            ReflectMapRefImpl::Generated(m) => m.is_empty(),
+            ReflectMapRefImpl::DynamicEmpty(..) => true,
+        }
     }
 
     /// Find a value by given key.
-    pub fn get(&self, key: ReflectValueRef) -> Option<ReflectValueRef> {
-        self.map.get(key)
+    ///
+    /// Returned value borrows from the message, not from this reference.
+    pub fn get(&self, key: ReflectValueRef) -> Option<ReflectValueRef<'a>> {
+        match &self.imp {
+            ReflectMapRefImpl::Generated(m) => m.get(key),
+            ReflectMapRefImpl::DynamicEmpty(..) => None,
+        }
     }
 
     /// Map key type
     pub fn key_type(&self) -> RuntimeTypeBox {
-        self.map.key_type()
+        match &self.imp {
+            ReflectMapRefImpl::Generated(m) => m.key_type(),
+            ReflectMapRefImpl::DynamicEmpty(k, _) => k.clone(),
+        }
     }
 
     /// Map value type
     pub fn value_type(&self) -> RuntimeTypeBox {
-        self.map.value_type()
+        match &self.imp {
+            ReflectMapRefImpl::Generated(m) => m.value_type(),
+            ReflectMapRefImpl::DynamicEmpty(_, v) => v.clone(),
+        }
     }
 }
 
@@ -129,7 +161,7 @@ impl<'a> ReflectMapMut<'a> {
     }
 
     fn as_ref(&'a self) -> ReflectMapRef<'a> {
-        ReflectMapRef { map: self.map }
+        ReflectMapRef::new(self.map)
     }
 
     /// Map key type
@@ -172,6 +204,28 @@ impl<'a> ReflectMapMut<'a> {
     }
 }
 
+// Types are only needed to implement `ReflectMapIterTrait`,
+// whose key and value type accessors are currently unused
+#[allow(dead_code)]
+struct EmptyMapIter {
+    key: RuntimeTypeBox,
+    value: RuntimeTypeBox,
+}
+
+impl<'a> ReflectMapIterTrait<'a> for EmptyMapIter {
+    fn next(&mut self) -> Option<(ReflectValueRef<'a>, ReflectValueRef<'a>)> {
+        None
+    }
+
+    fn key_type(&self) -> RuntimeTypeBox {
+        self.key.clone()
+    }
+
+    fn value_type(&self) -> RuntimeTypeBox {
+        self.value.clone()
+    }
+}
+
 /// Iterator over map
 pub struct ReflectMapRefIter<'a> {
     iter: ReflectMapIter<'a>,
@@ -200,8 +254,13 @@ impl<'a, 'b> IntoIterator for &'b ReflectMapRef<'a> {
     type IntoIter = ReflectMapRefIter<'a>;
 
     fn into_iter(self) -> ReflectMapRefIter<'a> {
-        ReflectMapRefIter {
-            iter: self.map.reflect_iter(),
-        }
+        let iter = match &self.imp {
+            ReflectMapRefImpl::Generated(m) => m.reflect_iter(),
+            ReflectMapRefImpl::DynamicEmpty(key, value) => ReflectMapIter::new(EmptyMapIter {
+                key: key.clone(),
+                value: value.clone(),
+            }),
+        };
+        ReflectMapRefIter { iter }
     }
 }
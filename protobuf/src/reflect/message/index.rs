@@ -23,19 +23,19 @@ impl MessageIndex {
             .map(|f| FieldIndex::index(f, building))
             .collect();
         for (i, f) in proto.field.iter().enumerate() {
-            let field_index = &fields[i];
-
             assert!(index_by_number.insert(f.get_number() as u32, i).is_none());
             assert!(index_by_name.insert(f.get_name().to_owned(), i).is_none());
             assert!(index_by_name_or_json_name
                 .insert(f.get_name().to_owned(), i)
                 .is_none());
+        }
 
-            if field_index.json_name != f.get_name() {
-                assert!(index_by_name_or_json_name
-                    .insert(field_index.json_name.clone(), i)
-                    .is_none());
-            }
+        // Codegen rejects JSON name collisions, but dynamic descriptors
+        // may contain them: field names take precedence, then the first field
+        for (i, field_index) in fields.iter().enumerate() {
+            index_by_name_or_json_name
+                .entry(field_index.json_name.clone())
+                .or_insert(i);
         }
 
         MessageIndex {
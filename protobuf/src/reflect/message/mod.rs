@@ -1,14 +1,17 @@
 use std::fmt;
+use std::ops::Range;
 
 use crate::message::Message;
 
 use crate::descriptor::DescriptorProto;
 use crate::descriptor::FileDescriptorProto;
+use crate::descriptor::MessageOptions;
 
 use crate::message_dyn::MessageDyn;
 use crate::reflect::dynamic::DynamicMessage;
 use crate::reflect::file::index::FileIndexMessageEntry;
 use crate::reflect::file::FileDescriptorImpl;
+use crate::reflect::fingerprint::message_fingerprint;
 use crate::reflect::message::dynamic::DynamicMessageDescriptor;
 use crate::reflect::message::generated::GeneratedMessageDescriptor;
 use crate::reflect::message::index::MessageIndex;
@@ -144,6 +147,9 @@ impl MessageDescriptor {
 
     /// New empty message.
     ///
+    /// Generated message for generated types, created with the message factory,
+    /// and [`DynamicMessage`] for descriptors created at runtime.
+    ///
     /// # Panics
     ///
     /// If this message is a map entry message.
@@ -157,7 +163,9 @@ impl MessageDescriptor {
 
     /// Shared immutable empty message.
     ///
-    /// Returns `None` for dynamic message.
+    /// Returns `None` for dynamic message, use
+    /// [`MessageRef::default_instance`](crate::reflect::MessageRef::default_instance)
+    /// to get an empty message of any type without allocating for generated types.
     ///
     /// # Panics
     ///
@@ -239,6 +247,35 @@ impl MessageDescriptor {
         &self.get_index_entry().full_name
     }
 
+    /// Deterministic 64-bit fingerprint of the message type schema,
+    /// for example, to detect schema mismatch between peers.
+    ///
+    /// Fingerprint covers full names of this message type and of all
+    /// message and enum types reachable from its fields, and for each
+    /// field its number, name, label and type; for enums, value numbers
+    /// and names. Declaration order, options, comments, default values
+    /// and file layout do not affect the fingerprint, so generated
+    /// and dynamic descriptors of the same schema have the same fingerprint.
+    ///
+    /// Fingerprint is stable across rust-protobuf versions and platforms.
+    ///
+    /// ```
+    /// # use protobuf::Message;
+    /// # use protobuf::well_known_types::Duration;
+    /// # use protobuf::well_known_types::Timestamp;
+    /// let duration = Duration::descriptor_static();
+    /// let timestamp = Timestamp::descriptor_static();
+    /// assert_ne!(duration.fingerprint(), timestamp.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        message_fingerprint(self)
+    }
+
+    /// Message options.
+    pub fn options(&self) -> &MessageOptions {
+        self.get_proto().options.get_or_default()
+    }
+
     /// Message field descriptors.
     pub fn fields<'a>(&'a self) -> impl ExactSizeIterator<Item = FieldDescriptor> + 'a {
         (0..self.get_index().fields.len()).map(move |index| FieldDescriptor {
@@ -247,7 +284,8 @@ impl MessageDescriptor {
         })
     }
 
-    /// Nested oneofs
+    /// Nested oneofs in declaration order including synthetic oneofs
+    /// of proto3 `optional` fields.
     pub fn oneofs<'a>(&'a self) -> impl ExactSizeIterator<Item = OneofDescriptor> + 'a {
         self.get_proto()
             .oneof_decl
@@ -259,6 +297,39 @@ impl MessageDescriptor {
             })
     }
 
+    /// Oneofs declared with `oneof` keyword, excluding synthetic oneofs.
+    pub fn real_oneofs<'a>(&'a self) -> impl Iterator<Item = OneofDescriptor> + 'a {
+        self.oneofs().filter(|o| !o.is_synthetic())
+    }
+
+    /// Find oneof by name.
+    pub fn oneof_by_name(&self, name: &str) -> Option<OneofDescriptor> {
+        self.oneofs().find(|o| o.get_name() == name)
+    }
+
+    /// Reserved field number ranges, end is exclusive.
+    pub fn reserved_ranges<'a>(&'a self) -> impl Iterator<Item = Range<u32>> + 'a {
+        self.get_proto()
+            .reserved_range
+            .iter()
+            .map(|r| r.get_start() as u32..r.get_end() as u32)
+    }
+
+    /// Reserved field names.
+    pub fn reserved_names<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+        self.get_proto().reserved_name.iter().map(|n| n.as_str())
+    }
+
+    /// Field number is reserved.
+    pub fn is_reserved_number(&self, number: u32) -> bool {
+        self.reserved_ranges().any(|r| r.contains(&number))
+    }
+
+    /// Field name is reserved.
+    pub fn is_reserved_name(&self, name: &str) -> bool {
+        self.reserved_names().any(|n| n == name)
+    }
+
     pub(crate) fn get_index(&self) -> &MessageIndex {
         match self.get_impl() {
             MessageDescriptorImplRef::Generated(g) => &g.non_map().index,
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::RwLock;
+
+use crate::descriptor::field_descriptor_proto::Type as FieldType;
+use crate::descriptor::FieldDescriptorProto;
+use crate::descriptor::FileDescriptorProto;
+use crate::lazy_v2::LazyV2;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::plugin::CodeGeneratorRequest;
+use crate::reflect::ExtensionDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::well_known_types::Any;
+use crate::well_known_types::Api;
+use crate::well_known_types::DoubleValue;
+use crate::well_known_types::Duration;
+use crate::well_known_types::Empty;
+use crate::well_known_types::FieldMask;
+use crate::well_known_types::SourceContext;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Timestamp;
+use crate::well_known_types::Type;
+
+/// Registry of message types and extensions by fully-qualified name.
+///
+/// Both generated and dynamic messages can be registered.
+/// Lookup by name is needed when message type is known only at runtime,
+/// e. g. when unpacking `Any`, parsing extensions in text format
+/// or implementing gRPC reflection.
+///
+/// ```
+/// # use protobuf::reflect::MessageFactory;
+/// let factory = MessageFactory::global();
+/// let message = factory.new_by_name("google.protobuf.Duration").unwrap();
+/// assert_eq!("google.protobuf.Duration", message.descriptor_dyn().full_name());
+/// ```
+///
+/// Generated files are registered with
+/// [`add_file`](MessageFactory::add_file) and `file_descriptor()` function
+/// of generated module:
+///
+/// ```
+/// # use protobuf::reflect::MessageFactory;
+/// # mod my_pb { pub use protobuf::descriptor::file_descriptor; }
+/// MessageFactory::global().add_file(&my_pb::file_descriptor());
+/// ```
+#[derive(Default)]
+pub struct MessageFactory {
+    messages: RwLock<HashMap<String, MessageDescriptor>>,
+    extensions: RwLock<Extensions>,
+}
+
+#[derive(Default)]
+struct Extensions {
+    by_name: HashMap<String, ExtensionDescriptor>,
+    // (extendee, number) -> full name
+    by_number: HashMap<(String, u32), String>,
+}
+
+impl Extensions {
+    fn add(&mut self, file: &FileDescriptor, scope: &str, protos: &[FieldDescriptorProto]) {
+        for proto in protos {
+            // Groups are not supported by reflection
+            if proto.get_field_type() == FieldType::TYPE_GROUP {
+                continue;
+            }
+            let ext = ExtensionDescriptor::new(file.clone(), scope, proto.clone());
+            self.by_number.insert(
+                (ext.extendee().to_owned(), ext.number()),
+                ext.full_name().to_owned(),
+            );
+            self.by_name.insert(ext.full_name().to_owned(), ext);
+        }
+    }
+}
+
+impl fmt::Debug for MessageFactory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MessageFactory")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl MessageFactory {
+    /// Create an empty factory.
+    pub fn new() -> MessageFactory {
+        MessageFactory::default()
+    }
+
+    /// Process-wide factory.
+    ///
+    /// Initially it contains well-known types, `descriptor.proto` and
+    /// `plugin.proto` messages.
+    pub fn global() -> &'static MessageFactory {
+        static GLOBAL: LazyV2<MessageFactory> = LazyV2::INIT;
+        GLOBAL.get(|| {
+            let factory = MessageFactory::new();
+            let files = [
+                FileDescriptorProto::descriptor_static(),
+                CodeGeneratorRequest::descriptor_static(),
+                Any::descriptor_static(),
+                Api::descriptor_static(),
+                Duration::descriptor_static(),
+                Empty::descriptor_static(),
+                FieldMask::descriptor_static(),
+                SourceContext::descriptor_static(),
+                Struct::descriptor_static(),
+                Timestamp::descriptor_static(),
+                Type::descriptor_static(),
+                DoubleValue::descriptor_static(),
+            ];
+            for d in &files {
+                factory.add_file(d.file_descriptor());
+            }
+            factory
+        })
+    }
+
+    /// Register all messages and extensions of the file and its dependencies.
+    ///
+    /// Previously registered messages with the same names are replaced.
+    pub fn add_file(&self, file: &FileDescriptor) {
+        let mut messages = self.messages.write().unwrap();
+        let mut extensions = self.extensions.write().unwrap();
+        for file in file.all_files() {
+            extensions.add(file, file.proto().get_package(), &file.proto().extension);
+            for message in file.messages_including_nested() {
+                extensions.add(file, message.full_name(), &message.get_proto().extension);
+                messages.insert(message.full_name().to_owned(), message);
+            }
+        }
+    }
+
+    /// Register single message.
+    ///
+    /// Previously registered message with the same name is replaced.
+    pub fn add_message(&self, message: MessageDescriptor) {
+        self.messages
+            .write()
+            .unwrap()
+            .insert(message.full_name().to_owned(), message);
+    }
+
+    /// Find message descriptor by fully-qualified name like `pkg.Message`.
+    ///
+    /// Leading dot (as in type names in `.proto` descriptors) is allowed.
+    pub fn descriptor_by_name(&self, full_name: &str) -> Option<MessageDescriptor> {
+        let full_name = full_name.strip_prefix('.').unwrap_or(full_name);
+        self.messages.read().unwrap().get(full_name).cloned()
+    }
+
+    /// Create an empty message by fully-qualified name like `pkg.Message`.
+    ///
+    /// Created message is generated message if generated descriptor
+    /// was registered and dynamic message otherwise.
+    pub fn new_by_name(&self, full_name: &str) -> Option<Box<dyn MessageDyn>> {
+        self.descriptor_by_name(full_name).map(|d| d.new_instance())
+    }
+
+    /// Find extension by fully-qualified name like `pkg.ext`.
+    ///
+    /// Leading dot is allowed.
+    pub fn extension_by_name(&self, full_name: &str) -> Option<ExtensionDescriptor> {
+        let full_name = full_name.strip_prefix('.').unwrap_or(full_name);
+        self.extensions
+            .read()
+            .unwrap()
+            .by_name
+            .get(full_name)
+            .cloned()
+    }
+
+    /// Find extension of message `extendee` by field number.
+    pub fn extension_by_number(&self, extendee: &str, number: u32) -> Option<ExtensionDescriptor> {
+        let extendee = extendee.strip_prefix('.').unwrap_or(extendee);
+        let extensions = self.extensions.read().unwrap();
+        let full_name = extensions.by_number.get(&(extendee.to_owned(), number))?;
+        extensions.by_name.get(full_name).cloned()
+    }
+
+    /// Number of registered messages.
+    pub fn len(&self) -> usize {
+        self.messages.read().unwrap().len()
+    }
+
+    /// Check if nothing is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+
+    #[test]
+    fn global() {
+        let factory = MessageFactory::global();
+        let m = factory.new_by_name("google.protobuf.Timestamp").unwrap();
+        assert!(m.is::<Timestamp>());
+        // nested message
+        let m = factory
+            .new_by_name(".google.protobuf.DescriptorProto.ExtensionRange")
+            .unwrap();
+        assert_eq!(
+            "google.protobuf.DescriptorProto.ExtensionRange",
+            m.descriptor_dyn().full_name()
+        );
+        assert!(factory.new_by_name("google.protobuf.Missing").is_none());
+    }
+
+    #[test]
+    fn dynamic() {
+        let mut message = DescriptorProto::new();
+        message.set_name("Message".to_owned());
+        let mut file = FileDescriptorProto::new();
+        file.set_name("dynamic.proto".to_owned());
+        file.set_package("pkg".to_owned());
+        file.message_type.push(message);
+        let file = FileDescriptor::new_dynamic(file, Vec::new());
+
+        let factory = MessageFactory::new();
+        assert!(factory.is_empty());
+        factory.add_file(&file);
+        assert_eq!(1, factory.len());
+        let m = factory.new_by_name("pkg.Message").unwrap();
+        assert_eq!("pkg.Message", m.descriptor_dyn().full_name());
+    }
+}
@@ -1,16 +1,29 @@
 //! Reflection implementation for protobuf types.
+//!
+//! Descriptors expose `.proto` options with `options()` functions.
+//! Custom options are extensions of options messages, and are read
+//! with [`ExtFieldOptional::get`](crate::ext::ExtFieldOptional::get).
 
 mod acc;
+mod builder;
+mod canonical_hash;
+mod compare;
 mod dynamic;
 mod enums;
+mod extension;
 mod field;
 mod file;
 mod find_message_or_enum;
+mod fingerprint;
 mod map;
 pub(crate) mod message;
+mod message_factory;
 mod oneof;
+mod registered_types;
 mod repeated;
+pub(crate) mod required;
 mod runtime_type_box;
+mod service;
 mod type_dynamic;
 pub(crate) mod value;
 
@@ -37,23 +50,44 @@ pub use self::map::ReflectMapRef;
 pub use self::enums::generated::GeneratedEnumDescriptorData;
 pub use self::enums::EnumDescriptor;
 pub use self::enums::EnumValueDescriptor;
+pub use self::extension::ExtensionDescriptor;
 
 #[doc(hidden)]
 pub use self::message::generated::GeneratedMessageDescriptorData;
 pub use self::message::message_ref::MessageRef;
 pub use self::message::MessageDescriptor;
+pub use self::message_factory::MessageFactory;
 
 pub use self::field::FieldDescriptor;
 pub use self::field::ReflectFieldRef;
 pub use self::field::RuntimeFieldType;
 
 pub use self::oneof::OneofDescriptor;
+pub use self::registered_types::RegisteredTypes;
 
+pub use self::service::invoker::DynamicInvoker;
+pub use self::service::MethodDescriptor;
+pub use self::service::ServiceDescriptor;
+
+pub use self::file::check::DescriptorSetError;
+pub use self::file::check::DescriptorSetProblem;
 #[doc(hidden)]
 pub use self::file::generated::GeneratedFileDescriptor;
 pub use self::file::FileDescriptor;
 
+pub use self::builder::EnumBuilder;
+pub use self::builder::FieldBuilder;
+pub use self::builder::FileDescriptorBuilder;
+pub use self::builder::MessageBuilder;
+
 pub use self::runtime_type_box::RuntimeTypeBox;
 
+pub use self::canonical_hash::canonical_hash;
+pub use self::canonical_hash::canonical_hash_with_options;
+pub use self::canonical_hash::CanonicalHashOptions;
+
+pub use self::compare::MessageComparer;
+pub use self::compare::MessageDifference;
+
 pub use self::reflect_eq::ReflectEq;
 pub use self::reflect_eq::ReflectEqMode;
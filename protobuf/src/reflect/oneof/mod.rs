@@ -1,4 +1,5 @@
 use crate::descriptor::OneofDescriptorProto;
+use crate::descriptor::OneofOptions;
 use crate::reflect::FieldDescriptor;
 use crate::reflect::MessageDescriptor;
 
@@ -20,6 +21,28 @@ impl OneofDescriptor {
         self.get_proto().get_name()
     }
 
+    /// Oneof options.
+    pub fn options(&self) -> &OneofOptions {
+        self.get_proto().options.get_or_default()
+    }
+
+    /// Message containing this oneof.
+    pub fn containing_message(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
+    /// Index of this oneof in [`MessageDescriptor::oneofs`](MessageDescriptor::oneofs),
+    /// same as `oneof_index` of fields of this oneof.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Oneof is generated by `protoc` for proto3 `optional` field
+    /// to track field presence, and not declared in `.proto` file.
+    pub fn is_synthetic(&self) -> bool {
+        self.fields().any(|f| f.is_proto3_optional())
+    }
+
     /// Fields in this oneof.
     pub fn fields<'a>(&'a self) -> impl Iterator<Item = FieldDescriptor> + 'a {
         self.message_descriptor
@@ -27,3 +50,60 @@ impl OneofDescriptor {
             .filter(move |f| f.containing_oneof().as_ref() == Some(self))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::field_descriptor_proto::Type;
+    use crate::reflect::FieldBuilder;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::FileDescriptorBuilder;
+    use crate::reflect::MessageBuilder;
+
+    #[test]
+    fn oneofs() {
+        let builder = FileDescriptorBuilder::new("oneof.proto").proto3().message(
+            MessageBuilder::new("M")
+                .field(FieldBuilder::new("plain", 1, Type::TYPE_INT32))
+                .oneof(
+                    "one",
+                    vec![
+                        FieldBuilder::new("a", 2, Type::TYPE_INT32),
+                        FieldBuilder::new("b", 3, Type::TYPE_STRING),
+                    ],
+                )
+                .oneof("_opt", vec![FieldBuilder::new("opt", 4, Type::TYPE_INT32)]),
+        );
+        let mut proto = builder.proto().clone();
+        proto.message_type[0].field[3].set_proto3_optional(true);
+        let file = FileDescriptor::new_dynamic(proto, Vec::new());
+        let m = file.message_by_package_relative_name("M").unwrap();
+
+        assert_eq!(2, m.oneofs().len());
+        let real: Vec<_> = m.real_oneofs().collect();
+        assert_eq!(1, real.len());
+        assert_eq!("one", real[0].get_name());
+        assert_eq!(0, real[0].index());
+        assert!(!real[0].is_synthetic());
+        assert_eq!(
+            vec!["a", "b"],
+            real[0]
+                .fields()
+                .map(|f| f.get_name().to_owned())
+                .collect::<Vec<_>>()
+        );
+        assert!(&m == real[0].containing_message());
+
+        let opt = m.oneof_by_name("_opt").unwrap();
+        assert!(opt.is_synthetic());
+        assert_eq!(1, opt.index());
+
+        let field = |name| m.get_field_by_name(name).unwrap();
+        assert_eq!(None, field("plain").oneof_index());
+        assert_eq!(Some(0), field("b").oneof_index());
+        assert!(Some(real[0].clone()) == field("b").real_containing_oneof());
+        assert_eq!(Some(1), field("opt").oneof_index());
+        assert!(field("opt").is_proto3_optional());
+        assert!(Some(opt) == field("opt").containing_oneof());
+        assert!(field("opt").real_containing_oneof().is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::message_dyn::MessageDyn;
+use crate::reflect::EnumDescriptor;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+
+/// Messages and enums defined in a set of files, including nested types.
+///
+/// Generated files have `registered_types()` function returning types
+/// defined in the file, and `mod.rs` generated with `gen_mod_rs` option
+/// has `registered_types()` function returning types of all generated files.
+///
+/// ```
+/// # use protobuf::reflect::RegisteredTypes;
+/// # mod my_pb { pub use protobuf::well_known_types::file_descriptors::duration as file_descriptor; }
+/// let types = RegisteredTypes::of_file(&my_pb::file_descriptor());
+/// assert_eq!(vec!["google.protobuf.Duration"], types.message_names().collect::<Vec<_>>());
+/// let message = types.new_message_by_name("google.protobuf.Duration").unwrap();
+/// assert_eq!("google.protobuf.Duration", message.descriptor_dyn().full_name());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RegisteredTypes {
+    messages: Vec<MessageDescriptor>,
+    enums: Vec<EnumDescriptor>,
+}
+
+impl RegisteredTypes {
+    /// Empty list.
+    pub fn new() -> RegisteredTypes {
+        RegisteredTypes::default()
+    }
+
+    /// Types defined in given file, not including dependencies.
+    ///
+    /// Map entry messages are not listed.
+    pub fn of_file(file: &FileDescriptor) -> RegisteredTypes {
+        RegisteredTypes {
+            messages: file
+                .messages_including_nested()
+                .into_iter()
+                .filter(|m| !m.is_map_entry())
+                .collect(),
+            enums: file.enums_including_nested(),
+        }
+    }
+
+    /// Append types from another list.
+    pub fn extend(&mut self, types: RegisteredTypes) {
+        self.messages.extend(types.messages);
+        self.enums.extend(types.enums);
+    }
+
+    /// Messages in declaration order.
+    pub fn messages(&self) -> &[MessageDescriptor] {
+        &self.messages
+    }
+
+    /// Enums in declaration order.
+    pub fn enums(&self) -> &[EnumDescriptor] {
+        &self.enums
+    }
+
+    /// Fully-qualified names of messages like `pkg.Message.Nested`.
+    pub fn message_names<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+        self.messages.iter().map(|m| m.full_name())
+    }
+
+    /// Fully-qualified names of enums like `pkg.Message.Enum`.
+    pub fn enum_names<'a>(&'a self) -> impl Iterator<Item = &'a str> + 'a {
+        self.enums.iter().map(|e| e.full_name())
+    }
+
+    /// Find message by fully-qualified name.
+    ///
+    /// Leading dot is allowed.
+    pub fn message_by_name(&self, full_name: &str) -> Option<&MessageDescriptor> {
+        let full_name = full_name.strip_prefix('.').unwrap_or(full_name);
+        self.messages.iter().find(|m| m.full_name() == full_name)
+    }
+
+    /// Find enum by fully-qualified name.
+    ///
+    /// Leading dot is allowed.
+    pub fn enum_by_name(&self, full_name: &str) -> Option<&EnumDescriptor> {
+        let full_name = full_name.strip_prefix('.').unwrap_or(full_name);
+        self.enums.iter().find(|e| e.full_name() == full_name)
+    }
+
+    /// Create an empty message by fully-qualified name.
+    ///
+    /// Messages of generated files are created as generated messages.
+    pub fn new_message_by_name(&self, full_name: &str) -> Option<Box<dyn MessageDyn>> {
+        self.message_by_name(full_name).map(|m| m.new_instance())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+
+    #[test]
+    fn nested_types() {
+        let types = RegisteredTypes::of_file(&crate::descriptor::file_descriptor());
+        let names: Vec<_> = types.message_names().collect();
+        assert_eq!("google.protobuf.FileDescriptorSet", names[0]);
+        assert!(names.contains(&"google.protobuf.DescriptorProto.ExtensionRange"));
+        assert!(types
+            .enum_names()
+            .any(|e| e == "google.protobuf.FieldDescriptorProto.Type"));
+
+        let m = types
+            .new_message_by_name(".google.protobuf.DescriptorProto")
+            .unwrap();
+        assert!(m.is::<DescriptorProto>());
+        assert!(types.new_message_by_name("google.protobuf.Any").is_none());
+    }
+
+    #[test]
+    fn extend() {
+        let mut types = RegisteredTypes::of_file(&crate::well_known_types::file_descriptors::any());
+        types.extend(RegisteredTypes::of_file(
+            &crate::well_known_types::file_descriptors::empty(),
+        ));
+        assert_eq!(
+            vec!["google.protobuf.Any", "google.protobuf.Empty"],
+            types.message_names().collect::<Vec<_>>()
+        );
+        assert!(types.enums().is_empty());
+    }
+
+    #[test]
+    fn no_map_entries() {
+        let types =
+            RegisteredTypes::of_file(&crate::well_known_types::file_descriptors::struct_pb());
+        assert_eq!(
+            vec![
+                "google.protobuf.Struct",
+                "google.protobuf.Value",
+                "google.protobuf.ListValue"
+            ],
+            types.message_names().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["google.protobuf.NullValue"],
+            types.enum_names().collect::<Vec<_>>()
+        );
+    }
+}
@@ -8,6 +8,7 @@ use crate::reflect::value::value_ref::ReflectValueRef;
 use crate::reflect::ProtobufValue;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeTypeBox;
+use crate::SmallVec;
 
 pub(crate) trait ReflectRepeated: Sync + 'static + fmt::Debug {
     fn reflect_iter(&self) -> ReflectRepeatedIter;
@@ -53,6 +54,40 @@ impl<V: ProtobufValue> ReflectRepeated for Vec<V> {
     }
 }
 
+impl<V: ProtobufValue, const N: usize> ReflectRepeated for SmallVec<V, N> {
+    fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
+        ReflectRepeatedIter {
+            imp: Box::new(ReflectRepeatedIterImplSlice::<'a, V> { iter: self.iter() }),
+        }
+    }
+
+    fn len(&self) -> usize {
+        SmallVec::len(self)
+    }
+
+    fn get(&self, index: usize) -> ReflectValueRef {
+        V::as_ref(&self[index])
+    }
+
+    fn set(&mut self, index: usize, value: ReflectValueBox) {
+        let value = value.downcast().expect("wrong type");
+        self[index] = value;
+    }
+
+    fn push(&mut self, value: ReflectValueBox) {
+        let value = value.downcast().expect("wrong type");
+        SmallVec::push(self, value)
+    }
+
+    fn clear(&mut self) {
+        SmallVec::clear(self)
+    }
+
+    fn element_type(&self) -> RuntimeTypeBox {
+        V::runtime_type_box()
+    }
+}
+
 // useless
 impl<V: ProtobufValue> ReflectRepeated for [V] {
     fn reflect_iter<'a>(&'a self) -> ReflectRepeatedIter<'a> {
@@ -340,7 +375,7 @@ impl<'a> Iterator for ReflectRepeatedRefIter<'a> {
     }
 }
 
-impl<'a> IntoIterator for &'a ReflectRepeatedRef<'a> {
+impl<'a, 'b> IntoIterator for &'b ReflectRepeatedRef<'a> {
     type Item = ReflectValueRef<'a>;
     type IntoIter = ReflectRepeatedRefIter<'a>;
 
@@ -0,0 +1,76 @@
+//! Find required fields which are not set.
+
+use std::fmt::Write;
+
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::dynamic::DynamicMessage;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+
+/// Paths to unset required fields of the message and nested messages,
+/// like `foo.bar[1].baz` or `foo.map["key"].baz`.
+pub(crate) fn missing_required_fields(message: &dyn MessageDyn) -> Vec<String> {
+    let mut missing = Vec::new();
+    let mut path = String::new();
+    collect(message, &mut path, &mut missing);
+    missing
+}
+
+fn collect(message: &dyn MessageDyn, path: &mut String, missing: &mut Vec<String>) {
+    // Fast path for generated messages,
+    // dynamic messages implement `is_initialized` with this function
+    if message.downcast_ref::<DynamicMessage>().is_none() && message.is_initialized_dyn() {
+        return;
+    }
+
+    for field in message.descriptor_dyn().fields() {
+        let prefix_len = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(field.get_name());
+
+        let field_path_len = path.len();
+        match field.get_reflect(message) {
+            ReflectFieldRef::Optional(None) => {
+                if field.get_proto().get_label() == Label::LABEL_REQUIRED {
+                    missing.push(path.clone());
+                }
+            }
+            ReflectFieldRef::Optional(Some(ReflectValueRef::Message(m))) => {
+                collect(&*m, path, missing);
+            }
+            ReflectFieldRef::Optional(Some(_)) => {}
+            ReflectFieldRef::Repeated(repeated) => {
+                for (i, v) in repeated.into_iter().enumerate() {
+                    if let ReflectValueRef::Message(m) = v {
+                        write!(path, "[{}]", i).unwrap();
+                        collect(&*m, path, missing);
+                        path.truncate(field_path_len);
+                    }
+                }
+            }
+            ReflectFieldRef::Map(map) => {
+                for (k, v) in &map {
+                    if let ReflectValueRef::Message(m) = v {
+                        match k {
+                            ReflectValueRef::String(s) => write!(path, "[{:?}]", s),
+                            ReflectValueRef::U32(k) => write!(path, "[{}]", k),
+                            ReflectValueRef::U64(k) => write!(path, "[{}]", k),
+                            ReflectValueRef::I32(k) => write!(path, "[{}]", k),
+                            ReflectValueRef::I64(k) => write!(path, "[{}]", k),
+                            ReflectValueRef::Bool(k) => write!(path, "[{}]", k),
+                            _ => write!(path, "[..]"),
+                        }
+                        .unwrap();
+                        collect(&*m, path, missing);
+                        path.truncate(field_path_len);
+                    }
+                }
+            }
+        }
+
+        path.truncate(prefix_len);
+    }
+}
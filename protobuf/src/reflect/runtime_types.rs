@@ -18,6 +18,7 @@ use crate::chars::Chars;
 use crate::enums::ProtobufEnum;
 use crate::enums::ProtobufEnumOrUnknown;
 use crate::message::Message;
+use crate::string_interner::InternedString;
 use std::collections::HashMap;
 
 /// `RuntimeType` is not implemented by all protobuf types directly
@@ -136,6 +137,10 @@ pub struct RuntimeTypeCarllercheBytes;
 #[derive(Debug, Copy, Clone)]
 pub struct RuntimeTypeCarllercheChars;
 
+/// Implementation for [`InternedString`].
+#[derive(Debug, Copy, Clone)]
+pub struct RuntimeTypeInternedString;
+
 /// Implementation for enum.
 #[derive(Debug, Copy, Clone)]
 pub struct RuntimeTypeEnum<E: ProtobufEnum + ProtobufValue>(marker::PhantomData<E>);
@@ -680,6 +685,62 @@ impl RuntimeTypeHashable for RuntimeTypeCarllercheChars {
     }
 }
 
+impl RuntimeType for RuntimeTypeInternedString {
+    type Value = InternedString;
+
+    fn default_value_ref() -> ReflectValueRef<'static> {
+        ReflectValueRef::String("")
+    }
+
+    fn runtime_type_box() -> RuntimeTypeBox
+    where
+        Self: Sized,
+    {
+        RuntimeTypeBox::String
+    }
+
+    fn from_value_box(value_box: ReflectValueBox) -> Result<InternedString, ReflectValueBox> {
+        match value_box {
+            ReflectValueBox::String(v) => Ok(v.into()),
+            b => Err(b),
+        }
+    }
+
+    fn into_value_box(value: InternedString) -> ReflectValueBox {
+        ReflectValueBox::String(value.into())
+    }
+
+    fn as_ref(value: &InternedString) -> ReflectValueRef {
+        ReflectValueRef::String(value.as_str())
+    }
+
+    fn is_non_zero(value: &InternedString) -> bool {
+        !value.is_empty()
+    }
+
+    fn as_mut(_value: &mut Self::Value) -> ReflectValueMut {
+        unimplemented!()
+    }
+}
+impl RuntimeTypeWithDeref for RuntimeTypeInternedString {
+    type DerefTarget = str;
+
+    fn defef_as_ref(value: &str) -> ReflectValueRef {
+        ReflectValueRef::String(value)
+    }
+}
+impl RuntimeTypeHashable for RuntimeTypeInternedString {
+    fn hash_map_get<'a, V>(
+        map: &'a HashMap<InternedString, V>,
+        key: ReflectValueRef,
+    ) -> Option<&'a V> {
+        match key {
+            ReflectValueRef::String(s) => map.get(&*s),
+            _ => None,
+        }
+    }
+}
+
 impl<E> RuntimeType for RuntimeTypeEnum<E>
 where
     E: ProtobufEnum + ProtobufValue + fmt::Debug,
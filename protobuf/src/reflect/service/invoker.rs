@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::MessageTypeMismatchError;
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+use crate::reflect::MethodDescriptor;
+
+type Handler = Box<dyn Fn(&dyn MessageDyn) -> ProtobufResult<Box<dyn MessageDyn>> + Send + Sync>;
+
+/// Generic dispatcher of unary method calls by method full name.
+///
+/// Methods are registered from service descriptors, and handlers
+/// operate on dynamic messages, so the dispatcher can be used
+/// to implement RPC servers without generated service code.
+///
+/// ```
+/// # use protobuf::reflect::DynamicInvoker;
+/// # use protobuf::reflect::FileDescriptor;
+/// # fn f(file: &FileDescriptor) {
+/// let mut invoker = DynamicInvoker::new();
+/// invoker.add_file(file);
+/// for method in invoker.methods() {
+///     println!("{}: {} -> {}", method.full_name(), method.input_type(), method.output_type());
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct DynamicInvoker {
+    methods: HashMap<String, MethodDescriptor>,
+    handlers: HashMap<String, Handler>,
+}
+
+impl DynamicInvoker {
+    /// New invoker without methods.
+    pub fn new() -> DynamicInvoker {
+        DynamicInvoker::default()
+    }
+
+    /// Register all methods of all services defined in given file.
+    pub fn add_file(&mut self, file: &FileDescriptor) {
+        for service in file.services() {
+            for method in service.methods() {
+                self.add_method(method);
+            }
+        }
+    }
+
+    /// Register single method.
+    pub fn add_method(&mut self, method: MethodDescriptor) {
+        self.methods.insert(method.full_name(), method);
+    }
+
+    /// All registered methods.
+    pub fn methods<'a>(&'a self) -> impl Iterator<Item = &'a MethodDescriptor> + 'a {
+        self.methods.values()
+    }
+
+    /// Find method by fully qualified name like `foo.bar.Service.Method`.
+    pub fn method(&self, full_name: &str) -> Option<&MethodDescriptor> {
+        self.methods.get(full_name)
+    }
+
+    /// Request and response types of the method.
+    pub fn method_types(&self, full_name: &str) -> Option<(MessageDescriptor, MessageDescriptor)> {
+        self.method(full_name)
+            .map(|m| (m.input_type(), m.output_type()))
+    }
+
+    /// Set the handler for registered method.
+    ///
+    /// Handler receives a message of method input type
+    /// and must return a message of method output type.
+    ///
+    /// # Panics
+    ///
+    /// If method is not registered.
+    pub fn set_handler<F>(&mut self, full_name: &str, handler: F)
+    where
+        F: Fn(&dyn MessageDyn) -> ProtobufResult<Box<dyn MessageDyn>> + Send + Sync + 'static,
+    {
+        assert!(
+            self.methods.contains_key(full_name),
+            "method not registered: {}",
+            full_name
+        );
+        self.handlers
+            .insert(full_name.to_owned(), Box::new(handler));
+    }
+
+    /// Call the handler with already parsed request.
+    ///
+    /// Return `None` if there's no handler for the method.
+    /// Return [`ProtobufError::MessageTypeMismatch`] if the request
+    /// or the message returned by the handler is not of the method type.
+    pub fn invoke_dyn(
+        &self,
+        full_name: &str,
+        request: &dyn MessageDyn,
+    ) -> Option<ProtobufResult<Box<dyn MessageDyn>>> {
+        let handler = self.handlers.get(full_name)?;
+        let method = &self.methods[full_name];
+        if let Err(e) = check_message_type(&method.input_type(), request) {
+            return Some(Err(e));
+        }
+        let response = handler(request).and_then(|response| {
+            check_message_type(&method.output_type(), &*response)?;
+            Ok(response)
+        });
+        Some(response)
+    }
+
+    /// Parse the request, call the handler and serialize the response.
+    ///
+    /// Request must have all required fields set,
+    /// otherwise [`ProtobufError::MessageNotInitialized`] is returned
+    /// and handler is not called.
+    ///
+    /// Return `None` if there's no handler for the method.
+    pub fn invoke(&self, full_name: &str, request: &[u8]) -> Option<ProtobufResult<Vec<u8>>> {
+        let method = self.methods.get(full_name)?;
+        if !self.handlers.contains_key(full_name) {
+            return None;
+        }
+        let mut message = method.input_type().new_instance();
+        if let Err(e) = message
+            .merge_from_bytes_dyn(request)
+            .and_then(|()| message.check_initialized_dyn())
+        {
+            return Some(Err(e));
+        }
+        let response = self.invoke_dyn(full_name, &*message)?;
+        Some(response.and_then(|r| r.write_to_bytes_dyn()))
+    }
+}
+
+fn check_message_type(
+    expected: &MessageDescriptor,
+    message: &dyn MessageDyn,
+) -> ProtobufResult<()> {
+    let actual = message.descriptor_dyn();
+    if *expected != actual {
+        return Err(ProtobufError::MessageTypeMismatch(
+            MessageTypeMismatchError {
+                expected: expected.full_name().to_owned(),
+                actual: actual.full_name().to_owned(),
+            },
+        ));
+    }
+    Ok(())
+}
+
+impl fmt::Debug for DynamicInvoker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut methods: Vec<&String> = self.methods.keys().collect();
+        methods.sort();
+        f.debug_struct("DynamicInvoker")
+            .field("methods", &methods)
+            .finish()
+    }
+}
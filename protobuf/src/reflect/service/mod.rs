@@ -0,0 +1,129 @@
+use crate::descriptor::MethodDescriptorProto;
+use crate::descriptor::MethodOptions;
+use crate::descriptor::ServiceDescriptorProto;
+use crate::descriptor::ServiceOptions;
+use crate::reflect::name::concat_paths;
+use crate::reflect::FileDescriptor;
+use crate::reflect::MessageDescriptor;
+
+pub(crate) mod invoker;
+
+/// Service descriptor.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ServiceDescriptor {
+    pub(crate) file_descriptor: FileDescriptor,
+    pub(crate) index: usize,
+}
+
+impl ServiceDescriptor {
+    /// `.proto` part associated with this descriptor
+    pub fn get_proto(&self) -> &ServiceDescriptorProto {
+        &self.file_descriptor.proto().service[self.index]
+    }
+
+    /// Service name as specified in `.proto` file.
+    pub fn get_name(&self) -> &str {
+        self.get_proto().get_name()
+    }
+
+    /// Fully qualified protobuf service name.
+    pub fn full_name(&self) -> String {
+        concat_paths(self.file_descriptor.proto().get_package(), self.get_name())
+    }
+
+    /// Service options.
+    pub fn options(&self) -> &ServiceOptions {
+        self.get_proto().options.get_or_default()
+    }
+
+    /// File this service is defined in.
+    pub fn file_descriptor(&self) -> &FileDescriptor {
+        &self.file_descriptor
+    }
+
+    /// Methods of this service.
+    pub fn methods<'a>(&'a self) -> impl ExactSizeIterator<Item = MethodDescriptor> + 'a {
+        (0..self.get_proto().method.len()).map(move |index| MethodDescriptor {
+            service_descriptor: self.clone(),
+            index,
+        })
+    }
+
+    /// Find method by name as specified in `.proto` file.
+    pub fn method_by_name(&self, name: &str) -> Option<MethodDescriptor> {
+        self.methods().find(|m| m.get_name() == name)
+    }
+}
+
+/// Service method descriptor.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct MethodDescriptor {
+    pub(crate) service_descriptor: ServiceDescriptor,
+    pub(crate) index: usize,
+}
+
+impl MethodDescriptor {
+    /// `.proto` part associated with this descriptor
+    pub fn get_proto(&self) -> &MethodDescriptorProto {
+        &self.service_descriptor.get_proto().method[self.index]
+    }
+
+    /// Method name as specified in `.proto` file.
+    pub fn get_name(&self) -> &str {
+        self.get_proto().get_name()
+    }
+
+    /// Fully qualified protobuf method name, e. g. `foo.bar.Service.Method`.
+    pub fn full_name(&self) -> String {
+        format!(
+            "{}.{}",
+            self.service_descriptor.full_name(),
+            self.get_name()
+        )
+    }
+
+    /// Method options.
+    pub fn options(&self) -> &MethodOptions {
+        self.get_proto().options.get_or_default()
+    }
+
+    /// Service containing this method.
+    pub fn service_descriptor(&self) -> &ServiceDescriptor {
+        &self.service_descriptor
+    }
+
+    /// Request message type.
+    ///
+    /// # Panics
+    ///
+    /// If the type cannot be found in the file or its dependencies.
+    pub fn input_type(&self) -> MessageDescriptor {
+        self.find_message(self.get_proto().get_input_type())
+    }
+
+    /// Response message type.
+    ///
+    /// # Panics
+    ///
+    /// If the type cannot be found in the file or its dependencies.
+    pub fn output_type(&self) -> MessageDescriptor {
+        self.find_message(self.get_proto().get_output_type())
+    }
+
+    /// Client sends a stream of requests.
+    pub fn is_client_streaming(&self) -> bool {
+        self.get_proto().get_client_streaming()
+    }
+
+    /// Server sends a stream of responses.
+    pub fn is_server_streaming(&self) -> bool {
+        self.get_proto().get_server_streaming()
+    }
+
+    fn find_message(&self, name: &str) -> MessageDescriptor {
+        self.service_descriptor
+            .file_descriptor
+            .message_by_full_name_with_deps(name)
+            .unwrap_or_else(|| panic!("message not found: {}", name))
+    }
+}
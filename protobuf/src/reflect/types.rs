@@ -19,6 +19,7 @@ use crate::message::Message;
 use crate::reflect::type_dynamic::ProtobufTypeDynamicImpl;
 use crate::reflect::ProtobufValue;
 use crate::rt;
+use crate::string_interner::InternedString;
 use crate::unknown::UnknownValues;
 use crate::wire_format::WireType;
 use crate::zigzag::decode_zig_zag_32;
@@ -150,6 +151,10 @@ pub struct ProtobufTypeCarllercheBytes;
 #[derive(Copy, Clone)]
 pub struct ProtobufTypeCarllercheChars;
 
+/// `string` as [`InternedString`](crate::InternedString)
+#[derive(Copy, Clone)]
+pub struct ProtobufTypeInternedString;
+
 /// `enum` as `ProtobufEnum`
 #[derive(Copy, Clone)]
 pub struct ProtobufTypeEnum<E: ProtobufEnum>(marker::PhantomData<E>);
@@ -644,6 +649,32 @@ impl ProtobufType for ProtobufTypeCarllercheChars {
     }
 }
 
+impl ProtobufType for ProtobufTypeInternedString {
+    type ProtobufValue = InternedString;
+
+    const WIRE_TYPE: WireType = ProtobufTypeString::WIRE_TYPE;
+
+    fn read(is: &mut CodedInputStream) -> ProtobufResult<InternedString> {
+        is.read_interned_string()
+    }
+
+    fn get_from_unknown(unknown_values: &UnknownValues) -> Option<InternedString> {
+        ProtobufTypeString::get_from_unknown(unknown_values).map(InternedString::from)
+    }
+
+    fn compute_size(value: &InternedString) -> u32 {
+        value.len() as u32
+    }
+
+    fn write_with_cached_size(
+        field_number: u32,
+        value: &InternedString,
+        os: &mut CodedOutputStream,
+    ) -> ProtobufResult<()> {
+        os.write_string(field_number, &value)
+    }
+}
+
 impl<E: ProtobufEnum + ProtobufValue + fmt::Debug> ProtobufType for ProtobufTypeEnum<E> {
     type ProtobufValue = E;
 
@@ -16,6 +16,7 @@ use crate::reflect::runtime_types::RuntimeTypeF32;
 use crate::reflect::runtime_types::RuntimeTypeF64;
 use crate::reflect::runtime_types::RuntimeTypeI32;
 use crate::reflect::runtime_types::RuntimeTypeI64;
+use crate::reflect::runtime_types::RuntimeTypeInternedString;
 use crate::reflect::runtime_types::RuntimeTypeString;
 use crate::reflect::runtime_types::RuntimeTypeU32;
 use crate::reflect::runtime_types::RuntimeTypeU64;
@@ -24,6 +25,7 @@ use crate::reflect::value::value_box::ReflectValueBox;
 use crate::reflect::value::value_ref::ReflectValueMut;
 use crate::reflect::value::value_ref::ReflectValueRef;
 use crate::reflect::RuntimeTypeBox;
+use crate::string_interner::InternedString;
 
 pub(crate) mod value_box;
 pub(crate) mod value_ref;
@@ -135,6 +137,10 @@ impl ProtobufValue for Chars {
     type RuntimeType = RuntimeTypeCarllercheChars;
 }
 
+impl ProtobufValue for InternedString {
+    type RuntimeType = RuntimeTypeInternedString;
+}
+
 // conflicting implementations, so generated code is used instead
 /*
 impl<E : ProtobufEnum> ProtobufValue for E {
@@ -131,7 +131,9 @@ impl<'a> ReflectValueRef<'a> {
     }
 
     /// Take `str` value.
-    pub fn to_str(&self) -> Option<&str> {
+    ///
+    /// Returned string borrows from the message, not from this value.
+    pub fn to_str(&self) -> Option<&'a str> {
         match *self {
             ReflectValueRef::String(v) => Some(v),
             _ => None,
@@ -139,7 +141,7 @@ impl<'a> ReflectValueRef<'a> {
     }
 
     /// Take `[u8]` value.
-    pub fn to_bytes(&self) -> Option<&[u8]> {
+    pub fn to_bytes(&self) -> Option<&'a [u8]> {
         match *self {
             ReflectValueRef::Bytes(v) => Some(v),
             _ => None,
@@ -321,3 +323,57 @@ impl<'a> From<&'a [u8]> for ReflectValueRef<'a> {
         ReflectValueRef::Bytes(v)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::FileDescriptorProto;
+    use crate::reflect::MessageRef;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+    use crate::Message;
+
+    // Functions below compile only if values borrow from the message
+    // rather than from temporary reflection references.
+
+    fn name<'a>(m: &'a FileDescriptorProto) -> &'a str {
+        let field = FileDescriptorProto::descriptor_static()
+            .get_field_by_name("name")
+            .unwrap();
+        field.get_singular_field_or_default(m).to_str().unwrap()
+    }
+
+    fn dependencies<'a>(m: &'a FileDescriptorProto) -> Vec<&'a str> {
+        let field = FileDescriptorProto::descriptor_static()
+            .get_field_by_name("dependency")
+            .unwrap();
+        let mut r = Vec::new();
+        for v in &field.get_repeated(m) {
+            r.push(v.to_str().unwrap());
+        }
+        r
+    }
+
+    fn struct_field<'a>(m: &'a Struct, key: &str) -> Option<MessageRef<'a>> {
+        let fields = Struct::descriptor_static()
+            .get_field_by_name("fields")
+            .unwrap();
+        fields.get_map(m).get(key.into())?.to_message()
+    }
+
+    #[test]
+    fn borrow_from_message() {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.dependency = vec!["b.proto".to_owned(), "c.proto".to_owned()];
+        assert_eq!("a.proto", name(&file));
+        assert_eq!(vec!["b.proto", "c.proto"], dependencies(&file));
+
+        let mut value = Value::new();
+        value.set_string_value("v".to_owned());
+        let mut s = Struct::new();
+        s.fields.insert("k".to_owned(), value.clone());
+        let v = struct_field(&s, "k").unwrap();
+        assert_eq!(Some(&value), v.downcast_ref::<Value>());
+        assert!(struct_field(&s, "x").is_none());
+    }
+}
@@ -2,9 +2,12 @@
 //!
 //! Should rarely be used by programs written by hands.
 
+use std::cmp;
 use std::collections::HashMap;
 use std::default::Default;
 use std::hash::Hash;
+use std::str;
+use std::sync::Arc;
 
 #[cfg(feature = "bytes")]
 use crate::bytes::Bytes;
@@ -12,13 +15,17 @@ use crate::bytes::Bytes;
 use crate::chars::Chars;
 
 use crate::coded_input_stream::CodedInputStream;
+use crate::coded_input_stream::UnknownEnumValuePolicy;
 use crate::coded_output_stream::CodedOutputStream;
 use crate::enums::ProtobufEnum;
+use crate::error::MessageTooLargeError;
 use crate::error::ProtobufError;
 use crate::error::ProtobufResult;
 use crate::error::WireError;
+use crate::ext::ExtensionFields;
 use crate::message::*;
 use crate::reflect::types::*;
+use crate::string_interner::InternedString;
 use crate::wire_format;
 use crate::wire_format::WireType;
 use crate::wire_format::WireTypeFixed32;
@@ -28,12 +35,46 @@ use crate::wire_format::WireTypeVarint;
 use crate::zigzag::*;
 use crate::MessageField;
 use crate::ProtobufEnumOrUnknown;
+use crate::SmallVec;
 
 use crate::unknown::UnknownFields;
+use crate::unknown::UnknownValueRef;
+use crate::view;
+use crate::view::MessageView;
 
 pub use crate::cached_size::CachedSize;
+pub use crate::drop_iterative::detach_nested_messages;
+pub use crate::drop_iterative::drop_iterative;
+pub use crate::drop_iterative::DetachNestedMessages;
+pub use crate::drop_iterative::DropIterative;
+pub use crate::drop_iterative::DropWorklist;
 pub use crate::lazy_v2::LazyV2;
 use crate::reflect::ProtobufValue;
+pub use crate::shrink_to_fit::shrink_to_fit;
+pub use crate::shrink_to_fit::ShrinkToFit;
+pub use crate::space_used::space_used_excluding_self;
+pub use crate::space_used::SpaceUsed;
+pub use crate::validate::validate_bytes_len;
+pub use crate::validate::validate_items;
+#[cfg(feature = "regex")]
+pub use crate::validate::validate_pattern;
+pub use crate::validate::validate_range;
+pub use crate::validate::validate_required;
+pub use crate::validate::validate_str_len;
+
+/// Markers referenced by generated code to check at compile time
+/// that the runtime supports the generated code.
+///
+/// Runtime supports code generated by codegen of the same major version
+/// and the same or older minor version, so patch and minor runtime
+/// upgrades do not break previously generated code.
+pub mod compat {
+    include!(concat!(env!("OUT_DIR"), "/compat.rs"));
+
+    /// Runtime is compiled with `with-bytes` feature.
+    #[cfg(feature = "bytes")]
+    pub const FEATURE_WITH_BYTES: () = ();
+}
 
 /// Given `u64` value compute varint encoded length.
 pub fn compute_raw_varint64_size(value: u64) -> u32 {
@@ -278,6 +319,11 @@ pub fn string_size(field_number: u32, s: &str) -> u32 {
     tag_size(field_number) + string_size_no_tag(s)
 }
 
+/// Size of encoded extension fields, also cache nested message sizes.
+pub fn extension_fields_size(extension_fields: &ExtensionFields) -> u32 {
+    extension_fields.compute_size()
+}
+
 /// Size of encoded unknown fields size.
 pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
     let mut r = 0;
@@ -298,6 +344,30 @@ pub fn unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
     r
 }
 
+/// Read repeated field of any type into given `SmallVec`.
+///
+/// Both packed and unpacked encodings are accepted for scalar types.
+pub fn read_repeated_into_small_vec<T: ProtobufType, const N: usize>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut SmallVec<T::ProtobufValue, N>,
+) -> ProtobufResult<()> {
+    if wire_type == T::WIRE_TYPE {
+        target.push(T::read(is)?);
+        Ok(())
+    } else if wire_type == WireTypeLengthDelimited {
+        let len = is.read_raw_varint64()?;
+        let old_limit = is.push_limit(len)?;
+        while !is.eof()? {
+            target.push(T::read(is)?);
+        }
+        is.pop_limit(old_limit);
+        Ok(())
+    } else {
+        Err(unexpected_wire_type(wire_type))
+    }
+}
+
 /// Read repeated `int32` field into given vec.
 pub fn read_repeated_int32_into(
     wire_type: WireType,
@@ -618,6 +688,79 @@ pub fn read_repeated_enum_or_unknown_into<E: ProtobufEnum>(
     }
 }
 
+/// Handle value of `proto2` enum field according to
+/// [`CodedInputStream::unknown_enum_value_policy`],
+/// `None` is returned when the value is stored in unknown fields.
+fn proto2_enum_value<E: ProtobufEnum>(
+    is: &mut CodedInputStream,
+    value: i32,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<Option<ProtobufEnumOrUnknown<E>>> {
+    if E::from_i32(value).is_some() {
+        return Ok(Some(ProtobufEnumOrUnknown::from_i32(value)));
+    }
+    match is.unknown_enum_value_policy() {
+        UnknownEnumValuePolicy::Keep => Ok(Some(ProtobufEnumOrUnknown::from_i32(value))),
+        UnknownEnumValuePolicy::UnknownFields => {
+            if !is.discard_unknown_fields() {
+                unknown_fields.add_varint(field_number, value as i64 as u64);
+            }
+            Ok(None)
+        }
+        UnknownEnumValuePolicy::Error => {
+            Err(ProtobufError::WireError(WireError::InvalidEnumValue(value)))
+        }
+        UnknownEnumValuePolicy::Default => Ok(Some(ProtobufEnumOrUnknown::new(E::default()))),
+    }
+}
+
+/// Read singular or oneof `proto2` enum field.
+///
+/// `None` is returned when the value is not declared in the enum
+/// and it is stored in unknown fields.
+pub fn read_proto2_enum_or_unknown<E: ProtobufEnum>(
+    is: &mut CodedInputStream,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<Option<ProtobufEnumOrUnknown<E>>> {
+    let value = is.read_int32()?;
+    proto2_enum_value(is, value, field_number, unknown_fields)
+}
+
+/// Read repeated `proto2` enum field into given vec.
+///
+/// Values not declared in the enum are handled according to
+/// [`CodedInputStream::unknown_enum_value_policy`].
+pub fn read_repeated_proto2_enum_or_unknown_into<E: ProtobufEnum>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<ProtobufEnumOrUnknown<E>>,
+    field_number: u32,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => {
+            let len = is.read_raw_varint64()?;
+            let old_limit = is.push_limit(len)?;
+            while !is.eof()? {
+                if let Some(e) = read_proto2_enum_or_unknown(is, field_number, unknown_fields)? {
+                    target.push(e);
+                }
+            }
+            is.pop_limit(old_limit);
+            Ok(())
+        }
+        WireTypeVarint => {
+            if let Some(e) = read_proto2_enum_or_unknown(is, field_number, unknown_fields)? {
+                target.push(e);
+            }
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
 /// Read repeated `enum` field into given vec,
 /// and when value is unknown store it in unknown fields
 /// which matches proto2 spec.
@@ -689,6 +832,21 @@ pub fn read_repeated_carllerche_string_into(
     }
 }
 
+/// Read repeated `InternedString` field into given vec.
+pub fn read_repeated_interned_string_into(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Vec<InternedString>,
+) -> ProtobufResult<()> {
+    match wire_type {
+        WireTypeLengthDelimited => {
+            target.push(is.read_interned_string()?);
+            Ok(())
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
 /// Read singular `Chars` field.
 #[cfg(feature = "bytes")]
 pub fn read_singular_carllerche_string_into(
@@ -816,16 +974,11 @@ pub fn read_repeated_message_into_vec<M: Message + Default>(
 ) -> ProtobufResult<()> {
     match wire_type {
         WireTypeLengthDelimited => {
-            is.incr_recursion()?;
-            let res = match is.read_message() {
-                Ok(m) => {
-                    target.push(m);
-                    Ok(())
-                }
-                Err(e) => Err(e),
-            };
-            is.decr_recursion();
-            res
+            let m = is.read_message()?;
+            let capacity = target.capacity();
+            target.push(m);
+            is.count_growth(capacity, target.capacity());
+            Ok(())
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
@@ -842,24 +995,75 @@ where
 {
     match wire_type {
         WireTypeLengthDelimited => {
-            is.incr_recursion()?;
             let mut m = M::new();
             let res = is.merge_message(&mut m);
             *target = MessageField::some(m);
-            is.decr_recursion();
+            is.count_allocation();
             res
         }
         _ => Err(unexpected_wire_type(wire_type)),
     }
 }
 
-fn skip_group(is: &mut CodedInputStream) -> ProtobufResult<()> {
-    loop {
-        let (_, wire_type) = is.read_tag_unpack()?;
-        if wire_type == wire_format::WireTypeEndGroup {
-            return Ok(());
+/// Read singular `message` field stored as `Option<M>`.
+pub fn read_singular_message_into_option<M>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<M>,
+) -> ProtobufResult<()>
+where
+    M: Message + Default,
+{
+    match wire_type {
+        WireTypeLengthDelimited => {
+            let mut m = M::new();
+            let res = is.merge_message(&mut m);
+            *target = Some(m);
+            res
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read singular `message` field stored as `Option<Box<M>>`.
+pub fn read_singular_message_into_option_box<M>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<Box<M>>,
+) -> ProtobufResult<()>
+where
+    M: Message + Default,
+{
+    match wire_type {
+        WireTypeLengthDelimited => {
+            let mut m = M::new();
+            let res = is.merge_message(&mut m);
+            *target = Some(Box::new(m));
+            is.count_allocation();
+            res
         }
-        is.skip_field(wire_type)?;
+        _ => Err(unexpected_wire_type(wire_type)),
+    }
+}
+
+/// Read singular `message` field stored as `Option<Arc<M>>`.
+pub fn read_singular_message_into_option_arc<M>(
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    target: &mut Option<Arc<M>>,
+) -> ProtobufResult<()>
+where
+    M: Message + Default,
+{
+    match wire_type {
+        WireTypeLengthDelimited => {
+            let mut m = M::new();
+            let res = is.merge_message(&mut m);
+            *target = Some(Arc::new(m));
+            is.count_allocation();
+            res
+        }
+        _ => Err(unexpected_wire_type(wire_type)),
     }
 }
 
@@ -871,16 +1075,219 @@ pub fn read_unknown_or_skip_group(
     is: &mut CodedInputStream,
     unknown_fields: &mut UnknownFields,
 ) -> ProtobufResult<()> {
+    if is.discard_unknown_fields() {
+        return skip_unknown_field(wire_type, is);
+    }
     match wire_type {
-        wire_format::WireTypeStartGroup => skip_group(is),
+        wire_format::WireTypeStartGroup => skip_unknown_field(wire_type, is),
         _ => {
+            let pos = is.pos();
             let unknown = is.read_unknown(wire_type)?;
+            is.count_unknown_bytes(is.pos() - pos);
             unknown_fields.add_value(field_number, unknown);
             Ok(())
         }
     }
 }
 
+/// Handle unknown field in generated code of message
+/// with `discard_unknown_fields` option: skip it.
+pub fn skip_unknown_field(wire_type: WireType, is: &mut CodedInputStream) -> ProtobufResult<()> {
+    let pos = is.pos();
+    is.skip_field(wire_type)?;
+    is.count_unknown_bytes(is.pos() - pos);
+    Ok(())
+}
+
+/// Handle unknown field in generated code of message with extension ranges.
+/// Registered extensions are stored in `extension_fields`,
+/// other fields are handled like in
+/// [`read_unknown_or_skip_group`](read_unknown_or_skip_group).
+pub fn read_unknown_or_extension<M: Message>(
+    field_number: u32,
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    extension_fields: &mut ExtensionFields,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    if let Some(registry) = is.extension_registry() {
+        if registry.read::<M>(field_number, wire_type, is, extension_fields)? {
+            return Ok(());
+        }
+    }
+    read_unknown_or_skip_group(field_number, wire_type, is, unknown_fields)
+}
+
+/// Handle unknown field in generated code of message with extension ranges
+/// and `discard_unknown_fields` option: registered extensions are stored
+/// in `extension_fields`, other fields are skipped.
+pub fn read_extension_or_skip_unknown<M: Message>(
+    field_number: u32,
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    extension_fields: &mut ExtensionFields,
+) -> ProtobufResult<()> {
+    if let Some(registry) = is.extension_registry() {
+        if registry.read::<M>(field_number, wire_type, is, extension_fields)? {
+            return Ok(());
+        }
+    }
+    skip_unknown_field(wire_type, is)
+}
+
+/// Field numbers of `MessageSet` item group.
+const MESSAGE_SET_ITEM: u32 = 1;
+const MESSAGE_SET_TYPE_ID: u32 = 2;
+const MESSAGE_SET_MESSAGE: u32 = 3;
+
+/// Read `MessageSet` item group content after the start group tag.
+///
+/// `read_message` is called with the stream positioned at the length-delimited
+/// message when `type_id` precedes it, which is what all encoders do.
+/// Otherwise message is stored in unknown fields when the group is finished.
+pub(crate) fn read_message_set_item<F>(
+    is: &mut CodedInputStream,
+    unknown_fields: &mut UnknownFields,
+    mut read_message: F,
+) -> ProtobufResult<()>
+where
+    F: FnMut(u32, &mut CodedInputStream, &mut UnknownFields) -> ProtobufResult<()>,
+{
+    let mut type_id = None;
+    let mut message = None;
+    loop {
+        let (field_number, wire_type) = is.read_tag_unpack()?;
+        match (field_number, wire_type) {
+            (MESSAGE_SET_ITEM, wire_format::WireTypeEndGroup) => break,
+            (_, wire_format::WireTypeEndGroup) => {
+                return Err(ProtobufError::WireError(WireError::UnexpectedEof));
+            }
+            (MESSAGE_SET_TYPE_ID, WireTypeVarint) => type_id = Some(is.read_uint32()?),
+            (MESSAGE_SET_MESSAGE, WireTypeLengthDelimited) => match type_id {
+                Some(type_id) => read_message(type_id, is, unknown_fields)?,
+                None => message = Some(is.read_bytes()?),
+            },
+            (_, wire_type) => is.skip_field(wire_type)?,
+        }
+    }
+    if let (Some(type_id), Some(message)) = (type_id, message) {
+        unknown_fields.add_length_delimited(type_id, message);
+    }
+    Ok(())
+}
+
+/// Handle unknown field in generated code of message with
+/// `message_set_wire_format` option.
+///
+/// Items of the set are stored like extension fields in regular encoding,
+/// i. e. message of type `type_id` is stored as length-delimited field
+/// number `type_id`, so they can be accessed with extension accessors.
+/// Registered extensions are decoded in `extension_fields`.
+pub fn read_message_set_field_or_extension<M: Message>(
+    field_number: u32,
+    wire_type: WireType,
+    is: &mut CodedInputStream,
+    extension_fields: &mut ExtensionFields,
+    unknown_fields: &mut UnknownFields,
+) -> ProtobufResult<()> {
+    if field_number != MESSAGE_SET_ITEM || wire_type != wire_format::WireTypeStartGroup {
+        return read_unknown_or_extension::<M>(
+            field_number,
+            wire_type,
+            is,
+            extension_fields,
+            unknown_fields,
+        );
+    }
+    is.incr_recursion()?;
+    let r = read_message_set_item(is, unknown_fields, |type_id, is, unknown_fields| {
+        read_unknown_or_extension::<M>(
+            type_id,
+            WireTypeLengthDelimited,
+            is,
+            extension_fields,
+            unknown_fields,
+        )
+    });
+    is.decr_recursion();
+    r
+}
+
+/// Extension and unknown fields of `MessageSet` in regular encoding.
+fn message_set_fields(
+    extension_fields: &ExtensionFields,
+    unknown_fields: &UnknownFields,
+) -> UnknownFields {
+    let mut fields = unknown_fields.clone();
+    extension_fields.append_to_unknown(&mut fields);
+    fields
+}
+
+fn message_set_item_size(type_id: u32, message: &[u8]) -> u32 {
+    tag_size(MESSAGE_SET_ITEM) * 2
+        + tag_size(MESSAGE_SET_TYPE_ID)
+        + compute_raw_varint32_size(type_id)
+        + tag_size(MESSAGE_SET_MESSAGE)
+        + bytes_size_no_tag(message)
+}
+
+/// Size of `MessageSet` items and other unknown fields.
+pub(crate) fn message_set_unknown_fields_size(unknown_fields: &UnknownFields) -> u32 {
+    let mut r = 0;
+    for (number, values) in unknown_fields {
+        r += (tag_size(number) + 4) * values.fixed32.len() as u32;
+        r += (tag_size(number) + 8) * values.fixed64.len() as u32;
+
+        r += tag_size(number) * values.varint.len() as u32;
+        for varint in &values.varint {
+            r += varint.len_varint();
+        }
+
+        for message in &values.length_delimited {
+            r += message_set_item_size(number, message);
+        }
+    }
+    r
+}
+
+/// Write length-delimited unknown fields as `MessageSet` items,
+/// and other unknown fields as is.
+pub(crate) fn write_message_set_unknown_fields(
+    unknown_fields: &UnknownFields,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    for (number, values) in unknown_fields {
+        for value in values {
+            match value {
+                UnknownValueRef::LengthDelimited(message) => {
+                    os.write_tag(MESSAGE_SET_ITEM, wire_format::WireTypeStartGroup)?;
+                    os.write_uint32(MESSAGE_SET_TYPE_ID, number)?;
+                    os.write_bytes(MESSAGE_SET_MESSAGE, message)?;
+                    os.write_tag(MESSAGE_SET_ITEM, wire_format::WireTypeEndGroup)?;
+                }
+                value => os.write_unknown(number, value)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Size of extension and unknown fields of message with
+/// `message_set_wire_format` option, also cache nested message sizes.
+pub fn message_set_size(extension_fields: &ExtensionFields, unknown_fields: &UnknownFields) -> u32 {
+    message_set_unknown_fields_size(&message_set_fields(extension_fields, unknown_fields))
+}
+
+/// Write extension and unknown fields of message with
+/// `message_set_wire_format` option as `MessageSet` items.
+pub fn write_message_set(
+    extension_fields: &ExtensionFields,
+    unknown_fields: &UnknownFields,
+    os: &mut CodedOutputStream,
+) -> ProtobufResult<()> {
+    write_message_set_unknown_fields(&message_set_fields(extension_fields, unknown_fields), os)
+}
+
 /// Create an error for unexpected wire type.
 ///
 /// Function is used in generated code, so error types can be changed,
@@ -889,6 +1296,62 @@ pub fn unexpected_wire_type(wire_type: WireType) -> ProtobufError {
     ProtobufError::WireError(WireError::UnexpectedWireType(wire_type))
 }
 
+fn message_too_large(message: &str, size: u64, max_size: u64) -> ProtobufError {
+    ProtobufError::MessageTooLarge(MessageTooLargeError {
+        message: message.to_owned(),
+        size,
+        max_size,
+    })
+}
+
+/// Check size of message with `rustproto.max_size` option.
+///
+/// Function is called from generated code before serialization.
+pub fn check_message_size(message: &str, size: u64, max_size: u64) -> ProtobufResult<()> {
+    if size > max_size {
+        return Err(message_too_large(message, size, max_size));
+    }
+    Ok(())
+}
+
+/// Merge message with `rustproto.max_size` option.
+///
+/// Input is limited to `max_size + 1` bytes while `merge` is running,
+/// so a field which does not fit the limit fails before it is read
+/// rather than being checked after it is allocated.
+pub fn merge_with_max_size<F>(
+    message: &str,
+    max_size: u64,
+    is: &mut CodedInputStream,
+    merge: F,
+) -> ProtobufResult<()>
+where
+    F: FnOnce(&mut CodedInputStream) -> ProtobufResult<()>,
+{
+    let start = is.pos();
+    let limit = cmp::min(max_size.saturating_add(1), is.bytes_until_limit());
+    let old_limit = is.push_limit(limit)?;
+    let r = merge(is);
+    let size = is.pos() - start;
+    is.pop_limit(old_limit);
+    match r {
+        // Stopped at the limit, so there's more
+        Ok(()) => check_message_size(message, size, max_size),
+        Err(ProtobufError::WireError(WireError::UnexpectedEof))
+        | Err(ProtobufError::WireError(WireError::TruncatedMessage))
+        | Err(ProtobufError::WireError(WireError::LimitIncrease)) => {
+            // Field crossed the limit, or input is truncated:
+            // these can be distinguished only if we know where input ends
+            let available = is.max_remaining_bytes();
+            if available != u64::MAX && size.saturating_add(available) > max_size {
+                return Err(message_too_large(message, size, max_size));
+            }
+            r
+        }
+        Err(e) => Err(e),
+    }
+}
+
 /// Compute serialized size of `map` field and cache nested field sizes.
 pub fn compute_map_size<K, V>(
     field_number: u32,
@@ -999,3 +1462,86 @@ where
 
     Ok(())
 }
+
+/// Read singular scalar field of serialized message in generated views.
+///
+/// Last occurrence of the field wins, `None` is returned if field is not set.
+pub fn view_singular<T: ProtobufType>(
+    bytes: &[u8],
+    field_number: u32,
+) -> ProtobufResult<Option<T::ProtobufValue>> {
+    match view::last_field(bytes, field_number)? {
+        Some(value) => Ok(Some(view::decode_scalar::<T>(value)?)),
+        None => Ok(None),
+    }
+}
+
+/// Check if field is present in serialized message in generated views.
+pub fn view_has_field(bytes: &[u8], field_number: u32) -> ProtobufResult<bool> {
+    Ok(view::last_field(bytes, field_number)?.is_some())
+}
+
+/// Number of the last present field of oneof with given field numbers
+/// in generated views.
+pub fn view_oneof_case(bytes: &[u8], field_numbers: &[u32]) -> ProtobufResult<Option<u32>> {
+    let mut r = None;
+    for field in view::fields(bytes) {
+        let field = field?;
+        if field_numbers.contains(&field.number) {
+            r = Some(field.number);
+        }
+    }
+    Ok(r)
+}
+
+/// Read singular `bytes` field of serialized message in generated views.
+pub fn view_bytes<'a>(bytes: &'a [u8], field_number: u32) -> ProtobufResult<Option<&'a [u8]>> {
+    match view::last_field(bytes, field_number)? {
+        Some(value) => Ok(Some(view::length_delimited(value)?)),
+        None => Ok(None),
+    }
+}
+
+/// Read singular `string` field of serialized message in generated views.
+pub fn view_string<'a>(bytes: &'a [u8], field_number: u32) -> ProtobufResult<Option<&'a str>> {
+    match view_bytes(bytes, field_number)? {
+        Some(bytes) => Ok(Some(str::from_utf8(bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Read singular message field of serialized message in generated views.
+///
+/// Unlike parser, which merges all occurrences of message field,
+/// only the last occurrence is returned.
+pub fn view_message<'a, V: MessageView<'a>>(
+    bytes: &'a [u8],
+    field_number: u32,
+) -> ProtobufResult<Option<V>> {
+    Ok(view_bytes(bytes, field_number)?.map(V::from_bytes))
+}
+
+/// Iterate repeated `bytes` field of serialized message in generated views.
+pub fn view_repeated_bytes<'a>(
+    bytes: &'a [u8],
+    field_number: u32,
+) -> impl Iterator<Item = ProtobufResult<&'a [u8]>> + 'a {
+    view::repeated_length_delimited(bytes, field_number)
+}
+
+/// Iterate repeated `string` field of serialized message in generated views.
+pub fn view_repeated_string<'a>(
+    bytes: &'a [u8],
+    field_number: u32,
+) -> impl Iterator<Item = ProtobufResult<&'a str>> + 'a {
+    view::repeated_length_delimited(bytes, field_number)
+        .map(|r| r.and_then(|bytes| Ok(str::from_utf8(bytes)?)))
+}
+
+/// Iterate repeated message field of serialized message in generated views.
+pub fn view_repeated_message<'a, V: MessageView<'a> + 'a>(
+    bytes: &'a [u8],
+    field_number: u32,
+) -> impl Iterator<Item = ProtobufResult<V>> + 'a {
+    view::repeated_length_delimited(bytes, field_number).map(|r| r.map(V::from_bytes))
+}
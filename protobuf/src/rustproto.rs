@@ -24,49 +24,200 @@
 /// Extension fields
 pub mod exts {
 
+    /// Extension field `expose_oneof_all` = 17001 of `.google.protobuf.FileOptions`
     pub const expose_oneof_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17001, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `expose_fields_all` = 17003 of `.google.protobuf.FileOptions`
     pub const expose_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `generate_accessors_all` = 17004 of `.google.protobuf.FileOptions`
     pub const generate_accessors_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `generate_getter_all` = 17005 of `.google.protobuf.FileOptions`
     pub const generate_getter_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17005, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `private_fields_all` = 17006 of `.google.protobuf.FileOptions`
+    pub const private_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17006, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `carllerche_bytes_for_bytes_all` = 17011 of `.google.protobuf.FileOptions`
     pub const carllerche_bytes_for_bytes_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `carllerche_bytes_for_string_all` = 17012 of `.google.protobuf.FileOptions`
     pub const carllerche_bytes_for_string_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `singular_field_option_box_all` = 17021 of `.google.protobuf.FileOptions`
+    pub const singular_field_option_box_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option_all` = 17022 of `.google.protobuf.FileOptions`
+    pub const singular_field_option_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17022, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option_arc_all` = 17023 of `.google.protobuf.FileOptions`
+    pub const singular_field_option_arc_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17023, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `serde_derive_all` = 17030 of `.google.protobuf.FileOptions`
     pub const serde_derive_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17030, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `serde_derive_cfg_all` = 17031 of `.google.protobuf.FileOptions`
     pub const serde_derive_cfg_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `lite_runtime_all` = 17035 of `.google.protobuf.FileOptions`
     pub const lite_runtime_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17035, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `small_vec_for_repeated_all` = 17040 of `.google.protobuf.FileOptions`
+    pub const small_vec_for_repeated_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeUint32> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `interned_string_for_string_all` = 17041 of `.google.protobuf.FileOptions`
+    pub const interned_string_for_string_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `derive_hash_eq_ord_all` = 17050 of `.google.protobuf.FileOptions`
+    pub const derive_hash_eq_ord_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17050, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `derive_hash_eq_ord_skip_ineligible_all` = 17051 of `.google.protobuf.FileOptions`
+    pub const derive_hash_eq_ord_skip_ineligible_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17051, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `impl_try_from_bytes_all` = 17052 of `.google.protobuf.FileOptions`
+    pub const impl_try_from_bytes_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17052, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `iterative_drop_all` = 17053 of `.google.protobuf.FileOptions`
+    pub const iterative_drop_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17053, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `generate_view_all` = 17054 of `.google.protobuf.FileOptions`
+    pub const generate_view_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17054, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `discard_unknown_fields_all` = 17056 of `.google.protobuf.FileOptions`
+    pub const discard_unknown_fields_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17056, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `uniform_accessors_all` = 17057 of `.google.protobuf.FileOptions`
+    pub const uniform_accessors_all: crate::ext::ExtFieldOptional<crate::descriptor::FileOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17057, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `expose_oneof` = 17001 of `.google.protobuf.MessageOptions`
     pub const expose_oneof: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17001, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `expose_fields` = 17003 of `.google.protobuf.MessageOptions`
     pub const expose_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `generate_accessors` = 17004 of `.google.protobuf.MessageOptions`
     pub const generate_accessors: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `generate_getter` = 17005 of `.google.protobuf.MessageOptions`
     pub const generate_getter: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17005, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `private_fields` = 17006 of `.google.protobuf.MessageOptions`
+    pub const private_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17006, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `carllerche_bytes_for_bytes` = 17011 of `.google.protobuf.MessageOptions`
     pub const carllerche_bytes_for_bytes: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `carllerche_bytes_for_string` = 17012 of `.google.protobuf.MessageOptions`
     pub const carllerche_bytes_for_string: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `singular_field_option_box` = 17021 of `.google.protobuf.MessageOptions`
+    pub const singular_field_option_box: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option` = 17022 of `.google.protobuf.MessageOptions`
+    pub const singular_field_option: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17022, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option_arc` = 17023 of `.google.protobuf.MessageOptions`
+    pub const singular_field_option_arc: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17023, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `serde_derive` = 17030 of `.google.protobuf.MessageOptions`
     pub const serde_derive: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17030, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `serde_derive_cfg` = 17031 of `.google.protobuf.MessageOptions`
     pub const serde_derive_cfg: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17031, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `small_vec_for_repeated` = 17040 of `.google.protobuf.MessageOptions`
+    pub const small_vec_for_repeated: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeUint32> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `interned_string_for_string` = 17041 of `.google.protobuf.MessageOptions`
+    pub const interned_string_for_string: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `derive_hash_eq_ord` = 17050 of `.google.protobuf.MessageOptions`
+    pub const derive_hash_eq_ord: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17050, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `derive_hash_eq_ord_skip_ineligible` = 17051 of `.google.protobuf.MessageOptions`
+    pub const derive_hash_eq_ord_skip_ineligible: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17051, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `impl_try_from_bytes` = 17052 of `.google.protobuf.MessageOptions`
+    pub const impl_try_from_bytes: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17052, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `iterative_drop` = 17053 of `.google.protobuf.MessageOptions`
+    pub const iterative_drop: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17053, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `generate_view` = 17054 of `.google.protobuf.MessageOptions`
+    pub const generate_view: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17054, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `max_size` = 17055 of `.google.protobuf.MessageOptions`
+    pub const max_size: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeUint64> = crate::ext::ExtFieldOptional { field_number: 17055, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `discard_unknown_fields` = 17056 of `.google.protobuf.MessageOptions`
+    pub const discard_unknown_fields: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17056, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `uniform_accessors` = 17057 of `.google.protobuf.MessageOptions`
+    pub const uniform_accessors: crate::ext::ExtFieldOptional<crate::descriptor::MessageOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17057, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `expose_fields_field` = 17003 of `.google.protobuf.FieldOptions`
     pub const expose_fields_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17003, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `generate_accessors_field` = 17004 of `.google.protobuf.FieldOptions`
     pub const generate_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17004, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `generate_getter_field` = 17005 of `.google.protobuf.FieldOptions`
     pub const generate_getter_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17005, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `private_fields_field` = 17006 of `.google.protobuf.FieldOptions`
+    pub const private_fields_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17006, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `carllerche_bytes_for_bytes_field` = 17011 of `.google.protobuf.FieldOptions`
     pub const carllerche_bytes_for_bytes_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17011, phantom: ::std::marker::PhantomData };
 
+    /// Extension field `carllerche_bytes_for_string_field` = 17012 of `.google.protobuf.FieldOptions`
     pub const carllerche_bytes_for_string_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17012, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option_box_field` = 17021 of `.google.protobuf.FieldOptions`
+    pub const singular_field_option_box_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17021, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option_field` = 17022 of `.google.protobuf.FieldOptions`
+    pub const singular_field_option_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17022, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `singular_field_option_arc_field` = 17023 of `.google.protobuf.FieldOptions`
+    pub const singular_field_option_arc_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17023, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `small_vec_for_repeated_field` = 17040 of `.google.protobuf.FieldOptions`
+    pub const small_vec_for_repeated_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeUint32> = crate::ext::ExtFieldOptional { field_number: 17040, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `interned_string_for_string_field` = 17041 of `.google.protobuf.FieldOptions`
+    pub const interned_string_for_string_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17041, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `uniform_accessors_field` = 17057 of `.google.protobuf.FieldOptions`
+    pub const uniform_accessors_field: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17057, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_gte` = 17060 of `.google.protobuf.FieldOptions`
+    pub const validate_gte: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeDouble> = crate::ext::ExtFieldOptional { field_number: 17060, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_lte` = 17061 of `.google.protobuf.FieldOptions`
+    pub const validate_lte: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeDouble> = crate::ext::ExtFieldOptional { field_number: 17061, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_min_len` = 17062 of `.google.protobuf.FieldOptions`
+    pub const validate_min_len: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeUint64> = crate::ext::ExtFieldOptional { field_number: 17062, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_max_len` = 17063 of `.google.protobuf.FieldOptions`
+    pub const validate_max_len: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeUint64> = crate::ext::ExtFieldOptional { field_number: 17063, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_pattern` = 17064 of `.google.protobuf.FieldOptions`
+    pub const validate_pattern: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeString> = crate::ext::ExtFieldOptional { field_number: 17064, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_required` = 17065 of `.google.protobuf.FieldOptions`
+    pub const validate_required: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17065, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_min_items` = 17066 of `.google.protobuf.FieldOptions`
+    pub const validate_min_items: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeUint64> = crate::ext::ExtFieldOptional { field_number: 17066, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `validate_max_items` = 17067 of `.google.protobuf.FieldOptions`
+    pub const validate_max_items: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeUint64> = crate::ext::ExtFieldOptional { field_number: 17067, phantom: ::std::marker::PhantomData };
+
+    /// Extension field `sensitive` = 17070 of `.google.protobuf.FieldOptions`
+    pub const sensitive: crate::ext::ExtFieldOptional<crate::descriptor::FieldOptions, crate::reflect::types::ProtobufTypeBool> = crate::ext::ExtFieldOptional { field_number: 17070, phantom: ::std::marker::PhantomData };
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
@@ -77,43 +228,115 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     FieldsAll:T\n\x16generate_accessors_all\x18\xec\x84\x01\x20\x01(\x08\x12\
     \x1c.google.protobuf.FileOptionsR\x14generateAccessorsAll:N\n\x13generat\
     e_getter_all\x18\xed\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOp\
-    tionsR\x11generateGetterAll:b\n\x1ecarllerche_bytes_for_bytes_all\x18\
-    \xf3\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1acarlle\
-    rcheBytesForBytesAll:d\n\x1fcarllerche_bytes_for_string_all\x18\xf4\x84\
-    \x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x1bcarllercheByte\
-    sForStringAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\x12\
+    tionsR\x11generateGetterAll:L\n\x12private_fields_all\x18\xee\x84\x01\
+    \x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x10privateFieldsAll:b\
+    \n\x1ecarllerche_bytes_for_bytes_all\x18\xf3\x84\x01\x20\x01(\x08\x12\
+    \x1c.google.protobuf.FileOptionsR\x1acarllercheBytesForBytesAll:d\n\x1fc\
+    arllerche_bytes_for_string_all\x18\xf4\x84\x01\x20\x01(\x08\x12\x1c.goog\
+    le.protobuf.FileOptionsR\x1bcarllercheBytesForStringAll:`\n\x1dsingular_\
+    field_option_box_all\x18\xfd\x84\x01\x20\x01(\x08\x12\x1c.google.protobu\
+    f.FileOptionsR\x19singularFieldOptionBoxAll:Y\n\x19singular_field_option\
+    _all\x18\xfe\x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\
+    \x16singularFieldOptionAll:`\n\x1dsingular_field_option_arc_all\x18\xff\
+    \x84\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x19singularFi\
+    eldOptionArcAll:H\n\x10serde_derive_all\x18\x86\x85\x01\x20\x01(\x08\x12\
     \x1c.google.protobuf.FileOptionsR\x0eserdeDeriveAll:O\n\x14serde_derive_\
     cfg_all\x18\x87\x85\x01\x20\x01(\t\x12\x1c.google.protobuf.FileOptionsR\
     \x11serdeDeriveCfgAll:H\n\x10lite_runtime_all\x18\x8b\x85\x01\x20\x01(\
-    \x08\x12\x1c.google.protobuf.FileOptionsR\x0eliteRuntimeAll:D\n\x0cexpos\
-    e_oneof\x18\xe9\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOpti\
-    onsR\x0bexposeOneof:F\n\rexpose_fields\x18\xeb\x84\x01\x20\x01(\x08\x12\
-    \x1f.google.protobuf.MessageOptionsR\x0cexposeFields:P\n\x12generate_acc\
-    essors\x18\xec\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
-    nsR\x11generateAccessors:J\n\x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\
-    \x08\x12\x1f.google.protobuf.MessageOptionsR\x0egenerateGetter:^\n\x1aca\
-    rllerche_bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.google.pro\
-    tobuf.MessageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche_bytes\
-    _for_string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.Message\
-    OptionsR\x18carllercheBytesForString:D\n\x0cserde_derive\x18\x86\x85\x01\
-    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0bserdeDerive:K\n\
-    \x10serde_derive_cfg\x18\x87\x85\x01\x20\x01(\t\x12\x1f.google.protobuf.\
-    MessageOptionsR\x0eserdeDeriveCfg:O\n\x13expose_fields_field\x18\xeb\x84\
-    \x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x11exposeFieldsF\
-    ield:Y\n\x18generate_accessors_field\x18\xec\x84\x01\x20\x01(\x08\x12\
-    \x1d.google.protobuf.FieldOptionsR\x16generateAccessorsField:S\n\x15gene\
-    rate_getter_field\x18\xed\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.F\
-    ieldOptionsR\x13generateGetterField:g\n\x20carllerche_bytes_for_bytes_fi\
-    eld\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\
-    \x1ccarllercheBytesForBytesField:i\n!carllerche_bytes_for_string_field\
-    \x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1dc\
-    arllercheBytesForStringFieldJ\x8e\x18\n\x06\x12\x04\0\0A\x01\n\x08\n\x01\
+    \x08\x12\x1c.google.protobuf.FileOptionsR\x0eliteRuntimeAll:Z\n\x1asmall\
+    _vec_for_repeated_all\x18\x90\x85\x01\x20\x01(\r\x12\x1c.google.protobuf\
+    .FileOptionsR\x16smallVecForRepeatedAll:b\n\x1einterned_string_for_strin\
+    g_all\x18\x91\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\
+    \x1ainternedStringForStringAll:R\n\x16derive_hash_eq_ord_all\x18\x9a\x85\
+    \x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x12deriveHashEqOr\
+    dAll:p\n&derive_hash_eq_ord_skip_ineligible_all\x18\x9b\x85\x01\x20\x01(\
+    \x08\x12\x1c.google.protobuf.FileOptionsR\x20deriveHashEqOrdSkipIneligib\
+    leAll:T\n\x17impl_try_from_bytes_all\x18\x9c\x85\x01\x20\x01(\x08\x12\
+    \x1c.google.protobuf.FileOptionsR\x13implTryFromBytesAll:L\n\x12iterativ\
+    e_drop_all\x18\x9d\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOpti\
+    onsR\x10iterativeDropAll:J\n\x11generate_view_all\x18\x9e\x85\x01\x20\
+    \x01(\x08\x12\x1c.google.protobuf.FileOptionsR\x0fgenerateViewAll:[\n\
+    \x1adiscard_unknown_fields_all\x18\xa0\x85\x01\x20\x01(\x08\x12\x1c.goog\
+    le.protobuf.FileOptionsR\x17discardUnknownFieldsAll:R\n\x15uniform_acces\
+    sors_all\x18\xa1\x85\x01\x20\x01(\x08\x12\x1c.google.protobuf.FileOption\
+    sR\x13uniformAccessorsAll:D\n\x0cexpose_oneof\x18\xe9\x84\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x0bexposeOneof:F\n\rexpose_\
+    fields\x18\xeb\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptio\
+    nsR\x0cexposeFields:P\n\x12generate_accessors\x18\xec\x84\x01\x20\x01(\
+    \x08\x12\x1f.google.protobuf.MessageOptionsR\x11generateAccessors:J\n\
+    \x0fgenerate_getter\x18\xed\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf\
+    .MessageOptionsR\x0egenerateGetter:H\n\x0eprivate_fields\x18\xee\x84\x01\
+    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\rprivateFields:^\n\
+    \x1acarllerche_bytes_for_bytes\x18\xf3\x84\x01\x20\x01(\x08\x12\x1f.goog\
+    le.protobuf.MessageOptionsR\x17carllercheBytesForBytes:`\n\x1bcarllerche\
+    _bytes_for_string\x18\xf4\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.M\
+    essageOptionsR\x18carllercheBytesForString:\\\n\x19singular_field_option\
+    _box\x18\xfd\x84\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptions\
+    R\x16singularFieldOptionBox:U\n\x15singular_field_option\x18\xfe\x84\x01\
+    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x13singularFieldOp\
+    tion:\\\n\x19singular_field_option_arc\x18\xff\x84\x01\x20\x01(\x08\x12\
+    \x1f.google.protobuf.MessageOptionsR\x16singularFieldOptionArc:D\n\x0cse\
+    rde_derive\x18\x86\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageO\
+    ptionsR\x0bserdeDerive:K\n\x10serde_derive_cfg\x18\x87\x85\x01\x20\x01(\
+    \t\x12\x1f.google.protobuf.MessageOptionsR\x0eserdeDeriveCfg:V\n\x16smal\
+    l_vec_for_repeated\x18\x90\x85\x01\x20\x01(\r\x12\x1f.google.protobuf.Me\
+    ssageOptionsR\x13smallVecForRepeated:^\n\x1ainterned_string_for_string\
+    \x18\x91\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\
+    \x17internedStringForString:N\n\x12derive_hash_eq_ord\x18\x9a\x85\x01\
+    \x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x0fderiveHashEqOrd\
+    :l\n\"derive_hash_eq_ord_skip_ineligible\x18\x9b\x85\x01\x20\x01(\x08\
+    \x12\x1f.google.protobuf.MessageOptionsR\x1dderiveHashEqOrdSkipIneligibl\
+    e:P\n\x13impl_try_from_bytes\x18\x9c\x85\x01\x20\x01(\x08\x12\x1f.google\
+    .protobuf.MessageOptionsR\x10implTryFromBytes:H\n\x0eiterative_drop\x18\
+    \x9d\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\ritera\
+    tiveDrop:F\n\rgenerate_view\x18\x9e\x85\x01\x20\x01(\x08\x12\x1f.google.\
+    protobuf.MessageOptionsR\x0cgenerateView:<\n\x08max_size\x18\x9f\x85\x01\
+    \x20\x01(\x04\x12\x1f.google.protobuf.MessageOptionsR\x07maxSize:W\n\x16\
+    discard_unknown_fields\x18\xa0\x85\x01\x20\x01(\x08\x12\x1f.google.proto\
+    buf.MessageOptionsR\x14discardUnknownFields:N\n\x11uniform_accessors\x18\
+    \xa1\x85\x01\x20\x01(\x08\x12\x1f.google.protobuf.MessageOptionsR\x10uni\
+    formAccessors:O\n\x13expose_fields_field\x18\xeb\x84\x01\x20\x01(\x08\
+    \x12\x1d.google.protobuf.FieldOptionsR\x11exposeFieldsField:Y\n\x18gener\
+    ate_accessors_field\x18\xec\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf\
+    .FieldOptionsR\x16generateAccessorsField:S\n\x15generate_getter_field\
+    \x18\xed\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x13g\
+    enerateGetterField:Q\n\x14private_fields_field\x18\xee\x84\x01\x20\x01(\
+    \x08\x12\x1d.google.protobuf.FieldOptionsR\x12privateFieldsField:g\n\x20\
+    carllerche_bytes_for_bytes_field\x18\xf3\x84\x01\x20\x01(\x08\x12\x1d.go\
+    ogle.protobuf.FieldOptionsR\x1ccarllercheBytesForBytesField:i\n!carllerc\
+    he_bytes_for_string_field\x18\xf4\x84\x01\x20\x01(\x08\x12\x1d.google.pr\
+    otobuf.FieldOptionsR\x1dcarllercheBytesForStringField:e\n\x1fsingular_fi\
+    eld_option_box_field\x18\xfd\x84\x01\x20\x01(\x08\x12\x1d.google.protobu\
+    f.FieldOptionsR\x1bsingularFieldOptionBoxField:^\n\x1bsingular_field_opt\
+    ion_field\x18\xfe\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOpti\
+    onsR\x18singularFieldOptionField:e\n\x1fsingular_field_option_arc_field\
+    \x18\xff\x84\x01\x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1bs\
+    ingularFieldOptionArcField:_\n\x1csmall_vec_for_repeated_field\x18\x90\
+    \x85\x01\x20\x01(\r\x12\x1d.google.protobuf.FieldOptionsR\x18smallVecFor\
+    RepeatedField:g\n\x20interned_string_for_string_field\x18\x91\x85\x01\
+    \x20\x01(\x08\x12\x1d.google.protobuf.FieldOptionsR\x1cinternedStringFor\
+    StringField:W\n\x17uniform_accessors_field\x18\xa1\x85\x01\x20\x01(\x08\
+    \x12\x1d.google.protobuf.FieldOptionsR\x15uniformAccessorsField:B\n\x0cv\
+    alidate_gte\x18\xa4\x85\x01\x20\x01(\x01\x12\x1d.google.protobuf.FieldOp\
+    tionsR\x0bvalidateGte:B\n\x0cvalidate_lte\x18\xa5\x85\x01\x20\x01(\x01\
+    \x12\x1d.google.protobuf.FieldOptionsR\x0bvalidateLte:I\n\x10validate_mi\
+    n_len\x18\xa6\x85\x01\x20\x01(\x04\x12\x1d.google.protobuf.FieldOptionsR\
+    \x0evalidateMinLen:I\n\x10validate_max_len\x18\xa7\x85\x01\x20\x01(\x04\
+    \x12\x1d.google.protobuf.FieldOptionsR\x0evalidateMaxLen:J\n\x10validate\
+    _pattern\x18\xa8\x85\x01\x20\x01(\t\x12\x1d.google.protobuf.FieldOptions\
+    R\x0fvalidatePattern:L\n\x11validate_required\x18\xa9\x85\x01\x20\x01(\
+    \x08\x12\x1d.google.protobuf.FieldOptionsR\x10validateRequired:M\n\x12va\
+    lidate_min_items\x18\xaa\x85\x01\x20\x01(\x04\x12\x1d.google.protobuf.Fi\
+    eldOptionsR\x10validateMinItems:M\n\x12validate_max_items\x18\xab\x85\
+    \x01\x20\x01(\x04\x12\x1d.google.protobuf.FieldOptionsR\x10validateMaxIt\
+    ems:=\n\tsensitive\x18\xae\x85\x01\x20\x01(\x08\x12\x1d.google.protobuf.\
+    FieldOptionsR\tsensitiveJ\x9cJ\n\x07\x12\x05\0\0\xb2\x01\x01\n\x08\n\x01\
     \x0c\x12\x03\0\0\x12\n\t\n\x02\x03\0\x12\x03\x02\0*\n\xe5\x01\n\x01\x02\
     \x12\x03\n\0\x122^\x20see\x20https://github.com/gogo/protobuf/blob/maste\
     r/gogoproto/gogo.proto\n\x20for\x20the\x20original\x20idea\n2{\x20Genera\
     ted\x20files\x20can\x20be\x20customized\x20using\x20this\x20proto\n\x20o\
     r\x20using\x20`Customize`\x20struct\x20when\x20codegen\x20is\x20invoked\
-    \x20programmatically.\n\n\t\n\x01\x07\x12\x04\x0c\0!\x01\n7\n\x02\x07\0\
+    \x20programmatically.\n\n\t\n\x01\x07\x12\x04\x0c\0D\x01\n7\n\x02\x07\0\
     \x12\x03\x0e\x04+\x1a,\x20When\x20true,\x20oneof\x20field\x20is\x20gener\
     ated\x20public\n\n\n\n\x03\x07\0\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\0\
     \x04\x12\x03\x0e\x04\x0c\n\n\n\x03\x07\0\x05\x12\x03\x0e\r\x11\n\n\n\x03\
@@ -132,83 +355,287 @@ static file_descriptor_proto_data: &'static [u8] = b"\
     \x20\"proto2\"`\n\n\n\n\x03\x07\x03\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
     \x03\x04\x12\x03\x14\x04\x0c\n\n\n\x03\x07\x03\x05\x12\x03\x14\r\x11\n\n\
     \n\x03\x07\x03\x01\x12\x03\x14\x12%\n\n\n\x03\x07\x03\x03\x12\x03\x14(-\
-    \n2\n\x02\x07\x04\x12\x03\x16\x049\x1a'\x20Use\x20`bytes::Bytes`\x20for\
-    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\
-    \x03\x07\x04\x04\x12\x03\x16\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x16\r\
-    \x11\n\n\n\x03\x07\x04\x01\x12\x03\x16\x120\n\n\n\x03\x07\x04\x03\x12\
-    \x03\x1638\n3\n\x02\x07\x05\x12\x03\x18\x04:\x1a(\x20Use\x20`bytes::Byte\
-    s`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x05\x02\x12\x03\x0c\x07\
-    \"\n\n\n\x03\x07\x05\x04\x12\x03\x18\x04\x0c\n\n\n\x03\x07\x05\x05\x12\
-    \x03\x18\r\x11\n\n\n\x03\x07\x05\x01\x12\x03\x18\x121\n\n\n\x03\x07\x05\
-    \x03\x12\x03\x1849\nJ\n\x02\x07\x06\x12\x03\x1b\x04+\x1a?\x20Use\x20`ser\
-    de_derive`\x20to\x20implement\x20`Serialize`\x20and\x20`Deserialize`\n\n\
-    \n\n\x03\x07\x06\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x06\x04\x12\x03\x1b\
-    \x04\x0c\n\n\n\x03\x07\x06\x05\x12\x03\x1b\r\x11\n\n\n\x03\x07\x06\x01\
-    \x12\x03\x1b\x12\"\n\n\n\x03\x07\x06\x03\x12\x03\x1b%*\n3\n\x02\x07\x07\
-    \x12\x03\x1d\x041\x1a(\x20Guard\x20serde\x20annotations\x20with\x20cfg\
-    \x20attr.\n\n\n\n\x03\x07\x07\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\
-    \x04\x12\x03\x1d\x04\x0c\n\n\n\x03\x07\x07\x05\x12\x03\x1d\r\x13\n\n\n\
-    \x03\x07\x07\x01\x12\x03\x1d\x14(\n\n\n\x03\x07\x07\x03\x12\x03\x1d+0\nN\
-    \n\x02\x07\x08\x12\x03\x20\x04+\x1aC\x20When\x20true,\x20will\x20only\
+    \nY\n\x02\x07\x04\x12\x03\x16\x04-\x1aN\x20When\x20true\x20all\x20fields\
+    \x20and\x20oneofs\x20are\x20private,\x20and\x20all\x20accessors\x20are\
+    \x20generated\n\n\n\n\x03\x07\x04\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\
+    \x04\x04\x12\x03\x16\x04\x0c\n\n\n\x03\x07\x04\x05\x12\x03\x16\r\x11\n\n\
+    \n\x03\x07\x04\x01\x12\x03\x16\x12$\n\n\n\x03\x07\x04\x03\x12\x03\x16',\
+    \n2\n\x02\x07\x05\x12\x03\x18\x049\x1a'\x20Use\x20`bytes::Bytes`\x20for\
+    \x20`bytes`\x20fields\n\n\n\n\x03\x07\x05\x02\x12\x03\x0c\x07\"\n\n\n\
+    \x03\x07\x05\x04\x12\x03\x18\x04\x0c\n\n\n\x03\x07\x05\x05\x12\x03\x18\r\
+    \x11\n\n\n\x03\x07\x05\x01\x12\x03\x18\x120\n\n\n\x03\x07\x05\x03\x12\
+    \x03\x1838\n3\n\x02\x07\x06\x12\x03\x1a\x04:\x1a(\x20Use\x20`bytes::Byte\
+    s`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\x06\x02\x12\x03\x0c\x07\
+    \"\n\n\n\x03\x07\x06\x04\x12\x03\x1a\x04\x0c\n\n\n\x03\x07\x06\x05\x12\
+    \x03\x1a\r\x11\n\n\n\x03\x07\x06\x01\x12\x03\x1a\x121\n\n\n\x03\x07\x06\
+    \x03\x12\x03\x1a49\n=\n\x02\x07\x07\x12\x03\x1c\x048\x1a2\x20Use\x20`Opt\
+    ion<Box<M>>`\x20for\x20singular\x20message\x20fields\n\n\n\n\x03\x07\x07\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x07\x04\x12\x03\x1c\x04\x0c\n\n\n\
+    \x03\x07\x07\x05\x12\x03\x1c\r\x11\n\n\n\x03\x07\x07\x01\x12\x03\x1c\x12\
+    /\n\n\n\x03\x07\x07\x03\x12\x03\x1c27\n8\n\x02\x07\x08\x12\x03\x1e\x044\
+    \x1a-\x20Use\x20`Option<M>`\x20for\x20singular\x20message\x20fields\n\n\
+    \n\n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x08\x04\x12\x03\x1e\
+    \x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x1e\r\x11\n\n\n\x03\x07\x08\x01\
+    \x12\x03\x1e\x12+\n\n\n\x03\x07\x08\x03\x12\x03\x1e.3\n=\n\x02\x07\t\x12\
+    \x03\x20\x048\x1a2\x20Use\x20`Option<Arc<M>>`\x20for\x20singular\x20mess\
+    age\x20fields\n\n\n\n\x03\x07\t\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\t\
+    \x04\x12\x03\x20\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03\x20\r\x11\n\n\n\x03\
+    \x07\t\x01\x12\x03\x20\x12/\n\n\n\x03\x07\t\x03\x12\x03\x2027\nJ\n\x02\
+    \x07\n\x12\x03#\x04+\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\
+    \x20`Serialize`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\n\x02\x12\x03\
+    \x0c\x07\"\n\n\n\x03\x07\n\x04\x12\x03#\x04\x0c\n\n\n\x03\x07\n\x05\x12\
+    \x03#\r\x11\n\n\n\x03\x07\n\x01\x12\x03#\x12\"\n\n\n\x03\x07\n\x03\x12\
+    \x03#%*\n3\n\x02\x07\x0b\x12\x03%\x041\x1a(\x20Guard\x20serde\x20annotat\
+    ions\x20with\x20cfg\x20attr.\n\n\n\n\x03\x07\x0b\x02\x12\x03\x0c\x07\"\n\
+    \n\n\x03\x07\x0b\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03%\r\
+    \x13\n\n\n\x03\x07\x0b\x01\x12\x03%\x14(\n\n\n\x03\x07\x0b\x03\x12\x03%+\
+    0\nN\n\x02\x07\x0c\x12\x03(\x04+\x1aC\x20When\x20true,\x20will\x20only\
     \x20generate\x20codes\x20that\x20works\x20with\x20lite\x20runtime.\n\n\n\
-    \n\x03\x07\x08\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x08\x04\x12\x03\x20\
-    \x04\x0c\n\n\n\x03\x07\x08\x05\x12\x03\x20\r\x11\n\n\n\x03\x07\x08\x01\
-    \x12\x03\x20\x12\"\n\n\n\x03\x07\x08\x03\x12\x03\x20%*\n\t\n\x01\x07\x12\
-    \x04#\04\x01\n7\n\x02\x07\t\x12\x03%\x04'\x1a,\x20When\x20true,\x20oneof\
-    \x20field\x20is\x20generated\x20public\n\n\n\n\x03\x07\t\x02\x12\x03#\
-    \x07%\n\n\n\x03\x07\t\x04\x12\x03%\x04\x0c\n\n\n\x03\x07\t\x05\x12\x03%\
-    \r\x11\n\n\n\x03\x07\t\x01\x12\x03%\x12\x1e\n\n\n\x03\x07\t\x03\x12\x03%\
-    !&\nI\n\x02\x07\n\x12\x03'\x04(\x1a>\x20When\x20true\x20all\x20fields\
-    \x20are\x20public,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\
-    \x07\n\x02\x12\x03#\x07%\n\n\n\x03\x07\n\x04\x12\x03'\x04\x0c\n\n\n\x03\
-    \x07\n\x05\x12\x03'\r\x11\n\n\n\x03\x07\n\x01\x12\x03'\x12\x1f\n\n\n\x03\
-    \x07\n\x03\x12\x03'\"'\nP\n\x02\x07\x0b\x12\x03)\x04-\x1aE\x20When\x20fa\
-    lse,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\
-    \x20generated\n\n\n\n\x03\x07\x0b\x02\x12\x03#\x07%\n\n\n\x03\x07\x0b\
-    \x04\x12\x03)\x04\x0c\n\n\n\x03\x07\x0b\x05\x12\x03)\r\x11\n\n\n\x03\x07\
-    \x0b\x01\x12\x03)\x12$\n\n\n\x03\x07\x0b\x03\x12\x03)',\nL\n\x02\x07\x0c\
-    \x12\x03+\x04*\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generate\
-    d\x20even\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x0c\x02\
-    \x12\x03#\x07%\n\n\n\x03\x07\x0c\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\x0c\
-    \x05\x12\x03+\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03+\x12!\n\n\n\x03\x07\
-    \x0c\x03\x12\x03+$)\n2\n\x02\x07\r\x12\x03-\x045\x1a'\x20Use\x20`bytes::\
-    Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\x03\x07\r\x02\x12\x03#\x07%\n\
-    \n\n\x03\x07\r\x04\x12\x03-\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03-\r\x11\n\
-    \n\n\x03\x07\r\x01\x12\x03-\x12,\n\n\n\x03\x07\r\x03\x12\x03-/4\n3\n\x02\
-    \x07\x0e\x12\x03/\x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\
-    \x20fields\n\n\n\n\x03\x07\x0e\x02\x12\x03#\x07%\n\n\n\x03\x07\x0e\x04\
-    \x12\x03/\x04\x0c\n\n\n\x03\x07\x0e\x05\x12\x03/\r\x11\n\n\n\x03\x07\x0e\
-    \x01\x12\x03/\x12-\n\n\n\x03\x07\x0e\x03\x12\x03/05\nJ\n\x02\x07\x0f\x12\
-    \x031\x04'\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\x20`Serializ\
-    e`\x20and\x20`Deserialize`\n\n\n\n\x03\x07\x0f\x02\x12\x03#\x07%\n\n\n\
-    \x03\x07\x0f\x04\x12\x031\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x031\r\x11\n\
-    \n\n\x03\x07\x0f\x01\x12\x031\x12\x1e\n\n\n\x03\x07\x0f\x03\x12\x031!&\n\
-    3\n\x02\x07\x10\x12\x033\x04-\x1a(\x20Guard\x20serde\x20annotations\x20w\
-    ith\x20cfg\x20attr.\n\n\n\n\x03\x07\x10\x02\x12\x03#\x07%\n\n\n\x03\x07\
-    \x10\x04\x12\x033\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x033\r\x13\n\n\n\x03\
-    \x07\x10\x01\x12\x033\x14$\n\n\n\x03\x07\x10\x03\x12\x033',\n\t\n\x01\
-    \x07\x12\x046\0A\x01\nI\n\x02\x07\x11\x12\x038\x04.\x1a>\x20When\x20true\
-    \x20all\x20fields\x20are\x20public,\x20and\x20not\x20accessors\x20genera\
-    ted\n\n\n\n\x03\x07\x11\x02\x12\x036\x07#\n\n\n\x03\x07\x11\x04\x12\x038\
-    \x04\x0c\n\n\n\x03\x07\x11\x05\x12\x038\r\x11\n\n\n\x03\x07\x11\x01\x12\
-    \x038\x12%\n\n\n\x03\x07\x11\x03\x12\x038(-\nP\n\x02\x07\x12\x12\x03:\
+    \n\x03\x07\x0c\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0c\x04\x12\x03(\x04\
+    \x0c\n\n\n\x03\x07\x0c\x05\x12\x03(\r\x11\n\n\n\x03\x07\x0c\x01\x12\x03(\
+    \x12\"\n\n\n\x03\x07\x0c\x03\x12\x03(%*\nU\n\x02\x07\r\x12\x03+\x047\x1a\
+    J\x20Store\x20repeated\x20fields\x20in\x20`protobuf::SmallVec`\x20with\
+    \x20given\x20inline\x20capacity\n\n\n\n\x03\x07\r\x02\x12\x03\x0c\x07\"\
+    \n\n\n\x03\x07\r\x04\x12\x03+\x04\x0c\n\n\n\x03\x07\r\x05\x12\x03+\r\x13\
+    \n\n\n\x03\x07\r\x01\x12\x03+\x14.\n\n\n\x03\x07\r\x03\x12\x03+16\nP\n\
+    \x02\x07\x0e\x12\x03-\x049\x1aE\x20Use\x20`protobuf::InternedString`\x20\
+    for\x20repeated\x20and\x20map\x20`string`\x20fields\n\n\n\n\x03\x07\x0e\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0e\x04\x12\x03-\x04\x0c\n\n\n\x03\
+    \x07\x0e\x05\x12\x03-\r\x11\n\n\n\x03\x07\x0e\x01\x12\x03-\x120\n\n\n\
+    \x03\x07\x0e\x03\x12\x03-38\nE\n\x02\x07\x0f\x12\x030\x041\x1a:\x20Deriv\
+    e\x20`Hash`,\x20`Eq`,\x20`PartialOrd`\x20and\x20`Ord`\x20for\x20messages\
+    \n\n\n\n\x03\x07\x0f\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x0f\x04\x12\x03\
+    0\x04\x0c\n\n\n\x03\x07\x0f\x05\x12\x030\r\x11\n\n\n\x03\x07\x0f\x01\x12\
+    \x030\x12(\n\n\n\x03\x07\x0f\x03\x12\x030+0\n\x99\x01\n\x02\x07\x10\x12\
+    \x033\x04A\x1a\x8d\x01\x20Do\x20not\x20derive\x20`Hash`,\x20`Eq`,\x20`Pa\
+    rtialOrd`\x20and\x20`Ord`\x20for\x20messages\n\x20which\x20cannot\x20hav\
+    e\x20them\x20(e.\x20g.\x20with\x20float\x20or\x20map\x20fields)\x20inste\
+    ad\x20of\x20failing\n\n\n\n\x03\x07\x10\x02\x12\x03\x0c\x07\"\n\n\n\x03\
+    \x07\x10\x04\x12\x033\x04\x0c\n\n\n\x03\x07\x10\x05\x12\x033\r\x11\n\n\n\
+    \x03\x07\x10\x01\x12\x033\x128\n\n\n\x03\x07\x10\x03\x12\x033;@\nS\n\x02\
+    \x07\x11\x12\x036\x042\x1aH\x20Implement\x20`TryFrom<&[u8]>`\x20for\x20m\
+    essages\x20and\x20`TryFrom<M>`\x20for\x20`Vec<u8>`\n\n\n\n\x03\x07\x11\
+    \x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x11\x04\x12\x036\x04\x0c\n\n\n\x03\
+    \x07\x11\x05\x12\x036\r\x11\n\n\n\x03\x07\x11\x01\x12\x036\x12)\n\n\n\
+    \x03\x07\x11\x03\x12\x036,1\nX\n\x02\x07\x12\x12\x039\x04-\x1aM\x20Imple\
+    ment\x20`Drop`\x20for\x20messages\x20which\x20drops\x20nested\x20message\
+    s\x20without\x20recursion\n\n\n\n\x03\x07\x12\x02\x12\x03\x0c\x07\"\n\n\
+    \n\x03\x07\x12\x04\x12\x039\x04\x0c\n\n\n\x03\x07\x12\x05\x12\x039\r\x11\
+    \n\n\n\x03\x07\x12\x01\x12\x039\x12$\n\n\n\x03\x07\x12\x03\x12\x039',\nJ\
+    \n\x02\x07\x13\x12\x03<\x04,\x1a?\x20Generate\x20zero-copy\x20read-only\
+    \x20`FooView<'a>`\x20types\x20for\x20messages\n\n\n\n\x03\x07\x13\x02\
+    \x12\x03\x0c\x07\"\n\n\n\x03\x07\x13\x04\x12\x03<\x04\x0c\n\n\n\x03\x07\
+    \x13\x05\x12\x03<\r\x11\n\n\n\x03\x07\x13\x01\x12\x03<\x12#\n\n\n\x03\
+    \x07\x13\x03\x12\x03<&+\nN\n\x02\x07\x14\x12\x03?\x045\x1aC\x20Skip\x20u\
+    nknown\x20fields\x20when\x20parsing\x20messages\x20instead\x20of\x20stor\
+    ing\x20them\n\n\n\n\x03\x07\x14\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x14\
+    \x04\x12\x03?\x04\x0c\n\n\n\x03\x07\x14\x05\x12\x03?\r\x11\n\n\n\x03\x07\
+    \x14\x01\x12\x03?\x12,\n\n\n\x03\x07\x14\x03\x12\x03?/4\n\x83\x01\n\x02\
+    \x07\x15\x12\x03C\x040\x1ax\x20Generate\x20`has_`,\x20`set_`,\x20`clear_\
+    `\x20and\x20`get_..._opt`\x20accessors\n\x20for\x20all\x20singular\x20fi\
+    elds\x20of\x20both\x20proto2\x20and\x20proto3\x20files\n\n\n\n\x03\x07\
+    \x15\x02\x12\x03\x0c\x07\"\n\n\n\x03\x07\x15\x04\x12\x03C\x04\x0c\n\n\n\
+    \x03\x07\x15\x05\x12\x03C\r\x11\n\n\n\x03\x07\x15\x01\x12\x03C\x12'\n\n\
+    \n\x03\x07\x15\x03\x12\x03C*/\n\t\n\x01\x07\x12\x04F\0~\x01\n7\n\x02\x07\
+    \x16\x12\x03H\x04'\x1a,\x20When\x20true,\x20oneof\x20field\x20is\x20gene\
+    rated\x20public\n\n\n\n\x03\x07\x16\x02\x12\x03F\x07%\n\n\n\x03\x07\x16\
+    \x04\x12\x03H\x04\x0c\n\n\n\x03\x07\x16\x05\x12\x03H\r\x11\n\n\n\x03\x07\
+    \x16\x01\x12\x03H\x12\x1e\n\n\n\x03\x07\x16\x03\x12\x03H!&\nI\n\x02\x07\
+    \x17\x12\x03J\x04(\x1a>\x20When\x20true\x20all\x20fields\x20are\x20publi\
+    c,\x20and\x20not\x20accessors\x20generated\n\n\n\n\x03\x07\x17\x02\x12\
+    \x03F\x07%\n\n\n\x03\x07\x17\x04\x12\x03J\x04\x0c\n\n\n\x03\x07\x17\x05\
+    \x12\x03J\r\x11\n\n\n\x03\x07\x17\x01\x12\x03J\x12\x1f\n\n\n\x03\x07\x17\
+    \x03\x12\x03J\"'\nP\n\x02\x07\x18\x12\x03L\x04-\x1aE\x20When\x20false,\
+    \x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20accessors\x20are\x20not\x20g\
+    enerated\n\n\n\n\x03\x07\x18\x02\x12\x03F\x07%\n\n\n\x03\x07\x18\x04\x12\
+    \x03L\x04\x0c\n\n\n\x03\x07\x18\x05\x12\x03L\r\x11\n\n\n\x03\x07\x18\x01\
+    \x12\x03L\x12$\n\n\n\x03\x07\x18\x03\x12\x03L',\nL\n\x02\x07\x19\x12\x03\
+    N\x04*\x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20eve\
+    n\x20if\x20`syntax\x20=\x20\"proto2\"`\n\n\n\n\x03\x07\x19\x02\x12\x03F\
+    \x07%\n\n\n\x03\x07\x19\x04\x12\x03N\x04\x0c\n\n\n\x03\x07\x19\x05\x12\
+    \x03N\r\x11\n\n\n\x03\x07\x19\x01\x12\x03N\x12!\n\n\n\x03\x07\x19\x03\
+    \x12\x03N$)\nY\n\x02\x07\x1a\x12\x03P\x04)\x1aN\x20When\x20true\x20all\
+    \x20fields\x20and\x20oneofs\x20are\x20private,\x20and\x20all\x20accessor\
+    s\x20are\x20generated\n\n\n\n\x03\x07\x1a\x02\x12\x03F\x07%\n\n\n\x03\
+    \x07\x1a\x04\x12\x03P\x04\x0c\n\n\n\x03\x07\x1a\x05\x12\x03P\r\x11\n\n\n\
+    \x03\x07\x1a\x01\x12\x03P\x12\x20\n\n\n\x03\x07\x1a\x03\x12\x03P#(\n2\n\
+    \x02\x07\x1b\x12\x03R\x045\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`byte\
+    s`\x20fields\n\n\n\n\x03\x07\x1b\x02\x12\x03F\x07%\n\n\n\x03\x07\x1b\x04\
+    \x12\x03R\x04\x0c\n\n\n\x03\x07\x1b\x05\x12\x03R\r\x11\n\n\n\x03\x07\x1b\
+    \x01\x12\x03R\x12,\n\n\n\x03\x07\x1b\x03\x12\x03R/4\n3\n\x02\x07\x1c\x12\
+    \x03T\x046\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\
+    \n\n\n\x03\x07\x1c\x02\x12\x03F\x07%\n\n\n\x03\x07\x1c\x04\x12\x03T\x04\
+    \x0c\n\n\n\x03\x07\x1c\x05\x12\x03T\r\x11\n\n\n\x03\x07\x1c\x01\x12\x03T\
+    \x12-\n\n\n\x03\x07\x1c\x03\x12\x03T05\n=\n\x02\x07\x1d\x12\x03V\x044\
+    \x1a2\x20Use\x20`Option<Box<M>>`\x20for\x20singular\x20message\x20fields\
+    \n\n\n\n\x03\x07\x1d\x02\x12\x03F\x07%\n\n\n\x03\x07\x1d\x04\x12\x03V\
+    \x04\x0c\n\n\n\x03\x07\x1d\x05\x12\x03V\r\x11\n\n\n\x03\x07\x1d\x01\x12\
+    \x03V\x12+\n\n\n\x03\x07\x1d\x03\x12\x03V.3\n8\n\x02\x07\x1e\x12\x03X\
+    \x040\x1a-\x20Use\x20`Option<M>`\x20for\x20singular\x20message\x20fields\
+    \n\n\n\n\x03\x07\x1e\x02\x12\x03F\x07%\n\n\n\x03\x07\x1e\x04\x12\x03X\
+    \x04\x0c\n\n\n\x03\x07\x1e\x05\x12\x03X\r\x11\n\n\n\x03\x07\x1e\x01\x12\
+    \x03X\x12'\n\n\n\x03\x07\x1e\x03\x12\x03X*/\n=\n\x02\x07\x1f\x12\x03Z\
+    \x044\x1a2\x20Use\x20`Option<Arc<M>>`\x20for\x20singular\x20message\x20f\
+    ields\n\n\n\n\x03\x07\x1f\x02\x12\x03F\x07%\n\n\n\x03\x07\x1f\x04\x12\
+    \x03Z\x04\x0c\n\n\n\x03\x07\x1f\x05\x12\x03Z\r\x11\n\n\n\x03\x07\x1f\x01\
+    \x12\x03Z\x12+\n\n\n\x03\x07\x1f\x03\x12\x03Z.3\nJ\n\x02\x07\x20\x12\x03\
+    \\\x04'\x1a?\x20Use\x20`serde_derive`\x20to\x20implement\x20`Serialize`\
+    \x20and\x20`Deserialize`\n\n\n\n\x03\x07\x20\x02\x12\x03F\x07%\n\n\n\x03\
+    \x07\x20\x04\x12\x03\\\x04\x0c\n\n\n\x03\x07\x20\x05\x12\x03\\\r\x11\n\n\
+    \n\x03\x07\x20\x01\x12\x03\\\x12\x1e\n\n\n\x03\x07\x20\x03\x12\x03\\!&\n\
+    3\n\x02\x07!\x12\x03^\x04-\x1a(\x20Guard\x20serde\x20annotations\x20with\
+    \x20cfg\x20attr.\n\n\n\n\x03\x07!\x02\x12\x03F\x07%\n\n\n\x03\x07!\x04\
+    \x12\x03^\x04\x0c\n\n\n\x03\x07!\x05\x12\x03^\r\x13\n\n\n\x03\x07!\x01\
+    \x12\x03^\x14$\n\n\n\x03\x07!\x03\x12\x03^',\nU\n\x02\x07\"\x12\x03a\x04\
+    3\x1aJ\x20Store\x20repeated\x20fields\x20in\x20`protobuf::SmallVec`\x20w\
+    ith\x20given\x20inline\x20capacity\n\n\n\n\x03\x07\"\x02\x12\x03F\x07%\n\
+    \n\n\x03\x07\"\x04\x12\x03a\x04\x0c\n\n\n\x03\x07\"\x05\x12\x03a\r\x13\n\
+    \n\n\x03\x07\"\x01\x12\x03a\x14*\n\n\n\x03\x07\"\x03\x12\x03a-2\nP\n\x02\
+    \x07#\x12\x03c\x045\x1aE\x20Use\x20`protobuf::InternedString`\x20for\x20\
+    repeated\x20and\x20map\x20`string`\x20fields\n\n\n\n\x03\x07#\x02\x12\
+    \x03F\x07%\n\n\n\x03\x07#\x04\x12\x03c\x04\x0c\n\n\n\x03\x07#\x05\x12\
+    \x03c\r\x11\n\n\n\x03\x07#\x01\x12\x03c\x12,\n\n\n\x03\x07#\x03\x12\x03c\
+    /4\nD\n\x02\x07$\x12\x03f\x04-\x1a9\x20Derive\x20`Hash`,\x20`Eq`,\x20`Pa\
+    rtialOrd`\x20and\x20`Ord`\x20for\x20message\n\n\n\n\x03\x07$\x02\x12\x03\
+    F\x07%\n\n\n\x03\x07$\x04\x12\x03f\x04\x0c\n\n\n\x03\x07$\x05\x12\x03f\r\
+    \x11\n\n\n\x03\x07$\x01\x12\x03f\x12$\n\n\n\x03\x07$\x03\x12\x03f',\n\
+    \x98\x01\n\x02\x07%\x12\x03i\x04=\x1a\x8c\x01\x20Do\x20not\x20derive\x20\
+    `Hash`,\x20`Eq`,\x20`PartialOrd`\x20and\x20`Ord`\x20for\x20message\n\x20\
+    which\x20cannot\x20have\x20them\x20(e.\x20g.\x20with\x20float\x20or\x20m\
+    ap\x20fields)\x20instead\x20of\x20failing\n\n\n\n\x03\x07%\x02\x12\x03F\
+    \x07%\n\n\n\x03\x07%\x04\x12\x03i\x04\x0c\n\n\n\x03\x07%\x05\x12\x03i\r\
+    \x11\n\n\n\x03\x07%\x01\x12\x03i\x124\n\n\n\x03\x07%\x03\x12\x03i7<\nR\n\
+    \x02\x07&\x12\x03l\x04.\x1aG\x20Implement\x20`TryFrom<&[u8]>`\x20for\x20\
+    message\x20and\x20`TryFrom<M>`\x20for\x20`Vec<u8>`\n\n\n\n\x03\x07&\x02\
+    \x12\x03F\x07%\n\n\n\x03\x07&\x04\x12\x03l\x04\x0c\n\n\n\x03\x07&\x05\
+    \x12\x03l\r\x11\n\n\n\x03\x07&\x01\x12\x03l\x12%\n\n\n\x03\x07&\x03\x12\
+    \x03l(-\nW\n\x02\x07'\x12\x03o\x04)\x1aL\x20Implement\x20`Drop`\x20for\
+    \x20message\x20which\x20drops\x20nested\x20messages\x20without\x20recurs\
+    ion\n\n\n\n\x03\x07'\x02\x12\x03F\x07%\n\n\n\x03\x07'\x04\x12\x03o\x04\
+    \x0c\n\n\n\x03\x07'\x05\x12\x03o\r\x11\n\n\n\x03\x07'\x01\x12\x03o\x12\
+    \x20\n\n\n\x03\x07'\x03\x12\x03o#(\nH\n\x02\x07(\x12\x03r\x04(\x1a=\x20G\
+    enerate\x20zero-copy\x20read-only\x20`FooView<'a>`\x20type\x20for\x20mes\
+    sage\n\n\n\n\x03\x07(\x02\x12\x03F\x07%\n\n\n\x03\x07(\x04\x12\x03r\x04\
+    \x0c\n\n\n\x03\x07(\x05\x12\x03r\r\x11\n\n\n\x03\x07(\x01\x12\x03r\x12\
+    \x1f\n\n\n\x03\x07(\x03\x12\x03r\"'\n_\n\x02\x07)\x12\x03v\x04%\x1aT\x20\
+    Maximum\x20serialized\x20size\x20of\x20message\x20in\x20bytes,\n\x20chec\
+    ked\x20when\x20parsing\x20and\x20serializing\n\n\n\n\x03\x07)\x02\x12\
+    \x03F\x07%\n\n\n\x03\x07)\x04\x12\x03v\x04\x0c\n\n\n\x03\x07)\x05\x12\
+    \x03v\r\x13\n\n\n\x03\x07)\x01\x12\x03v\x14\x1c\n\n\n\x03\x07)\x03\x12\
+    \x03v\x1f$\nM\n\x02\x07*\x12\x03y\x041\x1aB\x20Skip\x20unknown\x20fields\
+    \x20when\x20parsing\x20message\x20instead\x20of\x20storing\x20them\n\n\n\
+    \n\x03\x07*\x02\x12\x03F\x07%\n\n\n\x03\x07*\x04\x12\x03y\x04\x0c\n\n\n\
+    \x03\x07*\x05\x12\x03y\r\x11\n\n\n\x03\x07*\x01\x12\x03y\x12(\n\n\n\x03\
+    \x07*\x03\x12\x03y+0\nn\n\x02\x07+\x12\x03}\x04,\x1ac\x20Generate\x20`ha\
+    s_`,\x20`set_`,\x20`clear_`\x20and\x20`get_..._opt`\x20accessors\n\x20fo\
+    r\x20all\x20singular\x20fields\x20of\x20message\n\n\n\n\x03\x07+\x02\x12\
+    \x03F\x07%\n\n\n\x03\x07+\x04\x12\x03}\x04\x0c\n\n\n\x03\x07+\x05\x12\
+    \x03}\r\x11\n\n\n\x03\x07+\x01\x12\x03}\x12#\n\n\n\x03\x07+\x03\x12\x03}\
+    &+\n\x0b\n\x01\x07\x12\x06\x80\x01\0\xb2\x01\x01\nJ\n\x02\x07,\x12\x04\
+    \x82\x01\x04.\x1a>\x20When\x20true\x20all\x20fields\x20are\x20public,\
+    \x20and\x20not\x20accessors\x20generated\n\n\x0b\n\x03\x07,\x02\x12\x04\
+    \x80\x01\x07#\n\x0b\n\x03\x07,\x04\x12\x04\x82\x01\x04\x0c\n\x0b\n\x03\
+    \x07,\x05\x12\x04\x82\x01\r\x11\n\x0b\n\x03\x07,\x01\x12\x04\x82\x01\x12\
+    %\n\x0b\n\x03\x07,\x03\x12\x04\x82\x01(-\nQ\n\x02\x07-\x12\x04\x84\x01\
     \x043\x1aE\x20When\x20false,\x20`get_`,\x20`set_`,\x20`mut_`\x20etc.\x20\
-    accessors\x20are\x20not\x20generated\n\n\n\n\x03\x07\x12\x02\x12\x036\
-    \x07#\n\n\n\x03\x07\x12\x04\x12\x03:\x04\x0c\n\n\n\x03\x07\x12\x05\x12\
-    \x03:\r\x11\n\n\n\x03\x07\x12\x01\x12\x03:\x12*\n\n\n\x03\x07\x12\x03\
-    \x12\x03:-2\nL\n\x02\x07\x13\x12\x03<\x040\x1aA\x20When\x20false,\x20`ge\
-    t_`\x20is\x20not\x20generated\x20even\x20if\x20`syntax\x20=\x20\"proto2\
-    \"`\n\n\n\n\x03\x07\x13\x02\x12\x036\x07#\n\n\n\x03\x07\x13\x04\x12\x03<\
-    \x04\x0c\n\n\n\x03\x07\x13\x05\x12\x03<\r\x11\n\n\n\x03\x07\x13\x01\x12\
-    \x03<\x12'\n\n\n\x03\x07\x13\x03\x12\x03<*/\n2\n\x02\x07\x14\x12\x03>\
-    \x04;\x1a'\x20Use\x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\n\n\
-    \x03\x07\x14\x02\x12\x036\x07#\n\n\n\x03\x07\x14\x04\x12\x03>\x04\x0c\n\
-    \n\n\x03\x07\x14\x05\x12\x03>\r\x11\n\n\n\x03\x07\x14\x01\x12\x03>\x122\
-    \n\n\n\x03\x07\x14\x03\x12\x03>5:\n3\n\x02\x07\x15\x12\x03@\x04<\x1a(\
-    \x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\n\n\n\n\x03\x07\
-    \x15\x02\x12\x036\x07#\n\n\n\x03\x07\x15\x04\x12\x03@\x04\x0c\n\n\n\x03\
-    \x07\x15\x05\x12\x03@\r\x11\n\n\n\x03\x07\x15\x01\x12\x03@\x123\n\n\n\
-    \x03\x07\x15\x03\x12\x03@6;\
+    accessors\x20are\x20not\x20generated\n\n\x0b\n\x03\x07-\x02\x12\x04\x80\
+    \x01\x07#\n\x0b\n\x03\x07-\x04\x12\x04\x84\x01\x04\x0c\n\x0b\n\x03\x07-\
+    \x05\x12\x04\x84\x01\r\x11\n\x0b\n\x03\x07-\x01\x12\x04\x84\x01\x12*\n\
+    \x0b\n\x03\x07-\x03\x12\x04\x84\x01-2\nM\n\x02\x07.\x12\x04\x86\x01\x040\
+    \x1aA\x20When\x20false,\x20`get_`\x20is\x20not\x20generated\x20even\x20i\
+    f\x20`syntax\x20=\x20\"proto2\"`\n\n\x0b\n\x03\x07.\x02\x12\x04\x80\x01\
+    \x07#\n\x0b\n\x03\x07.\x04\x12\x04\x86\x01\x04\x0c\n\x0b\n\x03\x07.\x05\
+    \x12\x04\x86\x01\r\x11\n\x0b\n\x03\x07.\x01\x12\x04\x86\x01\x12'\n\x0b\n\
+    \x03\x07.\x03\x12\x04\x86\x01*/\nI\n\x02\x07/\x12\x04\x88\x01\x04/\x1a=\
+    \x20When\x20true\x20field\x20is\x20private,\x20and\x20all\x20accessors\
+    \x20are\x20generated\n\n\x0b\n\x03\x07/\x02\x12\x04\x80\x01\x07#\n\x0b\n\
+    \x03\x07/\x04\x12\x04\x88\x01\x04\x0c\n\x0b\n\x03\x07/\x05\x12\x04\x88\
+    \x01\r\x11\n\x0b\n\x03\x07/\x01\x12\x04\x88\x01\x12&\n\x0b\n\x03\x07/\
+    \x03\x12\x04\x88\x01).\n3\n\x02\x070\x12\x04\x8a\x01\x04;\x1a'\x20Use\
+    \x20`bytes::Bytes`\x20for\x20`bytes`\x20fields\n\n\x0b\n\x03\x070\x02\
+    \x12\x04\x80\x01\x07#\n\x0b\n\x03\x070\x04\x12\x04\x8a\x01\x04\x0c\n\x0b\
+    \n\x03\x070\x05\x12\x04\x8a\x01\r\x11\n\x0b\n\x03\x070\x01\x12\x04\x8a\
+    \x01\x122\n\x0b\n\x03\x070\x03\x12\x04\x8a\x015:\n4\n\x02\x071\x12\x04\
+    \x8c\x01\x04<\x1a(\x20Use\x20`bytes::Bytes`\x20for\x20`string`\x20fields\
+    \n\n\x0b\n\x03\x071\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x071\x04\x12\
+    \x04\x8c\x01\x04\x0c\n\x0b\n\x03\x071\x05\x12\x04\x8c\x01\r\x11\n\x0b\n\
+    \x03\x071\x01\x12\x04\x8c\x01\x123\n\x0b\n\x03\x071\x03\x12\x04\x8c\x016\
+    ;\n=\n\x02\x072\x12\x04\x8e\x01\x04:\x1a1\x20Use\x20`Option<Box<M>>`\x20\
+    for\x20singular\x20message\x20field\n\n\x0b\n\x03\x072\x02\x12\x04\x80\
+    \x01\x07#\n\x0b\n\x03\x072\x04\x12\x04\x8e\x01\x04\x0c\n\x0b\n\x03\x072\
+    \x05\x12\x04\x8e\x01\r\x11\n\x0b\n\x03\x072\x01\x12\x04\x8e\x01\x121\n\
+    \x0b\n\x03\x072\x03\x12\x04\x8e\x0149\n8\n\x02\x073\x12\x04\x90\x01\x046\
+    \x1a,\x20Use\x20`Option<M>`\x20for\x20singular\x20message\x20field\n\n\
+    \x0b\n\x03\x073\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x073\x04\x12\x04\
+    \x90\x01\x04\x0c\n\x0b\n\x03\x073\x05\x12\x04\x90\x01\r\x11\n\x0b\n\x03\
+    \x073\x01\x12\x04\x90\x01\x12-\n\x0b\n\x03\x073\x03\x12\x04\x90\x0105\n=\
+    \n\x02\x074\x12\x04\x92\x01\x04:\x1a1\x20Use\x20`Option<Arc<M>>`\x20for\
+    \x20singular\x20message\x20field\n\n\x0b\n\x03\x074\x02\x12\x04\x80\x01\
+    \x07#\n\x0b\n\x03\x074\x04\x12\x04\x92\x01\x04\x0c\n\x0b\n\x03\x074\x05\
+    \x12\x04\x92\x01\r\x11\n\x0b\n\x03\x074\x01\x12\x04\x92\x01\x121\n\x0b\n\
+    \x03\x074\x03\x12\x04\x92\x0149\nU\n\x02\x075\x12\x04\x95\x01\x049\x1aI\
+    \x20Store\x20repeated\x20field\x20in\x20`protobuf::SmallVec`\x20with\x20\
+    given\x20inline\x20capacity\n\n\x0b\n\x03\x075\x02\x12\x04\x80\x01\x07#\
+    \n\x0b\n\x03\x075\x04\x12\x04\x95\x01\x04\x0c\n\x0b\n\x03\x075\x05\x12\
+    \x04\x95\x01\r\x13\n\x0b\n\x03\x075\x01\x12\x04\x95\x01\x140\n\x0b\n\x03\
+    \x075\x03\x12\x04\x95\x0138\nP\n\x02\x076\x12\x04\x97\x01\x04;\x1aD\x20U\
+    se\x20`protobuf::InternedString`\x20for\x20repeated\x20and\x20map\x20`st\
+    ring`\x20field\n\n\x0b\n\x03\x076\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\
+    \x076\x04\x12\x04\x97\x01\x04\x0c\n\x0b\n\x03\x076\x05\x12\x04\x97\x01\r\
+    \x11\n\x0b\n\x03\x076\x01\x12\x04\x97\x01\x122\n\x0b\n\x03\x076\x03\x12\
+    \x04\x97\x015:\n^\n\x02\x077\x12\x04\x9a\x01\x042\x1aR\x20Generate\x20`h\
+    as_`,\x20`set_`,\x20`clear_`\x20and\x20`get_..._opt`\x20accessors\x20for\
+    \x20singular\x20field\n\n\x0b\n\x03\x077\x02\x12\x04\x80\x01\x07#\n\x0b\
+    \n\x03\x077\x04\x12\x04\x9a\x01\x04\x0c\n\x0b\n\x03\x077\x05\x12\x04\x9a\
+    \x01\r\x11\n\x0b\n\x03\x077\x01\x12\x04\x9a\x01\x12)\n\x0b\n\x03\x077\
+    \x03\x12\x04\x9a\x01,1\n\x98\x01\n\x02\x078\x12\x04\x9f\x01\x04)\x1aO\
+    \x20Number\x20field\x20(or\x20each\x20element\x20of\x20repeated\x20field\
+    )\x20is\x20not\x20less\x20than\x20given\x20value\n2;\x20Validation\x20ru\
+    les\x20checked\x20by\x20generated\x20`Message::validate`\n\n\x0b\n\x03\
+    \x078\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x078\x04\x12\x04\x9f\x01\x04\
+    \x0c\n\x0b\n\x03\x078\x05\x12\x04\x9f\x01\r\x13\n\x0b\n\x03\x078\x01\x12\
+    \x04\x9f\x01\x14\x20\n\x0b\n\x03\x078\x03\x12\x04\x9f\x01#(\n^\n\x02\x07\
+    9\x12\x04\xa1\x01\x04)\x1aR\x20Number\x20field\x20(or\x20each\x20element\
+    \x20of\x20repeated\x20field)\x20is\x20not\x20greater\x20than\x20given\
+    \x20value\n\n\x0b\n\x03\x079\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x079\
+    \x04\x12\x04\xa1\x01\x04\x0c\n\x0b\n\x03\x079\x05\x12\x04\xa1\x01\r\x13\
+    \n\x0b\n\x03\x079\x01\x12\x04\xa1\x01\x14\x20\n\x0b\n\x03\x079\x03\x12\
+    \x04\xa1\x01#(\nN\n\x02\x07:\x12\x04\xa3\x01\x04-\x1aB\x20Minimum\x20len\
+    gth\x20in\x20chars\x20of\x20`string`\x20or\x20in\x20bytes\x20of\x20`byte\
+    s`\x20field\n\n\x0b\n\x03\x07:\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x07:\
+    \x04\x12\x04\xa3\x01\x04\x0c\n\x0b\n\x03\x07:\x05\x12\x04\xa3\x01\r\x13\
+    \n\x0b\n\x03\x07:\x01\x12\x04\xa3\x01\x14$\n\x0b\n\x03\x07:\x03\x12\x04\
+    \xa3\x01',\nN\n\x02\x07;\x12\x04\xa5\x01\x04-\x1aB\x20Maximum\x20length\
+    \x20in\x20chars\x20of\x20`string`\x20or\x20in\x20bytes\x20of\x20`bytes`\
+    \x20field\n\n\x0b\n\x03\x07;\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x07;\
+    \x04\x12\x04\xa5\x01\x04\x0c\n\x0b\n\x03\x07;\x05\x12\x04\xa5\x01\r\x13\
+    \n\x0b\n\x03\x07;\x01\x12\x04\xa5\x01\x14$\n\x0b\n\x03\x07;\x03\x12\x04\
+    \xa5\x01',\nV\n\x02\x07<\x12\x04\xa7\x01\x04-\x1aJ\x20`string`\x20field\
+    \x20matches\x20regular\x20expression,\x20requires\x20`with-regex`\x20fea\
+    ture\n\n\x0b\n\x03\x07<\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x07<\x04\
+    \x12\x04\xa7\x01\x04\x0c\n\x0b\n\x03\x07<\x05\x12\x04\xa7\x01\r\x13\n\
+    \x0b\n\x03\x07<\x01\x12\x04\xa7\x01\x14$\n\x0b\n\x03\x07<\x03\x12\x04\
+    \xa7\x01',\nC\n\x02\x07=\x12\x04\xa9\x01\x04,\x1a7\x20Field\x20with\x20p\
+    resence\x20(e.\x20g.\x20message\x20field)\x20must\x20be\x20set\n\n\x0b\n\
+    \x03\x07=\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x07=\x04\x12\x04\xa9\x01\
+    \x04\x0c\n\x0b\n\x03\x07=\x05\x12\x04\xa9\x01\r\x11\n\x0b\n\x03\x07=\x01\
+    \x12\x04\xa9\x01\x12#\n\x0b\n\x03\x07=\x03\x12\x04\xa9\x01&+\nA\n\x02\
+    \x07>\x12\x04\xab\x01\x04/\x1a5\x20Minimum\x20number\x20of\x20elements\
+    \x20of\x20repeated\x20or\x20map\x20field\n\n\x0b\n\x03\x07>\x02\x12\x04\
+    \x80\x01\x07#\n\x0b\n\x03\x07>\x04\x12\x04\xab\x01\x04\x0c\n\x0b\n\x03\
+    \x07>\x05\x12\x04\xab\x01\r\x13\n\x0b\n\x03\x07>\x01\x12\x04\xab\x01\x14\
+    &\n\x0b\n\x03\x07>\x03\x12\x04\xab\x01).\nA\n\x02\x07?\x12\x04\xad\x01\
+    \x04/\x1a5\x20Maximum\x20number\x20of\x20elements\x20of\x20repeated\x20o\
+    r\x20map\x20field\n\n\x0b\n\x03\x07?\x02\x12\x04\x80\x01\x07#\n\x0b\n\
+    \x03\x07?\x04\x12\x04\xad\x01\x04\x0c\n\x0b\n\x03\x07?\x05\x12\x04\xad\
+    \x01\r\x13\n\x0b\n\x03\x07?\x01\x12\x04\xad\x01\x14&\n\x0b\n\x03\x07?\
+    \x03\x12\x04\xad\x01).\n\x98\x01\n\x02\x07@\x12\x04\xb1\x01\x04$\x1a\x8b\
+    \x01\x20Replace\x20field\x20value\x20with\x20`[REDACTED]`\x20in\x20`Debu\
+    g`,\x20text\x20format\x20and\x20JSON\x20output,\n\x20same\x20as\x20`debu\
+    g_redact`\x20option\x20of\x20newer\x20`descriptor.proto`\n\n\x0b\n\x03\
+    \x07@\x02\x12\x04\x80\x01\x07#\n\x0b\n\x03\x07@\x04\x12\x04\xb1\x01\x04\
+    \x0c\n\x0b\n\x03\x07@\x05\x12\x04\xb1\x01\r\x11\n\x0b\n\x03\x07@\x01\x12\
+    \x04\xb1\x01\x12\x1b\n\x0b\n\x03\x07@\x03\x12\x04\xb1\x01\x1e#\
 ";
 
 /// `FileDescriptorProto` object which was a source for this generated file
@@ -236,3 +663,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
@@ -0,0 +1,108 @@
+//! Thread-local buffer for `write_to_bytes_pooled` functions.
+
+use std::cell::RefCell;
+
+use bytes::Bytes;
+use bytes::BytesMut;
+
+use crate::CodedOutputStream;
+use crate::ProtobufResult;
+
+thread_local! {
+    static BUFFER: RefCell<BytesMut> = RefCell::new(BytesMut::new());
+}
+
+/// Write exactly `size` bytes with `write` into the thread-local buffer
+/// and split the written bytes off as `Bytes`.
+///
+/// The buffer keeps its allocation: the next write reuses it
+/// when all previously returned `Bytes` objects are dropped,
+/// so steady-state serialization does not allocate.
+pub(crate) fn write_pooled<F>(size: usize, write: F) -> ProtobufResult<Bytes>
+where
+    F: FnOnce(&mut CodedOutputStream) -> ProtobufResult<()>,
+{
+    let mut write = Some(write);
+    let pooled = BUFFER.try_with(|buffer| match buffer.try_borrow_mut() {
+        Ok(mut buffer) => Some(write_to_buffer(&mut buffer, size, write.take().unwrap())),
+        // Reentrant call, e. g. from serialize hook
+        Err(_) => None,
+    });
+    match pooled {
+        Ok(Some(r)) => r,
+        // Thread-local is being destroyed or already borrowed
+        _ => write_to_buffer(&mut BytesMut::new(), size, write.take().unwrap()),
+    }
+}
+
+fn write_to_buffer<F>(buffer: &mut BytesMut, size: usize, write: F) -> ProtobufResult<Bytes>
+where
+    F: FnOnce(&mut CodedOutputStream) -> ProtobufResult<()>,
+{
+    buffer.clear();
+    buffer.reserve(size);
+    // skip zerofill
+    unsafe {
+        buffer.set_len(size);
+    }
+    let r = {
+        let mut os = CodedOutputStream::bytes(&mut buffer[..]);
+        write(&mut os).map(|()| os.check_eof())
+    };
+    match r {
+        Ok(()) => Ok(buffer.split_to(size).freeze()),
+        Err(e) => {
+            buffer.clear();
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::descriptor::FileDescriptorProto;
+    use crate::Message;
+    use crate::MessageDyn;
+
+    fn file(name: &str) -> FileDescriptorProto {
+        let mut file = FileDescriptorProto::new();
+        file.set_name(name.to_owned());
+        file.set_package("some.package".to_owned());
+        file
+    }
+
+    #[test]
+    fn same_as_write_to_bytes() {
+        let a = file("a.proto");
+        let b = file("bb.proto");
+        let a_bytes = a.write_to_bytes_pooled().unwrap();
+        let b_bytes = b.write_to_bytes_pooled().unwrap();
+        let a_dyn_bytes = (&a as &dyn MessageDyn).write_to_bytes_pooled_dyn().unwrap();
+        // Earlier results are not affected by later writes
+        assert_eq!(a.write_to_bytes().unwrap(), &a_bytes[..]);
+        assert_eq!(b.write_to_bytes().unwrap(), &b_bytes[..]);
+        assert_eq!(a_bytes, a_dyn_bytes);
+    }
+
+    #[test]
+    fn reuse_allocation() {
+        let a = file("a.proto");
+        let first = a.write_to_bytes_pooled().unwrap();
+        let ptr = first.as_ptr();
+        drop(first);
+        let second = a.write_to_bytes_pooled().unwrap();
+        assert_eq!(ptr, second.as_ptr());
+    }
+
+    #[test]
+    fn uninitialized() {
+        use crate::descriptor::uninterpreted_option::NamePart;
+        assert!(NamePart::new().write_to_bytes_pooled().is_err());
+        // Buffer is still usable after error
+        let a = file("a.proto");
+        assert_eq!(
+            a.write_to_bytes().unwrap(),
+            &a.write_to_bytes_pooled().unwrap()[..]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Release of memory retained by messages.
+//!
+//! See [`Message::shrink_to_fit`](crate::Message::shrink_to_fit).
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::hash::Hash;
+use std::sync::Arc;
+
+#[cfg(feature = "bytes")]
+use crate::bytes::Bytes;
+#[cfg(feature = "bytes")]
+use crate::chars::Chars;
+use crate::string_interner::InternedString;
+use crate::MessageField;
+use crate::ProtobufEnumOrUnknown;
+use crate::SmallVec;
+
+/// Release unused capacity of a message field value.
+///
+/// Implemented for types used in generated messages,
+/// generated messages implement it by delegating to
+/// [`Message::shrink_to_fit`](crate::Message::shrink_to_fit).
+pub trait ShrinkToFit {
+    /// Shrink capacity of this value and nested values as much as possible.
+    fn shrink_to_fit(&mut self);
+}
+
+macro_rules! impl_shrink_to_fit_noop {
+    ($($t:ty)*) => {
+        $(
+            impl ShrinkToFit for $t {
+                fn shrink_to_fit(&mut self) {}
+            }
+        )*
+    }
+}
+
+impl_shrink_to_fit_noop!(u8 u32 u64 i32 i64 f32 f64 bool InternedString);
+
+#[cfg(feature = "bytes")]
+impl_shrink_to_fit_noop!(Bytes Chars);
+
+impl<E> ShrinkToFit for ProtobufEnumOrUnknown<E> {
+    fn shrink_to_fit(&mut self) {}
+}
+
+impl ShrinkToFit for String {
+    fn shrink_to_fit(&mut self) {
+        String::shrink_to_fit(self);
+    }
+}
+
+impl<T: ShrinkToFit> ShrinkToFit for Vec<T> {
+    fn shrink_to_fit(&mut self) {
+        Vec::shrink_to_fit(self);
+        self.iter_mut().for_each(T::shrink_to_fit);
+    }
+}
+
+impl<T: ShrinkToFit, const N: usize> ShrinkToFit for SmallVec<T, N> {
+    fn shrink_to_fit(&mut self) {
+        SmallVec::shrink_to_fit(self);
+        self.iter_mut().for_each(T::shrink_to_fit);
+    }
+}
+
+impl<T: ShrinkToFit> ShrinkToFit for Option<T> {
+    fn shrink_to_fit(&mut self) {
+        if let Some(v) = self {
+            v.shrink_to_fit();
+        }
+    }
+}
+
+impl<T: ShrinkToFit> ShrinkToFit for Box<T> {
+    fn shrink_to_fit(&mut self) {
+        (**self).shrink_to_fit();
+    }
+}
+
+/// Shared values are not modified.
+impl<T: ShrinkToFit> ShrinkToFit for Arc<T> {
+    fn shrink_to_fit(&mut self) {
+        if let Some(v) = Arc::get_mut(self) {
+            v.shrink_to_fit();
+        }
+    }
+}
+
+impl<T: ShrinkToFit> ShrinkToFit for MessageField<T> {
+    fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+}
+
+/// Keys are not modified: map keys are scalars or short strings.
+impl<K: Eq + Hash, V: ShrinkToFit, S: BuildHasher> ShrinkToFit for HashMap<K, V, S> {
+    fn shrink_to_fit(&mut self) {
+        HashMap::shrink_to_fit(self);
+        self.values_mut().for_each(V::shrink_to_fit);
+    }
+}
+
+/// Shrink capacity of a field value.
+pub fn shrink_to_fit<T: ShrinkToFit + ?Sized>(value: &mut T) {
+    value.shrink_to_fit();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::space_used::space_used_excluding_self;
+
+    #[test]
+    fn vec() {
+        let mut v: Vec<String> = Vec::with_capacity(10);
+        v.push(String::with_capacity(100));
+        v[0].push_str("ab");
+        shrink_to_fit(&mut v);
+        assert_eq!(1, v.capacity());
+        assert_eq!(2, v[0].capacity());
+    }
+
+    #[test]
+    fn map() {
+        let mut m: HashMap<u32, Vec<u32>> = HashMap::with_capacity(100);
+        m.insert(1, Vec::with_capacity(100));
+        let before = space_used_excluding_self(&m);
+        shrink_to_fit(&mut m);
+        assert!(space_used_excluding_self(&m) < before);
+        assert_eq!(0, m[&1].capacity());
+    }
+
+    #[test]
+    fn small_vec() {
+        let mut v: SmallVec<u32, 2> = vec![1, 2, 3].into_iter().collect();
+        v.truncate(1);
+        assert!(v.spilled());
+        shrink_to_fit(&mut v);
+        assert!(!v.spilled());
+        assert_eq!(&[1], &v[..]);
+    }
+}
@@ -0,0 +1,599 @@
+#[cfg(feature = "with-serde")]
+use serde;
+
+use std::cmp;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::iter::FromIterator;
+use std::mem;
+use std::mem::MaybeUninit;
+use std::ops::Bound;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::ops::RangeBounds;
+use std::ptr;
+use std::slice;
+use std::vec;
+
+use crate::clear::Clear;
+
+enum Storage<T, const N: usize> {
+    // number of initialized elements and the buffer
+    Inline(usize, MaybeUninit<[T; N]>),
+    Heap(Vec<T>),
+}
+
+/// Vector which stores up to `N` elements inline, without heap allocation.
+///
+/// When more than `N` elements are pushed, elements are moved to a `Vec`.
+///
+/// This type is used to store repeated fields when code generated with
+/// `small_vec_for_repeated` option.
+///
+/// ```
+/// # use protobuf::SmallVec;
+/// let mut v = SmallVec::<u32, 2>::new();
+/// v.push(10);
+/// v.push(20);
+/// assert!(!v.spilled());
+/// v.push(30);
+/// assert!(v.spilled());
+/// assert_eq!(&[10, 20, 30], &v[..]);
+/// ```
+pub struct SmallVec<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+impl<T, const N: usize> SmallVec<T, N> {
+    /// Create an empty vector.
+    #[inline]
+    pub const fn new() -> SmallVec<T, N> {
+        SmallVec {
+            storage: Storage::Inline(0, MaybeUninit::uninit()),
+        }
+    }
+
+    /// True if elements are stored on heap.
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        match self.storage {
+            Storage::Inline(..) => false,
+            Storage::Heap(..) => true,
+        }
+    }
+
+    /// Number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self.storage {
+            Storage::Inline(len, _) => len,
+            Storage::Heap(ref vec) => vec.len(),
+        }
+    }
+
+    /// True if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of elements vector can hold without reallocation.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match self.storage {
+            Storage::Inline(..) => N,
+            Storage::Heap(ref vec) => vec.capacity(),
+        }
+    }
+
+    /// Elements as slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        match self.storage {
+            Storage::Inline(len, ref buf) => unsafe {
+                slice::from_raw_parts(buf.as_ptr() as *const T, len)
+            },
+            Storage::Heap(ref vec) => vec,
+        }
+    }
+
+    /// Elements as mutable slice.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match self.storage {
+            Storage::Inline(len, ref mut buf) => unsafe {
+                slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut T, len)
+            },
+            Storage::Heap(ref mut vec) => vec,
+        }
+    }
+
+    // Move inline elements to a vec with at least given capacity.
+    fn spill(&mut self, capacity: usize) {
+        if let Storage::Inline(ref mut len, ref buf) = self.storage {
+            let mut vec = Vec::with_capacity(cmp::max(capacity, *len));
+            unsafe {
+                ptr::copy_nonoverlapping(buf.as_ptr() as *const T, vec.as_mut_ptr(), *len);
+                vec.set_len(*len);
+            }
+            // elements are now owned by `vec`
+            *len = 0;
+            self.storage = Storage::Heap(vec);
+        }
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize) {
+        match self.storage {
+            Storage::Inline(len, _) => {
+                if len + additional > N {
+                    self.spill(len + additional);
+                }
+            }
+            Storage::Heap(ref mut vec) => vec.reserve(additional),
+        }
+    }
+
+    /// Release unused heap capacity.
+    ///
+    /// Elements are moved back inline if they fit.
+    pub fn shrink_to_fit(&mut self) {
+        if let Storage::Heap(ref mut vec) = self.storage {
+            if vec.len() <= N {
+                let vec = mem::take(vec);
+                let mut inline = SmallVec::new();
+                for value in vec {
+                    inline.push(value);
+                }
+                *self = inline;
+            } else {
+                vec.shrink_to_fit();
+            }
+        }
+    }
+
+    /// Append an element.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        match self.storage {
+            Storage::Inline(ref mut len, ref mut buf) if *len < N => {
+                unsafe {
+                    (buf.as_mut_ptr() as *mut T).add(*len).write(value);
+                }
+                *len += 1;
+            }
+            Storage::Inline(len, _) => {
+                self.spill(cmp::max(len * 2, 4));
+                self.push(value);
+            }
+            Storage::Heap(ref mut vec) => vec.push(value),
+        }
+    }
+
+    /// Remove the last element.
+    pub fn pop(&mut self) -> Option<T> {
+        match self.storage {
+            Storage::Inline(ref mut len, ref buf) => {
+                if *len == 0 {
+                    None
+                } else {
+                    *len -= 1;
+                    Some(unsafe { (buf.as_ptr() as *const T).add(*len).read() })
+                }
+            }
+            Storage::Heap(ref mut vec) => vec.pop(),
+        }
+    }
+
+    /// Shorten the vector, keeping first `len` elements.
+    ///
+    /// Heap storage is not released.
+    pub fn truncate(&mut self, new_len: usize) {
+        match self.storage {
+            Storage::Inline(ref mut len, ref mut buf) => {
+                if new_len < *len {
+                    let old_len = *len;
+                    // set length first in case `drop` panics
+                    *len = new_len;
+                    unsafe {
+                        ptr::drop_in_place(slice::from_raw_parts_mut(
+                            (buf.as_mut_ptr() as *mut T).add(new_len),
+                            old_len - new_len,
+                        ));
+                    }
+                }
+            }
+            Storage::Heap(ref mut vec) => vec.truncate(new_len),
+        }
+    }
+
+    /// Remove all elements.
+    ///
+    /// Heap storage is not released.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Insert an element at position `index`, shifting all elements after it to the right.
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(index <= self.len(), "insertion index is out of bounds");
+        self.push(value);
+        self[index..].rotate_right(1);
+    }
+
+    /// Remove and return the element at position `index`,
+    /// shifting all elements after it to the left.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        assert!(index < self.len(), "removal index is out of bounds");
+        self[index..].rotate_left(1);
+        self.pop().unwrap()
+    }
+
+    /// Remove an element at position `index` and replace it with the last element.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(index < len, "swap_remove index is out of bounds");
+        self.swap(index, len - 1);
+        self.pop().unwrap()
+    }
+
+    /// Retain only the elements specified by the predicate, preserving the order.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let mut deleted = 0;
+        for i in 0..len {
+            if !f(&self[i]) {
+                deleted += 1;
+            } else if deleted > 0 {
+                self.swap(i - deleted, i);
+            }
+        }
+        self.truncate(len - deleted);
+    }
+
+    /// Remove the specified range from the vector, returning removed elements.
+    ///
+    /// Unlike `Vec::drain`, elements are removed eagerly.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn drain<R>(&mut self, range: R) -> vec::IntoIter<T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.checked_add(1).expect("range start overflow"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.checked_add(1).expect("range end overflow"),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => len,
+        };
+        assert!(start <= end, "drain range start is greater than end");
+        assert!(end <= len, "drain range end is out of bounds");
+
+        // move drained elements to the tail
+        self[start..].rotate_left(end - start);
+        let mut drained = Vec::with_capacity(end - start);
+        for _ in start..end {
+            drained.push(self.pop().unwrap());
+        }
+        drained.reverse();
+        drained.into_iter()
+    }
+
+    /// Append all elements of a slice.
+    pub fn extend_from_slice(&mut self, values: &[T])
+    where
+        T: Clone,
+    {
+        self.extend(values.iter().cloned());
+    }
+
+    /// Convert into `Vec`.
+    pub fn into_vec(mut self) -> Vec<T> {
+        self.spill(0);
+        match mem::replace(&mut self.storage, Storage::Inline(0, MaybeUninit::uninit())) {
+            Storage::Heap(vec) => vec,
+            Storage::Inline(..) => unreachable!(),
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for SmallVec<T, N> {
+    fn drop(&mut self) {
+        if let Storage::Inline(..) = self.storage {
+            self.clear();
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallVec<T, N> {
+    #[inline]
+    fn default() -> Self {
+        SmallVec::new()
+    }
+}
+
+impl<T, const N: usize> Deref for SmallVec<T, N> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallVec<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for SmallVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const N: usize> Clear for SmallVec<T, N> {
+    fn clear(&mut self) {
+        SmallVec::clear(self)
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for SmallVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T]> for SmallVec<T, N> {
+    fn eq(&self, other: &[T]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<Vec<T>> for SmallVec<T, N> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.as_slice() == &other[..]
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for SmallVec<T, N> {}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for SmallVec<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for SmallVec<T, N> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: Hash, const N: usize> Hash for SmallVec<T, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, const N: usize> Extend<&'a T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut r = SmallVec::new();
+        r.extend(iter);
+        r
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for SmallVec<T, N> {
+    fn from(vec: Vec<T>) -> Self {
+        SmallVec {
+            storage: Storage::Heap(vec),
+        }
+    }
+}
+
+impl<'a, T: Clone, const N: usize> From<&'a [T]> for SmallVec<T, N> {
+    fn from(values: &'a [T]) -> Self {
+        values.iter().cloned().collect()
+    }
+}
+
+impl<T, const N: usize> From<SmallVec<T, N>> for Vec<T> {
+    fn from(small_vec: SmallVec<T, N>) -> Vec<T> {
+        small_vec.into_vec()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallVec<T, N> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> vec::IntoIter<T> {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut SmallVec<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<T: serde::Serialize, const N: usize> serde::Serialize for SmallVec<T, N> {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<<S as serde::Serializer>::Ok, <S as serde::Serializer>::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_slice().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de, T: serde::Deserialize<'de>, const N: usize> serde::Deserialize<'de> for SmallVec<T, N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as serde::Deserializer<'de>>::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(SmallVec::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::SmallVec;
+
+    #[test]
+    fn push_pop() {
+        let mut v = SmallVec::<String, 2>::new();
+        v.push("a".to_owned());
+        v.push("b".to_owned());
+        assert!(!v.spilled());
+        v.push("c".to_owned());
+        assert!(v.spilled());
+        assert_eq!(&["a", "b", "c"], &v[..]);
+        assert_eq!(Some("c".to_owned()), v.pop());
+        assert_eq!(2, v.len());
+    }
+
+    #[test]
+    fn clear_keeps_heap() {
+        let mut v: SmallVec<u32, 1> = vec![1, 2, 3].into_iter().collect();
+        assert!(v.spilled());
+        v.clear();
+        assert!(v.is_empty());
+        assert!(v.spilled());
+        assert!(v.capacity() >= 3);
+    }
+
+    #[test]
+    fn drop_elements() {
+        let rc = Rc::new(());
+        {
+            let mut v = SmallVec::<Rc<()>, 4>::new();
+            v.push(rc.clone());
+            v.push(rc.clone());
+            assert_eq!(3, Rc::strong_count(&rc));
+            v.truncate(1);
+            assert_eq!(2, Rc::strong_count(&rc));
+            let c = v.clone();
+            assert_eq!(3, Rc::strong_count(&rc));
+            drop(c);
+        }
+        assert_eq!(1, Rc::strong_count(&rc));
+    }
+
+    #[test]
+    fn into_vec() {
+        let v: SmallVec<u32, 4> = vec![1, 2].into_iter().collect();
+        assert!(!v.spilled());
+        assert_eq!(vec![1, 2], v.into_vec());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut v: SmallVec<u32, 2> = vec![1, 3].into_iter().collect();
+        v.insert(1, 2);
+        v.insert(3, 4);
+        assert_eq!(&[1, 2, 3, 4], &v[..]);
+        assert_eq!(2, v.remove(1));
+        assert_eq!(1, v.swap_remove(0));
+        assert_eq!(&[4, 3], &v[..]);
+    }
+
+    #[test]
+    fn retain_drain() {
+        let mut v: SmallVec<u32, 4> = (1..=6).collect();
+        v.retain(|&x| x % 2 == 0);
+        assert_eq!(&[2, 4, 6], &v[..]);
+        v.extend_from_slice(&[8, 10]);
+        let drained: Vec<u32> = v.drain(1..3).collect();
+        assert_eq!(vec![4, 6], drained);
+        assert_eq!(&[2, 8, 10], &v[..]);
+        assert_eq!(&[8, 10], &v[1..]);
+        v.sort_by(|a, b| b.cmp(a));
+        assert_eq!(Ok(1), v.binary_search_by(|x| 8.cmp(x)));
+    }
+
+    #[test]
+    fn eq() {
+        let a: SmallVec<u32, 1> = vec![1, 2].into_iter().collect();
+        let mut b = SmallVec::<u32, 1>::new();
+        b.reserve(10);
+        b.push(1);
+        b.push(2);
+        assert_eq!(a, b);
+        assert_eq!(a, vec![1, 2]);
+        assert_eq!(vec![1, 2], Vec::from(a));
+    }
+
+    #[test]
+    #[should_panic(expected = "range end overflow")]
+    fn drain_range_end_overflow() {
+        let mut v: SmallVec<u32, 1> = SmallVec::new();
+        v.drain(..=usize::MAX);
+    }
+}
@@ -0,0 +1,249 @@
+//! Estimation of memory used by messages.
+//!
+//! See [`Message::space_used`](crate::Message::space_used).
+
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Arc;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::reflect::RuntimeTypeBox;
+
+#[cfg(feature = "bytes")]
+use crate::bytes::Bytes;
+#[cfg(feature = "bytes")]
+use crate::chars::Chars;
+use crate::string_interner::InternedString;
+use crate::MessageField;
+use crate::ProtobufEnumOrUnknown;
+use crate::SmallVec;
+
+/// Heap memory owned by a value of message field.
+///
+/// Implemented for types used in generated messages,
+/// generated messages implement it by delegating to
+/// [`Message::space_used_excluding_self`](crate::Message::space_used_excluding_self).
+pub trait SpaceUsed {
+    /// Bytes allocated on heap by this value, not including `size_of::<Self>()`.
+    fn space_used_excluding_self(&self) -> usize;
+}
+
+macro_rules! impl_space_used_zero {
+    ($($t:ty)*) => {
+        $(
+            impl SpaceUsed for $t {
+                fn space_used_excluding_self(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    }
+}
+
+impl_space_used_zero!(u8 u32 u64 i32 i64 f32 f64 bool);
+
+impl<E> SpaceUsed for ProtobufEnumOrUnknown<E> {
+    fn space_used_excluding_self(&self) -> usize {
+        0
+    }
+}
+
+impl SpaceUsed for String {
+    fn space_used_excluding_self(&self) -> usize {
+        self.capacity()
+    }
+}
+
+/// Interned strings are shared, full string length is counted
+/// for each reference.
+impl SpaceUsed for InternedString {
+    fn space_used_excluding_self(&self) -> usize {
+        self.len()
+    }
+}
+
+/// `Bytes` may be a slice of larger shared buffer,
+/// only the slice length is counted.
+#[cfg(feature = "bytes")]
+impl SpaceUsed for Bytes {
+    fn space_used_excluding_self(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl SpaceUsed for Chars {
+    fn space_used_excluding_self(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: SpaceUsed> SpaceUsed for Vec<T> {
+    fn space_used_excluding_self(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + elements_space_used(self)
+    }
+}
+
+impl<T: SpaceUsed, const N: usize> SpaceUsed for SmallVec<T, N> {
+    fn space_used_excluding_self(&self) -> usize {
+        let heap = match self.spilled() {
+            true => self.capacity() * mem::size_of::<T>(),
+            false => 0,
+        };
+        heap + elements_space_used(self)
+    }
+}
+
+impl<T: SpaceUsed> SpaceUsed for Option<T> {
+    fn space_used_excluding_self(&self) -> usize {
+        self.as_ref().map_or(0, T::space_used_excluding_self)
+    }
+}
+
+impl<T: SpaceUsed> SpaceUsed for Box<T> {
+    fn space_used_excluding_self(&self) -> usize {
+        mem::size_of::<T>() + (**self).space_used_excluding_self()
+    }
+}
+
+/// Shared values are counted for each reference.
+impl<T: SpaceUsed> SpaceUsed for Arc<T> {
+    fn space_used_excluding_self(&self) -> usize {
+        mem::size_of::<T>() + (**self).space_used_excluding_self()
+    }
+}
+
+impl<T: SpaceUsed> SpaceUsed for MessageField<T> {
+    fn space_used_excluding_self(&self) -> usize {
+        self.0.space_used_excluding_self()
+    }
+}
+
+impl<K: SpaceUsed, V: SpaceUsed, S> SpaceUsed for HashMap<K, V, S> {
+    fn space_used_excluding_self(&self) -> usize {
+        // One control byte per hash table entry
+        let entry_size = mem::size_of::<(K, V)>() + 1;
+        self.capacity() * entry_size
+            + self
+                .iter()
+                .map(|(k, v)| k.space_used_excluding_self() + v.space_used_excluding_self())
+                .sum::<usize>()
+    }
+}
+
+fn elements_space_used<T: SpaceUsed>(elements: &[T]) -> usize {
+    elements.iter().map(T::space_used_excluding_self).sum()
+}
+
+/// Estimate heap memory used by message through reflection.
+///
+/// Used for messages which do not track storage of fields, e. g. dynamic messages:
+/// lengths of values are counted, not capacities.
+pub(crate) fn space_used_by_reflection(message: &dyn MessageDyn) -> usize {
+    fn element_size(t: &RuntimeTypeBox) -> usize {
+        match t {
+            RuntimeTypeBox::I32 | RuntimeTypeBox::U32 | RuntimeTypeBox::F32 => 4,
+            RuntimeTypeBox::Enum(..) => 4,
+            RuntimeTypeBox::I64 | RuntimeTypeBox::U64 | RuntimeTypeBox::F64 => 8,
+            RuntimeTypeBox::Bool => 1,
+            RuntimeTypeBox::String => mem::size_of::<String>(),
+            RuntimeTypeBox::VecU8 => mem::size_of::<Vec<u8>>(),
+            RuntimeTypeBox::Message(..) => mem::size_of::<Box<dyn MessageDyn>>(),
+        }
+    }
+
+    fn value_space_used(value: &ReflectValueRef) -> usize {
+        match value {
+            ReflectValueRef::String(s) => s.len(),
+            ReflectValueRef::Bytes(b) => b.len(),
+            ReflectValueRef::Message(m) => m.space_used_dyn(),
+            _ => 0,
+        }
+    }
+
+    let mut size = 0;
+    for field in message.descriptor_dyn().fields() {
+        match field.get_reflect(message) {
+            ReflectFieldRef::Optional(v) => {
+                size += v.as_ref().map_or(0, value_space_used);
+            }
+            ReflectFieldRef::Repeated(r) => {
+                size += r.len() * element_size(&r.element_type());
+                size += r.into_iter().map(|v| value_space_used(&v)).sum::<usize>();
+            }
+            ReflectFieldRef::Map(m) => {
+                size += m.len() * (element_size(&m.key_type()) + element_size(&m.value_type()) + 1);
+                size += m
+                    .into_iter()
+                    .map(|(k, v)| value_space_used(&k) + value_space_used(&v))
+                    .sum::<usize>();
+            }
+        }
+    }
+    size + message.get_unknown_fields_dyn().space_used_excluding_self()
+}
+
+/// Heap memory used by a field value.
+pub fn space_used_excluding_self<T: SpaceUsed + ?Sized>(value: &T) -> usize {
+    value.space_used_excluding_self()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reflect::FileDescriptor;
+    use crate::reflect::ReflectValueBox;
+    use crate::well_known_types::StringValue;
+    use crate::Message;
+
+    #[test]
+    fn vec() {
+        let v: Vec<u32> = Vec::with_capacity(10);
+        assert_eq!(40, space_used_excluding_self(&v));
+
+        let mut v: Vec<String> = Vec::with_capacity(2);
+        v.push(String::with_capacity(100));
+        assert_eq!(
+            2 * mem::size_of::<String>() + 100,
+            space_used_excluding_self(&v)
+        );
+    }
+
+    #[test]
+    fn small_vec() {
+        let mut v: SmallVec<u32, 2> = SmallVec::new();
+        v.push(1);
+        assert_eq!(0, space_used_excluding_self(&v));
+        v.push(2);
+        v.push(3);
+        assert!(space_used_excluding_self(&v) >= 12);
+    }
+
+    #[test]
+    fn option_box() {
+        let v: Option<Box<String>> = Some(Box::new(String::with_capacity(10)));
+        assert_eq!(mem::size_of::<String>() + 10, space_used_excluding_self(&v));
+    }
+
+    #[test]
+    fn dynamic_message() {
+        let file = FileDescriptor::new_dynamic(
+            StringValue::descriptor_static()
+                .file_descriptor_proto()
+                .clone(),
+            Vec::new(),
+        );
+        let descriptor = file
+            .message_by_package_relative_name("StringValue")
+            .unwrap();
+        let mut m = descriptor.new_instance();
+        let empty = m.space_used_dyn();
+        descriptor
+            .get_field_by_name("value")
+            .unwrap()
+            .set_singular_field(&mut *m, ReflectValueBox::String("x".repeat(1000)));
+        assert!(m.space_used_dyn() >= empty + 1000);
+    }
+}
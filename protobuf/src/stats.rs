@@ -0,0 +1,192 @@
+//! Parse and serialize metrics (`stats` feature).
+//!
+//! [`CodedInputStream`] counts what it reads: consumed bytes, fields,
+//! bytes of unknown fields, nesting depth and allocations.
+//! Metrics of a single parse can be obtained with [`parse_from_bytes`]
+//! or [`CodedInputStream::stats`], and all parses performed by
+//! `Message::parse_from_*`, `Message::merge_from_bytes` and
+//! `MessageDyn::merge_from_bytes_dyn` are reported to the hook installed
+//! with [`set_parse_hook`], which can be used for telemetry:
+//!
+//! ```
+//! # use std::sync::atomic::AtomicU64;
+//! # use std::sync::atomic::Ordering;
+//! # use protobuf::Message;
+//! # use protobuf::well_known_types::Duration;
+//! static UNKNOWN_BYTES: AtomicU64 = AtomicU64::new(0);
+//!
+//! protobuf::stats::set_parse_hook(|stats| {
+//!     UNKNOWN_BYTES.fetch_add(stats.unknown_bytes, Ordering::Relaxed);
+//! });
+//! // Field 3 is not known to `Duration`
+//! Duration::parse_from_bytes(&[0x08, 0x01, 0x18, 0x02]).unwrap();
+//! protobuf::stats::clear_parse_hook();
+//! assert!(UNKNOWN_BYTES.load(Ordering::Relaxed) >= 1);
+//! ```
+//!
+//! Serialization with `Message::write_to*` functions is reported to
+//! the hook installed with [`set_serialize_hook`].
+//!
+//! Counting is cheap, but not free, so it is only compiled with `stats` feature.
+
+use std::sync::RwLock;
+
+use crate::lazy_v2::LazyV2;
+use crate::CodedInputStream;
+use crate::Message;
+use crate::ProtobufResult;
+
+/// Metrics of a parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// Bytes consumed from input.
+    pub bytes: u64,
+    /// Field tags read, including fields of nested messages,
+    /// unknown fields and fields inside skipped groups.
+    pub fields: u64,
+    /// Bytes of unknown fields (excluding their tags)
+    /// stored in unknown fields or skipped.
+    pub unknown_bytes: u64,
+    /// Maximum nesting depth of messages, `0` for a message without nested messages.
+    pub max_depth: u32,
+    /// Heap allocations made by the parser for strings, bytes and unknown
+    /// length-delimited values, packed repeated fields, boxed nested messages
+    /// and growth of repeated message fields.
+    ///
+    /// This is an estimate: allocations made by user code,
+    /// by map fields and by non-packed repeated scalar fields are not counted.
+    pub allocations: u64,
+}
+
+/// Metrics of a serialization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SerializeStats {
+    /// Size of the serialized message in bytes.
+    pub bytes: u64,
+}
+
+type Hook<S> = Box<dyn Fn(&S) + Send + Sync>;
+
+static PARSE_HOOK: LazyV2<RwLock<Option<Hook<ParseStats>>>> = LazyV2::INIT;
+static SERIALIZE_HOOK: LazyV2<RwLock<Option<Hook<SerializeStats>>>> = LazyV2::INIT;
+
+fn set_hook<S>(hook: &'static LazyV2<RwLock<Option<Hook<S>>>>, value: Option<Hook<S>>) {
+    let hook = hook.get(|| RwLock::new(None));
+    *hook.write().unwrap_or_else(|e| e.into_inner()) = value;
+}
+
+fn report<S>(hook: &'static LazyV2<RwLock<Option<Hook<S>>>>, stats: &S) {
+    let hook = hook.get(|| RwLock::new(None));
+    if let Some(hook) = &*hook.read().unwrap_or_else(|e| e.into_inner()) {
+        hook(stats);
+    }
+}
+
+/// Install the function called after each parse, successful or not.
+///
+/// Replaces previously installed hook.
+pub fn set_parse_hook<F: Fn(&ParseStats) + Send + Sync + 'static>(hook: F) {
+    set_hook(&PARSE_HOOK, Some(Box::new(hook)));
+}
+
+/// Remove the hook installed with [`set_parse_hook`].
+pub fn clear_parse_hook() {
+    set_hook(&PARSE_HOOK, None);
+}
+
+/// Install the function called after each serialization.
+///
+/// Replaces previously installed hook.
+pub fn set_serialize_hook<F: Fn(&SerializeStats) + Send + Sync + 'static>(hook: F) {
+    set_hook(&SERIALIZE_HOOK, Some(Box::new(hook)));
+}
+
+/// Remove the hook installed with [`set_serialize_hook`].
+pub fn clear_serialize_hook() {
+    set_hook(&SERIALIZE_HOOK, None);
+}
+
+pub(crate) fn report_parse(stats: &ParseStats) {
+    report(&PARSE_HOOK, stats);
+}
+
+pub(crate) fn report_serialize(stats: &SerializeStats) {
+    report(&SERIALIZE_HOOK, stats);
+}
+
+/// Parse message like [`Message::parse_from_bytes`] and return metrics
+/// of the parse alongside the result, so metrics are available
+/// for rejected input too.
+///
+/// ```
+/// # use protobuf::well_known_types::Duration;
+/// let (duration, stats) = protobuf::stats::parse_from_bytes::<Duration>(&[0x08, 0x01]);
+/// assert_eq!(1, duration.unwrap().seconds);
+/// assert_eq!(2, stats.bytes);
+/// assert_eq!(1, stats.fields);
+/// ```
+pub fn parse_from_bytes<M: Message>(bytes: &[u8]) -> (ProtobufResult<M>, ParseStats) {
+    let mut is = CodedInputStream::from_bytes(bytes);
+    let r = is.with_stats_report(|is| {
+        let r = M::parse_from(is)?;
+        is.check_eof()?;
+        Ok(r)
+    });
+    (r, is.stats())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Duration;
+
+    #[test]
+    fn nested() {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        let mut message = DescriptorProto::new();
+        message.set_name("A".to_owned());
+        message.nested_type.push(DescriptorProto::new());
+        file.message_type.push(message);
+        let bytes = file.write_to_bytes().unwrap();
+
+        let (parsed, stats) = parse_from_bytes::<FileDescriptorProto>(&bytes);
+        assert_eq!(file, parsed.unwrap());
+        assert_eq!(bytes.len() as u64, stats.bytes);
+        // name, message_type, message_type.name, message_type.nested_type
+        assert_eq!(4, stats.fields);
+        assert_eq!(0, stats.unknown_bytes);
+        assert_eq!(2, stats.max_depth);
+        // two strings, two vec allocations for messages
+        assert_eq!(4, stats.allocations);
+    }
+
+    #[test]
+    fn unknown_and_error() {
+        // field 1, field 3 (unknown, length-delimited with 2 bytes), truncated field 4
+        let bytes = [0x08, 0x01, 0x1a, 0x02, 0x00, 0x00, 0x20];
+        let (parsed, stats) = parse_from_bytes::<Duration>(&bytes);
+        assert!(parsed.is_err());
+        assert_eq!(7, stats.bytes);
+        assert_eq!(3, stats.fields);
+        assert_eq!(3, stats.unknown_bytes);
+        assert_eq!(0, stats.max_depth);
+    }
+
+    #[test]
+    fn serialize_hook() {
+        static SIZES: LazyV2<Mutex<Vec<u64>>> = LazyV2::INIT;
+        let sizes = SIZES.get(|| Mutex::new(Vec::new()));
+        set_serialize_hook(move |stats| sizes.lock().unwrap().push(stats.bytes));
+        let mut duration = Duration::new();
+        duration.seconds = 1000;
+        let bytes = duration.write_to_bytes().unwrap();
+        clear_serialize_hook();
+        // Other tests may serialize concurrently
+        assert!(sizes.lock().unwrap().contains(&(bytes.len() as u64)));
+    }
+}
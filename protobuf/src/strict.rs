@@ -0,0 +1,576 @@
+//! Strict parsing of untrusted input.
+//!
+//! Regular parser is lenient like parsers in other runtimes: the last
+//! occurrence of a singular field wins, unknown fields are preserved
+//! and varints may be padded. Different runtimes may interpret such input
+//! differently, which can be exploited when a message is checked by one
+//! service and used by another. Strict parsing rejects such input:
+//!
+//! ```
+//! # use protobuf::Message;
+//! # use protobuf::strict::StrictParseOptions;
+//! # use protobuf::well_known_types::Duration;
+//! // `seconds` field is specified twice
+//! let bytes = [0x08, 0x01, 0x08, 0x02];
+//! assert_eq!(2, Duration::parse_from_bytes(&bytes).unwrap().seconds);
+//! assert!(Duration::parse_from_bytes_strict(&bytes, &StrictParseOptions::default()).is_err());
+//! ```
+//!
+//! Input is checked against message descriptor before it is parsed,
+//! so it requires reflection and does not work with lite runtime.
+//! Extensions are treated as unknown fields, and content of groups
+//! is not checked.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use crate::descriptor::field_descriptor_proto::Label;
+use crate::descriptor::field_descriptor_proto::Type;
+use crate::descriptor::FieldDescriptorProto;
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::message::Message;
+use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageDescriptor;
+use crate::wire_format::WireType;
+
+// Same as default recursion limit of parser
+const RECURSION_LIMIT: u32 = 100;
+
+/// Rules of strict parsing, each can be disabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictParseOptions {
+    /// Reject repeated occurrences of singular fields,
+    /// and occurrences of different fields of the same oneof.
+    pub reject_duplicate_fields: bool,
+    /// Reject fields not declared in the message.
+    pub reject_unknown_fields: bool,
+    /// Reject varints (including tags and lengths) encoded with
+    /// more bytes than needed, and values out of range of field type,
+    /// e. g. `uint32` field value greater than `u32::MAX`.
+    pub reject_non_minimal_varints: bool,
+    /// Reject `string` fields which are not valid UTF-8.
+    pub require_utf8: bool,
+}
+
+impl Default for StrictParseOptions {
+    /// All rules are enabled.
+    fn default() -> Self {
+        StrictParseOptions {
+            reject_duplicate_fields: true,
+            reject_unknown_fields: true,
+            reject_non_minimal_varints: true,
+            require_utf8: true,
+        }
+    }
+}
+
+/// Which rule of strict parsing is violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrictParseErrorKind {
+    /// Singular field or oneof occurs more than once.
+    DuplicateField,
+    /// Field is not declared in the message.
+    UnknownField,
+    /// Varint is longer than needed or out of range.
+    NonMinimalVarint,
+    /// `string` field is not valid UTF-8.
+    InvalidUtf8,
+    /// Input is truncated or otherwise not a valid message,
+    /// or it is nested too deep.
+    Malformed,
+}
+
+/// Error returned when input does not pass strict parsing rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrictParseError {
+    /// Violated rule.
+    pub kind: StrictParseErrorKind,
+    /// Path to the field, like `foo.bar[1].baz`;
+    /// unknown fields are specified by number.
+    pub path: String,
+}
+
+impl fmt::Display for StrictParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self.kind {
+            StrictParseErrorKind::DuplicateField => "duplicate field",
+            StrictParseErrorKind::UnknownField => "unknown field",
+            StrictParseErrorKind::NonMinimalVarint => "non-minimal varint",
+            StrictParseErrorKind::InvalidUtf8 => "invalid UTF-8",
+            StrictParseErrorKind::Malformed => "malformed message",
+        };
+        if self.path.is_empty() {
+            write!(f, "strict parse: {}", description)
+        } else {
+            write!(f, "strict parse: {} at `{}`", description, self.path)
+        }
+    }
+}
+
+impl Error for StrictParseError {}
+
+impl StrictParseOptions {
+    /// Check that bytes are a message of given type satisfying enabled rules.
+    ///
+    /// Required fields are not checked.
+    pub fn check(
+        &self,
+        descriptor: &MessageDescriptor,
+        bytes: &[u8],
+    ) -> Result<(), StrictParseError> {
+        let mut checker = Checker {
+            options: self,
+            path: Vec::new(),
+        };
+        checker.message(descriptor, bytes, 0)
+    }
+
+    /// [Check](StrictParseOptions::check) and parse a message.
+    pub fn parse_from_bytes<M: Message>(&self, bytes: &[u8]) -> ProtobufResult<M> {
+        self.check(&M::descriptor_static(), bytes)
+            .map_err(ProtobufError::StrictParse)?;
+        M::parse_from_bytes(bytes)
+    }
+
+    /// [Check](StrictParseOptions::check) and parse a message of type specified by descriptor.
+    ///
+    /// Message is checked to be initialized.
+    pub fn parse_dynamic_from_bytes(
+        &self,
+        descriptor: &MessageDescriptor,
+        bytes: &[u8],
+    ) -> ProtobufResult<Box<dyn MessageDyn>> {
+        self.check(descriptor, bytes)
+            .map_err(ProtobufError::StrictParse)?;
+        let mut message = descriptor.new_instance();
+        message.merge_from_bytes_dyn(bytes)?;
+        message.check_initialized_dyn()?;
+        Ok(message)
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+/// Raw varint and whether it is encoded with the minimal number of bytes.
+fn read_varint(r: &mut Reader) -> Option<(u64, bool)> {
+    let mut value = 0;
+    for i in 0..10 {
+        let b = *r.bytes.get(r.pos)?;
+        r.pos += 1;
+        value |= ((b & 0x7f) as u64) << (i * 7);
+        if b < 0x80 {
+            if i == 9 && b > 1 {
+                return None;
+            }
+            return Some((value, i == 0 || b != 0));
+        }
+    }
+    None
+}
+
+fn read_slice<'a>(r: &mut Reader<'a>, len: usize) -> Option<&'a [u8]> {
+    let end = r.pos.checked_add(len)?;
+    let slice = r.bytes.get(r.pos..end)?;
+    r.pos = end;
+    Some(slice)
+}
+
+/// Check that varint value fits in the field type.
+fn varint_in_range(t: Type, value: u64) -> bool {
+    match t {
+        Type::TYPE_INT32 | Type::TYPE_ENUM => {
+            value as i64 >= i32::MIN as i64 && value as i64 <= i32::MAX as i64
+        }
+        Type::TYPE_UINT32 | Type::TYPE_SINT32 => value <= u32::MAX as u64,
+        Type::TYPE_BOOL => value <= 1,
+        _ => true,
+    }
+}
+
+fn wire_type_of(t: Type) -> WireType {
+    match t {
+        Type::TYPE_DOUBLE | Type::TYPE_FIXED64 | Type::TYPE_SFIXED64 => WireType::WireTypeFixed64,
+        Type::TYPE_FLOAT | Type::TYPE_FIXED32 | Type::TYPE_SFIXED32 => WireType::WireTypeFixed32,
+        Type::TYPE_STRING | Type::TYPE_BYTES | Type::TYPE_MESSAGE => {
+            WireType::WireTypeLengthDelimited
+        }
+        Type::TYPE_GROUP => WireType::WireTypeStartGroup,
+        _ => WireType::WireTypeVarint,
+    }
+}
+
+/// Fields which may occur only once.
+#[derive(PartialEq, Eq)]
+enum Singular {
+    Field(u32),
+    Oneof(i32),
+}
+
+struct Checker<'o> {
+    options: &'o StrictParseOptions,
+    /// Path to the current message.
+    path: Vec<String>,
+}
+
+impl<'o> Checker<'o> {
+    fn error(&self, kind: StrictParseErrorKind, last: Option<String>) -> StrictParseError {
+        let mut path = self.path.clone();
+        path.extend(last);
+        StrictParseError {
+            kind,
+            path: path.join("."),
+        }
+    }
+
+    fn malformed(&self) -> StrictParseError {
+        self.error(StrictParseErrorKind::Malformed, None)
+    }
+
+    fn varint(&self, r: &mut Reader, name: &dyn Fn() -> String) -> Result<u64, StrictParseError> {
+        match read_varint(r) {
+            Some((_, false)) if self.options.reject_non_minimal_varints => {
+                Err(self.error(StrictParseErrorKind::NonMinimalVarint, Some(name())))
+            }
+            Some((value, _)) => Ok(value),
+            None => Err(self.malformed()),
+        }
+    }
+
+    fn length_delimited<'a>(
+        &self,
+        r: &mut Reader<'a>,
+        name: &dyn Fn() -> String,
+    ) -> Result<&'a [u8], StrictParseError> {
+        let len = self.varint(r, name)?;
+        if len > u32::MAX as u64 {
+            return Err(self.malformed());
+        }
+        read_slice(r, len as usize).ok_or_else(|| self.malformed())
+    }
+
+    /// Skip field content or group until end group tag.
+    fn skip(
+        &self,
+        r: &mut Reader,
+        wire_type: WireType,
+        name: &dyn Fn() -> String,
+        depth: u32,
+    ) -> Result<(), StrictParseError> {
+        match wire_type {
+            WireType::WireTypeVarint => self.varint(r, name).map(drop),
+            WireType::WireTypeFixed64 => read_slice(r, 8).map(drop).ok_or_else(|| self.malformed()),
+            WireType::WireTypeFixed32 => read_slice(r, 4).map(drop).ok_or_else(|| self.malformed()),
+            WireType::WireTypeLengthDelimited => self.length_delimited(r, name).map(drop),
+            WireType::WireTypeStartGroup => {
+                if depth >= RECURSION_LIMIT {
+                    return Err(self.malformed());
+                }
+                loop {
+                    let (_, wire_type) = self.tag(r, name)?;
+                    if wire_type == WireType::WireTypeEndGroup {
+                        return Ok(());
+                    }
+                    self.skip(r, wire_type, name, depth + 1)?;
+                }
+            }
+            WireType::WireTypeEndGroup => Err(self.malformed()),
+        }
+    }
+
+    fn tag(
+        &self,
+        r: &mut Reader,
+        name: &dyn Fn() -> String,
+    ) -> Result<(u32, WireType), StrictParseError> {
+        let tag = self.varint(r, name)?;
+        let number = tag >> 3;
+        match WireType::new((tag & 7) as u32) {
+            Some(wire_type) if number != 0 && number < (1 << 29) => Ok((number as u32, wire_type)),
+            _ => Err(self.malformed()),
+        }
+    }
+
+    /// Check single value of a field of message `descriptor`,
+    /// `wire_type` is already checked.
+    fn value(
+        &mut self,
+        r: &mut Reader,
+        descriptor: &MessageDescriptor,
+        field: &FieldDescriptorProto,
+        name: &dyn Fn() -> String,
+        depth: u32,
+    ) -> Result<(), StrictParseError> {
+        match field.get_field_type() {
+            Type::TYPE_STRING => {
+                let bytes = self.length_delimited(r, name)?;
+                if self.options.require_utf8 && str::from_utf8(bytes).is_err() {
+                    return Err(self.error(StrictParseErrorKind::InvalidUtf8, Some(name())));
+                }
+                Ok(())
+            }
+            Type::TYPE_MESSAGE => {
+                let bytes = self.length_delimited(r, name)?;
+                // Map entries are not supported by `get_field_by_number`
+                // of generated descriptors, so fields are found by protos
+                let message = descriptor
+                    .file_descriptor()
+                    .message_by_full_name_with_deps(field.get_type_name())
+                    .ok_or_else(|| self.malformed())?;
+                self.path.push(name());
+                self.message(&message, bytes, depth + 1)?;
+                self.path.pop();
+                Ok(())
+            }
+            Type::TYPE_GROUP => self.skip(r, WireType::WireTypeStartGroup, name, depth),
+            t => match wire_type_of(t) {
+                WireType::WireTypeVarint => {
+                    let value = self.varint(r, name)?;
+                    if self.options.reject_non_minimal_varints && !varint_in_range(t, value) {
+                        return Err(
+                            self.error(StrictParseErrorKind::NonMinimalVarint, Some(name()))
+                        );
+                    }
+                    Ok(())
+                }
+                wire_type => self.skip(r, wire_type, name, depth),
+            },
+        }
+    }
+
+    fn message(
+        &mut self,
+        descriptor: &MessageDescriptor,
+        bytes: &[u8],
+        depth: u32,
+    ) -> Result<(), StrictParseError> {
+        if depth >= RECURSION_LIMIT {
+            return Err(self.malformed());
+        }
+        let mut r = Reader { bytes, pos: 0 };
+        let mut seen = Vec::new();
+        let mut counts: HashMap<u32, usize> = HashMap::new();
+        let map_entry = descriptor.is_map_entry();
+        while r.pos < bytes.len() {
+            let (number, wire_type) = self.tag(&mut r, &|| String::new())?;
+            let field = match descriptor
+                .get_proto()
+                .field
+                .iter()
+                .find(|f| f.get_number() as u32 == number)
+            {
+                Some(field) => field,
+                None => {
+                    if self.options.reject_unknown_fields {
+                        return Err(self
+                            .error(StrictParseErrorKind::UnknownField, Some(number.to_string())));
+                    }
+                    self.skip(&mut r, wire_type, &|| number.to_string(), depth)?;
+                    continue;
+                }
+            };
+            let t = field.get_field_type();
+            let repeated = field.get_label() == Label::LABEL_REPEATED;
+            let index = counts.entry(number).or_insert(0);
+            let name = {
+                let index = *index;
+                let field_name = field.get_name();
+                move || {
+                    if repeated {
+                        format!("{}[{}]", field_name, index)
+                    } else {
+                        field_name.to_owned()
+                    }
+                }
+            };
+            *index += 1;
+
+            if repeated {
+                let packable = match wire_type_of(t) {
+                    WireType::WireTypeVarint
+                    | WireType::WireTypeFixed32
+                    | WireType::WireTypeFixed64 => true,
+                    _ => false,
+                };
+                if packable && wire_type == WireType::WireTypeLengthDelimited {
+                    let packed = self.length_delimited(&mut r, &name)?;
+                    let mut packed = Reader {
+                        bytes: packed,
+                        pos: 0,
+                    };
+                    while packed.pos < packed.bytes.len() {
+                        self.value(&mut packed, descriptor, field, &name, depth)?;
+                    }
+                    continue;
+                }
+            } else if self.options.reject_duplicate_fields && !map_entry {
+                let singular = match field.has_oneof_index() {
+                    true => Singular::Oneof(field.get_oneof_index()),
+                    false => Singular::Field(number),
+                };
+                if seen.contains(&singular) {
+                    return Err(self.error(StrictParseErrorKind::DuplicateField, Some(name())));
+                }
+                seen.push(singular);
+            }
+            if wire_type != wire_type_of(t) {
+                return Err(self.malformed());
+            }
+            self.value(&mut r, descriptor, field, &name, depth)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Struct;
+
+    fn check<M: Message>(bytes: &[u8]) -> Result<(), StrictParseError> {
+        StrictParseOptions::default().check(&M::descriptor_static(), bytes)
+    }
+
+    fn kind<M: Message>(bytes: &[u8]) -> StrictParseErrorKind {
+        check::<M>(bytes).unwrap_err().kind
+    }
+
+    #[test]
+    fn valid() {
+        let mut file = FileDescriptorProto::new();
+        file.set_name("a.proto".to_owned());
+        file.dependency.push("b.proto".to_owned());
+        file.dependency.push("c.proto".to_owned());
+        let mut field = FieldDescriptorProto::new();
+        field.set_number(-1);
+        field.set_label(Label::LABEL_REPEATED);
+        file.message_type.push(DescriptorProto::new());
+        file.message_type[0].field.push(field);
+        file.message_type[0].set_name("M".to_owned());
+        let bytes = file.write_to_bytes().unwrap();
+        check::<FileDescriptorProto>(&bytes).unwrap();
+        let parsed = StrictParseOptions::default()
+            .parse_from_bytes::<FileDescriptorProto>(&bytes)
+            .unwrap();
+        assert_eq!(file, parsed);
+    }
+
+    #[test]
+    fn duplicate() {
+        // name: "a" name: "b"
+        let bytes = [0x0a, 0x01, b'a', 0x0a, 0x01, b'b'];
+        let error = check::<FileDescriptorProto>(&bytes).unwrap_err();
+        assert_eq!(StrictParseErrorKind::DuplicateField, error.kind);
+        assert_eq!("name", error.path);
+
+        let mut options = StrictParseOptions::default();
+        options.reject_duplicate_fields = false;
+        options
+            .check(&FileDescriptorProto::descriptor_static(), &bytes)
+            .unwrap();
+    }
+
+    #[test]
+    fn duplicate_nested_and_oneof() {
+        // message_type { field { number: 1 number: 2 } }
+        let bytes = [0x22, 0x06, 0x12, 0x04, 0x18, 0x01, 0x18, 0x02];
+        let error = check::<FileDescriptorProto>(&bytes).unwrap_err();
+        assert_eq!(StrictParseErrorKind::DuplicateField, error.kind);
+        assert_eq!("message_type[0].field[0].number", error.path);
+
+        // fields { key: "k" value { number_value: 1 string_value: "" } }
+        let bytes = [
+            0x0a, 0x10, 0x0a, 0x01, b'k', 0x12, 0x0b, 0x11, 0, 0, 0, 0, 0, 0, 0xf0, 0x3f, 0x1a,
+            0x00,
+        ];
+        let error = check::<Struct>(&bytes).unwrap_err();
+        assert_eq!(StrictParseErrorKind::DuplicateField, error.kind);
+        assert_eq!("fields[0].value.string_value", error.path);
+    }
+
+    #[test]
+    fn unknown() {
+        let bytes = [0xf8, 0x3e, 0x01];
+        let error = check::<FileDescriptorProto>(&bytes).unwrap_err();
+        assert_eq!(StrictParseErrorKind::UnknownField, error.kind);
+        assert_eq!("1007", error.path);
+        assert_eq!("strict parse: unknown field at `1007`", error.to_string());
+    }
+
+    #[test]
+    fn non_minimal_varint() {
+        // message_type { field { number: 1 } } with number padded
+        assert_eq!(
+            StrictParseErrorKind::NonMinimalVarint,
+            kind::<FileDescriptorProto>(&[0x22, 0x05, 0x12, 0x03, 0x18, 0x81, 0x00])
+        );
+        // padded tag
+        assert_eq!(
+            StrictParseErrorKind::NonMinimalVarint,
+            kind::<FileDescriptorProto>(&[0x8a, 0x00, 0x00])
+        );
+        // padded length
+        assert_eq!(
+            StrictParseErrorKind::NonMinimalVarint,
+            kind::<FileDescriptorProto>(&[0x0a, 0x80, 0x00])
+        );
+        // `int32` number out of range
+        let mut bytes = vec![0x22, 0x08, 0x12, 0x06, 0x18];
+        bytes.extend(&[0x80, 0x80, 0x80, 0x80, 0x10]);
+        assert_eq!(
+            StrictParseErrorKind::NonMinimalVarint,
+            kind::<FileDescriptorProto>(&bytes)
+        );
+        // negative `int32` is encoded with 10 bytes
+        let mut bytes = vec![0x22, 0x0d, 0x12, 0x0b, 0x18];
+        bytes.extend(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        check::<FileDescriptorProto>(&bytes).unwrap();
+    }
+
+    #[test]
+    fn utf8() {
+        let bytes = [0x0a, 0x01, 0xff];
+        assert_eq!(
+            StrictParseErrorKind::InvalidUtf8,
+            kind::<FileDescriptorProto>(&bytes)
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(
+            StrictParseErrorKind::Malformed,
+            kind::<FileDescriptorProto>(&[0x0a, 0x05, b'a'])
+        );
+        // `name` with varint wire type
+        assert_eq!(
+            StrictParseErrorKind::Malformed,
+            kind::<FileDescriptorProto>(&[0x08, 0x01])
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let bytes = [0x0a, 0x01, b'a', 0x0a, 0x01, b'b'];
+        match FileDescriptorProto::parse_from_bytes_strict(&bytes, &StrictParseOptions::default()) {
+            Err(ProtobufError::StrictParse(e)) => {
+                assert_eq!(StrictParseErrorKind::DuplicateField, e.kind)
+            }
+            r => panic!("{:?}", r),
+        }
+        let descriptor = FileDescriptorProto::descriptor_static();
+        assert!(StrictParseOptions::default()
+            .parse_dynamic_from_bytes(&descriptor, &bytes)
+            .is_err());
+        assert!(StrictParseOptions::default()
+            .parse_dynamic_from_bytes(&descriptor, &bytes[..3])
+            .is_ok());
+    }
+}
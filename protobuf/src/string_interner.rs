@@ -0,0 +1,252 @@
+//! String interning for low-cardinality string fields.
+
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::clear::Clear;
+use crate::lazy_v2::LazyV2;
+
+/// Immutable shared string.
+///
+/// Cloning is cheap, and strings obtained from the same [`StringInterner`]
+/// share a single allocation.
+/// Should be API-compatible to `&str`.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InternedString(Arc<str>);
+
+impl InternedString {
+    /// New empty string.
+    pub fn new() -> InternedString {
+        InternedString(Arc::from(""))
+    }
+
+    /// Get a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Len in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Self-explanatory
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Return `true` if both strings share the same allocation.
+    pub fn ptr_eq(&self, other: &InternedString) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<'a> From<&'a str> for InternedString {
+    fn from(src: &'a str) -> InternedString {
+        InternedString(Arc::from(src))
+    }
+}
+
+impl From<String> for InternedString {
+    fn from(src: String) -> InternedString {
+        InternedString(Arc::from(src))
+    }
+}
+
+impl Into<String> for InternedString {
+    fn into(self) -> String {
+        self.0.as_ref().to_owned()
+    }
+}
+
+impl Default for InternedString {
+    fn default() -> Self {
+        InternedString::new()
+    }
+}
+
+impl Deref for InternedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for InternedString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for InternedString {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for InternedString {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for InternedString {
+    fn eq(&self, other: &&'a str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<InternedString> for str {
+    fn eq(&self, other: &InternedString) -> bool {
+        self == &*other.0
+    }
+}
+
+impl<'a> PartialEq<InternedString> for &'a str {
+    fn eq(&self, other: &InternedString) -> bool {
+        *self == &*other.0
+    }
+}
+
+impl Clear for InternedString {
+    fn clear(&mut self) {
+        if !self.is_empty() {
+            *self = InternedString::new();
+        }
+    }
+}
+
+impl fmt::Display for InternedString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl fmt::Debug for InternedString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl serde::Serialize for InternedString {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "with-serde")]
+impl<'de> serde::Deserialize<'de> for InternedString {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(InternedString::from)
+    }
+}
+
+/// Set of strings which hands out shared [`InternedString`] instances.
+///
+/// An interner can be attached to a parser with
+/// [`CodedInputStream::set_string_interner`](crate::CodedInputStream::set_string_interner)
+/// to share strings of fields generated with `interned_string_for_string` option
+/// between parsed messages, otherwise each parser uses its own interner.
+///
+/// Strings are never removed from the interner, so it grows with each distinct
+/// string until it is dropped.
+///
+/// ```
+/// # use protobuf::StringInterner;
+/// let interner = StringInterner::new();
+/// let a = interner.intern("label");
+/// let b = interner.intern("label");
+/// assert!(a.ptr_eq(&b));
+/// assert_eq!(1, interner.len());
+/// ```
+#[derive(Default)]
+pub struct StringInterner {
+    strings: Mutex<HashSet<Arc<str>>>,
+}
+
+impl StringInterner {
+    /// New empty interner.
+    pub fn new() -> StringInterner {
+        StringInterner::default()
+    }
+
+    /// Process-wide interner.
+    ///
+    /// It is not used unless set explicitly on the parser.
+    /// Strings interned here are kept until the process exits,
+    /// so it should only be used for inputs with bounded set of strings.
+    pub fn global() -> &'static StringInterner {
+        static GLOBAL: LazyV2<StringInterner> = LazyV2::INIT;
+        GLOBAL.get(StringInterner::new)
+    }
+
+    /// Return interned copy of the given string.
+    pub fn intern(&self, s: &str) -> InternedString {
+        let mut strings = self.strings.lock().unwrap();
+        if let Some(s) = strings.get(s) {
+            return InternedString(s.clone());
+        }
+        let s: Arc<str> = Arc::from(s);
+        strings.insert(s.clone());
+        InternedString(s)
+    }
+
+    /// Number of distinct strings in this interner.
+    pub fn len(&self) -> usize {
+        self.strings.lock().unwrap().len()
+    }
+
+    /// Self-explanatory
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all strings. Previously returned strings remain valid.
+    pub fn clear(&self) {
+        self.strings.lock().unwrap().clear();
+    }
+}
+
+impl fmt::Debug for StringInterner {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StringInterner")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::InternedString;
+    use super::StringInterner;
+
+    #[test]
+    fn test_intern() {
+        let interner = StringInterner::new();
+        let a = interner.intern("aa");
+        let b = interner.intern("aa");
+        let c = interner.intern("bb");
+        assert!(a.ptr_eq(&b));
+        assert!(!a.ptr_eq(&c));
+        assert_eq!("aa", a);
+        assert_eq!(2, interner.len());
+    }
+
+    #[test]
+    fn test_display_and_debug() {
+        let s = "test";
+        let string: String = s.into();
+        let interned: InternedString = s.into();
+
+        assert_eq!(format!("{}", string), format!("{}", interned));
+        assert_eq!(format!("{:?}", string), format!("{:?}", interned));
+    }
+}
@@ -0,0 +1,106 @@
+//! Hex and base64 encoding of serialized messages.
+
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::TextEncodingError;
+use crate::json::base64;
+
+/// Lowercase hex without separators.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut r = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        r.push(DIGITS[(b >> 4) as usize] as char);
+        r.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    r
+}
+
+/// Decode hex digits of any case, ASCII whitespace between bytes is ignored.
+pub(crate) fn decode_hex(input: &str) -> ProtobufResult<Vec<u8>> {
+    let error = |position| {
+        ProtobufError::TextEncoding(TextEncodingError {
+            encoding: "hex",
+            position,
+        })
+    };
+    let mut r = Vec::with_capacity(input.len() / 2);
+    let mut chars = input
+        .char_indices()
+        .filter(|(_, c)| !c.is_ascii_whitespace());
+    while let Some((pos, first)) = chars.next() {
+        let high = first.to_digit(16).ok_or_else(|| error(pos))?;
+        let low = match chars.next() {
+            Some((pos, second)) => second.to_digit(16).ok_or_else(|| error(pos))?,
+            None => return Err(error(input.len())),
+        };
+        r.push((high << 4 | low) as u8);
+    }
+    Ok(r)
+}
+
+/// Standard base64 with padding.
+pub(crate) fn encode_base64(bytes: &[u8]) -> String {
+    base64::encode(bytes)
+}
+
+/// Decode standard or URL-safe base64, padding is optional.
+pub(crate) fn decode_base64(input: &str) -> ProtobufResult<Vec<u8>> {
+    base64::decode(input).map_err(|e| {
+        ProtobufError::TextEncoding(TextEncodingError {
+            encoding: "base64",
+            position: match e {
+                base64::FromBase64Error::InvalidBase64Byte(_, position) => position,
+                base64::FromBase64Error::InvalidBase64Length => input.len(),
+            },
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::well_known_types::Duration;
+    use crate::Message;
+
+    #[test]
+    fn hex() {
+        assert_eq!("", encode_hex(&[]));
+        assert_eq!("0aff10", encode_hex(&[0x0a, 0xff, 0x10]));
+        assert_eq!(vec![0x0a, 0xff, 0x10], decode_hex("0aFF10").unwrap());
+        assert_eq!(vec![0x08, 0x96, 0x01], decode_hex("08 96 01\n").unwrap());
+        match decode_hex("0x") {
+            Err(ProtobufError::TextEncoding(e)) => assert_eq!(1, e.position),
+            r => panic!("{:?}", r),
+        }
+        match decode_hex("abc") {
+            Err(ProtobufError::TextEncoding(e)) => assert_eq!(3, e.position),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!("+/8=", encode_base64(&[251, 255]));
+        assert_eq!(vec![251, 255], decode_base64("+/8=").unwrap());
+        assert_eq!(vec![251, 255], decode_base64("-_8").unwrap());
+        match decode_base64("ab!d") {
+            Err(ProtobufError::TextEncoding(e)) => {
+                assert_eq!("invalid base64 at position 2", e.to_string())
+            }
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn message() {
+        let mut d = Duration::new();
+        d.seconds = 150;
+        d.nanos = 1;
+        assert_eq!("0896011001", d.write_to_hex().unwrap());
+        assert_eq!("CJYBEAE=", d.write_to_base64().unwrap());
+        assert_eq!(d, Duration::parse_from_hex("08 96 01 10 01").unwrap());
+        assert_eq!(d, Duration::parse_from_base64("CJYBEAE=").unwrap());
+        assert!(Duration::parse_from_hex("0896").is_err());
+    }
+}
@@ -0,0 +1,432 @@
+//! Field-by-field difference of two messages in text format.
+
+use std::fmt::Write;
+
+use crate::message_dyn::MessageDyn;
+use crate::reflect::FieldDescriptor;
+use crate::reflect::MessageRef;
+use crate::reflect::ReflectFieldRef;
+use crate::reflect::ReflectValueRef;
+use crate::text_format::print::print_field_pretty;
+use crate::text_format::REDACTED;
+
+/// Assert that two messages are equal, and print the difference
+/// in text format if they are not.
+///
+/// Lines starting with `-` are from the left message,
+/// lines starting with `+` are from the right message,
+/// equal fields are not printed.
+///
+/// ```should_panic
+/// # use protobuf::assert_messages_eq;
+/// # use protobuf::well_known_types::Duration;
+/// let mut a = Duration::new();
+/// a.seconds = 1;
+/// let b = Duration::new();
+/// // panics with message:
+/// // assertion failed: `(left == right)`
+/// // diff (- left, + right):
+/// // - seconds: 1
+/// assert_messages_eq!(a, b);
+/// ```
+#[macro_export]
+macro_rules! assert_messages_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_messages_eq_masked!($left, $right, &[])
+    };
+}
+
+/// Like [`assert_messages_eq!`], but fields with given paths are not compared.
+///
+/// Paths are field names separated by dots like `FieldMask` paths,
+/// e. g. `"items.update_time"` ignores `update_time` field of all elements
+/// of repeated `items` field.
+///
+/// ```
+/// # use protobuf::assert_messages_eq_masked;
+/// # use protobuf::well_known_types::Duration;
+/// let mut a = Duration::new();
+/// a.seconds = 1;
+/// let b = Duration::new();
+/// assert_messages_eq_masked!(a, b, &["seconds"]);
+/// ```
+#[macro_export]
+macro_rules! assert_messages_eq_masked {
+    ($left:expr, $right:expr, $ignored:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let Some(diff) = $crate::text_format::diff_ignoring(left, right, $ignored) {
+                    panic!(
+                        "assertion failed: `(left == right)`\ndiff (- left, + right):\n{}",
+                        diff
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Difference of messages in text format, `None` if messages are equal.
+///
+/// Floats are compared exactly except that `NaN` values are equal.
+/// Values of sensitive fields are redacted like in `Debug` output.
+pub fn diff(a: &dyn MessageDyn, b: &dyn MessageDyn) -> Option<String> {
+    diff_ignoring(a, b, &[])
+}
+
+/// Difference of messages in text format ignoring fields with given paths.
+///
+/// See [`assert_messages_eq_masked!`] for paths syntax.
+pub fn diff_ignoring(a: &dyn MessageDyn, b: &dyn MessageDyn, ignored: &[&str]) -> Option<String> {
+    let a_descriptor = a.descriptor_dyn();
+    let b_descriptor = b.descriptor_dyn();
+    if a_descriptor != b_descriptor {
+        return Some(format!(
+            "- # {}\n+ # {}\n",
+            a_descriptor.full_name(),
+            b_descriptor.full_name()
+        ));
+    }
+    let mut differ = Differ {
+        ignored,
+        buf: String::new(),
+    };
+    differ.diff_messages(a, b, "", 0);
+    if differ.buf.is_empty() {
+        None
+    } else {
+        Some(differ.buf)
+    }
+}
+
+struct Differ<'a> {
+    ignored: &'a [&'a str],
+    buf: String,
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn scalar_eq(a: &ReflectValueRef, b: &ReflectValueRef) -> bool {
+    match (a, b) {
+        (ReflectValueRef::F32(a), ReflectValueRef::F32(b)) => a == b || a.is_nan() && b.is_nan(),
+        (ReflectValueRef::F64(a), ReflectValueRef::F64(b)) => a == b || a.is_nan() && b.is_nan(),
+        (a, b) => a == b,
+    }
+}
+
+impl<'a> Differ<'a> {
+    /// Print lines with a marker prefix.
+    fn marked(&mut self, marker: char, text: &str) {
+        for line in text.lines() {
+            self.buf.push(marker);
+            self.buf.push(' ');
+            self.buf.push_str(line);
+            self.buf.push('\n');
+        }
+    }
+
+    /// Print value removed or added.
+    fn value(
+        &mut self,
+        marker: char,
+        indent: usize,
+        name: &str,
+        comment: &str,
+        v: ReflectValueRef,
+    ) {
+        let mut text = String::new();
+        print_field_pretty(&mut text, indent, name, v);
+        if !comment.is_empty() {
+            // Comment on the first line, which is the whole field for scalars
+            let first_line_end = text.find('\n').unwrap_or(text.len());
+            text.insert_str(first_line_end, &format!("  # {}", comment));
+        }
+        self.marked(marker, &text);
+    }
+
+    /// Print map entry removed or added.
+    fn map_entry(
+        &mut self,
+        marker: char,
+        indent: usize,
+        name: &str,
+        k: ReflectValueRef,
+        v: ReflectValueRef,
+    ) {
+        let mut text = String::new();
+        push_indent(&mut text, indent);
+        write!(text, "{} {{\n", name).unwrap();
+        print_field_pretty(&mut text, indent + 1, "key", k);
+        print_field_pretty(&mut text, indent + 1, "value", v);
+        push_indent(&mut text, indent);
+        text.push_str("}\n");
+        self.marked(marker, &text);
+    }
+
+    fn diff_values(
+        &mut self,
+        indent: usize,
+        name: &str,
+        comment: &str,
+        path: &str,
+        a: ReflectValueRef,
+        b: ReflectValueRef,
+    ) {
+        match (&a, &b) {
+            (ReflectValueRef::Message(am), ReflectValueRef::Message(bm)) => {
+                self.diff_nested(indent, name, comment, path, am, bm)
+            }
+            _ => {
+                if !scalar_eq(&a, &b) {
+                    self.value('-', indent, name, comment, a);
+                    self.value('+', indent, name, comment, b);
+                }
+            }
+        }
+    }
+
+    /// Print difference of nested messages inside `name { ... }` block.
+    fn diff_nested(
+        &mut self,
+        indent: usize,
+        name: &str,
+        comment: &str,
+        path: &str,
+        a: &MessageRef,
+        b: &MessageRef,
+    ) {
+        let outer = std::mem::replace(&mut self.buf, String::new());
+        self.diff_messages(&**a, &**b, path, indent + 1);
+        let inner = std::mem::replace(&mut self.buf, outer);
+        if inner.is_empty() {
+            return;
+        }
+        let mut header = String::new();
+        push_indent(&mut header, indent);
+        write!(header, "{} {{", name).unwrap();
+        if !comment.is_empty() {
+            write!(header, "  # {}", comment).unwrap();
+        }
+        self.marked(' ', &header);
+        self.buf.push_str(&inner);
+        let mut footer = String::new();
+        push_indent(&mut footer, indent);
+        footer.push('}');
+        self.marked(' ', &footer);
+    }
+
+    fn diff_messages(&mut self, a: &dyn MessageDyn, b: &dyn MessageDyn, path: &str, indent: usize) {
+        let descriptor = a.descriptor_dyn();
+        for field in descriptor.fields() {
+            let name = field.get_name();
+            let path = join_path(path, name);
+            if self.ignored.contains(&path.as_str()) {
+                continue;
+            }
+            if field.is_redacted() {
+                // Only report that values differ like `Debug` output does
+                let outer = std::mem::replace(&mut self.buf, String::new());
+                self.diff_field(a, b, &field, &path, indent);
+                let inner = std::mem::replace(&mut self.buf, outer);
+                if !inner.is_empty() {
+                    let mut text = String::new();
+                    push_indent(&mut text, indent);
+                    write!(text, "{}: {}  # differs", name, REDACTED).unwrap();
+                    self.marked(' ', &text);
+                }
+                continue;
+            }
+            self.diff_field(a, b, &field, &path, indent);
+        }
+        if a.get_unknown_fields_dyn() != b.get_unknown_fields_dyn() {
+            let mut text = String::new();
+            push_indent(&mut text, indent);
+            text.push_str("# unknown fields differ");
+            self.marked(' ', &text);
+        }
+    }
+
+    fn diff_field(
+        &mut self,
+        a: &dyn MessageDyn,
+        b: &dyn MessageDyn,
+        field: &FieldDescriptor,
+        path: &str,
+        indent: usize,
+    ) {
+        let name = field.get_name();
+        {
+            match (field.get_reflect(a), field.get_reflect(b)) {
+                (ReflectFieldRef::Optional(a), ReflectFieldRef::Optional(b)) => match (a, b) {
+                    (None, None) => {}
+                    (Some(a), None) => self.value('-', indent, name, "", a),
+                    (None, Some(b)) => self.value('+', indent, name, "", b),
+                    (Some(a), Some(b)) => self.diff_values(indent, name, "", &path, a, b),
+                },
+                (ReflectFieldRef::Repeated(a), ReflectFieldRef::Repeated(b)) => {
+                    for i in 0..a.len().max(b.len()) {
+                        let comment = format!("[{}]", i);
+                        match (i < a.len(), i < b.len()) {
+                            (true, true) => {
+                                self.diff_values(indent, name, &comment, &path, a.get(i), b.get(i))
+                            }
+                            (true, false) => self.value('-', indent, name, &comment, a.get(i)),
+                            _ => self.value('+', indent, name, &comment, b.get(i)),
+                        }
+                    }
+                }
+                (ReflectFieldRef::Map(a), ReflectFieldRef::Map(b)) => {
+                    let mut keys: Vec<(String, ReflectValueRef)> = a
+                        .into_iter()
+                        .chain(b.into_iter())
+                        .map(|(k, _)| (format_key(&k), k))
+                        .collect();
+                    keys.sort_by(|x, y| x.0.cmp(&y.0));
+                    keys.dedup_by(|x, y| x.0 == y.0);
+                    for (key_text, k) in keys {
+                        let comment = format!("[{}]", key_text);
+                        match (a.get(k.clone()), b.get(k.clone())) {
+                            (Some(av), Some(bv)) => match (&av, &bv) {
+                                (ReflectValueRef::Message(am), ReflectValueRef::Message(bm)) => {
+                                    self.diff_nested(indent, name, &comment, &path, am, bm)
+                                }
+                                _ if scalar_eq(&av, &bv) => {}
+                                _ => self.diff_map_value(indent, name, &comment, av, bv),
+                            },
+                            (Some(av), None) => self.map_entry('-', indent, name, k, av),
+                            (None, Some(bv)) => self.map_entry('+', indent, name, k, bv),
+                            (None, None) => unreachable!(),
+                        }
+                    }
+                }
+                _ => unreachable!("same field of same message"),
+            }
+        }
+    }
+
+    /// Differing scalar value of map entry.
+    fn diff_map_value(
+        &mut self,
+        indent: usize,
+        name: &str,
+        comment: &str,
+        a: ReflectValueRef,
+        b: ReflectValueRef,
+    ) {
+        let mut header = String::new();
+        push_indent(&mut header, indent);
+        write!(header, "{} {{  # {}", name, comment).unwrap();
+        self.marked(' ', &header);
+        self.value('-', indent + 1, "value", "", a);
+        self.value('+', indent + 1, "value", "", b);
+        let mut footer = String::new();
+        push_indent(&mut footer, indent);
+        footer.push('}');
+        self.marked(' ', &footer);
+    }
+}
+
+fn push_indent(buf: &mut String, indent: usize) {
+    for _ in 0..indent {
+        buf.push_str("  ");
+    }
+}
+
+fn format_key(k: &ReflectValueRef) -> String {
+    match k {
+        ReflectValueRef::String(s) => format!("{:?}", s),
+        ReflectValueRef::I32(v) => v.to_string(),
+        ReflectValueRef::I64(v) => v.to_string(),
+        ReflectValueRef::U32(v) => v.to_string(),
+        ReflectValueRef::U64(v) => v.to_string(),
+        ReflectValueRef::Bool(v) => v.to_string(),
+        k => format!("{:?}", k),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::descriptor::DescriptorProto;
+    use crate::descriptor::FileDescriptorProto;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn nested_and_repeated() {
+        let mut a = FileDescriptorProto::new();
+        a.set_name("a.proto".to_owned());
+        a.dependency = vec!["x".to_owned(), "y".to_owned()];
+        let mut m = DescriptorProto::new();
+        m.set_name("M".to_owned());
+        a.message_type.push(m.clone());
+        a.message_type.push(m);
+        let mut b = a.clone();
+        b.dependency.pop();
+        b.message_type[1].set_name("N".to_owned());
+        b.set_package("p".to_owned());
+
+        assert_eq!(
+            Some(
+                "+ package: \"p\"\n\
+                 - dependency: \"y\"  # [1]\n\
+                 \x20 message_type {  # [1]\n\
+                 -   name: \"M\"\n\
+                 +   name: \"N\"\n\
+                 \x20 }\n"
+                    .to_owned()
+            ),
+            diff(&a, &b)
+        );
+        assert_eq!(
+            Some("+ package: \"p\"\n".to_owned()),
+            diff_ignoring(&a, &b, &["dependency", "message_type.name"])
+        );
+        assert_eq!(None, diff(&a, &a.clone()));
+    }
+
+    #[test]
+    fn map() {
+        let value = |v: &str| {
+            let mut value = Value::new();
+            value.set_string_value(v.to_owned());
+            value
+        };
+        let mut a = Struct::new();
+        a.fields.insert("k".to_owned(), value("a"));
+        a.fields.insert("r".to_owned(), value("r"));
+        let mut b = Struct::new();
+        b.fields.insert("k".to_owned(), value("b"));
+
+        assert_eq!(
+            Some(
+                "\x20 fields {  # [\"k\"]\n\
+                 -   string_value: \"a\"\n\
+                 +   string_value: \"b\"\n\
+                 \x20 }\n\
+                 - fields {\n\
+                 -   key: \"r\"\n\
+                 -   value {\n\
+                 -     string_value: \"r\"\n\
+                 -   }\n\
+                 - }\n"
+                    .to_owned()
+            ),
+            diff(&a, &b)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "diff (- left, + right):\n- name: \"a\"\n")]
+    fn assert_macro() {
+        let mut a = DescriptorProto::new();
+        a.set_name("a".to_owned());
+        assert_messages_eq!(a, DescriptorProto::new());
+    }
+}
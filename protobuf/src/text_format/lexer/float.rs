@@ -11,7 +11,52 @@ pub type ProtobufFloatParseResult<T> = Result<T, ProtobufFloatParseError>;
 pub const PROTOBUF_NAN: &str = "nan";
 pub const PROTOBUF_INF: &str = "inf";
 
-/// Format float as in protobuf `.proto` files
+/// Format finite float given in Rust `{:e}` format like `-1.25e-7`
+/// (which has shortest digits needed to parse back to the same value)
+/// like C `%g` does: `1e+20`, `1e-07`, `0.0001`, `123.5`.
+///
+/// `%g` precision is `digits` if the digits fit, and `max_digits` otherwise,
+/// this is how C++ protobuf formats floats.
+fn format_shortest(scientific: &str, digits: usize, max_digits: usize) -> String {
+    let (mantissa, exp) = scientific.split_at(scientific.find('e').unwrap());
+    let exp: i32 = exp[1..].parse().unwrap();
+    let (minus, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => ("-", mantissa),
+        None => ("", mantissa),
+    };
+    let mantissa: String = mantissa.chars().filter(|&c| c != '.').collect();
+    let precision = if mantissa.len() <= digits {
+        digits
+    } else {
+        max_digits
+    };
+
+    if exp < -4 || exp >= precision as i32 {
+        let (first, rest) = mantissa.split_at(1);
+        format!(
+            "{}{}{}{}e{}{:02}",
+            minus,
+            first,
+            if rest.is_empty() { "" } else { "." },
+            rest,
+            if exp < 0 { '-' } else { '+' },
+            exp.abs()
+        )
+    } else if exp < 0 {
+        format!("{}0.{}{}", minus, "0".repeat((-exp - 1) as usize), mantissa)
+    } else if mantissa.len() <= exp as usize + 1 {
+        let zeros = "0".repeat(exp as usize + 1 - mantissa.len());
+        format!("{}{}{}", minus, mantissa, zeros)
+    } else {
+        let (int, frac) = mantissa.split_at(exp as usize + 1);
+        format!("{}{}.{}", minus, int, frac)
+    }
+}
+
+/// Format float as in protobuf `.proto` files and text format.
+///
+/// Output is the shortest string which parses back to the same value,
+/// formatted like C++ protobuf does.
 pub fn format_protobuf_float(f: f64) -> String {
     if f.is_nan() {
         PROTOBUF_NAN.to_owned()
@@ -22,9 +67,84 @@ pub fn format_protobuf_float(f: f64) -> String {
             format!("-{}", PROTOBUF_INF)
         }
     } else {
-        // TODO: make sure doesn't lose precision
-        format!("{}", f)
+        format_shortest(&format!("{:e}", f), 15, 17)
+    }
+}
+
+/// Format `float` value like [`format_protobuf_float`].
+///
+/// The value is formatted with shortest digits of `f32`, not of `f64`.
+pub fn format_protobuf_float32(f: f32) -> String {
+    if f.is_finite() {
+        format_shortest(&format!("{:e}", f), 6, 9)
+    } else {
+        format_protobuf_float(f as f64)
+    }
+}
+
+/// Parse hexadecimal float like `0x1.8p3` (`12.0`).
+///
+/// Result is correctly rounded except subnormal numbers which may be rounded twice.
+fn parse_hex_float(s: &str) -> ProtobufFloatParseResult<f64> {
+    let s = &s[2..];
+    let p = s
+        .find(|c| c == 'p' || c == 'P')
+        .ok_or(ProtobufFloatParseError::CannotParseFloat)?;
+    let mut exp: i64 = s[p + 1..]
+        .parse::<i32>()
+        .map_err(|_| ProtobufFloatParseError::CannotParseFloat)? as i64;
+    let (int, frac) = match s[..p].find('.') {
+        Some(dot) => (&s[..dot], &s[dot + 1..p]),
+        None => (&s[..p], ""),
+    };
+    if int.is_empty() && frac.is_empty() {
+        return Err(ProtobufFloatParseError::CannotParseFloat);
+    }
+
+    // Digits which do not fit in `u64` only affect rounding
+    let mut mantissa: u64 = 0;
+    let mut sticky = false;
+    for (i, c) in int.chars().chain(frac.chars()).enumerate() {
+        let digit = c
+            .to_digit(16)
+            .ok_or(ProtobufFloatParseError::CannotParseFloat)? as u64;
+        let is_frac = i >= int.len();
+        if mantissa >> 60 == 0 {
+            mantissa = mantissa << 4 | digit;
+            if is_frac {
+                exp -= 4;
+            }
+        } else {
+            sticky |= digit != 0;
+            if !is_frac {
+                exp += 4;
+            }
+        }
     }
+    if mantissa == 0 {
+        return Ok(0.0);
+    }
+
+    // Round to nearest even 53-bit mantissa
+    let bits = 64 - mantissa.leading_zeros() as i64;
+    if bits > 53 {
+        let shift = bits - 53;
+        let rem = mantissa & ((1 << shift) - 1);
+        let half = 1 << (shift - 1);
+        mantissa >>= shift;
+        exp += shift;
+        if rem > half || rem == half && (sticky || mantissa & 1 == 1) {
+            mantissa += 1;
+        }
+    }
+
+    let mut f = mantissa as f64;
+    while exp != 0 {
+        let step = exp.max(-1000).min(1000);
+        f *= 2f64.powi(step as i32);
+        exp -= step;
+    }
+    Ok(f)
 }
 
 /// Parse float from `.proto` format
@@ -41,6 +161,9 @@ pub fn parse_protobuf_float(s: &str) -> ProtobufFloatParseResult<f64> {
     if s == format!("-{}", PROTOBUF_INF) {
         return Ok(f64::NEG_INFINITY);
     }
+    if s.starts_with("0x") || s.starts_with("0X") {
+        return parse_hex_float(s);
+    }
     match s.parse() {
         Ok(f) => Ok(f),
         Err(_) => Err(ProtobufFloatParseError::CannotParseFloat),
@@ -54,5 +177,88 @@ mod test {
     #[test]
     fn test_format_protobuf_float() {
         assert_eq!("10", format_protobuf_float(10.0));
+        assert_eq!("-0", format_protobuf_float(-0.0));
+        assert_eq!("0.1", format_protobuf_float(0.1));
+        assert_eq!("123.5", format_protobuf_float(123.5));
+        assert_eq!("0.0001", format_protobuf_float(0.0001));
+        assert_eq!("1e-05", format_protobuf_float(0.00001));
+        assert_eq!("1e+20", format_protobuf_float(1e20));
+        assert_eq!("123456789012345", format_protobuf_float(123456789012345.0));
+        assert_eq!(
+            "1234567890123456",
+            format_protobuf_float(1234567890123456.0)
+        );
+        assert_eq!(
+            "12345678901234568",
+            format_protobuf_float(12345678901234567.0)
+        );
+        assert_eq!("0.30000000000000004", format_protobuf_float(0.1 + 0.2));
+        assert_eq!("1.7976931348623157e+308", format_protobuf_float(f64::MAX));
+        assert_eq!("5e-324", format_protobuf_float(5e-324));
+        assert_eq!("nan", format_protobuf_float(f64::NAN));
+        assert_eq!("-inf", format_protobuf_float(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_format_protobuf_float32() {
+        assert_eq!("0.1", format_protobuf_float32(0.1));
+        assert_eq!("1e+06", format_protobuf_float32(1e6));
+        assert_eq!("1234567", format_protobuf_float32(1234567.0));
+        assert_eq!("3.4028235e+38", format_protobuf_float32(f32::MAX));
+        assert_eq!("inf", format_protobuf_float32(f32::INFINITY));
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        let mut x: u64 = 1;
+        for _ in 0..10000 {
+            // xorshift
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            let f = f64::from_bits(x);
+            if f.is_finite() {
+                let s = format_protobuf_float(f);
+                assert_eq!(
+                    f.to_bits(),
+                    parse_protobuf_float(&s).unwrap().to_bits(),
+                    "{}",
+                    s
+                );
+            }
+            let f = f32::from_bits(x as u32);
+            if f.is_finite() {
+                let s = format_protobuf_float32(f);
+                let parsed = parse_protobuf_float(&s).unwrap() as f32;
+                assert_eq!(f.to_bits(), parsed.to_bits(), "{}", s);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_protobuf_float() {
+        assert_eq!(12.5, parse_protobuf_float("12.5").unwrap());
+        assert_eq!(
+            0.1,
+            parse_protobuf_float("0.1000000000000000055511151231257827021181583404541015625")
+                .unwrap()
+        );
+        assert_eq!(12.0, parse_protobuf_float("0x1.8p3").unwrap());
+        assert_eq!(0.5, parse_protobuf_float("0X1P-1").unwrap());
+        assert_eq!(255.0, parse_protobuf_float("0xffp+0").unwrap());
+        assert_eq!(0.0, parse_protobuf_float("0x0p0").unwrap());
+        assert_eq!(
+            f64::MAX,
+            parse_protobuf_float("0x1.fffffffffffffp1023").unwrap()
+        );
+        assert_eq!(5e-324, parse_protobuf_float("0x1p-1074").unwrap());
+        // Rounded to even
+        assert_eq!(1.0, parse_protobuf_float("0x1.00000000000008p0").unwrap());
+        assert_eq!(
+            1.0 + f64::EPSILON,
+            parse_protobuf_float("0x1.000000000000080001p0").unwrap()
+        );
+        assert!(parse_protobuf_float("0x1.8").is_err());
+        assert!(parse_protobuf_float("0xp1").is_err());
     }
 }
@@ -190,9 +190,13 @@ impl<'a> Lexer<'a> {
 
     fn skip_comment(&mut self) -> LexerResult<()> {
         match self.language {
+            // Skip one comment, so comments can be collected one by one
             ParserLanguage::Proto => {
-                self.skip_c_comment()?;
-                self.skip_cpp_comment();
+                if self.lookahead_is_str("/*") {
+                    self.skip_c_comment()?;
+                } else {
+                    self.skip_cpp_comment();
+                }
             }
             ParserLanguage::TextFormat => {
                 self.skip_sh_comment();
@@ -447,6 +451,25 @@ impl<'a> Lexer<'a> {
         Ok(())
     }
 
+    // Text format only, `protoc` does not accept these in `.proto` files
+    // hexFloatLit = "0" ( "x" | "X" ) { hexDigit } [ "." { hexDigit } ] ( "p" | "P" ) [ "+" | "-" ] decimals
+    fn next_hex_float_lit(&mut self) -> LexerResult<()> {
+        assert_eq!(ParserLanguage::TextFormat, self.language);
+
+        if !self.skip_if_lookahead_is_str("0x") && !self.skip_if_lookahead_is_str("0X") {
+            return Err(LexerError::IncorrectFloatLit);
+        }
+        self.take_while(|c| c.is_ascii_hexdigit());
+        if self.next_char_if_eq('.') {
+            self.take_while(|c| c.is_ascii_hexdigit());
+        }
+        if self.next_char_if_in("pP") == None {
+            return Err(LexerError::IncorrectFloatLit);
+        }
+        self.next_char_if_in("+-");
+        self.next_decimal_digits()
+    }
+
     // String literals
 
     // charValue = hexEscape | octEscape | charEscape | /[^\0\n\\]/
@@ -632,6 +655,16 @@ impl<'a> Lexer<'a> {
             return Ok(token);
         }
 
+        if self.language == ParserLanguage::TextFormat {
+            let mut clone = self.clone();
+            let pos = clone.pos;
+            if let Ok(_) = clone.next_hex_float_lit() {
+                let f = float::parse_protobuf_float(&self.input[pos..clone.pos])?;
+                *self = clone;
+                return Ok(Token::FloatLit(f));
+            }
+        }
+
         if self.language != ParserLanguage::Json {
             let mut clone = self.clone();
             let pos = clone.pos;
@@ -641,8 +674,30 @@ impl<'a> Lexer<'a> {
                 return Ok(Token::FloatLit(f));
             }
 
-            if let Some(lit) = self.next_int_lit_opt()? {
-                return Ok(Token::IntLit(lit));
+            let mut clone = self.clone();
+            match clone.next_int_lit_opt() {
+                Ok(Some(lit)) => {
+                    *self = clone;
+                    return Ok(Token::IntLit(lit));
+                }
+                Ok(None) => {}
+                // Decimal integer out of `u64` range, e. g. large `double`
+                // printed without exponent, is parsed as float like protoc does.
+                Err(LexerError::ParseIntError) => {
+                    let mut clone = self.clone();
+                    clone.skip_ws()?;
+                    let pos = clone.pos;
+                    if clone.input[pos..].starts_with('0') {
+                        return Err(LexerError::ParseIntError);
+                    }
+                    clone.next_decimal_digits()?;
+                    let f = clone.input[pos..clone.pos]
+                        .parse()
+                        .map_err(|_| LexerError::ParseIntError)?;
+                    *self = clone;
+                    return Ok(Token::FloatLit(f));
+                }
+                Err(e) => return Err(e),
             }
         }
 
@@ -676,12 +731,55 @@ impl<'a> Lexer<'a> {
             Some(TokenWithLocation { token, loc })
         })
     }
+
+    /// Comments of the remaining input with their locations.
+    ///
+    /// Comments are skipped by [`next_token`](Lexer::next_token),
+    /// this function is used by tools which need to preserve them.
+    /// Returned comments include comment markers like `//`,
+    /// but not the trailing newline.
+    pub fn comments(mut self) -> LexerResult<Vec<(Loc, &'a str)>> {
+        let mut comments = Vec::new();
+        loop {
+            self.skip_whitespaces();
+            let loc = self.loc;
+            let start = self.pos;
+            self.skip_comment()?;
+            if self.pos != start {
+                comments.push((
+                    loc,
+                    self.input[start..self.pos].trim_end_matches(|c| c == '\n' || c == '\r'),
+                ));
+                continue;
+            }
+            if self.eof() {
+                return Ok(comments);
+            }
+            self.next_token_inner()?;
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn comments() {
+        let input = "// a\nmessage /* b */ M { } // c\r\n/**//* d */";
+        let comments = Lexer::new(input, ParserLanguage::Proto).comments().unwrap();
+        assert_eq!(
+            vec![
+                (Loc { line: 1, col: 1 }, "// a"),
+                (Loc { line: 2, col: 9 }, "/* b */"),
+                (Loc { line: 2, col: 23 }, "// c"),
+                (Loc { line: 3, col: 1 }, "/**/"),
+                (Loc { line: 3, col: 5 }, "/* d */"),
+            ],
+            comments
+        );
+    }
+
     fn lex<P, R>(input: &str, parse_what: P) -> R
     where
         P: FnOnce(&mut Lexer) -> LexerResult<R>,
@@ -716,4 +814,22 @@ mod test {
         let mess = lex(msg, |p| p.next_token_inner());
         assert_eq!(Token::FloatLit(12.3), mess);
     }
+
+    #[test]
+    fn test_lexer_hex_float_lit() {
+        let mut lexer = Lexer::new("0x1.8p3 0x10", ParserLanguage::TextFormat);
+        assert_eq!(Token::FloatLit(12.0), lexer.next_token_inner().unwrap());
+        lexer.skip_ws().unwrap();
+        assert_eq!(Token::IntLit(16), lexer.next_token_inner().unwrap());
+
+        let mut lexer = Lexer::new("0x1.8p3", ParserLanguage::Proto);
+        assert_eq!(Token::IntLit(1), lexer.next_token_inner().unwrap());
+    }
+
+    #[test]
+    fn test_lexer_int_lit_out_of_range() {
+        let msg = r#"100000000000000000000"#;
+        let mess = lex(msg, |p| p.next_token_inner());
+        assert_eq!(Token::FloatLit(1e20), mess);
+    }
 }
@@ -18,7 +18,16 @@
 //! This format is not specified, but it is implemented by all official
 //! protobuf implementations, including `protoc` command which can decode
 //! and encode messages using text format.
+//!
+//! Generated messages implement `Debug` with [`fmt`]: `{:?}` prints
+//! compact single-line text format, `{:#?}` prints multi-line text format,
+//! and fields marked `(rustproto.sensitive)` are redacted.
+//! Internal fields like cached size and unknown fields holder are never printed,
+//! but unknown fields are printed by number.
+//! With `lite_runtime` option `Debug` is derived because lite runtime
+//! has no reflection.
 
+mod diff;
 mod parse;
 mod print;
 
@@ -28,14 +37,20 @@ mod print;
 #[doc(hidden)]
 pub mod lexer;
 
+pub use self::diff::diff;
+pub use self::diff::diff_ignoring;
 pub use self::print::fmt;
 pub use self::print::print_to;
 pub use self::print::print_to_string;
+pub use self::print::print_to_string_unredacted;
 #[doc(hidden)]
 pub use self::print::quote_bytes_to;
 #[doc(hidden)]
 pub use self::print::quote_escape_bytes;
+pub use self::print::Unredacted;
+pub use self::print::REDACTED;
 
 pub use self::parse::merge_from_str;
+pub use self::parse::parse_dynamic_from_str;
 pub use self::parse::parse_from_str;
 pub use self::parse::ParseError;
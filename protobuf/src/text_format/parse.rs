@@ -2,11 +2,13 @@ use std::fmt;
 use std::str;
 
 use crate::message::Message;
+use crate::unknown::UnknownFields;
 
+use crate::error::ProtobufError;
 use crate::message_dyn::MessageDyn;
 use crate::reflect::EnumDescriptor;
-use crate::reflect::EnumValueDescriptor;
 use crate::reflect::MessageDescriptor;
+use crate::reflect::MessageFactory;
 use crate::reflect::ReflectValueBox;
 use crate::reflect::RuntimeFieldType;
 use crate::reflect::RuntimeTypeBox;
@@ -22,11 +24,15 @@ pub enum ParseErrorWithoutLoc {
     TokenizerError(TokenizerError),
     StrLitDecodeError(StrLitDecodeError),
     UnknownField(String),
+    UnknownExtension(String),
+    UnknownAnyType(String),
     UnknownEnumValue(String),
     MapFieldIsSpecifiedMoreThanOnce(String),
     IntegerOverflow,
     ExpectingBool,
     MessageNotInitialized,
+    /// Failed to serialize `Any` or extension message value.
+    SerializeValue(ProtobufError),
 }
 
 impl From<TokenizerError> for ParseErrorWithoutLoc {
@@ -60,7 +66,14 @@ impl fmt::Display for ParseError {
     }
 }
 
-impl std::error::Error for ParseError {}
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.error {
+            ParseErrorWithoutLoc::SerializeValue(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 pub type ParseResult<A> = Result<A, ParseErrorWithoutLoc>;
 pub type ParseWithLocResult<A> = Result<A, ParseError>;
@@ -77,20 +90,44 @@ impl<'a> Parser<'a> {
         Ok(self.tokenizer.next_ident()?)
     }
 
+    /// Name in brackets: extension name like `[pkg.ext]`
+    /// or `Any` type URL like `[type.googleapis.com/pkg.Message]`.
+    ///
+    /// Opening bracket is already consumed.
+    fn next_bracketed_name(&mut self) -> ParseResult<String> {
+        let mut name = self.tokenizer.next_ident()?;
+        loop {
+            if self.tokenizer.next_symbol_if_eq('.')? {
+                name.push('.');
+            } else if self.tokenizer.next_symbol_if_eq('/')? {
+                name.push('/');
+            } else {
+                break;
+            }
+            name.push_str(&self.tokenizer.next_ident()?);
+        }
+        self.tokenizer.next_symbol_expect_eq(']')?;
+        Ok(name)
+    }
+
     fn read_colon(&mut self) -> ParseResult<()> {
         Ok(self.tokenizer.next_symbol_expect_eq(':')?)
     }
 
-    fn read_enum<'e>(&mut self, e: &'e EnumDescriptor) -> ParseResult<EnumValueDescriptor> {
+    fn read_enum<'e>(&mut self, e: &'e EnumDescriptor) -> ParseResult<i32> {
         self.read_colon()?;
 
-        // TODO: read integer?
+        // Number may be a value unknown to the enum type
+        if self.tokenizer.lookahead_is_int_lit()? || self.tokenizer.lookahead_is_symbol('-')? {
+            return self.read_i32_value();
+        }
+
         let ident = self.tokenizer.next_ident()?;
         let value = match e.get_value_by_name(&ident) {
             Some(value) => value,
             None => return Err(ParseErrorWithoutLoc::UnknownEnumValue(ident)),
         };
-        Ok(value)
+        Ok(value.value())
     }
 
     fn read_u64(&mut self) -> ParseResult<u64> {
@@ -112,7 +149,10 @@ impl<'a> Parser<'a> {
 
     fn read_i64(&mut self) -> ParseResult<i64> {
         self.read_colon()?;
+        self.read_i64_value()
+    }
 
+    fn read_i64_value(&mut self) -> ParseResult<i64> {
         if self.tokenizer.next_symbol_if_eq('-')? {
             let int_lit = self.tokenizer.next_int_lit()?;
             Ok(int::neg(int_lit)?)
@@ -126,7 +166,12 @@ impl<'a> Parser<'a> {
     }
 
     fn read_i32(&mut self) -> ParseResult<i32> {
-        let value = self.read_i64()?;
+        self.read_colon()?;
+        self.read_i32_value()
+    }
+
+    fn read_i32_value(&mut self) -> ParseResult<i32> {
+        let value = self.read_i64_value()?;
         if value < i32::min_value() as i64 || value > i32::max_value() as i64 {
             return Err(ParseErrorWithoutLoc::IntegerOverflow);
         }
@@ -237,7 +282,7 @@ impl<'a> Parser<'a> {
     fn read_value_of_type(&mut self, t: &RuntimeTypeBox) -> ParseResult<ReflectValueBox> {
         Ok(match t {
             RuntimeTypeBox::Enum(d) => {
-                let value = self.read_enum(&d)?.value();
+                let value = self.read_enum(&d)?;
                 ReflectValueBox::Enum(d.clone(), value)
             }
             RuntimeTypeBox::U32 => ReflectValueBox::U32(self.read_u32()?),
@@ -253,11 +298,84 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn merge_extension(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+        name: String,
+    ) -> ParseResult<()> {
+        let ext = match MessageFactory::global().extension_by_name(&name) {
+            Some(ext) if ext.extendee() == descriptor.full_name() => ext,
+            _ => return Err(ParseErrorWithoutLoc::UnknownExtension(name)),
+        };
+        let value = self.read_value_of_type(&ext.runtime_type())?;
+        let value = ext
+            .value_to_unknown(value.as_value_ref())
+            .map_err(ParseErrorWithoutLoc::SerializeValue)?;
+        let number = ext.number();
+        let mut moved = UnknownFields::new();
+        if let Some(extension_fields) = message.mut_extension_fields_dyn() {
+            if ext.is_repeated() && extension_fields.contains(number) {
+                // Keep all values of the field in one place
+                extension_fields.append_to_unknown(&mut moved);
+            }
+            extension_fields.remove(number);
+        }
+        let unknown_fields = message.mut_unknown_fields_dyn();
+        if !ext.is_repeated() {
+            unknown_fields.remove(number);
+        }
+        for v in moved.get(number).into_iter().flatten() {
+            unknown_fields.add_value(number, v.to_unknown_value());
+        }
+        unknown_fields.add_value(number, value);
+        Ok(())
+    }
+
+    fn merge_any(
+        &mut self,
+        message: &mut dyn MessageDyn,
+        descriptor: &MessageDescriptor,
+        type_url: String,
+    ) -> ParseResult<()> {
+        if descriptor.full_name() != "google.protobuf.Any" {
+            return Err(ParseErrorWithoutLoc::UnknownField(type_url));
+        }
+        let type_name = &type_url[type_url.rfind('/').unwrap() + 1..];
+        let value_descriptor = match MessageFactory::global().descriptor_by_name(type_name) {
+            Some(d) => d,
+            None => return Err(ParseErrorWithoutLoc::UnknownAnyType(type_url)),
+        };
+        let value = self.read_message(&value_descriptor)?;
+        // Required fields are checked by the caller if needed
+        let value = value
+            .write_to_bytes_partial_dyn()
+            .map_err(ParseErrorWithoutLoc::SerializeValue)?;
+        descriptor
+            .get_field_by_name("type_url")
+            .unwrap()
+            .set_singular_field(message, ReflectValueBox::String(type_url));
+        descriptor
+            .get_field_by_name("value")
+            .unwrap()
+            .set_singular_field(message, ReflectValueBox::Bytes(value));
+        Ok(())
+    }
+
     fn merge_field(
         &mut self,
         message: &mut dyn MessageDyn,
         descriptor: &MessageDescriptor,
     ) -> ParseResult<()> {
+        if self.tokenizer.next_symbol_if_eq('[')? {
+            let name = self.next_bracketed_name()?;
+            return if name.contains('/') {
+                self.merge_any(message, descriptor, name)
+            } else {
+                self.merge_extension(message, descriptor, name)
+            };
+        }
+
         let field_name = self.next_field_name()?;
 
         let field = match descriptor.get_field_by_name(&field_name) {
@@ -310,6 +428,10 @@ impl<'a> Parser<'a> {
 
 /// Parse text format message.
 ///
+/// Extensions (`[pkg.ext]: 1`) and `Any` values
+/// (`[type.googleapis.com/pkg.Message] { ... }`) are resolved with
+/// [`MessageFactory::global`], and extension values are stored in unknown fields.
+///
 /// This function does not check if message required fields are set.
 pub fn merge_from_str(message: &mut dyn MessageDyn, input: &str) -> ParseWithLocResult<()> {
     let mut parser = Parser {
@@ -330,3 +452,20 @@ pub fn parse_from_str<M: Message>(input: &str) -> ParseWithLocResult<M> {
     }
     Ok(m)
 }
+
+/// Parse text format message of type given by descriptor,
+/// e. g. a dynamic message type loaded at runtime.
+pub fn parse_dynamic_from_str(
+    d: &MessageDescriptor,
+    input: &str,
+) -> ParseWithLocResult<Box<dyn MessageDyn>> {
+    let mut m = d.new_instance();
+    merge_from_str(&mut *m, input)?;
+    if let Err(_) = m.check_initialized_dyn() {
+        return Err(ParseError {
+            error: ParseErrorWithoutLoc::MessageNotInitialized,
+            loc: Loc::start(),
+        });
+    }
+    Ok(m)
+}
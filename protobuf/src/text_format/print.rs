@@ -2,9 +2,15 @@ use std::fmt;
 use std::fmt::Write;
 
 use crate::message_dyn::MessageDyn;
+use crate::reflect::MessageFactory;
 use crate::reflect::MessageRef;
 use crate::reflect::ReflectFieldRef;
 use crate::reflect::ReflectValueRef;
+use crate::text_format::lexer::float;
+
+/// Printed instead of values of fields marked with `debug_redact`
+/// or `rustproto.sensitive` options.
+pub const REDACTED: &str = "[REDACTED]";
 
 #[doc(hidden)]
 pub fn quote_bytes_to(bytes: &[u8], buf: &mut String) {
@@ -85,6 +91,7 @@ fn print_field<F: FieldName>(
     first: &mut bool,
     field_name: F,
     value: ReflectValueRef,
+    redact: bool,
 ) {
     print_start_field(buf, pretty, indent, first, field_name);
 
@@ -94,7 +101,7 @@ fn print_field<F: FieldName>(
             if pretty {
                 buf.push_str("\n");
             }
-            print_to_internal(&m, buf, pretty, indent + 1);
+            print_to_internal(&m, buf, pretty, indent + 1, redact);
             do_indent(buf, pretty, indent);
             buf.push_str("}");
         }
@@ -102,7 +109,7 @@ fn print_field<F: FieldName>(
             buf.push_str(": ");
             match d.get_value_by_number(v) {
                 Some(e) => buf.push_str(e.get_name()),
-                None => write!(buf, ": {}", v).unwrap(),
+                None => write!(buf, "{}", v).unwrap(),
             }
         }
         ReflectValueRef::String(s) => {
@@ -129,84 +136,240 @@ fn print_field<F: FieldName>(
             write!(buf, ": {}", v).unwrap();
         }
         ReflectValueRef::F32(v) => {
-            write!(buf, ": {}", v).unwrap();
+            buf.push_str(": ");
+            buf.push_str(&float::format_protobuf_float32(v));
         }
         ReflectValueRef::F64(v) => {
-            write!(buf, ": {}", v).unwrap();
+            buf.push_str(": ");
+            buf.push_str(&float::format_protobuf_float(v));
         }
     }
 
     print_end_field(buf, pretty);
 }
 
-fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usize) {
+/// Print single field in multi-line format with redaction, used by [`diff`](super::diff).
+pub(crate) fn print_field_pretty(
+    buf: &mut String,
+    indent: usize,
+    field_name: &str,
+    value: ReflectValueRef,
+) {
+    print_field(buf, true, indent, &mut true, field_name, value, true);
+}
+
+fn print_redacted_field(buf: &mut String, pretty: bool, indent: usize, first: &mut bool, f: &str) {
+    print_start_field(buf, pretty, indent, first, f);
+    write!(buf, ": {}", REDACTED).unwrap();
+    print_end_field(buf, pretty);
+}
+
+/// Print `Any` as `[type.googleapis.com/pkg.Message] { ... }` if the type is known.
+fn print_any_value(
+    m: &MessageRef,
+    buf: &mut String,
+    pretty: bool,
+    indent: usize,
+    first: &mut bool,
+    redact: bool,
+) -> bool {
+    let d = m.descriptor_dyn();
+    let type_url = d.get_field_by_name("type_url").unwrap();
+    let type_url = type_url
+        .get_singular_field_or_default(&**m)
+        .to_str()
+        .unwrap();
+    let value = d.get_field_by_name("value").unwrap();
+    let value = value
+        .get_singular_field_or_default(&**m)
+        .to_bytes()
+        .unwrap();
+    let type_name = match type_url.rfind('/') {
+        Some(i) => &type_url[i + 1..],
+        None => return false,
+    };
+    let mut message = match MessageFactory::global().new_by_name(type_name) {
+        Some(message) => message,
+        None => return false,
+    };
+    if message.merge_from_bytes_dyn(value).is_err() {
+        return false;
+    }
+    let name = format!("[{}]", type_url);
+    let value = ReflectValueRef::Message(MessageRef::from(&*message));
+    print_field(buf, pretty, indent, first, &name[..], value, redact);
+    true
+}
+
+fn print_to_internal(m: &MessageRef, buf: &mut String, pretty: bool, indent: usize, redact: bool) {
     let d = m.descriptor_dyn();
     let mut first = true;
-    for f in d.fields() {
-        match f.get_reflect(&**m) {
-            ReflectFieldRef::Map(map) => {
-                for (k, v) in &map {
-                    print_start_field(buf, pretty, indent, &mut first, f.get_name());
-                    buf.push_str(" {");
-                    if pretty {
-                        buf.push_str("\n");
-                    }
+    let is_any = d.full_name() == "google.protobuf.Any"
+        && print_any_value(m, buf, pretty, indent, &mut first, redact);
+    // `Any` fields are printed only if the value cannot be expanded
+    if !is_any {
+        for f in d.fields() {
+            if redact && f.is_redacted() {
+                // Single marker, so the number of elements is not printed either
+                let is_set = match f.get_reflect(&**m) {
+                    ReflectFieldRef::Map(map) => !map.is_empty(),
+                    ReflectFieldRef::Repeated(repeated) => !repeated.is_empty(),
+                    ReflectFieldRef::Optional(optional) => optional.is_some(),
+                };
+                if is_set {
+                    print_redacted_field(buf, pretty, indent, &mut first, f.get_name());
+                }
+                continue;
+            }
+            match f.get_reflect(&**m) {
+                ReflectFieldRef::Map(map) => {
+                    for (k, v) in &map {
+                        print_start_field(buf, pretty, indent, &mut first, f.get_name());
+                        buf.push_str(" {");
+                        if pretty {
+                            buf.push_str("\n");
+                        }
 
-                    let mut entry_first = true;
+                        let mut entry_first = true;
 
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "key", k);
-                    print_field(buf, pretty, indent + 1, &mut entry_first, "value", v);
-                    do_indent(buf, pretty, indent);
-                    buf.push_str("}");
-                    print_end_field(buf, pretty);
+                        print_field(buf, pretty, indent + 1, &mut entry_first, "key", k, redact);
+                        print_field(
+                            buf,
+                            pretty,
+                            indent + 1,
+                            &mut entry_first,
+                            "value",
+                            v,
+                            redact,
+                        );
+                        do_indent(buf, pretty, indent);
+                        buf.push_str("}");
+                        print_end_field(buf, pretty);
+                    }
                 }
-            }
-            ReflectFieldRef::Repeated(repeated) => {
-                // TODO: do not print zeros for v3
-                for v in repeated {
-                    print_field(buf, pretty, indent, &mut first, f.get_name(), v);
+                ReflectFieldRef::Repeated(repeated) => {
+                    // TODO: do not print zeros for v3
+                    for v in repeated {
+                        print_field(buf, pretty, indent, &mut first, f.get_name(), v, redact);
+                    }
                 }
-            }
-            ReflectFieldRef::Optional(optional) => {
-                if let Some(v) = optional {
-                    print_field(buf, pretty, indent, &mut first, f.get_name(), v);
+                ReflectFieldRef::Optional(optional) => {
+                    if let Some(v) = optional {
+                        print_field(buf, pretty, indent, &mut first, f.get_name(), v, redact);
+                    }
                 }
             }
         }
     }
 
-    let unknown_fields = m.get_unknown_fields_dyn();
-    let mut numbers: Vec<u32> = m.get_unknown_fields_dyn().iter().map(|(n, _)| n).collect();
+    let extension_fields;
+    let unknown_fields = match m.get_extension_fields_dyn() {
+        Some(e) if !e.is_empty() => {
+            let mut fields = m.get_unknown_fields_dyn().clone();
+            e.append_to_unknown(&mut fields);
+            extension_fields = fields;
+            &extension_fields
+        }
+        _ => m.get_unknown_fields_dyn(),
+    };
+    let mut numbers: Vec<u32> = unknown_fields.iter().map(|(n, _)| n).collect();
     // Sort for stable output
     numbers.sort();
+    let factory = MessageFactory::global();
     for &n in &numbers {
+        if let Some(ext) = factory.extension_by_number(d.full_name(), n) {
+            let values = ext
+                .values_from_unknown(unknown_fields.get(n).unwrap())
+                .filter(|values| !values.is_empty());
+            if let Some(mut values) = values {
+                if !ext.is_repeated() {
+                    // Last value wins, like when parsing
+                    values.drain(..values.len() - 1);
+                }
+                let name = format!("[{}]", ext.full_name());
+                for v in &values {
+                    let v = v.as_value_ref();
+                    print_field(buf, pretty, indent, &mut first, &name[..], v, redact);
+                }
+                continue;
+            }
+        }
         for v in unknown_fields.get(n).unwrap() {
             // TODO: try decode nested message for length-delimited
-            print_field(buf, pretty, indent, &mut first, n, v.to_reflect_value_ref());
+            print_field(
+                buf,
+                pretty,
+                indent,
+                &mut first,
+                n,
+                v.to_reflect_value_ref(),
+                redact,
+            );
         }
     }
 }
 
 /// Text-format
+///
+/// Extensions and `Any` values of types registered in
+/// [`MessageFactory::global`] are printed using bracket syntax.
+///
+/// Values of fields marked with `debug_redact` or `rustproto.sensitive`
+/// options are replaced with [`REDACTED`].
 pub fn print_to(m: &dyn MessageDyn, buf: &mut String) {
-    print_to_internal(&MessageRef::from(m), buf, false, 0)
+    print_to_internal(&MessageRef::from(m), buf, false, 0, true)
 }
 
-fn print_to_string_internal(m: &dyn MessageDyn, pretty: bool) -> String {
+fn print_to_string_internal(m: &dyn MessageDyn, pretty: bool, redact: bool) -> String {
     let mut r = String::new();
-    print_to_internal(&MessageRef::from(m), &mut r, pretty, 0);
+    print_to_internal(&MessageRef::from(m), &mut r, pretty, 0, redact);
     r.to_string()
 }
 
 /// Text-format
+///
+/// Values of fields marked with `debug_redact` or `rustproto.sensitive`
+/// options are replaced with [`REDACTED`], so such output cannot be parsed back;
+/// use [`print_to_string_unredacted`] when it needs to round-trip.
 pub fn print_to_string(m: &dyn MessageDyn) -> String {
-    print_to_string_internal(m, false)
+    print_to_string_internal(m, false, true)
+}
+
+/// Text-format including values of fields marked as sensitive.
+pub fn print_to_string_unredacted(m: &dyn MessageDyn) -> String {
+    print_to_string_internal(m, false, false)
 }
 
 /// Text-format to `fmt::Formatter`.
+///
+/// Values of sensitive fields are redacted, see [`Unredacted`]
+/// to print them.
 pub fn fmt(m: &dyn MessageDyn, f: &mut fmt::Formatter) -> fmt::Result {
     let pretty = f.alternate();
-    f.write_str(&print_to_string_internal(m, pretty))
+    f.write_str(&print_to_string_internal(m, pretty, true))
+}
+
+/// Wrapper to format message including values of fields marked as sensitive.
+///
+/// ```
+/// # use protobuf::text_format::Unredacted;
+/// # use protobuf::well_known_types::StringValue;
+/// let m = StringValue::new();
+/// println!("{:?}", Unredacted(&m));
+/// ```
+pub struct Unredacted<'a>(pub &'a dyn MessageDyn);
+
+impl<'a> fmt::Debug for Unredacted<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pretty = f.alternate();
+        f.write_str(&print_to_string_internal(self.0, pretty, false))
+    }
+}
+
+impl<'a> fmt::Display for Unredacted<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
 }
 
 #[cfg(test)]
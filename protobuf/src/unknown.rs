@@ -1,12 +1,16 @@
+use std::cmp;
 use std::collections::hash_map;
 use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::default::Default;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::mem;
 use std::slice;
 
 use crate::clear::Clear;
+use crate::shrink_to_fit::ShrinkToFit;
+use crate::space_used::SpaceUsed;
 
 use crate::reflect::ReflectValueRef;
 use crate::rt;
@@ -90,6 +94,7 @@ impl UnknownValue {
 /// Reference to unknown value.
 ///
 /// See [`UnknownFields`](crate::UnknownFields) for explanations.
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum UnknownValueRef<'o> {
     /// 32-bit unknown
     Fixed32(u32),
@@ -120,12 +125,21 @@ impl<'o> UnknownValueRef<'o> {
             UnknownValueRef::LengthDelimited(v) => ReflectValueRef::Bytes(v),
         }
     }
+
+    pub(crate) fn to_unknown_value(&self) -> UnknownValue {
+        match *self {
+            UnknownValueRef::Fixed32(v) => UnknownValue::Fixed32(v),
+            UnknownValueRef::Fixed64(v) => UnknownValue::Fixed64(v),
+            UnknownValueRef::Varint(v) => UnknownValue::Varint(v),
+            UnknownValueRef::LengthDelimited(v) => UnknownValue::LengthDelimited(v.to_vec()),
+        }
+    }
 }
 
 /// Field unknown values.
 ///
 /// See [`UnknownFields`](crate::UnknownFields) for explanations.
-#[derive(Clone, PartialEq, Eq, Debug, Default, Hash)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Hash)]
 pub struct UnknownValues {
     /// 32-bit unknowns
     pub fixed32: Vec<u32>,
@@ -238,6 +252,47 @@ impl Hash for UnknownFields {
     }
 }
 
+/// Fields are compared in field number order.
+impl PartialOrd for UnknownFields {
+    fn partial_cmp(&self, other: &UnknownFields) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for UnknownFields {
+    fn cmp(&self, other: &UnknownFields) -> cmp::Ordering {
+        fn sorted(map: &HashMap<u32, UnknownValues>) -> Vec<(&u32, &UnknownValues)> {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|&(k, _)| *k);
+            entries
+        }
+
+        let a = self.fields.as_ref().map(|m| sorted(m));
+        let b = other.fields.as_ref().map(|m| sorted(m));
+        a.cmp(&b)
+    }
+}
+
+impl SpaceUsed for UnknownFields {
+    fn space_used_excluding_self(&self) -> usize {
+        match &self.fields {
+            Some(map) => {
+                mem::size_of::<HashMap<u32, UnknownValues>>() + map.space_used_excluding_self()
+            }
+            None => 0,
+        }
+    }
+}
+
+impl SpaceUsed for UnknownValues {
+    fn space_used_excluding_self(&self) -> usize {
+        self.fixed32.space_used_excluding_self()
+            + self.fixed64.space_used_excluding_self()
+            + self.varint.space_used_excluding_self()
+            + self.length_delimited.space_used_excluding_self()
+    }
+}
+
 impl UnknownFields {
     /// Empty unknown fields
     pub const fn new() -> UnknownFields {
@@ -327,6 +382,26 @@ impl Clear for UnknownFields {
     }
 }
 
+/// Map is released if there are no unknown fields.
+impl ShrinkToFit for UnknownFields {
+    fn shrink_to_fit(&mut self) {
+        match &mut self.fields {
+            Some(fields) if fields.is_empty() => self.fields = None,
+            Some(fields) => ShrinkToFit::shrink_to_fit(&mut **fields),
+            None => {}
+        }
+    }
+}
+
+impl ShrinkToFit for UnknownValues {
+    fn shrink_to_fit(&mut self) {
+        self.fixed32.shrink_to_fit();
+        self.fixed64.shrink_to_fit();
+        self.varint.shrink_to_fit();
+        ShrinkToFit::shrink_to_fit(&mut self.length_delimited);
+    }
+}
+
 impl<'a> IntoIterator for &'a UnknownFields {
     type Item = (u32, &'a UnknownValues);
     type IntoIter = UnknownFieldsIter<'a>;
@@ -382,4 +457,23 @@ mod test {
 
         assert_eq!(hash(&unknown_fields_1), hash(&unknown_fields_2));
     }
+
+    #[test]
+    fn unknown_fields_ord() {
+        let mut unknown_fields_1 = UnknownFields::new();
+        let mut unknown_fields_2 = UnknownFields::new();
+
+        unknown_fields_1.add_varint(10, 1);
+        unknown_fields_1.add_varint(20, 1);
+
+        unknown_fields_2.add_varint(20, 1);
+        unknown_fields_2.add_varint(10, 1);
+        assert_eq!(
+            std::cmp::Ordering::Equal,
+            unknown_fields_1.cmp(&unknown_fields_2)
+        );
+
+        unknown_fields_2.add_varint(10, 2);
+        assert!(unknown_fields_1 < unknown_fields_2);
+    }
 }
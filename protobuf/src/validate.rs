@@ -0,0 +1,427 @@
+//! Validation rules declared in `.proto` files.
+//!
+//! Rules are specified with `rustproto.validate_*` field options:
+//!
+//! ```protobuf
+//! import "rustproto.proto";
+//!
+//! message User {
+//!     string name = 1 [(rustproto.validate_min_len) = 1, (rustproto.validate_max_len) = 64];
+//!     int32 age = 2 [(rustproto.validate_gte) = 0, (rustproto.validate_lte) = 150];
+//!     repeated string emails = 3 [(rustproto.validate_max_items) = 10];
+//!     Address address = 4 [(rustproto.validate_required) = true];
+//! }
+//! ```
+//!
+//! and checked by [`Message::validate`](crate::Message::validate)
+//! which is generated for messages with rules.
+//! Nested messages are validated recursively.
+//!
+//! `validate_pattern` rule requires `with-regex` feature of `protobuf` crate,
+//! invalid regular expression is reported as a violation.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "regex")]
+use crate::lazy_v2::LazyV2;
+
+/// Single rule violation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldViolation {
+    /// Path to the field, like `foo.bar[1].baz`.
+    pub field: String,
+    /// Human readable description of violated rule.
+    pub description: String,
+}
+
+impl fmt::Display for FieldViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.description)
+    }
+}
+
+/// Error returned by [`Message::validate`](crate::Message::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// All violations found in the message and nested messages.
+    pub violations: Vec<FieldViolation>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "validation failed")?;
+        for (i, v) in self.violations.iter().enumerate() {
+            write!(f, "{} {}", if i == 0 { ":" } else { ";" }, v)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValidationError {}
+
+/// Collector of violations, used in generated code.
+#[derive(Debug, Default)]
+pub struct Violations {
+    // Path to currently validated message
+    path: String,
+    violations: Vec<FieldViolation>,
+}
+
+impl Violations {
+    /// Empty collector.
+    pub fn new() -> Violations {
+        Violations::default()
+    }
+
+    fn with_path_suffix<F>(&mut self, suffix: fmt::Arguments, f: F)
+    where
+        F: FnOnce(&mut Violations),
+    {
+        let len = self.path.len();
+        fmt::Write::write_fmt(&mut self.path, suffix).unwrap();
+        f(self);
+        self.path.truncate(len);
+    }
+
+    /// Validate nested message.
+    pub fn with_field<F>(&mut self, field: &str, f: F)
+    where
+        F: FnOnce(&mut Violations),
+    {
+        let dot = if self.path.is_empty() { "" } else { "." };
+        self.with_path_suffix(format_args!("{}{}", dot, field), f)
+    }
+
+    /// Validate an element of repeated field.
+    pub fn with_index<F>(&mut self, field: &str, index: usize, f: F)
+    where
+        F: FnOnce(&mut Violations),
+    {
+        self.with_field(field, |v| {
+            v.with_path_suffix(format_args!("[{}]", index), f)
+        })
+    }
+
+    /// Validate a value of map field.
+    pub fn with_key<K: fmt::Debug, F>(&mut self, field: &str, key: &K, f: F)
+    where
+        F: FnOnce(&mut Violations),
+    {
+        self.with_field(field, |v| {
+            v.with_path_suffix(format_args!("[{:?}]", key), f)
+        })
+    }
+
+    /// Record a violation, empty `field` means current path.
+    pub fn add(&mut self, field: &str, description: String) {
+        let field = match (self.path.is_empty(), field.is_empty()) {
+            (true, _) => field.to_owned(),
+            (false, true) => self.path.clone(),
+            (false, false) => format!("{}.{}", self.path, field),
+        };
+        self.violations.push(FieldViolation { field, description });
+    }
+
+    /// Violations collected so far.
+    pub fn violations(&self) -> &[FieldViolation] {
+        &self.violations
+    }
+
+    /// `Ok` if there are no violations.
+    pub fn into_result(self) -> Result<(), ValidationError> {
+        match self.violations.is_empty() {
+            true => Ok(()),
+            false => Err(ValidationError {
+                violations: self.violations,
+            }),
+        }
+    }
+}
+
+/// Numbers which can be checked with `validate_gte` and `validate_lte`.
+pub trait ValidateNumber: Copy + fmt::Display {
+    /// Compare the value with a bound, `None` if the value is NaN.
+    ///
+    /// Integers are compared exactly, not converted to `f64`.
+    fn cmp_bound(self, bound: f64) -> Option<Ordering>;
+}
+
+macro_rules! impl_validate_number_int {
+    ($($t:ty: $max_exclusive:expr,)*) => {
+        $(
+            impl ValidateNumber for $t {
+                fn cmp_bound(self, bound: f64) -> Option<Ordering> {
+                    if bound.is_nan() {
+                        return None;
+                    }
+                    let floor = bound.floor();
+                    if floor < <$t>::MIN as f64 {
+                        return Some(Ordering::Greater);
+                    }
+                    if floor >= $max_exclusive {
+                        return Some(Ordering::Less);
+                    }
+                    // Exact: `floor` is integral in the type range
+                    Some(match self.cmp(&(floor as $t)) {
+                        Ordering::Equal if floor != bound => Ordering::Less,
+                        ordering => ordering,
+                    })
+                }
+            }
+        )*
+    }
+}
+
+impl_validate_number_int! {
+    i32: 2147483648.0,
+    i64: 9223372036854775808.0,
+    u32: 4294967296.0,
+    u64: 18446744073709551616.0,
+}
+
+impl ValidateNumber for f32 {
+    fn cmp_bound(self, bound: f64) -> Option<Ordering> {
+        (self as f64).partial_cmp(&bound)
+    }
+}
+
+impl ValidateNumber for f64 {
+    fn cmp_bound(self, bound: f64) -> Option<Ordering> {
+        self.partial_cmp(&bound)
+    }
+}
+
+/// Compiled regular expression of `validate_pattern` rule.
+#[cfg(feature = "regex")]
+pub struct Pattern {
+    pattern: &'static str,
+    regex: LazyV2<Result<regex::Regex, regex::Error>>,
+}
+
+#[cfg(feature = "regex")]
+impl Pattern {
+    /// Pattern is compiled on first use.
+    pub const fn new(pattern: &'static str) -> Pattern {
+        Pattern {
+            pattern,
+            regex: LazyV2::INIT,
+        }
+    }
+
+    /// Check if the string matches the pattern.
+    ///
+    /// Error is returned if pattern is not a valid regular expression.
+    pub fn is_match(&'static self, s: &str) -> Result<bool, &'static regex::Error> {
+        match self.regex.get(|| regex::Regex::new(self.pattern)) {
+            Ok(regex) => Ok(regex.is_match(s)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The pattern as specified in `.proto` file.
+    pub fn as_str(&self) -> &'static str {
+        self.pattern
+    }
+}
+
+#[doc(hidden)]
+pub fn validate_range<T: ValidateNumber>(
+    v: &mut Violations,
+    field: &str,
+    value: T,
+    gte: Option<f64>,
+    lte: Option<f64>,
+) {
+    if let Some(gte) = gte {
+        match value.cmp_bound(gte) {
+            Some(Ordering::Greater) | Some(Ordering::Equal) => {}
+            Some(Ordering::Less) | None => {
+                v.add(field, format!("value {} is less than {}", value, gte))
+            }
+        }
+    }
+    if let Some(lte) = lte {
+        match value.cmp_bound(lte) {
+            Some(Ordering::Less) | Some(Ordering::Equal) => {}
+            Some(Ordering::Greater) | None => {
+                v.add(field, format!("value {} is greater than {}", value, lte))
+            }
+        }
+    }
+}
+
+fn validate_len_impl(
+    v: &mut Violations,
+    field: &str,
+    len: usize,
+    what: &str,
+    min: Option<u64>,
+    max: Option<u64>,
+) {
+    let len = len as u64;
+    if let Some(min) = min {
+        if len < min {
+            v.add(
+                field,
+                format!("length {} is less than {} {}", len, min, what),
+            );
+        }
+    }
+    if let Some(max) = max {
+        if len > max {
+            v.add(
+                field,
+                format!("length {} is greater than {} {}", len, max, what),
+            );
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn validate_str_len(
+    v: &mut Violations,
+    field: &str,
+    value: &str,
+    min: Option<u64>,
+    max: Option<u64>,
+) {
+    validate_len_impl(v, field, value.chars().count(), "chars", min, max);
+}
+
+#[doc(hidden)]
+pub fn validate_bytes_len(
+    v: &mut Violations,
+    field: &str,
+    value: &[u8],
+    min: Option<u64>,
+    max: Option<u64>,
+) {
+    validate_len_impl(v, field, value.len(), "bytes", min, max);
+}
+
+#[doc(hidden)]
+#[cfg(feature = "regex")]
+pub fn validate_pattern(v: &mut Violations, field: &str, value: &str, pattern: &'static Pattern) {
+    match pattern.is_match(value) {
+        Ok(true) => {}
+        Ok(false) => v.add(
+            field,
+            format!("value does not match pattern `{}`", pattern.as_str()),
+        ),
+        Err(e) => v.add(
+            field,
+            format!(
+                "pattern `{}` is not a valid regular expression: {}",
+                pattern.as_str(),
+                e
+            ),
+        ),
+    }
+}
+
+#[doc(hidden)]
+pub fn validate_required(v: &mut Violations, field: &str, is_set: bool) {
+    if !is_set {
+        v.add(field, "required field is not set".to_owned());
+    }
+}
+
+#[doc(hidden)]
+pub fn validate_items(
+    v: &mut Violations,
+    field: &str,
+    len: usize,
+    min: Option<u64>,
+    max: Option<u64>,
+) {
+    validate_len_impl(v, field, len, "items", min, max);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        let mut v = Violations::new();
+        validate_required(&mut v, "a", false);
+        v.with_field("b", |v| {
+            v.with_index("c", 2, |v| validate_range(v, "", 10, Some(11.0), None));
+            v.with_key("d", &"k", |v| validate_str_len(v, "e", "", Some(1), None));
+        });
+        let fields: Vec<_> = v.violations().iter().map(|v| &v.field[..]).collect();
+        assert_eq!(vec!["a", "b.c[2]", "b.d[\"k\"].e"], fields);
+    }
+
+    #[test]
+    fn range() {
+        let mut v = Violations::new();
+        validate_range(&mut v, "f", 1.5f64, Some(0.0), Some(1.0));
+        validate_range(&mut v, "f", std::f32::NAN, Some(0.0), None);
+        validate_range(&mut v, "f", 7u64, Some(7.0), Some(7.0));
+        assert_eq!(2, v.violations().len());
+    }
+
+    #[test]
+    fn range_int() {
+        // 2^53 + 1 is equal to 2^53 when converted to `f64`
+        let bound = 9007199254740992.0;
+        let mut v = Violations::new();
+        validate_range(&mut v, "f", 9007199254740993i64, None, Some(bound));
+        validate_range(&mut v, "f", 9007199254740991u64, Some(bound), None);
+        validate_range(&mut v, "f", 2i32, Some(1.5), Some(1.5));
+        validate_range(&mut v, "f", std::u64::MAX, None, Some(1e30));
+        validate_range(&mut v, "f", std::i32::MIN, Some(-1e30), Some(std::f64::NAN));
+        let descriptions: Vec<_> = v.violations().iter().map(|v| &v.description[..]).collect();
+        assert_eq!(
+            vec![
+                "value 9007199254740993 is greater than 9007199254740992",
+                "value 9007199254740991 is less than 9007199254740992",
+                "value 2 is greater than 1.5",
+                "value -2147483648 is greater than NaN",
+            ],
+            descriptions
+        );
+    }
+
+    #[test]
+    fn len() {
+        let mut v = Violations::new();
+        validate_str_len(&mut v, "s", "ыы", None, Some(2));
+        validate_bytes_len(&mut v, "b", "ыы".as_bytes(), None, Some(2));
+        assert_eq!(
+            Err(ValidationError {
+                violations: vec![FieldViolation {
+                    field: "b".to_owned(),
+                    description: "length 4 is greater than 2 bytes".to_owned(),
+                }],
+            }),
+            v.into_result()
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn pattern() {
+        static P: Pattern = Pattern::new("^[a-z]+$");
+        let mut v = Violations::new();
+        validate_pattern(&mut v, "s", "abc", &P);
+        assert!(v.violations().is_empty());
+        validate_pattern(&mut v, "s", "ABC", &P);
+        assert_eq!(1, v.violations().len());
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn invalid_pattern() {
+        static P: Pattern = Pattern::new("(");
+        let mut v = Violations::new();
+        validate_pattern(&mut v, "s", "abc", &P);
+        assert_eq!(1, v.violations().len());
+        assert!(v.violations()[0]
+            .description
+            .starts_with("pattern `(` is not a valid regular expression"));
+    }
+}
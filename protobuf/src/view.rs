@@ -0,0 +1,241 @@
+//! Zero-copy read-only views of serialized messages.
+//!
+//! With `generate_view` codegen option enabled, for message `Foo`
+//! codegen also generates `FooView<'a>` which wraps serialized `Foo`
+//! and reads fields on demand: `string` and `bytes` fields are returned
+//! as `&'a str` and `&'a [u8]` pointing into the input buffer, and message
+//! fields are returned as views of nested messages.
+//! Views neither parse the whole message nor allocate.
+//!
+//! Each accessor scans the serialized message, finding the last
+//! occurrence of the field as the parser does, so views are efficient
+//! when only a few fields are read; to read most fields, parse the message.
+//! Input is validated only as far as it is read: accessors
+//! return errors for malformed input.
+//!
+//! Fields of serialized messages can also be iterated without generated code:
+//!
+//! ```
+//! use protobuf::view;
+//! use protobuf::UnknownValueRef;
+//!
+//! // field 1 = 7, field 2 = "ab"
+//! let bytes = [0x08, 0x07, 0x12, 0x02, b'a', b'b'];
+//! let fields: Vec<_> = view::fields(&bytes).collect::<Result<_, _>>().unwrap();
+//! assert_eq!(2, fields.len());
+//! assert_eq!((1, UnknownValueRef::Varint(7)), (fields[0].number, fields[0].value));
+//! assert_eq!(UnknownValueRef::LengthDelimited(b"ab"), fields[1].value);
+//! ```
+
+use std::fmt;
+
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::reflect::types::ProtobufType;
+use crate::varint::encode_varint64;
+use crate::wire::MAX_VARINT_LEN;
+use crate::wire_format::WireType;
+use crate::CodedInputStream;
+use crate::UnknownValueRef;
+
+/// Read-only view of serialized message, implemented by generated `FooView` types.
+pub trait MessageView<'a>: Copy + fmt::Debug {
+    /// Wrap serialized message.
+    ///
+    /// Input is not validated until fields are read.
+    fn from_bytes(bytes: &'a [u8]) -> Self;
+
+    /// Serialized message this view reads.
+    fn as_bytes(&self) -> &'a [u8];
+}
+
+/// Field of serialized message.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ViewField<'a> {
+    /// Field number
+    pub number: u32,
+    /// Field value, length-delimited values point into the input buffer
+    pub value: UnknownValueRef<'a>,
+}
+
+/// Iterator over fields of serialized message, created with [`fields`].
+///
+/// Groups are skipped. Iteration stops after the first error.
+pub struct ViewFields<'a> {
+    bytes: &'a [u8],
+    is: CodedInputStream<'a>,
+    failed: bool,
+}
+
+/// Iterate fields of serialized message in the order they are encoded.
+pub fn fields<'a>(bytes: &'a [u8]) -> ViewFields<'a> {
+    ViewFields {
+        bytes,
+        is: CodedInputStream::from_bytes(bytes),
+        failed: false,
+    }
+}
+
+impl<'a> ViewFields<'a> {
+    fn read_field(&mut self) -> ProtobufResult<Option<ViewField<'a>>> {
+        loop {
+            if self.is.eof()? {
+                return Ok(None);
+            }
+            let (number, wire_type) = self.is.read_tag_unpack()?;
+            let value = match wire_type {
+                WireType::WireTypeVarint => UnknownValueRef::Varint(self.is.read_raw_varint64()?),
+                WireType::WireTypeFixed64 => {
+                    UnknownValueRef::Fixed64(self.is.read_raw_little_endian64()?)
+                }
+                WireType::WireTypeFixed32 => {
+                    UnknownValueRef::Fixed32(self.is.read_raw_little_endian32()?)
+                }
+                WireType::WireTypeLengthDelimited => {
+                    let len = self.is.read_raw_varint32()?;
+                    let pos = self.is.pos() as usize;
+                    self.is.skip_raw_bytes(len)?;
+                    UnknownValueRef::LengthDelimited(&self.bytes[pos..pos + len as usize])
+                }
+                WireType::WireTypeStartGroup | WireType::WireTypeEndGroup => {
+                    self.is.skip_field(wire_type)?;
+                    continue;
+                }
+            };
+            return Ok(Some(ViewField { number, value }));
+        }
+    }
+}
+
+impl<'a> Iterator for ViewFields<'a> {
+    type Item = ProtobufResult<ViewField<'a>>;
+
+    fn next(&mut self) -> Option<ProtobufResult<ViewField<'a>>> {
+        if self.failed {
+            return None;
+        }
+        match self.read_field() {
+            Ok(field) => field.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn check_wire_type(value: &UnknownValueRef, expected: WireType) -> ProtobufResult<()> {
+    if value.wire_type() != expected {
+        return Err(ProtobufError::WireError(WireError::UnexpectedWireType(
+            value.wire_type(),
+        )));
+    }
+    Ok(())
+}
+
+/// Last occurrence of the field.
+pub(crate) fn last_field<'a>(
+    bytes: &'a [u8],
+    field_number: u32,
+) -> ProtobufResult<Option<UnknownValueRef<'a>>> {
+    let mut r = None;
+    for field in fields(bytes) {
+        let field = field?;
+        if field.number == field_number {
+            r = Some(field.value);
+        }
+    }
+    Ok(r)
+}
+
+/// Decode scalar value with `ProtobufType::read` from a small stack buffer.
+pub(crate) fn decode_scalar<T: ProtobufType>(
+    value: UnknownValueRef,
+) -> ProtobufResult<T::ProtobufValue> {
+    check_wire_type(&value, T::WIRE_TYPE)?;
+    let mut buf = [0; MAX_VARINT_LEN];
+    let len = match value {
+        UnknownValueRef::Varint(v) => encode_varint64(v, &mut buf),
+        UnknownValueRef::Fixed32(v) => {
+            buf[..4].copy_from_slice(&v.to_le_bytes());
+            4
+        }
+        UnknownValueRef::Fixed64(v) => {
+            buf[..8].copy_from_slice(&v.to_le_bytes());
+            8
+        }
+        UnknownValueRef::LengthDelimited(..) => unreachable!(),
+    };
+    let mut is = CodedInputStream::from_bytes(&buf[..len]);
+    T::read(&mut is)
+}
+
+/// Length-delimited value of field.
+pub(crate) fn length_delimited<'a>(value: UnknownValueRef<'a>) -> ProtobufResult<&'a [u8]> {
+    check_wire_type(&value, WireType::WireTypeLengthDelimited)?;
+    match value {
+        UnknownValueRef::LengthDelimited(bytes) => Ok(bytes),
+        _ => unreachable!(),
+    }
+}
+
+/// All occurrences of length-delimited field.
+pub(crate) fn repeated_length_delimited<'a>(
+    bytes: &'a [u8],
+    field_number: u32,
+) -> impl Iterator<Item = ProtobufResult<&'a [u8]>> + 'a {
+    fields(bytes).filter_map(move |field| match field {
+        Ok(field) if field.number == field_number => Some(length_delimited(field.value)),
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::reflect::types::ProtobufTypeInt32;
+    use crate::reflect::types::ProtobufTypeSint64;
+
+    #[test]
+    fn fields_skip_groups() {
+        // field 1 = 150, group 2 with field 3 = 1, field 4 = fixed32 1
+        let bytes = [0x08, 0x96, 0x01, 0x13, 0x18, 0x01, 0x14, 0x25, 1, 0, 0, 0];
+        let fields: Vec<_> = fields(&bytes).collect::<ProtobufResult<_>>().unwrap();
+        assert_eq!(
+            vec![
+                ViewField {
+                    number: 1,
+                    value: UnknownValueRef::Varint(150)
+                },
+                ViewField {
+                    number: 4,
+                    value: UnknownValueRef::Fixed32(1)
+                },
+            ],
+            fields
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let bytes = [0x08, 0x01, 0x12, 0x05, b'a'];
+        let mut iter = fields(&bytes);
+        assert!(iter.next().unwrap().is_ok());
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn last_field_wins() {
+        // field 1 = 1, field 2 = -1 (sint64), field 1 = 3
+        let bytes = [0x08, 0x01, 0x10, 0x01, 0x08, 0x03];
+        let value = last_field(&bytes, 1).unwrap().unwrap();
+        assert_eq!(3, decode_scalar::<ProtobufTypeInt32>(value).unwrap());
+        let value = last_field(&bytes, 2).unwrap().unwrap();
+        assert_eq!(-1, decode_scalar::<ProtobufTypeSint64>(value).unwrap());
+        assert!(length_delimited(value).is_err());
+        assert_eq!(None, last_field(&bytes, 3).unwrap());
+    }
+}
@@ -153,6 +153,21 @@ impl crate::Message for Any {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.type_url);
+        my_size += crate::rt::space_used_excluding_self(&self.value);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.type_url);
+        crate::rt::shrink_to_fit(&mut self.value);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -180,6 +195,23 @@ impl crate::Message for Any {
     }
 }
 
+impl crate::rt::SpaceUsed for Any {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Any {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Any {
+    fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+    }
+}
+
 impl crate::Clear for Any {
     fn clear(&mut self) {
         self.type_url.clear();
@@ -361,3 +393,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
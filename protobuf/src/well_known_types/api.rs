@@ -264,6 +264,31 @@ impl crate::Message for Api {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.methods);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.version);
+        my_size += crate::rt::space_used_excluding_self(&self.source_context);
+        my_size += crate::rt::space_used_excluding_self(&self.mixins);
+        my_size += crate::rt::space_used_excluding_self(&self.syntax);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.methods);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.version);
+        crate::rt::shrink_to_fit(&mut self.source_context);
+        crate::rt::shrink_to_fit(&mut self.mixins);
+        crate::rt::shrink_to_fit(&mut self.syntax);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -296,6 +321,27 @@ impl crate::Message for Api {
     }
 }
 
+impl crate::rt::SpaceUsed for Api {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Api {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Api {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.methods, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+        crate::rt::detach_nested_messages(&mut self.source_context, worklist);
+        crate::rt::detach_nested_messages(&mut self.mixins, worklist);
+    }
+}
+
 impl crate::Clear for Api {
     fn clear(&mut self) {
         self.name.clear();
@@ -523,6 +569,31 @@ impl crate::Message for Method {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.request_type_url);
+        my_size += crate::rt::space_used_excluding_self(&self.request_streaming);
+        my_size += crate::rt::space_used_excluding_self(&self.response_type_url);
+        my_size += crate::rt::space_used_excluding_self(&self.response_streaming);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.syntax);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.request_type_url);
+        crate::rt::shrink_to_fit(&mut self.request_streaming);
+        crate::rt::shrink_to_fit(&mut self.response_type_url);
+        crate::rt::shrink_to_fit(&mut self.response_streaming);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.syntax);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -555,6 +626,24 @@ impl crate::Message for Method {
     }
 }
 
+impl crate::rt::SpaceUsed for Method {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Method {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Method {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for Method {
     fn clear(&mut self) {
         self.name.clear();
@@ -684,6 +773,21 @@ impl crate::Message for Mixin {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.root);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.root);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -711,6 +815,23 @@ impl crate::Message for Mixin {
     }
 }
 
+impl crate::rt::SpaceUsed for Mixin {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Mixin {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Mixin {
+    fn take_nested_messages(&mut self, _worklist: &mut crate::rt::DropWorklist) {
+    }
+}
+
 impl crate::Clear for Mixin {
     fn clear(&mut self) {
         self.name.clear();
@@ -979,3 +1100,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
@@ -49,6 +49,8 @@ pub use self::wrappers::BoolValue;
 pub use self::wrappers::StringValue;
 pub use self::wrappers::BytesValue;
 
+pub use crate::well_known_types_util::*;
+
 #[doc(hidden)]
 pub mod file_descriptors {
     pub use super::any::file_descriptor as any;
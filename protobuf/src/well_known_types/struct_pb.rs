@@ -109,6 +109,19 @@ impl crate::Message for Struct {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.fields);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.fields);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -131,6 +144,24 @@ impl crate::Message for Struct {
     }
 }
 
+impl crate::rt::SpaceUsed for Struct {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Struct {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Struct {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.fields, worklist);
+    }
+}
+
 impl crate::Clear for Struct {
     fn clear(&mut self) {
         self.fields.clear();
@@ -276,6 +307,13 @@ impl Value {
         }
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_string_value(&mut self, v: ::std::string::String) -> ::std::string::String {
+        let r = self.take_string_value();
+        self.set_string_value(v);
+        r
+    }
+
     // bool bool_value = 4;
 
     pub fn get_bool_value(&self) -> bool {
@@ -350,6 +388,13 @@ impl Value {
         }
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_struct_value(&mut self, v: Struct) -> Struct {
+        let r = self.take_struct_value();
+        self.set_struct_value(v);
+        r
+    }
+
     // .google.protobuf.ListValue list_value = 6;
 
     pub fn get_list_value(&self) -> &ListValue {
@@ -399,6 +444,13 @@ impl Value {
         }
     }
 
+    // Replace field value, returning the previous one
+    pub fn replace_list_value(&mut self, v: ListValue) -> ListValue {
+        let r = self.take_list_value();
+        self.set_list_value(v);
+        r
+    }
+
     fn generated_message_descriptor_data() -> crate::reflect::GeneratedMessageDescriptorData {
         let mut fields = ::std::vec::Vec::new();
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
@@ -406,24 +458,28 @@ impl Value {
             Value::has_null_value,
             Value::get_null_value,
             Value::set_null_value,
+            Value::clear_null_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
             "number_value",
             Value::has_number_value,
             Value::get_number_value,
             Value::set_number_value,
+            Value::clear_number_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_deref_has_get_set_simpler_accessor::<_, _>(
             "string_value",
             Value::has_string_value,
             Value::get_string_value,
             Value::set_string_value,
+            Value::clear_string_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_copy_has_get_set_simpler_accessors::<_, _>(
             "bool_value",
             Value::has_bool_value,
             Value::get_bool_value,
             Value::set_bool_value,
+            Value::clear_bool_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, Struct>(
             "struct_value",
@@ -431,6 +487,7 @@ impl Value {
             Value::get_struct_value,
             Value::mut_struct_value,
             Value::set_struct_value,
+            Value::clear_struct_value,
         ));
         fields.push(crate::reflect::rt::v2::make_oneof_message_has_get_mut_set_accessor::<_, ListValue>(
             "list_value",
@@ -438,6 +495,7 @@ impl Value {
             Value::get_list_value,
             Value::mut_list_value,
             Value::set_list_value,
+            Value::clear_list_value,
         ));
         crate::reflect::GeneratedMessageDescriptorData::new_2::<Value>(
             "Value",
@@ -574,6 +632,50 @@ impl crate::Message for Value {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        if let ::std::option::Option::Some(ref v) = self.kind {
+            match v {
+                &value::Kind::null_value(v) => {
+                },
+                &value::Kind::number_value(v) => {
+                },
+                &value::Kind::string_value(ref v) => {
+                    my_size += crate::rt::space_used_excluding_self(v);
+                },
+                &value::Kind::bool_value(v) => {
+                },
+                &value::Kind::struct_value(ref v) => {
+                    my_size += crate::rt::space_used_excluding_self(v);
+                },
+                &value::Kind::list_value(ref v) => {
+                    my_size += crate::rt::space_used_excluding_self(v);
+                },
+            };
+        }
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        if let ::std::option::Option::Some(ref mut v) = self.kind {
+            match v {
+                &mut value::Kind::string_value(ref mut v) => {
+                    crate::rt::shrink_to_fit(v);
+                },
+                &mut value::Kind::struct_value(ref mut v) => {
+                    crate::rt::shrink_to_fit(v);
+                },
+                &mut value::Kind::list_value(ref mut v) => {
+                    crate::rt::shrink_to_fit(v);
+                },
+                _ => {},
+            };
+        }
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -600,6 +702,34 @@ impl crate::Message for Value {
     }
 }
 
+impl crate::rt::SpaceUsed for Value {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Value {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Value {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        match self.kind.take() {
+            ::std::option::Option::Some(value::Kind::struct_value(mut v)) => {
+                crate::rt::DropIterative::take_nested_messages(&mut v, worklist);
+                self.kind = ::std::option::Option::Some(value::Kind::struct_value(v));
+            },
+            ::std::option::Option::Some(value::Kind::list_value(mut v)) => {
+                crate::rt::DropIterative::take_nested_messages(&mut v, worklist);
+                self.kind = ::std::option::Option::Some(value::Kind::list_value(v));
+            },
+            v => self.kind = v,
+        };
+    }
+}
+
 impl crate::Clear for Value {
     fn clear(&mut self) {
         self.kind = ::std::option::Option::None;
@@ -732,6 +862,19 @@ impl crate::Message for ListValue {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.values);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.values);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -758,6 +901,24 @@ impl crate::Message for ListValue {
     }
 }
 
+impl crate::rt::SpaceUsed for ListValue {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for ListValue {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for ListValue {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.values, worklist);
+    }
+}
+
 impl crate::Clear for ListValue {
     fn clear(&mut self) {
         self.values.clear();
@@ -797,6 +958,19 @@ impl crate::ProtobufEnum for NullValue {
         }
     }
 
+    fn name(&self) -> &'static str {
+        match *self {
+            NullValue::NULL_VALUE => "NULL_VALUE",
+        }
+    }
+
+    fn from_str(name: &str) -> ::std::option::Option<NullValue> {
+        match name {
+            "NULL_VALUE" => ::std::option::Option::Some(NullValue::NULL_VALUE),
+            _ => ::std::option::Option::None
+        }
+    }
+
     fn values() -> &'static [Self] {
         static values: &'static [NullValue] = &[
             NullValue::NULL_VALUE,
@@ -815,6 +989,12 @@ impl ::std::default::Default for NullValue {
     }
 }
 
+impl ::std::fmt::Display for NullValue {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.pad(crate::ProtobufEnum::name(self))
+    }
+}
+
 impl crate::reflect::ProtobufValue for NullValue {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
 }
@@ -970,3 +1150,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
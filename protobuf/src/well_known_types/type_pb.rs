@@ -209,6 +209,29 @@ impl crate::Message for Type {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.fields);
+        my_size += crate::rt::space_used_excluding_self(&self.oneofs);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.source_context);
+        my_size += crate::rt::space_used_excluding_self(&self.syntax);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.fields);
+        crate::rt::shrink_to_fit(&mut self.oneofs);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.source_context);
+        crate::rt::shrink_to_fit(&mut self.syntax);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -240,6 +263,26 @@ impl crate::Message for Type {
     }
 }
 
+impl crate::rt::SpaceUsed for Type {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Type {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Type {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.fields, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+        crate::rt::detach_nested_messages(&mut self.source_context, worklist);
+    }
+}
+
 impl crate::Clear for Type {
     fn clear(&mut self) {
         self.name.clear();
@@ -525,6 +568,37 @@ impl crate::Message for Field {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.kind);
+        my_size += crate::rt::space_used_excluding_self(&self.cardinality);
+        my_size += crate::rt::space_used_excluding_self(&self.number);
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.type_url);
+        my_size += crate::rt::space_used_excluding_self(&self.oneof_index);
+        my_size += crate::rt::space_used_excluding_self(&self.packed);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.json_name);
+        my_size += crate::rt::space_used_excluding_self(&self.default_value);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.kind);
+        crate::rt::shrink_to_fit(&mut self.cardinality);
+        crate::rt::shrink_to_fit(&mut self.number);
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.type_url);
+        crate::rt::shrink_to_fit(&mut self.oneof_index);
+        crate::rt::shrink_to_fit(&mut self.packed);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.json_name);
+        crate::rt::shrink_to_fit(&mut self.default_value);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -560,6 +634,24 @@ impl crate::Message for Field {
     }
 }
 
+impl crate::rt::SpaceUsed for Field {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Field {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Field {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for Field {
     fn clear(&mut self) {
         self.kind = crate::ProtobufEnumOrUnknown::new(field::Kind::TYPE_UNKNOWN);
@@ -643,6 +735,55 @@ pub mod field {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Kind::TYPE_UNKNOWN => "TYPE_UNKNOWN",
+                Kind::TYPE_DOUBLE => "TYPE_DOUBLE",
+                Kind::TYPE_FLOAT => "TYPE_FLOAT",
+                Kind::TYPE_INT64 => "TYPE_INT64",
+                Kind::TYPE_UINT64 => "TYPE_UINT64",
+                Kind::TYPE_INT32 => "TYPE_INT32",
+                Kind::TYPE_FIXED64 => "TYPE_FIXED64",
+                Kind::TYPE_FIXED32 => "TYPE_FIXED32",
+                Kind::TYPE_BOOL => "TYPE_BOOL",
+                Kind::TYPE_STRING => "TYPE_STRING",
+                Kind::TYPE_GROUP => "TYPE_GROUP",
+                Kind::TYPE_MESSAGE => "TYPE_MESSAGE",
+                Kind::TYPE_BYTES => "TYPE_BYTES",
+                Kind::TYPE_UINT32 => "TYPE_UINT32",
+                Kind::TYPE_ENUM => "TYPE_ENUM",
+                Kind::TYPE_SFIXED32 => "TYPE_SFIXED32",
+                Kind::TYPE_SFIXED64 => "TYPE_SFIXED64",
+                Kind::TYPE_SINT32 => "TYPE_SINT32",
+                Kind::TYPE_SINT64 => "TYPE_SINT64",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<Kind> {
+            match name {
+                "TYPE_UNKNOWN" => ::std::option::Option::Some(Kind::TYPE_UNKNOWN),
+                "TYPE_DOUBLE" => ::std::option::Option::Some(Kind::TYPE_DOUBLE),
+                "TYPE_FLOAT" => ::std::option::Option::Some(Kind::TYPE_FLOAT),
+                "TYPE_INT64" => ::std::option::Option::Some(Kind::TYPE_INT64),
+                "TYPE_UINT64" => ::std::option::Option::Some(Kind::TYPE_UINT64),
+                "TYPE_INT32" => ::std::option::Option::Some(Kind::TYPE_INT32),
+                "TYPE_FIXED64" => ::std::option::Option::Some(Kind::TYPE_FIXED64),
+                "TYPE_FIXED32" => ::std::option::Option::Some(Kind::TYPE_FIXED32),
+                "TYPE_BOOL" => ::std::option::Option::Some(Kind::TYPE_BOOL),
+                "TYPE_STRING" => ::std::option::Option::Some(Kind::TYPE_STRING),
+                "TYPE_GROUP" => ::std::option::Option::Some(Kind::TYPE_GROUP),
+                "TYPE_MESSAGE" => ::std::option::Option::Some(Kind::TYPE_MESSAGE),
+                "TYPE_BYTES" => ::std::option::Option::Some(Kind::TYPE_BYTES),
+                "TYPE_UINT32" => ::std::option::Option::Some(Kind::TYPE_UINT32),
+                "TYPE_ENUM" => ::std::option::Option::Some(Kind::TYPE_ENUM),
+                "TYPE_SFIXED32" => ::std::option::Option::Some(Kind::TYPE_SFIXED32),
+                "TYPE_SFIXED64" => ::std::option::Option::Some(Kind::TYPE_SFIXED64),
+                "TYPE_SINT32" => ::std::option::Option::Some(Kind::TYPE_SINT32),
+                "TYPE_SINT64" => ::std::option::Option::Some(Kind::TYPE_SINT64),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Kind] = &[
                 Kind::TYPE_UNKNOWN,
@@ -679,6 +820,12 @@ pub mod field {
         }
     }
 
+    impl ::std::fmt::Display for Kind {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Kind {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -714,6 +861,25 @@ pub mod field {
             }
         }
 
+        fn name(&self) -> &'static str {
+            match *self {
+                Cardinality::CARDINALITY_UNKNOWN => "CARDINALITY_UNKNOWN",
+                Cardinality::CARDINALITY_OPTIONAL => "CARDINALITY_OPTIONAL",
+                Cardinality::CARDINALITY_REQUIRED => "CARDINALITY_REQUIRED",
+                Cardinality::CARDINALITY_REPEATED => "CARDINALITY_REPEATED",
+            }
+        }
+
+        fn from_str(name: &str) -> ::std::option::Option<Cardinality> {
+            match name {
+                "CARDINALITY_UNKNOWN" => ::std::option::Option::Some(Cardinality::CARDINALITY_UNKNOWN),
+                "CARDINALITY_OPTIONAL" => ::std::option::Option::Some(Cardinality::CARDINALITY_OPTIONAL),
+                "CARDINALITY_REQUIRED" => ::std::option::Option::Some(Cardinality::CARDINALITY_REQUIRED),
+                "CARDINALITY_REPEATED" => ::std::option::Option::Some(Cardinality::CARDINALITY_REPEATED),
+                _ => ::std::option::Option::None
+            }
+        }
+
         fn values() -> &'static [Self] {
             static values: &'static [Cardinality] = &[
                 Cardinality::CARDINALITY_UNKNOWN,
@@ -735,6 +901,12 @@ pub mod field {
         }
     }
 
+    impl ::std::fmt::Display for Cardinality {
+        fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+            f.pad(crate::ProtobufEnum::name(self))
+        }
+    }
+
     impl crate::reflect::ProtobufValue for Cardinality {
         type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
     }
@@ -918,6 +1090,27 @@ impl crate::Message for Enum {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.enumvalue);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.source_context);
+        my_size += crate::rt::space_used_excluding_self(&self.syntax);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.enumvalue);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.source_context);
+        crate::rt::shrink_to_fit(&mut self.syntax);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -948,6 +1141,26 @@ impl crate::Message for Enum {
     }
 }
 
+impl crate::rt::SpaceUsed for Enum {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Enum {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Enum {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.enumvalue, worklist);
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+        crate::rt::detach_nested_messages(&mut self.source_context, worklist);
+    }
+}
+
 impl crate::Clear for Enum {
     fn clear(&mut self) {
         self.name.clear();
@@ -1097,6 +1310,23 @@ impl crate::Message for EnumValue {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.number);
+        my_size += crate::rt::space_used_excluding_self(&self.options);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.number);
+        crate::rt::shrink_to_fit(&mut self.options);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -1125,6 +1355,24 @@ impl crate::Message for EnumValue {
     }
 }
 
+impl crate::rt::SpaceUsed for EnumValue {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for EnumValue {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for EnumValue {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.options, worklist);
+    }
+}
+
 impl crate::Clear for EnumValue {
     fn clear(&mut self) {
         self.name.clear();
@@ -1260,6 +1508,21 @@ impl crate::Message for Option {
         self.cached_size.get()
     }
 
+    #[allow(unused_variables)]
+    fn space_used_excluding_self(&self) -> usize {
+        let mut my_size = 0;
+        my_size += crate::rt::space_used_excluding_self(&self.name);
+        my_size += crate::rt::space_used_excluding_self(&self.value);
+        my_size += crate::rt::space_used_excluding_self(&self.unknown_fields);
+        my_size
+    }
+
+    fn shrink_to_fit(&mut self) {
+        crate::rt::shrink_to_fit(&mut self.name);
+        crate::rt::shrink_to_fit(&mut self.value);
+        crate::rt::shrink_to_fit(&mut self.unknown_fields);
+    }
+
     fn get_unknown_fields(&self) -> &crate::UnknownFields {
         &self.unknown_fields
     }
@@ -1287,6 +1550,24 @@ impl crate::Message for Option {
     }
 }
 
+impl crate::rt::SpaceUsed for Option {
+    fn space_used_excluding_self(&self) -> usize {
+        crate::Message::space_used_excluding_self(self)
+    }
+}
+
+impl crate::rt::ShrinkToFit for Option {
+    fn shrink_to_fit(&mut self) {
+        crate::Message::shrink_to_fit(self)
+    }
+}
+
+impl crate::rt::DropIterative for Option {
+    fn take_nested_messages(&mut self, worklist: &mut crate::rt::DropWorklist) {
+        crate::rt::detach_nested_messages(&mut self.value, worklist);
+    }
+}
+
 impl crate::Clear for Option {
     fn clear(&mut self) {
         self.name.clear();
@@ -1326,6 +1607,21 @@ impl crate::ProtobufEnum for Syntax {
         }
     }
 
+    fn name(&self) -> &'static str {
+        match *self {
+            Syntax::SYNTAX_PROTO2 => "SYNTAX_PROTO2",
+            Syntax::SYNTAX_PROTO3 => "SYNTAX_PROTO3",
+        }
+    }
+
+    fn from_str(name: &str) -> ::std::option::Option<Syntax> {
+        match name {
+            "SYNTAX_PROTO2" => ::std::option::Option::Some(Syntax::SYNTAX_PROTO2),
+            "SYNTAX_PROTO3" => ::std::option::Option::Some(Syntax::SYNTAX_PROTO3),
+            _ => ::std::option::Option::None
+        }
+    }
+
     fn values() -> &'static [Self] {
         static values: &'static [Syntax] = &[
             Syntax::SYNTAX_PROTO2,
@@ -1345,6 +1641,12 @@ impl ::std::default::Default for Syntax {
     }
 }
 
+impl ::std::fmt::Display for Syntax {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.pad(crate::ProtobufEnum::name(self))
+    }
+}
+
 impl crate::reflect::ProtobufValue for Syntax {
     type RuntimeType = crate::reflect::runtime_types::RuntimeTypeEnum<Self>;
 }
@@ -1674,3 +1976,16 @@ pub fn file_descriptor() -> crate::reflect::FileDescriptor {
     });
     crate::reflect::FileDescriptor::new_generated_2(file_descriptor)
 }
+
+/// Serialized `FileDescriptorSet` with this file and all its dependencies
+pub fn file_descriptor_set_bytes() -> &'static [u8] {
+    static file_descriptor_set_bytes_lazy: crate::rt::LazyV2<::std::vec::Vec<u8>> = crate::rt::LazyV2::INIT;
+    file_descriptor_set_bytes_lazy.get(|| {
+        crate::Message::write_to_bytes(&file_descriptor().file_descriptor_set()).unwrap()
+    })
+}
+
+/// Messages and enums defined in this file, including nested types
+pub fn registered_types() -> crate::reflect::RegisteredTypes {
+    crate::reflect::RegisteredTypes::of_file(&file_descriptor())
+}
@@ -1,9 +1,50 @@
+use std::error::Error;
+use std::fmt;
+
 use crate::message_dyn::MessageDyn;
 use crate::reflect::MessageDescriptor;
+use crate::reflect::MessageFactory;
 use crate::well_known_types::Any;
 use crate::Message;
+use crate::ProtobufError;
 use crate::ProtobufResult;
 
+/// Error returned by [`Any::unpack_checked`].
+#[derive(Debug)]
+pub enum AnyUnpackError {
+    /// `Any` contains a message of another type.
+    TypeMismatch {
+        /// Full name of requested message type.
+        expected: String,
+        /// Type URL of `Any`.
+        type_url: String,
+    },
+    /// Message type matches, but the message cannot be parsed.
+    Parse(ProtobufError),
+}
+
+impl fmt::Display for AnyUnpackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnyUnpackError::TypeMismatch { expected, type_url } => write!(
+                f,
+                "expecting `Any` with message `{}`, got type URL `{}`",
+                expected, type_url
+            ),
+            AnyUnpackError::Parse(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for AnyUnpackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnyUnpackError::TypeMismatch { .. } => None,
+            AnyUnpackError::Parse(e) => Some(e),
+        }
+    }
+}
+
 impl Any {
     fn type_url(type_url_prefix: &str, descriptor: &MessageDescriptor) -> String {
         format!("{}/{}", type_url_prefix, descriptor.full_name())
@@ -93,6 +134,33 @@ impl Any {
         Ok(Some(M::parse_from_bytes(&self.value)?))
     }
 
+    /// Extract a message from this `Any`, message type mismatch is an error.
+    ///
+    /// Type is checked like in [`is`](Any::is): only the part of type URL
+    /// after the last `/` is compared, so any type URL prefix is accepted.
+    ///
+    /// ```
+    /// # use protobuf::well_known_types::Any;
+    /// # use protobuf::well_known_types::AnyUnpackError;
+    /// # use protobuf::well_known_types::Duration;
+    /// # use protobuf::well_known_types::Timestamp;
+    /// let any = Any::pack(&Duration::new()).unwrap();
+    /// assert_eq!(Duration::new(), any.unpack_checked::<Duration>().unwrap());
+    /// match any.unpack_checked::<Timestamp>() {
+    ///     Err(AnyUnpackError::TypeMismatch { .. }) => {}
+    ///     r => panic!("{:?}", r),
+    /// }
+    /// ```
+    pub fn unpack_checked<M: Message>(&self) -> Result<M, AnyUnpackError> {
+        if !self.is::<M>() {
+            return Err(AnyUnpackError::TypeMismatch {
+                expected: M::descriptor_static().full_name().to_owned(),
+                type_url: self.type_url.clone(),
+            });
+        }
+        M::parse_from_bytes(&self.value).map_err(AnyUnpackError::Parse)
+    }
+
     /// Extract a message from this `Any`.
     ///
     /// # Returns
@@ -111,4 +179,32 @@ impl Any {
         message.check_initialized_dyn()?;
         Ok(Some(message))
     }
+
+    /// Extract a message from this `Any`, looking up message type by name in the factory.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` when message type is not registered in the factory
+    /// * `Err` when parse failed
+    ///
+    /// ```
+    /// # use protobuf::reflect::MessageFactory;
+    /// # use protobuf::well_known_types::Any;
+    /// # use protobuf::well_known_types::Duration;
+    /// let any = Any::pack(&Duration::new()).unwrap();
+    /// let message = any.unpack_with_factory(MessageFactory::global()).unwrap().unwrap();
+    /// assert!(message.is::<Duration>());
+    /// ```
+    pub fn unpack_with_factory(
+        &self,
+        factory: &MessageFactory,
+    ) -> ProtobufResult<Option<Box<dyn MessageDyn>>> {
+        let descriptor = match Any::get_type_name_from_type_url(&self.type_url)
+            .and_then(|name| factory.descriptor_by_name(name))
+        {
+            Some(descriptor) => descriptor,
+            None => return Ok(None),
+        };
+        self.unpack_dyn(&descriptor)
+    }
 }
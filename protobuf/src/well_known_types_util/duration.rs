@@ -1,3 +1,7 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
 use crate::cached_size::CachedSize;
 use crate::well_known_types::Duration;
 use crate::UnknownFields;
@@ -10,6 +14,107 @@ impl Duration {
         unknown_fields: UnknownFields::new(),
         cached_size: CachedSize::new(),
     };
+
+    /// Max absolute value of `seconds` field, approximately 10,000 years.
+    pub const MAX_SECONDS: i64 = 315_576_000_000;
+}
+
+/// Error returned by [`Duration::from_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DurationParseError {
+    /// String is not like `-1.5s`.
+    IncorrectFormat,
+    /// Value is outside of `Duration` range.
+    OutOfRange,
+}
+
+impl fmt::Display for DurationParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DurationParseError::IncorrectFormat => write!(f, "incorrect duration format"),
+            DurationParseError::OutOfRange => write!(f, "duration is out of range"),
+        }
+    }
+}
+
+impl Error for DurationParseError {}
+
+/// Format as in proto3 JSON: seconds with optional fraction and `s` suffix, like `-1.5s`.
+///
+/// Fraction has 0, 3, 6 or 9 digits, whichever is enough to represent the value.
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.seconds < 0 || self.nanos < 0 {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", (self.seconds as i128).abs())?;
+        let nanos = (self.nanos as i64).abs();
+        if nanos == 0 {
+        } else if nanos % 1_000_000 == 0 {
+            write!(f, ".{:03}", nanos / 1_000_000)?;
+        } else if nanos % 1_000 == 0 {
+            write!(f, ".{:06}", nanos / 1_000)?;
+        } else {
+            write!(f, ".{:09}", nanos)?;
+        }
+        write!(f, "s")
+    }
+}
+
+/// Parse proto3 JSON representation, like `-1.5s`.
+///
+/// Fraction may have up to 9 digits.
+impl FromStr for Duration {
+    type Err = DurationParseError;
+
+    fn from_str(s: &str) -> Result<Duration, DurationParseError> {
+        fn is_digits(s: &str) -> bool {
+            !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+        }
+
+        let s = s
+            .strip_suffix('s')
+            .ok_or(DurationParseError::IncorrectFormat)?;
+        let (minus, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (seconds, fraction) = match s.find('.') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        if !is_digits(seconds) {
+            return Err(DurationParseError::IncorrectFormat);
+        }
+        let seconds: i64 = seconds
+            .parse()
+            .map_err(|_| DurationParseError::OutOfRange)?;
+        if seconds > Duration::MAX_SECONDS {
+            return Err(DurationParseError::OutOfRange);
+        }
+
+        let nanos = match fraction {
+            Some(fraction) => {
+                if !is_digits(fraction) || fraction.len() > 9 {
+                    return Err(DurationParseError::IncorrectFormat);
+                }
+                let mut nanos: i32 = fraction.parse().unwrap();
+                for _ in fraction.len()..9 {
+                    nanos *= 10;
+                }
+                nanos
+            }
+            None => 0,
+        };
+
+        let sign = if minus { -1 } else { 1 };
+        Ok(Duration {
+            seconds: sign * seconds,
+            nanos: sign as i32 * nanos,
+            ..Default::default()
+        })
+    }
 }
 
 /// Convert from `std::time::Duration`.
@@ -45,6 +150,64 @@ impl Into<std::time::Duration> for Duration {
 #[cfg(test)]
 mod test {
     use crate::well_known_types::Duration;
+    use crate::well_known_types::DurationParseError;
+
+    fn d(seconds: i64, nanos: i32) -> Duration {
+        Duration {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("0s", d(0, 0).to_string());
+        assert_eq!("3s", d(3, 0).to_string());
+        assert_eq!("-1.500s", d(-1, -500_000_000).to_string());
+        assert_eq!("-0.000001s", d(0, -1_000).to_string());
+        assert_eq!("1.000340012s", d(1, 340_012).to_string());
+    }
+
+    #[test]
+    fn from_str() {
+        for s in &["0s", "3s", "-1.500s", "-0.000001s", "1.000340012s"] {
+            assert_eq!(*s, s.parse::<Duration>().unwrap().to_string());
+        }
+        assert_eq!(d(-1, -500_000_000), "-1.5s".parse().unwrap());
+        assert_eq!(
+            d(Duration::MAX_SECONDS, 0),
+            "315576000000s".parse().unwrap()
+        );
+
+        for s in &[
+            "",
+            "s",
+            "1",
+            "-s",
+            ".5s",
+            "1.s",
+            "1.0000000001s",
+            "+1s",
+            "1.-5s",
+            " 1s",
+        ] {
+            assert_eq!(
+                Err(DurationParseError::IncorrectFormat),
+                s.parse::<Duration>(),
+                "{}",
+                s
+            );
+        }
+        for s in &["315576000001s", "-99999999999999999999s"] {
+            assert_eq!(
+                Err(DurationParseError::OutOfRange),
+                s.parse::<Duration>(),
+                "{}",
+                s
+            );
+        }
+    }
 
     #[test]
     fn to_from_duration() {
@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::json::json_name;
+use crate::well_known_types::FieldMask;
+
+/// Error returned by [`FieldMask::from_str`] and [`FieldMask::from_json_string`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMaskParseError {
+    /// Path which is empty or has an empty component.
+    pub path: String,
+}
+
+impl fmt::Display for FieldMaskParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "incorrect field mask path: `{}`", self.path)
+    }
+}
+
+impl Error for FieldMaskParseError {}
+
+fn parse_paths(s: &str, map: impl Fn(&str) -> String) -> Result<FieldMask, FieldMaskParseError> {
+    let mut mask = FieldMask::new();
+    if s.is_empty() {
+        return Ok(mask);
+    }
+    for path in s.split(',') {
+        if path.split('.').any(|c| c.is_empty()) {
+            return Err(FieldMaskParseError {
+                path: path.to_owned(),
+            });
+        }
+        mask.paths.push(map(path));
+    }
+    Ok(mask)
+}
+
+/// Field name from lowerCamelCase JSON name, inverse of [`json_name`].
+fn snake_case(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    for c in input.chars() {
+        if c.is_ascii_uppercase() {
+            result.push('_');
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+impl FieldMask {
+    /// Paths converted to lowerCamelCase and joined with commas,
+    /// as in proto3 JSON: `foo_bar.baz,qux` is `fooBar.baz,qux`.
+    ///
+    /// Paths with uppercase letters do not survive the round trip
+    /// through [`from_json_string`](FieldMask::from_json_string).
+    pub fn to_json_string(&self) -> String {
+        self.paths
+            .iter()
+            .map(|p| json_name(p))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Parse field mask from proto3 JSON representation,
+    /// lowerCamelCase path components are converted to field names.
+    pub fn from_json_string(s: &str) -> Result<FieldMask, FieldMaskParseError> {
+        parse_paths(s, snake_case)
+    }
+}
+
+/// Paths joined with commas, like `a.b,c`.
+impl fmt::Display for FieldMask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, path) in self.paths.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse comma-separated paths, like `a.b,c`.
+///
+/// Paths are not converted, for JSON representation use
+/// [`FieldMask::from_json_string`].
+impl FromStr for FieldMask {
+    type Err = FieldMaskParseError;
+
+    fn from_str(s: &str) -> Result<FieldMask, FieldMaskParseError> {
+        parse_paths(s, |p| p.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::well_known_types::FieldMask;
+    use crate::well_known_types::FieldMaskParseError;
+
+    fn mask(paths: &[&str]) -> FieldMask {
+        let mut mask = FieldMask::new();
+        mask.paths = paths.iter().map(|p| (*p).to_owned()).collect();
+        mask
+    }
+
+    #[test]
+    fn display_from_str() {
+        for (s, paths) in &[
+            ("", &[][..]),
+            ("a", &["a"][..]),
+            ("a.b,c", &["a.b", "c"][..]),
+            ("foo_bar.baz", &["foo_bar.baz"][..]),
+        ] {
+            assert_eq!(*s, mask(paths).to_string());
+            assert_eq!(mask(paths), s.parse().unwrap());
+        }
+        for s in &[",", "a,", "a..b", ".a", "a,,b"] {
+            assert!(s.parse::<FieldMask>().is_err(), "{}", s);
+        }
+        assert_eq!(
+            Err(FieldMaskParseError {
+                path: "b.".to_owned()
+            }),
+            "a,b.".parse::<FieldMask>()
+        );
+    }
+
+    #[test]
+    fn json_string() {
+        let m = mask(&["foo_bar.baz_qux", "a", "x1_y"]);
+        assert_eq!("fooBar.bazQux,a,x1Y", m.to_json_string());
+        assert_eq!(
+            m,
+            FieldMask::from_json_string("fooBar.bazQux,a,x1Y").unwrap()
+        );
+        assert_eq!(FieldMask::new(), FieldMask::from_json_string("").unwrap());
+        assert!(FieldMask::from_json_string("a,,b").is_err());
+    }
+}
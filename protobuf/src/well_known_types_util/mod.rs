@@ -1,3 +1,9 @@
 mod any;
 mod duration;
+mod field_mask;
+mod struct_pb;
 mod timestamp;
+
+pub use self::any::AnyUnpackError;
+pub use self::duration::DurationParseError;
+pub use self::field_mask::FieldMaskParseError;
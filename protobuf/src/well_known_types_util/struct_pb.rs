@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use crate::well_known_types::value;
+use crate::well_known_types::ListValue;
+use crate::well_known_types::NullValue;
+use crate::well_known_types::Struct;
+use crate::well_known_types::Value;
+use crate::ProtobufEnumOrUnknown;
+
+impl Value {
+    /// Value of kind `null_value`.
+    pub fn null() -> Value {
+        Value::from(NullValue::NULL_VALUE)
+    }
+
+    /// `None` is converted to `null_value`.
+    ///
+    /// ```
+    /// # use protobuf::well_known_types::Value;
+    /// assert!(Value::from_option(None::<f64>).is_null());
+    /// assert_eq!(Value::from(2.5), Value::from_option(Some(2.5)));
+    /// ```
+    pub fn from_option<T: Into<Value>>(value: Option<T>) -> Value {
+        match value {
+            Some(value) => value.into(),
+            None => Value::null(),
+        }
+    }
+
+    /// Value is `null_value` or kind is not set.
+    ///
+    /// JSON printer prints both as `null`.
+    pub fn is_null(&self) -> bool {
+        match self.kind {
+            None | Some(value::Kind::null_value(..)) => true,
+            Some(..) => false,
+        }
+    }
+
+    /// `None` if value [is null](Value::is_null).
+    pub fn as_option(&self) -> Option<&Value> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+
+    /// `None` if value [is null](Value::is_null).
+    pub fn into_option(self) -> Option<Value> {
+        if self.is_null() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+impl From<value::Kind> for Value {
+    fn from(kind: value::Kind) -> Self {
+        let mut value = Value::new();
+        value.kind = Some(kind);
+        value
+    }
+}
+
+impl From<NullValue> for Value {
+    fn from(null_value: NullValue) -> Self {
+        Value::from(value::Kind::null_value(ProtobufEnumOrUnknown::new(
+            null_value,
+        )))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(number_value: f64) -> Self {
+        Value::from(value::Kind::number_value(number_value))
+    }
+}
+
+impl From<f32> for Value {
+    fn from(number_value: f32) -> Self {
+        Value::from(number_value as f64)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(number_value: i32) -> Self {
+        Value::from(number_value as f64)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(number_value: u32) -> Self {
+        Value::from(number_value as f64)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(bool_value: bool) -> Self {
+        Value::from(value::Kind::bool_value(bool_value))
+    }
+}
+
+impl From<String> for Value {
+    fn from(string_value: String) -> Self {
+        Value::from(value::Kind::string_value(string_value))
+    }
+}
+
+impl<'a> From<&'a str> for Value {
+    fn from(string_value: &'a str) -> Self {
+        Value::from(string_value.to_owned())
+    }
+}
+
+impl From<Struct> for Value {
+    fn from(struct_value: Struct) -> Self {
+        Value::from(value::Kind::struct_value(struct_value))
+    }
+}
+
+impl From<ListValue> for Value {
+    fn from(list_value: ListValue) -> Self {
+        Value::from(value::Kind::list_value(list_value))
+    }
+}
+
+impl Struct {
+    /// Take fields of the struct.
+    pub fn into_map(self) -> HashMap<String, Value> {
+        self.fields
+    }
+}
+
+impl From<HashMap<String, Value>> for Struct {
+    fn from(fields: HashMap<String, Value>) -> Self {
+        let mut s = Struct::new();
+        s.fields = fields;
+        s
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Struct {
+    fn from(fields: BTreeMap<String, Value>) -> Self {
+        Struct::from_iter(fields)
+    }
+}
+
+impl From<Struct> for HashMap<String, Value> {
+    fn from(s: Struct) -> Self {
+        s.into_map()
+    }
+}
+
+impl FromIterator<(String, Value)> for Struct {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Struct::from(HashMap::from_iter(iter))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+
+    use crate::well_known_types::NullValue;
+    use crate::well_known_types::Struct;
+    use crate::well_known_types::Value;
+
+    #[test]
+    fn option() {
+        assert!(Value::new().is_null());
+        assert!(Value::null().has_null_value());
+        assert!(Value::from(NullValue::NULL_VALUE).is_null());
+        assert!(!Value::from(0.0).is_null());
+        assert!(!Value::from(Struct::new()).is_null());
+
+        assert_eq!(Value::null(), Value::from_option(None::<&str>));
+        let v = Value::from_option(Some("a"));
+        assert_eq!("a", v.get_string_value());
+        assert_eq!(Some(&v), v.as_option());
+        assert_eq!(Some(v.clone()), v.into_option());
+        assert_eq!(None, Value::null().as_option());
+        assert_eq!(None, Value::new().into_option());
+    }
+
+    #[test]
+    fn struct_map() {
+        let mut map = BTreeMap::new();
+        map.insert("a".to_owned(), Value::from(1));
+        map.insert("b".to_owned(), Value::null());
+
+        let s = Struct::from(map.clone());
+        assert_eq!(2, s.fields.len());
+        assert_eq!(s, map.clone().into_iter().collect());
+        assert_eq!(s, Struct::from(s.clone().into_map()));
+        let back: BTreeMap<_, _> = HashMap::from(s).into_iter().collect();
+        assert_eq!(map, back);
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(3.0, Value::from(3).get_number_value());
+        assert_eq!(3.0, Value::from(3u32).get_number_value());
+        assert_eq!(0.5, Value::from(0.5f32).get_number_value());
+        assert_eq!(true, Value::from(true).get_bool_value());
+        assert_eq!("ab", Value::from("ab".to_owned()).get_string_value());
+    }
+}
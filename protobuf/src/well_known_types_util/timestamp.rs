@@ -4,6 +4,8 @@ use crate::UnknownFields;
 use std::time::Duration;
 use std::time::SystemTime;
 
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
 impl Timestamp {
     /// Unix epoch value of timestamp.
     pub const UNIX_EPOCH: Timestamp = Timestamp {
@@ -17,6 +19,119 @@ impl Timestamp {
     pub fn now() -> Timestamp {
         Timestamp::from(SystemTime::now())
     }
+
+    fn from_unix_units(units: i64, units_per_second: i64) -> Timestamp {
+        Timestamp {
+            seconds: units.div_euclid(units_per_second),
+            nanos: (units.rem_euclid(units_per_second) * (NANOS_PER_SECOND / units_per_second))
+                as i32,
+            ..Default::default()
+        }
+    }
+
+    fn to_unix_units(&self, units_per_second: i64) -> i64 {
+        self.seconds
+            .saturating_mul(units_per_second)
+            .saturating_add((self.nanos as i64).div_euclid(NANOS_PER_SECOND / units_per_second))
+    }
+
+    /// Timestamp from milliseconds since Unix epoch, negative before the epoch.
+    pub fn from_unix_millis(millis: i64) -> Timestamp {
+        Timestamp::from_unix_units(millis, 1_000)
+    }
+
+    /// Timestamp from microseconds since Unix epoch, negative before the epoch.
+    pub fn from_unix_micros(micros: i64) -> Timestamp {
+        Timestamp::from_unix_units(micros, 1_000_000)
+    }
+
+    /// Timestamp from nanoseconds since Unix epoch, negative before the epoch.
+    pub fn from_unix_nanos(nanos: i64) -> Timestamp {
+        Timestamp::from_unix_units(nanos, NANOS_PER_SECOND)
+    }
+
+    /// Milliseconds since Unix epoch.
+    ///
+    /// Sub-millisecond part is rounded towards negative infinity,
+    /// so `-0.5` milliseconds is `-1`. Result saturates on overflow.
+    pub fn to_unix_millis(&self) -> i64 {
+        self.to_unix_units(1_000)
+    }
+
+    /// Microseconds since Unix epoch.
+    ///
+    /// Sub-microsecond part is rounded towards negative infinity.
+    /// Result saturates on overflow.
+    pub fn to_unix_micros(&self) -> i64 {
+        self.to_unix_units(1_000_000)
+    }
+
+    /// Nanoseconds since Unix epoch.
+    ///
+    /// Result saturates on overflow, `i64` nanoseconds cover years 1677 to 2262.
+    pub fn to_unix_nanos(&self) -> i64 {
+        self.to_unix_units(NANOS_PER_SECOND)
+    }
+
+    fn truncate_to_units(&self, units_per_second: i64) -> Timestamp {
+        let nanos_per_unit = (NANOS_PER_SECOND / units_per_second) as i32;
+        Timestamp {
+            seconds: self.seconds,
+            nanos: self.nanos - self.nanos.rem_euclid(nanos_per_unit),
+            ..Default::default()
+        }
+    }
+
+    fn round_to_units(&self, units_per_second: i64) -> Timestamp {
+        let nanos_per_unit = (NANOS_PER_SECOND / units_per_second) as i32;
+        let rem = self.nanos.rem_euclid(nanos_per_unit);
+        let mut nanos = self.nanos - rem;
+        if rem >= nanos_per_unit - nanos_per_unit / 2 {
+            nanos += nanos_per_unit;
+        }
+        let mut seconds = self.seconds;
+        if nanos >= NANOS_PER_SECOND as i32 {
+            seconds += 1;
+            nanos -= NANOS_PER_SECOND as i32;
+        }
+        Timestamp {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
+
+    /// Drop sub-second part.
+    ///
+    /// Since `nanos` are never negative, this rounds towards negative infinity.
+    pub fn truncate_to_seconds(&self) -> Timestamp {
+        self.truncate_to_units(1)
+    }
+
+    /// Drop sub-millisecond part.
+    pub fn truncate_to_millis(&self) -> Timestamp {
+        self.truncate_to_units(1_000)
+    }
+
+    /// Drop sub-microsecond part.
+    pub fn truncate_to_micros(&self) -> Timestamp {
+        self.truncate_to_units(1_000_000)
+    }
+
+    /// Round to the nearest second, half rounds up (towards positive infinity).
+    pub fn round_to_seconds(&self) -> Timestamp {
+        self.round_to_units(1)
+    }
+
+    /// Round to the nearest millisecond, half rounds up (towards positive infinity).
+    pub fn round_to_millis(&self) -> Timestamp {
+        self.round_to_units(1_000)
+    }
+
+    /// Round to the nearest microsecond, half rounds up (towards positive infinity).
+    pub fn round_to_micros(&self) -> Timestamp {
+        self.round_to_units(1_000_000)
+    }
 }
 
 /// Convert from [`Timestamp`].
@@ -115,4 +230,51 @@ mod test {
             SystemTime::UNIX_EPOCH - Duration::from_millis(3_200),
         );
     }
+
+    fn ts(seconds: i64, nanos: i32) -> Timestamp {
+        Timestamp {
+            seconds,
+            nanos,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unix_millis_micros_nanos() {
+        assert_eq!(ts(1, 500_000_000), Timestamp::from_unix_millis(1_500));
+        assert_eq!(ts(-2, 500_000_000), Timestamp::from_unix_millis(-1_500));
+        assert_eq!(ts(-1, 999_999_000), Timestamp::from_unix_micros(-1));
+        assert_eq!(ts(-1, 999_999_999), Timestamp::from_unix_nanos(-1));
+
+        assert_eq!(1_500, ts(1, 500_000_000).to_unix_millis());
+        assert_eq!(-1_500, ts(-2, 500_000_000).to_unix_millis());
+        // -0.5 ms
+        assert_eq!(-1, ts(-1, 999_500_000).to_unix_millis());
+        assert_eq!(-500, ts(-1, 999_500_000).to_unix_micros());
+        assert_eq!(-500_000, ts(-1, 999_500_000).to_unix_nanos());
+        assert_eq!(i64::MAX, ts(i64::MAX / 10, 0).to_unix_millis());
+
+        for &millis in &[0, 1, -1, 999, -999, 1_000, -1_000, 1_234_567_890_123] {
+            assert_eq!(millis, Timestamp::from_unix_millis(millis).to_unix_millis());
+            assert_eq!(
+                millis * 1000,
+                Timestamp::from_unix_millis(millis).to_unix_micros()
+            );
+        }
+    }
+
+    #[test]
+    fn truncate_round() {
+        let t = ts(-2, 999_600_001);
+        assert_eq!(ts(-2, 0), t.truncate_to_seconds());
+        assert_eq!(ts(-2, 999_000_000), t.truncate_to_millis());
+        assert_eq!(ts(-2, 999_600_000), t.truncate_to_micros());
+        assert_eq!(ts(-1, 0), t.round_to_seconds());
+        assert_eq!(ts(-1, 0), t.round_to_millis());
+        assert_eq!(ts(-2, 999_600_000), t.round_to_micros());
+
+        assert_eq!(ts(3, 2_000_000), ts(3, 1_500_000).round_to_millis());
+        assert_eq!(ts(3, 1_000_000), ts(3, 1_499_999).round_to_millis());
+        assert_eq!(ts(4, 0), ts(3, 500_000_000).round_to_seconds());
+    }
 }
@@ -0,0 +1,184 @@
+//! Low-level protobuf wire format helpers.
+//!
+//! Functions of this module encode and decode primitives of the
+//! [wire format](https://developers.google.com/protocol-buffers/docs/encoding):
+//! varints, zigzag-encoded integers and field tags, and compute
+//! their encoded sizes. They are useful for custom framing
+//! or partial decoding of messages without generated code.
+//!
+//! ```
+//! use protobuf::wire;
+//! use protobuf::wire::WireType;
+//!
+//! // Field 2 of type `sint32` with value `-3`
+//! let mut buf = [0; 15];
+//! let mut len = wire::encode_varint32(wire::pack_tag(2, WireType::WireTypeVarint), &mut buf);
+//! len += wire::encode_varint64(wire::encode_zig_zag_32(-3) as u64, &mut buf[len..]);
+//! assert_eq!(&[0x10, 0x05], &buf[..len]);
+//!
+//! let (tag, tag_len) = wire::decode_tag(&buf[..len]).unwrap().unwrap();
+//! assert_eq!((2, WireType::WireTypeVarint), tag.unpack());
+//! let (value, _) = wire::decode_varint64(&buf[tag_len..len]).unwrap().unwrap();
+//! assert_eq!(-3, wire::decode_zig_zag_32(value as u32));
+//! ```
+//!
+//! This module is stable: unlike [`rt`](crate::rt), which is used by
+//! generated code and may change between versions, it is intended
+//! for use in client code.
+
+use crate::error::ProtobufError;
+use crate::error::ProtobufResult;
+use crate::error::WireError;
+use crate::rt;
+
+pub use crate::varint::encode_varint32;
+pub use crate::varint::encode_varint64;
+pub use crate::wire_format::Tag;
+pub use crate::wire_format::WireType;
+pub use crate::wire_format::FIELD_NUMBER_MAX;
+pub use crate::wire_format::TAG_TYPE_BITS;
+pub use crate::wire_format::TAG_TYPE_MASK;
+pub use crate::zigzag::decode_zig_zag_32;
+pub use crate::zigzag::decode_zig_zag_64;
+pub use crate::zigzag::encode_zig_zag_32;
+pub use crate::zigzag::encode_zig_zag_64;
+
+/// Maximum length of encoded varint.
+pub const MAX_VARINT_LEN: usize = 10;
+
+/// Decode varint from the beginning of the buffer.
+///
+/// Returns decoded value and the number of consumed bytes,
+/// or `None` if the buffer does not contain complete varint.
+/// Error is returned if varint is longer than [`MAX_VARINT_LEN`].
+pub fn decode_varint64(buf: &[u8]) -> ProtobufResult<Option<(u64, usize)>> {
+    let mut r: u64 = 0;
+    for (i, &b) in buf.iter().enumerate() {
+        if i == MAX_VARINT_LEN {
+            break;
+        }
+        r |= ((b & 0x7f) as u64) << (i * 7);
+        if b & 0x80 == 0 {
+            return Ok(Some((r, i + 1)));
+        }
+    }
+    if buf.len() >= MAX_VARINT_LEN {
+        return Err(ProtobufError::WireError(WireError::IncorrectVarint));
+    }
+    Ok(None)
+}
+
+/// Decode varint `u32` from the beginning of the buffer.
+///
+/// Like [`decode_varint64`], but higher bits are truncated
+/// as it is done for `int32` fields encoded as 10-byte varints.
+pub fn decode_varint32(buf: &[u8]) -> ProtobufResult<Option<(u32, usize)>> {
+    Ok(decode_varint64(buf)?.map(|(v, len)| (v as u32, len)))
+}
+
+/// Decode field tag from the beginning of the buffer.
+///
+/// Returns `None` if the buffer does not contain complete tag.
+pub fn decode_tag(buf: &[u8]) -> ProtobufResult<Option<(Tag, usize)>> {
+    match decode_varint64(buf)? {
+        Some((value, len)) => {
+            if value > u32::MAX as u64 {
+                return Err(ProtobufError::WireError(WireError::IncorrectTag(
+                    value as u32,
+                )));
+            }
+            match Tag::new(value as u32) {
+                Some(tag) => Ok(Some((tag, len))),
+                None => Err(ProtobufError::WireError(WireError::IncorrectTag(
+                    value as u32,
+                ))),
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Pack field number and wire type into tag value.
+///
+/// # Panics
+///
+/// If field number is outside of valid range.
+pub fn pack_tag(field_number: u32, wire_type: WireType) -> u32 {
+    Tag::make(field_number, wire_type).value()
+}
+
+/// Unpack tag value into field number and wire type.
+///
+/// Returns `None` if wire type is unknown or field number is zero.
+pub fn unpack_tag(value: u32) -> Option<(u32, WireType)> {
+    Tag::new(value).map(Tag::unpack)
+}
+
+/// Encoded size of `u64` varint.
+pub fn varint64_size(value: u64) -> u32 {
+    rt::compute_raw_varint64_size(value)
+}
+
+/// Encoded size of `u32` varint.
+pub fn varint32_size(value: u32) -> u32 {
+    rt::compute_raw_varint32_size(value)
+}
+
+/// Encoded size of `i32` varint (10 bytes for negative values).
+pub fn int32_size(value: i32) -> u32 {
+    rt::compute_raw_varint64_size(value as i64 as u64)
+}
+
+/// Encoded size of zigzag-encoded `sint32`.
+pub fn sint32_size(value: i32) -> u32 {
+    varint32_size(encode_zig_zag_32(value))
+}
+
+/// Encoded size of zigzag-encoded `sint64`.
+pub fn sint64_size(value: i64) -> u32 {
+    varint64_size(encode_zig_zag_64(value))
+}
+
+/// Encoded size of field tag. Size of tag does not depend on wire type.
+pub fn tag_size(field_number: u32) -> u32 {
+    rt::tag_size(field_number)
+}
+
+/// Encoded size of length-delimited value (length prefix and data)
+/// without tag.
+pub fn length_delimited_size(len: u32) -> u32 {
+    varint32_size(len) + len
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn varint() {
+        for &v in &[0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buf = [0; MAX_VARINT_LEN];
+            let len = encode_varint64(v, &mut buf);
+            assert_eq!(varint64_size(v) as usize, len);
+            assert_eq!(Some((v, len)), decode_varint64(&buf[..len]).unwrap());
+            assert_eq!(None, decode_varint64(&buf[..len - 1]).unwrap());
+        }
+        assert!(decode_varint64(&[0xff; MAX_VARINT_LEN]).is_err());
+        assert_eq!(10, int32_size(-1));
+        assert_eq!(1, sint32_size(-1));
+    }
+
+    #[test]
+    fn tag() {
+        let value = pack_tag(16, WireType::WireTypeLengthDelimited);
+        assert_eq!(0x82, value);
+        assert_eq!(
+            Some((16, WireType::WireTypeLengthDelimited)),
+            unpack_tag(value)
+        );
+        assert_eq!(2, tag_size(16));
+        assert_eq!(None, unpack_tag(0x07));
+        assert!(decode_tag(&[0x00]).is_err());
+        assert_eq!(None, decode_tag(&[0x82]).unwrap().map(|(t, _)| t.unpack()));
+    }
+}
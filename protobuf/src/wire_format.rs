@@ -43,7 +43,7 @@ impl WireType {
 }
 
 /// Parsed field tag (a pair of field number and wire type)
-#[derive(Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub struct Tag {
     field_number: u32,
     wire_type: WireType,
@@ -91,7 +91,7 @@ impl Tag {
     }
 
     /// Get wire type
-    fn wire_type(self) -> WireType {
+    pub fn wire_type(self) -> WireType {
         self.wire_type
     }
 
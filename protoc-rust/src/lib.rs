@@ -1,6 +1,6 @@
 //! API to generate `.rs` files.
 //!
-//! This API requires `protoc` command present in `$PATH`
+//! This API requires `protoc` command present in `$PATH` or in `PROTOC` environment variable
 //! or [passed explicitly to `Codegen` object](crate::Codegen::protoc_path).
 //!
 //! ```no_run
@@ -24,18 +24,17 @@
 #![deny(missing_docs)]
 #![deny(broken_intra_doc_links)]
 
-extern crate tempfile;
-
 extern crate protobuf;
 extern crate protobuf_codegen;
 extern crate protoc;
 
-use std::fs;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process;
 
+pub use protoc::CompileError;
+pub use protoc::Diagnostic;
 pub use protoc::Error;
 pub use protoc::Result;
 
@@ -141,27 +140,32 @@ impl Codegen {
     }
 
     /// Like `protoc --rust_out=...` but without requiring `protoc-gen-rust` command in `$PATH`.
+    ///
+    /// When `protoc` fails, the error wraps [`CompileError`] with parsed
+    /// diagnostics, it can be obtained with `error.get_ref()` and `downcast_ref`.
+    /// `protoc` warnings are discarded, use
+    /// [`run_with_diagnostics`](Codegen::run_with_diagnostics) to get them.
     pub fn run(&self) -> Result<()> {
+        self.run_with_diagnostics().map(|_| ())
+    }
+
+    /// Like [`run`](Codegen::run), but returns warnings printed by `protoc`.
+    pub fn run_with_diagnostics(&self) -> Result<Vec<Diagnostic>> {
         let protoc = match self.protoc.clone() {
             Some(protoc) => protoc,
-            None => Protoc::from_env_path(),
+            None => Protoc::discover()?,
         };
         protoc.check()?;
 
-        let temp_dir = tempfile::Builder::new().prefix("protoc-rust").tempdir()?;
-        let temp_file = temp_dir.path().join("descriptor.pbbin");
-
-        protoc
+        let output = protoc
             .descriptor_set_out_args()
-            .out(&temp_file)
             .includes(&self.includes)
             .inputs(&self.inputs)
             .include_imports(true)
             .extra_args(self.extra_args.iter())
-            .write_descriptor_set()?;
+            .compile()?;
 
-        let fds = fs::read(temp_file)?;
-        drop(temp_dir);
+        let fds = output.file_descriptor_set;
 
         let fds: protobuf::descriptor::FileDescriptorSet =
             FileDescriptorSet::parse_from_bytes(&fds)
@@ -195,7 +199,8 @@ impl Codegen {
             &files_to_generate,
             &self.out_dir,
             &self.customize,
-        )
+        )?;
+        Ok(output.diagnostics)
     }
 
     /// Similar to `run`, but prints the message to stderr and exits the process on error.
@@ -0,0 +1,187 @@
+//! Errors and warnings reported by `protoc`.
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io;
+
+/// Severity of [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Compilation failed.
+    Error,
+    /// Compilation succeeded, but the input is suspicious, e. g. unused import.
+    Warning,
+}
+
+/// Message about a `.proto` file printed by `protoc` to stderr.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// File path as printed by `protoc`.
+    pub file: String,
+    /// 1-based line number.
+    pub line: Option<u32>,
+    /// 1-based column number.
+    pub column: Option<u32>,
+    /// Error or warning.
+    pub severity: Severity,
+    /// Message without location.
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        match self.severity {
+            Severity::Error => write!(f, ": {}", self.message),
+            Severity::Warning => write!(f, ": warning: {}", self.message),
+        }
+    }
+}
+
+impl Diagnostic {
+    /// Parse a line like `dir/foo.proto:12:5: Expected ";".`
+    ///
+    /// `None` if the line is not about a file.
+    fn parse(line: &str) -> Option<Diagnostic> {
+        let sep = line.find(": ")?;
+        let (location, message) = (&line[..sep], &line[sep + 2..]);
+        // Command line flags like `--rust_out` and `[libprotobuf WARNING ...]` lines
+        if location.is_empty() || location.starts_with('-') || location.starts_with('[') {
+            return None;
+        }
+        if location == "warning" || location.contains(' ') {
+            return None;
+        }
+
+        let mut parts = location.rsplitn(3, ':');
+        let (file, line, column) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(column), Some(line), Some(file)) => {
+                match (line.parse().ok(), column.parse().ok()) {
+                    (Some(line), Some(column)) => (file, Some(line), Some(column)),
+                    _ => (location, None, None),
+                }
+            }
+            _ => (location, None, None),
+        };
+
+        let (severity, message) = match message.strip_prefix("warning: ") {
+            Some(message) => (Severity::Warning, message),
+            None => (Severity::Error, message),
+        };
+
+        Some(Diagnostic {
+            file: file.to_owned(),
+            line,
+            column,
+            severity,
+            message: message.to_owned(),
+        })
+    }
+
+    /// Parse all per-file messages from `protoc` stderr.
+    pub fn parse_stderr(stderr: &str) -> Vec<Diagnostic> {
+        stderr.lines().filter_map(Diagnostic::parse).collect()
+    }
+}
+
+/// Error of [`DescriptorSetOutArgs::compile`](crate::DescriptorSetOutArgs::compile).
+#[derive(Debug)]
+pub enum CompileError {
+    /// `protoc` binary could not be executed.
+    ProtocNotFound(io::Error),
+    /// `protoc` rejected the input.
+    Compile {
+        /// Messages parsed from `stderr`, including warnings.
+        diagnostics: Vec<Diagnostic>,
+        /// Complete `protoc` stderr.
+        stderr: String,
+    },
+    /// Other I/O error, like failure to read the output.
+    Io(io::Error),
+}
+
+impl CompileError {
+    /// Diagnostics of failed compilation, empty for other errors.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            CompileError::Compile { diagnostics, .. } => diagnostics,
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::ProtocNotFound(e) => write!(f, "protoc not found: {}", e),
+            CompileError::Compile { stderr, .. } => {
+                write!(f, "protoc failed")?;
+                if !stderr.trim().is_empty() {
+                    write!(f, ":\n{}", stderr.trim_end())?;
+                }
+                Ok(())
+            }
+            CompileError::Io(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl StdError for CompileError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            CompileError::ProtocNotFound(e) | CompileError::Io(e) => Some(e),
+            CompileError::Compile { .. } => None,
+        }
+    }
+}
+
+impl From<CompileError> for io::Error {
+    fn from(e: CompileError) -> io::Error {
+        let kind = match &e {
+            CompileError::ProtocNotFound(e) | CompileError::Io(e) => e.kind(),
+            CompileError::Compile { .. } => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let stderr = "\
+a/b.proto:12:5: Expected \";\".
+a/b.proto: Import \"c.proto\" was not found or had errors.
+c.proto:3:1: warning: Import d.proto is unused.
+[libprotobuf WARNING google/protobuf/compiler/parser.cc:648] No syntax specified.
+--rust_out: protoc-gen-rust: Plugin failed with status code 1.
+";
+        let diagnostics = Diagnostic::parse_stderr(stderr);
+        assert_eq!(3, diagnostics.len());
+        assert_eq!(
+            Diagnostic {
+                file: "a/b.proto".to_owned(),
+                line: Some(12),
+                column: Some(5),
+                severity: Severity::Error,
+                message: "Expected \";\".".to_owned(),
+            },
+            diagnostics[0]
+        );
+        assert_eq!(None, diagnostics[1].line);
+        assert_eq!("a/b.proto", diagnostics[1].file);
+        assert_eq!(Severity::Warning, diagnostics[2].severity);
+        assert_eq!(
+            "c.proto:3:1: warning: Import d.proto is unused.",
+            diagnostics[2].to_string()
+        );
+    }
+}
@@ -8,9 +8,10 @@
 #![deny(missing_docs)]
 #![deny(broken_intra_doc_links)]
 
+use std::env;
 use std::ffi::OsStr;
 use std::ffi::OsString;
-use std::fmt;
+use std::fs;
 use std::io;
 use std::path::Path;
 use std::path::PathBuf;
@@ -20,6 +21,15 @@ use std::process;
 extern crate log;
 extern crate which;
 
+mod diagnostic;
+mod version;
+
+pub use diagnostic::CompileError;
+pub use diagnostic::Diagnostic;
+pub use diagnostic::Severity;
+pub use version::Capabilities;
+pub use version::Version;
+
 /// Alias for io::Error
 pub type Error = io::Error;
 /// Alias for io::Result
@@ -237,12 +247,46 @@ impl DescriptorSetOutArgs {
 
     /// Execute `protoc --descriptor_set_out=`
     pub fn write_descriptor_set(&self) -> Result<()> {
+        let out = self.out.as_ref().ok_or_else(|| err_other("out is empty"))?;
+        let cmd_args = self.cmd_args(out)?;
+        self.protoc.run_with_args(cmd_args)
+    }
+
+    /// Execute `protoc --descriptor_set_out=` capturing its output.
+    ///
+    /// Returns serialized `FileDescriptorSet` along with warnings.
+    /// On failure, `protoc` messages are returned parsed into per-file diagnostics.
+    /// When [`out`](DescriptorSetOutArgs::out) is not specified,
+    /// descriptor set is written to a temporary file.
+    pub fn compile(&self) -> std::result::Result<CompileOutput, CompileError> {
+        // Removed on drop
+        let temp_dir;
+        let temp_out;
+        let out = match &self.out {
+            Some(out) => out,
+            None => {
+                temp_dir = tempfile::Builder::new()
+                    .prefix("protoc")
+                    .tempdir()
+                    .map_err(CompileError::Io)?;
+                temp_out = temp_dir.path().join("descriptor.pbbin");
+                &temp_out
+            }
+        };
+        let cmd_args = self.cmd_args(out).map_err(CompileError::Io)?;
+        let stderr = self.protoc.run_with_args_captured(cmd_args)?;
+        let file_descriptor_set = fs::read(out).map_err(CompileError::Io)?;
+        Ok(CompileOutput {
+            file_descriptor_set,
+            diagnostics: Diagnostic::parse_stderr(&stderr),
+        })
+    }
+
+    fn cmd_args(&self, out: &Path) -> Result<Vec<OsString>> {
         if self.inputs.is_empty() {
             return Err(err_other("input is empty"));
         }
 
-        let out = self.out.as_ref().ok_or_else(|| err_other("out is empty"))?;
-
         // -I{include}
         let include_flags = self.includes.iter().map(|include| {
             let mut flag = OsString::from("-I");
@@ -266,10 +310,19 @@ impl DescriptorSetOutArgs {
         cmd_args.extend(include_imports_flag);
         cmd_args.extend(self.inputs.iter().map(|path| path.as_os_str().to_owned()));
         cmd_args.extend(self.extra_args.iter().cloned());
-        self.protoc.run_with_args(cmd_args)
+        Ok(cmd_args)
     }
 }
 
+/// Result of [`DescriptorSetOutArgs::compile`].
+#[derive(Debug)]
+pub struct CompileOutput {
+    /// Serialized `google.protobuf.FileDescriptorSet`.
+    pub file_descriptor_set: Vec<u8>,
+    /// Warnings printed by `protoc`.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
 /// Protoc command.
 #[derive(Clone, Debug)]
 pub struct Protoc {
@@ -289,6 +342,43 @@ impl Protoc {
         }
     }
 
+    /// Find `protoc` command.
+    ///
+    /// Tried in order:
+    /// * path in `PROTOC` environment variable
+    /// * `protoc` in `$PATH`
+    /// * binary from `protoc-bin-vendored` crate if `vendored` feature is enabled
+    ///
+    /// Found binary is not checked, use [`check`](Protoc::check)
+    /// or [`check_min_version`](Protoc::check_min_version) for that.
+    pub fn discover() -> Result<Protoc> {
+        if let Some(path) = env::var_os("PROTOC") {
+            return Ok(Protoc::from_path(path));
+        }
+        let path_error = match which::which("protoc") {
+            Ok(path) => return Ok(Protoc::from_path(path)),
+            Err(e) => e,
+        };
+        #[cfg(feature = "vendored")]
+        {
+            if let Ok(path) = protoc_bin_vendored::protoc_bin_path() {
+                return Ok(Protoc::from_path(path));
+            }
+        }
+        Err(Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "protoc binary not found, `PROTOC` is not set and not found in `$PATH`: {}",
+                path_error
+            ),
+        ))
+    }
+
+    /// Path to `protoc` binary.
+    pub fn path(&self) -> &OsStr {
+        &self.exec
+    }
+
     /// New `protoc` command from specified path
     ///
     /// # Examples
@@ -350,13 +440,25 @@ impl Protoc {
         if output.is_empty() {
             return Err(err_other("version is empty"));
         }
-        let first = output.chars().next().unwrap();
-        if !first.is_digit(10) {
-            return Err(err_other("version does not start with digit"));
+        Version::parse(output).ok_or_else(|| err_other("version does not start with digit"))
+    }
+
+    /// Check that `protoc` version is at least `min_version` like `3.12`.
+    ///
+    /// # Panics
+    ///
+    /// If `min_version` is not a version.
+    pub fn check_min_version(&self, min_version: &str) -> Result<Version> {
+        let min = Version::parse(min_version)
+            .unwrap_or_else(|| panic!("incorrect version: {:?}", min_version));
+        let version = self.version()?;
+        if version < min {
+            return Err(err_other(format!(
+                "protoc {:?} version {} is older than required {}",
+                self.exec, version, min
+            )));
         }
-        Ok(Version {
-            version: output.to_owned(),
-        })
+        Ok(version)
     }
 
     /// Execute `protoc` command with given args, check it completed correctly.
@@ -377,6 +479,37 @@ impl Protoc {
         Ok(())
     }
 
+    /// Execute `protoc` command with given args, return its stderr.
+    fn run_with_args_captured(
+        &self,
+        args: Vec<OsString>,
+    ) -> std::result::Result<String, CompileError> {
+        let mut cmd = process::Command::new(&self.exec);
+        cmd.stdin(process::Stdio::null());
+        cmd.stdout(process::Stdio::null());
+        cmd.stderr(process::Stdio::piped());
+        cmd.args(args);
+
+        info!("spawning command {:?}", cmd);
+        let output = cmd.output().map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied => {
+                CompileError::ProtocNotFound(Error::new(
+                    e.kind(),
+                    format!("failed to spawn `{:?}`: {}", self.exec, e),
+                ))
+            }
+            _ => CompileError::Io(e),
+        })?;
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        if !output.status.success() {
+            return Err(CompileError::Compile {
+                diagnostics: Diagnostic::parse_stderr(&stderr),
+                stderr,
+            });
+        }
+        Ok(stderr)
+    }
+
     /// Get default Args for this command.
     pub fn args(&self) -> ProtocLangOut {
         ProtocLangOut {
@@ -398,24 +531,6 @@ impl Protoc {
     }
 }
 
-/// Protobuf (protoc) version.
-pub struct Version {
-    version: String,
-}
-
-impl Version {
-    /// `true` if the protoc major version is 3.
-    pub fn is_3(&self) -> bool {
-        self.version.starts_with("3")
-    }
-}
-
-impl fmt::Display for Version {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.version, f)
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -424,4 +539,53 @@ mod test {
     fn version() {
         Protoc::from_env_path().version().expect("version");
     }
+
+    #[test]
+    fn discover() {
+        let protoc = Protoc::discover().expect("discover");
+        protoc.check_min_version("3.0").expect("min version");
+        assert!(protoc.check_min_version("1000.0").is_err());
+    }
+
+    #[test]
+    fn compile() {
+        let dir = std::env::temp_dir().join(format!("protoc-test-compile-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("good.proto"), "syntax = \"proto3\"; message M {}").unwrap();
+        fs::write(
+            dir.join("bad.proto"),
+            "syntax = \"proto3\";\nmessage M {\n  int32 x = 1\n}",
+        )
+        .unwrap();
+
+        let protoc = Protoc::from_env_path();
+        let output = protoc
+            .descriptor_set_out_args()
+            .include(&dir)
+            .input(dir.join("good.proto"))
+            .compile()
+            .unwrap();
+        assert!(!output.file_descriptor_set.is_empty());
+
+        let err = protoc
+            .descriptor_set_out_args()
+            .include(&dir)
+            .input(dir.join("bad.proto"))
+            .compile()
+            .unwrap_err();
+        let diagnostics = err.diagnostics();
+        assert_eq!(1, diagnostics.len(), "{:?}", diagnostics);
+        assert_eq!(Severity::Error, diagnostics[0].severity);
+        assert!(diagnostics[0].file.ends_with("bad.proto"));
+        assert_eq!(Some(4), diagnostics[0].line);
+
+        let err = Protoc::from_path(dir.join("no-protoc"))
+            .descriptor_set_out_args()
+            .input(dir.join("good.proto"))
+            .compile()
+            .unwrap_err();
+        assert!(matches!(err, CompileError::ProtocNotFound(..)), "{:?}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
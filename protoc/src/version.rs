@@ -0,0 +1,131 @@
+//! `protoc` version and features supported by it.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Protobuf (protoc) version.
+///
+/// Since protobuf 22 minor version became major, e. g. `22.0`
+/// follows `3.21.12`. Such versions are compared as the `3.22.0`.
+#[derive(Clone, Debug)]
+pub struct Version {
+    version: String,
+    numbers: (u32, u32, u32),
+}
+
+/// Features supported by `protoc` version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `optional` fields in `proto3` files.
+    pub proto3_optional: bool,
+    /// `proto3_optional` needs `--experimental_allow_proto3_optional` flag.
+    pub proto3_optional_requires_flag: bool,
+    /// `edition = "2023"` files instead of `syntax`.
+    pub editions: bool,
+}
+
+impl Version {
+    /// Parse version like `3.21.12` or `25.1`, optionally with suffix like `-rc1`.
+    ///
+    /// `None` if version does not start with a number.
+    pub fn parse(version: &str) -> Option<Version> {
+        let numeric = version
+            .split(|c: char| c != '.' && !c.is_ascii_digit())
+            .next()
+            .unwrap_or("");
+        let mut parts = numeric.split('.').map(|p| p.parse::<u32>().ok());
+        let first = parts.next()??;
+        let second = parts.next().flatten().unwrap_or(0);
+        let third = parts.next().flatten().unwrap_or(0);
+        let numbers = if first >= 22 {
+            (3, first, second)
+        } else {
+            (first, second, third)
+        };
+        Some(Version {
+            version: version.to_owned(),
+            numbers,
+        })
+    }
+
+    /// Version numbers, with versions since 22 converted to `3.x.y` form.
+    pub fn numbers(&self) -> (u32, u32, u32) {
+        self.numbers
+    }
+
+    /// `true` if the protoc major version is 3.
+    pub fn is_3(&self) -> bool {
+        self.numbers.0 == 3
+    }
+
+    /// Features supported by this version.
+    pub fn capabilities(&self) -> Capabilities {
+        let at_least = |minor| self.numbers >= (3, minor, 0);
+        Capabilities {
+            proto3_optional: at_least(12),
+            proto3_optional_requires_flag: at_least(12) && !at_least(15),
+            editions: at_least(27),
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Version) -> bool {
+        self.numbers == other.numbers
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Version) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Version) -> Ordering {
+        self.numbers.cmp(&other.numbers)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.version, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn v(s: &str) -> Version {
+        Version::parse(s).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!((3, 6, 1), v("3.6.1").numbers());
+        assert_eq!((3, 25, 1), v("25.1").numbers());
+        assert_eq!((3, 22, 0), v("22.0-rc2").numbers());
+        assert_eq!("22.0-rc2", v("22.0-rc2").to_string());
+        assert!(v("25.1").is_3());
+        assert!(Version::parse("x").is_none());
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(v("3.21.12") < v("22.0"));
+        assert!(v("3.6.1") < v("3.12"));
+        assert_eq!(v("3.12"), v("3.12.0"));
+    }
+
+    #[test]
+    fn capabilities() {
+        assert!(!v("3.6.1").capabilities().proto3_optional);
+        assert!(v("3.12.4").capabilities().proto3_optional_requires_flag);
+        assert!(!v("3.15.0").capabilities().proto3_optional_requires_flag);
+        assert!(!v("26.1").capabilities().editions);
+        assert!(v("27.0").capabilities().editions);
+    }
+}